@@ -0,0 +1,71 @@
+// Sorting and filtering for the file table in ch-migrate HTML reports.
+(function () {
+  "use strict";
+
+  const table = document.getElementById("files");
+  if (!table) {
+    return;
+  }
+  const body = table.tBodies[0];
+  const headers = Array.from(table.tHead.rows[0].cells);
+  const rows = Array.from(body.rows);
+  const search = document.getElementById("file-search");
+  const status = document.getElementById("file-status");
+  const count = document.getElementById("file-count");
+
+  function applyFilter() {
+    const text = search.value.trim().toLowerCase();
+    const wanted = status.value;
+    let shown = 0;
+    for (const row of rows) {
+      const visible =
+        (!wanted || row.dataset.status === wanted) &&
+        (!text || row.cells[0].textContent.toLowerCase().includes(text));
+      row.hidden = !visible;
+      if (visible) {
+        shown += 1;
+      }
+    }
+    count.textContent = shown + " of " + rows.length + " files";
+  }
+
+  // Numeric cells carry their value in data-sort; others sort by text.
+  function sortValue(row, column) {
+    const cell = row.cells[column];
+    return cell.dataset.sort !== undefined
+      ? Number(cell.dataset.sort)
+      : cell.textContent.toLowerCase();
+  }
+
+  headers.forEach(function (header, column) {
+    header.addEventListener("click", function () {
+      const ascending = header.getAttribute("aria-sort") !== "ascending";
+      for (const other of headers) {
+        other.removeAttribute("aria-sort");
+      }
+      header.setAttribute("aria-sort", ascending ? "ascending" : "descending");
+      rows.sort(function (a, b) {
+        const x = sortValue(a, column);
+        const y = sortValue(b, column);
+        const order = x < y ? -1 : x > y ? 1 : 0;
+        return ascending ? order : -order;
+      });
+      for (const row of rows) {
+        body.appendChild(row);
+      }
+    });
+  });
+
+  // Directory names in the breakdown narrow the table to that directory.
+  for (const link of document.querySelectorAll("[data-filter]")) {
+    link.addEventListener("click", function () {
+      search.value = link.dataset.filter;
+      applyFilter();
+      table.scrollIntoView({ behavior: "smooth" });
+    });
+  }
+
+  search.addEventListener("input", applyFilter);
+  status.addEventListener("change", applyFilter);
+  applyFilter();
+})();
@@ -11,26 +11,180 @@
 //! # Scan and show summary
 //! ch-migrate scan --path /path/to/WebApp.Desktop/src
 //!
+//! # Don't count a universally accepted legacy import against any file
+//! ch-migrate scan --ignore-import shared/models/legacy-logger
+//!
+//! # Scan several roots with merged statistics
+//! ch-migrate scan --path src/app --path src/admin
+//!
+//! # Summary as Markdown for Confluence or a GitHub comment
+//! ch-migrate scan --format markdown
+//!
 //! # Interactive TUI with file watching
 //! ch-migrate watch --path /path/to/WebApp.Desktop/src
 //!
+//! # Keep status.json current for an office dashboard
+//! ch-migrate watch --status-file /srv/dashboard/status.json
+//!
+//! # Open the TUI on a shared view
+//! ch-migrate watch --view "status=legacy,partial sort=priority"
+//!
+//! # Rebind TUI commands
+//! ch-migrate watch --keys "rescan=R F5,quit=ctrl+q"
+//!
+//! # Per-directory progress, two levels deep
+//! ch-migrate summary --by-dir --depth 2
+//!
 //! # Generate JSON report
 //! ch-migrate report --format json --output report.json
+//!
+//! # Standalone HTML report with charts and a sortable file table
+//! ch-migrate report --format html --output report.html
+//!
+//! # Markdown status page with a collapsible section per top-level directory
+//! ch-migrate report --format md --output status.md
+//!
+//! # Excel workbook with summary, file and model sheets
+//! ch-migrate report --format xlsx --output report.xlsx
+//!
+//! # Legacy models with their consumer files and modern equivalents
+//! ch-migrate report --by-model --format csv --output models.csv
+//!
+//! # Graphviz graph of the files still importing legacy models
+//! ch-migrate report --format dot --status legacy | dot -Tsvg > legacy.svg
+//!
+//! # Mermaid chart of the most consumed legacy models, for Markdown docs
+//! ch-migrate report --format mermaid --top 5 >> docs/migration.md
+//!
+//! # Compact binary report for large repos, converted to CSV when needed
+//! ch-migrate report --format msgpack --output report.msgpack
+//! ch-migrate report convert report.msgpack --format csv --output report.csv
+//!
+//! # JSON Schema of the JSON report, for validating it elsewhere
+//! ch-migrate report --print-schema --output report.schema.json
+//!
+//! # GitHub Actions annotations for every legacy import
+//! ch-migrate scan --format github
+//!
+//! # GitHub Actions annotations for legacy imports not in the main-branch report
+//! ch-migrate report --format github --baseline main-report.json
+//!
+//! # HTML page of files migrated, regressed or added since an earlier report
+//! ch-migrate report --format diff --baseline last-week.json --output changes.html
+//!
+//! # Pull-request comment with the change since the main-branch report
+//! ch-migrate report --format pr-comment --baseline main-report.json
+//!
+//! # SARIF log for GitHub code scanning
+//! ch-migrate report --format sarif --output ch-migrate.sarif
+//!
+//! # Gzip-compressed JSON report
+//! ch-migrate report --output report.json.gz
+//!
+//! # Custom status page from a Tera template
+//! ch-migrate report --template status.html.tera --output status.html
+//!
+//! # Record progress after each scan, then view it over time
+//! ch-migrate stats --record
+//! ch-migrate stats --history
+//!
+//! # Chart migration velocity from the recorded history
+//! ch-migrate report --format trend --chart --output trend.html
+//!
+//! # Drop history entries older than a year
+//! ch-migrate maintenance --max-age-days 365
+//!
+//! # Export the model inventory for other tooling
+//! ch-migrate export-registry --format csv --output models.csv
+//!
+//! # Render a progress badge for the README
+//! ch-migrate badge --output badge.svg
+//!
+//! # Find ported models that are missing exports from the legacy version
+//! ch-migrate validate-parity
+//!
+//! # Propose a migration order, in waves of independent files
+//! ch-migrate plan
+//!
+//! # Show which files porting a legacy model would migrate or unblock
+//! ch-migrate impact Contract
+//!
+//! # Find every file importing a module, with line numbers
+//! ch-migrate grep-import shared/models/contract
+//!
+//! # Report scan performance on this machine
+//! ch-migrate selftest --files 20000
+//!
+//! # Fail CI on legacy-only files or scan errors, but tolerate partial ones
+//! ch-migrate scan --fail-on legacy,errors
+//!
+//! # Fail CI on scan errors, only warning about legacy files for now
+//! ch-migrate scan --fail-on legacy:warn,errors
+//!
+//! # Check only the files changed on this branch
+//! git diff --name-only main | ch-migrate scan --stdin-paths
+//!
+//! # Check only one subtree, relative to the root path
+//! ch-migrate scan --path-prefix app/contracts
+//!
+//! # Include per-team progress from a CODEOWNERS file
+//! ch-migrate scan --codeowners .github/CODEOWNERS
+//!
+//! # Machine-readable progress on stderr for wrapper scripts
+//! ch-migrate report --progress json --output report.json
+//!
+//! # Find out where a slow scan spends its time
+//! ch-migrate --profile=20 scan
+//!
+//! # Only parse the files changed since the previous cached scan
+//! ch-migrate --cache scan
+//!
+//! # Use your team's wording for statuses
+//! ch-migrate scan --status-labels "legacy=To do,partial=In progress,migrated=Done"
 //! ```
 
 #![deny(clippy::all)]
 #![warn(missing_docs)]
 
-use std::io::Write;
-
-use camino::Utf8PathBuf;
-use ch_core::{Config, FileInfo, MigrationStatus};
-use ch_scanner::{ScanConfig as ScannerConfig, Scanner, StatsSnapshot};
+mod report;
+
+use std::io::{BufRead, BufWriter, Read, Write};
+use std::time::{Duration, Instant};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use ch_core::{
+    Config, FileInfo, ImportInfo, KeyOverrides, MigrationStatus, ModelRegistry, StatusLabels,
+};
+use ch_scanner::{
+    append_history, check_parity, compact_history, load_history, model_impact, plan_migration,
+    rollup_by_directory, rollup_by_model, rollup_by_owner, FileKind, HistoryEntry,
+    HistoryRetention, CancellationToken, ModelRenames, OwnerStats, ParseCounts, RegistryBuilder,
+    ResourceLimits, RetryPolicy, ScanConfig as ScannerConfig, ScanError, ScanProfile, ScanResult,
+    ScanUpdate, Scanner, SizeBucket, SkipCounts, SkipReason, SkippedPath, StatsSnapshot, Trend,
+};
 use ch_ts_parser::ModelPathMatcher;
-use clap::{Parser, Subcommand, ValueEnum};
-use tracing::info;
+use ch_tui::{toolchain, Session, ViewSpec};
+use chrono::{DateTime, Local, Utc};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use tracing::{info, warn};
 use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
+use report::{
+    diff_baseline, generate_badge_svg, generate_binary_report, generate_csv_model_report,
+    generate_csv_registry, generate_csv_report, generate_diff_html, generate_dot_report,
+    generate_github_annotations, generate_github_report, generate_html_report,
+    generate_impact_text, generate_json_model_report, generate_json_registry,
+    generate_markdown_model_report, generate_markdown_report, generate_markdown_summary,
+    generate_mermaid_report, generate_parity_text, generate_plan_text, generate_pr_comment,
+    generate_sarif_report, generate_trend_csv, generate_trend_html, generate_xlsx_report,
+    load_baseline, load_binary_report, render_template_report, unmatched_imports,
+    write_json_report, JsonReport, ReportError, ReportMetadata,
+    JSON_REPORT_SCHEMA, MERMAID_TOP_MODELS,
+};
+
 // =============================================================================
 // CLI ARGUMENT TYPES
 // =============================================================================
@@ -49,9 +203,11 @@ struct Cli {
 
     /// Path to WebApp.Desktop/src directory.
     ///
-    /// Defaults to `./WebApp.Desktop/src` if not specified.
+    /// Defaults to `./WebApp.Desktop/src` if not specified. Repeat to scan
+    /// further directories for model consumers (e.g. `--path src --path
+    /// src/admin`); statistics from all of them are merged.
     #[arg(short, long, global = true, env = "CH_MIGRATE_PATH")]
-    path: Option<Utf8PathBuf>,
+    path: Vec<Utf8PathBuf>,
 
     /// Absolute path to legacy shared directory.
     ///
@@ -83,6 +239,126 @@ struct Cli {
     /// Editor to use for opening files (overrides $EDITOR).
     #[arg(long, global = true, env = "CH_MIGRATE_EDITOR")]
     editor: Option<String>,
+
+    /// CODEOWNERS file used to group statistics by team.
+    #[arg(long, global = true, env = "CH_MIGRATE_CODEOWNERS")]
+    codeowners: Option<Utf8PathBuf>,
+
+    /// Mapping of models renamed during porting (`LegacyName -> ModernName` per line).
+    #[arg(long, global = true, env = "CH_MIGRATE_RENAMES")]
+    renames: Option<Utf8PathBuf>,
+
+    /// Only scan the files this tsconfig.json's `files`, `include` and
+    /// `exclude` settings let the TypeScript compiler build, and resolve
+    /// imports through its `paths` aliases.
+    #[arg(long, global = true, env = "CH_MIGRATE_TSCONFIG")]
+    tsconfig: Option<Utf8PathBuf>,
+
+    /// Import path or model name to leave out of migration status (repeatable).
+    ///
+    /// Entries containing `/` match import paths by suffix, e.g.
+    /// `shared/models/legacy-logger`; others match imported model names.
+    /// Ignored imports are still listed, dimmed, in the TUI detail pane.
+    #[arg(
+        long = "ignore-import",
+        global = true,
+        value_name = "RULE",
+        env = "CH_MIGRATE_IGNORE_IMPORTS",
+        value_delimiter = ','
+    )]
+    ignore_imports: Vec<String>,
+
+    /// Display labels for statuses, e.g. "legacy=To do,partial=In progress,migrated=Done".
+    ///
+    /// Labels apply to text, Markdown, CSV and the TUI; JSON output keeps the
+    /// canonical status names.
+    #[arg(long, global = true, env = "CH_MIGRATE_STATUS_LABELS")]
+    status_labels: Option<StatusLabels>,
+
+    /// Resident memory in MiB above which scans switch to a slower
+    /// low-memory mode instead of risking an out-of-memory kill.
+    #[arg(long, global = true, value_name = "MIB")]
+    max_memory_mb: Option<u64>,
+
+    /// Arena size in MiB per analysis thread above which scans switch to
+    /// low-memory mode.
+    #[arg(long, global = true, value_name = "MIB")]
+    max_arena_mb: Option<u64>,
+
+    /// Skip files larger than this many bytes instead of parsing them.
+    ///
+    /// Skipped files are listed as errors in the `skipped` category.
+    #[arg(long, global = true, value_name = "BYTES", env = "CH_MIGRATE_MAX_FILE_SIZE")]
+    max_file_size: Option<u64>,
+
+    /// Memory-map files of this many bytes or more instead of reading them.
+    ///
+    /// Speeds up one-shot scans of very large generated files. Nothing may
+    /// write to a mapped file during the scan, so only use it on trees no
+    /// one is editing; `watch` sessions and file rescans always read files.
+    #[arg(long, global = true, value_name = "BYTES", env = "CH_MIGRATE_MMAP_THRESHOLD")]
+    mmap_threshold: Option<u64>,
+
+    /// Directory the TUI scans first, relative to the app directory
+    /// (repeatable, most important first).
+    ///
+    /// Directories where the previous scan found legacy imports follow.
+    #[arg(long = "priority-dir", global = true, value_name = "DIR")]
+    priority_dirs: Vec<Utf8PathBuf>,
+
+    /// Keep the imports of at most this many files in memory; the least
+    /// recently used others keep only their status until rescanned.
+    ///
+    /// Bounds memory in long `watch` sessions on very large trees. Other
+    /// commands ignore it, so their reports always list every import.
+    #[arg(long, global = true, value_name = "FILES")]
+    max_detailed_files: Option<usize>,
+
+    /// Analyze files on this many threads instead of one per CPU.
+    ///
+    /// Scans run in their own thread pool, leaving the rest of the machine
+    /// free for the editor and build.
+    #[arg(long = "jobs", global = true, value_name = "THREADS")]
+    jobs: Option<usize>,
+
+    /// Retry files that failed to read transiently (e.g. a network file
+    /// system timing out) this many times once the rest of the scan is done.
+    ///
+    /// Defaults to 2; `0` reports them as errors straight away.
+    #[arg(long, global = true, value_name = "N")]
+    retries: Option<u32>,
+
+    /// Keep analysis results in an on-disk cache so repeat scans only parse
+    /// changed files (default directory `.ch-migrate/cache`).
+    #[arg(
+        long,
+        global = true,
+        value_name = "DIR",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = ".ch-migrate/cache",
+        env = "CH_MIGRATE_CACHE"
+    )]
+    cache: Option<Utf8PathBuf>,
+
+    /// Emit scan progress events to stderr in the given format.
+    ///
+    /// `json` writes one `{"scanned":N,"total":M,"rate":R}` object per line
+    /// (rate in files per second) so wrappers can render their own progress.
+    #[arg(long, global = true, value_name = "FORMAT")]
+    progress: Option<ProgressFormat>,
+
+    /// Print a per-phase timing breakdown and the N slowest files (default 10) to stderr.
+    #[arg(
+        long,
+        global = true,
+        value_name = "N",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "10",
+        conflicts_with = "progress"
+    )]
+    profile: Option<usize>,
 }
 
 /// Available subcommands.
@@ -93,6 +369,51 @@ enum Commands {
         /// Show detailed file list.
         #[arg(short, long)]
         detailed: bool,
+
+        /// Output format.
+        #[arg(short, long, value_enum, default_value_t = ScanFormat::Text)]
+        format: ScanFormat,
+
+        /// Exit with an error when a rule is broken (comma-separated, e.g.
+        /// `legacy,errors`). Rules count `legacy`, `partial` or `blocked`
+        /// files, scan `errors`, and with `--baseline` status `regressions`
+        /// and `new-debt` legacy imports. Append `=N` to allow up to N
+        /// findings (e.g. `legacy=50`), and `:warn` to only print a warning
+        /// (e.g. `legacy:warn`).
+        #[arg(long, value_name = "RULES", env = "CH_MIGRATE_FAIL_ON")]
+        fail_on: Option<FailPolicy>,
+
+        /// Previous JSON or binary report that the `regressions` and
+        /// `new-debt` rules of `--fail-on` compare with.
+        #[arg(long, value_name = "REPORT", requires = "fail_on")]
+        baseline: Option<Utf8PathBuf>,
+
+        /// Analyze only these files instead of walking the source tree.
+        #[arg(
+            long,
+            num_args = 1..,
+            value_name = "FILE",
+            conflicts_with_all = ["stdin_paths", "files_from"]
+        )]
+        files: Vec<Utf8PathBuf>,
+
+        /// Read the files to analyze from stdin, one path per line.
+        #[arg(long, conflicts_with = "files_from")]
+        stdin_paths: bool,
+
+        /// Read the files to analyze from this file, one path per line
+        /// (`-` for stdin).
+        #[arg(long, value_name = "LIST")]
+        files_from: Option<Utf8PathBuf>,
+
+        /// Walk and analyze only this directory, relative to the root path
+        /// (e.g. `app/contracts`).
+        #[arg(
+            long,
+            value_name = "DIR",
+            conflicts_with_all = ["files", "stdin_paths", "files_from"]
+        )]
+        path_prefix: Option<Utf8PathBuf>,
     },
 
     /// Start interactive TUI with live file watching.
@@ -100,20 +421,333 @@ enum Commands {
         /// Disable file watching (static view).
         #[arg(long)]
         no_watch: bool,
+
+        /// Initial view, e.g. "status=legacy,partial sort=priority path=app/contracts".
+        #[arg(long)]
+        view: Option<ViewSpec>,
+
+        /// Keep a JSON file with the current statistics up to date for dashboards.
+        #[arg(long, env = "CH_MIGRATE_STATUS_FILE")]
+        status_file: Option<Utf8PathBuf>,
+
+        /// Also watch `shared/` and `shared_2023/`, checking imports against the
+        /// model registry and updating it as model files change.
+        #[arg(long, conflicts_with = "no_watch")]
+        watch_shared: bool,
+
+        /// Rebind commands, e.g. "rescan=R F5,quit=ctrl+q,copy-view=none".
+        ///
+        /// Command IDs are listed in the help panel (`?`).
+        #[arg(long, env = "CH_MIGRATE_KEYS")]
+        keys: Option<KeyOverrides>,
+
+        /// Session file handed over by `:upgrade` (set automatically).
+        #[arg(long, env = "CH_MIGRATE_RESUME_SESSION", hide = true)]
+        resume_session: Option<Utf8PathBuf>,
+    },
+
+    /// Show aggregated migration statistics.
+    Summary {
+        /// Break statistics down per directory subtree.
+        #[arg(long)]
+        by_dir: bool,
+
+        /// Number of directory levels to group by (with `--by-dir`).
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+        depth: u16,
+    },
+
+    /// Show migration statistics, optionally recording them to a history file.
+    Stats {
+        /// Append this scan's statistics (with timestamp and git commit) to the history file.
+        #[arg(long)]
+        record: bool,
+
+        /// Print recorded progress over time instead of scanning.
+        #[arg(long, conflicts_with = "record")]
+        history: bool,
+
+        /// History file (JSON Lines).
+        #[arg(long, default_value = ".ch-migrate/history.jsonl")]
+        history_file: Utf8PathBuf,
     },
 
     /// Generate migration report.
+    #[command(args_conflicts_with_subcommands = true)]
     Report {
+        /// Operation on an existing report instead of scanning.
+        #[command(subcommand)]
+        action: Option<ReportAction>,
+
         /// Output format.
         #[arg(short, long, value_enum, default_value_t = ReportFormat::Json)]
         format: ReportFormat,
 
+        /// Output file (defaults to stdout); gzip-compressed if it ends in
+        /// `.gz`.
+        #[arg(short, long)]
+        output: Option<Utf8PathBuf>,
+
+        /// Gzip-compress the report, whatever the output file is called.
+        #[arg(long)]
+        compress: bool,
+
+        /// Previous JSON (or `msgpack`) report, optionally gzip-compressed;
+        /// with `--format github`, only legacy imports not present in it are
+        /// annotated, and `--format diff` and `pr-comment` show what changed
+        /// since it.
+        #[arg(long)]
+        baseline: Option<Utf8PathBuf>,
+
+        /// Only include files with these statuses: `legacy`, `partial`,
+        /// `migrated`, `no_models` (comma-separated or repeated).
+        #[arg(long, value_delimiter = ',')]
+        status: Vec<MigrationStatus>,
+
+        /// Only include files under this directory, relative to the root
+        /// path (repeatable), e.g. `app/jobs`.
+        #[arg(long)]
+        dir: Vec<Utf8PathBuf>,
+
+        /// Only include files with at least this many legacy imports.
+        #[arg(long, value_name = "N")]
+        min_legacy_imports: Option<usize>,
+
+        /// List each legacy model with its consumer files and modern
+        /// equivalent instead of listing files (`json`, `csv`, `md` or `dot`).
+        #[arg(long, conflicts_with = "baseline")]
+        by_model: bool,
+
+        /// Also list each legacy import's line, imported names and kind, for
+        /// deep links into files (`json` and `csv` only).
+        #[arg(long, conflicts_with = "by_model")]
+        with_imports: bool,
+
+        /// CSV columns to write, in order (comma-separated) [default:
+        /// `path,status,import_count,legacy_imports,migrated_imports`, plus
+        /// `owner` with `--codeowners`].
+        #[arg(long, value_delimiter = ',', value_name = "COLUMNS", conflicts_with = "by_model")]
+        columns: Vec<CsvColumn>,
+
+        /// Start CSV reports with the report metadata as `#` comment lines,
+        /// which most CSV readers don't skip.
+        #[arg(long)]
+        csv_metadata: bool,
+
+        /// Render the report with a Tera template instead of a built-in
+        /// format; the template sees the fields of the JSON report.
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["format", "by_model", "columns"])]
+        template: Option<Utf8PathBuf>,
+
+        /// Number of directory levels grouped in the `directories` section of
+        /// JSON reports [default: 1].
+        #[arg(
+            long,
+            value_name = "N",
+            conflicts_with = "by_model",
+            value_parser = clap::value_parser!(u16).range(1..)
+        )]
+        dir_depth: Option<u16>,
+
+        /// Number of legacy models in `--format mermaid` charts, most
+        /// consumed first [default: 10].
+        #[arg(
+            long,
+            value_name = "N",
+            conflicts_with = "by_model",
+            value_parser = clap::value_parser!(u16).range(1..)
+        )]
+        top: Option<u16>,
+
+        /// Print the JSON Schema of `--format json` reports instead of
+        /// scanning.
+        #[arg(
+            long,
+            conflicts_with_all = [
+                "baseline",
+                "status",
+                "dir",
+                "min_legacy_imports",
+                "by_model",
+                "columns",
+                "compress",
+                "template"
+            ]
+        )]
+        print_schema: bool,
+
+        /// History file read by `--format trend` (JSON Lines).
+        #[arg(long, default_value = ".ch-migrate/history.jsonl")]
+        history_file: Utf8PathBuf,
+
+        /// Render `--format trend` as a standalone HTML page with a progress
+        /// chart instead of CSV.
+        #[arg(long)]
+        chart: bool,
+    },
+
+    /// Export the model registry (legacy and modern models with their exports).
+    ExportRegistry {
+        /// Output format.
+        #[arg(short, long, value_enum, default_value_t = RegistryFormat::Json)]
+        format: RegistryFormat,
+
+        /// Output file (defaults to stdout).
+        #[arg(short, long)]
+        output: Option<Utf8PathBuf>,
+    },
+
+    /// Render an SVG badge showing migration progress.
+    Badge {
+        /// Text on the left side of the badge.
+        #[arg(short, long, default_value = "migration")]
+        label: String,
+
+        /// Output file (defaults to stdout).
+        #[arg(short, long)]
+        output: Option<Utf8PathBuf>,
+    },
+
+    /// Check that each ported model provides every export of its legacy version.
+    ///
+    /// Exits with an error if any `shared_2023` model is missing exports.
+    ValidateParity {
+        /// Output format.
+        #[arg(short, long, value_enum, default_value_t = ParityFormat::Text)]
+        format: ParityFormat,
+
+        /// Output file (defaults to stdout).
+        #[arg(short, long)]
+        output: Option<Utf8PathBuf>,
+    },
+
+    /// Propose an order for migrating the remaining files.
+    ///
+    /// Prints numbered waves: each wave's files only import pending files
+    /// from earlier waves, so they can be migrated independently. Files
+    /// waiting for a legacy model to be ported come last, with the models
+    /// blocking the most files first.
+    Plan {
+        /// Output format.
+        #[arg(short, long, value_enum, default_value_t = PlanFormat::Text)]
+        format: PlanFormat,
+
+        /// Output file (defaults to stdout).
+        #[arg(short, long)]
+        output: Option<Utf8PathBuf>,
+    },
+
+    /// Show which files would change status if a legacy model were ported.
+    ///
+    /// Assumes the model is fully ported to `shared_2023` and every import of
+    /// it switched over, then lists the files that would become migrated or
+    /// partial, and those no longer blocked on unported models.
+    Impact {
+        /// Legacy model name (e.g. `Contract`).
+        model: String,
+
+        /// Output format.
+        #[arg(short, long, value_enum, default_value_t = PlanFormat::Text)]
+        format: PlanFormat,
+
+        /// Output file (defaults to stdout).
+        #[arg(short, long)]
+        output: Option<Utf8PathBuf>,
+    },
+
+    /// List files importing a module whose path contains a fragment.
+    ///
+    /// Prints one `file:line: module (names)` line per matching import.
+    /// Exits with an error if nothing matches.
+    GrepImport {
+        /// Fragment of the import path, e.g. `shared/models/contract`.
+        fragment: String,
+    },
+
+    /// Compact the scan history file according to its retention policy.
+    ///
+    /// Entries beyond `history.max_entries` or older than
+    /// `history.max_age_days` are dropped. Recording a scan applies the same
+    /// policy, so this is only needed after tightening the limits.
+    Maintenance {
+        /// History file (JSON Lines).
+        #[arg(long, default_value = ".ch-migrate/history.jsonl")]
+        history_file: Utf8PathBuf,
+
+        /// Keep at most this many entries, newest first.
+        #[arg(long, value_name = "N")]
+        max_entries: Option<usize>,
+
+        /// Drop entries older than this many days.
+        #[arg(long, value_name = "DAYS")]
+        max_age_days: Option<u64>,
+    },
+
+    /// Measure scan performance on a generated repository.
+    ///
+    /// Generates a synthetic repository in a temporary directory, runs a full
+    /// scan, a streaming scan and a simulated watch burst, prints timings and
+    /// peak memory, then deletes the repository.
+    Selftest {
+        /// Number of TypeScript files to generate.
+        #[arg(long, default_value_t = 20_000)]
+        files: usize,
+
+        /// Keep the generated repository instead of deleting it.
+        #[arg(long)]
+        keep: bool,
+    },
+
+    /// Generate man pages for ch-migrate and each subcommand.
+    ///
+    /// Writes roff pages (`ch-migrate.1`, `ch-migrate-scan.1`, ...) built
+    /// from the argument definitions, for packaging.
+    #[command(hide = true)]
+    Mangen {
+        /// Directory to write the pages to, created if missing.
+        #[arg(long, default_value = "man")]
+        out_dir: Utf8PathBuf,
+    },
+}
+
+/// Operations on existing reports.
+#[derive(Subcommand)]
+enum ReportAction {
+    /// Convert a binary (`--format msgpack`) report to JSON or CSV.
+    Convert {
+        /// Binary report to read.
+        input: Utf8PathBuf,
+
+        /// Output format.
+        #[arg(short, long, value_enum, default_value_t = ConvertFormat::Json)]
+        format: ConvertFormat,
+
         /// Output file (defaults to stdout).
         #[arg(short, long)]
         output: Option<Utf8PathBuf>,
     },
 }
 
+/// Output format of `report convert`.
+#[derive(Clone, Copy, ValueEnum)]
+enum ConvertFormat {
+    /// JSON format, as from `report --format json`.
+    Json,
+    /// CSV format, as from `report --format csv`.
+    Csv,
+}
+
+/// Scan summary output format.
+#[derive(Clone, Copy, ValueEnum)]
+enum ScanFormat {
+    /// Plain text for the terminal.
+    Text,
+    /// Markdown tables for pasting into Confluence or GitHub.
+    Markdown,
+    /// GitHub Actions `::warning` annotations for every legacy import.
+    Github,
+}
+
 /// Report output format.
 #[derive(Clone, Copy, ValueEnum)]
 enum ReportFormat {
@@ -121,99 +755,698 @@ enum ReportFormat {
     Json,
     /// CSV format.
     Csv,
+    /// GitHub Actions annotations, plus a job summary in `$GITHUB_STEP_SUMMARY`.
+    Github,
+    /// Standalone HTML page with progress charts and a sortable file table.
+    Html,
+    /// Markdown page with a collapsible section of files per top-level directory.
+    #[value(name = "md", alias = "markdown")]
+    Markdown,
+    /// SARIF 2.1.0 log for GitHub code scanning, one result per legacy import.
+    Sarif,
+    /// Excel workbook with summary, file and per-model consumer sheets
+    /// (requires `--output`).
+    Xlsx,
+    /// Compact `MessagePack` encoding of the JSON report, for very large repos
+    /// (requires `--output`; read it back with `report convert`).
+    Msgpack,
+    /// Progress over time from the history file as CSV, or an HTML chart with
+    /// `--chart` (doesn't scan).
+    Trend,
+    /// Standalone HTML page of what changed since the `--baseline` report:
+    /// newly migrated files, regressions and new files with legacy imports.
+    Diff,
+    /// Short Markdown block for a pull-request comment: progress, the change
+    /// since `--baseline` and the top new legacy files.
+    PrComment,
+    /// Graphviz DOT graph of files and the legacy models they import;
+    /// `--by-model` points the edges from models to their consumers.
+    Dot,
+    /// Mermaid flowchart of the `--top` legacy models and the directories
+    /// consuming them, in a fenced block for Markdown docs.
+    Mermaid,
 }
 
-// =============================================================================
-// INITIALIZATION FUNCTIONS
-// =============================================================================
+impl ReportFormat {
+    /// Returns `true` for binary formats, which are only written to files.
+    const fn is_binary(self) -> bool {
+        matches!(self, Self::Xlsx | Self::Msgpack)
+    }
+}
 
-/// Initializes the tracing subscriber for logging.
-///
-/// Respects the `RUST_LOG` environment variable if set. Otherwise, uses
-/// `debug` level if `--verbose` is set, or `info` level by default.
-/// Noisy crates like `hyper` and `mio` are filtered to `warn` level.
-///
-/// # Arguments
-///
-/// * `verbose` - Enable debug-level logging
-/// * `no_color` - Disable ANSI colors in output
-fn init_tracing(verbose: bool, no_color: bool) {
-    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
-        let level = if verbose { "debug" } else { "info" };
-        EnvFilter::new(format!("{level},hyper=warn,mio=warn,notify=warn"))
-    });
+/// Column of a CSV report.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "snake_case")]
+enum CsvColumn {
+    /// File path.
+    Path,
+    /// Migration status.
+    Status,
+    /// Number of imports.
+    ImportCount,
+    /// Number of legacy imports.
+    LegacyImports,
+    /// Number of migrated imports.
+    MigratedImports,
+    /// CODEOWNERS owners, space-separated (requires `--codeowners`).
+    Owner,
+    /// Line of the first legacy import, empty without one.
+    FirstLegacyLine,
+    /// Directory of the file, relative to the app path.
+    Directory,
+}
 
-    // Check if colors should be disabled (flag or NO_COLOR env var)
-    let use_ansi = !no_color && std::env::var("NO_COLOR").is_err();
+impl CsvColumn {
+    /// Columns written without `--columns`.
+    const DEFAULT: [Self; 5] = [
+        Self::Path,
+        Self::Status,
+        Self::ImportCount,
+        Self::LegacyImports,
+        Self::MigratedImports,
+    ];
+
+    /// Returns the column's header.
+    const fn key(self) -> &'static str {
+        match self {
+            Self::Path => "path",
+            Self::Status => "status",
+            Self::ImportCount => "import_count",
+            Self::LegacyImports => "legacy_imports",
+            Self::MigratedImports => "migrated_imports",
+            Self::Owner => "owner",
+            Self::FirstLegacyLine => "first_legacy_line",
+            Self::Directory => "directory",
+        }
+    }
 
-    tracing_subscriber::registry()
-        .with(fmt::layer().with_target(false).with_ansi(use_ansi))
-        .with(filter)
-        .init();
+    /// Returns the columns written without `--columns`: the defaults, plus
+    /// `owner` when a CODEOWNERS file is loaded.
+    fn defaults(with_owners: bool) -> Vec<Self> {
+        let mut columns = Self::DEFAULT.to_vec();
+        if with_owners {
+            columns.push(Self::Owner);
+        }
+        columns
+    }
 }
 
-/// Builds a [`Config`] from CLI arguments.
-///
-/// Validates that the path exists and is a directory.
-///
-/// # Errors
-///
-/// Returns an error if the path is not provided, doesn't exist, or isn't a directory.
-fn build_config(cli: &Cli, require_shared_paths: bool) -> color_eyre::Result<Config> {
-    let path = cli
-        .path
-        .clone()
-        .unwrap_or_else(|| Utf8PathBuf::from("./WebApp.Desktop/src"));
+/// Model registry export format.
+#[derive(Clone, Copy, ValueEnum)]
+enum RegistryFormat {
+    /// JSON format.
+    Json,
+    /// CSV format.
+    Csv,
+}
 
-    // Validate path exists
-    if !path.exists() {
-        return Err(color_eyre::eyre::eyre!(
-            "Path does not exist: {}",
-            path
-        ));
-    }
+/// Export parity output format.
+#[derive(Clone, Copy, ValueEnum)]
+enum ParityFormat {
+    /// Plain text for the terminal.
+    Text,
+    /// JSON format.
+    Json,
+}
 
-    // Validate path is a directory
-    if !path.is_dir() {
-        return Err(color_eyre::eyre::eyre!(
-            "Path is not a directory: {}",
-            path
-        ));
+/// Migration plan output format.
+#[derive(Clone, Copy, ValueEnum)]
+enum PlanFormat {
+    /// Numbered waves for the terminal.
+    Text,
+    /// JSON format.
+    Json,
+}
+
+/// Scan progress event format.
+#[derive(Clone, Copy, ValueEnum)]
+enum ProgressFormat {
+    /// One JSON object per line.
+    Json,
+}
+
+/// What a scan reports about itself on stderr while it runs.
+#[derive(Clone, Copy)]
+struct ScanReporting {
+    /// Format of progress events, if any.
+    progress: Option<ProgressFormat>,
+    /// Number of slowest files to list in a timing profile, if profiling.
+    profile: Option<usize>,
+}
+
+impl ScanReporting {
+    /// Returns `true` if progress events or a profile were requested.
+    const fn is_requested(self) -> bool {
+        self.progress.is_some() || self.profile.is_some()
     }
+}
 
-    let mut config = Config::default();
-    config.scan.root_path = path;
-    config.scan.shared_path = cli
-        .shared_path
-        .clone()
-        .unwrap_or_else(|| config.scan.root_path.join("app").join("shared"));
-    config.scan.shared_2023_path = cli
-        .shared_2023_path
-        .clone()
-        .unwrap_or_else(|| config.scan.root_path.join("app").join("shared_2023"));
+/// Optional sections of a report.
+#[derive(Clone, Copy, Default)]
+struct ReportSections<'a> {
+    /// Whether JSON and CSV reports list each legacy import.
+    imports: bool,
+    /// Number of directory levels grouped in the JSON `directories` section,
+    /// if not the default of one.
+    directory_depth: Option<usize>,
+    /// CSV columns in order, or empty for the default columns.
+    columns: &'a [CsvColumn],
+    /// Whether CSV reports start with the metadata as `#` comment lines.
+    csv_metadata: bool,
+    /// Tera template rendering the JSON report data in place of the format.
+    template: Option<&'a Utf8Path>,
+    /// Number of legacy models in Mermaid charts, if not the default.
+    top_models: Option<usize>,
+}
 
-    // Set app_path: use CLI arg or default to ./WebApp.Desktop/src/app
-    config.scan.app_path = cli
-        .app_path
-        .clone()
-        .unwrap_or_else(|| config.scan.root_path.join("app"));
+/// Finding counted by a `--fail-on` rule.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FailCondition {
+    /// Files that only use legacy models.
+    Legacy,
+    /// Files that use both legacy and modern models.
+    Partial,
+    /// Files that could not be scanned.
+    Errors,
+    /// Files importing legacy models that have no `shared_2023/` equivalent.
+    Blocked,
+    /// Files whose status got worse since the `--baseline` report.
+    Regressions,
+    /// Legacy imports that are not in the `--baseline` report.
+    NewDebt,
+}
 
-    if let Some(name) = config.scan.shared_path.file_name() {
-        config.scan.shared_dir = name.to_owned();
+impl FailCondition {
+    /// All conditions, in the order they are documented.
+    const ALL: [Self; 6] = [
+        Self::Legacy,
+        Self::Partial,
+        Self::Errors,
+        Self::Blocked,
+        Self::Regressions,
+        Self::NewDebt,
+    ];
+
+    /// Returns the name used on the command line.
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Legacy => "legacy",
+            Self::Partial => "partial",
+            Self::Errors => "errors",
+            Self::Blocked => "blocked",
+            Self::Regressions => "regressions",
+            Self::NewDebt => "new-debt",
+        }
     }
-    if let Some(name) = config.scan.shared_2023_path.file_name() {
-        config.scan.shared_2023_dir = name.to_owned();
+
+    /// Returns `true` if the condition is counted against a `--baseline` report.
+    const fn needs_baseline(self) -> bool {
+        matches!(self, Self::Regressions | Self::NewDebt)
     }
-    config.editor.editor.clone_from(&cli.editor);
+}
 
-    validate_dir(&config.scan.shared_path, "shared", require_shared_paths)?;
-    validate_dir(
-        &config.scan.shared_2023_path,
+/// Findings of a scan, counted for each [`FailCondition`].
+///
+/// Conditions that need extra work, like the baseline comparison, are only
+/// counted when a rule asks for them and are zero otherwise.
+#[derive(Clone, Copy, Default)]
+struct FailCounts {
+    legacy: u64,
+    partial: u64,
+    errors: u64,
+    blocked: u64,
+    regressions: u64,
+    new_debt: u64,
+}
+
+impl FailCounts {
+    /// Takes the legacy, partial and error counts from `stats`.
+    const fn from_stats(stats: &StatsSnapshot) -> Self {
+        Self {
+            legacy: stats.legacy,
+            partial: stats.partial,
+            errors: stats.errors,
+            blocked: 0,
+            regressions: 0,
+            new_debt: 0,
+        }
+    }
+
+    /// Returns how many findings of `condition` were counted.
+    const fn get(&self, condition: FailCondition) -> u64 {
+        match condition {
+            FailCondition::Legacy => self.legacy,
+            FailCondition::Partial => self.partial,
+            FailCondition::Errors => self.errors,
+            FailCondition::Blocked => self.blocked,
+            FailCondition::Regressions => self.regressions,
+            FailCondition::NewDebt => self.new_debt,
+        }
+    }
+}
+
+/// How a broken `--fail-on` rule is reported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Severity {
+    /// Print the finding and exit with an error.
+    Error,
+    /// Print the finding but still succeed.
+    Warning,
+}
+
+impl Severity {
+    /// Parses a severity suffix such as `warn` or `error`.
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "error" => Some(Self::Error),
+            "warn" | "warning" => Some(Self::Warning),
+            _ => None,
+        }
+    }
+
+    /// Returns the name used on the command line.
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warn",
+        }
+    }
+}
+
+/// One `--fail-on` rule, written `CONDITION[=MAX][:SEVERITY]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct FailRule {
+    condition: FailCondition,
+    /// Most findings allowed before the rule is broken.
+    max: u64,
+    severity: Severity,
+}
+
+impl std::str::FromStr for FailRule {
+    type Err = String;
+
+    fn from_str(entry: &str) -> Result<Self, Self::Err> {
+        let (rule, severity) = match entry.split_once(':') {
+            Some((rule, level)) => {
+                let severity = Severity::parse(level.trim()).ok_or_else(|| {
+                    format!("unknown severity '{level}' in '{entry}' (expected warn or error)")
+                })?;
+                (rule.trim(), severity)
+            }
+            None => (entry, Severity::Error),
+        };
+        let (name, max) = match rule.split_once('=') {
+            Some((name, max)) => {
+                let max = max.trim().parse().map_err(|_| {
+                    format!("invalid maximum '{max}' in '{entry}' (expected a number)")
+                })?;
+                (name.trim(), max)
+            }
+            None => (rule, 0),
+        };
+        let condition = FailCondition::ALL
+            .into_iter()
+            .find(|condition| condition.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                format!(
+                    "unknown condition '{name}' (expected legacy, partial, errors, blocked, \
+                     regressions or new-debt)"
+                )
+            })?;
+        Ok(Self { condition, max, severity })
+    }
+}
+
+/// Rules that make a scan warn or exit with an error, e.g. `legacy=50,errors`.
+///
+/// Parsed from `--fail-on` so CI can gate on exactly the findings it cares
+/// about; commands that scan check it with [`FailPolicy::check`]. Each rule
+/// has its own severity, so a condition can warn at one level and fail at
+/// another (`legacy:warn,legacy=200`) while enforcement is rolled out.
+#[derive(Clone, Debug, Default)]
+struct FailPolicy {
+    /// Rules in the order given, with at most one per condition and severity.
+    rules: Vec<FailRule>,
+}
+
+impl FailPolicy {
+    /// Returns `true` if any rule counts `condition`.
+    fn has(&self, condition: FailCondition) -> bool {
+        self.rules.iter().any(|rule| rule.condition == condition)
+    }
+
+    /// Returns `true` if any rule is counted against a `--baseline` report.
+    fn needs_baseline(&self) -> bool {
+        self.rules.iter().any(|rule| rule.condition.needs_baseline())
+    }
+
+    /// Describes the rules of `severity` that `counts` breaks.
+    fn broken(&self, counts: &FailCounts, severity: Severity) -> Vec<String> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.severity == severity)
+            .filter_map(|rule| {
+                let count = counts.get(rule.condition);
+                (count > rule.max).then(|| {
+                    format!("{}: {count} found, at most {} allowed", rule.condition.name(), rule.max)
+                })
+            })
+            .collect()
+    }
+
+    /// Prints the broken warning and error rules to stderr in separate
+    /// sections.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any error-level rule is broken.
+    fn check(&self, counts: &FailCounts) -> color_eyre::Result<()> {
+        let warnings = self.broken(counts, Severity::Warning);
+        let errors = self.broken(counts, Severity::Error);
+
+        let stderr = std::io::stderr();
+        let mut handle = stderr.lock();
+        for (title, broken) in [("warnings (not failing)", &warnings), ("errors", &errors)] {
+            if !broken.is_empty() {
+                let _ = writeln!(handle);
+                let _ = writeln!(handle, "--fail-on {title}:");
+                for rule in broken {
+                    let _ = writeln!(handle, "  {rule}");
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(color_eyre::eyre::eyre!("--fail-on: {} rule(s) broken", errors.len()))
+        }
+    }
+}
+
+impl std::str::FromStr for FailPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut policy = Self::default();
+
+        for entry in s.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+            let rule: FailRule = entry.parse()?;
+            let duplicate = policy.rules.iter().any(|existing| {
+                existing.condition == rule.condition && existing.severity == rule.severity
+            });
+            if duplicate {
+                return Err(format!(
+                    "'{}' is given twice with severity {}",
+                    rule.condition.name(),
+                    rule.severity.name()
+                ));
+            }
+            policy.rules.push(rule);
+        }
+
+        if policy.rules.is_empty() {
+            return Err("expected at least one rule, e.g. legacy or errors".to_owned());
+        }
+        Ok(policy)
+    }
+}
+
+/// `--fail-on` rules of a scan and the report they compare with.
+#[derive(Clone, Copy)]
+struct FailGate<'a> {
+    policy: &'a FailPolicy,
+    /// Previous JSON or binary report for the `regressions` and `new-debt`
+    /// rules, from `--baseline`.
+    baseline: Option<&'a Utf8Path>,
+}
+
+/// Counts the findings `policy` checks in the scanned `files`.
+///
+/// Blocked files are only counted when a rule asks for them, since that
+/// needs the model registry, and the `--baseline` report is only read for
+/// the `regressions` and `new-debt` rules.
+///
+/// # Errors
+///
+/// Returns an error if the registry can't be built or the baseline can't
+/// be read.
+fn fail_counts(
+    config: &Config,
+    policy: &FailPolicy,
+    baseline: Option<&Utf8Path>,
+    stats: &StatsSnapshot,
+    files: &[FileInfo],
+) -> color_eyre::Result<FailCounts> {
+    let mut counts = FailCounts::from_stats(stats);
+    let root = &config.scan.root_path;
+
+    if policy.has(FailCondition::Blocked) {
+        let registry = build_registry(config)?;
+        counts.blocked = files
+            .iter()
+            .filter(|file| matches!(file.status, MigrationStatus::Legacy | MigrationStatus::Partial))
+            .filter(|file| !registry.missing_equivalents(file).is_empty())
+            .count() as u64;
+    }
+
+    if let Some(baseline) = baseline {
+        if policy.has(FailCondition::Regressions) {
+            let (diff, _) = diff_baseline(baseline, &ReportSelection::default(), root, files)?;
+            counts.regressions = diff.regressed.len() as u64;
+        }
+        if policy.has(FailCondition::NewDebt) {
+            let previous = load_baseline(baseline)?;
+            counts.new_debt = files
+                .iter()
+                .flat_map(|file| {
+                    let relative = file.path.strip_prefix(root).unwrap_or(&file.path);
+                    file.legacy_imports()
+                        .map(move |import| (relative.to_owned(), import.path.clone()))
+                })
+                .filter(|key| !previous.contains(key))
+                .count() as u64;
+        }
+    }
+    Ok(counts)
+}
+
+/// Files a report is narrowed to, from `report --status/--dir/--min-legacy-imports`.
+///
+/// A file must pass every filter given; an empty selection includes all files.
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
+struct ReportSelection {
+    /// Statuses to include.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    status: Vec<MigrationStatus>,
+    /// Directories to include, relative to the root path.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    dirs: Vec<Utf8PathBuf>,
+    /// Minimum number of legacy imports.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    min_legacy_imports: Option<usize>,
+}
+
+impl ReportSelection {
+    /// Returns `true` if no filter is set.
+    fn is_empty(&self) -> bool {
+        self.status.is_empty() && self.dirs.is_empty() && self.min_legacy_imports.is_none()
+    }
+
+    /// Returns `true` if `file` passes every filter.
+    ///
+    /// Directories match either the path relative to `root` or the path as
+    /// scanned.
+    fn matches(&self, file: &FileInfo, root: &Utf8Path) -> bool {
+        (self.status.is_empty() || self.status.contains(&file.status))
+            && self.matches_dir(&file.path, root)
+            && self
+                .min_legacy_imports
+                .is_none_or(|min| file.legacy_imports().count() >= min)
+    }
+
+    /// Returns `true` if `path`, of a file that failed to scan or that the
+    /// walk left out, passes every filter.
+    ///
+    /// Such files have no status or imports, so a status or legacy import
+    /// filter leaves them out.
+    fn matches_unscanned(&self, path: &Utf8Path, root: &Utf8Path) -> bool {
+        self.status.is_empty() && self.min_legacy_imports.is_none() && self.matches_dir(path, root)
+    }
+
+    /// Returns `true` if `path` is in one of the directories, or there is
+    /// no directory filter.
+    fn matches_dir(&self, path: &Utf8Path, root: &Utf8Path) -> bool {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        self.dirs.is_empty()
+            || self.dirs.iter().any(|dir| relative.starts_with(dir) || path.starts_with(dir))
+    }
+
+    /// Writes the filters as `# key: value` comment lines (for CSV output).
+    fn write_comments(&self, output: &mut String) {
+        use std::fmt::Write;
+
+        if !self.status.is_empty() {
+            let keys: Vec<&str> = self.status.iter().map(|status| status.key()).collect();
+            let _ = writeln!(output, "# status: {}", keys.join(" "));
+        }
+        for dir in &self.dirs {
+            let _ = writeln!(output, "# dir: {dir}");
+        }
+        if let Some(min) = self.min_legacy_imports {
+            let _ = writeln!(output, "# min_legacy_imports: {min}");
+        }
+    }
+}
+
+// =============================================================================
+// INITIALIZATION FUNCTIONS
+// =============================================================================
+
+/// Initializes the tracing subscriber for logging.
+///
+/// Respects the `RUST_LOG` environment variable if set. Otherwise, uses
+/// `debug` level if `--verbose` is set, or `info` level by default.
+/// Noisy crates like `hyper` and `mio` are filtered to `warn` level.
+///
+/// # Arguments
+///
+/// * `verbose` - Enable debug-level logging
+/// * `no_color` - Disable ANSI colors in output
+fn init_tracing(verbose: bool, no_color: bool) {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+        let level = if verbose { "debug" } else { "info" };
+        EnvFilter::new(format!("{level},hyper=warn,mio=warn,notify=warn"))
+    });
+
+    // Check if colors should be disabled (flag or NO_COLOR env var)
+    let use_ansi = !no_color && std::env::var("NO_COLOR").is_err();
+
+    tracing_subscriber::registry()
+        .with(fmt::layer().with_target(false).with_ansi(use_ansi))
+        .with(filter)
+        .init();
+}
+
+/// Builds a [`Config`] from CLI arguments.
+///
+/// Validates that the path exists and is a directory.
+///
+/// # Errors
+///
+/// Returns an error if the path is not provided, doesn't exist, or isn't a directory.
+fn build_config(cli: &Cli, require_shared_paths: bool) -> color_eyre::Result<Config> {
+    // CH_MIGRATE_<SECTION>_<KEY> variables sit between defaults and flags
+    let mut config = Config::default();
+    config.apply_env_overrides()?;
+    let from_env = |path: &Utf8PathBuf| Some(path.clone()).filter(|path| !path.as_str().is_empty());
+
+    let path = cli
+        .path
+        .first()
+        .cloned()
+        .or_else(|| from_env(&config.scan.root_path))
+        .unwrap_or_else(|| Utf8PathBuf::from("./WebApp.Desktop/src"));
+
+    // Validate path exists
+    if !path.exists() {
+        return Err(color_eyre::eyre::eyre!(
+            "Path does not exist: {}",
+            path
+        ));
+    }
+
+    // Validate path is a directory
+    if !path.is_dir() {
+        return Err(color_eyre::eyre::eyre!(
+            "Path is not a directory: {}",
+            path
+        ));
+    }
+
+    config.scan.root_path = path;
+    config.scan.shared_path = cli
+        .shared_path
+        .clone()
+        .or_else(|| from_env(&config.scan.shared_path))
+        .unwrap_or_else(|| config.scan.root_path.join("app").join("shared"));
+    config.scan.shared_2023_path = cli
+        .shared_2023_path
+        .clone()
+        .or_else(|| from_env(&config.scan.shared_2023_path))
+        .unwrap_or_else(|| config.scan.root_path.join("app").join("shared_2023"));
+
+    // Set app_path: use CLI arg or default to ./WebApp.Desktop/src/app
+    config.scan.app_path = cli
+        .app_path
+        .clone()
+        .or_else(|| from_env(&config.scan.app_path))
+        .unwrap_or_else(|| config.scan.root_path.join("app"));
+
+    if let Some(name) = config.scan.shared_path.file_name() {
+        config.scan.shared_dir = name.to_owned();
+    }
+    if let Some(name) = config.scan.shared_2023_path.file_name() {
+        config.scan.shared_2023_dir = name.to_owned();
+    }
+    if cli.editor.is_some() {
+        config.editor.editor.clone_from(&cli.editor);
+    }
+    if cli.codeowners.is_some() {
+        config.scan.codeowners_path.clone_from(&cli.codeowners);
+    }
+    if cli.renames.is_some() {
+        config.scan.renames_path.clone_from(&cli.renames);
+    }
+    if cli.tsconfig.is_some() {
+        config.scan.tsconfig_path.clone_from(&cli.tsconfig);
+    }
+    if !cli.ignore_imports.is_empty() {
+        config.scan.ignore_imports.clone_from(&cli.ignore_imports);
+    }
+    if let Some(labels) = &cli.status_labels {
+        config.labels.clone_from(labels);
+    }
+    if cli.max_memory_mb.is_some() {
+        config.scan.max_memory_mb = cli.max_memory_mb;
+    }
+    if cli.max_arena_mb.is_some() {
+        config.scan.max_arena_mb = cli.max_arena_mb;
+    }
+    if cli.cache.is_some() {
+        config.scan.cache_dir.clone_from(&cli.cache);
+    }
+    if cli.max_file_size.is_some() {
+        config.scan.max_file_size = cli.max_file_size;
+    }
+    if cli.mmap_threshold.is_some() {
+        config.scan.mmap_threshold = cli.mmap_threshold;
+    }
+    if !cli.priority_dirs.is_empty() {
+        config.scan.priority_dirs.clone_from(&cli.priority_dirs);
+    }
+    if cli.max_detailed_files.is_some() {
+        config.scan.max_detailed_files = cli.max_detailed_files;
+    }
+    if cli.jobs.is_some() {
+        config.scan.max_parallel_jobs = cli.jobs;
+    }
+    if cli.retries.is_some() {
+        config.scan.retry_attempts = cli.retries;
+    }
+
+    validate_dir(&config.scan.shared_path, "shared", require_shared_paths)?;
+    validate_dir(
+        &config.scan.shared_2023_path,
         "shared_2023",
         require_shared_paths,
     )?;
     // app_path is always required since we scan it for model consumers
     validate_dir(&config.scan.app_path, "app", true)?;
+    for path in cli.path.iter().skip(1) {
+        validate_dir(path, "additional", true)?;
+        config.scan.additional_app_paths.push(path.clone());
+    }
 
     Ok(config)
 }
@@ -249,152 +1482,1560 @@ fn validate_dir(path: &Utf8PathBuf, label: &str, required: bool) -> color_eyre::
 /// Creates a [`Scanner`] from the configuration.
 ///
 /// Uses `app_path` as the scan root to restrict scanning to only application
-/// code, excluding shared model definition directories.
+/// code, excluding shared model definition directories. Additional `--path`
+/// directories are scanned alongside it, with merged statistics.
 ///
 /// # Errors
 ///
 /// Returns an error if the scanner cannot be created.
 fn create_scanner(config: &Config) -> color_eyre::Result<Scanner> {
-    // Use app_path for scanning (not root_path) to restrict to application code only
-    let scanner_config = ScannerConfig::new(&config.scan.app_path)
-        .with_skip_dirs(&["node_modules", "dist", ".git"]);
+    build_scanner(config, scanner_config(config))
+}
+
+/// Creates a [`Scanner`] with `scanner_config` and the configuration's
+/// model path matcher.
+///
+/// # Errors
+///
+/// Returns an error if the scanner cannot be created.
+fn build_scanner(config: &Config, scanner_config: ScannerConfig) -> color_eyre::Result<Scanner> {
     let matcher = ModelPathMatcher::from_scan_config(&config.scan);
 
     Scanner::new_with_matcher(scanner_config, matcher)
         .map_err(|e| color_eyre::eyre::eyre!("Failed to create scanner: {}", e))
 }
 
+/// Returns the scanner settings of the configuration.
+fn scanner_config(config: &Config) -> ScannerConfig {
+    // Use app_path for scanning (not root_path) to restrict to application code only
+    let mut scanner_config = ScannerConfig::new(&config.scan.app_path)
+        .with_additional_roots(&config.scan.additional_app_paths)
+        .with_skip_dirs(&["node_modules", "dist", ".git"])
+        .with_ignore_imports(&config.scan.ignore_imports)
+        .with_priority_dirs(&config.scan.priority_dirs)
+        .with_legacy_first(config.scan.legacy_first);
+    if let Some(codeowners) = &config.scan.codeowners_path {
+        scanner_config = scanner_config.with_codeowners(codeowners);
+    }
+    if let Some(tsconfig) = &config.scan.tsconfig_path {
+        scanner_config = scanner_config.with_tsconfig(tsconfig);
+    }
+    if let Some(dir) = &config.scan.cache_dir {
+        scanner_config = scanner_config.with_cache_dir(dir);
+    }
+    if let Some(bytes) = config.scan.max_file_size {
+        scanner_config = scanner_config.with_max_file_size(bytes);
+    }
+    if let Some(bytes) = config.scan.mmap_threshold {
+        scanner_config = scanner_config.with_mmap_threshold(bytes);
+    }
+    if let Some(threads) = config.scan.max_parallel_jobs {
+        scanner_config = scanner_config.with_threads(threads);
+    }
+    if let Some(attempts) = config.scan.retry_attempts {
+        scanner_config = scanner_config.with_retry(RetryPolicy::default().with_attempts(attempts));
+    }
+    scanner_config
+        .with_skip_binary(config.scan.skip_binary)
+        .with_resource_limits(ResourceLimits::from(&config.scan))
+}
+
+/// Builds the model registry from the shared directories.
+///
+/// Applies the configured rename mapping, which fails if a mapped target is
+/// not a model in `shared_2023/`.
+///
+/// # Errors
+///
+/// Returns an error if the rename mapping cannot be loaded or the registry
+/// cannot be built.
+fn build_registry(config: &Config) -> color_eyre::Result<ModelRegistry> {
+    let mut builder =
+        RegistryBuilder::new(&config.scan.shared_path, &config.scan.shared_2023_path);
+    if let Some(path) = &config.scan.renames_path {
+        let renames = ModelRenames::load(path)
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to load model renames: {}", e))?;
+        builder = builder.with_renames(renames);
+    }
+
+    builder
+        .build()
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to build model registry: {}", e))
+}
+
+/// Loads a session handed over by `:upgrade` and deletes its file.
+///
+/// # Errors
+///
+/// Returns an error if the session file cannot be read or is malformed.
+fn load_session(path: &Utf8Path) -> color_eyre::Result<Session> {
+    let session = Session::load(path)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to resume session from {}: {}", path, e))?;
+    if let Err(e) = std::fs::remove_file(path) {
+        warn!(path = %path, error = %e, "Failed to remove session file");
+    }
+    Ok(session)
+}
+
 // =============================================================================
 // COMMAND IMPLEMENTATIONS
 // =============================================================================
 
-/// Runs a one-shot scan with summary output.
+/// Minimum time between two progress events.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Number of generated files per feature directory in `selftest`.
+const SELFTEST_FILES_PER_DIR: usize = 100;
+
+/// Number of generated models in each shared directory in `selftest`.
+const SELFTEST_MODELS: usize = 50;
+
+/// Scans the app directory and any additional paths, reporting on the scan
+/// as requested.
 ///
-/// # Arguments
+/// The scanner walks every path into one set of files, and a file reachable
+/// from several paths is counted once.
 ///
-/// * `config` - The application configuration
-/// * `detailed` - Whether to show detailed file list
+/// With `--profile` the scan is timed with [`Scanner::scan_profiled`] and
+/// the breakdown is written to stderr afterwards. With a progress format it
+/// goes through [`Scanner::scan_streaming`] instead; see [`scan_with_json_progress`].
+///
+/// Ctrl-C cancels the scan; see [`cancel_on_interrupt`].
 ///
 /// # Errors
 ///
-/// Returns an error if scanning fails.
-fn run_scan(config: &Config, detailed: bool) -> color_eyre::Result<()> {
-    info!(app_path = %config.scan.app_path, "Starting scan");
+/// Returns an error if scanning fails or is cancelled.
+async fn run_scanner(scanner: &Scanner, reporting: ScanReporting) -> color_eyre::Result<ScanResult> {
+    let cancel = CancellationToken::new();
+
+    if let Some(slowest) = reporting.profile {
+        let scan_cancel = cancel.clone();
+        let scan = spawn_scan(scanner, move |scanner| scanner.scan_profiled(&scan_cancel));
+        let (result, profile) = cancel_on_interrupt(&cancel, scan).await??;
+        let text = generate_profile_text(&profile, &result.stats.parsed, slowest);
+        let _ = write!(std::io::stderr(), "{text}");
+        return Ok(result);
+    }
 
-    let scanner = create_scanner(config)?;
-    let result = scanner.scan()?;
+    match reporting.progress {
+        Some(ProgressFormat::Json) => {
+            cancel_on_interrupt(&cancel, scan_with_json_progress(scanner, &cancel)).await
+        }
+        None => {
+            let scan_cancel = cancel.clone();
+            let scan = spawn_scan(scanner, move |scanner| scanner.scan_cancellable(&scan_cancel));
+            Ok(cancel_on_interrupt(&cancel, scan).await??)
+        }
+    }
+}
 
-    print_stats_summary(&result.stats);
+/// Starts `scan` with a handle to `scanner` on tokio's blocking thread pool,
+/// keeping the runtime free for the interrupt handler and progress updates.
+///
+/// The scan runs whether or not the returned future is polled; awaiting it
+/// gives the result, or an error if `scan` panicked.
+fn spawn_scan<T: Send + 'static>(
+    scanner: &Scanner,
+    scan: impl FnOnce(&Scanner) -> T + Send + 'static,
+) -> impl Future<Output = color_eyre::Result<T>> {
+    let scanner = scanner.clone();
+    let task = tokio::task::spawn_blocking(move || scan(&scanner));
+    async { task.await.map_err(|_| color_eyre::eyre::eyre!("Scan task panicked")) }
+}
 
-    if detailed {
-        print_detailed_file_list(&scanner);
-    }
+/// Runs blocking file system `work` on tokio's blocking thread pool, like
+/// [`spawn_scan`] does for scans.
+///
+/// # Errors
+///
+/// Returns the error of `work`, or an error if it panicked.
+async fn spawn_io<T: Send + 'static>(
+    work: impl FnOnce() -> std::io::Result<T> + Send + 'static,
+) -> color_eyre::Result<T> {
+    let result = tokio::task::spawn_blocking(work).await;
+    Ok(result.map_err(|_| color_eyre::eyre::eyre!("File task panicked"))??)
+}
 
-    // Print any errors encountered
-    if !result.errors.is_empty() {
-        let stderr = std::io::stderr();
-        let mut handle = stderr.lock();
-        writeln!(handle)?;
-        writeln!(handle, "Errors ({}):", result.errors.len())?;
-        for (path, error) in &result.errors {
-            writeln!(handle, "  {path} - {error}")?;
+/// Awaits `scan`, cancelling `cancel` if Ctrl-C is pressed meanwhile.
+///
+/// The scan then stops between files and the command fails with "scan
+/// cancelled" instead of being killed halfway through writing its output.
+/// Ctrl-C is only caught while a scan runs; commands that don't scan keep
+/// the default handling.
+async fn cancel_on_interrupt<T>(cancel: &CancellationToken, scan: impl Future<Output = T>) -> T {
+    tokio::pin!(scan);
+    tokio::select! {
+        result = &mut scan => return result,
+        Ok(()) = tokio::signal::ctrl_c() => {
+            warn!("Interrupted; stopping the scan");
+            cancel.cancel();
         }
     }
+    scan.await
+}
+
+/// Runs a scan, writing JSON progress events to stderr.
+///
+/// The scan goes through [`Scanner::scan_streaming`] on the blocking thread
+/// pool while this task counts results. An event is written once the walk
+/// has found every file, at most every [`PROGRESS_INTERVAL`] while files are
+/// analyzed, and when the scan completes. Events during analysis carry the
+/// scanner's estimate of the time remaining.
+///
+/// # Errors
+///
+/// Returns an error if scanning fails or is cancelled.
+async fn scan_with_json_progress(
+    scanner: &Scanner,
+    cancel: &CancellationToken,
+) -> color_eyre::Result<ScanResult> {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(scanner.channel_capacity());
+    let cancel = cancel.clone();
+    let scan = spawn_scan(scanner, move |scanner| scanner.scan_streaming_cancellable(tx, &cancel));
+
+    let mut event = ProgressEvent::default();
+    let mut started = Instant::now();
+    let mut last_emitted = started;
+    let mut result = None;
+
+    while let Some(update) = rx.recv().await {
+        match update {
+            ScanUpdate::PathsDiscovered(total) => {
+                event.total = total;
+                started = Instant::now();
+                emit_progress(&event);
+                last_emitted = started;
+            }
+            ScanUpdate::FileScanned(_) | ScanUpdate::FileError { .. } => {
+                event.scanned += 1;
+            }
+            ScanUpdate::Progress(stats) => {
+                if last_emitted.elapsed() >= PROGRESS_INTERVAL {
+                    event.rate = files_per_second(event.scanned, started.elapsed());
+                    event.eta = stats.eta().map(|eta| eta.as_secs());
+                    emit_progress(&event);
+                    last_emitted = Instant::now();
+                }
+            }
+            ScanUpdate::Complete(complete) => {
+                event.rate = files_per_second(event.scanned, started.elapsed());
+                event.eta = Some(0);
+                emit_progress(&event);
+                result = Some(complete);
+            }
+        }
+    }
+
+    scan.await??;
+    result.ok_or_else(|| color_eyre::eyre::eyre!("Scan ended without a result"))
+}
+
+/// Collects the files given with `--files`, `--stdin-paths` or
+/// `--files-from`.
+///
+/// Returns `None` if none was used, so the source tree is walked.
+/// Blank lines in a list are skipped.
+///
+/// # Errors
+///
+/// Returns an error if stdin or the list file cannot be read.
+fn listed_files(
+    files: &[Utf8PathBuf],
+    stdin_paths: bool,
+    files_from: Option<&Utf8Path>,
+) -> color_eyre::Result<Option<Vec<Utf8PathBuf>>> {
+    let list = match files_from {
+        Some(path) if path != "-" => std::fs::File::open(path)
+            .map(std::io::BufReader::new)
+            .and_then(read_path_list)
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to read file list {}: {}", path, e))?,
+        Some(_) => read_path_list(std::io::stdin().lock())?,
+        None if stdin_paths => read_path_list(std::io::stdin().lock())?,
+        None => return Ok((!files.is_empty()).then(|| files.to_vec())),
+    };
+    Ok(Some(list))
+}
+
+/// Reads a list of paths, one per line, skipping blank lines.
+///
+/// # Errors
+///
+/// Returns an error if `reader` fails or holds invalid UTF-8.
+fn read_path_list(reader: impl BufRead) -> std::io::Result<Vec<Utf8PathBuf>> {
+    let mut paths = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        if !line.is_empty() {
+            paths.push(Utf8PathBuf::from(line));
+        }
+    }
+    Ok(paths)
+}
+
+/// Runs a one-shot scan with summary output.
+///
+/// # Arguments
+///
+/// * `config` - The application configuration
+/// * `detailed` - Whether to show detailed file list (text format only)
+/// * `format` - Output format for the summary
+/// * `files` - Files to analyze instead of walking the source roots
+/// * `fail_on` - Rules that make the scan warn or exit with an error
+/// * `reporting` - Progress events and profiling requested for the scan
+///
+/// # Errors
+///
+/// Returns an error if scanning fails, if an error-level `fail_on` rule is
+/// broken, if a `fail_on` rule needs a baseline that wasn't given or can't
+/// be read, or if progress events or a profile are requested for listed
+/// files or a directory, which are scanned without either.
+async fn run_scan(
+    config: &Config,
+    detailed: bool,
+    format: ScanFormat,
+    files: Option<&[Utf8PathBuf]>,
+    path_prefix: Option<&Utf8Path>,
+    fail_on: Option<FailGate<'_>>,
+    reporting: ScanReporting,
+) -> color_eyre::Result<()> {
+    if (files.is_some() || path_prefix.is_some()) && reporting.is_requested() {
+        return Err(color_eyre::eyre::eyre!(
+            "--progress and --profile only apply to full scans, not to listed files or --path-prefix"
+        ));
+    }
+    if fail_on.is_some_and(|gate| gate.policy.needs_baseline() && gate.baseline.is_none()) {
+        return Err(color_eyre::eyre::eyre!(
+            "--fail-on regressions and new-debt need a --baseline report to compare with"
+        ));
+    }
+    info!(app_path = %config.scan.app_path, "Starting scan");
+
+    let scanner = create_scanner(config)?;
+    let result = match (files, path_prefix) {
+        (Some(files), _) => {
+            let files = files.to_vec();
+            spawn_scan(&scanner, move |scanner| scanner.scan_paths(&files)).await?
+        }
+        (None, Some(prefix)) => {
+            let root = &config.scan.root_path;
+            let dir = if prefix.is_absolute() || prefix.starts_with(root) {
+                prefix.to_path_buf()
+            } else {
+                root.join(prefix)
+            };
+            let prefix = dir.clone();
+            spawn_scan(&scanner, move |scanner| scanner.rescan_prefix(&prefix))
+                .await?
+                .map_err(|e| color_eyre::eyre::eyre!("Failed to scan {}: {}", dir, e))?
+        }
+        (None, None) => run_scanner(&scanner, reporting).await?,
+    };
+
+    let teams = if scanner.codeowners().is_some() {
+        rollup_by_owner(&scanner.cache().all_files())
+    } else {
+        Vec::new()
+    };
+
+    match format {
+        ScanFormat::Text => {
+            print_stats_summary(&result.stats, &config.labels);
+
+            if !teams.is_empty() {
+                let _ = writeln!(std::io::stdout());
+                print_stats_table("Team", &owner_rows(&teams), &result.stats, &config.labels);
+            }
+
+            if detailed {
+                let registry = build_registry(config)?;
+                print_detailed_file_list(&scanner, &registry, &config.labels);
+                print_skipped_paths(&result.skipped);
+            }
+        }
+        ScanFormat::Markdown => {
+            let legacy_files = scanner.files_with_status(MigrationStatus::Legacy);
+            let markdown = generate_markdown_summary(
+                &result.stats,
+                &teams,
+                &legacy_files,
+                &config.scan.root_path,
+                &config.labels,
+            );
+            let _ = write!(std::io::stdout(), "{markdown}");
+        }
+        ScanFormat::Github => {
+            let mut files = scanner.cache().all_files();
+            files.sort_unstable_by(|a, b| a.path.cmp(&b.path));
+            let annotations = generate_github_annotations(&files, &config.scan.root_path, None);
+            let _ = write!(std::io::stdout(), "{}", annotations.output);
+            info!(count = annotations.count, "Emitted legacy import annotations");
+        }
+    }
+
+    // Print any errors encountered
+    if !result.errors.is_empty() {
+        let stderr = std::io::stderr();
+        let mut handle = stderr.lock();
+        writeln!(handle)?;
+        writeln!(handle, "Errors ({}):", result.errors.len())?;
+        for (path, error) in &result.errors {
+            writeln!(handle, "  {path} - {error}")?;
+        }
+
+        let panicked = result
+            .errors
+            .iter()
+            .filter(|(_, error)| matches!(error, ScanError::Internal { .. }))
+            .count();
+        if panicked > 0 {
+            writeln!(
+                handle,
+                "  {panicked} file(s) crashed the analyzer and were skipped; please report them"
+            )?;
+        }
+    }
+
+    let Some(gate) = fail_on else {
+        return Ok(());
+    };
+    // Building the registry and reading the baseline touch the file system
+    let config = config.clone();
+    let policy = gate.policy.clone();
+    let baseline = gate.baseline.map(Utf8Path::to_path_buf);
+    let stats = result.stats;
+    let files = scanner.cache().all_files();
+    let counts = tokio::task::spawn_blocking(move || {
+        fail_counts(&config, &policy, baseline.as_deref(), &stats, &files)
+    })
+    .await
+    .map_err(|_| color_eyre::eyre::eyre!("--fail-on task panicked"))??;
+    gate.policy.check(&counts)
+}
+
+/// Runs a one-shot scan and prints aggregated statistics.
+///
+/// # Arguments
+///
+/// * `config` - The application configuration
+/// * `by_dir` - Whether to break statistics down per directory subtree
+/// * `depth` - Number of directory levels to group by
+/// * `reporting` - Progress events and profiling requested for the scan
+///
+/// # Errors
+///
+/// Returns an error if scanning fails.
+async fn run_summary(
+    config: &Config,
+    by_dir: bool,
+    depth: u16,
+    reporting: ScanReporting,
+) -> color_eyre::Result<()> {
+    info!(app_path = %config.scan.app_path, by_dir, depth, "Starting summary");
+
+    let scanner = create_scanner(config)?;
+    let result = run_scanner(&scanner, reporting).await?;
+
+    if by_dir {
+        let files = scanner.cache().all_files();
+        let rollup = rollup_by_directory(&files, &config.scan.app_path, usize::from(depth));
+        let rows: Vec<_> = rollup
+            .iter()
+            .map(|dir| (dir.path.as_str(), &dir.stats))
+            .collect();
+        print_stats_table("Directory", &rows, &result.stats, &config.labels);
+    } else {
+        print_stats_summary(&result.stats, &config.labels);
+    }
+
+    Ok(())
+}
+
+/// Runs the interactive TUI with optional file watching.
+///
+/// # Arguments
+///
+/// * `config` - The application configuration
+/// * `no_watch` - Whether to disable file watching
+/// * `view` - Initial view (filter + sort) to open with
+/// * `session` - Session to resume after an in-place upgrade
+///
+/// # Errors
+///
+/// Returns an error if the TUI fails.
+async fn run_watch(
+    config: Config,
+    no_watch: bool,
+    view: Option<ViewSpec>,
+    session: Option<Session>,
+) -> color_eyre::Result<()> {
+    let watch = config.watch.enabled && !no_watch;
+    info!(app_path = %config.scan.app_path, watch, "Starting TUI");
+
+    // Editors write files while a session runs, and a mapped file must not
+    // change, so sessions read every file
+    let mut scanner_config = scanner_config(&config);
+    scanner_config.mmap_threshold = None;
+    if let Some(limit) = config.scan.max_detailed_files {
+        scanner_config = scanner_config.with_max_detailed_files(limit);
+    }
+    // Watching the shared directories needs a registry to keep up to date
+    if watch && config.watch.shared {
+        scanner_config = scanner_config
+            .with_shared_paths(&config.scan.shared_path, &config.scan.shared_2023_path);
+    }
+    let scanner = build_scanner(&config, scanner_config)?;
+    if !config.scan.additional_app_paths.is_empty() {
+        warn!("Additional --path directories are not watched; edits there need a rescan");
+    }
+
+    let mut config = config;
+    config.watch.enabled &= !no_watch;
+
+    // Handle SIGTERM for graceful shutdown on Unix
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut sigterm = signal(SignalKind::terminate())?;
+
+        tokio::select! {
+            result = ch_tui::run_with_session(config, scanner, view, session) => {
+                result.map_err(|e| color_eyre::eyre::eyre!("TUI error: {}", e))?;
+            }
+            _ = sigterm.recv() => {
+                info!("Received SIGTERM, shutting down");
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        ch_tui::run_with_session(config, scanner, view, session)
+            .await
+            .map_err(|e| color_eyre::eyre::eyre!("TUI error: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Runs a scan and prints statistics, optionally recording them.
+///
+/// # Arguments
+///
+/// * `config` - The application configuration
+/// * `record` - Whether to append the statistics to the history file
+/// * `history_file` - Path to the JSON Lines history file
+/// * `reporting` - Progress events and profiling requested for the scan
+///
+/// # Errors
+///
+/// Returns an error if scanning or writing the history fails.
+async fn run_stats(
+    config: &Config,
+    record: bool,
+    history_file: &Utf8Path,
+    reporting: ScanReporting,
+) -> color_eyre::Result<()> {
+    info!(app_path = %config.scan.app_path, "Starting scan");
+
+    let scanner = create_scanner(config)?;
+    let result = run_scanner(&scanner, reporting).await?;
+
+    print_stats_summary(&result.stats, &config.labels);
+
+    if record {
+        let entry = HistoryEntry::new(result.stats, git_commit(&config.scan.root_path));
+        append_history(history_file, &entry).map_err(|e| {
+            color_eyre::eyre::eyre!("Failed to record history to {}: {}", history_file, e)
+        })?;
+        info!(path = %history_file, commit = ?entry.commit, "Recorded scan statistics");
+
+        let retention = HistoryRetention::from(&config.history);
+        if !retention.is_empty() {
+            let compaction = compact_history(history_file, &retention).map_err(|e| {
+                color_eyre::eyre::eyre!("Failed to compact history {}: {}", history_file, e)
+            })?;
+            if compaction.removed > 0 {
+                info!(path = %history_file, removed = compaction.removed, "Compacted history");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Compacts the history file according to a retention policy.
+///
+/// # Arguments
+///
+/// * `history_file` - Path to the JSON Lines history file
+/// * `retention` - Which entries to keep
+///
+/// # Errors
+///
+/// Returns an error if no limit is set, or if the history file cannot be
+/// read, is malformed, or cannot be rewritten.
+fn run_maintenance(
+    history_file: &Utf8Path,
+    retention: &HistoryRetention,
+) -> color_eyre::Result<()> {
+    if retention.is_empty() {
+        return Err(color_eyre::eyre::eyre!(
+            "No history retention limit set; pass --max-entries or --max-age-days, \
+             or set CH_MIGRATE_HISTORY_MAX_ENTRIES or CH_MIGRATE_HISTORY_MAX_AGE_DAYS"
+        ));
+    }
+
+    let compaction = compact_history(history_file, retention).map_err(|e| {
+        color_eyre::eyre::eyre!("Failed to compact history {}: {}", history_file, e)
+    })?;
+    let _ = writeln!(
+        std::io::stdout(),
+        "{history_file}: removed {} entries, kept {}",
+        compaction.removed,
+        compaction.kept
+    );
+    Ok(())
+}
+
+/// Prints recorded migration progress over time.
+///
+/// # Arguments
+///
+/// * `history_file` - Path to the JSON Lines history file
+/// * `labels` - Display labels for the status columns
+///
+/// # Errors
+///
+/// Returns an error if the history file cannot be read or is malformed.
+fn run_history(history_file: &Utf8Path, labels: &StatusLabels) -> color_eyre::Result<()> {
+    let entries = load_history(history_file).map_err(|e| {
+        color_eyre::eyre::eyre!("Failed to read history from {}: {}", history_file, e)
+    })?;
+
+    if entries.is_empty() {
+        let _ = writeln!(std::io::stdout(), "No recorded scans in {history_file}");
+        return Ok(());
+    }
+
+    print_history_table(&entries, labels);
+    Ok(())
+}
+
+/// Writes recorded migration progress over time as CSV or an HTML chart.
+///
+/// # Arguments
+///
+/// * `history_file` - Path to the JSON Lines history file
+/// * `chart` - Whether to render an HTML page instead of CSV
+/// * `output` - Where to write the report
+/// * `labels` - Display labels for the HTML status columns
+///
+/// # Errors
+///
+/// Returns an error if the history file cannot be read, is malformed or has
+/// no entries, or if writing fails.
+fn run_trend_report(
+    history_file: &Utf8Path,
+    chart: bool,
+    output: ReportOutput<'_>,
+    labels: &StatusLabels,
+) -> color_eyre::Result<()> {
+    let entries = load_history(history_file).map_err(|e| {
+        color_eyre::eyre::eyre!("Failed to read history from {}: {}", history_file, e)
+    })?;
+    let trend = Trend::from_entries(&entries);
+    if trend.is_empty() {
+        return Err(color_eyre::eyre::eyre!(
+            "No recorded scans in {history_file}; record some with `stats --record`"
+        ));
+    }
+    info!(path = %history_file, scans = trend.points.len(), "Generating trend report");
+
+    let content = if chart {
+        generate_trend_html(&trend, history_file, labels)
+    } else {
+        generate_trend_csv(&trend)
+    };
+    output.write(&content)
+}
+
+/// Returns the short hash of the commit checked out at `root`, if any.
+fn git_commit(root: &Utf8Path) -> Option<String> {
+    let output = toolchain::command("git", root)
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+
+    let commit = String::from_utf8(output.stdout).ok()?.trim().to_owned();
+    (!commit.is_empty()).then_some(commit)
+}
+
+/// Generates a migration report in the specified format.
+///
+/// # Arguments
+///
+/// * `config` - The application configuration
+/// * `format` - Output format (JSON, CSV, GitHub, HTML, Markdown, SARIF, XLSX,
+///   `MessagePack`, diff or PR comment)
+/// * `output` - Where to write the report
+/// * `baseline` - Previous JSON report used to find new legacy imports, or
+///   compared with by the diff and PR comment formats
+/// * `selection` - Filters narrowing which files the report includes
+/// * `sections` - Optional sections to include
+/// * `reporting` - Progress events and profiling requested for the scan
+///
+/// # Errors
+///
+/// Returns an error if scanning, reading the baseline, or writing fails, if
+/// a binary format is requested without an output file or XLSX is
+/// compressed, if the diff format has no baseline, or if `sections` asks for
+/// a section the format doesn't have.
+async fn run_report(
+    config: &Config,
+    format: ReportFormat,
+    output: ReportOutput<'_>,
+    baseline: Option<&Utf8PathBuf>,
+    selection: &ReportSelection,
+    sections: ReportSections<'_>,
+    reporting: ScanReporting,
+) -> color_eyre::Result<()> {
+    info!(app_path = %config.scan.app_path, "Generating report");
+    if format.is_binary() && output.path.is_none() {
+        return Err(color_eyre::eyre::eyre!(
+            "XLSX and msgpack reports are binary; write them to a file with --output"
+        ));
+    }
+    if output.gzip && matches!(format, ReportFormat::Xlsx) {
+        return Err(color_eyre::eyre::eyre!("XLSX workbooks are already compressed"));
+    }
+    if matches!(format, ReportFormat::Diff) && baseline.is_none() {
+        return Err(color_eyre::eyre::eyre!(
+            "--format diff needs a --baseline report to compare with"
+        ));
+    }
+    if sections.imports && !matches!(format, ReportFormat::Json | ReportFormat::Csv) {
+        return Err(color_eyre::eyre::eyre!(
+            "--with-imports supports the json and csv formats"
+        ));
+    }
+    if sections.directory_depth.is_some() && !matches!(format, ReportFormat::Json) {
+        return Err(color_eyre::eyre::eyre!("--dir-depth supports the json format"));
+    }
+    if !sections.columns.is_empty() && !matches!(format, ReportFormat::Csv) {
+        return Err(color_eyre::eyre::eyre!("--columns supports the csv format"));
+    }
+    if sections.csv_metadata && !matches!(format, ReportFormat::Csv) {
+        return Err(color_eyre::eyre::eyre!("--csv-metadata supports the csv format"));
+    }
+    if sections.top_models.is_some() && !matches!(format, ReportFormat::Mermaid) {
+        return Err(color_eyre::eyre::eyre!("--top supports the mermaid format"));
+    }
+
+    let scanner = create_scanner(config)?;
+    let started_at = Local::now();
+    let started = Instant::now();
+    let mut result = run_scanner(&scanner, reporting).await?;
+    let registry = build_registry(config)?;
+    let metadata = ReportMetadata::new(
+        config,
+        &scanner,
+        &registry,
+        selection,
+        started_at,
+        started.elapsed(),
+    );
+
+    let mut all_files = scanner.cache().all_files();
+    if !selection.is_empty() {
+        // Statistics describe the files in the report, and the work of the
+        // whole scan
+        let root = &config.scan.root_path;
+        all_files.retain(|file| selection.matches(file, root));
+        result.errors.retain(|(path, _)| selection.matches_unscanned(path, root));
+        result.skipped.retain(|skipped| selection.matches_unscanned(&skipped.path, root));
+        let mut stats = StatsSnapshot {
+            errors: u64::try_from(result.errors.len()).unwrap_or(u64::MAX),
+            reused: result.stats.reused,
+            parsed: std::mem::take(&mut result.stats.parsed),
+            skipped: SkipCounts::from_skipped(&result.skipped),
+            ..StatsSnapshot::default()
+        };
+        for file in &all_files {
+            stats.record(file.status);
+        }
+        result.stats = stats;
+        info!(files = all_files.len(), "Report narrowed by filters");
+    }
+    let with_owners = scanner.codeowners().is_some();
+    if sections.columns.contains(&CsvColumn::Owner) && !with_owners {
+        return Err(color_eyre::eyre::eyre!("--columns owner needs a --codeowners file"));
+    }
+    let teams = if with_owners {
+        rollup_by_owner(&all_files)
+    } else {
+        Vec::new()
+    };
+
+    let unmatched = unmatched_imports(&all_files, &registry);
+    if !unmatched.is_empty() {
+        info!(imports = unmatched.len(), "Imports match no registered export");
+    }
+    let errors = ReportError::from_scan(&result.errors);
+    let json_report = || {
+        JsonReport::new(
+            &metadata,
+            &result.stats,
+            &teams,
+            &all_files,
+            &unmatched,
+            &errors,
+            sections,
+        )
+    };
+
+    if let Some(template) = sections.template {
+        return output.write(render_template_report(template, &json_report())?);
+    }
+
+    let content = match format {
+        ReportFormat::Json => {
+            // Streamed, as reports of the whole monorepo run to hundreds of MB
+            let report = json_report();
+            return output.stream(|writer| write_json_report(writer, &report));
+        }
+        ReportFormat::Csv => {
+            let columns = if sections.columns.is_empty() {
+                CsvColumn::defaults(with_owners)
+            } else {
+                sections.columns.to_vec()
+            };
+            generate_csv_report(
+                sections.csv_metadata.then_some(&metadata),
+                &all_files,
+                &columns,
+                sections.imports,
+                &config.labels,
+                &metadata.app_path,
+            )
+        }
+        ReportFormat::Github => {
+            let baseline = baseline.map(|path| load_baseline(path)).transpose()?;
+            generate_github_report(
+                &metadata,
+                &result.stats,
+                &teams,
+                &all_files,
+                baseline.as_ref(),
+                &config.labels,
+            )?
+        }
+        ReportFormat::Html => generate_html_report(
+            &metadata,
+            &result.stats,
+            &teams,
+            &all_files,
+            &unmatched,
+            with_owners,
+            &config.labels,
+        ),
+        ReportFormat::Markdown => generate_markdown_report(
+            &metadata,
+            &result.stats,
+            &teams,
+            &all_files,
+            &unmatched,
+            &config.labels,
+        ),
+        ReportFormat::Sarif => generate_sarif_report(&metadata, &all_files)?,
+        ReportFormat::Dot => {
+            let models = rollup_by_model(&all_files, &registry);
+            generate_dot_report(&metadata, &all_files, &models, false, &config.labels)
+        }
+        ReportFormat::Mermaid => {
+            let models = rollup_by_model(&all_files, &registry);
+            let top = sections.top_models.unwrap_or(MERMAID_TOP_MODELS);
+            generate_mermaid_report(&metadata, &models, top)
+        }
+        ReportFormat::Diff => {
+            let Some(baseline) = baseline else {
+                return Err(color_eyre::eyre::eyre!("--format diff needs a --baseline report"));
+            };
+            let (diff, previous_stats) =
+                diff_baseline(baseline, selection, &metadata.root_path, &all_files)?;
+            generate_diff_html(
+                &metadata,
+                baseline,
+                &diff,
+                &previous_stats,
+                &result.stats,
+                &config.labels,
+            )
+        }
+        ReportFormat::PrComment => {
+            let previous = baseline
+                .map(|path| diff_baseline(path, selection, &metadata.root_path, &all_files))
+                .transpose()?;
+            generate_pr_comment(
+                &metadata,
+                &result.stats,
+                previous.as_ref().map(|(diff, stats)| (diff, stats)),
+                &config.labels,
+            )
+        }
+        ReportFormat::Xlsx => {
+            let workbook = generate_xlsx_report(
+                &metadata,
+                &result.stats,
+                &all_files,
+                with_owners,
+                &config.labels,
+            )?;
+            return output.write(&workbook);
+        }
+        ReportFormat::Msgpack => {
+            let report =
+                generate_binary_report(&metadata, &result.stats, &teams, &all_files, &errors)?;
+            return output.write(&report);
+        }
+        ReportFormat::Trend => {
+            return Err(color_eyre::eyre::eyre!(
+                "--format trend reads the history file instead of scanning"
+            ));
+        }
+    };
+
+    output.write(&content)
+}
+
+/// Converts a binary report to JSON or CSV.
+///
+/// The output matches what `report --format json` or `--format csv` would
+/// have produced for the same scan.
+///
+/// # Arguments
+///
+/// * `input` - Binary report to read
+/// * `format` - Output format
+/// * `output` - Where to write the report
+/// * `labels` - Display labels for the CSV `status` column
+///
+/// # Errors
+///
+/// Returns an error if the report cannot be read, or has an unsupported
+/// schema version, or if writing fails.
+fn run_convert_report(
+    input: &Utf8Path,
+    format: ConvertFormat,
+    output: ReportOutput<'_>,
+    labels: &StatusLabels,
+) -> color_eyre::Result<()> {
+    let report = load_binary_report(input)?;
+    info!(path = %input, files = report.files.len(), "Converting binary report");
+
+    let content = match format {
+        ConvertFormat::Json => {
+            let report = JsonReport::new(
+                &report.metadata,
+                &report.stats,
+                &report.teams,
+                &report.files,
+                &[],
+                &report.errors,
+                ReportSections::default(),
+            );
+            return output.stream(|writer| write_json_report(writer, &report));
+        }
+        ConvertFormat::Csv => generate_csv_report(
+            None,
+            &report.files,
+            &CsvColumn::defaults(report.metadata.filters.codeowners.is_some()),
+            false,
+            labels,
+            &report.metadata.app_path,
+        ),
+    };
+    output.write(&content)
+}
+
+/// Generates a report listing each legacy model's consumer files.
+///
+/// # Arguments
+///
+/// * `config` - The application configuration
+/// * `format` - Output format (JSON, CSV, Markdown or DOT)
+/// * `output` - Where to write the report
+/// * `selection` - Filters narrowing which files count as consumers
+/// * `csv_metadata` - Whether CSV reports start with the metadata
+/// * `reporting` - Progress events and profiling requested for the scan
+///
+/// # Errors
+///
+/// Returns an error if the format has no model view, or if scanning,
+/// building the registry, or writing fails.
+async fn run_model_report(
+    config: &Config,
+    format: ReportFormat,
+    output: ReportOutput<'_>,
+    selection: &ReportSelection,
+    csv_metadata: bool,
+    reporting: ScanReporting,
+) -> color_eyre::Result<()> {
+    if csv_metadata && !matches!(format, ReportFormat::Csv) {
+        return Err(color_eyre::eyre::eyre!("--csv-metadata supports the csv format"));
+    }
+    if !matches!(
+        format,
+        ReportFormat::Json | ReportFormat::Csv | ReportFormat::Markdown | ReportFormat::Dot
+    ) {
+        return Err(color_eyre::eyre::eyre!(
+            "--by-model supports the json, csv, md and dot formats"
+        ));
+    }
+    info!(app_path = %config.scan.app_path, "Generating model report");
+
+    let scanner = create_scanner(config)?;
+    let started_at = Local::now();
+    let started = Instant::now();
+    run_scanner(&scanner, reporting).await?;
+    let registry = build_registry(config)?;
+    let metadata = ReportMetadata::new(
+        config,
+        &scanner,
+        &registry,
+        selection,
+        started_at,
+        started.elapsed(),
+    );
+
+    let mut all_files = scanner.cache().all_files();
+    if !selection.is_empty() {
+        all_files.retain(|file| selection.matches(file, &config.scan.root_path));
+    }
+    let models = rollup_by_model(&all_files, &registry);
+    info!(models = models.len(), "Grouped legacy imports by model");
+
+    let content = match format {
+        ReportFormat::Csv => {
+            generate_csv_model_report(csv_metadata.then_some(&metadata), &models)
+        }
+        ReportFormat::Markdown => generate_markdown_model_report(&metadata, &models),
+        ReportFormat::Dot => {
+            generate_dot_report(&metadata, &all_files, &models, true, &config.labels)
+        }
+        _ => generate_json_model_report(&metadata, &models)?,
+    };
+    output.write(&content)
+}
+
+/// Exports the model registry in the specified format.
+///
+/// # Arguments
+///
+/// * `config` - The application configuration
+/// * `format` - Output format (JSON or CSV)
+/// * `output` - Output file path (stdout if None)
+///
+/// # Errors
+///
+/// Returns an error if building the registry or writing fails.
+fn run_export_registry(
+    config: &Config,
+    format: RegistryFormat,
+    output: Option<&Utf8PathBuf>,
+) -> color_eyre::Result<()> {
+    info!(
+        shared = %config.scan.shared_path,
+        shared_2023 = %config.scan.shared_2023_path,
+        "Exporting model registry"
+    );
+
+    let registry = build_registry(config)?;
+
+    let content = match format {
+        RegistryFormat::Json => generate_json_registry(&registry)?,
+        RegistryFormat::Csv => generate_csv_registry(&registry),
+    };
+
+    write_output(&content, output)?;
+    Ok(())
+}
+
+/// Renders a migration progress badge as SVG.
+///
+/// # Arguments
+///
+/// * `config` - The application configuration
+/// * `label` - Text on the left side of the badge
+/// * `output` - Output file path (stdout if None)
+/// * `reporting` - Progress events and profiling requested for the scan
+///
+/// # Errors
+///
+/// Returns an error if scanning or writing fails.
+async fn run_badge(
+    config: &Config,
+    label: &str,
+    output: Option<&Utf8PathBuf>,
+    reporting: ScanReporting,
+) -> color_eyre::Result<()> {
+    info!(app_path = %config.scan.app_path, "Generating badge");
+
+    let scanner = create_scanner(config)?;
+    let result = run_scanner(&scanner, reporting).await?;
+
+    let svg = generate_badge_svg(label, result.stats.progress_percent());
+    write_output(&svg, output)?;
+    Ok(())
+}
+
+/// Compares legacy and modern export sets and reports missing exports.
+///
+/// # Arguments
+///
+/// * `config` - The application configuration
+/// * `format` - Output format
+/// * `output` - Output file path (stdout if None)
+///
+/// # Errors
+///
+/// Returns an error if the registry cannot be built, writing fails, or any
+/// ported model is missing exports.
+fn run_validate_parity(
+    config: &Config,
+    format: ParityFormat,
+    output: Option<&Utf8PathBuf>,
+) -> color_eyre::Result<()> {
+    info!(
+        shared = %config.scan.shared_path,
+        shared_2023 = %config.scan.shared_2023_path,
+        "Validating export parity"
+    );
+
+    let registry = build_registry(config)?;
+    let report = check_parity(&registry);
+
+    let content = match format {
+        ParityFormat::Text => generate_parity_text(&report),
+        ParityFormat::Json => serde_json::to_string_pretty(&report)?,
+    };
+    write_output(&content, output)?;
+
+    if report.is_complete() {
+        Ok(())
+    } else {
+        Err(color_eyre::eyre::eyre!(
+            "{} ported models are missing {} exports",
+            report.gaps.len(),
+            report.missing_export_count()
+        ))
+    }
+}
+
+/// Scans the codebase and proposes a migration order.
+///
+/// # Arguments
+///
+/// * `config` - The application configuration
+/// * `format` - Output format
+/// * `output` - Output file path (stdout if None)
+/// * `reporting` - Progress events and profiling requested for the scan
+///
+/// # Errors
+///
+/// Returns an error if scanning, building the registry, or writing fails.
+async fn run_plan(
+    config: &Config,
+    format: PlanFormat,
+    output: Option<&Utf8PathBuf>,
+    reporting: ScanReporting,
+) -> color_eyre::Result<()> {
+    info!(app_path = %config.scan.app_path, "Planning migration");
+
+    let scanner = create_scanner(config)?;
+    run_scanner(&scanner, reporting).await?;
+    let registry = build_registry(config)?;
+
+    let plan = plan_migration(&scanner.cache().all_files(), &registry);
+    info!(
+        waves = plan.waves.len(),
+        blocked = plan.blocked.len(),
+        "Planned migration"
+    );
+
+    let content = match format {
+        PlanFormat::Text => generate_plan_text(&plan),
+        PlanFormat::Json => serde_json::to_string_pretty(&plan)?,
+    };
+    write_output(&content, output)
+}
+
+/// Scans the codebase and reports the impact of porting a legacy model.
+///
+/// # Arguments
+///
+/// * `config` - The application configuration
+/// * `model` - Legacy model name
+/// * `format` - Output format
+/// * `output` - Output file path (stdout if None)
+/// * `reporting` - Progress events and profiling requested for the scan
+///
+/// # Errors
+///
+/// Returns an error if scanning, building the registry, or writing fails, or
+/// if no legacy model or import has that name.
+async fn run_impact(
+    config: &Config,
+    model: &str,
+    format: PlanFormat,
+    output: Option<&Utf8PathBuf>,
+    reporting: ScanReporting,
+) -> color_eyre::Result<()> {
+    info!(app_path = %config.scan.app_path, model, "Analyzing model impact");
+
+    let scanner = create_scanner(config)?;
+    run_scanner(&scanner, reporting).await?;
+    let registry = build_registry(config)?;
+
+    let impact = model_impact(&scanner.cache().all_files(), &registry, model);
+    if impact.consumers == 0 && registry.get_legacy_model(model).is_none() {
+        return Err(color_eyre::eyre::eyre!("Unknown legacy model '{model}'"));
+    }
+    info!(
+        consumers = impact.consumers,
+        changes = impact.changes.len(),
+        "Analyzed model impact"
+    );
+
+    let content = match format {
+        PlanFormat::Text => generate_impact_text(&impact, &config.labels),
+        PlanFormat::Json => serde_json::to_string_pretty(&impact)?,
+    };
+    write_output(&content, output)
+}
+
+/// Lists imports whose module path contains `fragment`.
+///
+/// # Arguments
+///
+/// * `config` - The application configuration
+/// * `fragment` - Fragment of the import path to search for
+/// * `reporting` - Progress events and profiling requested for the scan
+///
+/// # Errors
+///
+/// Returns an error if scanning or writing fails, or if no import matches.
+async fn run_grep_import(
+    config: &Config,
+    fragment: &str,
+    reporting: ScanReporting,
+) -> color_eyre::Result<()> {
+    info!(app_path = %config.scan.app_path, fragment, "Searching imports");
+
+    let scanner = create_scanner(config)?;
+    run_scanner(&scanner, reporting).await?;
+
+    let found = scanner.cache().find_imports(fragment);
+    if found.is_empty() {
+        return Err(color_eyre::eyre::eyre!("No imports match '{fragment}'"));
+    }
 
-    Ok(())
+    write_output(generate_import_matches(&found), None)
 }
 
-/// Runs the interactive TUI with optional file watching.
+/// Timings collected by `selftest`.
+struct SelftestTimings {
+    /// Number of generated files.
+    files: usize,
+    /// Number of files scanned (generated files plus shared models).
+    scanned: usize,
+    /// Time spent generating the repository.
+    generate: Duration,
+    /// Full scan time.
+    scan: Duration,
+    /// Streaming scan time.
+    streaming: Duration,
+    /// Number of files modified in the watch burst.
+    burst_files: usize,
+    /// Time to modify and re-scan the burst files.
+    burst: Duration,
+    /// Peak resident memory, if the platform reports it.
+    peak_memory: Option<u64>,
+}
+
+/// Measures scan performance on a generated repository.
 ///
 /// # Arguments
 ///
-/// * `config` - The application configuration
-/// * `no_watch` - Whether to disable file watching
+/// * `files` - Number of TypeScript files to generate
+/// * `keep` - Whether to keep the generated repository
 ///
 /// # Errors
 ///
-/// Returns an error if the TUI fails.
-async fn run_watch(config: Config, no_watch: bool) -> color_eyre::Result<()> {
-    info!(app_path = %config.scan.app_path, watch = !no_watch, "Starting TUI");
+/// Returns an error if the repository cannot be generated or a scan fails.
+async fn run_selftest(files: usize, keep: bool) -> color_eyre::Result<()> {
+    let dir = tempfile::Builder::new()
+        .prefix("ch-migrate-selftest-")
+        .tempdir()
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to create self-test directory: {}", e))?;
+    let root = Utf8PathBuf::from_path_buf(dir.path().to_owned())
+        .map_err(|p| color_eyre::eyre::eyre!("Temporary directory is not UTF-8: {}", p.display()))?;
+    info!(root = %root, files, "Generating self-test repository");
+
+    let result = selftest_in(&root, files).await;
+
+    if keep {
+        let _ = dir.keep();
+        info!(root = %root, "Keeping self-test repository");
+    } else if let Err(e) = spawn_io(move || dir.close()).await {
+        warn!(root = %root, error = %e, "Failed to remove self-test repository");
+    }
 
-    let scanner = create_scanner(&config)?;
+    write_output(generate_selftest_text(&result?), None)
+}
 
-    let mut config = config;
-    config.watch.enabled = !no_watch;
+/// Generates a repository of `files` files under `root` and times scans of it.
+async fn selftest_in(root: &Utf8Path, files: usize) -> color_eyre::Result<SelftestTimings> {
+    let started = Instant::now();
+    let repo = root.to_owned();
+    let paths = spawn_io(move || generate_selftest_repo(&repo, files)).await?;
+    let generate = started.elapsed();
+
+    let app = root.join("app");
+    let new_scanner = || {
+        Scanner::new(ScannerConfig::new(&app))
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to create scanner: {}", e))
+    };
 
-    // Handle SIGTERM for graceful shutdown on Unix
-    #[cfg(unix)]
-    {
-        use tokio::signal::unix::{signal, SignalKind};
+    let scanner = new_scanner()?;
+    let started = Instant::now();
+    let result = spawn_scan(&scanner, Scanner::scan).await??;
+    let scan = started.elapsed();
+    let scanned = usize::try_from(result.stats.total).unwrap_or(usize::MAX);
+
+    let started = Instant::now();
+    scan_streaming_to_end(&new_scanner()?).await?;
+    let streaming = started.elapsed();
+
+    // A burst of saves, as a branch switch or formatter run would produce
+    let burst_paths: Vec<Utf8PathBuf> =
+        paths.iter().step_by(10).take(1000).cloned().collect();
+    let started = Instant::now();
+    let paths = burst_paths.clone();
+    spawn_io(move || {
+        for path in &paths {
+            let mut file = std::fs::OpenOptions::new().append(true).open(path.as_std_path())?;
+            writeln!(file, "// touched by selftest")?;
+        }
+        Ok(())
+    })
+    .await?;
+    let paths = burst_paths.clone();
+    let rescan = spawn_scan(&scanner, move |scanner| scanner.rescan_files(&paths)).await?;
+    let failed = rescan.outcomes.iter().filter(|(_, outcome)| outcome.is_err()).count();
+    let burst = started.elapsed();
+    if failed > 0 {
+        warn!(failed, "Self-test re-scan had errors");
+    }
 
-        let mut sigterm = signal(SignalKind::terminate())?;
+    Ok(SelftestTimings {
+        files,
+        scanned,
+        generate,
+        scan,
+        streaming,
+        burst_files: burst_paths.len(),
+        burst,
+        peak_memory: peak_memory_bytes(),
+    })
+}
 
-        tokio::select! {
-            result = ch_tui::run(config, scanner) => {
-                result.map_err(|e| color_eyre::eyre::eyre!("TUI error: {}", e))?;
-            }
-            _ = sigterm.recv() => {
-                info!("Received SIGTERM, shutting down");
-            }
+/// Writes a synthetic repository and returns the generated feature file paths.
+///
+/// Mirrors the `WebApp.Desktop/src` layout: models live in `app/shared` and
+/// `app/shared_2023`, and feature files under `app/features` import a mix of
+/// legacy models, modern models, both, or neither.
+fn generate_selftest_repo(root: &Utf8Path, files: usize) -> std::io::Result<Vec<Utf8PathBuf>> {
+    use std::fmt::Write;
+
+    let app = root.join("app");
+    for (dir, suffix) in [("shared", ""), ("shared_2023", "Dto")] {
+        let models = app.join(dir).join("models");
+        std::fs::create_dir_all(models.as_std_path())?;
+        for m in 0..SELFTEST_MODELS {
+            let contents = format!(
+                "export interface Model{m}{suffix} {{\n    id: number;\n    name: string;\n}}\n"
+            );
+            std::fs::write(models.join(format!("model-{m}.ts")).as_std_path(), contents)?;
         }
     }
 
-    #[cfg(not(unix))]
-    {
-        ch_tui::run(config, scanner)
-            .await
-            .map_err(|e| color_eyre::eyre::eyre!("TUI error: {}", e))?;
+    let mut paths = Vec::with_capacity(files);
+    for i in 0..files {
+        let dir = app.join("features").join(format!("feature-{}", i / SELFTEST_FILES_PER_DIR));
+        if i % SELFTEST_FILES_PER_DIR == 0 {
+            std::fs::create_dir_all(dir.as_std_path())?;
+        }
+
+        let m = i % SELFTEST_MODELS;
+        let mut contents = String::from("import { Component } from '@angular/core';\n");
+        let mut fields = String::new();
+        if i % 4 == 0 || i % 4 == 1 {
+            let _ = writeln!(
+                contents,
+                "import {{ Model{m} }} from '../../shared/models/model-{m}';"
+            );
+            let _ = writeln!(fields, "    legacy?: Model{m};");
+        }
+        if i % 4 == 1 || i % 4 == 2 {
+            let _ = writeln!(
+                contents,
+                "import {{ Model{m}Dto }} from '../../shared_2023/models/model-{m}';"
+            );
+            let _ = writeln!(fields, "    modern?: Model{m}Dto;");
+        }
+        let _ = write!(
+            contents,
+            "\n@Component({{ selector: 'feature-{i}', template: '' }})\n\
+             export class Feature{i}Component {{\n{fields}\n    \
+             count = {i};\n\n    \
+             increment(): number {{\n        return ++this.count;\n    }}\n}}\n"
+        );
+
+        let path = dir.join(format!("feature-{i}.component.ts"));
+        std::fs::write(path.as_std_path(), contents)?;
+        paths.push(path);
     }
 
+    Ok(paths)
+}
+
+/// Runs a streaming scan and drains its updates until it completes.
+///
+/// # Errors
+///
+/// Returns an error if scanning fails.
+async fn scan_streaming_to_end(scanner: &Scanner) -> color_eyre::Result<()> {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(scanner.channel_capacity());
+    let scan = spawn_scan(scanner, move |scanner| scanner.scan_streaming(tx));
+    while rx.recv().await.is_some() {}
+    scan.await??;
     Ok(())
 }
 
-/// Generates a migration report in the specified format.
+/// Returns the peak resident memory of this process, if the platform reports it.
+fn peak_memory_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// Writes man pages for the binary and its visible subcommands.
 ///
 /// # Arguments
 ///
-/// * `config` - The application configuration
-/// * `format` - Output format (JSON or CSV)
-/// * `output` - Output file path (stdout if None)
+/// * `out_dir` - Directory to write the `.1` files to
 ///
 /// # Errors
 ///
-/// Returns an error if scanning or writing fails.
-fn run_report(
-    config: &Config,
-    format: ReportFormat,
-    output: Option<Utf8PathBuf>,
-) -> color_eyre::Result<()> {
-    info!(app_path = %config.scan.app_path, "Generating report");
+/// Returns an error if the directory or a page cannot be written.
+fn run_mangen(out_dir: &Utf8Path) -> color_eyre::Result<()> {
+    std::fs::create_dir_all(out_dir.as_std_path())
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to create {}: {}", out_dir, e))?;
+    clap_mangen::generate_to(Cli::command(), out_dir.as_std_path())
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to write man pages to {}: {}", out_dir, e))?;
+
+    info!(out_dir = %out_dir, "Man pages written");
+    Ok(())
+}
 
-    let scanner = create_scanner(config)?;
-    let result = scanner.scan()?;
+// =============================================================================
+// OUTPUT HELPERS
+// =============================================================================
+
+/// Where a report is written: a file or stdout, optionally gzip-compressed.
+#[derive(Clone, Copy)]
+struct ReportOutput<'a> {
+    /// Output file (stdout if None).
+    path: Option<&'a Utf8PathBuf>,
+    /// Whether to gzip the report.
+    gzip: bool,
+}
 
-    let all_files = scanner.cache().all_files();
+impl<'a> ReportOutput<'a> {
+    /// Creates a destination that is gzip-compressed when `compress` is set
+    /// or the output file name ends in `.gz`.
+    fn new(path: Option<&'a Utf8PathBuf>, compress: bool) -> Self {
+        Self {
+            path,
+            gzip: compress || path.is_some_and(|path| path.extension() == Some("gz")),
+        }
+    }
 
-    let content = match format {
-        ReportFormat::Json => generate_json_report(&result.stats, &all_files)?,
-        ReportFormat::Csv => generate_csv_report(&all_files),
-    };
+    /// Writes generated content to the destination.
+    fn write(self, content: impl AsRef<[u8]>) -> color_eyre::Result<()> {
+        self.stream(|writer| Ok(writer.write_all(content.as_ref())?))
+    }
+
+    /// Streams a report to the destination through `write`, compressing it
+    /// on the fly if needed, so neither the report nor its compressed form
+    /// is held in memory.
+    fn stream(
+        self,
+        write: impl FnOnce(&mut dyn Write) -> color_eyre::Result<()>,
+    ) -> color_eyre::Result<()> {
+        let sink: Box<dyn Write> = match self.path {
+            Some(path) => Box::new(BufWriter::new(std::fs::File::create(path.as_std_path())?)),
+            None => Box::new(BufWriter::new(std::io::stdout().lock())),
+        };
+        if self.gzip {
+            let mut encoder = GzEncoder::new(sink, Compression::default());
+            write(&mut encoder)?;
+            encoder.finish()?.flush()?;
+        } else {
+            let mut sink = sink;
+            write(&mut sink)?;
+            sink.flush()?;
+        }
+        if let Some(path) = self.path {
+            info!(path = %path, compressed = self.gzip, "Output written");
+        }
+        Ok(())
+    }
+}
+
+/// Reads a report file, decompressing it if it is gzipped.
+fn read_report(path: &Utf8Path) -> std::io::Result<Vec<u8>> {
+    let bytes = std::fs::read(path.as_std_path())?;
+    if !bytes.starts_with(&[0x1f, 0x8b]) {
+        return Ok(bytes);
+    }
+    let mut contents = Vec::new();
+    GzDecoder::new(bytes.as_slice()).read_to_end(&mut contents)?;
+    Ok(contents)
+}
 
+/// Writes generated output to a file, or to stdout if no path is given.
+fn write_output(
+    content: impl AsRef<[u8]>,
+    output: Option<&Utf8PathBuf>,
+) -> color_eyre::Result<()> {
     if let Some(output_path) = output {
-        std::fs::write(output_path.as_std_path(), &content)?;
-        info!(path = %output_path, "Report written");
+        std::fs::write(output_path.as_std_path(), content)?;
+        info!(path = %output_path, "Output written");
     } else {
         let stdout = std::io::stdout();
         let mut handle = stdout.lock();
-        write!(handle, "{content}")?;
+        handle.write_all(content.as_ref())?;
     }
-
     Ok(())
 }
 
-// =============================================================================
-// OUTPUT HELPERS
-// =============================================================================
-
 /// Prints a summary of scan statistics.
-fn print_stats_summary(stats: &StatsSnapshot) {
+fn print_stats_summary(stats: &StatsSnapshot, labels: &StatusLabels) {
     let stdout = std::io::stdout();
     let mut handle = stdout.lock();
 
@@ -403,18 +3044,228 @@ fn print_stats_summary(stats: &StatsSnapshot) {
     let _ = writeln!(handle, "========================");
     let _ = writeln!(handle);
     let _ = writeln!(handle, "Total files scanned: {}", stats.total);
-    let _ = writeln!(handle, "  Legacy:           {} (need migration)", stats.legacy);
-    let _ = writeln!(handle, "  Partial:          {} (in progress)", stats.partial);
-    let _ = writeln!(handle, "  Migrated:         {} (complete)", stats.migrated);
-    let _ = writeln!(handle, "  No models:        {} (no action needed)", stats.no_models);
-    let _ = writeln!(handle, "  Errors:           {}", stats.errors);
+    let rows = [
+        (MigrationStatus::Legacy, stats.legacy, " (need migration)"),
+        (MigrationStatus::Partial, stats.partial, " (in progress)"),
+        (MigrationStatus::Migrated, stats.migrated, " (complete)"),
+        (MigrationStatus::NoModels, stats.no_models, " (no action needed)"),
+    ];
+    for (status, count, note) in rows {
+        let label = format!("{}:", labels.get(status));
+        let _ = writeln!(handle, "  {label:<17} {count}{note}");
+    }
+    let _ = writeln!(handle, "  {:<17} {}", "Errors:", stats.errors);
+    if !stats.skipped.is_empty() {
+        let reasons: Vec<String> = SkipReason::ALL
+            .into_iter()
+            .filter(|&reason| stats.skipped.get(reason) > 0)
+            .map(|reason| format!("{} {}", stats.skipped.get(reason), reason.label()))
+            .collect();
+        let _ = writeln!(
+            handle,
+            "  {:<17} {} ({})",
+            "Skipped paths:",
+            stats.skipped.total(),
+            reasons.join(", ")
+        );
+    }
+    if stats.reused > 0 {
+        let _ = writeln!(
+            handle,
+            "  {:<17} {} (unchanged since the last scan)",
+            "Reused:", stats.reused
+        );
+    }
     let _ = writeln!(handle);
     let _ = writeln!(handle, "Migration progress: {:.1}%", stats.progress_percent());
     let _ = writeln!(handle, "Files needing work: {}", stats.needs_migration());
 }
 
+/// Prints the paths the walker left out, with the reason for each.
+fn print_skipped_paths(skipped: &[SkippedPath]) {
+    if skipped.is_empty() {
+        return;
+    }
+    let stdout = std::io::stdout();
+    let mut handle = stdout.lock();
+
+    let _ = writeln!(handle);
+    let _ = writeln!(handle, "Skipped paths ({}):", skipped.len());
+    for skip in skipped {
+        let _ = writeln!(handle, "  {} ({})", skip.path, skip.reason.label());
+    }
+}
+
+/// Prints grouped statistics as an aligned table with a total row.
+///
+/// # Arguments
+///
+/// * `label` - Header for the group column (e.g. "Directory", "Team")
+/// * `rows` - Group names with their statistics
+/// * `total` - Statistics for the total row
+/// * `labels` - Display labels for the status columns
+fn print_stats_table(
+    label: &str,
+    rows: &[(&str, &StatsSnapshot)],
+    total: &StatsSnapshot,
+    labels: &StatusLabels,
+) {
+    let stdout = std::io::stdout();
+    let mut handle = stdout.lock();
+
+    let width = rows
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0)
+        .max(label.len());
+    let columns = StatusColumns::new(labels);
+    let rule = "-".repeat(width + 25 + columns.width());
+
+    let _ = writeln!(
+        handle,
+        "{:<width$}  {:>7}  {}  {:>10}",
+        label,
+        "Files",
+        columns.header(),
+        "% Complete"
+    );
+    let _ = writeln!(handle, "{rule}");
+    for (name, stats) in rows {
+        write_stats_row(&mut handle, name, stats, width, &columns);
+    }
+    let _ = writeln!(handle, "{rule}");
+    write_stats_row(&mut handle, "Total", total, width, &columns);
+}
+
+/// Prints recorded scans as a table with the change in progress between scans.
+fn print_history_table(entries: &[HistoryEntry], labels: &StatusLabels) {
+    let stdout = std::io::stdout();
+    let mut handle = stdout.lock();
+    let columns = StatusColumns::new(labels);
+
+    let _ = writeln!(
+        handle,
+        "{:<16}  {:<10}  {:>7}  {}  {:>10}  {:>8}",
+        "Recorded",
+        "Commit",
+        "Files",
+        columns.header(),
+        "% Complete",
+        "Change"
+    );
+    let _ = writeln!(handle, "{}", "-".repeat(67 + columns.width()));
+
+    let mut previous: Option<f64> = None;
+    for entry in entries {
+        let recorded = recorded_time(entry.recorded_at).map_or_else(
+            || entry.recorded_at.to_string(),
+            |time| time.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string(),
+        );
+        let progress = entry.stats.progress_percent();
+        let change = previous.map_or_else(String::new, |prev| format!("{:+.1}", progress - prev));
+        previous = Some(progress);
+
+        let _ = writeln!(
+            handle,
+            "{:<16}  {:<10}  {:>7}  {}  {:>9.1}%  {:>8}",
+            recorded,
+            entry.commit.as_deref().unwrap_or("-"),
+            entry.stats.total,
+            columns.counts(&entry.stats),
+            progress,
+            change
+        );
+    }
+}
+
+/// Converts a history timestamp (Unix seconds) to a date and time.
+fn recorded_time(recorded_at: u64) -> Option<DateTime<Utc>> {
+    i64::try_from(recorded_at)
+        .ok()
+        .and_then(|secs| DateTime::from_timestamp(secs, 0))
+}
+
+/// Converts per-team statistics into table rows.
+fn owner_rows(teams: &[OwnerStats]) -> Vec<(&str, &StatsSnapshot)> {
+    teams
+        .iter()
+        .map(|team| (team.owner.as_str(), &team.stats))
+        .collect()
+}
+
+/// Writes a single row of a grouped statistics table.
+fn write_stats_row(
+    handle: &mut impl Write,
+    label: &str,
+    stats: &StatsSnapshot,
+    width: usize,
+    columns: &StatusColumns<'_>,
+) {
+    let _ = writeln!(
+        handle,
+        "{:<width$}  {:>7}  {}  {:>9.1}%",
+        label,
+        stats.total,
+        columns.counts(stats),
+        stats.progress_percent()
+    );
+}
+
+/// The Legacy, Partial and Migrated columns of a statistics table.
+///
+/// Each column is at least as wide as its default header so custom labels
+/// keep the counts aligned.
+struct StatusColumns<'a> {
+    labels: [&'a str; 3],
+    widths: [usize; 3],
+}
+
+impl<'a> StatusColumns<'a> {
+    /// Statuses shown as columns, in display order.
+    const STATUSES: [MigrationStatus; 3] = [
+        MigrationStatus::Legacy,
+        MigrationStatus::Partial,
+        MigrationStatus::Migrated,
+    ];
+
+    fn new(labels: &'a StatusLabels) -> Self {
+        let labels = Self::STATUSES.map(|status| labels.get(status));
+        let widths = [7, 7, 8];
+        Self {
+            widths: std::array::from_fn(|i| widths[i].max(labels[i].chars().count())),
+            labels,
+        }
+    }
+
+    /// Total width of the columns, including the separators between them.
+    fn width(&self) -> usize {
+        self.widths.iter().sum::<usize>() + 2 * (self.widths.len() - 1)
+    }
+
+    /// Formats the column headers.
+    fn header(&self) -> String {
+        let [legacy, partial, migrated] = self.labels;
+        let [lw, pw, mw] = self.widths;
+        format!("{legacy:>lw$}  {partial:>pw$}  {migrated:>mw$}")
+    }
+
+    /// Formats the counts for a row.
+    fn counts(&self, stats: &StatsSnapshot) -> String {
+        let [lw, pw, mw] = self.widths;
+        format!(
+            "{:>lw$}  {:>pw$}  {:>mw$}",
+            stats.legacy, stats.partial, stats.migrated
+        )
+    }
+}
+
 /// Prints a detailed list of files needing migration.
-fn print_detailed_file_list(scanner: &Scanner) {
+///
+/// Each file lists its legacy imports that need a different name in
+/// `shared_2023/` or have no equivalent there; files with missing
+/// equivalents are listed again as blocked.
+fn print_detailed_file_list(scanner: &Scanner, registry: &ModelRegistry, labels: &StatusLabels) {
     let stdout = std::io::stdout();
     let mut handle = stdout.lock();
 
@@ -423,67 +3274,241 @@ fn print_detailed_file_list(scanner: &Scanner) {
 
     if !legacy_files.is_empty() {
         let _ = writeln!(handle);
-        let _ = writeln!(handle, "Legacy files ({}):", legacy_files.len());
+        let _ = writeln!(
+            handle,
+            "{} files ({}):",
+            labels.get(MigrationStatus::Legacy),
+            legacy_files.len()
+        );
         for file in &legacy_files {
-            let _ = writeln!(handle, "  {}", file.path);
+            write_file_suggestions(&mut handle, file, registry);
         }
     }
 
     if !partial_files.is_empty() {
         let _ = writeln!(handle);
-        let _ = writeln!(handle, "Partial files ({}):", partial_files.len());
+        let _ = writeln!(
+            handle,
+            "{} files ({}):",
+            labels.get(MigrationStatus::Partial),
+            partial_files.len()
+        );
         for file in &partial_files {
-            let _ = writeln!(handle, "  {}", file.path);
+            write_file_suggestions(&mut handle, file, registry);
+        }
+    }
+
+    let blocked: Vec<_> = legacy_files
+        .iter()
+        .chain(&partial_files)
+        .map(|file| (file, registry.missing_equivalents(file)))
+        .filter(|(_, missing)| !missing.is_empty())
+        .collect();
+    if !blocked.is_empty() {
+        let _ = writeln!(handle);
+        let _ = writeln!(handle, "Blocked files ({}):", blocked.len());
+        for (file, missing) in blocked {
+            let _ = writeln!(handle, "  {} (missing: {})", file.path, missing.join(", "));
+        }
+    }
+
+    let dead: Vec<_> = legacy_files
+        .iter()
+        .chain(&partial_files)
+        .map(|file| (file, file.dead_legacy_imports()))
+        .filter(|(_, unused)| !unused.is_empty())
+        .collect();
+    if !dead.is_empty() {
+        let _ = writeln!(handle);
+        let _ = writeln!(handle, "Dead legacy imports ({}):", dead.len());
+        for (file, unused) in dead {
+            let _ = writeln!(handle, "  {} (unused: {})", file.path, unused.join(", "));
         }
     }
 }
 
-/// Generates a JSON report.
-fn generate_json_report(
-    stats: &StatsSnapshot,
-    files: &[FileInfo],
-) -> color_eyre::Result<String> {
-    #[derive(serde::Serialize)]
-    struct Report<'a> {
-        stats: &'a StatsSnapshot,
-        files: &'a [FileInfo],
+/// Writes a file path followed by its legacy imports that need attention.
+///
+/// Imports whose modern export has the same name are omitted.
+fn write_file_suggestions(handle: &mut impl Write, file: &FileInfo, registry: &ModelRegistry) {
+    let _ = writeln!(handle, "  {}", file.path);
+    for name in file.legacy_imports().flat_map(|import| import.names.iter()) {
+        match registry.modern_name_for(name) {
+            Some(modern) if modern == *name => {}
+            Some(modern) => {
+                let _ = writeln!(handle, "      {name} -> {modern}");
+            }
+            None => {
+                let _ = writeln!(handle, "      {name} (no shared_2023 equivalent)");
+            }
+        }
     }
+}
 
-    let report = Report { stats, files };
-    serde_json::to_string_pretty(&report)
-        .map_err(|e| color_eyre::eyre::eyre!("Failed to serialize JSON: {}", e))
+/// Writes the JSON Schema of the JSON report.
+///
+/// # Errors
+///
+/// Returns an error if `format` isn't JSON, or if writing fails.
+fn run_print_schema(format: ReportFormat, output: Option<&Utf8PathBuf>) -> color_eyre::Result<()> {
+    if !matches!(format, ReportFormat::Json) {
+        return Err(color_eyre::eyre::eyre!(
+            "--print-schema only describes JSON reports; drop --format"
+        ));
+    }
+    write_output(JSON_REPORT_SCHEMA, output)
 }
 
-/// Generates a CSV report.
-fn generate_csv_report(files: &[FileInfo]) -> String {
+/// Generates the `--profile` timing breakdown, followed by the files parsed
+/// by kind and size.
+fn generate_profile_text(profile: &ScanProfile, parsed: &ParseCounts, slowest: usize) -> String {
     use std::fmt::Write;
 
-    let mut output = String::from("path,status,import_count,legacy_imports,migrated_imports\n");
-
-    for file in files {
-        let legacy_count = file.legacy_imports().count();
-        let migrated_count = file.migrated_imports().count();
-        let escaped_path = escape_csv(file.path.as_str());
-        let status = file.status.label();
-        let import_count = file.import_count();
+    let mut out = String::new();
+    let _ = writeln!(out, "Scan profile");
+    let _ = writeln!(out, "============");
+    let registry = profile
+        .registry
+        .map_or_else(|| "not built".to_owned(), format_millis);
+    let _ = writeln!(out, "  {:<18}{registry:>12}", "Registry build");
+    for (phase, duration) in [
+        ("Directory walk", profile.walk),
+        ("Parsing", profile.parse),
+        ("Cache insertion", profile.cache_insert),
+        ("Total", profile.total()),
+    ] {
+        let _ = writeln!(out, "  {phase:<18}{:>12}", format_millis(duration));
+    }
 
-        // Use write! to avoid extra allocation from format!
+    if !parsed.is_empty() {
+        let _ = writeln!(out);
+        #[allow(clippy::cast_precision_loss)] // Acceptable for display
+        let bytes = parsed.bytes as f64;
+        let rate = bytes / profile.parse.as_secs_f64().max(f64::EPSILON);
         let _ = writeln!(
-            output,
-            "{escaped_path},{status},{import_count},{legacy_count},{migrated_count}"
+            out,
+            "Parsed {} files, {} ({}/s):",
+            parsed.files(),
+            format_bytes(bytes),
+            format_bytes(rate)
         );
+        for kind in FileKind::ALL {
+            let _ = writeln!(out, "  {:<18}{:>12}", kind.label(), parsed.kind(kind));
+        }
+        for bucket in SizeBucket::ALL {
+            let _ = writeln!(out, "  {:<18}{:>12}", bucket.label(), parsed.size(bucket));
+        }
     }
 
-    output
+    let files = profile.slowest(slowest);
+    if !files.is_empty() {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "Slowest {} files:", files.len());
+        for file in files {
+            let _ = writeln!(out, "  {:>12}  {}", format_millis(file.duration), file.path);
+        }
+    }
+    out
 }
 
-/// Escapes a string for CSV output.
-fn escape_csv(s: &str) -> String {
-    if s.contains(',') || s.contains('"') || s.contains('\n') {
-        format!("\"{}\"", s.replace('"', "\"\""))
+/// Formats a size in bytes with one decimal place, in KiB below a mebibyte.
+fn format_bytes(bytes: f64) -> String {
+    if bytes < 1024.0 * 1024.0 {
+        format!("{:.1} KiB", bytes / 1024.0)
     } else {
-        s.to_owned()
+        format!("{:.1} MiB", bytes / (1024.0 * 1024.0))
+    }
+}
+
+/// Formats a duration in milliseconds with one decimal place.
+fn format_millis(duration: Duration) -> String {
+    format!("{:.1} ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// A scan progress event, written to stderr as one JSON line.
+#[derive(Debug, Default, serde::Serialize)]
+struct ProgressEvent {
+    /// Files analyzed so far (including files that failed to parse).
+    scanned: usize,
+    /// Files found by the directory walk.
+    total: usize,
+    /// Analysis rate in files per second.
+    rate: usize,
+    /// Estimated seconds until the scan completes, once known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    eta: Option<u64>,
+}
+
+/// Writes a progress event to stderr.
+fn emit_progress(event: &ProgressEvent) {
+    if let Ok(line) = serde_json::to_string(event) {
+        let _ = writeln!(std::io::stderr(), "{line}");
+    }
+}
+
+/// Formats matched imports as `file:line: module (names)` lines.
+fn generate_import_matches(found: &[(Utf8PathBuf, ImportInfo)]) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+    for (path, import) in found {
+        let module = import.path.trim_matches(|c| matches!(c, '\'' | '"' | '`'));
+        let _ = write!(output, "{path}:{}: {module}", import.location.line);
+        if !import.names.is_empty() {
+            let _ = write!(output, " ({})", import.names.join(", "));
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// Formats `selftest` timings.
+fn generate_selftest_text(timings: &SelftestTimings) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+    let title = format!(
+        "Self-test ({} generated files, {} scanned)",
+        timings.files, timings.scanned
+    );
+    let _ = writeln!(output, "{title}");
+    let _ = writeln!(output, "{}", "=".repeat(title.len()));
+    let _ = writeln!(output);
+    let _ = writeln!(output, "  {:<16} {:>10}", "Generate:", format_millis(timings.generate));
+    for (label, elapsed) in [("Full scan:", timings.scan), ("Streaming scan:", timings.streaming)] {
+        let _ = writeln!(
+            output,
+            "  {label:<16} {:>10}  ({} files/s)",
+            format_millis(elapsed),
+            files_per_second(timings.scanned, elapsed)
+        );
     }
+    let _ = writeln!(
+        output,
+        "  {:<16} {:>10}  ({} files)",
+        "Watch burst:",
+        format_millis(timings.burst),
+        timings.burst_files
+    );
+    #[allow(clippy::cast_precision_loss)] // Acceptable for display
+    let memory = timings.peak_memory.map_or_else(
+        || "unavailable".to_owned(),
+        |bytes| format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0)),
+    );
+    let _ = writeln!(output, "  {:<16} {memory:>10}", "Peak memory:");
+    let _ = writeln!(
+        output,
+        "\n{} CPUs, ch-migrate {}",
+        std::thread::available_parallelism().map_or(1, std::num::NonZero::get),
+        env!("CARGO_PKG_VERSION")
+    );
+    output
+}
+
+/// Returns the number of files per second for `files` analyzed in `elapsed`.
+fn files_per_second(files: usize, elapsed: Duration) -> usize {
+    let millis = usize::try_from(elapsed.as_millis()).unwrap_or(usize::MAX).max(1);
+    files.saturating_mul(1000) / millis
 }
 
 // =============================================================================
@@ -498,23 +3523,435 @@ async fn main() -> color_eyre::Result<()> {
 
     // 2. Parse CLI arguments
     let cli = Cli::parse();
+    let reporting = ScanReporting {
+        progress: cli.progress,
+        profile: cli.profile,
+    };
 
     // 3. Initialize tracing (handles --no-color for log output)
     init_tracing(cli.verbose, cli.no_color);
 
     // 5. Route to appropriate command
     match &cli.command {
-        Commands::Scan { detailed } => {
+        Commands::Scan {
+            detailed,
+            format,
+            fail_on,
+            baseline,
+            files,
+            stdin_paths,
+            files_from,
+            path_prefix,
+        } => {
+            let config = build_config(&cli, true)?;
+            let files = listed_files(files, *stdin_paths, files_from.as_deref())?;
+            let fail_on = fail_on.as_ref().map(|policy| FailGate {
+                policy,
+                baseline: baseline.as_deref(),
+            });
+            run_scan(
+                &config,
+                *detailed,
+                *format,
+                files.as_deref(),
+                path_prefix.as_deref(),
+                fail_on,
+                reporting,
+            ).await
+        }
+        Commands::Summary { by_dir, depth } => {
+            let config = build_config(&cli, true)?;
+            run_summary(&config, *by_dir, *depth, reporting).await
+        }
+        Commands::Watch {
+            no_watch,
+            view,
+            status_file,
+            watch_shared,
+            keys,
+            resume_session,
+        } => {
+            let mut config = build_config(&cli, false)?;
+            if status_file.is_some() {
+                config.watch.status_file.clone_from(status_file);
+            }
+            config.watch.shared |= *watch_shared;
+            if let Some(keys) = keys {
+                config.tui.keys.clone_from(keys);
+            }
+            let session = resume_session.as_deref().map(load_session).transpose()?;
+            run_watch(config, *no_watch, view.clone(), session).await
+        }
+        Commands::Stats {
+            history: true,
+            history_file,
+            ..
+        } => run_history(
+            history_file,
+            cli.status_labels.as_ref().unwrap_or(StatusLabels::standard()),
+        ),
+        Commands::Stats {
+            record,
+            history_file,
+            ..
+        } => {
+            let config = build_config(&cli, true)?;
+            run_stats(&config, *record, history_file, reporting).await
+        }
+        Commands::Report {
+            action: Some(ReportAction::Convert {
+                input,
+                format,
+                output,
+            }),
+            ..
+        } => {
+            let mut config = Config::default();
+            config.apply_env_overrides()?;
+            if let Some(labels) = &cli.status_labels {
+                config.labels.clone_from(labels);
+            }
+            run_convert_report(
+                input,
+                *format,
+                ReportOutput::new(output.as_ref(), false),
+                &config.labels,
+            )
+        }
+        Commands::Report {
+            action: None,
+            format,
+            output,
+            print_schema: true,
+            ..
+        } => run_print_schema(*format, output.as_ref()),
+        Commands::Report {
+            action: None,
+            format: ReportFormat::Trend,
+            output,
+            compress,
+            baseline,
+            status,
+            dir,
+            min_legacy_imports,
+            by_model,
+            with_imports,
+            columns,
+            csv_metadata,
+            template: _,
+            dir_depth,
+            top,
+            history_file,
+            chart,
+            print_schema: false,
+        } => {
+            let scan_options = baseline.is_some()
+                || !status.is_empty()
+                || !dir.is_empty()
+                || min_legacy_imports.is_some()
+                || *by_model
+                || *with_imports
+                || !columns.is_empty()
+                || *csv_metadata
+                || dir_depth.is_some()
+                || top.is_some();
+            if scan_options {
+                return Err(color_eyre::eyre::eyre!(
+                    "--format trend reads the history file; scan and filter options don't apply"
+                ));
+            }
+            run_trend_report(
+                history_file,
+                *chart,
+                ReportOutput::new(output.as_ref(), *compress),
+                cli.status_labels.as_ref().unwrap_or(StatusLabels::standard()),
+            )
+        }
+        Commands::Report {
+            action: None,
+            format,
+            output,
+            compress,
+            baseline,
+            status,
+            dir,
+            min_legacy_imports,
+            by_model,
+            with_imports,
+            columns,
+            csv_metadata,
+            template,
+            dir_depth,
+            top,
+            chart,
+            print_schema: false,
+            ..
+        } => {
+            if *chart {
+                return Err(color_eyre::eyre::eyre!("--chart supports the trend format"));
+            }
+            let output = ReportOutput::new(output.as_ref(), *compress);
+            let config = build_config(&cli, true)?;
+            let selection = ReportSelection {
+                status: status.clone(),
+                dirs: dir.clone(),
+                min_legacy_imports: *min_legacy_imports,
+            };
+            if *by_model {
+                return run_model_report(
+                    &config,
+                    *format,
+                    output,
+                    &selection,
+                    *csv_metadata,
+                    reporting,
+                ).await;
+            }
+            run_report(
+                &config,
+                *format,
+                output,
+                baseline.as_ref(),
+                &selection,
+                ReportSections {
+                    imports: *with_imports,
+                    directory_depth: dir_depth.map(usize::from),
+                    columns,
+                    csv_metadata: *csv_metadata,
+                    template: template.as_deref(),
+                    top_models: top.map(usize::from),
+                },
+                reporting,
+            ).await
+        }
+        Commands::ExportRegistry { format, output } => {
+            let config = build_config(&cli, true)?;
+            run_export_registry(&config, *format, output.as_ref())
+        }
+        Commands::Badge { label, output } => {
+            let config = build_config(&cli, true)?;
+            run_badge(&config, label, output.as_ref(), reporting).await
+        }
+        Commands::ValidateParity { format, output } => {
+            let config = build_config(&cli, true)?;
+            run_validate_parity(&config, *format, output.as_ref())
+        }
+        Commands::Plan { format, output } => {
             let config = build_config(&cli, true)?;
-            run_scan(&config, *detailed)
+            run_plan(&config, *format, output.as_ref(), reporting).await
         }
-        Commands::Watch { no_watch } => {
-            let config = build_config(&cli, false)?;
-            run_watch(config, *no_watch).await
+        Commands::Impact {
+            model,
+            format,
+            output,
+        } => {
+            let config = build_config(&cli, true)?;
+            run_impact(&config, model, *format, output.as_ref(), reporting).await
+        }
+        Commands::Maintenance {
+            history_file,
+            max_entries,
+            max_age_days,
+        } => {
+            let mut config = Config::default();
+            config.apply_env_overrides()?;
+            if max_entries.is_some() {
+                config.history.max_entries = *max_entries;
+            }
+            if max_age_days.is_some() {
+                config.history.max_age_days = *max_age_days;
+            }
+            run_maintenance(history_file, &HistoryRetention::from(&config.history))
         }
-        Commands::Report { format, output } => {
+        Commands::Selftest { files, keep } => run_selftest(*files, *keep).await,
+        Commands::Mangen { out_dir } => run_mangen(out_dir),
+        Commands::GrepImport { fragment } => {
             let config = build_config(&cli, true)?;
-            run_report(&config, *format, output.clone())
+            run_grep_import(&config, fragment, reporting).await
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the names of the test files `selection` includes.
+    fn selected<'a>(
+        selection: &ReportSelection,
+        files: &'a [FileInfo],
+        root: &Utf8Path,
+    ) -> Vec<&'a str> {
+        files
+            .iter()
+            .filter(|file| selection.matches(file, root))
+            .filter_map(|file| file.path.file_name())
+            .collect()
+    }
+
+    #[test]
+    fn test_report_selection_matches() {
+        let (files, _) = report::test_files();
+        let root = Utf8Path::new("/repo/src");
+        let all = ["job-list.ts", "job-edit.ts", "home.ts"];
+        assert_eq!(selected(&ReportSelection::default(), &files, root), all);
+
+        let status = ReportSelection {
+            status: vec![MigrationStatus::Legacy, MigrationStatus::Partial],
+            ..ReportSelection::default()
+        };
+        assert_eq!(selected(&status, &files, root), ["job-list.ts", "job-edit.ts"]);
+
+        let min_legacy = |min| ReportSelection {
+            min_legacy_imports: Some(min),
+            ..ReportSelection::default()
+        };
+        assert_eq!(selected(&min_legacy(1), &files, root), ["job-list.ts", "job-edit.ts"]);
+        assert!(selected(&min_legacy(2), &files, root).is_empty());
+
+        // Every filter must pass
+        let both = ReportSelection {
+            status: vec![MigrationStatus::Partial, MigrationStatus::Migrated],
+            dirs: vec![Utf8PathBuf::from("app/jobs")],
+            ..ReportSelection::default()
+        };
+        assert_eq!(selected(&both, &files, root), ["job-edit.ts"]);
+    }
+
+    #[test]
+    fn test_report_selection_dirs() {
+        let (files, _) = report::test_files();
+        let dirs = |dirs: &[&str]| ReportSelection {
+            dirs: dirs.iter().map(Utf8PathBuf::from).collect(),
+            ..ReportSelection::default()
+        };
+
+        // Relative to an absolute root, or as scanned
+        let root = Utf8Path::new("/repo/src");
+        let jobs = ["job-list.ts", "job-edit.ts"];
+        assert_eq!(selected(&dirs(&["app/jobs"]), &files, root), jobs);
+        assert_eq!(selected(&dirs(&["/repo/src/app/jobs"]), &files, root), jobs);
+        assert_eq!(selected(&dirs(&["app/jobs", "app/home.ts"]), &files, root).len(), 3);
+        assert!(selected(&dirs(&["app/job"]), &files, root).is_empty());
+
+        // Relative to a relative root
+        let relative: Vec<FileInfo> = files
+            .iter()
+            .map(|file| {
+                let mut file = file.clone();
+                file.path = file.path.strip_prefix("/repo").unwrap_or(&file.path).to_owned();
+                file
+            })
+            .collect();
+        let root = Utf8Path::new("src");
+        assert_eq!(selected(&dirs(&["app/jobs"]), &relative, root), jobs);
+        assert_eq!(selected(&dirs(&["src/app/jobs"]), &relative, root), jobs);
+        assert!(selected(&dirs(&["/repo/src/app/jobs"]), &relative, root).is_empty());
+    }
+
+    #[test]
+    fn test_report_selection_matches_unscanned() {
+        let root = Utf8Path::new("/repo/src");
+        let jobs = Utf8Path::new("/repo/src/app/jobs/bad.ts");
+        let home = Utf8Path::new("/repo/src/app/bad.ts");
+        assert!(ReportSelection::default().matches_unscanned(jobs, root));
+
+        let dir = ReportSelection {
+            dirs: vec![Utf8PathBuf::from("app/jobs")],
+            ..ReportSelection::default()
+        };
+        assert!(dir.matches_unscanned(jobs, root));
+        assert!(!dir.matches_unscanned(home, root));
+
+        // Unscanned files have no status or imports to filter on
+        let status = ReportSelection {
+            status: vec![MigrationStatus::Legacy],
+            ..ReportSelection::default()
+        };
+        assert!(!status.matches_unscanned(jobs, root));
+        let min_legacy = ReportSelection {
+            min_legacy_imports: Some(0),
+            ..ReportSelection::default()
+        };
+        assert!(!min_legacy.matches_unscanned(jobs, root));
+    }
+
+    /// Parses `--fail-on` rules.
+    fn rules(s: &str) -> Result<Vec<FailRule>, String> {
+        s.parse::<FailPolicy>().map(|policy| policy.rules)
+    }
+
+    const fn rule(condition: FailCondition, max: u64, severity: Severity) -> FailRule {
+        FailRule { condition, max, severity }
+    }
+
+    #[test]
+    fn test_fail_policy_parse_warn() {
+        let warn = Ok(vec![rule(FailCondition::Legacy, 0, Severity::Warning)]);
+        assert_eq!(rules("legacy:warn"), warn);
+        assert_eq!(rules("Legacy : Warning"), warn);
+        assert_eq!(rules("legacy"), Ok(vec![rule(FailCondition::Legacy, 0, Severity::Error)]));
+        assert_eq!(rules("legacy:error"), rules("legacy"));
+    }
+
+    #[test]
+    fn test_fail_policy_parse_mixed() {
+        assert_eq!(
+            rules("legacy=50:warn, partial,errors:error,blocked=3,regressions:warn,new-debt,"),
+            Ok(vec![
+                rule(FailCondition::Legacy, 50, Severity::Warning),
+                rule(FailCondition::Partial, 0, Severity::Error),
+                rule(FailCondition::Errors, 0, Severity::Error),
+                rule(FailCondition::Blocked, 3, Severity::Error),
+                rule(FailCondition::Regressions, 0, Severity::Warning),
+                rule(FailCondition::NewDebt, 0, Severity::Error),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_fail_policy_parse_invalid() {
+        assert!(rules("legacy:fatal").is_err());
+        assert!(rules("legacy:").is_err());
+        assert!(rules("legacy=many").is_err());
+        assert!(rules("legacy=-1").is_err());
+        assert!(rules("legacy=").is_err());
+        assert!(rules("stale").is_err());
+        assert!(rules("").is_err());
+        assert!(rules(" , ").is_err());
+    }
+
+    #[test]
+    fn test_fail_policy_parse_duplicates() {
+        assert!(rules("legacy,legacy=10").is_err());
+        assert!(rules("legacy:warn,legacy:warning").is_err());
+
+        // One condition can warn at one level and fail at another
+        assert_eq!(
+            rules("legacy:warn,legacy=200"),
+            Ok(vec![
+                rule(FailCondition::Legacy, 0, Severity::Warning),
+                rule(FailCondition::Legacy, 200, Severity::Error),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_fail_policy_check() {
+        let policy = |s: &str| s.parse::<FailPolicy>().unwrap_or_default();
+        let counts = FailCounts {
+            legacy: 20,
+            blocked: 2,
+            ..FailCounts::default()
+        };
+
+        assert!(policy("partial,errors,regressions").check(&counts).is_ok());
+        assert!(policy("legacy:warn,blocked:warn").check(&counts).is_ok());
+        assert!(policy("legacy=20,blocked=2").check(&counts).is_ok());
+        assert!(policy("legacy:warn,legacy=19").check(&counts).is_err());
+        assert!(policy("blocked").check(&counts).is_err());
+
+        let broken = policy("legacy=5:warn,blocked=1,errors").broken(&counts, Severity::Error);
+        assert_eq!(broken, ["blocked: 2 found, at most 1 allowed"]);
+    }
+}
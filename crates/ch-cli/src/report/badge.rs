@@ -0,0 +1,117 @@
+//! SVG progress badges for READMEs.
+
+use super::escape_xml;
+
+/// Generates a flat shields.io-style SVG badge for a progress percentage.
+///
+/// The percentage is rounded down so the badge only reads 100% once every
+/// file is migrated. Text widths are estimated, since the badge is rendered
+/// without font metrics.
+pub(crate) fn generate_badge_svg(label: &str, percent: f64) -> String {
+    use std::fmt::Write;
+
+    let value = format!("{:.0}%", percent.floor());
+    let color = badge_color(percent);
+    let label_width = badge_text_width(label);
+    let value_width = badge_text_width(&value);
+    let width = label_width + value_width;
+    let label = escape_xml(label);
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {value}">"#
+    );
+    let _ = writeln!(svg, "  <title>{label}: {value}</title>");
+    let _ = writeln!(
+        svg,
+        r##"  <linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient>"##
+    );
+    let _ = writeln!(
+        svg,
+        r##"  <clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath>"##
+    );
+    let _ = writeln!(svg, r#"  <g clip-path="url(#r)">"#);
+    let _ = writeln!(svg, r##"    <rect width="{label_width}" height="20" fill="#555"/>"##);
+    let _ = writeln!(
+        svg,
+        r#"    <rect x="{label_width}" width="{value_width}" height="20" fill="{color}"/>"#
+    );
+    let _ = writeln!(svg, r#"    <rect width="{width}" height="20" fill="url(#s)"/>"#);
+    let _ = writeln!(svg, "  </g>");
+    let _ = writeln!(
+        svg,
+        r##"  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">"##
+    );
+    for (text, x) in [
+        (label.as_str(), label_width / 2),
+        (value.as_str(), label_width + value_width / 2),
+    ] {
+        let _ = writeln!(
+            svg,
+            r##"    <text x="{x}" y="15" fill="#010101" fill-opacity=".3">{text}</text>"##
+        );
+        let _ = writeln!(svg, r#"    <text x="{x}" y="14">{text}</text>"#);
+    }
+    let _ = writeln!(svg, "  </g>");
+    let _ = writeln!(svg, "</svg>");
+    svg
+}
+
+/// Returns the badge color for a progress percentage.
+fn badge_color(percent: f64) -> &'static str {
+    match percent {
+        p if p >= 100.0 => "#4c1",
+        p if p >= 75.0 => "#97ca00",
+        p if p >= 50.0 => "#dfb317",
+        p if p >= 25.0 => "#fe7d37",
+        _ => "#e05d44",
+    }
+}
+
+/// Estimates the width in pixels of a badge segment for the given text.
+fn badge_text_width(text: &str) -> usize {
+    text.chars().count() * 7 + 10
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_badge_color_thresholds() {
+        assert_eq!(badge_color(0.0), "#e05d44");
+        assert_eq!(badge_color(24.9), "#e05d44");
+        assert_eq!(badge_color(25.0), "#fe7d37");
+        assert_eq!(badge_color(50.0), "#dfb317");
+        assert_eq!(badge_color(74.9), "#dfb317");
+        assert_eq!(badge_color(75.0), "#97ca00");
+        assert_eq!(badge_color(99.9), "#97ca00");
+        assert_eq!(badge_color(100.0), "#4c1");
+    }
+
+    #[test]
+    fn test_badge_text() {
+        let svg = generate_badge_svg("migration", 42.9);
+        assert!(svg.contains(r#"aria-label="migration: 42%""#));
+        assert!(svg.contains("<title>migration: 42%</title>"));
+        assert!(svg.contains(r#"<text x="36" y="14">migration</text>"#));
+        assert!(svg.contains(r#"<text x="88" y="14">42%</text>"#));
+        assert!(svg.contains(r##"fill="#fe7d37""##));
+        assert!(svg.contains(r#"width="104""#));
+    }
+
+    #[test]
+    fn test_badge_never_rounds_up_to_complete() {
+        let svg = generate_badge_svg("migration", 99.95);
+        assert!(svg.contains("<title>migration: 99%</title>"));
+        assert!(svg.contains(r##"fill="#97ca00""##));
+    }
+
+    #[test]
+    fn test_badge_escapes_label() {
+        let svg = generate_badge_svg("a<b>&c", 100.0);
+        assert!(svg.contains("<title>a&lt;b&gt;&amp;c: 100%</title>"));
+        assert!(!svg.contains("a<b>"));
+    }
+}
@@ -0,0 +1,85 @@
+//! Baselines: previous reports that new results are compared with.
+
+use camino::{Utf8Path, Utf8PathBuf};
+use ch_core::{FileInfo, FxHashSet};
+use ch_scanner::{diff_scans, ScanDiff, StatsSnapshot};
+
+use super::binary::load_binary_report;
+use crate::{read_report, ReportSelection};
+
+/// Legacy imports already present in a baseline report.
+///
+/// Keyed by file path relative to the baseline's scan root and import path.
+pub(super) type BaselineImports = FxHashSet<(Utf8PathBuf, String)>;
+
+/// Loads the legacy imports from a previous JSON or binary report.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or isn't a report.
+pub(crate) fn load_baseline(path: &Utf8Path) -> color_eyre::Result<BaselineImports> {
+    let (root, files) = load_baseline_files(path)?;
+    Ok(files
+        .iter()
+        .flat_map(|file| {
+            let relative = file.path.strip_prefix(&root).unwrap_or(&file.path).to_owned();
+            file.legacy_imports()
+                .map(move |import| (relative.clone(), import.path.clone()))
+        })
+        .collect())
+}
+
+/// Compares the files of a baseline report with the current scan.
+///
+/// The baseline files are narrowed by the same `selection` as the report.
+/// Returns the differences and the baseline's statistics.
+///
+/// # Errors
+///
+/// Returns an error if the baseline cannot be read or isn't a report.
+pub(crate) fn diff_baseline(
+    path: &Utf8Path,
+    selection: &ReportSelection,
+    root: &Utf8Path,
+    files: &[FileInfo],
+) -> color_eyre::Result<(ScanDiff, StatsSnapshot)> {
+    let (previous_root, mut previous) = load_baseline_files(path)?;
+    if !selection.is_empty() {
+        previous.retain(|file| selection.matches(file, &previous_root));
+    }
+    let mut stats = StatsSnapshot::default();
+    for file in &previous {
+        stats.record(file.status);
+    }
+    Ok((diff_scans(&previous_root, &previous, root, files), stats))
+}
+
+/// Loads the scan root and files of a previous JSON or binary report.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or isn't a report.
+fn load_baseline_files(path: &Utf8Path) -> color_eyre::Result<(Utf8PathBuf, Vec<FileInfo>)> {
+    #[derive(serde::Deserialize)]
+    struct BaselineMetadata {
+        root_path: Utf8PathBuf,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Baseline {
+        metadata: Option<BaselineMetadata>,
+        files: Vec<FileInfo>,
+    }
+
+    let contents = read_report(path)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to read baseline {}: {}", path, e))?;
+    if contents.trim_ascii_start().starts_with(b"{") {
+        let baseline: Baseline = serde_json::from_slice(&contents)
+            .map_err(|e| color_eyre::eyre::eyre!("Invalid baseline report {}: {}", path, e))?;
+        let root = baseline.metadata.map(|m| m.root_path).unwrap_or_default();
+        Ok((root, baseline.files))
+    } else {
+        let report = load_binary_report(path)?;
+        Ok((report.metadata.into_owned().root_path, report.files.into_owned()))
+    }
+}
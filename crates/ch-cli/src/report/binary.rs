@@ -0,0 +1,80 @@
+//! Binary (`MessagePack`) reports, written by `report --format msgpack` and
+//! read back by `report convert` and `--baseline`.
+
+use std::borrow::Cow;
+
+use camino::Utf8Path;
+use ch_core::FileInfo;
+use ch_scanner::{OwnerStats, StatsSnapshot};
+
+use super::{ReportError, ReportMetadata};
+use crate::read_report;
+
+/// Version of the binary report layout, bumped on incompatible changes.
+const BINARY_REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// A report in the binary (`msgpack`) format.
+///
+/// Holds the same metadata, statistics, teams, files and scan errors as the
+/// JSON report (dead imports are derived from the files), encoded as `MessagePack` with
+/// field names so later tool versions can still read it.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub(crate) struct BinaryReport<'a> {
+    /// Layout version, checked before the rest is decoded.
+    schema_version: u32,
+    pub(crate) metadata: Cow<'a, ReportMetadata>,
+    pub(crate) stats: StatsSnapshot,
+    #[serde(default)]
+    pub(crate) teams: Cow<'a, [OwnerStats]>,
+    pub(crate) files: Cow<'a, [FileInfo]>,
+    #[serde(default)]
+    pub(crate) errors: Cow<'a, [ReportError]>,
+}
+
+/// Generates a binary (`MessagePack`) report.
+pub(crate) fn generate_binary_report(
+    metadata: &ReportMetadata,
+    stats: &StatsSnapshot,
+    teams: &[OwnerStats],
+    files: &[FileInfo],
+    errors: &[ReportError],
+) -> color_eyre::Result<Vec<u8>> {
+    let report = BinaryReport {
+        schema_version: BINARY_REPORT_SCHEMA_VERSION,
+        metadata: Cow::Borrowed(metadata),
+        stats: *stats,
+        teams: Cow::Borrowed(teams),
+        files: Cow::Borrowed(files),
+        errors: Cow::Borrowed(errors),
+    };
+    rmp_serde::to_vec_named(&report)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to serialize MessagePack: {}", e))
+}
+
+/// Loads a binary report written by `report --format msgpack`.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, isn't a binary report, or
+/// has a schema version this tool doesn't know.
+pub(crate) fn load_binary_report(path: &Utf8Path) -> color_eyre::Result<BinaryReport<'static>> {
+    #[derive(serde::Deserialize)]
+    struct Header {
+        schema_version: u32,
+    }
+
+    let bytes = read_report(path)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to read report {}: {}", path, e))?;
+    let header: Header = rmp_serde::from_slice(&bytes)
+        .map_err(|e| color_eyre::eyre::eyre!("Invalid binary report {}: {}", path, e))?;
+    if header.schema_version != BINARY_REPORT_SCHEMA_VERSION {
+        return Err(color_eyre::eyre::eyre!(
+            "Binary report {} has schema version {}, expected {}",
+            path,
+            header.schema_version,
+            BINARY_REPORT_SCHEMA_VERSION
+        ));
+    }
+    rmp_serde::from_slice(&bytes)
+        .map_err(|e| color_eyre::eyre::eyre!("Invalid binary report {}: {}", path, e))
+}
@@ -0,0 +1,235 @@
+//! CSV reports of files, legacy models, the registry and recorded scans.
+
+use camino::Utf8Path;
+use ch_core::{FileInfo, ModelRegistry, StatusLabels};
+use ch_scanner::{directory_group, ModelConsumers, Trend};
+use chrono::SecondsFormat;
+
+use super::{sorted_models, ReportMetadata};
+use crate::{recorded_time, CsvColumn};
+
+/// Generates a CSV report.
+///
+/// The report has one row per file with `columns` in order (see
+/// [`CsvColumn`]), preceded by `metadata` as `#` comment lines if given. The
+/// `owner` column lists each file's owners separated by spaces, the `status`
+/// column uses the display labels, and `directory` is relative to `app_path`.
+///
+/// With `with_imports`, there is one row per legacy import, with its
+/// `import_line`, `import_kind`, `import_module` and space-separated
+/// `import_names`; files without legacy imports keep a single row with those
+/// columns empty.
+pub(crate) fn generate_csv_report(
+    metadata: Option<&ReportMetadata>,
+    files: &[FileInfo],
+    columns: &[CsvColumn],
+    with_imports: bool,
+    labels: &StatusLabels,
+    app_path: &Utf8Path,
+) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+    if let Some(metadata) = metadata {
+        metadata.write_comments(&mut output);
+    }
+    let header: Vec<&str> = columns.iter().map(|column| column.key()).collect();
+    output.push_str(&header.join(","));
+    if with_imports {
+        output.push_str(",import_line,import_kind,import_module,import_names");
+    }
+    output.push('\n');
+
+    for file in files {
+        let legacy_count = file.legacy_imports().count();
+        let fields: Vec<String> = columns
+            .iter()
+            .map(|column| match column {
+                CsvColumn::Path => escape_csv(file.path.as_str()),
+                CsvColumn::Status => escape_csv(labels.get(file.status)),
+                CsvColumn::ImportCount => file.import_count().to_string(),
+                CsvColumn::LegacyImports => legacy_count.to_string(),
+                CsvColumn::MigratedImports => file.migrated_imports().count().to_string(),
+                CsvColumn::Owner => escape_csv(&file.owners.join(" ")),
+                CsvColumn::FirstLegacyLine => file
+                    .legacy_imports()
+                    .map(|import| import.location.line)
+                    .min()
+                    .map_or_else(String::new, |line| line.to_string()),
+                CsvColumn::Directory => escape_csv(
+                    directory_group(&file.path, app_path, usize::MAX).as_str(),
+                ),
+            })
+            .collect();
+        let row = fields.join(",");
+        if !with_imports {
+            let _ = writeln!(output, "{row}");
+            continue;
+        }
+        if legacy_count == 0 {
+            let _ = writeln!(output, "{row},,,,");
+        }
+        for import in file.legacy_imports() {
+            let _ = writeln!(
+                output,
+                "{row},{},{},{},{}",
+                import.location.line,
+                import.kind.key(),
+                escape_csv(&import.path),
+                escape_csv(&import.names.join(" "))
+            );
+        }
+    }
+
+    output
+}
+
+/// Generates a CSV model report.
+///
+/// The report starts with `metadata` as `#` comment lines if given. The
+/// `files` column lists the consumer files separated by spaces, and
+/// `modern_equivalent` is empty for models not yet ported.
+pub(crate) fn generate_csv_model_report(
+    metadata: Option<&ReportMetadata>,
+    models: &[ModelConsumers],
+) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+    if let Some(metadata) = metadata {
+        metadata.write_comments(&mut output);
+    }
+    output.push_str("model,consumers,modern_equivalent,files\n");
+    for model in models {
+        let files: Vec<&str> = model.files.iter().map(|path| path.as_str()).collect();
+        let _ = writeln!(
+            output,
+            "{},{},{},{}",
+            escape_csv(&model.name),
+            model.consumer_count(),
+            escape_csv(model.modern_equivalent.as_deref().unwrap_or_default()),
+            escape_csv(&files.join(" "))
+        );
+    }
+    output
+}
+
+/// Generates a CSV export of the model registry.
+///
+/// Each row is one model; its exports are separated by spaces. Legacy rows
+/// name their `shared_2023/` counterpart in `modern_equivalent`.
+pub(crate) fn generate_csv_registry(registry: &ModelRegistry) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::from("name,source,definition_path,exports,modern_equivalent\n");
+    let models = sorted_models(registry.iter_legacy_models())
+        .into_iter()
+        .chain(sorted_models(registry.iter_modern_models()));
+
+    for model in models {
+        let modern_equivalent = if model.is_legacy() {
+            registry.modern_model_for(&model.name).map_or("", |m| m.name.as_str())
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            output,
+            "{},{},{},{},{}",
+            escape_csv(&model.name),
+            model.source.dir_name(),
+            escape_csv(model.definition_path.as_str()),
+            escape_csv(&model.exports.join(" ")),
+            escape_csv(modern_equivalent)
+        );
+    }
+
+    output
+}
+
+/// Generates the `--format trend` CSV, one row per recorded scan.
+pub(crate) fn generate_trend_csv(trend: &Trend) -> String {
+    use std::fmt::Write;
+
+    let optional = |value: Option<f64>| value.map_or_else(String::new, |v| format!("{v:.2}"));
+    let mut output = String::from(
+        "recorded_at,commit,total,legacy,partial,migrated,no_models,errors,\
+         progress_percent,change,migrated_per_day\n",
+    );
+    for point in &trend.points {
+        let stats = &point.stats;
+        let _ = writeln!(
+            output,
+            "{},{},{},{},{},{},{},{},{:.1},{},{}",
+            recorded_time(point.recorded_at).map_or_else(
+                || point.recorded_at.to_string(),
+                |time| time.to_rfc3339_opts(SecondsFormat::Secs, true)
+            ),
+            escape_csv(point.commit.as_deref().unwrap_or_default()),
+            stats.total,
+            stats.legacy,
+            stats.partial,
+            stats.migrated,
+            stats.no_models,
+            stats.errors,
+            point.progress_percent(),
+            optional(point.change),
+            optional(point.migrated_per_day)
+        );
+    }
+    output
+}
+
+/// Escapes a string for CSV output.
+fn escape_csv(s: &str) -> String {
+    if s.contains(',') || s.contains('"') || s.contains('\n') {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{test_files, test_metadata};
+
+    #[test]
+    fn test_csv_report_rows() {
+        let metadata = test_metadata();
+        let (files, _) = test_files();
+        let csv = generate_csv_report(
+            None,
+            &files,
+            &CsvColumn::defaults(false),
+            false,
+            &StatusLabels::default(),
+            &metadata.app_path,
+        );
+        assert_eq!(
+            csv,
+            "path,status,import_count,legacy_imports,migrated_imports\n\
+             /repo/src/app/jobs/job-list.ts,Legacy,1,1,0\n\
+             /repo/src/app/jobs/job-edit.ts,Partial,2,1,1\n\
+             /repo/src/app/home.ts,Migrated,1,0,1\n"
+        );
+    }
+
+    #[test]
+    fn test_csv_report_metadata_comments() {
+        let metadata = test_metadata();
+        let (files, _) = test_files();
+        let csv = generate_csv_report(
+            Some(&metadata),
+            &files,
+            &[CsvColumn::Path, CsvColumn::Directory],
+            false,
+            &StatusLabels::default(),
+            &metadata.app_path,
+        );
+        let (comments, rows): (Vec<_>, Vec<_>) = csv.lines().partition(|line| line.starts_with('#'));
+        assert!(comments.contains(&"# tool_version: 1.2.3"));
+        assert!(comments.contains(&"# skip_dirs: node_modules"));
+        assert_eq!(rows.first(), Some(&"path,directory"));
+        assert_eq!(rows.get(1), Some(&"/repo/src/app/jobs/job-list.ts,jobs"));
+    }
+}
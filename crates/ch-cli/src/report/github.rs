@@ -0,0 +1,132 @@
+//! GitHub Actions output: `::warning` annotations and the job summary.
+
+use std::io::Write;
+
+use camino::Utf8Path;
+use ch_core::{FileInfo, MigrationStatus, StatusLabels};
+use ch_scanner::{OwnerStats, StatsSnapshot};
+use tracing::info;
+
+use super::baseline::BaselineImports;
+use super::markdown::generate_markdown_summary;
+use super::{current_dir_utf8, legacy_import_message, workspace_relative, ReportMetadata};
+
+/// Generates a GitHub Actions report.
+///
+/// Returns `::warning` annotations for new legacy imports (see
+/// [`generate_github_annotations`]) and appends the Markdown summary plus a
+/// metadata footer to the job summary.
+///
+/// # Errors
+///
+/// Returns an error if the job summary cannot be written.
+pub(crate) fn generate_github_report(
+    metadata: &ReportMetadata,
+    stats: &StatsSnapshot,
+    teams: &[OwnerStats],
+    files: &[FileInfo],
+    baseline: Option<&BaselineImports>,
+    labels: &StatusLabels,
+) -> color_eyre::Result<String> {
+    use std::fmt::Write;
+
+    let annotations = generate_github_annotations(files, &metadata.root_path, baseline);
+
+    let legacy_files: Vec<_> = files
+        .iter()
+        .filter(|file| file.status == MigrationStatus::Legacy)
+        .cloned()
+        .collect();
+    let mut summary =
+        generate_markdown_summary(stats, teams, &legacy_files, &metadata.root_path, labels);
+    let _ = writeln!(
+        summary,
+        "\n_ch-migrate {} · scanned `{}` at {} in {} ms · {} new legacy imports_",
+        metadata.tool_version,
+        metadata.root_path,
+        metadata.generated_at,
+        metadata.duration_ms,
+        annotations.count
+    );
+    write_step_summary(&summary)?;
+
+    Ok(annotations.output)
+}
+
+/// GitHub workflow command output and the number of annotations it contains.
+pub(crate) struct GithubAnnotations {
+    pub(crate) output: String,
+    pub(crate) count: usize,
+}
+
+/// Generates `::warning` workflow commands for legacy imports.
+///
+/// When a baseline is given, imports it already contains are skipped so only
+/// newly introduced legacy imports are annotated. File paths are made
+/// relative to the working directory, which GitHub expects to be the
+/// repository checkout.
+pub(crate) fn generate_github_annotations(
+    files: &[FileInfo],
+    root: &Utf8Path,
+    baseline: Option<&BaselineImports>,
+) -> GithubAnnotations {
+    use std::fmt::Write;
+
+    let cwd = current_dir_utf8();
+
+    let mut output = String::new();
+    let mut count = 0;
+    for file in files {
+        let relative = file.path.strip_prefix(root).unwrap_or(&file.path);
+        let display = workspace_relative(&file.path, cwd.as_deref());
+
+        for import in file.legacy_imports() {
+            let key = (relative.to_owned(), import.path.clone());
+            if baseline.is_some_and(|baseline| baseline.contains(&key)) {
+                continue;
+            }
+
+            count += 1;
+            let message = legacy_import_message(import);
+            let _ = writeln!(
+                output,
+                "::warning file={},line={},col={},title=Legacy model import::{}",
+                escape_workflow_property(display.as_str()),
+                import.location.line,
+                import.location.column + 1,
+                escape_workflow_data(&message)
+            );
+        }
+    }
+
+    GithubAnnotations { output, count }
+}
+
+/// Appends Markdown to the GitHub Actions job summary, if running in Actions.
+///
+/// # Errors
+///
+/// Returns an error if `$GITHUB_STEP_SUMMARY` is set but can't be written.
+fn write_step_summary(markdown: &str) -> color_eyre::Result<()> {
+    let Some(path) = std::env::var_os("GITHUB_STEP_SUMMARY") else {
+        info!("GITHUB_STEP_SUMMARY not set; skipping job summary");
+        return Ok(());
+    };
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?;
+    file.write_all(markdown.as_bytes())?;
+    Ok(())
+}
+
+/// Escapes workflow command message data.
+fn escape_workflow_data(s: &str) -> String {
+    s.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Escapes a workflow command property value.
+fn escape_workflow_property(s: &str) -> String {
+    escape_workflow_data(s).replace(':', "%3A").replace(',', "%2C")
+}
@@ -0,0 +1,183 @@
+//! Graph reports: Graphviz DOT and Mermaid charts of legacy model usage.
+
+use std::collections::BTreeMap;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use ch_core::{FileInfo, MigrationStatus, StatusLabels};
+use ch_scanner::{directory_group, ModelConsumers};
+
+use super::ReportMetadata;
+
+/// Generates a Graphviz DOT graph of legacy model usage.
+///
+/// Each file with legacy imports is a box filled by migration status, grouped
+/// in a cluster per top-level directory; each legacy model is an ellipse,
+/// green if it has a `shared_2023/` equivalent. Edges point from files to the
+/// models they import, or from models to their consumers with `by_model`.
+pub(crate) fn generate_dot_report(
+    metadata: &ReportMetadata,
+    files: &[FileInfo],
+    models: &[ModelConsumers],
+    by_model: bool,
+    labels: &StatusLabels,
+) -> String {
+    use std::fmt::Write;
+
+    let root = &metadata.app_path;
+    let relative = |path: &Utf8Path| path.strip_prefix(root).unwrap_or(path).to_string();
+    let mut output = String::from("digraph migration {\n");
+    let _ = writeln!(output, "  label=\"Legacy models · {}\";", escape_dot(root.as_str()));
+    let _ = writeln!(output, "  rankdir={};", if by_model { "LR" } else { "RL" });
+    let _ = writeln!(output, "  node [style=filled, fontname=\"Helvetica\"];");
+
+    let mut groups: BTreeMap<Utf8PathBuf, Vec<&FileInfo>> = BTreeMap::new();
+    for file in files.iter().filter(|file| file.legacy_imports().next().is_some()) {
+        groups.entry(directory_group(&file.path, root, 1)).or_default().push(file);
+    }
+    for (index, (group, files)) in groups.iter().enumerate() {
+        let _ = writeln!(output);
+        let _ = writeln!(output, "  subgraph cluster_{index} {{");
+        let _ = writeln!(output, "    label=\"{}\";", escape_dot(group.as_str()));
+        for file in files {
+            let fill = match file.status {
+                MigrationStatus::Legacy => "#f8d7da",
+                MigrationStatus::Partial => "#fff3cd",
+                _ => "#d4edda",
+            };
+            let _ = writeln!(
+                output,
+                "    \"f:{}\" [shape=box, fillcolor=\"{fill}\", label=\"{}\", tooltip=\"{}\"];",
+                escape_dot(file.path.as_str()),
+                escape_dot(&relative(&file.path)),
+                escape_dot(labels.get(file.status))
+            );
+        }
+        let _ = writeln!(output, "  }}");
+    }
+
+    let _ = writeln!(output);
+    for model in models {
+        let (fill, label) = match &model.modern_equivalent {
+            Some(modern) => ("#cfe2ff", format!("{} → {modern}", model.name)),
+            None => ("#e2e3e5", model.name.clone()),
+        };
+        let _ = writeln!(
+            output,
+            "  \"m:{}\" [shape=ellipse, fillcolor=\"{fill}\", label=\"{}\"];",
+            escape_dot(&model.name),
+            escape_dot(&label)
+        );
+    }
+
+    let _ = writeln!(output);
+    for model in models {
+        let name = escape_dot(&model.name);
+        for path in &model.files {
+            let path = escape_dot(path.as_str());
+            if by_model {
+                let _ = writeln!(output, "  \"m:{name}\" -> \"f:{path}\";");
+            } else {
+                let _ = writeln!(output, "  \"f:{path}\" -> \"m:{name}\";");
+            }
+        }
+    }
+    output.push_str("}\n");
+    output
+}
+
+/// Number of legacy models in a Mermaid chart without `--top`.
+pub(crate) const MERMAID_TOP_MODELS: usize = 10;
+
+/// Generates a Mermaid flowchart of the most consumed legacy models.
+///
+/// The `top` models (by consumer count) link to the top-level directories
+/// importing them, each edge labelled with the number of files. Models with
+/// a `shared_2023/` equivalent are styled as ported. The chart is wrapped in a
+/// fenced `mermaid` block, ready to paste into Markdown.
+pub(crate) fn generate_mermaid_report(
+    metadata: &ReportMetadata,
+    models: &[ModelConsumers],
+    top: usize,
+) -> String {
+    use std::fmt::Write;
+
+    let root = &metadata.app_path;
+    let shown = &models[..models.len().min(top)];
+    let mut directories: BTreeMap<Utf8PathBuf, usize> = BTreeMap::new();
+    let mut edges = Vec::new();
+    for (index, model) in shown.iter().enumerate() {
+        let mut consumers: BTreeMap<Utf8PathBuf, usize> = BTreeMap::new();
+        for path in &model.files {
+            *consumers.entry(directory_group(path, root, 1)).or_default() += 1;
+        }
+        for (directory, count) in consumers {
+            let next = directories.len();
+            let node = *directories.entry(directory).or_insert(next);
+            edges.push((index, node, count));
+        }
+    }
+
+    let mut output = String::from("```mermaid\nflowchart LR\n");
+    let _ = writeln!(
+        output,
+        "  %% Top {} of {} legacy models in {}",
+        shown.len(),
+        models.len(),
+        root
+    );
+    for (index, model) in shown.iter().enumerate() {
+        let (label, class) = match &model.modern_equivalent {
+            Some(modern) => (format!("{} → {modern}", model.name), "ported"),
+            None => (model.name.clone(), "legacy"),
+        };
+        let _ = writeln!(output, "  m{index}[\"{}\"]:::{class}", escape_mermaid(&label));
+    }
+    for (directory, node) in &directories {
+        let _ = writeln!(
+            output,
+            "  d{node}([\"{}\"]):::directory",
+            escape_mermaid(directory.as_str())
+        );
+    }
+    for (model, directory, count) in edges {
+        let _ = writeln!(output, "  m{model} -->|{count}| d{directory}");
+    }
+    let _ = writeln!(output, "  classDef legacy fill:#f8d7da,stroke:#842029");
+    let _ = writeln!(output, "  classDef ported fill:#cfe2ff,stroke:#084298");
+    let _ = writeln!(output, "  classDef directory fill:#e2e3e5,stroke:#41464b");
+    output.push_str("```\n");
+    output
+}
+
+/// Escapes a string for a quoted DOT identifier or attribute value.
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Escapes a string for a quoted Mermaid label.
+fn escape_mermaid(s: &str) -> String {
+    s.replace('#', "#35;")
+        .replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{test_files, test_metadata};
+
+    #[test]
+    fn test_dot_report_clusters_files_needing_work() {
+        let (files, _) = test_files();
+        let dot = generate_dot_report(&test_metadata(), &files, &[], false, &StatusLabels::default());
+        assert!(dot.starts_with("digraph migration {\n  label=\"Legacy models · /repo/src/app\";\n"));
+        assert!(dot.contains(
+            "  subgraph cluster_0 {\n    label=\"jobs\";\n    \
+             \"f:/repo/src/app/jobs/job-list.ts\" [shape=box, fillcolor=\"#f8d7da\", \
+             label=\"jobs/job-list.ts\", tooltip=\"Legacy\"];\n"
+        ));
+        assert!(!dot.contains("home.ts"));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+}
@@ -0,0 +1,684 @@
+//! Standalone HTML pages: the report, the changes since a baseline and the
+//! progress trend.
+
+use camino::Utf8Path;
+use ch_core::{FileInfo, MigrationStatus, StatusLabels};
+use ch_scanner::{rollup_by_directory, OwnerStats, ScanDiff, StatsSnapshot, StatusChange, Trend};
+use chrono::Local;
+
+use super::json::UnmatchedImport;
+use super::{escape_xml, ReportMetadata};
+use crate::recorded_time;
+
+/// Stylesheet embedded in HTML reports.
+const HTML_REPORT_CSS: &str = include_str!("../../assets/report.css");
+
+/// Script embedded in HTML reports to sort and filter the file table.
+const HTML_REPORT_JS: &str = include_str!("../../assets/report.js");
+
+/// Generates a standalone HTML report.
+///
+/// The page shows progress gauges, per-directory (and, when `teams` is
+/// non-empty, per-team) progress bars, and a file table that can be sorted
+/// by clicking a column header and filtered by path or status. Styles and
+/// script are embedded, so the file works offline, e.g. as a CI artifact.
+/// Paths are shown relative to the app path, matching the directory groups.
+/// The registry counts and any unmatched imports follow the file table.
+pub(crate) fn generate_html_report(
+    metadata: &ReportMetadata,
+    stats: &StatsSnapshot,
+    teams: &[OwnerStats],
+    files: &[FileInfo],
+    unmatched: &[UnmatchedImport<'_>],
+    with_owners: bool,
+    labels: &StatusLabels,
+) -> String {
+    use std::fmt::Write;
+
+    let root = &metadata.app_path;
+    let mut output = String::new();
+    let _ = writeln!(output, "<!DOCTYPE html>");
+    let _ = writeln!(output, r#"<html lang="en">"#);
+    let _ = writeln!(output, "<head>");
+    let _ = writeln!(output, r#"<meta charset="utf-8">"#);
+    let _ = writeln!(
+        output,
+        r#"<meta name="viewport" content="width=device-width, initial-scale=1">"#
+    );
+    let _ = writeln!(output, "<title>Migration Report · {}</title>", escape_xml(root.as_str()));
+    let _ = writeln!(output, "<style>\n{HTML_REPORT_CSS}</style>");
+    let _ = writeln!(output, "</head>");
+    let _ = writeln!(output, "<body>");
+
+    let _ = writeln!(output, "<h1>Migration Report</h1>");
+    let _ = writeln!(
+        output,
+        r#"<p class="meta">{} · generated {} by ch-migrate {} in {} ms</p>"#,
+        escape_xml(root.as_str()),
+        escape_xml(&metadata.generated_at),
+        metadata.tool_version,
+        metadata.duration_ms
+    );
+
+    let _ = writeln!(output, r#"<section class="overview">"#);
+    let with_models = stats.legacy + stats.partial + stats.migrated;
+    let started = html_share(stats.migrated + stats.partial, with_models);
+    for (class, value, label) in [
+        ("gauge", stats.progress_percent(), "Migrated"),
+        ("gauge started", started, "Started"),
+    ] {
+        let _ = writeln!(
+            output,
+            r#"<div class="card"><div class="{class}" style="--value: {value:.1}"><span>{value:.1}%</span></div><span class="label">{label}</span></div>"#
+        );
+    }
+    let counts = [stats.legacy, stats.partial, stats.migrated, stats.no_models];
+    for (status, count) in MigrationStatus::ALL.into_iter().zip(counts) {
+        let _ = writeln!(
+            output,
+            r#"<div class="card {}"><span class="value status">{count}</span><span class="label">{}</span></div>"#,
+            status.key(),
+            escape_xml(labels.get(status))
+        );
+    }
+    for (count, label) in [(stats.total, "Total files"), (stats.errors, "Errors")] {
+        let _ = writeln!(
+            output,
+            r#"<div class="card"><span class="value">{count}</span><span class="label">{label}</span></div>"#
+        );
+    }
+    let _ = writeln!(output, "</section>");
+    write_html_status_bar(&mut output, stats, labels, "bar large");
+    let _ = writeln!(output, r#"<div class="legend">"#);
+    for status in MigrationStatus::ALL {
+        let _ = writeln!(
+            output,
+            r#"<span class="{}"><i></i>{}</span>"#,
+            status.key(),
+            escape_xml(labels.get(status))
+        );
+    }
+    let _ = writeln!(output, "</div>");
+
+    let directories = rollup_by_directory(files, root, 1);
+    let rows: Vec<_> = directories
+        .iter()
+        .map(|dir| (dir.path.as_str(), &dir.stats))
+        .collect();
+    write_html_breakdown(&mut output, "By Directory", &rows, true, labels);
+    if !teams.is_empty() {
+        let rows: Vec<_> = teams
+            .iter()
+            .map(|team| (team.owner.as_str(), &team.stats))
+            .collect();
+        write_html_breakdown(&mut output, "By Team", &rows, false, labels);
+    }
+
+    let _ = writeln!(output, "<h2>Files</h2>");
+    let _ = writeln!(output, r#"<div class="controls">"#);
+    let _ = writeln!(
+        output,
+        r#"<input id="file-search" type="search" placeholder="Filter by path" aria-label="Filter by path">"#
+    );
+    let _ = writeln!(output, r#"<select id="file-status" aria-label="Filter by status">"#);
+    let _ = writeln!(output, r#"<option value="">All statuses</option>"#);
+    for status in MigrationStatus::ALL {
+        let _ = writeln!(
+            output,
+            r#"<option value="{}">{}</option>"#,
+            status.key(),
+            escape_xml(labels.get(status))
+        );
+    }
+    let _ = writeln!(output, "</select>");
+    let _ = writeln!(output, r#"<span id="file-count"></span>"#);
+    let _ = writeln!(output, "</div>");
+
+    let _ = writeln!(output, r#"<table id="files">"#);
+    let _ = write!(
+        output,
+        r#"<thead><tr><th>Path</th><th>Status</th><th class="num">Imports</th><th class="num">Legacy</th><th class="num">Migrated</th>"#
+    );
+    if with_owners {
+        let _ = write!(output, "<th>Owner</th>");
+    }
+    let _ = writeln!(output, "</tr></thead>");
+    let _ = writeln!(output, "<tbody>");
+    let mut sorted: Vec<&FileInfo> = files.iter().collect();
+    sorted.sort_by(|a, b| a.path.cmp(&b.path));
+    for file in sorted {
+        let path = file.path.strip_prefix(root).unwrap_or(&file.path);
+        let key = file.status.key();
+        let _ = write!(
+            output,
+            r#"<tr data-status="{key}"><td class="path">{}</td><td class="status {key}">{}</td>"#,
+            escape_xml(path.as_str()),
+            escape_xml(labels.get(file.status))
+        );
+        for count in [
+            file.import_count(),
+            file.legacy_imports().count(),
+            file.migrated_imports().count(),
+        ] {
+            let _ = write!(output, r#"<td class="num" data-sort="{count}">{count}</td>"#);
+        }
+        if with_owners {
+            let _ = write!(output, "<td>{}</td>", escape_xml(&file.owners.join(" ")));
+        }
+        let _ = writeln!(output, "</tr>");
+    }
+    let _ = writeln!(output, "</tbody>");
+    let _ = writeln!(output, "</table>");
+
+    let registry = &metadata.registry;
+    let _ = writeln!(output, "<h2>Registry</h2>");
+    let _ = writeln!(output, "<table>");
+    let _ = writeln!(
+        output,
+        r#"<thead><tr><th class="num">Legacy models</th><th class="num">Modern models</th><th class="num">Ported</th><th class="num">Legacy exports</th><th class="num">Modern exports</th><th class="num">Renames</th></tr></thead>"#
+    );
+    let _ = write!(output, "<tbody><tr>");
+    for count in [
+        registry.legacy_models,
+        registry.modern_models,
+        registry.ported_models,
+        registry.legacy_exports,
+        registry.modern_exports,
+        registry.renames,
+    ] {
+        let _ = write!(output, r#"<td class="num">{count}</td>"#);
+    }
+    let _ = writeln!(output, "</tr></tbody>");
+    let _ = writeln!(output, "</table>");
+
+    if !unmatched.is_empty() {
+        let _ = writeln!(output, "<h2>Unmatched Imports ({})</h2>", unmatched.len());
+        let _ = writeln!(
+            output,
+            r#"<p class="meta">Imports from a shared directory naming no registered export.</p>"#
+        );
+        let _ = writeln!(output, "<table>");
+        let _ = writeln!(
+            output,
+            r#"<thead><tr><th>Path</th><th class="num">Line</th><th>Module</th><th>Names</th></tr></thead>"#
+        );
+        let _ = writeln!(output, "<tbody>");
+        for import in unmatched {
+            let path = import.file.strip_prefix(root).unwrap_or(import.file);
+            let _ = writeln!(
+                output,
+                r#"<tr><td class="path">{}</td><td class="num">{}</td><td class="path">{}</td><td>{}</td></tr>"#,
+                escape_xml(path.as_str()),
+                import.line,
+                escape_xml(import.module),
+                escape_xml(&import.names.join(", "))
+            );
+        }
+        let _ = writeln!(output, "</tbody>");
+        let _ = writeln!(output, "</table>");
+    }
+
+    let _ = writeln!(output, "<script>\n{HTML_REPORT_JS}</script>");
+    let _ = writeln!(output, "</body>");
+    let _ = writeln!(output, "</html>");
+    output
+}
+
+/// Generates a standalone HTML page of what changed since a baseline report.
+///
+/// Overview cards compare the progress of both scans; sections list the newly
+/// migrated files, the regressions and the new files with legacy imports,
+/// each with its legacy import names.
+pub(crate) fn generate_diff_html(
+    metadata: &ReportMetadata,
+    baseline: &Utf8Path,
+    diff: &ScanDiff,
+    previous: &StatsSnapshot,
+    stats: &StatsSnapshot,
+    labels: &StatusLabels,
+) -> String {
+    use std::fmt::Write;
+
+    let root = &metadata.app_path;
+    let mut output = String::new();
+    let _ = writeln!(output, "<!DOCTYPE html>");
+    let _ = writeln!(output, r#"<html lang="en">"#);
+    let _ = writeln!(output, "<head>");
+    let _ = writeln!(output, r#"<meta charset="utf-8">"#);
+    let _ = writeln!(
+        output,
+        r#"<meta name="viewport" content="width=device-width, initial-scale=1">"#
+    );
+    let _ = writeln!(output, "<title>Migration Changes · {}</title>", escape_xml(root.as_str()));
+    let _ = writeln!(output, "<style>\n{HTML_REPORT_CSS}</style>");
+    let _ = writeln!(output, "</head>");
+    let _ = writeln!(output, "<body>");
+
+    let _ = writeln!(output, "<h1>Migration Changes</h1>");
+    let _ = writeln!(
+        output,
+        r#"<p class="meta">{} · compared with {} · generated {} by ch-migrate {}</p>"#,
+        escape_xml(root.as_str()),
+        escape_xml(baseline.as_str()),
+        escape_xml(&metadata.generated_at),
+        metadata.tool_version
+    );
+
+    let _ = writeln!(output, r#"<section class="overview">"#);
+    for (value, label) in [
+        (format!("{:.1}%", stats.progress_percent()), "Migrated"),
+        (
+            format!("{:+.1}", stats.progress_percent() - previous.progress_percent()),
+            "Change (points)",
+        ),
+        (diff.migrated.len().to_string(), "Newly migrated"),
+        (diff.regressed.len().to_string(), "Regressed"),
+        (diff.new_legacy.len().to_string(), "New legacy files"),
+    ] {
+        let _ = writeln!(
+            output,
+            r#"<div class="card"><span class="value">{value}</span><span class="label">{label}</span></div>"#
+        );
+    }
+    let _ = writeln!(output, "</section>");
+
+    for (heading, changes, with_before) in [
+        ("Newly Migrated", &diff.migrated, true),
+        ("Regressed", &diff.regressed, true),
+        ("New Files with Legacy Imports", &diff.new_legacy, false),
+    ] {
+        write_html_changes(&mut output, heading, changes, root, with_before, labels);
+    }
+    if diff.removed > 0 {
+        let _ = writeln!(
+            output,
+            r#"<p class="meta">{} files from the baseline no longer exist.</p>"#,
+            diff.removed
+        );
+    }
+
+    let _ = writeln!(output, "</body>");
+    let _ = writeln!(output, "</html>");
+    output
+}
+
+/// Writes a table of files whose status changed, with their legacy imports.
+///
+/// With `with_before`, the table shows the baseline status next to the
+/// current one.
+fn write_html_changes(
+    output: &mut String,
+    heading: &str,
+    changes: &[StatusChange],
+    root: &Utf8Path,
+    with_before: bool,
+    labels: &StatusLabels,
+) {
+    use std::fmt::Write;
+
+    let _ = writeln!(output, "<h2>{heading} ({})</h2>", changes.len());
+    if changes.is_empty() {
+        let _ = writeln!(output, r#"<p class="meta">None</p>"#);
+        return;
+    }
+    let _ = writeln!(output, "<table>");
+    let _ = write!(output, "<thead><tr><th>Path</th>");
+    if with_before {
+        let _ = write!(output, "<th>Before</th>");
+    }
+    let _ = writeln!(output, "<th>Now</th><th>Legacy imports</th></tr></thead>");
+    let _ = writeln!(output, "<tbody>");
+    for change in changes {
+        let path = change.path.strip_prefix(root).unwrap_or(&change.path);
+        let _ = write!(output, r#"<tr><td class="path">{}</td>"#, escape_xml(path.as_str()));
+        let statuses = if with_before {
+            [Some(change.from), Some(change.to)]
+        } else {
+            [None, Some(change.to)]
+        };
+        for status in statuses.into_iter().flatten() {
+            let _ = write!(
+                output,
+                r#"<td class="status {}">{}</td>"#,
+                status.key(),
+                escape_xml(labels.get(status))
+            );
+        }
+        let _ = writeln!(output, "<td>{}</td></tr>", escape_xml(&change.remaining.join(", ")));
+    }
+    let _ = writeln!(output, "</tbody>");
+    let _ = writeln!(output, "</table>");
+}
+
+/// Writes a table of groups with their file counts and progress bars.
+///
+/// With `filter_links`, group names narrow the file table to that directory.
+fn write_html_breakdown(
+    output: &mut String,
+    heading: &str,
+    rows: &[(&str, &StatsSnapshot)],
+    filter_links: bool,
+    labels: &StatusLabels,
+) {
+    use std::fmt::Write;
+
+    if rows.is_empty() {
+        return;
+    }
+
+    let _ = writeln!(output, "<h2>{heading}</h2>");
+    let _ = writeln!(output, "<table>");
+    let _ = writeln!(
+        output,
+        r#"<thead><tr><th>Name</th><th class="num">Files</th><th>Progress</th><th class="num">% Complete</th></tr></thead>"#
+    );
+    let _ = writeln!(output, "<tbody>");
+    for (name, stats) in rows {
+        let name = escape_xml(name);
+        let _ = write!(output, "<tr><td class=\"path\">");
+        if filter_links && name != "." {
+            let _ = write!(output, r#"<button class="link" data-filter="{name}/">{name}</button>"#);
+        } else {
+            let _ = write!(output, "{name}");
+        }
+        let _ = write!(output, r#"</td><td class="num">{}</td><td class="chart">"#, stats.total);
+        write_html_status_bar(output, stats, labels, "bar");
+        let _ = writeln!(
+            output,
+            r#"</td><td class="num">{:.1}%</td></tr>"#,
+            stats.progress_percent()
+        );
+    }
+    let _ = writeln!(output, "</tbody>");
+    let _ = writeln!(output, "</table>");
+}
+
+/// Writes a bar split into legacy, partial and migrated segments.
+fn write_html_status_bar(
+    output: &mut String,
+    stats: &StatsSnapshot,
+    labels: &StatusLabels,
+    class: &str,
+) {
+    use std::fmt::Write;
+
+    let with_models = stats.legacy + stats.partial + stats.migrated;
+    let _ = write!(output, r#"<div class="{class}">"#);
+    for (status, count) in [
+        (MigrationStatus::Legacy, stats.legacy),
+        (MigrationStatus::Partial, stats.partial),
+        (MigrationStatus::Migrated, stats.migrated),
+    ] {
+        if count > 0 {
+            let _ = write!(
+                output,
+                r#"<span class="{}" style="width: {:.2}%" title="{}: {count}"></span>"#,
+                status.key(),
+                html_share(count, with_models),
+                escape_xml(labels.get(status))
+            );
+        }
+    }
+    let _ = writeln!(output, "</div>");
+}
+
+/// Generates the `--format trend --chart` page: velocity cards, a progress
+/// chart and a table of recorded scans.
+pub(crate) fn generate_trend_html(trend: &Trend, history_file: &Utf8Path, labels: &StatusLabels) -> String {
+    use std::fmt::Write;
+
+    let date = |recorded_at: u64| {
+        recorded_time(recorded_at).map_or_else(
+            || recorded_at.to_string(),
+            |time| time.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string(),
+        )
+    };
+    let mut output = String::new();
+    let _ = writeln!(output, "<!DOCTYPE html>");
+    let _ = writeln!(output, r#"<html lang="en">"#);
+    let _ = writeln!(output, "<head>");
+    let _ = writeln!(output, r#"<meta charset="utf-8">"#);
+    let _ = writeln!(
+        output,
+        r#"<meta name="viewport" content="width=device-width, initial-scale=1">"#
+    );
+    let _ = writeln!(output, "<title>Migration Trend</title>");
+    let _ = writeln!(output, "<style>\n{HTML_REPORT_CSS}</style>");
+    let _ = writeln!(output, "</head>");
+    let _ = writeln!(output, "<body>");
+
+    let _ = writeln!(output, "<h1>Migration Trend</h1>");
+    let (Some(first), Some(last)) = (trend.points.first(), trend.points.last()) else {
+        let _ = writeln!(output, "</body>\n</html>");
+        return output;
+    };
+    let _ = writeln!(
+        output,
+        r#"<p class="meta">{} · {} scans from {} to {} · generated by ch-migrate {}</p>"#,
+        escape_xml(history_file.as_str()),
+        trend.points.len(),
+        date(first.recorded_at),
+        date(last.recorded_at),
+        env!("CARGO_PKG_VERSION")
+    );
+
+    let _ = writeln!(output, r#"<section class="overview">"#);
+    let velocity = trend
+        .migrated_per_day()
+        .map_or_else(|| "–".to_owned(), |per_day| format!("{per_day:+.1}"));
+    for (value, label) in [
+        (format!("{:.1}%", last.progress_percent()), "Migrated"),
+        (
+            format!("{:+.1}", last.progress_percent() - first.progress_percent()),
+            "Change (points)",
+        ),
+        (velocity, "Files migrated / day"),
+        (last.stats.needs_migration().to_string(), "Files needing work"),
+    ] {
+        let _ = writeln!(
+            output,
+            r#"<div class="card"><span class="value">{value}</span><span class="label">{label}</span></div>"#
+        );
+    }
+    let _ = writeln!(output, "</section>");
+
+    let _ = writeln!(output, "<h2>Progress</h2>");
+    write_trend_chart(&mut output, trend, &date);
+    let _ = writeln!(output, r#"<div class="legend">"#);
+    for (class, label) in [("migrated", "Migrated"), ("partial", "Started")] {
+        let _ = writeln!(output, r#"<span class="{class}"><i></i>{label}</span>"#);
+    }
+    let _ = writeln!(output, "</div>");
+
+    let _ = writeln!(output, "<h2>Scans</h2>");
+    let _ = writeln!(output, "<table>");
+    let _ = write!(
+        output,
+        r#"<thead><tr><th>Recorded</th><th>Commit</th><th class="num">Files</th>"#
+    );
+    for status in MigrationStatus::ALL {
+        let _ = write!(output, r#"<th class="num">{}</th>"#, escape_xml(labels.get(status)));
+    }
+    let _ = writeln!(
+        output,
+        r#"<th class="num">% Complete</th><th class="num">Change</th><th class="num">Migrated / day</th></tr></thead>"#
+    );
+    let _ = writeln!(output, "<tbody>");
+    for point in trend.points.iter().rev() {
+        let stats = &point.stats;
+        let _ = write!(
+            output,
+            r#"<tr><td>{}</td><td>{}</td><td class="num">{}</td>"#,
+            date(point.recorded_at),
+            escape_xml(point.commit.as_deref().unwrap_or("-")),
+            stats.total
+        );
+        for count in [stats.legacy, stats.partial, stats.migrated, stats.no_models] {
+            let _ = write!(output, r#"<td class="num">{count}</td>"#);
+        }
+        let _ = writeln!(
+            output,
+            r#"<td class="num">{:.1}%</td><td class="num">{}</td><td class="num">{}</td></tr>"#,
+            point.progress_percent(),
+            point.change.map_or_else(String::new, |change| format!("{change:+.1}")),
+            point
+                .migrated_per_day
+                .map_or_else(String::new, |per_day| format!("{per_day:+.1}"))
+        );
+    }
+    let _ = writeln!(output, "</tbody>");
+    let _ = writeln!(output, "</table>");
+    let _ = writeln!(output, "</body>");
+    let _ = writeln!(output, "</html>");
+    output
+}
+
+/// Writes an SVG line chart of migrated and started percentages over time.
+///
+/// Points are spaced by recording time, or evenly if every scan was
+/// recorded at the same time.
+#[allow(clippy::cast_precision_loss)] // Acceptable for chart coordinates
+fn write_trend_chart(output: &mut String, trend: &Trend, date: &dyn Fn(u64) -> String) {
+    use std::fmt::Write;
+
+    const WIDTH: f64 = 800.0;
+    const HEIGHT: f64 = 260.0;
+    const LEFT: f64 = 44.0;
+    const RIGHT: f64 = 16.0;
+    const TOP: f64 = 12.0;
+    const BOTTOM: f64 = 28.0;
+
+    let (Some(first), Some(last)) = (trend.points.first(), trend.points.last()) else {
+        return;
+    };
+    let span = last.recorded_at - first.recorded_at;
+    let count = trend.points.len();
+    let x = |index: usize, recorded_at: u64| {
+        let fraction = if span > 0 {
+            (recorded_at - first.recorded_at) as f64 / span as f64
+        } else if count > 1 {
+            index as f64 / (count - 1) as f64
+        } else {
+            0.5
+        };
+        LEFT + fraction * (WIDTH - LEFT - RIGHT)
+    };
+    let y = |percent: f64| TOP + (100.0 - percent) / 100.0 * (HEIGHT - TOP - BOTTOM);
+
+    let _ = writeln!(
+        output,
+        r#"<svg class="trend-chart" viewBox="0 0 {WIDTH} {HEIGHT}" role="img" aria-label="Migration progress over time">"#
+    );
+    for percent in [0.0, 25.0, 50.0, 75.0, 100.0] {
+        let line_y = y(percent);
+        let _ = writeln!(
+            output,
+            r#"<line class="grid" x1="{LEFT}" x2="{}" y1="{line_y:.1}" y2="{line_y:.1}"/><text x="{}" y="{:.1}" text-anchor="end">{percent}%</text>"#,
+            WIDTH - RIGHT,
+            LEFT - 6.0,
+            line_y + 4.0
+        );
+    }
+    for (anchor, point) in [("start", first), ("end", last)] {
+        let _ = writeln!(
+            output,
+            r#"<text x="{:.1}" y="{}" text-anchor="{anchor}">{}</text>"#,
+            if anchor == "start" { LEFT } else { WIDTH - RIGHT },
+            HEIGHT - 8.0,
+            date(point.recorded_at)
+        );
+    }
+
+    let started =
+        |stats: &StatsSnapshot| html_share(stats.migrated + stats.partial, stats.with_models());
+    let series = [
+        ("partial", started as fn(&StatsSnapshot) -> f64),
+        ("migrated", StatsSnapshot::progress_percent),
+    ];
+    for (class, percent) in series {
+        let points: Vec<String> = trend
+            .points
+            .iter()
+            .enumerate()
+            .map(|(index, point)| {
+                format!("{:.1},{:.1}", x(index, point.recorded_at), y(percent(&point.stats)))
+            })
+            .collect();
+        let _ = writeln!(
+            output,
+            r#"<polyline class="line {class}" points="{}"/>"#,
+            points.join(" ")
+        );
+        for (index, point) in trend.points.iter().enumerate() {
+            let value = percent(&point.stats);
+            let _ = writeln!(
+                output,
+                r#"<circle class="{class}" cx="{:.1}" cy="{:.1}" r="3"><title>{}{}: {value:.1}%</title></circle>"#,
+                x(index, point.recorded_at),
+                y(value),
+                date(point.recorded_at),
+                point
+                    .commit
+                    .as_deref()
+                    .map_or_else(String::new, |commit| format!(" {}", escape_xml(commit)))
+            );
+        }
+    }
+    let _ = writeln!(output, "</svg>");
+}
+
+/// Returns `part` as a percentage of `whole` (0 when `whole` is 0).
+fn html_share(part: u64, whole: u64) -> f64 {
+    if whole == 0 {
+        return 0.0;
+    }
+    #[allow(clippy::cast_precision_loss)] // Acceptable for display
+    let share = part as f64 / whole as f64 * 100.0;
+    share
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{test_files, test_metadata};
+
+    #[test]
+    fn test_html_report_rows() {
+        let (files, stats) = test_files();
+        let html = generate_html_report(
+            &test_metadata(),
+            &stats,
+            &[],
+            &files,
+            &[],
+            false,
+            &StatusLabels::default(),
+        );
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Migration Report · /repo/src/app</title>"));
+        assert!(html.contains(
+            r#"<div class="card legacy"><span class="value status">1</span><span class="label">Legacy</span></div>"#
+        ));
+        assert!(html.contains(
+            r#"<tr data-status="legacy"><td class="path">jobs/job-list.ts</td><td class="status legacy">Legacy</td><td class="num" data-sort="1">1</td><td class="num" data-sort="1">1</td><td class="num" data-sort="0">0</td></tr>"#
+        ));
+        assert!(html.trim_end().ends_with("</html>"));
+    }
+
+    #[test]
+    fn test_html_report_escapes_paths() {
+        let (mut files, stats) = test_files();
+        files[0].path = Utf8Path::new("/repo/src/app/a<b>&c.ts").to_owned();
+        let html = generate_html_report(
+            &test_metadata(),
+            &stats,
+            &[],
+            &files,
+            &[],
+            false,
+            &StatusLabels::default(),
+        );
+        assert!(html.contains("a&lt;b&gt;&amp;c.ts"));
+        assert!(!html.contains("a<b>"));
+    }
+}
@@ -0,0 +1,381 @@
+//! JSON reports: the `report --format json` layout (also the data seen by
+//! `--template` templates), the model report and the registry export.
+
+use std::io::Write;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use ch_core::{FileInfo, ImportKind, ModelDefinition, ModelRegistry};
+use ch_scanner::{rollup_by_directory, ModelConsumers, OwnerStats, StatsSnapshot};
+use tera::Tera;
+
+use super::{sorted_models, ReportError, ReportMetadata};
+use crate::ReportSections;
+
+/// Version of the JSON report layout, bumped on incompatible changes.
+///
+/// Must match the `schema_version` constant in [`JSON_REPORT_SCHEMA`].
+const JSON_REPORT_SCHEMA_VERSION: u32 = 2;
+
+/// JSON Schema describing the JSON report, printed by `report --print-schema`.
+pub(crate) const JSON_REPORT_SCHEMA: &str = include_str!("../report.schema.json");
+
+/// A directory in the `directories` section of the JSON report.
+#[derive(serde::Serialize)]
+struct JsonDirectory {
+    path: Utf8PathBuf,
+    stats: StatsSnapshot,
+    progress_percent: f64,
+}
+
+/// A file in the `dead_imports` section of the JSON report.
+#[derive(serde::Serialize)]
+struct JsonDeadImports<'a> {
+    path: &'a Utf8Path,
+    names: Vec<&'a str>,
+}
+
+/// An entry in the `legacy_imports` section of the JSON report.
+#[derive(serde::Serialize)]
+struct JsonLegacyImport<'a> {
+    file: &'a Utf8Path,
+    line: u32,
+    column: u32,
+    kind: ImportKind,
+    module: &'a str,
+    names: &'a [String],
+}
+
+/// An import from a shared directory naming exports the registry doesn't
+/// know, pointing at a registry gap or a parsing blind spot.
+#[derive(serde::Serialize)]
+pub(crate) struct UnmatchedImport<'a> {
+    pub(super) file: &'a Utf8Path,
+    pub(super) line: u32,
+    column: u32,
+    kind: ImportKind,
+    pub(super) module: &'a str,
+    /// The unmatched names only.
+    pub(super) names: Vec<&'a str>,
+}
+
+/// Finds the imports of `files` naming exports missing from `registry` (see
+/// [`ModelRegistry::unmatched_names`]), in file order.
+pub(crate) fn unmatched_imports<'a>(
+    files: &'a [FileInfo],
+    registry: &ModelRegistry,
+) -> Vec<UnmatchedImport<'a>> {
+    files
+        .iter()
+        .flat_map(|file| file.imports.iter().map(move |import| (file, import)))
+        .filter_map(|(file, import)| {
+            let names = registry.unmatched_names(import);
+            (!names.is_empty()).then(|| UnmatchedImport {
+                file: &file.path,
+                line: import.location.line,
+                column: import.location.column,
+                kind: import.kind,
+                module: &import.path,
+                names,
+            })
+        })
+        .collect()
+}
+
+/// The JSON report, also the data passed to `--template` templates.
+///
+/// The report starts with a `schema_version` (see
+/// [`JSON_REPORT_SCHEMA_VERSION`]) so consumers can detect layout changes.
+///
+/// Per-team statistics are included only when `teams` is non-empty, and the
+/// `dead_imports` section (files with legacy imports that are never used)
+/// only when there are any. The `directories` section rolls the files up per
+/// directory under the app path, `sections.directory_depth` levels deep (one by
+/// default). With
+/// `sections.imports`, a `legacy_imports` section lists every legacy import
+/// with its file, line, column, kind and names. Imports naming exports the
+/// registry doesn't know are listed in `unmatched_imports`, and files that
+/// couldn't be scanned in `errors`, if there are any.
+#[derive(serde::Serialize)]
+pub(crate) struct JsonReport<'a> {
+    schema_version: u32,
+    metadata: &'a ReportMetadata,
+    stats: &'a StatsSnapshot,
+    #[serde(skip_serializing_if = "<[OwnerStats]>::is_empty")]
+    teams: &'a [OwnerStats],
+    directories: Vec<JsonDirectory>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    dead_imports: Vec<JsonDeadImports<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    legacy_imports: Option<Vec<JsonLegacyImport<'a>>>,
+    #[serde(skip_serializing_if = "<[UnmatchedImport]>::is_empty")]
+    unmatched_imports: &'a [UnmatchedImport<'a>],
+    #[serde(skip_serializing_if = "<[ReportError]>::is_empty")]
+    errors: &'a [ReportError],
+    files: &'a [FileInfo],
+}
+
+impl<'a> JsonReport<'a> {
+    /// Builds the report of `files`.
+    pub(crate) fn new(
+        metadata: &'a ReportMetadata,
+        stats: &'a StatsSnapshot,
+        teams: &'a [OwnerStats],
+        files: &'a [FileInfo],
+        unmatched_imports: &'a [UnmatchedImport<'a>],
+        errors: &'a [ReportError],
+        sections: ReportSections<'_>,
+    ) -> Self {
+        let depth = sections.directory_depth.unwrap_or(1);
+        Self {
+            schema_version: JSON_REPORT_SCHEMA_VERSION,
+            metadata,
+            stats,
+            teams,
+            directories: rollup_by_directory(files, &metadata.app_path, depth)
+                .into_iter()
+                .map(|directory| JsonDirectory {
+                    progress_percent: (directory.stats.progress_percent() * 10.0).round()
+                        / 10.0,
+                    path: directory.path,
+                    stats: directory.stats,
+                })
+                .collect(),
+            dead_imports: files
+                .iter()
+                .map(|file| JsonDeadImports {
+                    path: &file.path,
+                    names: file.dead_legacy_imports(),
+                })
+                .filter(|dead| !dead.names.is_empty())
+                .collect(),
+            legacy_imports: sections.imports.then(|| {
+                files
+                    .iter()
+                    .flat_map(|file| file.legacy_imports().map(move |import| (file, import)))
+                    .map(|(file, import)| JsonLegacyImport {
+                        file: &file.path,
+                        line: import.location.line,
+                        column: import.location.column,
+                        kind: import.kind,
+                        module: &import.path,
+                        names: &import.names,
+                    })
+                    .collect()
+            }),
+            unmatched_imports,
+            errors,
+            files,
+        }
+    }
+}
+
+/// Writes a JSON report to `writer`, serializing it as it goes.
+pub(crate) fn write_json_report(writer: &mut dyn Write, report: &JsonReport<'_>) -> color_eyre::Result<()> {
+    serde_json::to_writer_pretty(writer, report)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to write JSON report: {}", e))
+}
+
+/// Renders the JSON report data with a Tera template.
+///
+/// The template sees the fields of the JSON report (`stats`, `files`,
+/// `directories`, ...) as top-level variables. A `.tera` extension is ignored
+/// when choosing whether to escape values, so `status.html.tera` is
+/// autoescaped like any `.html` template.
+///
+/// # Errors
+///
+/// Returns an error if the template can't be read, parsed or rendered.
+pub(crate) fn render_template_report(
+    template: &Utf8Path,
+    report: &JsonReport<'_>,
+) -> color_eyre::Result<String> {
+    use color_eyre::eyre::WrapErr;
+
+    let source = std::fs::read_to_string(template)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to read template {}: {}", template, e))?;
+    let name = template.file_name().unwrap_or(template.as_str());
+    let name = name.strip_suffix(".tera").unwrap_or(name);
+
+    let mut tera = Tera::default();
+    tera.add_raw_template(name, &source)
+        .wrap_err_with(|| format!("Invalid template {template}"))?;
+    let context = tera::Context::from_serialize(report)
+        .wrap_err("Failed to pass the report to the template")?;
+    tera.render(name, &context)
+        .wrap_err_with(|| format!("Failed to render template {template}"))
+}
+
+/// Generates a JSON model report: the metadata and one entry per legacy
+/// model, most consumed first.
+pub(crate) fn generate_json_model_report(
+    metadata: &ReportMetadata,
+    models: &[ModelConsumers],
+) -> color_eyre::Result<String> {
+    #[derive(serde::Serialize)]
+    struct Model<'a> {
+        consumers: usize,
+        #[serde(flatten)]
+        model: &'a ModelConsumers,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Report<'a> {
+        metadata: &'a ReportMetadata,
+        models: Vec<Model<'a>>,
+    }
+
+    let report = Report {
+        metadata,
+        models: models
+            .iter()
+            .map(|model| Model {
+                consumers: model.consumer_count(),
+                model,
+            })
+            .collect(),
+    };
+    serde_json::to_string_pretty(&report)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to serialize JSON: {}", e))
+}
+
+/// Generates a JSON export of the model registry.
+///
+/// Legacy models include the name of their `shared_2023/` counterpart (if
+/// any) as `modern_equivalent`.
+pub(crate) fn generate_json_registry(registry: &ModelRegistry) -> color_eyre::Result<String> {
+    #[derive(serde::Serialize)]
+    struct LegacyModel<'a> {
+        #[serde(flatten)]
+        model: &'a ModelDefinition,
+        modern_equivalent: Option<&'a str>,
+    }
+
+    #[derive(serde::Serialize)]
+    struct RegistryExport<'a> {
+        legacy: Vec<LegacyModel<'a>>,
+        modern: Vec<&'a ModelDefinition>,
+    }
+
+    let export = RegistryExport {
+        legacy: sorted_models(registry.iter_legacy_models())
+            .into_iter()
+            .map(|model| LegacyModel {
+                model,
+                modern_equivalent: registry
+                    .modern_model_for(&model.name)
+                    .map(|modern| modern.name.as_str()),
+            })
+            .collect(),
+        modern: sorted_models(registry.iter_modern_models()),
+    };
+    let json = serde_json::to_string_pretty(&export)?;
+    Ok(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{test_files, test_metadata};
+
+    #[test]
+    fn test_json_report_layout() {
+        let metadata = test_metadata();
+        let (files, stats) = test_files();
+        let report =
+            JsonReport::new(&metadata, &stats, &[], &files, &[], &[], ReportSections::default());
+        let mut output = Vec::new();
+        assert!(write_json_report(&mut output, &report).is_ok());
+
+        let json: serde_json::Value = serde_json::from_slice(&output).unwrap_or_default();
+        assert_eq!(json["schema_version"], JSON_REPORT_SCHEMA_VERSION);
+        assert_eq!(json["metadata"]["app_path"], "/repo/src/app");
+        assert_eq!(json["metadata"]["filters"]["skip_dirs"], serde_json::json!(["node_modules"]));
+        assert_eq!(json["stats"]["total"], 3);
+        assert_eq!(json["stats"]["legacy"], 1);
+        assert_eq!(json["directories"][1]["path"], "jobs");
+        assert_eq!(json["directories"][1]["progress_percent"], 0.0);
+        assert_eq!(json["dead_imports"][0]["names"], serde_json::json!(["Job"]));
+        assert_eq!(json["files"][0]["path"], "/repo/src/app/jobs/job-list.ts");
+        assert_eq!(json["files"][0]["imports"][0]["kind"], "named");
+        assert_eq!(json["files"][1]["status"], "partial");
+    }
+    #[test]
+    fn test_json_report_matches_schema() {
+        use ch_core::{ImportInfo, MigrationStatus, SourceLocation};
+        use ch_scanner::ErrorCategory;
+
+        let mut metadata = test_metadata();
+        metadata.additional_app_paths.push(Utf8PathBuf::from("/repo/src/admin"));
+        metadata.filters.codeowners = Some(Utf8PathBuf::from("/repo/CODEOWNERS"));
+        metadata.filters.ignore_imports.push("shared/models/logger".to_owned());
+        metadata.filters.selection.status.push(MigrationStatus::Legacy);
+        metadata.filters.selection.min_legacy_imports = Some(1);
+
+        let (mut files, mut stats) = test_files();
+        stats.reused = 1;
+        stats.parsed.record(ch_scanner::FileKind::Ts, 5000);
+        stats.skipped.duplicate = 2;
+        let namespace = ImportInfo::new(
+            "../../shared/models",
+            ImportKind::Namespace,
+            vec!["Models".to_owned()].into(),
+            Some(ch_core::ModelSource::SharedLegacy),
+            SourceLocation::new(4, 0, 80),
+        );
+        let mut namespace = namespace.with_alias("Job", "LegacyJob");
+        namespace.members.push("Job".to_owned());
+        files[0].imports.push(namespace.clone());
+        files[0].ignored_imports.push(namespace);
+        files[0].owners.push("@jobs-team".to_owned());
+        files[1].imports.push(ImportInfo::new(
+            "../../shared/models/contract",
+            ImportKind::Require,
+            vec!["contract".to_owned()].into(),
+            Some(ch_core::ModelSource::SharedLegacy),
+            SourceLocation::new(5, 0, 120),
+        ));
+
+        let unmatched = [UnmatchedImport {
+            file: &files[0].path,
+            line: 4,
+            column: 0,
+            kind: ImportKind::Namespace,
+            module: "../../shared/models",
+            names: vec!["Models"],
+        }];
+        let errors: Vec<_> = [
+            ErrorCategory::Read,
+            ErrorCategory::Transient,
+            ErrorCategory::Permission,
+            ErrorCategory::Encoding,
+            ErrorCategory::Parse,
+            ErrorCategory::Internal,
+            ErrorCategory::Skipped,
+            ErrorCategory::Other,
+        ]
+        .into_iter()
+        .map(|category| ReportError {
+            path: Utf8PathBuf::from(format!("/repo/src/app/{}.ts", category.key())),
+            category,
+            message: "failed".to_owned(),
+        })
+        .collect();
+        let sections = ReportSections { imports: true, ..ReportSections::default() };
+        let report =
+            JsonReport::new(&metadata, &stats, &[], &files, &unmatched, &errors, sections);
+        let mut output = Vec::new();
+        assert!(write_json_report(&mut output, &report).is_ok());
+
+        let schema: serde_json::Value =
+            serde_json::from_str(JSON_REPORT_SCHEMA).unwrap_or_default();
+        assert_eq!(schema["properties"]["schema_version"]["const"], JSON_REPORT_SCHEMA_VERSION);
+        let validator = jsonschema::validator_for(&schema);
+        assert!(validator.is_ok(), "invalid schema: {:?}", validator.err());
+        let instance: serde_json::Value = serde_json::from_slice(&output).unwrap_or_default();
+        if let Ok(validator) = validator {
+            let errors: Vec<_> =
+                validator.iter_errors(&instance).map(|error| error.to_string()).collect();
+            assert!(errors.is_empty(), "report doesn't match its schema: {errors:#?}");
+        }
+    }
+}
@@ -0,0 +1,451 @@
+//! Markdown reports: scan summaries, wiki status pages, model reports and
+//! pull-request comments.
+
+use std::collections::BTreeMap;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use ch_core::{FileInfo, MigrationStatus, StatusLabels};
+use ch_scanner::{
+    directory_group, rollup_by_directory, ModelConsumers, OwnerStats, ScanDiff, StatsSnapshot,
+};
+
+use super::json::UnmatchedImport;
+use super::{escape_xml, ReportMetadata};
+
+/// Generates a Markdown scan summary.
+///
+/// Includes the status table, per-team table (when `teams` is non-empty),
+/// and a collapsible `<details>` list of legacy files with paths relative
+/// to `root`.
+pub(crate) fn generate_markdown_summary(
+    stats: &StatsSnapshot,
+    teams: &[OwnerStats],
+    legacy_files: &[FileInfo],
+    root: &Utf8Path,
+    labels: &StatusLabels,
+) -> String {
+    use std::fmt::Write;
+
+    let label = |status| escape_markdown(labels.get(status));
+    let mut output = String::from("## Migration Status Summary\n\n");
+    let _ = writeln!(output, "| Status | Files |");
+    let _ = writeln!(output, "| --- | ---: |");
+    let _ = writeln!(output, "| {} | {} |", label(MigrationStatus::Legacy), stats.legacy);
+    let _ = writeln!(output, "| {} | {} |", label(MigrationStatus::Partial), stats.partial);
+    let _ = writeln!(output, "| {} | {} |", label(MigrationStatus::Migrated), stats.migrated);
+    let _ = writeln!(output, "| {} | {} |", label(MigrationStatus::NoModels), stats.no_models);
+    let _ = writeln!(output, "| Errors | {} |", stats.errors);
+    let _ = writeln!(output, "| **Total** | **{}** |", stats.total);
+    let _ = writeln!(output);
+    let _ = writeln!(
+        output,
+        "**Migration progress:** {:.1}% · **Files needing work:** {}",
+        stats.progress_percent(),
+        stats.needs_migration()
+    );
+
+    if !teams.is_empty() {
+        let _ = writeln!(output);
+        let _ = writeln!(output, "### By Team");
+        let _ = writeln!(output);
+        let _ = writeln!(
+            output,
+            "| Team | Files | {} | {} | {} | % Complete |",
+            label(MigrationStatus::Legacy),
+            label(MigrationStatus::Partial),
+            label(MigrationStatus::Migrated)
+        );
+        let _ = writeln!(output, "| --- | ---: | ---: | ---: | ---: | ---: |");
+        for team in teams {
+            let _ = writeln!(
+                output,
+                "| {} | {} | {} | {} | {} | {:.1}% |",
+                escape_markdown(&team.owner),
+                team.stats.total,
+                team.stats.legacy,
+                team.stats.partial,
+                team.stats.migrated,
+                team.stats.progress_percent()
+            );
+        }
+    }
+
+    if !legacy_files.is_empty() {
+        let mut paths: Vec<_> = legacy_files
+            .iter()
+            .map(|file| {
+                let path = file.path.strip_prefix(root).unwrap_or(&file.path);
+                (path, file.legacy_imports().count())
+            })
+            .collect();
+        paths.sort_unstable();
+
+        let _ = writeln!(output);
+        let _ = writeln!(output, "<details>");
+        let _ = writeln!(
+            output,
+            "<summary>{} files ({})</summary>",
+            label(MigrationStatus::Legacy),
+            paths.len()
+        );
+        let _ = writeln!(output);
+        for (path, legacy_count) in paths {
+            let _ = writeln!(output, "- `{path}` (legacy imports: {legacy_count})");
+        }
+        let _ = writeln!(output);
+        let _ = writeln!(output, "</details>");
+    }
+
+    output
+}
+
+/// Hidden marker at the top of PR comments, so bots can find and update their
+/// earlier comment instead of posting a new one.
+const PR_COMMENT_MARKER: &str = "<!-- ch-migrate-pr-comment -->";
+
+/// Number of new legacy files listed in a PR comment.
+const PR_COMMENT_NEW_FILES: usize = 5;
+
+/// Generates a short Markdown block to post as a pull-request comment.
+///
+/// Shows the progress and file counts per status and, with a baseline
+/// (`previous`), how they changed, how many files were migrated, regressed
+/// or added with legacy imports, and the new files with the most legacy
+/// imports.
+pub(crate) fn generate_pr_comment(
+    metadata: &ReportMetadata,
+    stats: &StatsSnapshot,
+    previous: Option<(&ScanDiff, &StatsSnapshot)>,
+    labels: &StatusLabels,
+) -> String {
+    use std::fmt::Write;
+
+    let signed = |now: u64, before: u64| match now.cmp(&before) {
+        std::cmp::Ordering::Equal => "0".to_owned(),
+        std::cmp::Ordering::Greater => format!("+{}", now - before),
+        std::cmp::Ordering::Less => format!("-{}", before - now),
+    };
+    let mut output = format!("{PR_COMMENT_MARKER}\n");
+    let _ = write!(output, "### 🧭 Migration: {:.1}% migrated", stats.progress_percent());
+    if let Some((_, before)) = previous {
+        let change = stats.progress_percent() - before.progress_percent();
+        let trend = if change > 0.0 {
+            "📈"
+        } else if change < 0.0 {
+            "📉"
+        } else {
+            "➖"
+        };
+        let _ = write!(output, " {trend} {change:+.1} pts");
+    }
+    let _ = writeln!(output);
+    let _ = writeln!(output);
+
+    if previous.is_some() {
+        let _ = writeln!(output, "| | Files | Change |");
+        let _ = writeln!(output, "| --- | ---: | ---: |");
+    } else {
+        let _ = writeln!(output, "| | Files |");
+        let _ = writeln!(output, "| --- | ---: |");
+    }
+    for (emoji, status, count, before) in [
+        ("✅", MigrationStatus::Migrated, stats.migrated, previous.map(|(_, b)| b.migrated)),
+        ("🟡", MigrationStatus::Partial, stats.partial, previous.map(|(_, b)| b.partial)),
+        ("🔴", MigrationStatus::Legacy, stats.legacy, previous.map(|(_, b)| b.legacy)),
+    ] {
+        let label = escape_markdown(labels.get(status));
+        match before {
+            Some(before) => {
+                let change = signed(count, before);
+                let _ = writeln!(output, "| {emoji} {label} | {count} | {change} |");
+            }
+            None => {
+                let _ = writeln!(output, "| {emoji} {label} | {count} |");
+            }
+        }
+    }
+
+    if let Some((diff, _)) = previous {
+        let _ = writeln!(output);
+        let _ = writeln!(
+            output,
+            "🎉 {} newly migrated · ⚠️ {} regressed · 🆕 {} new with legacy imports",
+            diff.migrated.len(),
+            diff.regressed.len(),
+            diff.new_legacy.len()
+        );
+
+        if !diff.new_legacy.is_empty() {
+            let mut new_files: Vec<_> = diff.new_legacy.iter().collect();
+            new_files.sort_by_key(|change| std::cmp::Reverse(change.remaining.len()));
+            let _ = writeln!(output);
+            let _ = writeln!(output, "**New legacy files**");
+            let _ = writeln!(output);
+            for change in new_files.iter().take(PR_COMMENT_NEW_FILES) {
+                let path = change.path.strip_prefix(&metadata.app_path).unwrap_or(&change.path);
+                let _ = write!(output, "- `{path}`");
+                if !change.remaining.is_empty() {
+                    let _ = write!(output, ": {}", change.remaining.join(", "));
+                }
+                let _ = writeln!(output);
+            }
+            if new_files.len() > PR_COMMENT_NEW_FILES {
+                let _ = writeln!(
+                    output,
+                    "- …and {} more",
+                    new_files.len() - PR_COMMENT_NEW_FILES
+                );
+            }
+        }
+    }
+
+    let _ = writeln!(output);
+    let _ = writeln!(
+        output,
+        "<sub>ch-migrate {} · {} files scanned in {} ms</sub>",
+        metadata.tool_version, stats.total, metadata.duration_ms
+    );
+    output
+}
+
+/// Generates a Markdown report for posting as a wiki status page.
+///
+/// After the summary tables from [`generate_markdown_summary`], files are
+/// listed in a collapsible `<details>` section per top-level directory whose
+/// title shows the directory's progress. Paths are relative to the app path.
+/// The registry counts and any unmatched imports come last.
+pub(crate) fn generate_markdown_report(
+    metadata: &ReportMetadata,
+    stats: &StatsSnapshot,
+    teams: &[OwnerStats],
+    files: &[FileInfo],
+    unmatched: &[UnmatchedImport<'_>],
+    labels: &StatusLabels,
+) -> String {
+    use std::fmt::Write;
+
+    let root = &metadata.app_path;
+    let label = |status| escape_markdown(labels.get(status));
+    let mut output = format!("# Migration Report · {}
+
+", escape_markdown(root.as_str()));
+    let _ = writeln!(
+        output,
+        "Generated {} by ch-migrate {} in {} ms.",
+        metadata.generated_at, metadata.tool_version, metadata.duration_ms
+    );
+    let _ = writeln!(output);
+    output.push_str(&generate_markdown_summary(stats, teams, &[], root, labels));
+
+    let mut groups: BTreeMap<Utf8PathBuf, Vec<&FileInfo>> = BTreeMap::new();
+    for file in files {
+        groups.entry(directory_group(&file.path, root, 1)).or_default().push(file);
+    }
+
+    let _ = writeln!(output);
+    let _ = writeln!(output, "## By Directory");
+    for directory in rollup_by_directory(files, root, 1) {
+        let dir_stats = &directory.stats;
+        let _ = writeln!(output);
+        let _ = writeln!(output, "<details>");
+        let _ = writeln!(
+            output,
+            "<summary><b>{}</b> · {} files · {:.1}% complete · {} {} · {} {}</summary>",
+            escape_xml(directory.path.as_str()),
+            dir_stats.total,
+            dir_stats.progress_percent(),
+            dir_stats.legacy,
+            label(MigrationStatus::Legacy),
+            dir_stats.partial,
+            label(MigrationStatus::Partial)
+        );
+        let _ = writeln!(output);
+        let _ = writeln!(output, "| File | Status | Legacy imports | Migrated imports |");
+        let _ = writeln!(output, "| --- | --- | ---: | ---: |");
+        let mut dir_files = groups.remove(&directory.path).unwrap_or_default();
+        dir_files.sort_by(|a, b| a.path.cmp(&b.path));
+        for file in dir_files {
+            let path = file.path.strip_prefix(root).unwrap_or(&file.path);
+            let _ = writeln!(
+                output,
+                "| `{}` | {} | {} | {} |",
+                escape_markdown(path.as_str()),
+                label(file.status),
+                file.legacy_imports().count(),
+                file.migrated_imports().count()
+            );
+        }
+        let _ = writeln!(output);
+        let _ = writeln!(output, "</details>");
+    }
+
+    let registry = &metadata.registry;
+    let _ = writeln!(output);
+    let _ = writeln!(output, "## Registry");
+    let _ = writeln!(output);
+    let _ = writeln!(
+        output,
+        "| Legacy models | Modern models | Ported | Legacy exports | Modern exports | Renames |"
+    );
+    let _ = writeln!(output, "| ---: | ---: | ---: | ---: | ---: | ---: |");
+    let _ = writeln!(
+        output,
+        "| {} | {} | {} | {} | {} | {} |",
+        registry.legacy_models,
+        registry.modern_models,
+        registry.ported_models,
+        registry.legacy_exports,
+        registry.modern_exports,
+        registry.renames
+    );
+
+    if !unmatched.is_empty() {
+        let _ = writeln!(output);
+        let _ = writeln!(output, "## Unmatched Imports");
+        let _ = writeln!(output);
+        let _ = writeln!(output, "Imports from a shared directory naming no registered export.");
+        let _ = writeln!(output);
+        let _ = writeln!(output, "| File | Line | Module | Names |");
+        let _ = writeln!(output, "| --- | ---: | --- | --- |");
+        for import in unmatched {
+            let path = import.file.strip_prefix(root).unwrap_or(import.file);
+            let _ = writeln!(
+                output,
+                "| `{}` | {} | `{}` | {} |",
+                escape_markdown(path.as_str()),
+                import.line,
+                escape_markdown(import.module),
+                escape_markdown(&import.names.join(", "))
+            );
+        }
+    }
+
+    output
+}
+
+/// Generates a Markdown model report.
+///
+/// A table lists each legacy model's consumer count and modern equivalent,
+/// followed by a collapsible `<details>` section per model listing its
+/// consumer files relative to the app path.
+pub(crate) fn generate_markdown_model_report(metadata: &ReportMetadata, models: &[ModelConsumers]) -> String {
+    use std::fmt::Write;
+
+    let root = &metadata.app_path;
+    let mut output = format!("# Legacy Models · {}\n\n", escape_markdown(root.as_str()));
+    let _ = writeln!(
+        output,
+        "Generated {} by ch-migrate {} in {} ms.",
+        metadata.generated_at, metadata.tool_version, metadata.duration_ms
+    );
+    let _ = writeln!(output);
+    let ported = models.iter().filter(|model| model.modern_equivalent.is_some()).count();
+    let _ = writeln!(
+        output,
+        "{} legacy models are imported; {ported} have a `shared_2023/` equivalent.",
+        models.len()
+    );
+    let _ = writeln!(output);
+    let _ = writeln!(output, "| Model | Consumers | Modern equivalent |");
+    let _ = writeln!(output, "| --- | ---: | --- |");
+    for model in models {
+        let _ = writeln!(
+            output,
+            "| `{}` | {} | {} |",
+            escape_markdown(&model.name),
+            model.consumer_count(),
+            model
+                .modern_equivalent
+                .as_deref()
+                .map_or_else(|| "—".to_owned(), |name| format!("`{}`", escape_markdown(name)))
+        );
+    }
+
+    let _ = writeln!(output);
+    let _ = writeln!(output, "## Consumers");
+    for model in models {
+        let _ = writeln!(output);
+        let _ = writeln!(output, "<details>");
+        let _ = writeln!(
+            output,
+            "<summary><b>{}</b> · {} files</summary>",
+            escape_xml(&model.name),
+            model.consumer_count()
+        );
+        let _ = writeln!(output);
+        for path in &model.files {
+            let path = path.strip_prefix(root).unwrap_or(path);
+            let _ = writeln!(output, "- `{path}`");
+        }
+        let _ = writeln!(output);
+        let _ = writeln!(output, "</details>");
+    }
+
+    output
+}
+
+/// Escapes characters that would break a Markdown table cell.
+fn escape_markdown(s: &str) -> String {
+    s.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{test_files, test_metadata};
+
+    #[test]
+    fn test_markdown_summary() {
+        let (files, stats) = test_files();
+        let legacy: Vec<_> = files.iter().filter(|file| file.needs_migration()).cloned().collect();
+        let summary = generate_markdown_summary(
+            &stats,
+            &[],
+            &legacy,
+            Utf8Path::new("/repo/src/app"),
+            &StatusLabels::default(),
+        );
+        assert!(summary.starts_with(
+            "## Migration Status Summary\n\n\
+             | Status | Files |\n\
+             | --- | ---: |\n\
+             | Legacy | 1 |\n\
+             | Partial | 1 |\n\
+             | Migrated | 1 |\n\
+             | No Models | 0 |\n\
+             | Errors | 0 |\n\
+             | **Total** | **3** |\n\n\
+             **Migration progress:** 33.3% · **Files needing work:** 2\n"
+        ));
+        assert!(summary.contains(
+            "<summary>Legacy files (2)</summary>\n\n\
+             - `jobs/job-edit.ts` (legacy imports: 1)\n\
+             - `jobs/job-list.ts` (legacy imports: 1)\n"
+        ));
+    }
+
+    #[test]
+    fn test_markdown_report_groups_by_directory() {
+        let (files, stats) = test_files();
+        let report = generate_markdown_report(
+            &test_metadata(),
+            &stats,
+            &[],
+            &files,
+            &[],
+            &StatusLabels::default(),
+        );
+        assert!(report.starts_with(
+            "# Migration Report · /repo/src/app\n\n\
+             Generated 2026-01-05T09:30:00+01:00 by ch-migrate 1.2.3 in 1500 ms.\n"
+        ));
+        assert!(report.contains(
+            "<summary><b>jobs</b> · 2 files · 0.0% complete · 1 Legacy · 1 Partial</summary>\n\n\
+             | File | Status | Legacy imports | Migrated imports |\n\
+             | --- | --- | ---: | ---: |\n\
+             | `jobs/job-edit.ts` | Partial | 1 | 1 |\n\
+             | `jobs/job-list.ts` | Legacy | 1 | 0 |\n"
+        ));
+        assert!(report.contains("| `home.ts` | Migrated | 0 | 1 |\n"));
+    }
+}
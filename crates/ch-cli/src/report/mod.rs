@@ -0,0 +1,293 @@
+//! Report rendering.
+//!
+//! Every report format has its own submodule, which renders scan results
+//! (files, statistics and [`ReportMetadata`]) into a string or bytes;
+//! `main` decides what to scan and where the output goes.
+
+mod badge;
+mod baseline;
+mod binary;
+mod csv;
+mod github;
+mod graph;
+mod html;
+mod json;
+mod markdown;
+mod sarif;
+mod text;
+mod xlsx;
+
+use std::time::Duration;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use ch_core::{Config, ImportInfo, ModelDefinition, ModelRegistry, RegistryStats};
+use ch_scanner::{ErrorCategory, ScanError, Scanner};
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+
+pub(crate) use badge::generate_badge_svg;
+pub(crate) use baseline::{diff_baseline, load_baseline};
+pub(crate) use binary::{generate_binary_report, load_binary_report};
+pub(crate) use csv::{
+    generate_csv_model_report, generate_csv_registry, generate_csv_report, generate_trend_csv,
+};
+pub(crate) use github::{generate_github_annotations, generate_github_report};
+pub(crate) use graph::{generate_dot_report, generate_mermaid_report, MERMAID_TOP_MODELS};
+pub(crate) use html::{generate_diff_html, generate_html_report, generate_trend_html};
+pub(crate) use json::{
+    generate_json_model_report, generate_json_registry, render_template_report,
+    unmatched_imports, write_json_report, JsonReport, JSON_REPORT_SCHEMA,
+};
+pub(crate) use markdown::{
+    generate_markdown_model_report, generate_markdown_report, generate_markdown_summary,
+    generate_pr_comment,
+};
+pub(crate) use sarif::generate_sarif_report;
+pub(crate) use text::{generate_impact_text, generate_parity_text, generate_plan_text};
+pub(crate) use xlsx::generate_xlsx_report;
+
+use crate::ReportSelection;
+
+/// Context about what was scanned and when, included in every report.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct ReportMetadata {
+    /// Version of `ch-migrate` that produced the report.
+    tool_version: String,
+    /// Scan root (`WebApp.Desktop/src`).
+    pub(crate) root_path: Utf8PathBuf,
+    /// Application directory that was scanned for model consumers.
+    pub(crate) app_path: Utf8PathBuf,
+    /// Further directories scanned alongside `app_path`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    additional_app_paths: Vec<Utf8PathBuf>,
+    /// Legacy shared models directory.
+    shared_path: Utf8PathBuf,
+    /// Modern shared models directory.
+    shared_2023_path: Utf8PathBuf,
+    /// Model, export and rename counts of the registry.
+    #[serde(flatten)]
+    registry: RegistryStats,
+    /// Scan start time in the local timezone (RFC 3339).
+    generated_at: String,
+    /// Scan start time in UTC (RFC 3339).
+    generated_at_utc: String,
+    /// Scan duration in milliseconds.
+    duration_ms: u64,
+    /// Filters that were in effect for the scan.
+    pub(crate) filters: ReportFilters,
+}
+
+/// Filters that restricted which files a report covers.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct ReportFilters {
+    /// Directory names skipped while walking.
+    skip_dirs: Vec<String>,
+    /// `CODEOWNERS` file used for team attribution.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) codeowners: Option<Utf8PathBuf>,
+    /// Import paths or model names left out of migration status.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    ignore_imports: Vec<String>,
+    /// Report options narrowing which scanned files are included.
+    #[serde(flatten)]
+    selection: ReportSelection,
+}
+
+impl ReportMetadata {
+    /// Collects metadata for a scan that started at `started_at` and took `duration`.
+    pub(crate) fn new(
+        config: &Config,
+        scanner: &Scanner,
+        registry: &ModelRegistry,
+        selection: &ReportSelection,
+        started_at: DateTime<Local>,
+        duration: Duration,
+    ) -> Self {
+        Self {
+            tool_version: env!("CARGO_PKG_VERSION").to_owned(),
+            root_path: config.scan.root_path.clone(),
+            app_path: config.scan.app_path.clone(),
+            additional_app_paths: config.scan.additional_app_paths.clone(),
+            shared_path: config.scan.shared_path.clone(),
+            shared_2023_path: config.scan.shared_2023_path.clone(),
+            registry: registry.stats(),
+            generated_at: started_at.to_rfc3339_opts(SecondsFormat::Secs, false),
+            generated_at_utc: started_at
+                .with_timezone(&Utc)
+                .to_rfc3339_opts(SecondsFormat::Secs, true),
+            duration_ms: u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+            filters: ReportFilters {
+                skip_dirs: scanner.config().skip_dirs.clone(),
+                codeowners: config.scan.codeowners_path.clone(),
+                ignore_imports: config.scan.ignore_imports.clone(),
+                selection: selection.clone(),
+            },
+        }
+    }
+
+    /// Writes the metadata as `# key: value` comment lines (for CSV output).
+    fn write_comments(&self, output: &mut String) {
+        use std::fmt::Write;
+
+        let _ = writeln!(output, "# tool_version: {}", self.tool_version);
+        let _ = writeln!(output, "# root_path: {}", self.root_path);
+        let _ = writeln!(output, "# app_path: {}", self.app_path);
+        for path in &self.additional_app_paths {
+            let _ = writeln!(output, "# additional_app_path: {path}");
+        }
+        let _ = writeln!(output, "# shared_path: {}", self.shared_path);
+        let _ = writeln!(output, "# shared_2023_path: {}", self.shared_2023_path);
+        let registry = &self.registry;
+        let _ = writeln!(output, "# legacy_models: {}", registry.legacy_models);
+        let _ = writeln!(output, "# modern_models: {}", registry.modern_models);
+        let _ = writeln!(output, "# ported_models: {}", registry.ported_models);
+        let _ = writeln!(output, "# legacy_exports: {}", registry.legacy_exports);
+        let _ = writeln!(output, "# modern_exports: {}", registry.modern_exports);
+        let _ = writeln!(output, "# renames: {}", registry.renames);
+        let _ = writeln!(output, "# generated_at: {}", self.generated_at);
+        let _ = writeln!(output, "# generated_at_utc: {}", self.generated_at_utc);
+        let _ = writeln!(output, "# duration_ms: {}", self.duration_ms);
+        let _ = writeln!(output, "# skip_dirs: {}", self.filters.skip_dirs.join(" "));
+        if let Some(codeowners) = &self.filters.codeowners {
+            let _ = writeln!(output, "# codeowners: {codeowners}");
+        }
+        if !self.filters.ignore_imports.is_empty() {
+            let _ = writeln!(output, "# ignore_imports: {}", self.filters.ignore_imports.join(" "));
+        }
+        self.filters.selection.write_comments(output);
+    }
+}
+
+/// A file that couldn't be scanned, as listed in JSON and binary reports.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct ReportError {
+    path: Utf8PathBuf,
+    category: ErrorCategory,
+    /// The error message, without the path.
+    message: String,
+}
+
+impl ReportError {
+    /// Converts the non-fatal errors of a scan, sorted by path.
+    pub(crate) fn from_scan(errors: &[(Utf8PathBuf, ScanError)]) -> Vec<Self> {
+        let mut errors: Vec<_> = errors
+            .iter()
+            .map(|(path, error)| Self {
+                path: path.clone(),
+                category: error.category(),
+                message: error.message(),
+            })
+            .collect();
+        errors.sort_by(|a, b| a.path.cmp(&b.path));
+        errors
+    }
+}
+
+/// Returns registry models from one source, sorted by name.
+fn sorted_models<'a>(models: impl Iterator<Item = &'a ModelDefinition>) -> Vec<&'a ModelDefinition> {
+    let mut models: Vec<_> = models.collect();
+    models.sort_by(|a, b| a.name.cmp(&b.name));
+    models
+}
+
+/// Returns the working directory, if it is valid UTF-8.
+fn current_dir_utf8() -> Option<Utf8PathBuf> {
+    std::env::current_dir()
+        .ok()
+        .and_then(|dir| Utf8PathBuf::from_path_buf(dir).ok())
+}
+
+/// Returns `path` relative to `cwd` when it lies inside it.
+fn workspace_relative<'a>(path: &'a Utf8Path, cwd: Option<&Utf8Path>) -> &'a Utf8Path {
+    cwd.and_then(|cwd| path.strip_prefix(cwd).ok()).unwrap_or(path)
+}
+
+/// Describes a legacy import for annotations and code scanning results.
+fn legacy_import_message(import: &ImportInfo) -> String {
+    format!(
+        "Legacy import of {} from '{}'",
+        import.names.join(", "),
+        import.path.trim_matches(['\'', '"'])
+    )
+}
+
+/// Escapes a string for XML text and attribute values.
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Metadata of a fixed scan of `/repo/src/app`, for rendering tests.
+#[cfg(test)]
+fn test_metadata() -> ReportMetadata {
+    ReportMetadata {
+        tool_version: "1.2.3".to_owned(),
+        root_path: Utf8PathBuf::from("/repo/src"),
+        app_path: Utf8PathBuf::from("/repo/src/app"),
+        additional_app_paths: Vec::new(),
+        shared_path: Utf8PathBuf::from("/repo/src/shared"),
+        shared_2023_path: Utf8PathBuf::from("/repo/src/shared_2023"),
+        registry: RegistryStats::default(),
+        generated_at: "2026-01-05T09:30:00+01:00".to_owned(),
+        generated_at_utc: "2026-01-05T08:30:00Z".to_owned(),
+        duration_ms: 1500,
+        filters: ReportFilters {
+            skip_dirs: vec!["node_modules".to_owned()],
+            codeowners: None,
+            ignore_imports: Vec::new(),
+            selection: ReportSelection::default(),
+        },
+    }
+}
+
+/// A legacy, a partially migrated and a migrated file, with their statistics.
+#[cfg(test)]
+pub(crate) fn test_files() -> (Vec<ch_core::FileInfo>, ch_scanner::StatsSnapshot) {
+    use ch_core::{FileId, FileInfo, ImportKind, MigrationStatus, ModelSource, SourceLocation};
+
+    let import = |path: &str, name: &str, source, line| {
+        ImportInfo::new(
+            path,
+            ImportKind::Named,
+            vec![name.to_owned()].into(),
+            Some(source),
+            SourceLocation::new(line, 0, 0),
+        )
+    };
+    let file = |id, path: &str, status, imports: Vec<ImportInfo>| {
+        let mut file = FileInfo::new(FileId::new(id), Utf8PathBuf::from(path));
+        file.imports = imports.into();
+        file.status = status;
+        file
+    };
+
+    let files = vec![
+        file(
+            1,
+            "/repo/src/app/jobs/job-list.ts",
+            MigrationStatus::Legacy,
+            vec![import("../../shared/models/job", "Job", ModelSource::SharedLegacy, 3)],
+        ),
+        file(
+            2,
+            "/repo/src/app/jobs/job-edit.ts",
+            MigrationStatus::Partial,
+            vec![
+                import("../../shared/models/contract", "Contract", ModelSource::SharedLegacy, 1),
+                import("../../shared_2023/models/job", "Job", ModelSource::Shared2023, 2),
+            ],
+        ),
+        file(
+            3,
+            "/repo/src/app/home.ts",
+            MigrationStatus::Migrated,
+            vec![import("../shared_2023/models/job", "Job", ModelSource::Shared2023, 1)],
+        ),
+    ];
+    let mut stats = ch_scanner::StatsSnapshot::default();
+    for file in &files {
+        stats.record(file.status);
+    }
+    (files, stats)
+}
@@ -0,0 +1,166 @@
+//! SARIF logs for code scanning, with one result per legacy import.
+
+use camino::Utf8Path;
+use ch_core::FileInfo;
+
+use super::{current_dir_utf8, legacy_import_message, workspace_relative, ReportMetadata};
+
+/// Rule ID of SARIF results for legacy imports.
+const SARIF_RULE_ID: &str = "legacy-model-import";
+
+/// Generates a SARIF 2.1.0 log with one result per legacy import.
+///
+/// All results use the `legacy-model-import` rule at warning level. As with
+/// GitHub annotations, file URIs are relative to the working directory,
+/// which code scanning expects to be the repository checkout.
+pub(crate) fn generate_sarif_report(
+    metadata: &ReportMetadata,
+    files: &[FileInfo],
+) -> color_eyre::Result<String> {
+    #[derive(serde::Serialize)]
+    struct Log {
+        #[serde(rename = "$schema")]
+        schema: &'static str,
+        version: &'static str,
+        runs: [Run; 1],
+    }
+
+    #[derive(serde::Serialize)]
+    struct Run {
+        tool: Tool,
+        results: Vec<SarifResult>,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Tool {
+        driver: Driver,
+    }
+
+    #[derive(serde::Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Driver {
+        name: &'static str,
+        version: String,
+        rules: [Rule; 1],
+    }
+
+    #[derive(serde::Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Rule {
+        id: &'static str,
+        name: &'static str,
+        short_description: Message,
+        full_description: Message,
+        default_configuration: RuleConfiguration,
+    }
+
+    #[derive(serde::Serialize)]
+    struct RuleConfiguration {
+        level: &'static str,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Message {
+        text: String,
+    }
+
+    #[derive(serde::Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct SarifResult {
+        rule_id: &'static str,
+        rule_index: usize,
+        level: &'static str,
+        message: Message,
+        locations: [Location; 1],
+    }
+
+    #[derive(serde::Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Location {
+        physical_location: PhysicalLocation,
+    }
+
+    #[derive(serde::Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct PhysicalLocation {
+        artifact_location: ArtifactLocation,
+        region: Region,
+    }
+
+    #[derive(serde::Serialize)]
+    struct ArtifactLocation {
+        uri: String,
+    }
+
+    #[derive(serde::Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Region {
+        start_line: u32,
+        start_column: u32,
+    }
+
+    let mut sorted: Vec<&FileInfo> = files.iter().collect();
+    sorted.sort_unstable_by(|a, b| a.path.cmp(&b.path));
+
+    let cwd = current_dir_utf8();
+    let mut results = Vec::new();
+    for file in sorted {
+        let uri = sarif_uri(workspace_relative(&file.path, cwd.as_deref()));
+        for import in file.legacy_imports() {
+            results.push(SarifResult {
+                rule_id: SARIF_RULE_ID,
+                rule_index: 0,
+                level: "warning",
+                message: Message {
+                    text: legacy_import_message(import),
+                },
+                locations: [Location {
+                    physical_location: PhysicalLocation {
+                        artifact_location: ArtifactLocation { uri: uri.clone() },
+                        region: Region {
+                            start_line: import.location.line,
+                            start_column: import.location.column + 1,
+                        },
+                    },
+                }],
+            });
+        }
+    }
+
+    let log = Log {
+        schema: "https://json.schemastore.org/sarif-2.1.0.json",
+        version: "2.1.0",
+        runs: [Run {
+            tool: Tool {
+                driver: Driver {
+                    name: "ch-migrate",
+                    version: metadata.tool_version.clone(),
+                    rules: [Rule {
+                        id: SARIF_RULE_ID,
+                        name: "LegacyModelImport",
+                        short_description: Message {
+                            text: "Legacy model import".to_owned(),
+                        },
+                        full_description: Message {
+                            text: "The file imports a model from the legacy shared/ directory \
+                                   instead of its shared_2023/ replacement."
+                                .to_owned(),
+                        },
+                        default_configuration: RuleConfiguration { level: "warning" },
+                    }],
+                },
+            },
+            results,
+        }],
+    };
+    serde_json::to_string_pretty(&log)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to serialize SARIF: {}", e))
+}
+
+/// Encodes a relative path as a SARIF URI reference.
+fn sarif_uri(path: &Utf8Path) -> String {
+    path.components()
+        .map(|component| component.as_str().replace('%', "%25").replace(' ', "%20"))
+        .collect::<Vec<_>>()
+        .join("/")
+}
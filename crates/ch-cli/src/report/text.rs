@@ -0,0 +1,158 @@
+//! Plain-text output of the `validate-parity`, `impact` and `plan` commands.
+
+use ch_core::StatusLabels;
+use ch_scanner::{MigrationPlan, ModelImpact, ParityReport};
+
+/// Generates a plain-text export parity report.
+///
+/// Lists each ported model with missing exports, followed by the exports
+/// its `shared_2023/` version needs to add.
+pub(crate) fn generate_parity_text(report: &ParityReport) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+    let _ = writeln!(
+        output,
+        "Export parity: {} ported models checked, {} with missing exports ({} not yet ported)",
+        report.matched,
+        report.gaps.len(),
+        report.unported
+    );
+
+    for gap in &report.gaps {
+        let _ = writeln!(output);
+        if gap.legacy == gap.modern {
+            let _ = writeln!(output, "{} ({})", gap.legacy, gap.modern_path);
+        } else {
+            let _ = writeln!(output, "{} -> {} ({})", gap.legacy, gap.modern, gap.modern_path);
+        }
+        for export in &gap.missing_exports {
+            let _ = writeln!(output, "  missing: {export}");
+        }
+    }
+
+    output
+}
+
+/// Renders a model impact as text: the files changing status, then the
+/// files no longer blocked.
+pub(crate) fn generate_impact_text(impact: &ModelImpact, labels: &StatusLabels) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+    let _ = writeln!(
+        output,
+        "Porting {} ({}): {} files import it",
+        impact.model,
+        impact.names.join(", "),
+        impact.consumers
+    );
+    if let Some(modern) = &impact.modern_equivalent {
+        let _ = writeln!(output, "Already ported as {modern}");
+    }
+
+    let _ = writeln!(output);
+    if impact.changes.is_empty() {
+        let _ = writeln!(output, "No file would change status.");
+    } else {
+        let _ = writeln!(
+            output,
+            "Would change status ({} files, {} fully migrated):",
+            impact.changes.len(),
+            impact.migrated_count()
+        );
+        for change in &impact.changes {
+            let _ = write!(
+                output,
+                "  {}: {} -> {}",
+                change.path,
+                labels.get(change.from),
+                labels.get(change.to)
+            );
+            if change.remaining.is_empty() {
+                let _ = writeln!(output);
+            } else {
+                let _ = writeln!(output, " (still uses: {})", change.remaining.join(", "));
+            }
+        }
+    }
+
+    if !impact.unblocked.is_empty() {
+        let _ = writeln!(output);
+        let _ = writeln!(output, "No longer blocked ({} files):", impact.unblocked.len());
+        for path in &impact.unblocked {
+            let _ = writeln!(output, "  {path}");
+        }
+    }
+
+    output
+}
+
+/// Generates the migration plan as numbered waves.
+pub(crate) fn generate_plan_text(plan: &MigrationPlan) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+    let _ = writeln!(
+        output,
+        "Migration plan: {} files in {} waves, {} blocked",
+        plan.planned_count(),
+        plan.waves.len(),
+        plan.blocked.len()
+    );
+
+    for (number, wave) in (1..).zip(&plan.waves) {
+        let _ = writeln!(output);
+        if wave.cyclic {
+            let _ = writeln!(
+                output,
+                "Wave {number} ({} files, import cycle: migrate together):",
+                wave.files.len()
+            );
+        } else {
+            let _ = writeln!(output, "Wave {number} ({} files):", wave.files.len());
+        }
+        for file in &wave.files {
+            if file.legacy_models.is_empty() {
+                let _ = writeln!(output, "  {}", file.path);
+            } else {
+                let _ = writeln!(output, "  {} ({})", file.path, file.legacy_models.join(", "));
+            }
+        }
+    }
+
+    if !plan.blocked.is_empty() {
+        let _ = writeln!(output);
+        let _ = writeln!(
+            output,
+            "Blocked until models are ported ({} files):",
+            plan.blocked.len()
+        );
+        for file in &plan.blocked {
+            if file.missing_models.is_empty() {
+                let after: Vec<&str> = file
+                    .depends_on
+                    .iter()
+                    .filter(|path| plan.blocked.iter().any(|blocked| &blocked.path == *path))
+                    .map(|path| path.as_str())
+                    .collect();
+                let _ = writeln!(output, "  {} (after: {})", file.path, after.join(", "));
+            } else {
+                let _ = writeln!(
+                    output,
+                    "  {} (missing: {})",
+                    file.path,
+                    file.missing_models.join(", ")
+                );
+            }
+        }
+
+        let _ = writeln!(output);
+        let _ = writeln!(output, "Models to port:");
+        for blocker in &plan.blockers {
+            let _ = writeln!(output, "  {} (blocks {} files)", blocker.name, blocker.files);
+        }
+    }
+
+    output
+}
@@ -0,0 +1,286 @@
+//! Excel (XLSX) workbooks, written as a zip of spreadsheet XML parts.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use camino::Utf8Path;
+use ch_core::{FileInfo, FxHashSet, MigrationStatus, StatusLabels};
+use ch_scanner::StatsSnapshot;
+
+use super::{escape_xml, ReportMetadata};
+
+/// A cell in an XLSX worksheet.
+enum XlsxCell {
+    /// A string, stored inline in the sheet.
+    Text(String),
+    /// A number, already formatted.
+    Number(String),
+}
+
+impl XlsxCell {
+    /// Creates a text cell.
+    fn text(value: impl Into<String>) -> Self {
+        Self::Text(value.into())
+    }
+
+    /// Creates a number cell.
+    fn number(value: impl std::fmt::Display) -> Self {
+        Self::Number(value.to_string())
+    }
+}
+
+/// A worksheet of an XLSX report: a name and its rows, header first.
+struct XlsxSheet {
+    /// Sheet tab name.
+    name: &'static str,
+    /// Rows of cells; an empty row leaves a blank line.
+    rows: Vec<Vec<XlsxCell>>,
+    /// Whether the first row is a header kept in view while scrolling.
+    header: bool,
+}
+
+/// Generates an Excel workbook with three sheets:
+///
+/// - **Summary**: report metadata and the file count for each status
+/// - **Files**: one row per file, like the CSV report
+/// - **Models**: the number of files importing each shared model, by source
+///
+/// Strings are stored inline in each sheet, so the workbook needs no shared
+/// string table or styles.
+pub(crate) fn generate_xlsx_report(
+    metadata: &ReportMetadata,
+    stats: &StatsSnapshot,
+    files: &[FileInfo],
+    with_owners: bool,
+    labels: &StatusLabels,
+) -> color_eyre::Result<Vec<u8>> {
+    let mut summary = vec![
+        vec![XlsxCell::text("Root"), XlsxCell::text(metadata.app_path.as_str())],
+        vec![XlsxCell::text("Generated"), XlsxCell::text(&metadata.generated_at)],
+        vec![XlsxCell::text("Tool version"), XlsxCell::text(&metadata.tool_version)],
+        vec![XlsxCell::text("Duration (ms)"), XlsxCell::number(metadata.duration_ms)],
+        Vec::new(),
+        vec![XlsxCell::text("Status"), XlsxCell::text("Files")],
+    ];
+    let counts = [
+        (MigrationStatus::Legacy, stats.legacy),
+        (MigrationStatus::Partial, stats.partial),
+        (MigrationStatus::Migrated, stats.migrated),
+        (MigrationStatus::NoModels, stats.no_models),
+    ];
+    for (status, count) in counts {
+        summary.push(vec![XlsxCell::text(labels.get(status)), XlsxCell::number(count)]);
+    }
+    summary.extend([
+        vec![XlsxCell::text("Total"), XlsxCell::number(stats.total)],
+        vec![XlsxCell::text("Errors"), XlsxCell::number(stats.errors)],
+        vec![
+            XlsxCell::text("Progress (%)"),
+            XlsxCell::number(format!("{:.1}", stats.progress_percent())),
+        ],
+    ]);
+
+    let mut header = vec![
+        XlsxCell::text("Path"),
+        XlsxCell::text("Status"),
+        XlsxCell::text("Imports"),
+        XlsxCell::text("Legacy imports"),
+        XlsxCell::text("Migrated imports"),
+    ];
+    if with_owners {
+        header.push(XlsxCell::text("Owner"));
+    }
+    let mut file_rows = vec![header];
+    // Models keyed by (name, source), each with the files importing it
+    let mut consumers: BTreeMap<(&str, &str), FxHashSet<&Utf8Path>> = BTreeMap::new();
+    for file in files {
+        let mut row = vec![
+            XlsxCell::text(file.path.as_str()),
+            XlsxCell::text(labels.get(file.status)),
+            XlsxCell::number(file.import_count()),
+            XlsxCell::number(file.legacy_imports().count()),
+            XlsxCell::number(file.migrated_imports().count()),
+        ];
+        if with_owners {
+            row.push(XlsxCell::text(file.owners.join(" ")));
+        }
+        file_rows.push(row);
+
+        for import in &file.imports {
+            let Some(source) = import.source else {
+                continue;
+            };
+            for name in &import.names {
+                consumers
+                    .entry((name.as_str(), source.dir_name()))
+                    .or_default()
+                    .insert(&file.path);
+            }
+        }
+    }
+
+    let mut models = vec![vec![
+        XlsxCell::text("Model"),
+        XlsxCell::text("Source"),
+        XlsxCell::text("Consumer files"),
+    ]];
+    models.extend(consumers.into_iter().map(|((name, source), files)| {
+        vec![XlsxCell::text(name), XlsxCell::text(source), XlsxCell::number(files.len())]
+    }));
+
+    write_xlsx(&[
+        XlsxSheet {
+            name: "Summary",
+            rows: summary,
+            header: false,
+        },
+        XlsxSheet {
+            name: "Files",
+            rows: file_rows,
+            header: true,
+        },
+        XlsxSheet {
+            name: "Models",
+            rows: models,
+            header: true,
+        },
+    ])
+}
+
+/// Packages worksheets into an XLSX (Office Open XML) workbook.
+fn write_xlsx(sheets: &[XlsxSheet]) -> color_eyre::Result<Vec<u8>> {
+    use std::fmt::Write as _;
+
+    use zip::write::SimpleFileOptions;
+
+    let mut content_types = String::from(concat!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+        r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">"#,
+        r#"<Default Extension="rels" "#,
+        r#"ContentType="application/vnd.openxmlformats-package.relationships+xml"/>"#,
+        r#"<Default Extension="xml" ContentType="application/xml"/>"#,
+        r#"<Override PartName="/xl/workbook.xml" ContentType="application/"#,
+        r#"vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/>"#,
+    ));
+    let mut workbook = String::from(concat!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+        r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" "#,
+        r#"xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">"#,
+        "<sheets>",
+    ));
+    let mut workbook_rels = String::from(concat!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+        r#"<Relationships "#,
+        r#"xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+    ));
+    for (index, sheet) in sheets.iter().enumerate() {
+        let id = index + 1;
+        let _ = write!(
+            content_types,
+            r#"<Override PartName="/xl/worksheets/sheet{id}.xml" ContentType="application/"#
+        );
+        content_types
+            .push_str(r#"vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/>"#);
+        let _ = write!(
+            workbook,
+            r#"<sheet name="{}" sheetId="{id}" r:id="rId{id}"/>"#,
+            escape_xml(sheet.name)
+        );
+        let _ = write!(
+            workbook_rels,
+            r#"<Relationship Id="rId{id}" Type="http://schemas.openxmlformats.org/"#
+        );
+        let _ = write!(
+            workbook_rels,
+            r#"officeDocument/2006/relationships/worksheet" Target="worksheets/sheet{id}.xml"/>"#
+        );
+    }
+    content_types.push_str("</Types>");
+    workbook.push_str("</sheets></workbook>");
+    workbook_rels.push_str("</Relationships>");
+    let root_rels = concat!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+        r#"<Relationships "#,
+        r#"xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+        r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/"#,
+        r#"officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/>"#,
+        "</Relationships>",
+    );
+
+    let mut parts = vec![
+        ("[Content_Types].xml".to_owned(), content_types),
+        ("_rels/.rels".to_owned(), root_rels.to_owned()),
+        ("xl/workbook.xml".to_owned(), workbook),
+        ("xl/_rels/workbook.xml.rels".to_owned(), workbook_rels),
+    ];
+    for (index, sheet) in sheets.iter().enumerate() {
+        parts.push((format!("xl/worksheets/sheet{}.xml", index + 1), xlsx_sheet_xml(sheet)));
+    }
+
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    for (name, xml) in parts {
+        zip.start_file(name, SimpleFileOptions::default())
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to write XLSX: {}", e))?;
+        zip.write_all(xml.as_bytes())?;
+    }
+    let cursor = zip
+        .finish()
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to write XLSX: {}", e))?;
+    Ok(cursor.into_inner())
+}
+
+/// Renders a worksheet part, freezing the header row if it has one.
+fn xlsx_sheet_xml(sheet: &XlsxSheet) -> String {
+    use std::fmt::Write;
+
+    let mut xml = String::from(concat!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+        r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">"#,
+    ));
+    if sheet.header {
+        xml.push_str(concat!(
+            r#"<sheetViews><sheetView workbookViewId="0">"#,
+            r#"<pane ySplit="1" topLeftCell="A2" activePane="bottomLeft" state="frozen"/>"#,
+            "</sheetView></sheetViews>",
+        ));
+    }
+    xml.push_str("<sheetData>");
+    for (index, row) in sheet.rows.iter().enumerate() {
+        let number = index + 1;
+        let _ = write!(xml, r#"<row r="{number}">"#);
+        for (column, cell) in row.iter().enumerate() {
+            let reference = format!("{}{number}", xlsx_column(column));
+            match cell {
+                XlsxCell::Text(text) => {
+                    let _ = write!(xml, r#"<c r="{reference}" t="inlineStr"><is>"#);
+                    let _ = write!(
+                        xml,
+                        r#"<t xml:space="preserve">{}</t></is></c>"#,
+                        escape_xml(text)
+                    );
+                }
+                XlsxCell::Number(value) => {
+                    let _ = write!(xml, r#"<c r="{reference}"><v>{value}</v></c>"#);
+                }
+            }
+        }
+        xml.push_str("</row>");
+    }
+    xml.push_str("</sheetData></worksheet>");
+    xml
+}
+
+/// Returns the letters of a zero-based column index (`A`, …, `Z`, `AA`, …).
+fn xlsx_column(mut index: usize) -> String {
+    let mut letters = Vec::new();
+    loop {
+        // `index % 26` is below 26, so the cast cannot truncate
+        #[allow(clippy::cast_possible_truncation)]
+        letters.push(char::from(b'A' + (index % 26) as u8));
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    letters.iter().rev().collect()
+}
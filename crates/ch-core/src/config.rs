@@ -4,14 +4,27 @@
 //!
 //! - [`ScanConfig`] - Scanner settings (paths, extensions, parallelism)
 //! - [`WatchConfig`] - File watcher settings (debouncing, recursion)
-//! - [`TuiConfig`] - Terminal UI settings (tick rate, colors)
+//! - [`TuiConfig`] - Terminal UI settings (tick rate, colors, key bindings)
+//! - [`KeyOverrides`] - Key binding overrides for TUI commands
+//! - [`HistoryConfig`] - Retention of the recorded scan history
+//! - [`StatusLabels`] - Display labels for migration statuses
 //! - [`Config`] - Root configuration combining all settings
 //!
 //! All configuration types implement [`Default`] with sensible values for the
-//! `ClickHome` project structure.
+//! `ClickHome` project structure. Any key can be overridden with a
+//! `CH_MIGRATE_<SECTION>_<KEY>` environment variable; see
+//! [`Config::apply_env_overrides`].
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::str::FromStr;
 
 use camino::Utf8PathBuf;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::ConfigError;
+use crate::types::MigrationStatus;
 
 /// Color scheme for the TUI.
 ///
@@ -61,6 +74,10 @@ pub struct ScanConfig {
     /// defaults to `root_path` joined with "app" at runtime.
     pub app_path: Utf8PathBuf,
 
+    /// Further directories scanned for model consumers alongside `app_path`,
+    /// with results merged into one set of statistics.
+    pub additional_app_paths: Vec<Utf8PathBuf>,
+
     /// Name of the legacy shared directory (typically "shared").
     pub shared_dir: String,
 
@@ -82,6 +99,68 @@ pub struct ScanConfig {
     /// Maximum number of parallel scanning jobs.
     /// `None` means use all available CPU cores.
     pub max_parallel_jobs: Option<usize>,
+
+    /// Resident memory in MiB above which the scanner switches to a slower
+    /// low-memory mode. `None` disables the limit.
+    pub max_memory_mb: Option<u64>,
+
+    /// Arena size in MiB per analysis thread above which the scanner
+    /// switches to low-memory mode. `None` disables the limit.
+    pub max_arena_mb: Option<u64>,
+
+    /// Path to a `CODEOWNERS` file for per-team reporting.
+    /// `None` disables ownership attribution.
+    pub codeowners_path: Option<Utf8PathBuf>,
+
+    /// Path to a mapping of models renamed during porting
+    /// (`LegacyName -> ModernName` per line).
+    pub renames_path: Option<Utf8PathBuf>,
+
+    /// Path to a `tsconfig.json` whose `files`, `include` and `exclude`
+    /// settings restrict scans to the files the compiler builds, and whose
+    /// `paths` aliases imports are resolved through. `None` scans every
+    /// TypeScript file.
+    pub tsconfig_path: Option<Utf8PathBuf>,
+
+    /// Imports excluded from migration status, e.g. universally accepted
+    /// exceptions. Entries containing `/` match import paths by suffix
+    /// (`shared/models/legacy-logger`); other entries match imported model
+    /// names.
+    pub ignore_imports: Vec<String>,
+
+    /// Directory of the on-disk scan cache (e.g. `.ch-migrate/cache`) that
+    /// lets repeat scans skip unchanged files. `None` disables the cache.
+    pub cache_dir: Option<Utf8PathBuf>,
+
+    /// Size in bytes above which files are skipped instead of parsed.
+    /// `None` disables the limit.
+    pub max_file_size: Option<u64>,
+
+    /// Whether to skip files whose contents look binary instead of parsing
+    /// them.
+    pub skip_binary: bool,
+
+    /// Size in bytes from which one-shot scans memory-map files instead of
+    /// reading them. `None` reads every file, as watch sessions always do.
+    pub mmap_threshold: Option<u64>,
+
+    /// Directories, relative to `app_path` unless absolute, whose files the
+    /// TUI scans first.
+    pub priority_dirs: Vec<Utf8PathBuf>,
+
+    /// Whether the TUI first scans directories where the previous scan
+    /// found files needing migration.
+    pub legacy_first: bool,
+
+    /// Most files a `watch` session keeps in memory with their imports; the
+    /// least recently used others keep only their status. `None` keeps
+    /// every file's imports.
+    pub max_detailed_files: Option<usize>,
+
+    /// Times files that failed to read transiently (e.g. a network file
+    /// system timing out) are retried at the end of a scan. `None` uses
+    /// the scanner's default.
+    pub retry_attempts: Option<u32>,
 }
 
 impl Default for ScanConfig {
@@ -91,6 +170,7 @@ impl Default for ScanConfig {
             shared_path: Utf8PathBuf::new(),
             shared_2023_path: Utf8PathBuf::new(),
             app_path: Utf8PathBuf::new(),
+            additional_app_paths: Vec::new(),
             shared_dir: "shared".to_owned(),
             shared_2023_dir: "shared_2023".to_owned(),
             models_subdir: "models".to_owned(),
@@ -103,6 +183,20 @@ impl Default for ScanConfig {
                 "*.test.ts".to_owned(),
             ],
             max_parallel_jobs: None,
+            max_memory_mb: None,
+            max_arena_mb: None,
+            codeowners_path: None,
+            renames_path: None,
+            tsconfig_path: None,
+            ignore_imports: Vec::new(),
+            cache_dir: None,
+            max_file_size: None,
+            skip_binary: true,
+            mmap_threshold: None,
+            priority_dirs: Vec::new(),
+            legacy_first: true,
+            max_detailed_files: None,
+            retry_attempts: None,
         }
     }
 }
@@ -139,7 +233,7 @@ impl ScanConfig {
 /// assert_eq!(config.debounce_ms, 100);
 /// assert!(config.recursive);
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(default)]
 pub struct WatchConfig {
     /// Whether file watching is enabled.
@@ -152,6 +246,15 @@ pub struct WatchConfig {
 
     /// Whether to watch subdirectories recursively.
     pub recursive: bool,
+
+    /// Path of a JSON status file rewritten whenever the statistics change.
+    /// `None` disables the status file.
+    pub status_file: Option<Utf8PathBuf>,
+
+    /// Whether to also watch the shared model directories, checking imports
+    /// against a model registry that follows their changes, so models added
+    /// to `shared_2023/` are recognized without restarting.
+    pub shared: bool,
 }
 
 impl Default for WatchConfig {
@@ -160,6 +263,8 @@ impl Default for WatchConfig {
             enabled: true,
             debounce_ms: 100,
             recursive: true,
+            status_file: None,
+            shared: false,
         }
     }
 }
@@ -178,7 +283,7 @@ impl Default for WatchConfig {
 /// assert_eq!(config.frame_rate, 60);
 /// assert_eq!(config.color_scheme, ColorScheme::Auto);
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(default)]
 pub struct TuiConfig {
     /// UI tick rate in milliseconds (for periodic updates).
@@ -197,6 +302,9 @@ pub struct TuiConfig {
 
     /// Color scheme for the interface.
     pub color_scheme: ColorScheme,
+
+    /// Keys bound to commands instead of their defaults.
+    pub keys: KeyOverrides,
 }
 
 impl Default for TuiConfig {
@@ -206,7 +314,88 @@ impl Default for TuiConfig {
             frame_rate: 60,
             show_hidden: false,
             color_scheme: ColorScheme::Auto,
+            keys: KeyOverrides::default(),
+        }
+    }
+}
+
+/// Key binding overrides for TUI commands.
+///
+/// Maps command IDs (e.g. `rescan`) to the keys that run them, replacing
+/// the command's default keys. Command IDs and key names are only checked
+/// when the TUI resolves its bindings, since it owns the command registry.
+///
+/// # Examples
+///
+/// ```
+/// use ch_core::KeyOverrides;
+///
+/// let keys: KeyOverrides = "rescan=R F5,quit=ctrl+q".parse()?;
+/// assert_eq!(keys.get("rescan"), Some(&["R".to_owned(), "F5".to_owned()][..]));
+/// assert_eq!(keys.get("next-item"), None);
+/// # Ok::<(), ch_core::ConfigError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct KeyOverrides {
+    /// Keys per command ID.
+    bindings: BTreeMap<String, Vec<String>>,
+}
+
+impl KeyOverrides {
+    /// Returns `true` if no command is rebound.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.bindings.is_empty()
+    }
+
+    /// Returns the keys bound to a command, if overridden.
+    #[must_use]
+    pub fn get(&self, command: &str) -> Option<&[String]> {
+        self.bindings.get(command).map(Vec::as_slice)
+    }
+
+    /// Binds `keys` to a command, replacing its default keys.
+    pub fn set(&mut self, command: impl Into<String>, keys: Vec<String>) {
+        self.bindings.insert(command.into(), keys);
+    }
+
+    /// Returns an iterator over the overridden commands and their keys.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[String])> {
+        self.bindings
+            .iter()
+            .map(|(command, keys)| (command.as_str(), keys.as_slice()))
+    }
+}
+
+impl FromStr for KeyOverrides {
+    type Err = ConfigError;
+
+    /// Parses comma-separated `command=keys` overrides, e.g.
+    /// `rescan=R F5,quit=ctrl+q`.
+    ///
+    /// A command's keys are separated by spaces; `none` leaves the command
+    /// without a key.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut overrides = Self::default();
+
+        for entry in s.split(',').filter(|entry| !entry.trim().is_empty()) {
+            let (command, keys) = entry.split_once('=').ok_or_else(|| ConfigError::InvalidOption {
+                option: "keys".to_owned(),
+                reason: format!("expected command=keys, got: {}", entry.trim()),
+            })?;
+            let keys: Vec<String> = keys.split_whitespace().map(str::to_owned).collect();
+            if keys.is_empty() {
+                return Err(ConfigError::InvalidOption {
+                    option: "keys".to_owned(),
+                    reason: format!("no keys for command: {}", command.trim()),
+                });
+            }
+            let keys = if keys == ["none"] { Vec::new() } else { keys };
+            overrides.set(command.trim(), keys);
         }
+
+        Ok(overrides)
     }
 }
 
@@ -221,6 +410,142 @@ pub struct EditorConfig {
     pub editor: Option<String>,
 }
 
+/// Retention policy for the scan history file.
+///
+/// Recording a scan appends to the history, so without limits it grows for
+/// as long as the project is tracked. Once a limit is set, older entries are
+/// dropped whenever a scan is recorded or `ch-migrate maintenance` runs.
+///
+/// # Examples
+///
+/// ```
+/// use ch_core::HistoryConfig;
+///
+/// let config = HistoryConfig::default();
+/// assert_eq!(config.max_entries, None);
+/// assert_eq!(config.max_age_days, None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryConfig {
+    /// Maximum number of entries to keep, newest first. `None` keeps all.
+    pub max_entries: Option<usize>,
+
+    /// Maximum age of an entry in days. `None` keeps entries of any age.
+    pub max_age_days: Option<u64>,
+}
+
+/// Display labels for migration statuses.
+///
+/// Labels only affect what people read in the TUI, CLI and human-oriented
+/// reports. Serialized statuses always use the canonical
+/// [keys](MigrationStatus::key), so machine consumers are unaffected.
+///
+/// Defaults borrow the built-in [labels](MigrationStatus::label), so only
+/// overridden labels allocate.
+///
+/// # Examples
+///
+/// ```
+/// use ch_core::{MigrationStatus, StatusLabels};
+///
+/// let labels: StatusLabels = "legacy=To do,partial=In progress,migrated=Done".parse()?;
+/// assert_eq!(labels.get(MigrationStatus::Partial), "In progress");
+/// assert_eq!(labels.get(MigrationStatus::NoModels), "No Models");
+/// # Ok::<(), ch_core::ConfigError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatusLabels {
+    /// Label for [`MigrationStatus::Legacy`].
+    pub legacy: Cow<'static, str>,
+
+    /// Label for [`MigrationStatus::Partial`].
+    pub partial: Cow<'static, str>,
+
+    /// Label for [`MigrationStatus::Migrated`].
+    pub migrated: Cow<'static, str>,
+
+    /// Label for [`MigrationStatus::NoModels`].
+    pub no_models: Cow<'static, str>,
+}
+
+/// The built-in labels, shared by [`StatusLabels::standard`].
+static STANDARD_LABELS: StatusLabels = StatusLabels {
+    legacy: Cow::Borrowed(MigrationStatus::Legacy.label()),
+    partial: Cow::Borrowed(MigrationStatus::Partial.label()),
+    migrated: Cow::Borrowed(MigrationStatus::Migrated.label()),
+    no_models: Cow::Borrowed(MigrationStatus::NoModels.label()),
+};
+
+impl StatusLabels {
+    /// Returns the built-in labels.
+    ///
+    /// Useful as a default for widgets and helpers that borrow labels.
+    #[must_use]
+    pub const fn standard() -> &'static Self {
+        &STANDARD_LABELS
+    }
+
+    /// Returns the display label for a status.
+    #[must_use]
+    pub fn get(&self, status: MigrationStatus) -> &str {
+        match status {
+            MigrationStatus::Legacy => &self.legacy,
+            MigrationStatus::Partial => &self.partial,
+            MigrationStatus::Migrated => &self.migrated,
+            MigrationStatus::NoModels => &self.no_models,
+        }
+    }
+
+    /// Overrides the display label for a status.
+    pub fn set(&mut self, status: MigrationStatus, label: impl Into<Cow<'static, str>>) {
+        let slot = match status {
+            MigrationStatus::Legacy => &mut self.legacy,
+            MigrationStatus::Partial => &mut self.partial,
+            MigrationStatus::Migrated => &mut self.migrated,
+            MigrationStatus::NoModels => &mut self.no_models,
+        };
+        *slot = label.into();
+    }
+}
+
+impl Default for StatusLabels {
+    fn default() -> Self {
+        STANDARD_LABELS.clone()
+    }
+}
+
+impl FromStr for StatusLabels {
+    type Err = ConfigError;
+
+    /// Parses comma-separated `status=Label` overrides, e.g.
+    /// `legacy=To do,partial=In progress,migrated=Done`.
+    ///
+    /// Statuses are given by their canonical [key](MigrationStatus::key);
+    /// statuses that are not mentioned keep their built-in label.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut labels = Self::default();
+
+        for entry in s.split(',').filter(|entry| !entry.trim().is_empty()) {
+            let (key, label) = entry.split_once('=').ok_or_else(|| ConfigError::InvalidOption {
+                option: "status labels".to_owned(),
+                reason: format!("expected status=label, got: {}", entry.trim()),
+            })?;
+            let label = label.trim();
+            if label.is_empty() {
+                return Err(ConfigError::InvalidOption {
+                    option: "status labels".to_owned(),
+                    reason: format!("empty label for status: {}", key.trim()),
+                });
+            }
+            labels.set(key.trim().parse()?, label.to_owned());
+        }
+
+        Ok(labels)
+    }
+}
+
 /// Root configuration for the ch-migration tool.
 ///
 /// Combines all component configurations into a single structure that can be
@@ -251,6 +576,124 @@ pub struct Config {
 
     /// External editor configuration.
     pub editor: EditorConfig,
+
+    /// Scan history retention.
+    pub history: HistoryConfig,
+
+    /// Display labels for migration statuses.
+    pub labels: StatusLabels,
+}
+
+/// Prefix of environment variables that override configuration keys.
+const ENV_PREFIX: &str = "CH_MIGRATE_";
+
+impl Config {
+    /// Applies `CH_MIGRATE_<SECTION>_<KEY>` environment variables on top of
+    /// this configuration.
+    ///
+    /// See [`apply_env_vars`](Self::apply_env_vars) for how variables are
+    /// named and read. Variables that aren't valid UTF-8 are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::InvalidOption`] naming the variable if its
+    /// value doesn't fit the key's type.
+    pub fn apply_env_overrides(&mut self) -> Result<(), ConfigError> {
+        self.apply_env_vars(std::env::vars_os().filter_map(|(name, value)| {
+            Some((name.into_string().ok()?, value.into_string().ok()?))
+        }))
+    }
+
+    /// Applies overrides from environment-style `(name, value)` pairs.
+    ///
+    /// Every key has a variable named after its section and field in upper
+    /// case, e.g. `CH_MIGRATE_WATCH_DEBOUNCE_MS` for `watch.debounce_ms` or
+    /// `CH_MIGRATE_TUI_FRAME_RATE` for `tui.frame_rate`. Values are read
+    /// according to the key's type:
+    ///
+    /// - strings and paths are taken as-is
+    /// - numbers and booleans (`true`/`false`) are parsed
+    /// - lists are comma-separated, e.g. `.ts,.tsx`
+    /// - maps such as `tui.keys` take a JSON object
+    ///
+    /// Other variables are ignored, including `CH_MIGRATE_*` ones that don't
+    /// name a key, since command-line flags read some (e.g. `CH_MIGRATE_PATH`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::InvalidOption`] naming the variable if its
+    /// value doesn't fit the key's type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ch_core::Config;
+    ///
+    /// let mut config = Config::default();
+    /// config.apply_env_vars([
+    ///     ("CH_MIGRATE_WATCH_DEBOUNCE_MS", "250"),
+    ///     ("CH_MIGRATE_TUI_FRAME_RATE", "30"),
+    ///     ("HOME", "/home/me"),
+    /// ])?;
+    /// assert_eq!(config.watch.debounce_ms, 250);
+    /// assert_eq!(config.tui.frame_rate, 30);
+    /// # Ok::<(), ch_core::ConfigError>(())
+    /// ```
+    pub fn apply_env_vars<K, V>(
+        &mut self,
+        vars: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<(), ConfigError>
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut tree = serde_json::to_value(&*self)?;
+
+        for (name, raw) in vars {
+            let name = name.as_ref();
+            let Some(key) = name.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+            let Some(slot) = env_key_slot(&mut tree, &key.to_ascii_lowercase()) else {
+                continue;
+            };
+            *slot = env_value(slot, raw.as_ref());
+
+            *self = Self::deserialize(&tree).map_err(|err| ConfigError::InvalidOption {
+                option: name.to_owned(),
+                reason: err.to_string(),
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Finds the value a lower-cased `section_field` key names in a serialized
+/// [`Config`].
+fn env_key_slot<'a>(tree: &'a mut Value, key: &str) -> Option<&'a mut Value> {
+    tree.as_object_mut()?
+        .iter_mut()
+        .find_map(|(section, fields)| {
+            let field = key.strip_prefix(section.as_str())?.strip_prefix('_')?;
+            fields.get_mut(field)
+        })
+}
+
+/// Reads a variable's text as a value of the same kind as `current`.
+fn env_value(current: &Value, raw: &str) -> Value {
+    match current {
+        Value::String(_) => Value::String(raw.to_owned()),
+        Value::Array(_) => raw
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| Value::String(item.to_owned()))
+            .collect(),
+        // Numbers, booleans, maps and unset options; text that isn't JSON
+        // is kept as a string, for paths and editor names.
+        _ => serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_owned())),
+    }
 }
 
 
@@ -285,6 +728,98 @@ mod tests {
         assert_eq!(config.frame_rate, 60);
         assert!(!config.show_hidden);
         assert_eq!(config.color_scheme, ColorScheme::Auto);
+        assert!(config.keys.is_empty());
+    }
+
+    #[test]
+    fn test_key_overrides_parse() {
+        let keys = " rescan=R F5 , quit=ctrl+q,copy-view=none"
+            .parse::<KeyOverrides>()
+            .unwrap_or_default();
+        assert_eq!(keys.get("rescan"), Some(&["R".to_owned(), "F5".to_owned()][..]));
+        assert_eq!(keys.get("quit"), Some(&["ctrl+q".to_owned()][..]));
+        assert_eq!(keys.get("copy-view"), Some(&[][..]));
+        assert_eq!(keys.iter().count(), 3);
+
+        assert!("rescan".parse::<KeyOverrides>().is_err());
+        assert!("rescan= ".parse::<KeyOverrides>().is_err());
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let mut config = Config::default();
+        let result = config.apply_env_vars([
+            ("CH_MIGRATE_WATCH_DEBOUNCE_MS", "250"),
+            ("CH_MIGRATE_WATCH_STATUS_FILE", "/tmp/status.json"),
+            ("CH_MIGRATE_TUI_SHOW_HIDDEN", "true"),
+            ("CH_MIGRATE_TUI_COLOR_SCHEME", "dark"),
+            ("CH_MIGRATE_TUI_KEYS", r#"{"quit":["ctrl+q"]}"#),
+            ("CH_MIGRATE_SCAN_FILE_EXTENSIONS", ".ts, .mts"),
+            ("CH_MIGRATE_SCAN_MAX_PARALLEL_JOBS", "4"),
+            ("CH_MIGRATE_SCAN_SHARED_2023_DIR", "shared_next"),
+            ("CH_MIGRATE_EDITOR_EDITOR", "nvim"),
+            ("CH_MIGRATE_LABELS_NO_MODELS", "n/a"),
+            ("CH_MIGRATE_HISTORY_MAX_AGE_DAYS", "90"),
+            ("CH_MIGRATE_PATH", "src"),
+            ("CH_MIGRATE_TUI_UNKNOWN", "1"),
+            ("EDITOR", "vi"),
+        ]);
+        assert!(result.is_ok());
+
+        assert_eq!(config.watch.debounce_ms, 250);
+        assert_eq!(config.watch.status_file, Some(Utf8PathBuf::from("/tmp/status.json")));
+        assert!(config.tui.show_hidden);
+        assert_eq!(config.tui.color_scheme, ColorScheme::Dark);
+        assert_eq!(config.tui.keys.get("quit"), Some(&["ctrl+q".to_owned()][..]));
+        assert_eq!(config.scan.file_extensions, vec![".ts", ".mts"]);
+        assert_eq!(config.scan.max_parallel_jobs, Some(4));
+        assert_eq!(config.scan.shared_2023_dir, "shared_next");
+        assert_eq!(config.editor.editor.as_deref(), Some("nvim"));
+        assert_eq!(config.labels.get(MigrationStatus::NoModels), "n/a");
+        assert_eq!(config.history.max_age_days, Some(90));
+        assert_eq!(config.tui.frame_rate, 60);
+    }
+
+    #[test]
+    fn test_env_override_invalid_value() {
+        let mut config = Config::default();
+        let result = config.apply_env_vars([("CH_MIGRATE_TUI_FRAME_RATE", "fast")]);
+        assert!(
+            matches!(result, Err(ConfigError::InvalidOption { ref option, .. })
+                if option == "CH_MIGRATE_TUI_FRAME_RATE")
+        );
+        assert_eq!(config.tui.frame_rate, 60);
+    }
+
+    #[test]
+    fn test_status_labels_defaults() {
+        let labels = StatusLabels::default();
+        assert_eq!(&labels, StatusLabels::standard());
+        assert_eq!(labels.get(MigrationStatus::Legacy), "Legacy");
+        assert_eq!(labels.get(MigrationStatus::NoModels), "No Models");
+    }
+
+    #[test]
+    fn test_status_labels_parse() {
+        let labels = " legacy=To do, partial=In progress,migrated=Done "
+            .parse::<StatusLabels>()
+            .unwrap_or_default();
+        assert_eq!(labels.get(MigrationStatus::Legacy), "To do");
+        assert_eq!(labels.get(MigrationStatus::Partial), "In progress");
+        assert_eq!(labels.get(MigrationStatus::Migrated), "Done");
+        assert_eq!(labels.get(MigrationStatus::NoModels), "No Models");
+
+        assert!("legacy".parse::<StatusLabels>().is_err());
+        assert!("done=Done".parse::<StatusLabels>().is_err());
+        assert!("legacy= ".parse::<StatusLabels>().is_err());
+    }
+
+    #[test]
+    fn test_status_labels_partial_deserialization() {
+        let labels: StatusLabels =
+            serde_json::from_str(r#"{"migrated":"Done"}"#).unwrap_or_default();
+        assert_eq!(labels.get(MigrationStatus::Migrated), "Done");
+        assert_eq!(labels.get(MigrationStatus::Legacy), "Legacy");
     }
 
     #[test]
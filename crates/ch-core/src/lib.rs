@@ -77,7 +77,10 @@ pub mod hash;
 pub mod types;
 
 // Re-export configuration types
-pub use config::{ColorScheme, Config, ScanConfig, TuiConfig, WatchConfig};
+pub use config::{
+    ColorScheme, Config, HistoryConfig, KeyOverrides, ScanConfig, StatusLabels, TuiConfig,
+    WatchConfig,
+};
 
 // Re-export error types
 pub use error::ConfigError;
@@ -91,5 +94,5 @@ pub use hash::{
 // Re-export domain types
 pub use types::{
     ExportKind, FileId, FileInfo, ImportInfo, ImportKind, MigrationStatus, ModelCategory,
-    ModelDefinition, ModelReference, ModelRegistry, ModelSource, SourceLocation,
+    ModelDefinition, ModelReference, ModelRegistry, ModelSource, RegistryStats, SourceLocation,
 };
@@ -105,6 +105,8 @@ impl From<FileId> for u64 {
 ///     model_refs: smallvec![],
 ///     status: MigrationStatus::NoModels,
 ///     last_scanned: 1704067200,
+///     owners: smallvec![],
+///     ignored_imports: smallvec![],
 /// };
 ///
 /// assert!(!file.status.needs_migration());
@@ -140,6 +142,19 @@ pub struct FileInfo {
 
     /// Unix timestamp of when this file was last scanned.
     pub last_scanned: u64,
+
+    /// Owners of this file resolved from `CODEOWNERS` (empty if unknown).
+    ///
+    /// The first owner is treated as the owning team for per-team reporting.
+    #[serde(default, skip_serializing_if = "SmallVec::is_empty")]
+    pub owners: SmallVec<[String; 2]>,
+
+    /// Model imports matched by an ignore rule.
+    ///
+    /// These are kept out of [`imports`](Self::imports), so they don't count
+    /// towards the migration status, but remain available for display.
+    #[serde(default, skip_serializing_if = "SmallVec::is_empty")]
+    pub ignored_imports: SmallVec<[ImportInfo; 2]>,
 }
 
 impl FileInfo {
@@ -173,9 +188,31 @@ impl FileInfo {
             model_refs: SmallVec::new(),
             status: MigrationStatus::NoModels,
             last_scanned: 0,
+            owners: SmallVec::new(),
+            ignored_imports: SmallVec::new(),
         }
     }
 
+    /// Returns the primary owner (team) of this file, if known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ch_core::{FileInfo, FileId};
+    /// use camino::Utf8PathBuf;
+    ///
+    /// let mut file = FileInfo::new(FileId::new(1), Utf8PathBuf::from("src/foo.ts"));
+    /// assert_eq!(file.primary_owner(), None);
+    ///
+    /// file.owners.push("@contracts".to_owned());
+    /// assert_eq!(file.primary_owner(), Some("@contracts"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn primary_owner(&self) -> Option<&str> {
+        self.owners.first().map(String::as_str)
+    }
+
     /// Returns the number of imports in this file.
     ///
     /// # Examples
@@ -266,12 +303,67 @@ impl FileInfo {
             .iter()
             .filter(|i| i.source.is_some_and(|s| !s.is_legacy()))
     }
+
+    /// Returns legacy import names that are never referenced in the file body.
+    ///
+    /// These are dead imports: removing them is a quick win that may flip
+    /// the file to [`Migrated`](MigrationStatus::Migrated). Relies on
+    /// [`model_refs`](Self::model_refs) holding the referenced imports, as
+    /// populated by the scanner.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ch_core::{
+    ///     FileId, FileInfo, ImportInfo, ImportKind, ModelCategory, ModelReference, ModelSource,
+    ///     SourceLocation,
+    /// };
+    /// use camino::Utf8PathBuf;
+    /// use smallvec::smallvec;
+    ///
+    /// let mut file = FileInfo::new(FileId::new(1), Utf8PathBuf::from("src/foo.ts"));
+    /// file.imports.push(ImportInfo::new(
+    ///     "../shared/models/foo",
+    ///     ImportKind::Named,
+    ///     smallvec!["Foo".to_owned(), "FooModel".to_owned()],
+    ///     Some(ModelSource::SharedLegacy),
+    ///     SourceLocation::default(),
+    /// ));
+    /// file.model_refs.push(ModelReference::new("Foo", ModelCategory::Model, ModelSource::SharedLegacy));
+    ///
+    /// assert_eq!(file.dead_legacy_imports(), ["FooModel"]);
+    /// ```
+    #[must_use]
+    pub fn dead_legacy_imports(&self) -> Vec<&str> {
+        self.legacy_imports()
+            .flat_map(ImportInfo::used_names)
+            .map(String::as_str)
+            .filter(|name| !self.references_legacy(name))
+            .collect()
+    }
+
+    /// Returns `true` if any legacy import is never referenced in the file body.
+    ///
+    /// See [`dead_legacy_imports`](Self::dead_legacy_imports).
+    #[must_use]
+    pub fn has_dead_legacy_imports(&self) -> bool {
+        self.legacy_imports()
+            .flat_map(ImportInfo::used_names)
+            .any(|name| !self.references_legacy(name))
+    }
+
+    /// Returns `true` if the body references the legacy import `name`.
+    fn references_legacy(&self, name: &str) -> bool {
+        self.model_refs
+            .iter()
+            .any(|model_ref| model_ref.is_legacy() && model_ref.name == name)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{ImportKind, ModelSource, SourceLocation};
+    use crate::types::{ImportKind, ModelCategory, ModelSource, SourceLocation};
     use smallvec::smallvec;
 
     #[test]
@@ -381,6 +473,39 @@ mod tests {
         assert_eq!(migrated[0].path, "../shared_2023/models/bar");
     }
 
+    #[test]
+    fn test_file_info_dead_legacy_imports() {
+        let mut file = FileInfo::new(FileId::new(1), Utf8PathBuf::from("src/foo.ts"));
+        file.imports = smallvec![
+            ImportInfo::new(
+                "../shared/models/foo",
+                ImportKind::Named,
+                smallvec!["Foo".to_owned(), "FooModel".to_owned()],
+                Some(ModelSource::SharedLegacy),
+                SourceLocation::default(),
+            ),
+            ImportInfo::new(
+                "../shared_2023/models/bar",
+                ImportKind::Named,
+                smallvec!["Bar".to_owned()],
+                Some(ModelSource::Shared2023),
+                SourceLocation::default(),
+            ),
+        ];
+        assert_eq!(file.dead_legacy_imports(), ["Foo", "FooModel"]);
+
+        file.model_refs = smallvec![
+            ModelReference::new("Foo", ModelCategory::Model, ModelSource::SharedLegacy),
+            ModelReference::new("FooModel", ModelCategory::Interface, ModelSource::SharedLegacy),
+        ];
+        assert!(file.dead_legacy_imports().is_empty());
+        assert!(!file.has_dead_legacy_imports());
+
+        file.model_refs.pop();
+        assert_eq!(file.dead_legacy_imports(), ["FooModel"]);
+        assert!(file.has_dead_legacy_imports());
+    }
+
     #[test]
     fn test_file_info_serialization() {
         let file = FileInfo {
@@ -391,6 +516,8 @@ mod tests {
             model_refs: smallvec![],
             status: MigrationStatus::NoModels,
             last_scanned: 1_704_067_200,
+            owners: smallvec!["@contracts".to_owned()],
+            ignored_imports: smallvec![],
         };
 
         let json = serde_json::to_string(&file).unwrap();
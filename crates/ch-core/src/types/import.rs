@@ -43,6 +43,15 @@ pub enum ImportKind {
 
     /// Dynamic import: `await import('...')`
     Dynamic,
+
+    /// Require of a whole module: `const foo = require('...')`,
+    /// `import foo = require('...')` or a bare `require('...')`
+    ///
+    /// A destructuring require, `const { Foo } = require('...')`, binds
+    /// exported names as a named import does and is recorded as [`Named`].
+    ///
+    /// [`Named`]: Self::Named
+    Require,
 }
 
 impl ImportKind {
@@ -100,6 +109,31 @@ impl ImportKind {
     pub const fn is_type_only(self) -> bool {
         matches!(self, Self::TypeOnly)
     }
+
+    /// Returns the canonical machine-readable name for this kind.
+    ///
+    /// This matches the serialized form.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ch_core::ImportKind;
+    ///
+    /// assert_eq!(ImportKind::SideEffect.key(), "side_effect");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::Named => "named",
+            Self::Default => "default",
+            Self::Namespace => "namespace",
+            Self::SideEffect => "side_effect",
+            Self::TypeOnly => "type_only",
+            Self::Dynamic => "dynamic",
+            Self::Require => "require",
+        }
+    }
 }
 
 /// Information about an import statement in a TypeScript file.
@@ -119,6 +153,8 @@ impl ImportKind {
 ///     names: smallvec!["ActiveContract".to_owned(), "ActiveContractForm".to_owned()],
 ///     source: Some(ModelSource::SharedLegacy),
 ///     location: SourceLocation::new(5, 0, 120),
+///     aliases: smallvec![],
+///     members: smallvec![],
 /// };
 ///
 /// assert_eq!(import.names.len(), 2);
@@ -152,6 +188,22 @@ pub struct ImportInfo {
 
     /// The location of the import statement in the source file.
     pub location: SourceLocation,
+
+    /// Names imported under another local name, as `(name, alias)` pairs.
+    ///
+    /// For `import { ActiveContract as AC }`, [`names`](Self::names) holds
+    /// `ActiveContract`, the name the module exports, and this holds
+    /// `("ActiveContract", "AC")`, since `AC` is what the file's code uses.
+    #[serde(default, skip_serializing_if = "SmallVec::is_empty")]
+    pub aliases: SmallVec<[(String, String); 1]>,
+
+    /// For an import binding the whole module to one name, the exports the
+    /// file uses through it, sorted.
+    ///
+    /// For `import * as Models` used as `new Models.Job()`, this holds `Job`
+    /// while [`names`](Self::names) holds `Models`. Empty for other imports.
+    #[serde(default, skip_serializing_if = "SmallVec::is_empty")]
+    pub members: SmallVec<[String; 4]>,
 }
 
 impl ImportInfo {
@@ -178,6 +230,82 @@ impl ImportInfo {
             names,
             source,
             location,
+            aliases: SmallVec::new(),
+            members: SmallVec::new(),
+        }
+    }
+
+    /// Records that `name` is imported under the local name `alias`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ch_core::{ImportInfo, ImportKind, SourceLocation};
+    /// use smallvec::smallvec;
+    ///
+    /// let import = ImportInfo::new(
+    ///     "../shared/models/active-contract",
+    ///     ImportKind::Named,
+    ///     smallvec!["ActiveContract".to_owned(), "Job".to_owned()],
+    ///     None,
+    ///     SourceLocation::default(),
+    /// )
+    /// .with_alias("ActiveContract", "AC");
+    /// assert_eq!(import.local_name("ActiveContract"), "AC");
+    /// assert_eq!(import.local_name("Job"), "Job");
+    /// ```
+    #[must_use]
+    pub fn with_alias(mut self, name: impl Into<String>, alias: impl Into<String>) -> Self {
+        self.aliases.push((name.into(), alias.into()));
+        self
+    }
+
+    /// Returns the name the file's code uses for the imported `name`: its
+    /// alias if it has one, otherwise `name` itself.
+    #[must_use]
+    pub fn local_name<'a>(&'a self, name: &'a str) -> &'a str {
+        self.aliases
+            .iter()
+            .find(|(imported, _)| imported == name)
+            .map_or(name, |(_, alias)| alias.as_str())
+    }
+
+    /// Returns `true` if this import binds the whole module to one local
+    /// name: a namespace import or a require of the whole module.
+    #[inline]
+    #[must_use]
+    pub const fn binds_module(&self) -> bool {
+        matches!(self.kind, ImportKind::Namespace | ImportKind::Require)
+    }
+
+    /// Returns the exports this import uses: the [`members`](Self::members)
+    /// used through an import binding the whole module, otherwise the
+    /// imported [`names`](Self::names).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ch_core::{ImportInfo, ImportKind, SourceLocation};
+    /// use smallvec::smallvec;
+    ///
+    /// let mut import = ImportInfo::new(
+    ///     "../shared/models",
+    ///     ImportKind::Namespace,
+    ///     smallvec!["Models".to_owned()],
+    ///     None,
+    ///     SourceLocation::default(),
+    /// );
+    /// assert!(import.used_names().is_empty());
+    ///
+    /// import.members = smallvec!["Job".to_owned()];
+    /// assert_eq!(import.used_names(), ["Job"]);
+    /// ```
+    #[must_use]
+    pub fn used_names(&self) -> &[String] {
+        if self.binds_module() {
+            &self.members
+        } else {
+            &self.names
         }
     }
 
@@ -195,6 +323,8 @@ impl ImportInfo {
     ///     names: smallvec!["Foo".to_owned()],
     ///     source: Some(ModelSource::SharedLegacy),
     ///     location: SourceLocation::default(),
+    ///     aliases: smallvec![],
+    ///     members: smallvec![],
     /// };
     /// assert!(shared_import.is_model_import());
     ///
@@ -204,6 +334,8 @@ impl ImportInfo {
     ///     names: smallvec!["Component".to_owned()],
     ///     source: None,
     ///     location: SourceLocation::default(),
+    ///     aliases: smallvec![],
+    ///     members: smallvec![],
     /// };
     /// assert!(!other_import.is_model_import());
     /// ```
@@ -227,6 +359,8 @@ impl ImportInfo {
     ///     names: smallvec!["Foo".to_owned()],
     ///     source: Some(ModelSource::SharedLegacy),
     ///     location: SourceLocation::default(),
+    ///     aliases: smallvec![],
+    ///     members: smallvec![],
     /// };
     /// assert!(legacy_import.is_legacy_import());
     /// ```
@@ -260,6 +394,23 @@ mod tests {
         assert!(!ImportKind::SideEffect.has_bindings());
         assert!(ImportKind::TypeOnly.has_bindings());
         assert!(ImportKind::Dynamic.has_bindings());
+        assert!(ImportKind::Require.has_bindings());
+    }
+
+    #[test]
+    fn test_import_kind_key_matches_serde() {
+        for kind in [
+            ImportKind::Named,
+            ImportKind::Default,
+            ImportKind::Namespace,
+            ImportKind::SideEffect,
+            ImportKind::TypeOnly,
+            ImportKind::Dynamic,
+            ImportKind::Require,
+        ] {
+            let json = serde_json::to_string(&kind).ok();
+            assert_eq!(json, Some(format!("\"{}\"", kind.key())));
+        }
     }
 
     #[test]
@@ -283,6 +434,29 @@ mod tests {
         assert!(import.is_model_import());
     }
 
+    #[test]
+    fn test_import_info_aliases() {
+        let import = ImportInfo::new(
+            "../shared/models/foo",
+            ImportKind::Named,
+            smallvec!["Foo".to_owned(), "Bar".to_owned()],
+            None,
+            SourceLocation::default(),
+        );
+        let json = serde_json::to_string(&import).unwrap_or_default();
+        assert!(!json.contains("aliases"));
+        let parsed: Option<ImportInfo> = serde_json::from_str(&json).ok();
+        assert_eq!(parsed.as_ref(), Some(&import));
+
+        let import = import.with_alias("Foo", "F");
+        assert_eq!(import.local_name("Foo"), "F");
+        assert_eq!(import.used_names(), ["Foo", "Bar"]);
+        assert_eq!(import.local_name("Bar"), "Bar");
+        let json = serde_json::to_string(&import).unwrap_or_default();
+        let parsed: Option<ImportInfo> = serde_json::from_str(&json).ok();
+        assert_eq!(parsed, Some(import));
+    }
+
     #[test]
     fn test_import_info_is_model_import() {
         let model_import = ImportInfo {
@@ -291,6 +465,8 @@ mod tests {
             names: smallvec!["Foo".to_owned()],
             source: Some(ModelSource::SharedLegacy),
             location: SourceLocation::default(),
+            aliases: smallvec![],
+            members: smallvec![],
         };
         assert!(model_import.is_model_import());
 
@@ -300,6 +476,8 @@ mod tests {
             names: smallvec!["Component".to_owned()],
             source: None,
             location: SourceLocation::default(),
+            aliases: smallvec![],
+            members: smallvec![],
         };
         assert!(!non_model_import.is_model_import());
     }
@@ -312,6 +490,8 @@ mod tests {
             names: smallvec!["Foo".to_owned()],
             source: Some(ModelSource::SharedLegacy),
             location: SourceLocation::default(),
+            aliases: smallvec![],
+            members: smallvec![],
         };
         assert!(legacy.is_legacy_import());
 
@@ -321,6 +501,8 @@ mod tests {
             names: smallvec!["Foo".to_owned()],
             source: Some(ModelSource::Shared2023),
             location: SourceLocation::default(),
+            aliases: smallvec![],
+            members: smallvec![],
         };
         assert!(!new.is_legacy_import());
 
@@ -330,6 +512,8 @@ mod tests {
             names: smallvec!["Component".to_owned()],
             source: None,
             location: SourceLocation::default(),
+            aliases: smallvec![],
+            members: smallvec![],
         };
         assert!(!none.is_legacy_import());
     }
@@ -342,6 +526,8 @@ mod tests {
             names: smallvec!["Foo".to_owned(), "Bar".to_owned()],
             source: Some(ModelSource::SharedLegacy),
             location: SourceLocation::new(10, 5, 245),
+            aliases: smallvec![],
+            members: smallvec![],
         };
         let json = serde_json::to_string(&import).unwrap();
         let parsed: ImportInfo = serde_json::from_str(&json).unwrap();
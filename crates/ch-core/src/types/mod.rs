@@ -37,5 +37,6 @@ pub use import::{ImportInfo, ImportKind};
 pub use location::SourceLocation;
 pub use model::{
     ExportKind, ModelCategory, ModelDefinition, ModelReference, ModelRegistry, ModelSource,
+    RegistryStats,
 };
 pub use status::MigrationStatus;
@@ -30,10 +30,11 @@
 //! assert!(!registry.is_modern_export("ActiveContract"));
 //! ```
 
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 
+use crate::types::{FileInfo, ImportInfo, ImportKind};
 use crate::{FxHashMap, FxHashSet};
 
 /// The source directory of a model.
@@ -169,6 +170,34 @@ impl ModelCategory {
         }
     }
 
+    /// Infers the category of an export from its naming suffix.
+    ///
+    /// Names without a known suffix are treated as the main [`Model`](Self::Model).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ch_core::ModelCategory;
+    ///
+    /// assert_eq!(ModelCategory::from_export_name("JobCodeGenForm"), ModelCategory::CodeGenForm);
+    /// assert_eq!(ModelCategory::from_export_name("JobModel"), ModelCategory::Interface);
+    /// assert_eq!(ModelCategory::from_export_name("Job"), ModelCategory::Model);
+    /// ```
+    #[must_use]
+    pub fn from_export_name(name: &str) -> Self {
+        // Longest suffixes first, so `CodeGenForm` isn't matched as `CodeGen`
+        [
+            Self::CodeGenFormArray,
+            Self::CodeGenForApi,
+            Self::CodeGenForm,
+            Self::CodeGen,
+            Self::Interface,
+        ]
+        .into_iter()
+        .find(|category| name.len() > category.suffix().len() && name.ends_with(category.suffix()))
+        .unwrap_or(Self::Model)
+    }
+
     /// Returns `true` if this is a codegen-related category.
     ///
     /// # Examples
@@ -295,6 +324,9 @@ pub enum ExportKind {
 
     /// Re-export from another module: `export { Foo } from './foo'`
     ReExport,
+
+    /// Re-export of everything another module exports: `export * from './foo'`
+    Wildcard,
 }
 
 impl ExportKind {
@@ -400,6 +432,32 @@ impl ModelDefinition {
     }
 }
 
+/// Counts describing a [`ModelRegistry`], for reports.
+///
+/// Every field defaults to zero when deserialized, so reports written before
+/// a field was added still load.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryStats {
+    /// Number of legacy models.
+    #[serde(default)]
+    pub legacy_models: usize,
+    /// Number of modern models.
+    #[serde(default)]
+    pub modern_models: usize,
+    /// Number of legacy models with a modern equivalent.
+    #[serde(default)]
+    pub ported_models: usize,
+    /// Number of legacy export names.
+    #[serde(default)]
+    pub legacy_exports: usize,
+    /// Number of modern export names.
+    #[serde(default)]
+    pub modern_exports: usize,
+    /// Number of recorded renames.
+    #[serde(default)]
+    pub renames: usize,
+}
+
 /// Registry of all known models from both shared directories.
 ///
 /// Provides O(1) lookup for validating whether an imported name is an actual
@@ -447,6 +505,13 @@ pub struct ModelRegistry {
 
     /// Set of all modern export names for O(1) lookup.
     modern_exports: FxHashSet<String>,
+
+    /// Legacy model names mapped to the modern names they were renamed to.
+    renames: FxHashMap<String, String>,
+
+    /// Barrel files by source and directory under it, each mapping the names
+    /// it re-exports to the source and name of the model defining them.
+    barrels: FxHashMap<(ModelSource, String), FxHashMap<String, (ModelSource, String)>>,
 }
 
 impl ModelRegistry {
@@ -490,6 +555,8 @@ impl ModelRegistry {
                 modern_capacity * 6,
                 crate::FxBuildHasher::default(),
             ),
+            renames: FxHashMap::default(),
+            barrels: FxHashMap::default(),
         }
     }
 
@@ -639,6 +706,23 @@ impl ModelRegistry {
         self.modern_exports.len()
     }
 
+    /// Returns the registry's model, export and rename counts.
+    #[must_use]
+    pub fn stats(&self) -> RegistryStats {
+        RegistryStats {
+            legacy_models: self.legacy_models.len(),
+            modern_models: self.modern_models.len(),
+            ported_models: self
+                .legacy_models
+                .keys()
+                .filter(|name| self.modern_model_for(name).is_some())
+                .count(),
+            legacy_exports: self.legacy_exports.len(),
+            modern_exports: self.modern_exports.len(),
+            renames: self.renames.len(),
+        }
+    }
+
     /// Returns `true` if the registry is empty.
     #[inline]
     #[must_use]
@@ -673,12 +757,224 @@ impl ModelRegistry {
         self.modern_models.get(name)
     }
 
-    /// Clears all registered models from the registry.
+    /// Records that a legacy model was renamed when it was ported.
+    ///
+    /// Renames are used by [`modern_name_for`](Self::modern_name_for) and
+    /// [`modern_model_for`](Self::modern_model_for) to cross-reference models
+    /// whose modern name differs from the legacy one.
+    ///
+    /// # Arguments
+    ///
+    /// * `legacy_name` - The model name in `shared/` (e.g. `ActiveContract`)
+    /// * `modern_name` - The model name in `shared_2023/` (e.g. `ContractActive`)
+    pub fn add_rename(&mut self, legacy_name: impl Into<String>, modern_name: impl Into<String>) {
+        self.renames.insert(legacy_name.into(), modern_name.into());
+    }
+
+    /// Returns the number of recorded renames.
+    #[inline]
+    #[must_use]
+    pub fn rename_count(&self) -> usize {
+        self.renames.len()
+    }
+
+    /// Returns the modern model corresponding to a legacy model name.
+    ///
+    /// Uses the recorded rename if there is one, otherwise a modern model
+    /// with the same name.
+    #[must_use]
+    pub fn modern_model_for(&self, legacy_name: &str) -> Option<&ModelDefinition> {
+        let name = self.renames.get(legacy_name).map_or(legacy_name, String::as_str);
+        self.modern_models.get(name)
+    }
+
+    /// Returns the modern export name to use in place of a legacy import name.
+    ///
+    /// Renames apply to the model name itself and to exports derived from it,
+    /// so with `ActiveContract` renamed to `ContractActive`, the legacy
+    /// `ActiveContractCodeGen` maps to `ContractActiveCodeGen` if that is a
+    /// modern export. Names without a rename map to themselves when they are
+    /// also exported from `shared_2023/`.
+    ///
+    /// Returns `None` if there is no modern equivalent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ch_core::{ModelDefinition, ModelRegistry, ModelSource};
+    ///
+    /// let mut registry = ModelRegistry::new();
+    /// let mut modern = ModelDefinition::new(
+    ///     "ContractActive",
+    ///     ModelSource::Shared2023,
+    ///     "shared_2023/models/contract-active.ts",
+    /// );
+    /// modern.add_export("ContractActive");
+    /// modern.add_export("ContractActiveCodeGen");
+    /// registry.register(modern);
+    /// registry.add_rename("ActiveContract", "ContractActive");
+    ///
+    /// assert_eq!(
+    ///     registry.modern_name_for("ActiveContractCodeGen").as_deref(),
+    ///     Some("ContractActiveCodeGen")
+    /// );
+    /// assert_eq!(registry.modern_name_for("Unported"), None);
+    /// ```
+    #[must_use]
+    pub fn modern_name_for(&self, legacy_name: &str) -> Option<String> {
+        let renamed = self
+            .renames
+            .iter()
+            .filter_map(|(old, new)| {
+                let suffix = legacy_name.strip_prefix(old.as_str())?;
+                Some((old.len(), format!("{new}{suffix}")))
+            })
+            .filter(|(_, candidate)| self.is_modern_export(candidate))
+            .max_by_key(|(prefix_len, _)| *prefix_len)
+            .map(|(_, candidate)| candidate);
+
+        renamed.or_else(|| {
+            self.is_modern_export(legacy_name)
+                .then(|| legacy_name.to_owned())
+        })
+    }
+
+    /// Returns the legacy import names in a file that have no modern equivalent.
+    ///
+    /// A file with any such imports is blocked: it cannot be fully migrated
+    /// until the missing models are ported to `shared_2023/`. An import
+    /// binding the whole module names the members used through it.
+    #[must_use]
+    pub fn missing_equivalents<'a>(&self, file: &'a FileInfo) -> Vec<&'a str> {
+        file.legacy_imports()
+            .flat_map(ImportInfo::used_names)
+            .map(String::as_str)
+            .filter(|name| self.modern_name_for(name).is_none())
+            .collect()
+    }
+
+    /// Returns the names of an import from a shared directory that match no
+    /// registered export of that directory.
+    ///
+    /// Only named and type-only imports are checked: default and namespace
+    /// imports bind local names that needn't match an export. Nothing is
+    /// unmatched while the registry is empty.
+    #[must_use]
+    pub fn unmatched_names<'a>(&self, import: &'a ImportInfo) -> Vec<&'a str> {
+        let Some(source) = import.source else {
+            return Vec::new();
+        };
+        if self.is_empty() || !matches!(import.kind, ImportKind::Named | ImportKind::TypeOnly) {
+            return Vec::new();
+        }
+        import
+            .names
+            .iter()
+            .map(String::as_str)
+            .filter(|name| !self.is_export_from(name, source))
+            .collect()
+    }
+
+    /// Removes the models defined in the file at `path`, returning how many
+    /// were removed.
+    ///
+    /// Export names are dropped unless another model of the same source
+    /// still exports them. Renames are kept.
+    pub fn remove_defined_in(&mut self, path: &Utf8Path) -> usize {
+        let mut removed = 0;
+        for (models, exports) in [
+            (&mut self.legacy_models, &mut self.legacy_exports),
+            (&mut self.modern_models, &mut self.modern_exports),
+        ] {
+            let before = models.len();
+            models.retain(|_, model| model.definition_path != path);
+            if models.len() < before {
+                removed += before - models.len();
+                *exports =
+                    models.values().flat_map(|model| model.exports.iter().cloned()).collect();
+            }
+        }
+        removed
+    }
+
+    /// Records that the barrel file of directory `dir` under `barrel`'s
+    /// directory re-exports `name`, defined by the model `model` of `source`.
+    ///
+    /// # Arguments
+    ///
+    /// * `barrel` - The shared directory holding the barrel
+    /// * `dir` - The barrel's directory under it, `/`-separated; `""` for
+    ///   the `index.ts` at its top
+    /// * `name` - The export name
+    /// * `source` - The shared directory defining the model
+    /// * `model` - The model's base name
+    pub fn add_barrel_export(
+        &mut self,
+        barrel: ModelSource,
+        dir: &str,
+        name: impl Into<String>,
+        source: ModelSource,
+        model: impl Into<String>,
+    ) {
+        self.barrels
+            .entry((barrel, dir.to_owned()))
+            .or_default()
+            .insert(name.into(), (source, model.into()));
+    }
+
+    /// Returns the model defining `name` as re-exported by the barrel file of
+    /// directory `dir` under `barrel`'s directory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ch_core::{ModelDefinition, ModelRegistry, ModelSource};
+    ///
+    /// let mut registry = ModelRegistry::new();
+    /// let legacy = ModelSource::SharedLegacy;
+    /// let mut model = ModelDefinition::new("Foo", legacy, "shared/models/foo.ts");
+    /// model.add_export("FooModel");
+    /// registry.register(model);
+    /// registry.add_barrel_export(legacy, "", "FooModel", legacy, "Foo");
+    ///
+    /// let model = registry.barrel_model(legacy, "", "FooModel");
+    /// assert_eq!(model.map(|model| model.name.as_str()), Some("Foo"));
+    /// assert!(registry.barrel_model(ModelSource::Shared2023, "", "FooModel").is_none());
+    /// ```
+    #[must_use]
+    pub fn barrel_model(
+        &self,
+        barrel: ModelSource,
+        dir: &str,
+        name: &str,
+    ) -> Option<&ModelDefinition> {
+        let (source, model) = self.barrels.get(&(barrel, dir.to_owned()))?.get(name)?;
+        match source {
+            ModelSource::SharedLegacy => self.legacy_models.get(model),
+            ModelSource::Shared2023 => self.modern_models.get(model),
+        }
+    }
+
+    /// Returns each barrel file's source, directory and re-exported name.
+    pub fn barrel_exports(&self) -> impl Iterator<Item = (ModelSource, &str, &str)> {
+        self.barrels.iter().flat_map(|((barrel, dir), names)| {
+            names.keys().map(move |name| (*barrel, dir.as_str(), name.as_str()))
+        })
+    }
+
+    /// Forgets every barrel file, as before rereading them.
+    pub fn clear_barrels(&mut self) {
+        self.barrels.clear();
+    }
+
+    /// Clears all registered models, renames and barrels from the registry.
     pub fn clear(&mut self) {
         self.legacy_models.clear();
         self.modern_models.clear();
         self.legacy_exports.clear();
         self.modern_exports.clear();
+        self.renames.clear();
+        self.barrels.clear();
     }
 }
 
@@ -721,6 +1017,16 @@ mod tests {
         assert_eq!(ModelCategory::CodeGenFormArray.suffix(), "CodeGenFormArray");
     }
 
+    #[test]
+    fn test_model_category_from_export_name() {
+        assert_eq!(ModelCategory::from_export_name("Job"), ModelCategory::Model);
+        assert_eq!(ModelCategory::from_export_name("JobModel"), ModelCategory::Interface);
+        assert_eq!(ModelCategory::from_export_name("JobCodeGen"), ModelCategory::CodeGen);
+        assert_eq!(ModelCategory::from_export_name("JobCodeGenForApi"), ModelCategory::CodeGenForApi);
+        assert_eq!(ModelCategory::from_export_name("JobCodeGenFormArray"), ModelCategory::CodeGenFormArray);
+        assert_eq!(ModelCategory::from_export_name("Model"), ModelCategory::Model);
+    }
+
     #[test]
     fn test_model_category_is_codegen() {
         assert!(!ModelCategory::Interface.is_codegen());
@@ -783,6 +1089,7 @@ mod tests {
         assert!(!ExportKind::Interface.is_class());
         assert!(!ExportKind::Named.is_class());
         assert!(!ExportKind::ReExport.is_class());
+        assert!(!ExportKind::Wildcard.is_class());
     }
 
     #[test]
@@ -977,4 +1284,137 @@ mod tests {
         assert_eq!(registry.total_model_count(), 0);
         assert!(!registry.is_legacy_export("Foo"));
     }
+
+    #[test]
+    fn test_model_registry_remove_defined_in() {
+        let mut registry = ModelRegistry::new();
+        for (name, path, exports) in [
+            ("Foo", "shared/models/foo.ts", ["Foo", "FooModel"]),
+            ("interfaces", "shared/interfaces.ts", ["FooModel", "Bar"]),
+        ] {
+            registry.register(ModelDefinition {
+                name: name.to_owned(),
+                source: ModelSource::SharedLegacy,
+                definition_path: path.into(),
+                exports: exports.iter().map(|export| (*export).to_owned()).collect(),
+            });
+        }
+
+        assert_eq!(registry.remove_defined_in(Utf8Path::new("shared/models/bar.ts")), 0);
+        assert_eq!(registry.remove_defined_in(Utf8Path::new("shared/models/foo.ts")), 1);
+        assert!(registry.get_legacy_model("Foo").is_none());
+        assert!(!registry.is_legacy_export("Foo"));
+        assert!(registry.is_legacy_export("FooModel"), "still exported by interfaces.ts");
+        assert_eq!(registry.legacy_export_count(), 2);
+    }
+
+    fn rename_registry() -> ModelRegistry {
+        let mut registry = ModelRegistry::new();
+        registry.register(ModelDefinition {
+            name: "ActiveContract".to_owned(),
+            source: ModelSource::SharedLegacy,
+            definition_path: "shared/models/active-contract.ts".into(),
+            exports: smallvec!["ActiveContract".to_owned(), "ActiveContractCodeGen".to_owned()],
+        });
+        registry.register(ModelDefinition {
+            name: "ContractActive".to_owned(),
+            source: ModelSource::Shared2023,
+            definition_path: "shared_2023/models/contract-active.ts".into(),
+            exports: smallvec!["ContractActive".to_owned(), "ContractActiveCodeGen".to_owned()],
+        });
+        registry.register(ModelDefinition {
+            name: "Job".to_owned(),
+            source: ModelSource::Shared2023,
+            definition_path: "shared_2023/models/job.ts".into(),
+            exports: smallvec!["Job".to_owned()],
+        });
+        registry
+    }
+
+    #[test]
+    fn test_model_registry_renames() {
+        let mut registry = rename_registry();
+        assert_eq!(registry.modern_name_for("ActiveContract"), None);
+        assert!(registry.modern_model_for("ActiveContract").is_none());
+
+        registry.add_rename("ActiveContract", "ContractActive");
+        assert_eq!(registry.rename_count(), 1);
+        assert_eq!(
+            registry.modern_name_for("ActiveContract").as_deref(),
+            Some("ContractActive")
+        );
+        assert_eq!(
+            registry.modern_name_for("ActiveContractCodeGen").as_deref(),
+            Some("ContractActiveCodeGen")
+        );
+        assert_eq!(registry.modern_name_for("Job").as_deref(), Some("Job"));
+        assert_eq!(
+            registry.modern_model_for("ActiveContract").map(|m| m.name.as_str()),
+            Some("ContractActive")
+        );
+    }
+
+    #[test]
+    fn test_model_registry_missing_equivalents() {
+        use crate::types::{FileId, SourceLocation};
+
+        let registry = rename_registry();
+        let mut file = FileInfo::new(FileId::new(1), "app/contracts/list.ts".into());
+        file.imports.push(ImportInfo::new(
+            "'../shared/models/active-contract'",
+            ImportKind::Named,
+            smallvec!["ActiveContract".to_owned(), "Job".to_owned()],
+            Some(ModelSource::SharedLegacy),
+            SourceLocation::new(1, 0, 0),
+        ));
+        file.imports.push(ImportInfo::new(
+            "'../shared/models/job'",
+            ImportKind::Require,
+            smallvec!["models".to_owned()],
+            Some(ModelSource::SharedLegacy),
+            SourceLocation::new(2, 0, 0),
+        ));
+
+        assert_eq!(registry.missing_equivalents(&file), vec!["ActiveContract"]);
+    }
+
+    #[test]
+    fn test_model_registry_stats() {
+        let mut registry = rename_registry();
+        registry.add_rename("ActiveContract", "ContractActive");
+
+        let stats = registry.stats();
+        assert_eq!(stats.legacy_models, 1);
+        assert_eq!(stats.modern_models, 2);
+        assert_eq!(stats.ported_models, 1);
+        assert_eq!(stats.legacy_exports, 2);
+        assert_eq!(stats.modern_exports, 3);
+        assert_eq!(stats.renames, 1);
+    }
+
+    #[test]
+    fn test_model_registry_unmatched_names() {
+        use crate::types::SourceLocation;
+
+        let registry = rename_registry();
+        let import = |kind, source| {
+            ImportInfo::new(
+                "'../shared/models/active-contract'",
+                kind,
+                smallvec!["ActiveContract".to_owned(), "ActiveContractForm".to_owned()],
+                source,
+                SourceLocation::new(1, 0, 0),
+            )
+        };
+
+        let named = import(ImportKind::Named, Some(ModelSource::SharedLegacy));
+        assert_eq!(registry.unmatched_names(&named), vec!["ActiveContractForm"]);
+        let modern = import(ImportKind::TypeOnly, Some(ModelSource::Shared2023));
+        assert_eq!(registry.unmatched_names(&modern).len(), 2);
+        let default = import(ImportKind::Default, Some(ModelSource::SharedLegacy));
+        assert!(registry.unmatched_names(&default).is_empty());
+        let local = import(ImportKind::Named, None);
+        assert!(registry.unmatched_names(&local).is_empty());
+        assert!(ModelRegistry::new().unmatched_names(&named).is_empty());
+    }
 }
@@ -3,8 +3,13 @@
 //! This module provides the [`MigrationStatus`] enum for tracking the
 //! migration state of files in the codebase.
 
+use std::fmt;
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
 
+use crate::error::ConfigError;
+
 /// The migration status of a file.
 ///
 /// Represents whether a file has been migrated from the legacy `shared/`
@@ -122,6 +127,58 @@ impl MigrationStatus {
             Self::NoModels => "No Models",
         }
     }
+
+    /// Returns the canonical machine-readable name for this status.
+    ///
+    /// This matches the serialized form and never changes with display
+    /// labels, so it is safe to use in view strings and scripts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ch_core::MigrationStatus;
+    ///
+    /// assert_eq!(MigrationStatus::NoModels.key(), "no_models");
+    /// assert_eq!("no_models".parse::<MigrationStatus>().ok(), Some(MigrationStatus::NoModels));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::Legacy => "legacy",
+            Self::Migrated => "migrated",
+            Self::Partial => "partial",
+            Self::NoModels => "no_models",
+        }
+    }
+
+    /// All statuses, in urgency order.
+    pub const ALL: [Self; 4] = [Self::Legacy, Self::Partial, Self::Migrated, Self::NoModels];
+}
+
+impl fmt::Display for MigrationStatus {
+    /// Formats the default [label](Self::label).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for MigrationStatus {
+    type Err = ConfigError;
+
+    /// Parses a canonical [key](Self::key), also accepting `no-models`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "legacy" => Ok(Self::Legacy),
+            "partial" => Ok(Self::Partial),
+            "migrated" => Ok(Self::Migrated),
+            "no_models" | "no-models" => Ok(Self::NoModels),
+            other => Err(ConfigError::InvalidOption {
+                option: "status".to_owned(),
+                reason: format!("unknown status: {other}"),
+            }),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -160,6 +217,23 @@ mod tests {
         assert_eq!(MigrationStatus::NoModels.label(), "No Models");
     }
 
+    #[test]
+    fn test_migration_status_key_round_trip() {
+        for status in MigrationStatus::ALL {
+            assert_eq!(status.key().parse::<MigrationStatus>().ok(), Some(status));
+            assert_eq!(
+                serde_json::to_string(&status).ok(),
+                Some(format!("\"{}\"", status.key()))
+            );
+        }
+        assert!("done".parse::<MigrationStatus>().is_err());
+    }
+
+    #[test]
+    fn test_migration_status_display() {
+        assert_eq!(MigrationStatus::NoModels.to_string(), "No Models");
+    }
+
     #[test]
     fn test_migration_status_default() {
         assert_eq!(MigrationStatus::default(), MigrationStatus::Legacy);
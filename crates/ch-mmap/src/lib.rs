@@ -0,0 +1,59 @@
+//! Read-only memory maps of source files.
+//!
+//! Scanning a very large generated file is cheaper when the parser reads it
+//! straight from the page cache instead of copying it into a buffer. Mapping
+//! a file is unsafe, and every other crate in the workspace forbids unsafe
+//! code, so the one mapping call lives here on its own.
+//!
+//! # The invariant
+//!
+//! memmap2 maps the file shared: the bytes behind a [`MappedFile`] are the
+//! file's pages, not a copy. If any process writes to the file in place
+//! while it's mapped, the bytes change under the `&[u8]` (and any `&str`
+//! made from it), which is undefined behaviour; if it truncates the file,
+//! reading past the new end raises `SIGBUS`. Rust can't check either, so
+//! callers must only map files that nothing modifies while the map is
+//! alive. In practice that means one-shot scans of generated files, never
+//! rescans of files that were just saved or scans while editors are open on
+//! the tree.
+
+use std::fs::File;
+use std::io;
+use std::ops::Deref;
+
+use memmap2::Mmap;
+
+/// A file's contents mapped into memory, read-only.
+///
+/// See the [crate docs](crate) for the invariant callers must keep.
+pub struct MappedFile(Mmap);
+
+impl MappedFile {
+    /// Maps the whole of `file` into memory.
+    ///
+    /// The caller must ensure that no process writes to or truncates the
+    /// file until the returned map is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be mapped, e.g. because it isn't
+    /// a regular file.
+    #[allow(unsafe_code)] // the only unsafe code in the workspace; see the crate docs
+    pub fn map(file: &File) -> io::Result<Self> {
+        // SAFETY: the map is read-only and private to this value, so nothing
+        // in this process writes through it. Soundness also needs the file to
+        // stay unmodified by other processes while it's mapped; that isn't
+        // checkable here and is the documented contract of this function,
+        // which the scanner keeps by mapping only during one-shot scans.
+        let map = unsafe { Mmap::map(file) }?;
+        Ok(Self(map))
+    }
+}
+
+impl Deref for MappedFile {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
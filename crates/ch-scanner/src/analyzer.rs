@@ -25,6 +25,14 @@
 //! - **Efficient allocation**: Arena allocation for parse strings
 //! - **Work stealing**: Rayon's work-stealing scheduler
 //!
+//! When a scanner's [`ResourceLimits`](crate::ResourceLimits) are exceeded,
+//! threads switch from the herd to a single arena reset after every file.
+//!
+//! Files are read into one buffer each, which is validated as UTF-8 in place
+//! and handed to the parser without another copy. Files at or above
+//! [`FileAnalyzer::with_mmap_threshold`] are memory-mapped and parsed straight
+//! from the mapping instead.
+//!
 //! # Examples
 //!
 //! ```ignore
@@ -44,25 +52,53 @@
 //! }
 //! ```
 
+use std::any::Any;
 use std::fs;
 use std::hash::{Hash, Hasher};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::io::{self, Read as _};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use bumpalo_herd::Herd;
+use bumpalo::Bump;
+use bumpalo_herd::{Herd, Member};
 use camino::{Utf8Path, Utf8PathBuf};
-use ch_core::{FileId, FileInfo, ImportInfo, MigrationStatus, ModelRegistry, ModelSource};
-use ch_ts_parser::{detect_model_source_with, ArenaParser, ModelPathMatcher};
+use ch_core::{
+    FileId, FileInfo, ImportInfo, MigrationStatus, ModelCategory, ModelReference,
+    ModelRegistry, ModelSource,
+};
+use ch_ts_parser::{
+    count_references, detect_model_source_with, namespace_members, ArenaParser, ModelPathMatcher,
+    Tree,
+};
 use parking_lot::Mutex;
+use rayon::ThreadPool;
 use rayon::prelude::*;
+use ch_mmap::MappedFile;
 use rustc_hash::FxHasher;
 use smallvec::SmallVec;
 use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
 
 use crate::cache::ScanCache;
+use crate::dedup::{ContentIndex, Duplicates};
 use crate::error::ScanError;
+use crate::ids::FileIds;
+use crate::ignores::ImportIgnores;
+use crate::limits::MemoryGuard;
+use crate::observer::SharedObserver;
+use crate::ownership::CodeOwners;
+use crate::profile::FileTimings;
+use crate::progress::ProgressTicker;
 use crate::stats::ScanStats;
 use crate::ScanUpdate;
 
+/// Number of leading bytes checked for NUL bytes when sniffing for binary
+/// content.
+const BINARY_SNIFF_LEN: usize = 8000;
+
 /// Parallel file analyzer using rayon and per-thread arenas.
 ///
 /// Processes TypeScript files in parallel, extracting imports and determining
@@ -83,7 +119,79 @@ use crate::ScanUpdate;
 /// ```
 #[derive(Debug, Default)]
 pub struct FileAnalyzer {
-    _private: (), // Prevent external construction
+    /// Ownership rules used to attribute files to teams (if configured).
+    owners: Option<Arc<CodeOwners>>,
+    /// Rules for model imports excluded from migration status (if configured).
+    ignores: Option<Arc<ImportIgnores>>,
+    /// Memory limits that switch analysis to low-memory mode (if configured).
+    memory: Option<Arc<MemoryGuard>>,
+    /// Number of threads currently analyzing a file (if tracked).
+    busy: Option<Arc<AtomicUsize>>,
+    /// Token that stops analysis before the next file (if configured).
+    cancel: Option<CancellationToken>,
+    /// Size in bytes above which files are skipped (if configured).
+    max_file_size: Option<u64>,
+    /// Whether to skip files whose contents look binary.
+    skip_binary: bool,
+    /// Size in bytes from which files are memory-mapped (if configured).
+    mmap_threshold: Option<u64>,
+    /// Where to record each file's analysis time (if configured).
+    timings: Option<Arc<FileTimings>>,
+    /// Where to count the files read for parsing (if configured).
+    parsed: Option<Arc<ScanStats>>,
+    /// Pool analyzing files in parallel (if configured; rayon's global pool
+    /// otherwise).
+    pool: Option<Arc<ThreadPool>>,
+    /// Table handing out file ids (if configured; path hashes otherwise).
+    ids: Option<Arc<FileIds>>,
+    /// When streaming analysis sends progress updates (if configured).
+    progress: Option<Arc<ProgressTicker>>,
+    /// Analyses reused for files with the same contents (if configured).
+    dedup: Option<Arc<ContentIndex>>,
+    /// Embedder's hook told about each file analyzed (if configured).
+    observer: Option<SharedObserver>,
+}
+
+/// Per-thread analysis state: parsers, arenas and limit bookkeeping.
+struct ThreadState<'h> {
+    /// Parser for `.ts` files.
+    ts_parser: Option<ArenaParser>,
+    /// Parser for `.tsx` files.
+    tsx_parser: Option<ArenaParser>,
+    /// Arena from the herd, kept for the whole call.
+    member: Member<'h>,
+    /// Arena reset after every file, used in low-memory mode.
+    scratch: Bump,
+    /// Number of files this thread analyzed before the current one.
+    analyzed: usize,
+    /// Where the timings below are merged when the thread is done (if
+    /// configured).
+    timings: Option<Arc<FileTimings>>,
+    /// Analysis times of the files this thread analyzed, not yet merged.
+    timed: Vec<(Utf8PathBuf, Duration)>,
+}
+
+impl<'h> ThreadState<'h> {
+    /// Creates parsers and takes an arena from `herd`.
+    fn new(herd: &'h Herd, timings: Option<&Arc<FileTimings>>) -> Self {
+        Self {
+            ts_parser: ArenaParser::new().ok(),
+            tsx_parser: ArenaParser::new_tsx().ok(),
+            member: herd.get(),
+            scratch: Bump::new(),
+            analyzed: 0,
+            timings: timings.map(Arc::clone),
+            timed: Vec::new(),
+        }
+    }
+}
+
+impl Drop for ThreadState<'_> {
+    fn drop(&mut self) {
+        if let Some(timings) = &self.timings {
+            timings.record_all(self.timed.drain(..));
+        }
+    }
 }
 
 impl FileAnalyzer {
@@ -102,6 +210,157 @@ impl FileAnalyzer {
         Self::default()
     }
 
+    /// Attributes analyzed files to owners using the given `CODEOWNERS` rules.
+    ///
+    /// # Arguments
+    ///
+    /// * `owners` - Parsed ownership rules
+    #[must_use]
+    pub fn with_codeowners(mut self, owners: Arc<CodeOwners>) -> Self {
+        self.owners = Some(owners);
+        self
+    }
+
+    /// Excludes model imports matching the given rules from migration status.
+    ///
+    /// Matching imports are moved to [`FileInfo::ignored_imports`].
+    ///
+    /// # Arguments
+    ///
+    /// * `ignores` - Import ignore rules
+    #[must_use]
+    pub fn with_import_ignores(mut self, ignores: Arc<ImportIgnores>) -> Self {
+        self.ignores = Some(ignores);
+        self
+    }
+
+    /// Checks memory limits while analyzing, switching to low-memory mode
+    /// once the guard reports one exceeded.
+    #[must_use]
+    pub(crate) fn with_memory_guard(mut self, memory: Arc<MemoryGuard>) -> Self {
+        self.memory = Some(memory);
+        self
+    }
+
+    /// Counts the threads analyzing a file in `busy` while they work.
+    #[must_use]
+    pub(crate) fn with_busy_counter(mut self, busy: Arc<AtomicUsize>) -> Self {
+        self.busy = Some(busy);
+        self
+    }
+
+    /// Records the time spent analyzing each file in `timings`.
+    #[must_use]
+    pub(crate) fn with_timings(mut self, timings: Arc<FileTimings>) -> Self {
+        self.timings = Some(timings);
+        self
+    }
+
+    /// Counts each file read for parsing, by kind and size, in `stats`.
+    #[must_use]
+    pub(crate) fn with_parse_stats(mut self, stats: Arc<ScanStats>) -> Self {
+        self.parsed = Some(stats);
+        self
+    }
+
+    /// Sends [`ScanUpdate::Progress`] from streaming analysis when
+    /// `progress` says one is due.
+    #[must_use]
+    pub(crate) fn with_progress(mut self, progress: Arc<ProgressTicker>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Takes file ids from `ids`, so they follow renamed files.
+    #[must_use]
+    pub(crate) fn with_file_ids(mut self, ids: Arc<FileIds>) -> Self {
+        self.ids = Some(ids);
+        self
+    }
+
+    /// Parses each distinct file contents at most twice, reusing the
+    /// analysis for further copies and recording copies in `duplicates`.
+    #[must_use]
+    pub(crate) fn with_duplicates(mut self, duplicates: Arc<Duplicates>) -> Self {
+        self.dedup = Some(Arc::new(ContentIndex::new(duplicates)));
+        self
+    }
+
+    /// Tells `observer` as each file's analysis starts and ends.
+    #[must_use]
+    pub(crate) fn with_observer(mut self, observer: SharedObserver) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Analyzes files in `pool` instead of rayon's global pool.
+    ///
+    /// Bounds the threads analysis uses without affecting other rayon users
+    /// in the process.
+    #[must_use]
+    pub fn with_thread_pool(mut self, pool: Arc<ThreadPool>) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    /// Runs `op` in the configured pool, if any.
+    fn install<R: Send>(&self, op: impl FnOnce() -> R + Send) -> R {
+        match &self.pool {
+            Some(pool) => pool.install(op),
+            None => op(),
+        }
+    }
+
+    /// Stops analysis once `cancel` is cancelled.
+    ///
+    /// Each thread checks the token before starting its next file, so files
+    /// already being analyzed finish and the rest are left out of the
+    /// results.
+    #[must_use]
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// Skips files larger than `bytes` without reading them.
+    ///
+    /// Such files fail with [`ScanError::Skipped`] instead of being read in
+    /// full and handed to the parser.
+    #[must_use]
+    pub const fn with_max_file_size(mut self, bytes: u64) -> Self {
+        self.max_file_size = Some(bytes);
+        self
+    }
+
+    /// Configures whether to skip files whose contents look binary.
+    ///
+    /// A file looks binary if its first 8000 bytes contain a NUL byte, the
+    /// same check `git` uses. Such files fail with [`ScanError::Skipped`]
+    /// instead of failing in the parser.
+    #[must_use]
+    pub const fn with_skip_binary(mut self, skip: bool) -> Self {
+        self.skip_binary = skip;
+        self
+    }
+
+    /// Memory-maps files of `bytes` or more instead of reading them.
+    ///
+    /// The parser reads a mapped file straight from the page cache, which
+    /// saves allocating and copying very large generated files. Mapping is
+    /// only sound while no process writes to or truncates the file (see
+    /// [`ch_mmap`]), so only set this on analyzers for one-shot scans of
+    /// files nothing is editing, never for rescans of saved files.
+    #[must_use]
+    pub const fn with_mmap_threshold(mut self, bytes: u64) -> Self {
+        self.mmap_threshold = Some(bytes);
+        self
+    }
+
+    /// Returns `true` if analysis has been cancelled.
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancel.as_ref().is_some_and(CancellationToken::is_cancelled)
+    }
+
     /// Analyzes multiple files in parallel.
     ///
     /// Uses rayon's parallel iterator with per-thread parser and arena
@@ -117,6 +376,8 @@ impl FileAnalyzer {
     ///
     /// A vector of `(path, Result<FileInfo, ScanError>)` tuples.
     /// Failed analyses return errors while successful ones continue.
+    /// Files skipped after [cancellation](Self::with_cancellation) have no
+    /// entry.
     ///
     /// # Registry Filtering
     ///
@@ -143,33 +404,42 @@ impl FileAnalyzer {
         matcher: &ModelPathMatcher,
         registry: Option<&ModelRegistry>,
     ) -> Vec<(Utf8PathBuf, Result<FileInfo, ScanError>)> {
+        self.analyze_files_timed(paths, matcher, registry)
+            .into_iter()
+            .map(|(path, result, _)| (path, result))
+            .collect()
+    }
+
+    /// Analyzes multiple files in parallel, timing each one.
+    ///
+    /// Same as [`analyze_files`](Self::analyze_files), with the time spent
+    /// reading, parsing and analyzing each file appended to its result.
+    #[must_use]
+    pub fn analyze_files_timed(
+        &self,
+        paths: &[Utf8PathBuf],
+        matcher: &ModelPathMatcher,
+        registry: Option<&ModelRegistry>,
+    ) -> Vec<(Utf8PathBuf, Result<FileInfo, ScanError>, Duration)> {
         // Create a Herd for per-thread arenas
         let herd = Herd::new();
 
-        paths
-            .par_iter()
-            .map_init(
-                // Per-thread initialization: create parser + get arena member
-                || {
-                    let ts_parser = ArenaParser::new().ok();
-                    let tsx_parser = ArenaParser::new_tsx().ok();
-                    let member = herd.get();
-                    (ts_parser, tsx_parser, member)
-                },
-                // Process each file
-                |(ts_parser, tsx_parser, member), path| {
-                    let result = self.analyze_file_inner(
-                        path,
-                        ts_parser.as_mut(),
-                        tsx_parser.as_mut(),
-                        member.as_bump(),
-                        matcher,
-                        registry,
-                    );
-                    (path.clone(), result)
-                },
-            )
-            .collect()
+        self.install(|| {
+            paths
+                .par_iter()
+                .filter(|_| !self.is_cancelled())
+                .map_init(
+                    // Per-thread initialization: create parser + get arena member
+                    || ThreadState::new(&herd, self.timings.as_ref()),
+                    // Process each file
+                    |state, path| {
+                        let started = Instant::now();
+                        let result = self.analyze_with_state(state, path, matcher, registry);
+                        (path.clone(), result, started.elapsed())
+                    },
+                )
+                .collect()
+        })
     }
 
     /// Analyzes files in parallel, streaming results via channel.
@@ -195,7 +465,8 @@ impl FileAnalyzer {
     /// # Cancellation
     ///
     /// If the channel receiver is dropped, `blocking_send` will fail and
-    /// the remaining work will complete without sending updates.
+    /// the remaining work will complete without sending updates. To stop the
+    /// remaining work as well, use [`with_cancellation`](Self::with_cancellation).
     #[must_use]
     pub fn analyze_files_streaming(
         &self,
@@ -211,62 +482,58 @@ impl FileAnalyzer {
         // Collect errors using mutex (errors are rare, so contention is minimal)
         let errors: Mutex<Vec<(Utf8PathBuf, ScanError)>> = Mutex::new(Vec::new());
 
-        paths
-            .par_iter()
-            .for_each_init(
-                // Per-thread initialization: create parser + get arena member
-                || {
-                    let ts_parser = ArenaParser::new().ok();
-                    let tsx_parser = ArenaParser::new_tsx().ok();
-                    let member = herd.get();
-                    (ts_parser, tsx_parser, member, tx.clone())
-                },
-                // Process each file
-                |(ts_parser, tsx_parser, member, sender), path| {
-                    stats.increment_total();
-
-                    let result = self.analyze_file_inner(
-                        path,
-                        ts_parser.as_mut(),
-                        tsx_parser.as_mut(),
-                        member.as_bump(),
-                        matcher,
-                        registry,
-                    );
-
-                    match result {
-                        Ok(file_info) => {
-                            // Update statistics based on status
-                            match file_info.status {
-                                MigrationStatus::Legacy => stats.increment_legacy(),
-                                MigrationStatus::Migrated => stats.increment_migrated(),
-                                MigrationStatus::Partial => stats.increment_partial(),
-                                MigrationStatus::NoModels => stats.increment_no_models(),
-                                _ => {} // Handle any future status variants
+        self.install(|| {
+            paths
+                .par_iter()
+                .filter(|_| !self.is_cancelled())
+                .for_each_init(
+                    // Per-thread initialization: create parser + get arena member
+                    || (ThreadState::new(&herd, self.timings.as_ref()), tx.clone()),
+                    // Process each file
+                    |(state, sender), path| {
+                        stats.increment_total();
+
+                        let result = self.analyze_with_state(state, path, matcher, registry);
+
+                        match result {
+                            Ok(file_info) => {
+                                // Update statistics based on status
+                                match file_info.status {
+                                    MigrationStatus::Legacy => stats.increment_legacy(),
+                                    MigrationStatus::Migrated => stats.increment_migrated(),
+                                    MigrationStatus::Partial => stats.increment_partial(),
+                                    MigrationStatus::NoModels => stats.increment_no_models(),
+                                    _ => {} // Handle any future status variants
+                                }
+
+                                // Insert into cache
+                                cache.insert(file_info.clone());
+
+                                // Send update (ignore if receiver dropped)
+                                // Box the FileInfo to match ScanUpdate::FileScanned(Box<FileInfo>)
+                                let _ = sender
+                                    .blocking_send(ScanUpdate::FileScanned(Box::new(file_info)));
                             }
+                            Err(e) => {
+                                stats.increment_errors();
 
-                            // Insert into cache
-                            cache.insert(file_info.clone());
+                                // Collect error
+                                errors.lock().push((path.clone(), e.clone()));
 
-                            // Send update (ignore if receiver dropped)
-                            // Box the FileInfo to match ScanUpdate::FileScanned(Box<FileInfo>)
-                            let _ = sender.blocking_send(ScanUpdate::FileScanned(Box::new(file_info)));
+                                // Send error update (ignore if receiver dropped)
+                                let _ = sender.blocking_send(ScanUpdate::FileError {
+                                    path: path.clone(),
+                                    error: e,
+                                });
+                            }
                         }
-                        Err(e) => {
-                            stats.increment_errors();
-
-                            // Collect error
-                            errors.lock().push((path.clone(), e.clone()));
 
-                            // Send error update (ignore if receiver dropped)
-                            let _ = sender.blocking_send(ScanUpdate::FileError {
-                                path: path.clone(),
-                                error: e,
-                            });
+                        if self.progress.as_ref().is_some_and(|progress| progress.tick()) {
+                            let _ = sender.blocking_send(ScanUpdate::Progress(stats.snapshot()));
                         }
-                    }
-                },
-            );
+                    },
+                );
+        });
 
         // Return collected errors
         errors.into_inner()
@@ -290,14 +557,16 @@ impl FileAnalyzer {
     /// # Errors
     ///
     /// - [`ScanError::Read`] if the file cannot be read
+    /// - [`ScanError::Skipped`] if the file is too large or looks binary
     /// - [`ScanError::Parse`] if the file cannot be parsed
+    /// - [`ScanError::Internal`] if analysis panics
     pub fn analyze_single(
         &self,
         path: &Utf8Path,
         matcher: &ModelPathMatcher,
         registry: Option<&ModelRegistry>,
     ) -> Result<FileInfo, ScanError> {
-        let arena = bumpalo::Bump::new();
+        let arena = Bump::new();
         let is_tsx = path.extension().is_some_and(|e| e == "tsx");
 
         let mut parser = if is_tsx {
@@ -307,39 +576,172 @@ impl FileAnalyzer {
         }
         .map_err(|e| ScanError::parse(path, e))?;
 
-        self.analyze_file_inner(
+        catch_panic(path, || {
+            self.analyze_file_inner(path, Some(&mut parser), None, &arena, matcher, registry)
+        })
+    }
+
+    /// Analyzes a file with a thread's parsers and arena, then checks limits.
+    ///
+    /// In low-memory mode the thread's scratch arena is used and reset
+    /// afterwards; otherwise the herd arena is used and its size checked
+    /// against the memory guard.
+    fn analyze_with_state(
+        &self,
+        state: &mut ThreadState<'_>,
+        path: &Utf8Path,
+        matcher: &ModelPathMatcher,
+        registry: Option<&ModelRegistry>,
+    ) -> Result<FileInfo, ScanError> {
+        if let Some(busy) = &self.busy {
+            busy.fetch_add(1, Ordering::Relaxed);
+        }
+        if let Some(SharedObserver(observer)) = &self.observer {
+            observer.on_file_start(path);
+        }
+        let started = Instant::now();
+        let low_memory = self.memory.as_ref().is_some_and(|memory| memory.is_low_memory());
+        let arena = if low_memory {
+            &state.scratch
+        } else {
+            state.member.as_bump()
+        };
+
+        let result = self.analyze_file_guarded(
             path,
-            Some(&mut parser),
-            None,
-            &arena,
+            &mut state.ts_parser,
+            &mut state.tsx_parser,
+            arena,
             matcher,
             registry,
-        )
+        );
+
+        if low_memory {
+            state.scratch.reset();
+        } else if let Some(memory) = &self.memory {
+            memory.check(state.member.as_bump().allocated_bytes(), state.analyzed);
+        }
+        state.analyzed += 1;
+        let elapsed = started.elapsed();
+        if state.timings.is_some() {
+            state.timed.push((path.to_owned(), elapsed));
+        }
+        if let Some(SharedObserver(observer)) = &self.observer {
+            match &result {
+                Ok(file) => observer.on_file_done(path, file, elapsed),
+                Err(e) => observer.on_error(path, e),
+            }
+        }
+        if let Some(busy) = &self.busy {
+            busy.fetch_sub(1, Ordering::Relaxed);
+        }
+        result
+    }
+
+    /// Analyzes a file with a per-thread parser pair, isolating panics.
+    ///
+    /// A panic is converted into [`ScanError::Internal`] so the rest of the
+    /// scan can continue. The thread's parsers are recreated afterwards since
+    /// their internal state may be inconsistent.
+    fn analyze_file_guarded(
+        &self,
+        path: &Utf8Path,
+        ts_parser: &mut Option<ArenaParser>,
+        tsx_parser: &mut Option<ArenaParser>,
+        arena: &Bump,
+        matcher: &ModelPathMatcher,
+        registry: Option<&ModelRegistry>,
+    ) -> Result<FileInfo, ScanError> {
+        let result = catch_panic(path, || {
+            self.analyze_file_inner(
+                path,
+                ts_parser.as_mut(),
+                tsx_parser.as_mut(),
+                arena,
+                matcher,
+                registry,
+            )
+        });
+
+        if let Err(ScanError::Internal { message, .. }) = &result {
+            warn!(path = %path, panic = %message, "File analysis panicked");
+            *ts_parser = ArenaParser::new().ok();
+            *tsx_parser = ArenaParser::new_tsx().ok();
+        }
+
+        result
+    }
+
+    /// Reads or maps a file's contents, unless it's over the size limit or
+    /// looks binary.
+    fn read_source(&self, path: &Utf8Path) -> Result<Source, ScanError> {
+        let read = |e| ScanError::read(path, e);
+        let mut file = fs::File::open(path).map_err(read)?;
+        if self.max_file_size.is_some() || self.mmap_threshold.is_some() {
+            let size = file.metadata().map_err(read)?.len();
+            if let Some(max) = self.max_file_size.filter(|&max| size > max) {
+                return Err(ScanError::skipped(
+                    path,
+                    format!("file is {size} bytes, over the limit of {max}"),
+                ));
+            }
+            if self.mmap_threshold.is_some_and(|threshold| size >= threshold) {
+                let map = MappedFile::map(&file).map_err(read)?;
+                if self.skip_binary && looks_binary(&map) {
+                    return Err(ScanError::skipped(path, "file looks binary"));
+                }
+                return Ok(Source::Mapped(map));
+            }
+        }
+
+        // Sniff the start of the file before reading the rest of it
+        let mut bytes = Vec::new();
+        if self.skip_binary {
+            (&mut file).take(BINARY_SNIFF_LEN as u64).read_to_end(&mut bytes).map_err(read)?;
+            if looks_binary(&bytes) {
+                return Err(ScanError::skipped(path, "file looks binary"));
+            }
+        }
+        file.read_to_end(&mut bytes).map_err(read)?;
+        String::from_utf8(bytes).map(Source::Read).map_err(|e| invalid_utf8(path, e))
     }
 
     /// Internal file analysis implementation.
-    #[allow(clippy::unused_self)] // Method signature kept for consistency
     fn analyze_file_inner(
         &self,
         path: &Utf8Path,
         ts_parser: Option<&mut ArenaParser>,
         tsx_parser: Option<&mut ArenaParser>,
-        arena: &bumpalo::Bump,
+        arena: &Bump,
         matcher: &ModelPathMatcher,
         registry: Option<&ModelRegistry>,
     ) -> Result<FileInfo, ScanError> {
         // Read file contents
-        let contents = fs::read_to_string(path.as_std_path())
-            .map_err(|e| ScanError::read(path, e))?;
+        let source = self.read_source(path)?;
+        let contents = source.as_str(path)?;
 
         // Calculate content hash
-        let content_hash = hash_content(&contents);
+        let content_hash = hash_content(contents);
 
-        // Generate file ID from path hash
-        let file_id = FileId::new(hash_path(path));
+        // Take the file's id from the table, or hash its path without one
+        let file_id =
+            self.ids.as_ref().map_or_else(|| FileId::new(hash_path(path)), |ids| ids.assign(path));
 
-        // Select parser based on extension
+        // Reuse the analysis of an earlier file with the same contents
         let is_tsx = path.extension().is_some_and(|e| e == "tsx");
+        let reused = self.dedup.as_ref().and_then(|dedup| dedup.reuse(path, content_hash, is_tsx));
+        if let Some(mut file) = reused {
+            file.id = file_id;
+            path.clone_into(&mut file.path);
+            file.last_scanned = now_secs();
+            file.owners = self.owners_of(path);
+            return Ok(file);
+        }
+        if let Some(stats) = &self.parsed {
+            stats.record_parsed(path, u64::try_from(contents.len()).unwrap_or(u64::MAX));
+        }
+
+        // Select parser based on extension
         let parser = if is_tsx {
             tsx_parser.or(ts_parser)
         } else {
@@ -352,7 +754,7 @@ impl FileAnalyzer {
 
         // Parse the file
         let parse_result = parser
-            .parse_with_arena(arena, &contents)
+            .parse_with_arena(arena, contents)
             .map_err(|e| ScanError::parse(path, e))?;
 
         // Convert imports to owned and calculate status
@@ -361,15 +763,19 @@ impl FileAnalyzer {
             .into_iter()
             .map(ch_ts_parser::BumpImportInfo::into_owned)
             .collect();
+        find_members(&mut imports, &parse_result.tree, contents);
 
         // Process each import: detect source and optionally filter by registry
         for import in &mut imports {
             // First, detect if this is a shared directory import
             if let Some(detected_source) = detect_model_source_with(&import.path, matcher) {
-                // If we have a registry, validate that at least one imported name
-                // is a known model export from the detected source
-                if let Some(reg) = registry {
-                    let has_model_export = import.names.iter().any(|name| {
+                // If we have a registry, validate that at least one used name
+                // is a known model export from the detected source. A whole
+                // module used through no members names no exports, so it's
+                // matched by path alone.
+                let checkable = !import.binds_module() || !import.members.is_empty();
+                if let Some(reg) = registry.filter(|_| checkable) {
+                    let has_model_export = import.used_names().iter().any(|name| {
                         reg.is_export_from(name, detected_source)
                     });
 
@@ -384,28 +790,140 @@ impl FileAnalyzer {
                     import.source = Some(detected_source);
                 }
             } else {
-                import.source = None;
+                // Imports from a barrel file take the source of the models it re-exports
+                import.source = registry.and_then(|reg| barrel_source(import, matcher, reg));
             }
         }
 
-        let status = determine_status(&imports);
+        let ignored_imports = self
+            .ignores
+            .as_ref()
+            .map(|ignores| ignores.split_off(&mut imports))
+            .unwrap_or_default();
 
-        // Get current timestamp
-        let last_scanned = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map(|d| d.as_secs())
-            .unwrap_or(0);
+        let status = determine_status(&imports);
+        let model_refs = find_model_refs(&imports, &parse_result.tree, contents);
 
-        Ok(FileInfo {
+        let file = FileInfo {
             id: file_id,
             path: path.to_owned(),
             content_hash,
             imports,
-            model_refs: SmallVec::new(), // TODO: populate from imports
+            model_refs,
             status,
-            last_scanned,
-        })
+            last_scanned: now_secs(),
+            owners: self.owners_of(path),
+            ignored_imports,
+        };
+        if let Some(dedup) = &self.dedup {
+            dedup.insert(&file, is_tsx);
+        }
+        Ok(file)
+    }
+
+    /// Returns the owners of `path`, empty without ownership rules.
+    fn owners_of(&self, path: &Utf8Path) -> SmallVec<[String; 2]> {
+        self.owners
+            .as_ref()
+            .map(|owners| owners.owners_of(path).iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Returns the current Unix timestamp in seconds.
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Returns the source of the models an import from a shared barrel file,
+/// such as `'../shared'`, names, following the barrel's re-exports.
+///
+/// An import naming models from both directories counts as legacy, since
+/// it still needs migrating.
+fn barrel_source(
+    import: &ImportInfo,
+    matcher: &ModelPathMatcher,
+    registry: &ModelRegistry,
+) -> Option<ModelSource> {
+    let (barrel, dir) = matcher.barrel_of(&import.path)?;
+    let mut sources = import
+        .used_names()
+        .iter()
+        .filter_map(|name| registry.barrel_model(barrel, &dir, name))
+        .map(|model| model.source);
+    let first = sources.next()?;
+    Some(if sources.any(ModelSource::is_legacy) {
+        ModelSource::SharedLegacy
+    } else {
+        first
+    })
+}
+
+/// Records the members each import binding a whole module is used through.
+fn find_members(imports: &mut [ImportInfo], tree: &Tree, contents: &str) {
+    let bindings: Vec<(usize, &str)> = imports
+        .iter()
+        .enumerate()
+        .filter(|(_, import)| import.binds_module())
+        .filter_map(|(index, import)| Some((index, import.names.first()?.as_str())))
+        .collect();
+    let namespaces: Vec<&str> = bindings.iter().map(|(_, name)| *name).collect();
+    let members = namespace_members(tree, contents, &namespaces);
+    let indices: Vec<usize> = bindings.iter().map(|(index, _)| *index).collect();
+    for (index, members) in indices.into_iter().zip(members) {
+        imports[index].members = members;
+    }
+}
+
+/// Finds the model imports that are referenced in the file body.
+///
+/// Each imported model name used at least once outside the import
+/// statements yields one [`ModelReference`]; unused names yield none, which
+/// is how dead imports are detected. A name imported under an alias is
+/// used where its alias is, but referenced by its exported name, and the
+/// members of an import binding a whole module are used by definition.
+fn find_model_refs(
+    imports: &[ImportInfo],
+    tree: &Tree,
+    contents: &str,
+) -> SmallVec<[ModelReference; 4]> {
+    let mut candidates: Vec<(&str, &str, ModelSource)> = Vec::new();
+    let mut members: Vec<(&str, ModelSource)> = Vec::new();
+    for import in imports {
+        let Some(source) = import.source else {
+            continue;
+        };
+        if import.binds_module() {
+            for member in &import.members {
+                if !members.iter().any(|(seen, _)| seen == member) {
+                    members.push((member, source));
+                }
+            }
+            continue;
+        }
+        for name in &import.names {
+            let local = import.local_name(name);
+            if !candidates.iter().any(|(_, seen, _)| *seen == local) {
+                candidates.push((name, local, source));
+            }
+        }
     }
+
+    let locals: Vec<&str> = candidates.iter().map(|(_, local, _)| *local).collect();
+    let counts = count_references(tree, contents, &locals);
+
+    candidates
+        .into_iter()
+        .zip(counts)
+        .filter(|(_, count)| *count > 0)
+        .map(|((name, _, source), _)| (name, source))
+        .chain(members)
+        .map(|(name, source)| {
+            ModelReference::new(name, ModelCategory::from_export_name(name), source)
+        })
+        .collect()
 }
 
 /// Determines the migration status based on imports.
@@ -439,20 +957,71 @@ fn determine_status(imports: &[ImportInfo]) -> MigrationStatus {
     }
 }
 
+/// A file's contents, read into memory or mapped from disk.
+enum Source {
+    /// Contents read into a buffer, already checked to be UTF-8.
+    Read(String),
+    /// Contents mapped from disk, not yet checked.
+    Mapped(MappedFile),
+}
+
+impl Source {
+    /// Returns the contents as text.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScanError::Read`] if mapped contents aren't valid UTF-8.
+    fn as_str(&self, path: &Utf8Path) -> Result<&str, ScanError> {
+        match self {
+            Self::Read(contents) => Ok(contents),
+            Self::Mapped(map) => std::str::from_utf8(map).map_err(|e| invalid_utf8(path, e)),
+        }
+    }
+}
+
+/// Turns a UTF-8 validation failure into a read error.
+fn invalid_utf8(path: &Utf8Path, e: impl std::error::Error + Send + Sync + 'static) -> ScanError {
+    ScanError::read(path, io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Returns `true` if there is a NUL byte within the first
+/// [`BINARY_SNIFF_LEN`] bytes.
+fn looks_binary(bytes: &[u8]) -> bool {
+    bytes[..bytes.len().min(BINARY_SNIFF_LEN)].contains(&0)
+}
+
 /// Computes a fast hash of file contents using `FxHash`.
-fn hash_content(content: &str) -> u64 {
+pub(crate) fn hash_content(content: &str) -> u64 {
     let mut hasher = FxHasher::default();
     content.hash(&mut hasher);
     hasher.finish()
 }
 
 /// Computes a fast hash of a file path using `FxHash`.
-fn hash_path(path: &Utf8Path) -> u64 {
+pub(crate) fn hash_path(path: &Utf8Path) -> u64 {
     let mut hasher = FxHasher::default();
     path.hash(&mut hasher);
     hasher.finish()
 }
 
+/// Runs `f`, converting a panic into [`ScanError::Internal`] for `path`.
+fn catch_panic<T>(
+    path: &Utf8Path,
+    f: impl FnOnce() -> Result<T, ScanError>,
+) -> Result<T, ScanError> {
+    panic::catch_unwind(AssertUnwindSafe(f))
+        .unwrap_or_else(|payload| Err(ScanError::internal(path, panic_message(payload.as_ref()))))
+}
+
+/// Extracts a readable message from a panic payload.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| (*s).to_owned())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "panicked with a non-string payload".to_owned())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -468,6 +1037,86 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_catch_panic_converts_to_internal_error() {
+        let path = Utf8Path::new("src/boom.ts");
+        let result: Result<(), ScanError> =
+            catch_panic(path, || panic::resume_unwind(Box::new("boom")));
+
+        let err = result.err();
+        assert_eq!(err.as_ref().and_then(ScanError::path).map(|p| p.as_str()), Some("src/boom.ts"));
+        assert!(err.is_some_and(|e| e.is_recoverable() && e.to_string().ends_with("boom")));
+    }
+
+    #[test]
+    fn test_catch_panic_passes_through_result() {
+        let path = Utf8Path::new("src/ok.ts");
+        assert_eq!(catch_panic(path, || Ok(7)).ok(), Some(7));
+    }
+
+    #[test]
+    fn test_panic_message_string_payload() {
+        let payload: Box<dyn Any + Send> = Box::new(String::from("index out of bounds"));
+        assert_eq!(panic_message(payload.as_ref()), "index out of bounds");
+        let payload: Box<dyn Any + Send> = Box::new(42_u32);
+        assert_eq!(panic_message(payload.as_ref()), "panicked with a non-string payload");
+    }
+
+    #[test]
+    fn test_find_model_refs_skips_dead_imports() {
+        let source = r"
+            import { Foo, FooModel } from '../shared/models/foo';
+            import { Bar } from '../shared_2023/models/bar';
+            import { Component } from '@angular/core';
+            const foo = new Foo(new Bar());
+        ";
+        let refs = ch_ts_parser::TsParser::new()
+            .and_then(|mut parser| parser.parse(source))
+            .map(|result| find_model_refs(&result.imports, &result.tree, source))
+            .unwrap_or_default();
+
+        let names: Vec<_> = refs.iter().map(|r| (r.name.as_str(), r.source)).collect();
+        assert_eq!(
+            names,
+            [("Foo", ModelSource::SharedLegacy), ("Bar", ModelSource::Shared2023)]
+        );
+    }
+
+    #[test]
+    fn test_find_model_refs_follows_aliases() {
+        let source = r"
+            import { ActiveContract as AC, Job } from '../shared/models/active-contract';
+            import { Bar as Unused } from '../shared_2023/models/bar';
+            const contract: AC = new AC();
+            const bar = new Bar();
+        ";
+        let refs = ch_ts_parser::TsParser::new()
+            .and_then(|mut parser| parser.parse(source))
+            .map(|result| find_model_refs(&result.imports, &result.tree, source))
+            .unwrap_or_default();
+
+        let names: Vec<_> = refs.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["ActiveContract"]);
+    }
+
+    #[test]
+    fn test_find_model_refs_follows_namespace_members() {
+        let source = r"
+            import * as Models from '../shared/models';
+            const contract: Models.ActiveContract = new Models.Job();
+        ";
+        let refs = ch_ts_parser::TsParser::new()
+            .and_then(|mut parser| parser.parse(source))
+            .map(|mut result| {
+                find_members(&mut result.imports, &result.tree, source);
+                find_model_refs(&result.imports, &result.tree, source)
+            })
+            .unwrap_or_default();
+
+        let names: Vec<_> = refs.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["ActiveContract", "Job"]);
+    }
+
     #[test]
     fn test_determine_status_no_models() {
         let imports: Vec<ImportInfo> = vec![make_import(None), make_import(None)];
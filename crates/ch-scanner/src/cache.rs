@@ -11,6 +11,22 @@
 //! - **Uses read locks** for lookups and queries
 //! - **Uses write locks** for mutations and clears
 //!
+//! [`ScanCache::view`] read-locks the cache for queries that borrow
+//! entries instead of cloning them: the files under a directory, the files
+//! importing a model, or the imports whose path contains a fragment. The
+//! cache indexes files by the model exports they import, so finding the
+//! importers of a model doesn't visit any other file.
+//!
+//! # Detail Limit
+//!
+//! A cache created with [`ScanCache::with_max_detailed_files`] keeps the
+//! imports and model references of only that many files, those inserted or
+//! read most recently. Older entries are compacted: their imports, ignored
+//! imports and model references are dropped, while path, status, content
+//! hash and owners are kept, so counts by status stay right in long watch
+//! sessions on very large trees. Compacted files are analyzed again by the
+//! next scan instead of being reused.
+//!
 //! # Examples
 //!
 //! ```
@@ -30,9 +46,16 @@
 //! }
 //! ```
 
+use std::collections::BTreeSet;
+use std::ops::Bound;
+use std::sync::atomic::{AtomicU64, Ordering};
+
 use camino::{Utf8Path, Utf8PathBuf};
-use ch_core::{fx_hash_map_with_capacity, FxHashMap, FileInfo, MigrationStatus};
-use parking_lot::RwLock;
+use ch_core::{fx_hash_map_with_capacity, FxHashMap, FileInfo, ImportInfo, MigrationStatus};
+use parking_lot::{Mutex, RwLock, RwLockReadGuard};
+use smallvec::SmallVec;
+
+use crate::stats::StatsSnapshot;
 
 /// A thread-safe cache for storing [`FileInfo`] results.
 ///
@@ -69,8 +92,16 @@ use parking_lot::RwLock;
 /// ```
 #[derive(Debug, Default)]
 pub struct ScanCache {
-    /// The underlying concurrent map.
-    files: RwLock<FxHashMap<Utf8PathBuf, FileInfo>>,
+    /// The underlying concurrent map and its path index.
+    entries: RwLock<Entries>,
+    /// Most files kept with imports and model references (if limited).
+    max_detailed: Option<usize>,
+    /// Last use of each file kept in detail, when limited.
+    ///
+    /// Locked before `entries` whenever both are held.
+    detailed: Mutex<FxHashMap<Utf8PathBuf, u64>>,
+    /// Counter ordering uses of detailed files.
+    clock: AtomicU64,
 }
 
 impl ScanCache {
@@ -106,7 +137,76 @@ impl ScanCache {
     #[must_use]
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
-            files: RwLock::new(fx_hash_map_with_capacity(capacity)),
+            entries: RwLock::new(Entries {
+                files: fx_hash_map_with_capacity(capacity),
+                ..Entries::default()
+            }),
+            ..Self::default()
+        }
+    }
+
+    /// Creates a new cache keeping the details of at most `limit` files.
+    ///
+    /// Once more files are cached, the least recently inserted or read ones
+    /// are compacted (see the [module docs](self#detail-limit)).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ch_scanner::ScanCache;
+    /// use ch_core::{FileInfo, FileId};
+    /// use camino::{Utf8Path, Utf8PathBuf};
+    ///
+    /// let cache = ScanCache::with_max_detailed_files(1);
+    /// cache.insert(FileInfo::new(FileId::new(1), Utf8PathBuf::from("src/a.ts")));
+    /// cache.insert(FileInfo::new(FileId::new(2), Utf8PathBuf::from("src/b.ts")));
+    ///
+    /// assert_eq!(cache.len(), 2);
+    /// assert!(cache.is_compacted(Utf8Path::new("src/a.ts")));
+    /// ```
+    #[must_use]
+    pub fn with_max_detailed_files(limit: usize) -> Self {
+        Self {
+            max_detailed: Some(limit),
+            ..Self::default()
+        }
+    }
+
+    /// Returns `true` if the file at `path` is cached without its details.
+    #[must_use]
+    pub fn is_compacted(&self, path: &Utf8Path) -> bool {
+        self.max_detailed.is_some()
+            && !self.detailed.lock().contains_key(path)
+            && self.entries.read().files.contains_key(path)
+    }
+
+    /// Records a use of the detailed file at `path`, or adds it as detailed
+    /// if `inserted`, compacting the least recently used files beyond the
+    /// limit.
+    fn touch(&self, path: &Utf8Path, inserted: bool) {
+        let Some(limit) = self.max_detailed else {
+            return;
+        };
+        let tick = self.clock.fetch_add(1, Ordering::Relaxed);
+        let mut detailed = self.detailed.lock();
+        if inserted {
+            detailed.insert(path.to_owned(), tick);
+        } else if let Some(used) = detailed.get_mut(path) {
+            *used = tick;
+        }
+        if detailed.len() <= limit {
+            return;
+        }
+
+        // Compact a sixteenth more than needed so inserts don't sort every time
+        let excess = detailed.len() - limit + limit / 16;
+        let mut by_use: Vec<_> =
+            detailed.iter().map(|(path, used)| (*used, path.clone())).collect();
+        by_use.select_nth_unstable(excess - 1);
+        let mut entries = self.entries.write();
+        for (_, path) in by_use.into_iter().take(excess) {
+            detailed.remove(&path);
+            entries.compact(&path);
         }
     }
 
@@ -131,7 +231,34 @@ impl ScanCache {
     /// assert_eq!(cache.len(), 1);
     /// ```
     pub fn insert(&self, file: FileInfo) {
-        self.files.write().insert(file.path.clone(), file);
+        let path = file.path.clone();
+        self.entries.write().insert(file);
+        self.touch(&path, true);
+    }
+
+    /// Inserts a file into the cache, returning the entry it replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ch_scanner::ScanCache;
+    /// use ch_core::{FileInfo, FileId, MigrationStatus};
+    /// use camino::Utf8PathBuf;
+    ///
+    /// let cache = ScanCache::new();
+    /// let path = Utf8PathBuf::from("src/foo.ts");
+    /// assert!(cache.replace(FileInfo::new(FileId::new(1), path.clone())).is_none());
+    ///
+    /// let mut file = FileInfo::new(FileId::new(1), path);
+    /// file.status = MigrationStatus::Legacy;
+    /// let previous = cache.replace(file);
+    /// assert_eq!(previous.map(|f| f.status), Some(MigrationStatus::NoModels));
+    /// ```
+    pub fn replace(&self, file: FileInfo) -> Option<FileInfo> {
+        let path = file.path.clone();
+        let previous = self.entries.write().insert(file);
+        self.touch(&path, true);
+        previous
     }
 
     /// Returns a clone of the file info for the given path, if present.
@@ -160,7 +287,7 @@ impl ScanCache {
     /// ```
     #[must_use]
     pub fn get(&self, path: &Utf8PathBuf) -> Option<FileInfo> {
-        self.files.read().get(path).cloned()
+        self.get_by_path(path)
     }
 
     /// Returns a clone of the file info for the given path reference, if present.
@@ -174,7 +301,11 @@ impl ScanCache {
     /// A clone of the [`FileInfo`] if found, or `None`.
     #[must_use]
     pub fn get_by_path(&self, path: &Utf8Path) -> Option<FileInfo> {
-        self.files.read().get(path).cloned()
+        let file = self.entries.read().files.get(path).cloned();
+        if file.is_some() {
+            self.touch(path, false);
+        }
+        file
     }
 
     /// Checks if a file is in the cache.
@@ -198,7 +329,7 @@ impl ScanCache {
     /// ```
     #[must_use]
     pub fn contains(&self, path: &Utf8PathBuf) -> bool {
-        self.files.read().contains_key(path)
+        self.entries.read().files.contains_key(path)
     }
 
     /// Removes a file from the cache.
@@ -211,7 +342,23 @@ impl ScanCache {
     ///
     /// The removed [`FileInfo`] if found, or `None`.
     pub fn remove(&self, path: &Utf8PathBuf) -> Option<FileInfo> {
-        self.files.write().remove(path)
+        self.detailed.lock().remove(path);
+        self.entries.write().remove(path)
+    }
+
+    /// Removes every file under a directory from the cache.
+    ///
+    /// # Returns
+    ///
+    /// The number of files removed.
+    pub fn remove_prefix(&self, dir: &Utf8Path) -> usize {
+        self.detailed.lock().retain(|path, _| !path.starts_with(dir));
+        let mut entries = self.entries.write();
+        let under: Vec<_> = entries.paths_under(dir).cloned().collect();
+        for path in &under {
+            entries.remove(path);
+        }
+        under.len()
     }
 
     /// Returns the number of files in the cache.
@@ -226,7 +373,7 @@ impl ScanCache {
     /// ```
     #[must_use]
     pub fn len(&self) -> usize {
-        self.files.read().len()
+        self.entries.read().files.len()
     }
 
     /// Returns `true` if the cache is empty.
@@ -241,12 +388,28 @@ impl ScanCache {
     /// ```
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.files.read().is_empty()
+        self.entries.read().files.is_empty()
     }
 
     /// Clears all files from the cache.
     pub fn clear(&self) {
-        self.files.write().clear();
+        self.detailed.lock().clear();
+        let mut entries = self.entries.write();
+        entries.files.clear();
+        entries.paths.clear();
+        entries.importers.clear();
+    }
+
+    /// Counts the cached files by status.
+    ///
+    /// The returned snapshot has no errors, since failed files aren't cached.
+    #[must_use]
+    pub fn stats(&self) -> StatsSnapshot {
+        let mut stats = StatsSnapshot::default();
+        for file in self.entries.read().files.values() {
+            stats.record(file.status);
+        }
+        stats
     }
 
     /// Checks if a file needs to be updated based on content hash.
@@ -286,8 +449,9 @@ impl ScanCache {
     /// ```
     #[must_use]
     pub fn needs_update(&self, path: &Utf8PathBuf, content_hash: u64) -> bool {
-        self.files
+        self.entries
             .read()
+            .files
             .get(path)
             .is_none_or(|file| file.content_hash != content_hash)
     }
@@ -320,8 +484,9 @@ impl ScanCache {
     /// ```
     #[must_use]
     pub fn files_with_status(&self, status: MigrationStatus) -> Vec<FileInfo> {
-        self.files
+        self.entries
             .read()
+            .files
             .values()
             .filter(|file| file.status == status)
             .cloned()
@@ -351,8 +516,9 @@ impl ScanCache {
     /// ```
     #[must_use]
     pub fn files_needing_migration(&self) -> Vec<FileInfo> {
-        self.files
+        self.entries
             .read()
+            .files
             .values()
             .filter(|file| file.status.needs_migration())
             .cloned()
@@ -366,7 +532,7 @@ impl ScanCache {
     /// A vector of cloned [`FileInfo`] for all cached files.
     #[must_use]
     pub fn all_files(&self) -> Vec<FileInfo> {
-        self.files.read().values().cloned().collect()
+        self.entries.read().files.values().cloned().collect()
     }
 
     /// Returns all file paths in the cache.
@@ -376,7 +542,286 @@ impl ScanCache {
     /// A vector of cloned paths for all cached files.
     #[must_use]
     pub fn all_paths(&self) -> Vec<Utf8PathBuf> {
-        self.files.read().keys().cloned().collect()
+        self.entries.read().files.keys().cloned().collect()
+    }
+
+    /// Read-locks the cache for queries that borrow its entries.
+    ///
+    /// Writers, including the analysis threads of a running scan, wait
+    /// until the view is dropped, so keep it short-lived: collect what you
+    /// need from it, drop it, then act on the results.
+    ///
+    /// # Deadlocks
+    ///
+    /// The view holds the cache's lock until it is dropped, and the lock
+    /// isn't reentrant. On the thread holding a view, don't call any other
+    /// [`ScanCache`] method:
+    ///
+    /// - [`insert`](Self::insert), [`remove`](Self::remove) and other
+    ///   writes wait for the view forever.
+    /// - Reads such as [`get`](Self::get) or [`len`](Self::len) block too
+    ///   once another thread is waiting to write, since waiting writers go
+    ///   first.
+    ///
+    /// Query through the view itself instead, or take the owned results of
+    /// methods like [`all_files`](Self::all_files) where the cache is used
+    /// outside the scan loop.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ch_scanner::ScanCache;
+    /// use ch_core::{FileInfo, FileId};
+    /// use camino::{Utf8Path, Utf8PathBuf};
+    ///
+    /// let cache = ScanCache::new();
+    /// cache.insert(FileInfo::new(FileId::new(1), Utf8PathBuf::from("app/jobs/a.ts")));
+    /// cache.insert(FileInfo::new(FileId::new(2), Utf8PathBuf::from("app/main.ts")));
+    ///
+    /// let view = cache.view();
+    /// let jobs: Vec<_> = view.under(Utf8Path::new("app/jobs")).map(|f| &f.path).collect();
+    /// assert_eq!(jobs, ["app/jobs/a.ts"]);
+    /// ```
+    #[must_use]
+    pub fn view(&self) -> CacheView<'_> {
+        CacheView {
+            entries: self.entries.read(),
+        }
+    }
+
+    /// Finds imports whose module path contains `fragment`.
+    ///
+    /// Ignored imports are included. Results are sorted by file path and
+    /// line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ch_scanner::ScanCache;
+    /// use ch_core::{FileInfo, FileId, ImportInfo, ImportKind, SourceLocation};
+    /// use camino::Utf8PathBuf;
+    /// use smallvec::smallvec;
+    ///
+    /// let cache = ScanCache::new();
+    ///
+    /// let mut file = FileInfo::new(FileId::new(1), Utf8PathBuf::from("src/foo.ts"));
+    /// file.imports.push(ImportInfo::new(
+    ///     "'../shared/models/contract'",
+    ///     ImportKind::Named,
+    ///     smallvec!["Contract".to_owned()],
+    ///     None,
+    ///     SourceLocation::new(3, 0, 40),
+    /// ));
+    /// cache.insert(file);
+    ///
+    /// let found = cache.find_imports("shared/models/contract");
+    /// assert_eq!(found.len(), 1);
+    /// assert_eq!(found[0].1.location.line, 3);
+    /// ```
+    #[must_use]
+    pub fn find_imports(&self, fragment: &str) -> Vec<(Utf8PathBuf, ImportInfo)> {
+        let mut found: Vec<(Utf8PathBuf, ImportInfo)> = self
+            .view()
+            .imports_matching(fragment)
+            .map(|(file, import)| (file.path.clone(), import.clone()))
+            .collect();
+
+        // In path order already; a file's ignored imports follow the others
+        found.sort_by(|(a_path, a), (b_path, b)| {
+            a_path.cmp(b_path).then(a.location.line.cmp(&b.location.line))
+        });
+        found
+    }
+}
+
+/// Cached files with the index kept alongside them.
+#[derive(Debug, Default)]
+struct Entries {
+    /// Files by path.
+    files: FxHashMap<Utf8PathBuf, FileInfo>,
+    /// Paths in order, so the files under a directory are adjacent.
+    paths: BTreeSet<Utf8PathBuf>,
+    /// Files by the model exports they import.
+    importers: ModelIndex,
+}
+
+impl Entries {
+    /// Inserts `file`, returning the entry it replaced.
+    fn insert(&mut self, file: FileInfo) -> Option<FileInfo> {
+        let path = file.path.clone();
+        if let Some(previous) = self.files.get(&path) {
+            self.importers.remove(previous);
+        }
+        self.importers.add(&file);
+        let previous = self.files.insert(path.clone(), file);
+        if previous.is_none() {
+            self.paths.insert(path);
+        }
+        previous
+    }
+
+    /// Removes the file at `path`, returning it.
+    fn remove(&mut self, path: &Utf8Path) -> Option<FileInfo> {
+        let removed = self.files.remove(path)?;
+        self.paths.remove(path);
+        self.importers.remove(&removed);
+        Some(removed)
+    }
+
+    /// Drops the imports and model references of the file at `path`.
+    fn compact(&mut self, path: &Utf8Path) {
+        if let Some(file) = self.files.get_mut(path) {
+            self.importers.remove(file);
+            file.imports = SmallVec::new();
+            file.model_refs = SmallVec::new();
+            file.ignored_imports = SmallVec::new();
+        }
+    }
+
+    /// Returns the paths under `dir`, in order.
+    fn paths_under<'a>(&'a self, dir: &'a Utf8Path) -> impl Iterator<Item = &'a Utf8PathBuf> {
+        self.paths
+            .range::<Utf8Path, _>((Bound::Included(dir), Bound::Unbounded))
+            .take_while(move |path| path.starts_with(dir))
+    }
+
+    /// Returns the files at `paths`.
+    fn files_at<'a>(
+        &'a self,
+        paths: impl Iterator<Item = &'a Utf8PathBuf>,
+    ) -> impl Iterator<Item = &'a FileInfo> {
+        paths.filter_map(|path| self.files.get(path))
+    }
+}
+
+/// Paths of the cached files importing each model export.
+#[derive(Debug, Default)]
+struct ModelIndex {
+    /// Importing files by export name, in path order.
+    by_name: FxHashMap<String, BTreeSet<Utf8PathBuf>>,
+}
+
+impl ModelIndex {
+    /// Returns the export names `file` imports, ignored imports included.
+    fn names(file: &FileInfo) -> impl Iterator<Item = &String> {
+        file.imports
+            .iter()
+            .chain(&file.ignored_imports)
+            .flat_map(ImportInfo::used_names)
+    }
+
+    /// Indexes the imports of `file`.
+    fn add(&mut self, file: &FileInfo) {
+        for name in Self::names(file) {
+            if let Some(paths) = self.by_name.get_mut(name) {
+                paths.insert(file.path.clone());
+            } else {
+                self.by_name.insert(name.clone(), BTreeSet::from([file.path.clone()]));
+            }
+        }
+    }
+
+    /// Unindexes the imports of `file`.
+    fn remove(&mut self, file: &FileInfo) {
+        for name in Self::names(file) {
+            if let Some(paths) = self.by_name.get_mut(name) {
+                paths.remove(&file.path);
+                if paths.is_empty() {
+                    self.by_name.remove(name);
+                }
+            }
+        }
+    }
+
+    /// Returns the paths of the files importing `name`.
+    fn get(&self, name: &str) -> impl Iterator<Item = &Utf8PathBuf> {
+        self.by_name.get(name).into_iter().flatten()
+    }
+
+    /// Removes every entry.
+    fn clear(&mut self) {
+        self.by_name.clear();
+    }
+}
+
+/// A read-locked view of a [`ScanCache`], from [`ScanCache::view`].
+///
+/// Queries borrow the cached entries instead of cloning them and yield
+/// files in path order. Calling the cache's own methods on the same thread
+/// while a view is alive can deadlock (see [`ScanCache::view`]).
+#[derive(Debug)]
+pub struct CacheView<'a> {
+    /// Read guard of the cache entries.
+    entries: RwLockReadGuard<'a, Entries>,
+}
+
+impl CacheView<'_> {
+    /// Returns the number of cached files.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.files.len()
+    }
+
+    /// Returns `true` if the cache is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.files.is_empty()
+    }
+
+    /// Returns the cached file at `path`, if any.
+    #[must_use]
+    pub fn get(&self, path: &Utf8Path) -> Option<&FileInfo> {
+        self.entries.files.get(path)
+    }
+
+    /// Returns every cached file.
+    pub fn files(&self) -> impl Iterator<Item = &FileInfo> {
+        self.entries.files_at(self.entries.paths.iter())
+    }
+
+    /// Returns the files under `dir`.
+    ///
+    /// Only those files are visited, however many others are cached.
+    pub fn under<'q>(&'q self, dir: &'q Utf8Path) -> impl Iterator<Item = &'q FileInfo> {
+        self.entries.files_at(self.entries.paths_under(dir))
+    }
+
+    /// Returns the files importing the model export `name`, e.g.
+    /// `ActiveContractCodeGen`.
+    ///
+    /// Ignored imports count, so files whose status doesn't depend on the
+    /// export are included. Compacted files, whose imports were dropped,
+    /// aren't. The files are looked up in an index, so no other file is
+    /// visited.
+    pub fn importing<'q>(&'q self, name: &'q str) -> impl Iterator<Item = &'q FileInfo> {
+        self.entries.files_at(self.entries.importers.get(name))
+    }
+
+    /// Returns each imported model export with the number of files
+    /// importing it, in no particular order.
+    pub fn imported_models(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.entries
+            .importers
+            .by_name
+            .iter()
+            .map(|(name, paths)| (name.as_str(), paths.len()))
+    }
+
+    /// Returns the imports whose module path contains `fragment`, each with
+    /// its file.
+    ///
+    /// Ignored imports are included, after the file's other imports.
+    pub fn imports_matching<'q>(
+        &'q self,
+        fragment: &'q str,
+    ) -> impl Iterator<Item = (&'q FileInfo, &'q ImportInfo)> {
+        self.files().flat_map(move |file| {
+            file.imports
+                .iter()
+                .chain(&file.ignored_imports)
+                .filter(move |import| import.path.contains(fragment))
+                .map(move |import| (file, import))
+        })
     }
 }
 
@@ -430,6 +875,154 @@ mod tests {
         assert!(!cache.contains(&path));
     }
 
+    #[test]
+    fn test_cache_remove_prefix_and_stats() {
+        let cache = ScanCache::new();
+        cache.insert(make_file(1, "app/contracts/a.ts", MigrationStatus::Legacy));
+        cache.insert(make_file(2, "app/contracts/list/b.ts", MigrationStatus::Legacy));
+        cache.insert(make_file(3, "app/contracts-old/c.ts", MigrationStatus::Partial));
+        cache.insert(make_file(4, "app/jobs/d.ts", MigrationStatus::Migrated));
+
+        assert_eq!(cache.remove_prefix(Utf8Path::new("app/contracts")), 2);
+        let stats = cache.stats();
+        assert_eq!(stats.total, 2);
+        assert_eq!(stats.legacy, 0);
+        assert_eq!(stats.partial, 1, "sibling directories sharing a name prefix are kept");
+        assert_eq!(stats.migrated, 1);
+    }
+
+    #[test]
+    fn test_cache_compacts_least_recently_used() {
+        let cache = ScanCache::with_max_detailed_files(2);
+        let detailed = |id, path: &str| {
+            let mut file = make_file(id, path, MigrationStatus::Legacy);
+            file.imports.push(ImportInfo::new(
+                "../shared/models/foo",
+                ch_core::ImportKind::Named,
+                smallvec::smallvec!["Foo".to_owned()],
+                None,
+                ch_core::SourceLocation::default(),
+            ));
+            file
+        };
+        cache.insert(detailed(1, "a.ts"));
+        cache.insert(detailed(2, "b.ts"));
+        assert!(cache.get_by_path(Utf8Path::new("a.ts")).is_some());
+        cache.insert(detailed(3, "c.ts"));
+
+        // b.ts was used least recently
+        assert!(cache.is_compacted(Utf8Path::new("b.ts")));
+        assert!(!cache.is_compacted(Utf8Path::new("a.ts")));
+        assert!(!cache.is_compacted(Utf8Path::new("missing.ts")));
+        let compacted = cache.get_by_path(Utf8Path::new("b.ts"));
+        assert_eq!(compacted.as_ref().map(|f| f.imports.len()), Some(0));
+        assert_eq!(compacted.map(|f| f.status), Some(MigrationStatus::Legacy));
+        assert_eq!((cache.len(), cache.stats().legacy), (3, 3));
+
+        // Replacing a compacted file brings its details back
+        cache.insert(detailed(2, "b.ts"));
+        assert!(!cache.is_compacted(Utf8Path::new("b.ts")));
+        assert!(cache.is_compacted(Utf8Path::new("a.ts")));
+        assert!(cache.remove(&Utf8PathBuf::from("b.ts")).is_some());
+        cache.insert(detailed(4, "d.ts"));
+        assert!(!cache.is_compacted(Utf8Path::new("c.ts")));
+    }
+
+    #[test]
+    fn test_cache_view_queries() {
+        let cache = ScanCache::new();
+        let import = |path: &str, name: &str| {
+            ImportInfo::new(
+                path,
+                ch_core::ImportKind::Named,
+                smallvec::smallvec![name.to_owned()],
+                None,
+                ch_core::SourceLocation::default(),
+            )
+        };
+        let mut a = make_file(1, "app/contracts/list/a.ts", MigrationStatus::Legacy);
+        a.imports.push(import("../shared/models/contract", "ContractCodeGen"));
+        let mut b = make_file(2, "app/contracts/b.ts", MigrationStatus::Migrated);
+        b.ignored_imports.push(import("../shared/models/logger", "Logger"));
+        b.imports.push(import("../shared_2023/models/contract", "ContractCodeGen"));
+        cache.insert(b);
+        cache.insert(a);
+        cache.insert(make_file(3, "app/contracts-old/c.ts", MigrationStatus::NoModels));
+        cache.insert(make_file(4, "app/jobs/d.ts", MigrationStatus::NoModels));
+
+        let view = cache.view();
+        let paths = |files: Vec<&FileInfo>| -> Vec<String> {
+            files.into_iter().map(|f| f.path.to_string()).collect()
+        };
+        assert_eq!(
+            paths(view.under(Utf8Path::new("app/contracts")).collect()),
+            ["app/contracts/b.ts", "app/contracts/list/a.ts"]
+        );
+        assert_eq!(view.under(Utf8Path::new("app")).count(), 4);
+        assert_eq!(
+            paths(view.importing("ContractCodeGen").collect()),
+            ["app/contracts/b.ts", "app/contracts/list/a.ts"]
+        );
+        assert_eq!(paths(view.importing("Logger").collect()), ["app/contracts/b.ts"]);
+        let matching: Vec<_> = view
+            .imports_matching("shared/models")
+            .map(|(file, import)| (file.path.as_str(), import.path.as_str()))
+            .collect();
+        assert_eq!(
+            matching,
+            [
+                ("app/contracts/b.ts", "../shared/models/logger"),
+                ("app/contracts/list/a.ts", "../shared/models/contract"),
+            ]
+        );
+        assert_eq!(view.get(Utf8Path::new("app/jobs/d.ts")).map(|f| f.id), Some(FileId::new(4)));
+        drop(view);
+
+        assert_eq!(cache.remove_prefix(Utf8Path::new("app/contracts")), 2);
+        assert_eq!(paths(cache.view().files().collect()).len(), 2);
+    }
+
+    #[test]
+    fn test_cache_model_index_follows_changes() {
+        let cache = ScanCache::with_max_detailed_files(2);
+        let importing = |name: &str, names: &[&str]| {
+            let mut file = make_file(1, name, MigrationStatus::Legacy);
+            file.imports.push(ImportInfo::new(
+                "../shared/models/contract",
+                ch_core::ImportKind::Named,
+                names.iter().map(|name| (*name).to_owned()).collect(),
+                None,
+                ch_core::SourceLocation::default(),
+            ));
+            file
+        };
+        let importers = |cache: &ScanCache, name: &str| -> Vec<String> {
+            cache.view().importing(name).map(|f| f.path.to_string()).collect()
+        };
+        cache.insert(importing("b.ts", &["Contract", "Job"]));
+        cache.insert(importing("a.ts", &["Contract"]));
+        assert_eq!(importers(&cache, "Contract"), ["a.ts", "b.ts"]);
+        assert_eq!(importers(&cache, "Job"), ["b.ts"]);
+
+        // Replacing drops the names the new version no longer imports
+        cache.replace(importing("b.ts", &["Contract"]));
+        assert!(importers(&cache, "Job").is_empty());
+        assert_eq!(cache.view().imported_models().collect::<Vec<_>>(), [("Contract", 2)]);
+
+        cache.remove(&Utf8PathBuf::from("a.ts"));
+        assert_eq!(importers(&cache, "Contract"), ["b.ts"]);
+
+        // Compacted files lose their imports, so they leave the index
+        cache.insert(importing("c.ts", &["Job"]));
+        cache.insert(importing("d.ts", &["Job"]));
+        assert!(cache.is_compacted(Utf8Path::new("b.ts")));
+        assert!(importers(&cache, "Contract").is_empty());
+        assert_eq!(importers(&cache, "Job"), ["c.ts", "d.ts"]);
+
+        cache.clear();
+        assert_eq!(cache.view().imported_models().count(), 0);
+    }
+
     #[test]
     fn test_cache_clear() {
         let cache = ScanCache::new();
@@ -511,6 +1104,42 @@ mod tests {
         assert_eq!(paths.len(), 2);
     }
 
+    #[test]
+    fn test_cache_find_imports() {
+        use ch_core::{ImportKind, SourceLocation};
+
+        let import = |path: &str, line: u32| {
+            ImportInfo::new(
+                path,
+                ImportKind::Named,
+                smallvec::smallvec![],
+                None,
+                SourceLocation::new(line, 0, 0),
+            )
+        };
+
+        let mut a = make_file(1, "a.ts", MigrationStatus::Legacy);
+        a.imports.push(import("'../shared/models/contract-list'", 7));
+        a.imports.push(import("'@angular/core'", 1));
+        a.ignored_imports.push(import("'../shared/models/contract'", 2));
+        let mut b = make_file(2, "b.ts", MigrationStatus::Legacy);
+        b.imports.push(import("'../shared/models/job'", 1));
+
+        let cache = ScanCache::new();
+        cache.insert(b);
+        cache.insert(a);
+
+        let found: Vec<_> = cache
+            .find_imports("shared/models/contract")
+            .into_iter()
+            .map(|(path, import)| (path, import.location.line))
+            .collect();
+        assert_eq!(
+            found,
+            [(Utf8PathBuf::from("a.ts"), 2), (Utf8PathBuf::from("a.ts"), 7)]
+        );
+    }
+
     #[test]
     fn test_cache_replace() {
         let cache = ScanCache::new();
@@ -0,0 +1,160 @@
+//! Pausing and cancelling streaming scans.
+//!
+//! A [`ScanControl`] is handed to
+//! [`Scanner::scan_streaming_controlled`](crate::Scanner::scan_streaming_controlled)
+//! and kept by the caller, who can pause the scan while the user is busy and
+//! resume it later, or cancel it. Clones share their state, so the handle can
+//! move to another thread.
+//!
+//! Pausing holds the scan between chunks of [`PAUSE_CHUNK`] files, on the
+//! thread running the scan: files already handed to analysis threads finish
+//! first, and the analysis threads themselves are never blocked, so rescans
+//! started while paused still run.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::{Condvar, Mutex};
+use tokio_util::sync::CancellationToken;
+
+/// Number of files analyzed between checks for a pause.
+pub(crate) const PAUSE_CHUNK: usize = 512;
+
+/// How often a paused scan checks for cancellation through the token alone.
+const CANCEL_POLL: Duration = Duration::from_millis(50);
+
+/// Handle pausing, resuming and cancelling a streaming scan.
+///
+/// # Examples
+///
+/// ```
+/// use ch_scanner::ScanControl;
+///
+/// let control = ScanControl::new();
+/// control.pause();
+/// assert!(control.is_paused());
+///
+/// control.resume();
+/// assert!(!control.is_paused());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ScanControl {
+    /// Token stopping the scan for good.
+    cancel: CancellationToken,
+    /// Pause state shared by all clones.
+    pause: Arc<Pause>,
+}
+
+/// Pause flag with the condition variable paused scans wait on.
+#[derive(Debug, Default)]
+struct Pause {
+    /// Whether the scan is paused.
+    paused: Mutex<bool>,
+    /// Signalled on resume and on cancellation.
+    changed: Condvar,
+}
+
+impl ScanControl {
+    /// Creates a handle for a scan that is neither paused nor cancelled.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pauses the scan before its next chunk of files.
+    pub fn pause(&self) {
+        *self.pause.paused.lock() = true;
+    }
+
+    /// Resumes a paused scan.
+    pub fn resume(&self) {
+        *self.pause.paused.lock() = false;
+        self.pause.changed.notify_all();
+    }
+
+    /// Returns `true` if the scan is paused.
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        *self.pause.paused.lock()
+    }
+
+    /// Cancels the scan, waking it if paused.
+    pub fn cancel(&self) {
+        self.cancel.cancel();
+        self.pause.changed.notify_all();
+    }
+
+    /// Returns `true` if the scan has been cancelled.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// Returns the token cancelling the scan.
+    #[must_use]
+    pub const fn token(&self) -> &CancellationToken {
+        &self.cancel
+    }
+
+    /// Blocks while the scan is paused.
+    ///
+    /// Returns `false` if the scan was cancelled, paused or not.
+    pub(crate) fn wait_until_resumed(&self) -> bool {
+        let mut paused = self.pause.paused.lock();
+        while *paused && !self.cancel.is_cancelled() {
+            self.pause.changed.wait_for(&mut paused, CANCEL_POLL);
+        }
+        !self.cancel.is_cancelled()
+    }
+}
+
+impl From<CancellationToken> for ScanControl {
+    /// Creates a handle cancelled through `cancel` as well.
+    fn from(cancel: CancellationToken) -> Self {
+        Self {
+            cancel,
+            pause: Arc::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
+
+    #[test]
+    fn test_paused_scan_waits_for_resume() {
+        let control = ScanControl::new();
+        assert!(control.wait_until_resumed());
+
+        control.pause();
+        let resumed = AtomicBool::new(false);
+        thread::scope(|scope| {
+            let waiter = scope.spawn(|| {
+                let proceed = control.wait_until_resumed();
+                (proceed, resumed.load(Ordering::SeqCst))
+            });
+            thread::sleep(Duration::from_millis(20));
+            resumed.store(true, Ordering::SeqCst);
+            control.clone().resume();
+            assert_eq!(waiter.join().ok(), Some((true, true)));
+        });
+    }
+
+    #[test]
+    fn test_cancel_wakes_paused_scan() {
+        let token = CancellationToken::new();
+        let control = ScanControl::from(token.clone());
+        control.pause();
+        thread::scope(|scope| {
+            let waiter = scope.spawn(|| control.wait_until_resumed());
+            // Cancelling through the token alone is noticed too
+            token.cancel();
+            assert_eq!(waiter.join().ok(), Some(false));
+        });
+        assert!(control.is_paused());
+        assert!(control.is_cancelled());
+    }
+}
@@ -0,0 +1,164 @@
+//! Sharing one analysis among files with identical contents.
+//!
+//! Generated files are often copied verbatim across a tree. Their imports,
+//! model references and status depend only on their contents, so once an
+//! analysis pass has parsed two files with a given content hash, it hands
+//! the result to the other copies, changing only the path, id and owners.
+//! Only the path of the first file is kept for contents seen once, so the
+//! pass holds analyses just for contents that are actually copied. Each
+//! copy is recorded as a duplicate of the first file, see
+//! [`Scanner::duplicate_of`].
+//!
+//! `.ts` and `.tsx` files are parsed with different grammars, so identical
+//! contents under the two extensions are analyzed separately.
+//!
+//! [`Scanner::duplicate_of`]: crate::Scanner::duplicate_of
+
+use std::collections::hash_map::Entry;
+use std::sync::Arc;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use ch_core::{FileInfo, FxHashMap};
+use parking_lot::{Mutex, RwLock};
+
+/// Files found to be copies of another, shared by a scanner's clones.
+#[derive(Debug, Default)]
+pub(crate) struct Duplicates {
+    /// The file each duplicate reused the analysis of.
+    originals: RwLock<FxHashMap<Utf8PathBuf, Utf8PathBuf>>,
+}
+
+impl Duplicates {
+    /// Records `path` as a copy of `original`, or as parsed itself with
+    /// `None`.
+    pub(crate) fn record(&self, path: &Utf8Path, original: Option<&Utf8Path>) {
+        let mut originals = self.originals.write();
+        match original {
+            Some(original) => originals.insert(path.to_owned(), original.to_owned()),
+            None => originals.remove(path),
+        };
+    }
+
+    /// Returns the file whose analysis `path` reused, if any.
+    pub(crate) fn original_of(&self, path: &Utf8Path) -> Option<Utf8PathBuf> {
+        self.originals.read().get(path).cloned()
+    }
+
+    /// Returns every duplicate with its original.
+    pub(crate) fn all(&self) -> Vec<(Utf8PathBuf, Utf8PathBuf)> {
+        let originals = self.originals.read();
+        originals.iter().map(|(path, original)| (path.clone(), original.clone())).collect()
+    }
+
+    /// Forgets the duplicates not matching `keep`.
+    pub(crate) fn retain(&self, keep: impl Fn(&Utf8Path) -> bool) {
+        self.originals.write().retain(|path, _| keep(path));
+    }
+}
+
+/// What an analysis pass knows of some contents.
+#[derive(Debug)]
+enum Seen {
+    /// Parsed once, by the file at this path.
+    Once(Utf8PathBuf),
+    /// Parsed again; the analysis, with the path of the first file.
+    Copied(Box<FileInfo>),
+}
+
+/// Analyses by content, kept for one analysis pass.
+///
+/// A pass uses one registry and one set of ignore rules, so an analysis
+/// can't go stale while it's kept.
+#[derive(Debug)]
+pub(crate) struct ContentIndex {
+    /// Contents parsed so far, by hash and whether they were parsed as TSX.
+    seen: Mutex<FxHashMap<(u64, bool), Seen>>,
+    /// Where duplicates are recorded.
+    duplicates: Arc<Duplicates>,
+}
+
+impl ContentIndex {
+    /// Creates an empty index recording duplicates in `duplicates`.
+    pub(crate) fn new(duplicates: Arc<Duplicates>) -> Self {
+        Self {
+            seen: Mutex::default(),
+            duplicates,
+        }
+    }
+
+    /// Returns the analysis of earlier files with the same contents, once
+    /// two were parsed, recording `path` as a duplicate of the first.
+    pub(crate) fn reuse(&self, path: &Utf8Path, content_hash: u64, tsx: bool) -> Option<FileInfo> {
+        let analysis = match self.seen.lock().get(&(content_hash, tsx))? {
+            Seen::Once(_) => return None,
+            Seen::Copied(analysis) => FileInfo::clone(analysis),
+        };
+        self.duplicates.record(path, Some(&analysis.path));
+        Some(analysis)
+    }
+
+    /// Records a parsed file, keeping its analysis for later copies if an
+    /// earlier file had the same contents.
+    ///
+    /// Of files with the same contents analyzed at the same time, the first
+    /// recorded is the one the others are duplicates of.
+    pub(crate) fn insert(&self, file: &FileInfo, tsx: bool) {
+        let mut seen = self.seen.lock();
+        let original = match seen.entry((file.content_hash, tsx)) {
+            Entry::Vacant(entry) => {
+                entry.insert(Seen::Once(file.path.clone()));
+                None
+            }
+            Entry::Occupied(mut entry) => match entry.get() {
+                Seen::Once(original) => {
+                    let original = original.clone();
+                    let mut analysis = Box::new(file.clone());
+                    analysis.path.clone_from(&original);
+                    entry.insert(Seen::Copied(analysis));
+                    Some(original)
+                }
+                Seen::Copied(analysis) => Some(analysis.path.clone()),
+            },
+        };
+        drop(seen);
+        let original = original.filter(|original| *original != file.path);
+        self.duplicates.record(&file.path, original.as_deref());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ch_core::FileId;
+
+    use super::*;
+
+    fn file(path: &str, content_hash: u64) -> FileInfo {
+        let mut file = FileInfo::new(FileId::new(0), Utf8PathBuf::from(path));
+        file.content_hash = content_hash;
+        file
+    }
+
+    #[test]
+    fn test_copies_reuse_the_first_analysis() {
+        let duplicates = Arc::new(Duplicates::default());
+        let index = ContentIndex::new(Arc::clone(&duplicates));
+        // Contents seen once keep no analysis
+        index.insert(&file("a.ts", 1), false);
+        assert!(index.reuse(Utf8Path::new("b.ts"), 1, false).is_none());
+        index.insert(&file("b.ts", 1), false);
+        let original = duplicates.original_of(Utf8Path::new("b.ts"));
+        assert_eq!(original.as_deref(), Some(Utf8Path::new("a.ts")));
+
+        let reused = index.reuse(Utf8Path::new("c.ts"), 1, false);
+        assert_eq!(reused.map(|file| file.path), Some(Utf8PathBuf::from("a.ts")));
+        let original = duplicates.original_of(Utf8Path::new("c.ts"));
+        assert_eq!(original.as_deref(), Some(Utf8Path::new("a.ts")));
+        assert!(index.reuse(Utf8Path::new("c.tsx"), 1, true).is_none());
+        assert!(index.reuse(Utf8Path::new("d.ts"), 2, false).is_none());
+
+        // A file parsed itself with other contents is no longer a duplicate
+        index.insert(&file("c.ts", 3), false);
+        assert_eq!(duplicates.original_of(Utf8Path::new("c.ts")), None);
+        assert_eq!(duplicates.all().len(), 1);
+    }
+}
@@ -0,0 +1,184 @@
+//! Changes in migration status between two scans.
+//!
+//! [`diff_scans`] matches the files of a previous scan (usually loaded from
+//! an earlier report) with the current ones by their path relative to each
+//! scan's root, so reports of different checkouts can be compared. It lists
+//! the files that became migrated, the files that went backwards, and the
+//! new files that arrived with legacy imports.
+//!
+//! # Examples
+//!
+//! ```
+//! use camino::{Utf8Path, Utf8PathBuf};
+//! use ch_core::{FileId, FileInfo, MigrationStatus};
+//! use ch_scanner::diff_scans;
+//!
+//! let mut before = FileInfo::new(FileId::new(1), Utf8PathBuf::from("old/src/a.ts"));
+//! before.status = MigrationStatus::Legacy;
+//! let mut after = FileInfo::new(FileId::new(1), Utf8PathBuf::from("new/src/a.ts"));
+//! after.status = MigrationStatus::Migrated;
+//!
+//! let diff = diff_scans(Utf8Path::new("old"), &[before], Utf8Path::new("new"), &[after]);
+//! assert_eq!(diff.migrated[0].path, "new/src/a.ts");
+//! assert!(diff.regressed.is_empty());
+//! ```
+
+use camino::Utf8Path;
+use ch_core::{FileInfo, FxHashMap, MigrationStatus};
+use serde::{Deserialize, Serialize};
+
+use crate::StatusChange;
+
+/// What changed between two scans.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanDiff {
+    /// Files that needed migration before and are now migrated, sorted by path.
+    pub migrated: Vec<StatusChange>,
+
+    /// Files that moved away from migration, sorted by path: migrated or
+    /// model-free files that now import legacy models, and partial files
+    /// that went back to legacy.
+    pub regressed: Vec<StatusChange>,
+
+    /// Files missing from the previous scan that need migration, sorted by
+    /// path.
+    pub new_legacy: Vec<StatusChange>,
+
+    /// Number of files in the previous scan that no longer exist.
+    pub removed: usize,
+}
+
+impl ScanDiff {
+    /// Returns `true` if nothing worth reporting changed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.migrated.is_empty() && self.regressed.is_empty() && self.new_legacy.is_empty()
+    }
+}
+
+/// Compares the files of a previous scan with the current ones.
+///
+/// Files are matched by their path relative to `previous_root` and
+/// `current_root`; paths outside the root are compared as they are. Changes
+/// carry the current path and, in `remaining`, the file's current legacy
+/// import names. A new file's `from` status is its current status.
+#[must_use]
+pub fn diff_scans(
+    previous_root: &Utf8Path,
+    previous: &[FileInfo],
+    current_root: &Utf8Path,
+    current: &[FileInfo],
+) -> ScanDiff {
+    let before: FxHashMap<&Utf8Path, MigrationStatus> = previous
+        .iter()
+        .map(|file| (relative(&file.path, previous_root), file.status))
+        .collect();
+
+    let mut diff = ScanDiff::default();
+    let mut matched = 0;
+    for file in current {
+        let change = |from| StatusChange {
+            path: file.path.clone(),
+            from,
+            to: file.status,
+            remaining: legacy_names(file),
+        };
+        let Some(&from) = before.get(relative(&file.path, current_root)) else {
+            if file.status.needs_migration() {
+                diff.new_legacy.push(change(file.status));
+            }
+            continue;
+        };
+        matched += 1;
+        if from.needs_migration() && file.status.is_migrated() {
+            diff.migrated.push(change(from));
+        } else if is_regression(from, file.status) {
+            diff.regressed.push(change(from));
+        }
+    }
+    diff.removed = previous.len().saturating_sub(matched);
+
+    for changes in [&mut diff.migrated, &mut diff.regressed, &mut diff.new_legacy] {
+        changes.sort_by(|a, b| a.path.cmp(&b.path));
+    }
+    diff
+}
+
+/// Returns `path` relative to `root`, or `path` itself if it's outside.
+fn relative<'a>(path: &'a Utf8Path, root: &Utf8Path) -> &'a Utf8Path {
+    path.strip_prefix(root).unwrap_or(path)
+}
+
+/// Returns `true` if a file going from `from` to `to` moved away from
+/// migration.
+fn is_regression(from: MigrationStatus, to: MigrationStatus) -> bool {
+    match to {
+        MigrationStatus::Legacy => from != MigrationStatus::Legacy,
+        MigrationStatus::Partial => {
+            matches!(from, MigrationStatus::Migrated | MigrationStatus::NoModels)
+        }
+        _ => false,
+    }
+}
+
+/// Returns the file's legacy import names, sorted and deduplicated.
+fn legacy_names(file: &FileInfo) -> Vec<String> {
+    let mut names: Vec<String> = file
+        .legacy_imports()
+        .flat_map(|import| import.used_names().iter().cloned())
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use camino::Utf8PathBuf;
+    use ch_core::FileId;
+
+    fn file(path: &str, status: MigrationStatus) -> FileInfo {
+        let mut file = FileInfo::new(FileId::new(1), Utf8PathBuf::from(path));
+        file.status = status;
+        file
+    }
+
+    #[test]
+    fn test_diff_scans() {
+        let previous = vec![
+            file("a/src/done.ts", MigrationStatus::Partial),
+            file("a/src/back.ts", MigrationStatus::Migrated),
+            file("a/src/worse.ts", MigrationStatus::Partial),
+            file("a/src/started.ts", MigrationStatus::Legacy),
+            file("a/src/gone.ts", MigrationStatus::Legacy),
+        ];
+        let current = vec![
+            file("b/src/done.ts", MigrationStatus::Migrated),
+            file("b/src/back.ts", MigrationStatus::Partial),
+            file("b/src/worse.ts", MigrationStatus::Legacy),
+            file("b/src/started.ts", MigrationStatus::Partial),
+            file("b/src/new.ts", MigrationStatus::Legacy),
+            file("b/src/clean.ts", MigrationStatus::NoModels),
+        ];
+
+        let diff = diff_scans(Utf8Path::new("a"), &previous, Utf8Path::new("b"), &current);
+        let paths = |changes: &[StatusChange]| {
+            changes.iter().map(|change| change.path.to_string()).collect::<Vec<_>>()
+        };
+        assert_eq!(paths(&diff.migrated), ["b/src/done.ts"]);
+        assert_eq!(paths(&diff.regressed), ["b/src/back.ts", "b/src/worse.ts"]);
+        assert_eq!(paths(&diff.new_legacy), ["b/src/new.ts"]);
+        assert_eq!(diff.migrated[0].from, MigrationStatus::Partial);
+        assert_eq!(diff.removed, 1);
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn test_diff_scans_unchanged() {
+        let files = [file("src/a.ts", MigrationStatus::Legacy)];
+        let diff = diff_scans(Utf8Path::new(""), &files, Utf8Path::new(""), &files);
+        assert!(diff.is_empty());
+        assert_eq!(diff.removed, 0);
+    }
+}
@@ -6,6 +6,7 @@
 use std::sync::Arc;
 
 use camino::Utf8PathBuf;
+use serde::{Deserialize, Serialize};
 
 /// Errors that can occur during scanning operations.
 ///
@@ -21,9 +22,13 @@ use camino::Utf8PathBuf;
 /// # Error Recovery Strategy
 ///
 /// - **Walker errors** ([`ScanError::Walk`]): Fatal - propagate immediately
-/// - **File read errors** ([`ScanError::Read`]): Log warning, skip file, continue scan
+/// - **File read errors** ([`ScanError::Read`]): Log warning, skip file, continue scan;
+///   [transient](ScanError::is_transient) ones are retried once the other files are done
 /// - **Parse errors** ([`ScanError::Parse`]): Log warning, skip file, continue scan
+/// - **Internal errors** ([`ScanError::Internal`]): Analysis panicked; skip file, continue scan
+/// - **Skipped files** ([`ScanError::Skipped`]): Too large or binary; skip file, continue scan
 /// - **Registry errors** ([`ScanError::Registry`]): Fatal - cannot proceed without registry
+/// - **Cancellation** ([`ScanError::Cancelled`]): The caller stopped the scan
 ///
 /// # Examples
 ///
@@ -39,6 +44,9 @@ use camino::Utf8PathBuf;
 ///         ScanError::Config(msg) => eprintln!("Config error: {msg}"),
 ///         ScanError::NonUtf8Path(p) => eprintln!("Invalid path: {}", p.display()),
 ///         ScanError::Registry(msg) => eprintln!("Registry error: {msg}"),
+///         ScanError::Internal { path, .. } => eprintln!("Internal error: {path}"),
+///         ScanError::Skipped { path, reason } => eprintln!("Skipped {path}: {reason}"),
+///         ScanError::Cancelled => eprintln!("Scan cancelled"),
 ///     }
 /// }
 /// ```
@@ -99,6 +107,76 @@ pub enum ScanError {
     /// from working correctly.
     #[error("model registry error: {0}")]
     Registry(String),
+
+    /// Analysis of a file panicked.
+    ///
+    /// The panic is caught per file so that one bad file cannot abort the
+    /// whole scan. Scanning can continue by skipping this file.
+    #[error("internal error while analyzing {path}: {message}")]
+    Internal {
+        /// The path of the file being analyzed when the panic occurred.
+        path: Utf8PathBuf,
+        /// The panic message.
+        message: String,
+    },
+
+    /// A file was left out without being parsed.
+    ///
+    /// Files over the configured size limit, or whose contents look binary,
+    /// are skipped before parsing. Scanning continues with the next file.
+    #[error("skipped {path}: {reason}")]
+    Skipped {
+        /// The path of the skipped file.
+        path: Utf8PathBuf,
+        /// Why the file was skipped.
+        reason: String,
+    },
+
+    /// The scan was cancelled through its cancellation token.
+    ///
+    /// Files analyzed before cancelling stay in the scanner's cache.
+    #[error("scan cancelled")]
+    Cancelled,
+}
+
+/// Broad category of a [`ScanError`], as recorded in reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    /// The file couldn't be read.
+    Read,
+    /// Reading the file failed in a way that may pass, e.g. a network file
+    /// system timing out, and kept failing when retried.
+    Transient,
+    /// The file couldn't be read for lack of permission.
+    Permission,
+    /// The file's contents or a path aren't valid UTF-8.
+    Encoding,
+    /// The file couldn't be parsed.
+    Parse,
+    /// Analysis of the file panicked.
+    Internal,
+    /// The file was too large or binary and wasn't parsed.
+    Skipped,
+    /// Walking, configuration or registry failure.
+    Other,
+}
+
+impl ErrorCategory {
+    /// Returns the category's name, as serialized.
+    #[must_use]
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Transient => "transient",
+            Self::Permission => "permission",
+            Self::Encoding => "encoding",
+            Self::Parse => "parse",
+            Self::Internal => "internal",
+            Self::Skipped => "skipped",
+            Self::Other => "other",
+        }
+    }
 }
 
 impl From<ignore::Error> for ScanError {
@@ -140,6 +218,24 @@ impl ScanError {
         Self::Registry(message.into())
     }
 
+    /// Creates a new [`ScanError::Internal`] error.
+    #[inline]
+    pub fn internal(path: impl Into<Utf8PathBuf>, message: impl Into<String>) -> Self {
+        Self::Internal {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Creates a new [`ScanError::Skipped`] error.
+    #[inline]
+    pub fn skipped(path: impl Into<Utf8PathBuf>, reason: impl Into<String>) -> Self {
+        Self::Skipped {
+            path: path.into(),
+            reason: reason.into(),
+        }
+    }
+
     /// Returns `true` if this error is recoverable (scanning can continue).
     ///
     /// Recoverable errors are file-specific issues that don't prevent
@@ -147,7 +243,13 @@ impl ScanError {
     #[inline]
     #[must_use]
     pub const fn is_recoverable(&self) -> bool {
-        matches!(self, Self::Read { .. } | Self::Parse { .. })
+        matches!(
+            self,
+            Self::Read { .. }
+                | Self::Parse { .. }
+                | Self::Internal { .. }
+                | Self::Skipped { .. }
+        )
     }
 
     /// Returns `true` if this error is fatal (scanning should stop).
@@ -161,8 +263,78 @@ impl ScanError {
     #[must_use]
     pub fn path(&self) -> Option<&Utf8PathBuf> {
         match self {
-            Self::Read { path, .. } | Self::Parse { path, .. } => Some(path),
-            Self::Walk { .. } | Self::Config(_) | Self::NonUtf8Path(_) | Self::Registry(_) => None,
+            Self::Read { path, .. }
+            | Self::Parse { path, .. }
+            | Self::Internal { path, .. }
+            | Self::Skipped { path, .. } => Some(path),
+            Self::Walk { .. }
+            | Self::Config(_)
+            | Self::NonUtf8Path(_)
+            | Self::Registry(_)
+            | Self::Cancelled => None,
+        }
+    }
+
+    /// Returns `true` if this error may not happen again, such as a read
+    /// timing out on a network file system.
+    ///
+    /// Scans retry files failing with transient errors according to their
+    /// [`RetryPolicy`](crate::RetryPolicy).
+    #[must_use]
+    pub fn is_transient(&self) -> bool {
+        self.category() == ErrorCategory::Transient
+    }
+
+    /// Returns the category of this error.
+    ///
+    /// Read errors are split by their I/O error kind: files that aren't
+    /// valid UTF-8 fail to read with
+    /// [`InvalidData`](std::io::ErrorKind::InvalidData) and count as
+    /// encoding errors, time-outs, interruptions and failures of network
+    /// file systems count as transient, and denied access as a permission
+    /// error.
+    #[must_use]
+    pub fn category(&self) -> ErrorCategory {
+        use std::io::ErrorKind;
+
+        match self {
+            Self::Read { error, .. } => match error.kind() {
+                ErrorKind::InvalidData => ErrorCategory::Encoding,
+                ErrorKind::PermissionDenied => ErrorCategory::Permission,
+                ErrorKind::TimedOut
+                | ErrorKind::Interrupted
+                | ErrorKind::WouldBlock
+                | ErrorKind::ResourceBusy
+                | ErrorKind::StaleNetworkFileHandle
+                | ErrorKind::ConnectionReset
+                | ErrorKind::ConnectionAborted
+                | ErrorKind::NotConnected
+                | ErrorKind::BrokenPipe
+                | ErrorKind::NetworkDown
+                | ErrorKind::NetworkUnreachable
+                | ErrorKind::HostUnreachable => ErrorCategory::Transient,
+                _ => ErrorCategory::Read,
+            },
+            Self::NonUtf8Path(_) => ErrorCategory::Encoding,
+            Self::Parse { .. } => ErrorCategory::Parse,
+            Self::Internal { .. } => ErrorCategory::Internal,
+            Self::Skipped { .. } => ErrorCategory::Skipped,
+            Self::Walk { .. } | Self::Config(_) | Self::Registry(_) | Self::Cancelled => {
+                ErrorCategory::Other
+            }
+        }
+    }
+
+    /// Returns the error message without the file path, for reports that
+    /// list the path separately.
+    #[must_use]
+    pub fn message(&self) -> String {
+        match self {
+            Self::Read { error, .. } => error.to_string(),
+            Self::Parse { error, .. } => error.to_string(),
+            Self::Internal { message, .. } => message.clone(),
+            Self::Skipped { reason, .. } => reason.clone(),
+            _ => self.to_string(),
         }
     }
 }
@@ -217,6 +389,54 @@ mod tests {
         assert!(err.path().is_none());
     }
 
+    #[test]
+    fn test_scan_error_internal() {
+        let err = ScanError::internal("src/baz.ts", "index out of bounds");
+        assert!(err.is_recoverable());
+        assert!(!err.is_fatal());
+        assert_eq!(err.path().map(|p| p.as_str()), Some("src/baz.ts"));
+        assert_eq!(
+            err.to_string(),
+            "internal error while analyzing src/baz.ts: index out of bounds"
+        );
+    }
+
+    #[test]
+    fn test_scan_error_category() {
+        let err = ScanError::read("src/foo.ts", io::Error::new(io::ErrorKind::NotFound, "not found"));
+        assert_eq!(err.category(), ErrorCategory::Read);
+        assert_eq!(err.message(), "not found");
+
+        let err = ScanError::read("src/foo.ts", io::Error::new(io::ErrorKind::InvalidData, "bad"));
+        assert_eq!(err.category(), ErrorCategory::Encoding);
+        assert!(!err.is_transient());
+
+        let err = ScanError::read("src/foo.ts", io::Error::from(io::ErrorKind::PermissionDenied));
+        assert_eq!(err.category(), ErrorCategory::Permission);
+        assert!(!err.is_transient());
+
+        for kind in [io::ErrorKind::TimedOut, io::ErrorKind::StaleNetworkFileHandle] {
+            let err = ScanError::read("src/foo.ts", io::Error::from(kind));
+            assert_eq!(err.category().key(), "transient");
+            assert!(err.is_transient());
+        }
+
+        let err = ScanError::parse("src/bar.ts", ch_ts_parser::ParseError::Parse);
+        assert_eq!(err.category(), ErrorCategory::Parse);
+        assert_eq!(err.message(), "failed to parse source code");
+
+        let err = ScanError::internal("src/baz.ts", "index out of bounds");
+        assert_eq!(err.category().key(), "internal");
+        assert_eq!(err.message(), "index out of bounds");
+
+        let err = ScanError::skipped("src/big.ts", "binary content");
+        assert!(err.is_recoverable());
+        assert_eq!(err.category(), ErrorCategory::Skipped);
+        assert_eq!(err.message(), "binary content");
+        assert_eq!(err.to_string(), "skipped src/big.ts: binary content");
+        assert_eq!(ScanError::config("bad").category(), ErrorCategory::Other);
+    }
+
     #[test]
     fn test_scan_error_display() {
         let err = ScanError::Config("test error".to_owned());
@@ -0,0 +1,472 @@
+//! Scan history recorded as JSON Lines.
+//!
+//! Each scan can be appended to a history file as one [`HistoryEntry`] per
+//! line, recording when it ran, which commit was scanned, and the resulting
+//! [`StatsSnapshot`]. The file can then be loaded to show progress over time,
+//! and compacted with [`compact_history`] to keep it from growing forever.
+//! [`Trend`] turns loaded entries into progress-over-time points with the
+//! migration velocity between them.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use camino::Utf8Path;
+//! use ch_scanner::{append_history, load_history, HistoryEntry, StatsSnapshot};
+//!
+//! let path = Utf8Path::new(".ch-migrate/history.jsonl");
+//! let entry = HistoryEntry::new(StatsSnapshot::default(), Some("a1b2c3d".to_owned()));
+//! append_history(path, &entry)?;
+//!
+//! for entry in load_history(path)? {
+//!     println!("{}: {:.1}%", entry.recorded_at, entry.stats.progress_percent());
+//! }
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use camino::Utf8Path;
+use serde::{Deserialize, Serialize};
+
+use crate::stats::StatsSnapshot;
+
+/// A single recorded scan.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// When the scan was recorded (Unix timestamp in seconds).
+    pub recorded_at: u64,
+
+    /// Commit that was scanned, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+
+    /// Statistics from the scan.
+    pub stats: StatsSnapshot,
+}
+
+impl HistoryEntry {
+    /// Creates an entry for the given statistics, recorded now.
+    #[must_use]
+    pub fn new(stats: StatsSnapshot, commit: Option<String>) -> Self {
+        Self {
+            recorded_at: unix_now(),
+            commit,
+            stats,
+        }
+    }
+}
+
+/// Seconds in a day, for per-day velocities.
+const SECS_PER_DAY: f64 = 86_400.0;
+
+/// One recorded scan in a [`Trend`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TrendPoint {
+    /// When the scan was recorded (Unix timestamp in seconds).
+    pub recorded_at: u64,
+
+    /// Commit that was scanned, if known.
+    pub commit: Option<String>,
+
+    /// Statistics from the scan.
+    pub stats: StatsSnapshot,
+
+    /// Change in progress since the previous point, in percentage points.
+    pub change: Option<f64>,
+
+    /// Net files migrated per day since the previous point.
+    ///
+    /// `None` for the first point and for points recorded at the same second
+    /// as the previous one.
+    pub migrated_per_day: Option<f64>,
+}
+
+impl TrendPoint {
+    /// Returns the migration progress percentage at this point.
+    #[must_use]
+    pub fn progress_percent(&self) -> f64 {
+        self.stats.progress_percent()
+    }
+}
+
+/// Migration progress over time, built from history entries.
+///
+/// # Examples
+///
+/// ```
+/// use ch_scanner::{HistoryEntry, StatsSnapshot, Trend};
+///
+/// let entry = |recorded_at, migrated| HistoryEntry {
+///     recorded_at,
+///     commit: None,
+///     stats: StatsSnapshot {
+///         total: 10,
+///         legacy: 10 - migrated,
+///         migrated,
+///         ..StatsSnapshot::default()
+///     },
+/// };
+/// let trend = Trend::from_entries(&[entry(0, 2), entry(2 * 86_400, 6)]);
+///
+/// assert_eq!(trend.points[1].migrated_per_day, Some(2.0));
+/// assert_eq!(trend.migrated_per_day(), Some(2.0));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Trend {
+    /// Recorded scans, oldest first.
+    pub points: Vec<TrendPoint>,
+}
+
+impl Trend {
+    /// Builds a trend from history entries, ordering them by recording time.
+    #[must_use]
+    pub fn from_entries(entries: &[HistoryEntry]) -> Self {
+        let mut entries: Vec<&HistoryEntry> = entries.iter().collect();
+        entries.sort_by_key(|entry| entry.recorded_at);
+
+        let mut points: Vec<TrendPoint> = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let (change, migrated_per_day) = points.last().map_or((None, None), |previous| {
+                (
+                    Some(entry.stats.progress_percent() - previous.progress_percent()),
+                    migrated_per_day(&previous.stats, previous.recorded_at, entry),
+                )
+            });
+            points.push(TrendPoint {
+                recorded_at: entry.recorded_at,
+                commit: entry.commit.clone(),
+                stats: entry.stats,
+                change,
+                migrated_per_day,
+            });
+        }
+        Self { points }
+    }
+
+    /// Returns `true` if there are no recorded scans.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns the net files migrated per day between the first and last
+    /// points, if they were recorded at different times.
+    #[must_use]
+    pub fn migrated_per_day(&self) -> Option<f64> {
+        let first = self.points.first()?;
+        let last = self.points.last()?;
+        let entry = HistoryEntry {
+            recorded_at: last.recorded_at,
+            commit: None,
+            stats: last.stats,
+        };
+        migrated_per_day(&first.stats, first.recorded_at, &entry)
+    }
+}
+
+/// Returns the net files migrated per day from `stats` at `since` to `entry`.
+#[allow(clippy::cast_precision_loss)] // Acceptable for statistics display
+fn migrated_per_day(stats: &StatsSnapshot, since: u64, entry: &HistoryEntry) -> Option<f64> {
+    let elapsed = entry.recorded_at.checked_sub(since).filter(|&secs| secs > 0)?;
+    let migrated = entry.stats.migrated as f64 - stats.migrated as f64;
+    Some(migrated / (elapsed as f64 / SECS_PER_DAY))
+}
+
+/// Which entries a history file keeps when it is compacted.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use ch_scanner::HistoryRetention;
+///
+/// let retention = HistoryRetention::default()
+///     .with_max_entries(1000)
+///     .with_max_age(Duration::from_secs(365 * 24 * 60 * 60));
+/// assert!(!retention.is_empty());
+/// assert!(HistoryRetention::default().is_empty());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HistoryRetention {
+    /// Maximum number of entries to keep; the newest are kept.
+    pub max_entries: Option<usize>,
+
+    /// Maximum age of an entry.
+    pub max_age: Option<Duration>,
+}
+
+impl HistoryRetention {
+    /// Returns `true` if no limit is set, so every entry is kept.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.max_entries.is_none() && self.max_age.is_none()
+    }
+
+    /// Sets the maximum number of entries.
+    #[must_use]
+    pub const fn with_max_entries(mut self, entries: usize) -> Self {
+        self.max_entries = Some(entries);
+        self
+    }
+
+    /// Sets the maximum entry age.
+    #[must_use]
+    pub const fn with_max_age(mut self, age: Duration) -> Self {
+        self.max_age = Some(age);
+        self
+    }
+
+    /// Drops the entries this policy doesn't keep, as of `now` (Unix
+    /// timestamp in seconds). Entries are assumed to be in recording order.
+    pub fn apply(&self, entries: &mut Vec<HistoryEntry>, now: u64) {
+        if let Some(max_age) = self.max_age {
+            let cutoff = now.saturating_sub(max_age.as_secs());
+            entries.retain(|entry| entry.recorded_at >= cutoff);
+        }
+        if let Some(max_entries) = self.max_entries {
+            let excess = entries.len().saturating_sub(max_entries);
+            entries.drain(..excess);
+        }
+    }
+}
+
+impl From<&ch_core::HistoryConfig> for HistoryRetention {
+    /// Converts the configured `max_entries` and `max_age_days`.
+    fn from(history: &ch_core::HistoryConfig) -> Self {
+        const DAY_SECS: u64 = 24 * 60 * 60;
+
+        Self {
+            max_entries: history.max_entries,
+            max_age: history
+                .max_age_days
+                .map(|days| Duration::from_secs(days.saturating_mul(DAY_SECS))),
+        }
+    }
+}
+
+/// Outcome of [`compact_history`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HistoryCompaction {
+    /// Entries left in the file.
+    pub kept: usize,
+
+    /// Entries dropped from the file.
+    pub removed: usize,
+}
+
+/// Appends an entry to a JSON Lines history file.
+///
+/// The file and its parent directories are created if they don't exist.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be created or written.
+pub fn append_history(path: &Utf8Path, entry: &HistoryEntry) -> io::Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(line.as_bytes())
+}
+
+/// Drops the entries of a history file that `retention` doesn't keep.
+///
+/// The file is only rewritten if entries were dropped, via a temporary file
+/// next to it so an interrupted compaction leaves the old file intact. A
+/// missing file counts as empty.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or is malformed (see
+/// [`load_history`]), or cannot be rewritten.
+pub fn compact_history(
+    path: &Utf8Path,
+    retention: &HistoryRetention,
+) -> io::Result<HistoryCompaction> {
+    let mut entries = match load_history(path) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(e),
+    };
+    let total = entries.len();
+    retention.apply(&mut entries, unix_now());
+
+    let compaction = HistoryCompaction {
+        kept: entries.len(),
+        removed: total - entries.len(),
+    };
+    if compaction.removed == 0 {
+        return Ok(compaction);
+    }
+
+    let mut contents = String::new();
+    for entry in &entries {
+        contents.push_str(&serde_json::to_string(entry)?);
+        contents.push('\n');
+    }
+    let temp = path.with_extension("jsonl.tmp");
+    fs::write(&temp, contents)?;
+    fs::rename(&temp, path)?;
+    Ok(compaction)
+}
+
+/// Returns the current time as a Unix timestamp in seconds.
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Loads all entries from a JSON Lines history file, in file order.
+///
+/// Blank lines are skipped.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, or an
+/// [`io::ErrorKind::InvalidData`] error naming the line that is malformed.
+pub fn load_history(path: &Utf8Path) -> io::Result<Vec<HistoryEntry>> {
+    let contents = fs::read_to_string(path)?;
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{path}:{}: {e}", index + 1),
+                )
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use camino::Utf8PathBuf;
+    use tempfile::TempDir;
+
+    fn temp_history() -> (TempDir, Utf8PathBuf) {
+        let (dir, root) = crate::tests::temp_root();
+        (dir, root.join("nested/history.jsonl"))
+    }
+
+    #[test]
+    fn test_history_round_trip() {
+        let (_dir, path) = temp_history();
+        let first = HistoryEntry {
+            recorded_at: 1,
+            commit: Some("abc1234".to_owned()),
+            stats: StatsSnapshot {
+                total: 10,
+                legacy: 4,
+                ..StatsSnapshot::default()
+            },
+        };
+        let second = HistoryEntry {
+            recorded_at: 2,
+            commit: None,
+            stats: StatsSnapshot {
+                total: 10,
+                migrated: 6,
+                ..StatsSnapshot::default()
+            },
+        };
+
+        assert!(append_history(&path, &first).is_ok());
+        assert!(append_history(&path, &second).is_ok());
+        assert_eq!(load_history(&path).ok(), Some(vec![first, second]));
+    }
+
+    fn entry_at(recorded_at: u64) -> HistoryEntry {
+        HistoryEntry {
+            recorded_at,
+            commit: None,
+            stats: StatsSnapshot::default(),
+        }
+    }
+
+    #[test]
+    fn test_retention_apply() {
+        let recorded = |entries: &[HistoryEntry]| -> Vec<u64> {
+            entries.iter().map(|entry| entry.recorded_at).collect()
+        };
+        let mut entries: Vec<_> = (1..=5).map(|day| entry_at(day * 100)).collect();
+
+        HistoryRetention::default().apply(&mut entries, 1000);
+        assert_eq!(recorded(&entries), [100, 200, 300, 400, 500]);
+
+        HistoryRetention::default()
+            .with_max_age(Duration::from_secs(750))
+            .apply(&mut entries, 1000);
+        assert_eq!(recorded(&entries), [300, 400, 500]);
+
+        HistoryRetention::default().with_max_entries(2).apply(&mut entries, 1000);
+        assert_eq!(recorded(&entries), [400, 500]);
+    }
+
+    #[test]
+    fn test_trend_from_entries() {
+        let entry = |recorded_at, migrated| HistoryEntry {
+            recorded_at,
+            commit: None,
+            stats: StatsSnapshot {
+                total: 10,
+                legacy: 10 - migrated,
+                migrated,
+                ..StatsSnapshot::default()
+            },
+        };
+        let day = 86_400;
+        let trend = Trend::from_entries(&[entry(4 * day, 3), entry(0, 1), entry(4 * day, 5)]);
+
+        let recorded: Vec<u64> = trend.points.iter().map(|point| point.recorded_at).collect();
+        assert_eq!(recorded, [0, 4 * day, 4 * day]);
+        assert_eq!(trend.points[0].change, None);
+        assert!(trend.points[1].change.is_some_and(|change| (change - 20.0).abs() < 1e-9));
+        assert_eq!(trend.points[1].migrated_per_day, Some(0.5));
+        // Recorded at the same time as the previous scan
+        assert_eq!(trend.points[2].migrated_per_day, None);
+        assert_eq!(trend.migrated_per_day(), Some(1.0));
+        assert_eq!(Trend::from_entries(&[entry(0, 1)]).migrated_per_day(), None);
+    }
+
+    #[test]
+    fn test_compact_history() {
+        let (_dir, path) = temp_history();
+        let retention = HistoryRetention::default().with_max_entries(2);
+        assert_eq!(compact_history(&path, &retention).ok(), Some(HistoryCompaction::default()));
+
+        for recorded_at in 1..=3 {
+            assert!(append_history(&path, &entry_at(recorded_at)).is_ok());
+        }
+        let compaction = compact_history(&path, &retention).ok();
+        assert_eq!(compaction, Some(HistoryCompaction { kept: 2, removed: 1 }));
+        assert_eq!(load_history(&path).ok(), Some(vec![entry_at(2), entry_at(3)]));
+        assert!(!path.with_extension("jsonl.tmp").exists());
+    }
+
+    #[test]
+    fn test_history_reports_malformed_line() {
+        let (_dir, path) = temp_history();
+        let entry = HistoryEntry::new(StatsSnapshot::default(), None);
+        assert!(append_history(&path, &entry).is_ok());
+        let mut file = OpenOptions::new().append(true).open(&path).ok();
+        if let Some(file) = file.as_mut() {
+            let _ = writeln!(file, "\n{{not json");
+        }
+
+        let err = load_history(&path).err();
+        assert_eq!(err.as_ref().map(io::Error::kind), Some(io::ErrorKind::InvalidData));
+        assert!(err.is_some_and(|e| e.to_string().contains(":3:")));
+    }
+}
@@ -0,0 +1,321 @@
+//! File ids that stay the same across renames and runs.
+//!
+//! A file first seen gets the hash of its path as its [`FileId`], unless
+//! another file already holds that id. From then on the id follows the
+//! file: a rename found by [`Scanner::rescan_files`] moves it to the new
+//! path, and a scanner with a cache directory saves the table there after
+//! each full scan, so later runs hand out the same ids. Annotations and
+//! scan history can then be keyed by id rather than by path.
+//!
+//! [`Scanner::rescan_files`]: crate::Scanner::rescan_files
+
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use ch_core::{FileId, FxHashMap};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::analyzer::hash_path;
+use crate::persist::{roots_file, write_atomically};
+
+/// Version of the id file layout; older files are discarded.
+const FORMAT_VERSION: u32 = 1;
+
+/// Most deleted and created files kept waiting for the other half of a
+/// rename; the ids of older deleted files are forgotten.
+const RENAME_WINDOW: usize = 64;
+
+/// Contents of an id file.
+#[derive(Debug, Serialize, Deserialize)]
+struct IdContents {
+    version: u32,
+    ids: Vec<(Utf8PathBuf, FileId)>,
+}
+
+/// Ids by path and paths by id.
+#[derive(Debug, Default)]
+struct Table {
+    by_path: FxHashMap<Utf8PathBuf, FileId>,
+    by_id: FxHashMap<FileId, Utf8PathBuf>,
+}
+
+impl Table {
+    /// Records `id` for `path`.
+    fn insert(&mut self, path: Utf8PathBuf, id: FileId) {
+        self.by_id.insert(id, path.clone());
+        self.by_path.insert(path, id);
+    }
+
+    /// Forgets the id of `path`, returning it.
+    fn remove(&mut self, path: &Utf8Path) -> Option<FileId> {
+        let id = self.by_path.remove(path)?;
+        self.by_id.remove(&id);
+        Some(id)
+    }
+}
+
+/// A deleted or created file that may be half of a rename.
+#[derive(Debug)]
+struct Unmatched {
+    content_hash: u64,
+    path: Utf8PathBuf,
+    deleted: bool,
+}
+
+/// Table of the ids handed out to files, shared by analysis threads.
+#[derive(Debug, Default)]
+pub(crate) struct FileIds {
+    /// Id file for the scan roots, or `None` for a table only in memory.
+    path: Option<Utf8PathBuf>,
+    /// Ids handed out so far.
+    table: RwLock<Table>,
+    /// Recently deleted and created files not yet paired, oldest first.
+    unmatched: Mutex<VecDeque<Unmatched>>,
+}
+
+impl FileIds {
+    /// Loads the ids of files under `roots` saved in `dir`.
+    ///
+    /// A missing or unreadable file yields an empty table.
+    pub(crate) fn load<'a>(dir: &Utf8Path, roots: impl IntoIterator<Item = &'a Utf8Path>) -> Self {
+        let path = roots_file(dir, roots, "ids");
+        let mut table = Table::default();
+        match fs::read(&path) {
+            Ok(bytes) => match rmp_serde::from_slice::<IdContents>(&bytes) {
+                Ok(contents) if contents.version == FORMAT_VERSION => {
+                    for (file, id) in contents.ids {
+                        table.insert(file, id);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => warn!(path = %path, error = %e, "Ignoring unreadable file ids"),
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => warn!(path = %path, error = %e, "Ignoring unreadable file ids"),
+        }
+
+        Self {
+            path: Some(path),
+            table: RwLock::new(table),
+            unmatched: Mutex::default(),
+        }
+    }
+
+    /// Returns `true` if the table is saved to a file.
+    pub(crate) const fn is_persistent(&self) -> bool {
+        self.path.is_some()
+    }
+
+    /// Returns the id of `path`, if it has one.
+    pub(crate) fn get(&self, path: &Utf8Path) -> Option<FileId> {
+        self.table.read().by_path.get(path).copied()
+    }
+
+    /// Returns the path holding `id`, if any.
+    pub(crate) fn path_of(&self, id: FileId) -> Option<Utf8PathBuf> {
+        self.table.read().by_id.get(&id).cloned()
+    }
+
+    /// Returns the id of `path`, handing out a new one if it has none.
+    ///
+    /// A new id is the hash of the path, or the next free value after it if
+    /// another file holds that one.
+    pub(crate) fn assign(&self, path: &Utf8Path) -> FileId {
+        if let Some(id) = self.get(path) {
+            return id;
+        }
+
+        let mut table = self.table.write();
+        if let Some(id) = table.by_path.get(path) {
+            return *id;
+        }
+        let mut id = hash_path(path);
+        while table.by_id.contains_key(&FileId::new(id)) {
+            id = id.wrapping_add(1);
+        }
+        table.insert(path.to_owned(), FileId::new(id));
+        FileId::new(id)
+    }
+
+    /// Moves the id of `from` to `to`, returning it.
+    ///
+    /// Any id `to` held before is forgotten. Returns `None`, changing
+    /// nothing, if `from` has no id.
+    pub(crate) fn rename(&self, from: &Utf8Path, to: &Utf8Path) -> Option<FileId> {
+        let mut table = self.table.write();
+        let id = table.remove(from)?;
+        table.remove(to);
+        table.insert(to.to_owned(), id);
+        Some(id)
+    }
+
+    /// Forgets the id of `path`.
+    pub(crate) fn remove(&self, path: &Utf8Path) {
+        self.table.write().remove(path);
+    }
+
+    /// Records the deletion of the file at `path`, whose contents hashed to
+    /// `content_hash`.
+    ///
+    /// If a file with the same contents was created recently, the two make
+    /// a rename: the id moves to the created file, which is returned with
+    /// it. Otherwise the id is kept for a file created soon after.
+    pub(crate) fn deleted(
+        &self,
+        path: &Utf8Path,
+        content_hash: u64,
+    ) -> Option<(Utf8PathBuf, FileId)> {
+        let created = self.pair(path, content_hash, true)?;
+        let id = self.rename(path, &created)?;
+        Some((created, id))
+    }
+
+    /// Records the creation of the file at `path`, whose contents hashed to
+    /// `content_hash`.
+    ///
+    /// If a file with the same contents was deleted recently, the two make a
+    /// rename: the created file takes the deleted one's id, which is
+    /// returned.
+    pub(crate) fn created(&self, path: &Utf8Path, content_hash: u64) -> Option<FileId> {
+        let deleted = self.pair(path, content_hash, false)?;
+        self.rename(&deleted, path)
+    }
+
+    /// Returns the other half of a rename of `path`, or keeps `path` waiting
+    /// for one in place of its earlier changes.
+    fn pair(&self, path: &Utf8Path, content_hash: u64, deleted: bool) -> Option<Utf8PathBuf> {
+        let mut unmatched = self.unmatched.lock();
+        unmatched.retain(|file| file.path != path);
+        let other = unmatched.iter().position(|file| {
+            file.content_hash == content_hash && file.deleted != deleted
+        });
+        if let Some(other) = other.and_then(|index| unmatched.remove(index)) {
+            return Some(other.path);
+        }
+
+        unmatched.push_back(Unmatched {
+            content_hash,
+            path: path.to_owned(),
+            deleted,
+        });
+        if unmatched.len() > RENAME_WINDOW {
+            if let Some(oldest) = unmatched.pop_front().filter(|file| file.deleted) {
+                self.remove(&oldest.path);
+            }
+        }
+        None
+    }
+
+    /// Forgets the ids of the paths not matching `keep`.
+    pub(crate) fn retain(&self, keep: impl Fn(&Utf8Path) -> bool) {
+        let table = &mut *self.table.write();
+        table.by_path.retain(|path, _| keep(path));
+        let by_path = &table.by_path;
+        table.by_id.retain(|_, path| by_path.contains_key(path));
+    }
+
+    /// Replaces the id file with the table; a table in memory isn't saved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be encoded, written or renamed.
+    pub(crate) fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let mut ids: Vec<_> = {
+            let table = self.table.read();
+            table.by_path.iter().map(|(path, id)| (path.clone(), *id)).collect()
+        };
+        ids.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+        let contents = IdContents {
+            version: FORMAT_VERSION,
+            ids,
+        };
+        let bytes = rmp_serde::to_vec_named(&contents).map_err(io::Error::other)?;
+        write_atomically(path, &bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_ids_hash_paths_and_avoid_taken_ones() {
+        let ids = FileIds::default();
+        let a = Utf8Path::new("app/a.ts");
+        let b = Utf8Path::new("app/b.ts");
+        let id = ids.assign(a);
+        assert_eq!(id, FileId::new(hash_path(a)));
+        assert_eq!(ids.assign(a), id);
+
+        // The renamed file keeps its id; a new file at its old path can't take it
+        assert_eq!(ids.rename(a, b), Some(id));
+        assert_eq!(ids.get(b), Some(id));
+        assert_eq!(ids.path_of(id).as_deref(), Some(b));
+        assert_eq!(ids.assign(a), FileId::new(hash_path(a).wrapping_add(1)));
+
+        ids.retain(|path| path == b);
+        assert_eq!(ids.get(a), None);
+        ids.remove(b);
+        assert_eq!(ids.path_of(id), None);
+        assert_eq!(ids.rename(b, a), None);
+    }
+
+    #[test]
+    fn test_deletions_and_creations_pair_into_renames() {
+        let ids = FileIds::default();
+        let (a, b, c) = (Utf8Path::new("a.ts"), Utf8Path::new("b.ts"), Utf8Path::new("c.ts"));
+        let id = ids.assign(a);
+
+        // Deleted first: the id waits for a file with the same contents
+        assert_eq!(ids.deleted(a, 7), None);
+        assert_eq!(ids.get(a), Some(id));
+        assert_eq!(ids.created(b, 8), None);
+        assert_eq!(ids.created(c, 7), Some(id));
+        assert_eq!((ids.get(a), ids.get(c)), (None, Some(id)));
+
+        // Created first: the deletion moves the id over
+        assert_eq!(ids.created(a, 9), None);
+        assert_eq!(ids.deleted(c, 9), Some((a.to_owned(), id)));
+        assert_eq!(ids.path_of(id).as_deref(), Some(a));
+
+        // A file recreated in place is no longer half of a rename
+        assert_eq!(ids.deleted(a, 10), None);
+        assert_eq!(ids.created(a, 11), None);
+        assert_eq!(ids.created(b, 10), None);
+        assert_eq!(ids.get(a), Some(id));
+
+        // Deletions not paired in time give up their ids
+        assert_eq!(ids.deleted(a, 12), None);
+        for (n, content_hash) in (0..RENAME_WINDOW).zip(100..) {
+            ids.created(Utf8Path::new(&format!("new-{n}.ts")), content_hash);
+        }
+        assert_eq!(ids.get(a), None);
+    }
+
+    #[test]
+    fn test_ids_survive_reload() {
+        let (_temp, dir) = crate::tests::temp_root();
+        let roots = [Utf8Path::new("/repo/src")];
+        let moved = Utf8Path::new("/repo/src/moved.ts");
+
+        let ids = FileIds::load(&dir, roots);
+        assert!(ids.is_persistent());
+        let id = ids.assign(Utf8Path::new("/repo/src/a.ts"));
+        ids.rename(Utf8Path::new("/repo/src/a.ts"), moved);
+        assert!(ids.save().is_ok());
+
+        let reloaded = FileIds::load(&dir, roots);
+        assert_eq!(reloaded.get(moved), Some(id));
+        assert_eq!(reloaded.path_of(id).as_deref(), Some(moved));
+        // Other roots have their own table
+        assert_eq!(FileIds::load(&dir, [Utf8Path::new("/other")]).get(moved), None);
+    }
+}
@@ -0,0 +1,216 @@
+//! Rules for ignoring specific model imports during analysis.
+//!
+//! Some legacy imports are accepted exceptions everywhere (a logger, a
+//! shared enum) and would otherwise keep every file that uses them from
+//! counting as migrated. [`ImportIgnores`] moves matching imports into
+//! [`FileInfo::ignored_imports`](ch_core::FileInfo::ignored_imports) before
+//! the migration status is determined.
+//!
+//! Each rule is either:
+//!
+//! - an import path, if it contains `/`: matches imports whose path ends
+//!   with it, e.g. `shared/models/legacy-logger` matches
+//!   `../../shared/models/legacy-logger`
+//! - a model name otherwise: matches that imported name only, so other names
+//!   imported by the same statement still count
+//!
+//! # Examples
+//!
+//! ```
+//! use ch_scanner::ImportIgnores;
+//!
+//! let ignores = ImportIgnores::new(&["shared/models/legacy-logger", "LegacyEnum"]);
+//! assert!(ignores.matches_path("../../shared/models/legacy-logger"));
+//! assert!(!ignores.matches_path("../../shared/models/legacy-logger-config"));
+//! assert!(ignores.matches_name("LegacyEnum"));
+//! ```
+
+use ch_core::ImportInfo;
+use smallvec::SmallVec;
+
+/// Import paths and model names excluded from migration status.
+///
+/// See the [module documentation](self) for how rules match.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportIgnores {
+    /// Import path suffixes, without leading `./` or trailing `.ts`.
+    paths: Vec<String>,
+    /// Imported model names.
+    names: Vec<String>,
+}
+
+impl ImportIgnores {
+    /// Creates ignore rules from configuration entries.
+    ///
+    /// Blank entries are skipped.
+    #[must_use]
+    pub fn new<S: AsRef<str>>(rules: &[S]) -> Self {
+        let mut ignores = Self::default();
+
+        for rule in rules {
+            let rule = rule.as_ref().trim();
+            if rule.is_empty() {
+                continue;
+            }
+
+            if rule.contains('/') {
+                let path = rule.trim_start_matches("./").trim_start_matches('/');
+                let path = path.strip_suffix(".ts").unwrap_or(path);
+                ignores.paths.push(path.to_owned());
+            } else {
+                ignores.names.push(rule.to_owned());
+            }
+        }
+
+        ignores
+    }
+
+    /// Returns `true` if there are no rules.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty() && self.names.is_empty()
+    }
+
+    /// Returns the number of rules.
+    #[must_use]
+    pub fn rule_count(&self) -> usize {
+        self.paths.len() + self.names.len()
+    }
+
+    /// Returns `true` if an import of `path` is ignored entirely.
+    ///
+    /// Quotes around `path`, as captured from the import statement, are
+    /// ignored.
+    #[must_use]
+    pub fn matches_path(&self, path: &str) -> bool {
+        let path = path.trim_matches(|c| matches!(c, '\'' | '"' | '`'));
+        let path = path.strip_suffix(".ts").unwrap_or(path);
+        self.paths.iter().any(|rule| {
+            path.strip_suffix(rule.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.ends_with('/'))
+        })
+    }
+
+    /// Returns `true` if the imported model `name` is ignored.
+    #[must_use]
+    pub fn matches_name(&self, name: &str) -> bool {
+        self.names.iter().any(|rule| rule == name)
+    }
+
+    /// Moves ignored model imports out of `imports`.
+    ///
+    /// Only imports with a detected model source are considered. An import
+    /// with some ignored names is split: the ignored names are returned as a
+    /// copy of the import, and the rest stay in `imports`.
+    pub fn split_off(&self, imports: &mut SmallVec<[ImportInfo; 8]>) -> SmallVec<[ImportInfo; 2]> {
+        let mut ignored = SmallVec::new();
+        if self.is_empty() {
+            return ignored;
+        }
+
+        let mut kept = SmallVec::with_capacity(imports.len());
+        for mut import in imports.drain(..) {
+            if import.source.is_none() {
+                kept.push(import);
+                continue;
+            }
+
+            if self.matches_path(&import.path) {
+                ignored.push(import);
+                continue;
+            }
+
+            let (ignored_names, names): (SmallVec<[String; 4]>, SmallVec<[String; 4]>) =
+                import.names.drain(..).partition(|name| self.matches_name(name));
+
+            if ignored_names.is_empty() {
+                import.names = names;
+                kept.push(import);
+            } else if names.is_empty() {
+                import.names = ignored_names;
+                ignored.push(import);
+            } else {
+                let (ignored_aliases, aliases) =
+                    import.aliases.drain(..).partition(|(name, _)| ignored_names.contains(name));
+                ignored.push(ImportInfo {
+                    names: ignored_names,
+                    aliases: ignored_aliases,
+                    ..import.clone()
+                });
+                import.names = names;
+                import.aliases = aliases;
+                kept.push(import);
+            }
+        }
+
+        *imports = kept;
+        ignored
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ch_core::{ImportKind, ModelSource, SourceLocation};
+    use smallvec::smallvec;
+
+    fn import(path: &str, names: &[&str], source: Option<ModelSource>) -> ImportInfo {
+        ImportInfo::new(
+            path,
+            ImportKind::Named,
+            names.iter().map(|name| (*name).to_owned()).collect(),
+            source,
+            SourceLocation::default(),
+        )
+    }
+
+    #[test]
+    fn test_ignores_match_path_suffix() {
+        let ignores = ImportIgnores::new(&["./shared/models/legacy-logger.ts"]);
+        assert!(ignores.matches_path("shared/models/legacy-logger"));
+        assert!(ignores.matches_path("../../shared/models/legacy-logger"));
+        assert!(ignores.matches_path("'../../shared/models/legacy-logger'"));
+        assert!(!ignores.matches_path("../../shared/models/my-legacy-logger"));
+        assert!(!ignores.matches_name("legacy-logger"));
+    }
+
+    #[test]
+    fn test_ignores_split_off() {
+        let ignores = ImportIgnores::new(&["shared/models/logger", "LegacyEnum", " "]);
+        assert_eq!(ignores.rule_count(), 2);
+
+        let legacy = Some(ModelSource::SharedLegacy);
+        let mut imports: SmallVec<[ImportInfo; 8]> = smallvec![
+            import("../shared/models/logger", &["Logger"], legacy),
+            import("../shared/models/foo", &["Foo", "LegacyEnum"], legacy)
+                .with_alias("Foo", "F")
+                .with_alias("LegacyEnum", "LE"),
+            import("../shared/models/enums", &["LegacyEnum"], legacy),
+            import("@angular/core", &["LegacyEnum"], None),
+        ];
+
+        let ignored = ignores.split_off(&mut imports);
+
+        // Aliases stay with the names they rename
+        assert_eq!(imports[0].aliases.as_slice(), [("Foo".to_owned(), "F".to_owned())]);
+        assert_eq!(ignored[1].aliases.as_slice(), [("LegacyEnum".to_owned(), "LE".to_owned())]);
+
+        let kept: Vec<_> = imports.iter().map(|i| (i.path.as_str(), i.names.to_vec())).collect();
+        assert_eq!(
+            kept,
+            [
+                ("../shared/models/foo", vec!["Foo".to_owned()]),
+                ("@angular/core", vec!["LegacyEnum".to_owned()]),
+            ]
+        );
+        let ignored: Vec<_> = ignored.iter().map(|i| (i.path.as_str(), i.names.to_vec())).collect();
+        assert_eq!(
+            ignored,
+            [
+                ("../shared/models/logger", vec!["Logger".to_owned()]),
+                ("../shared/models/foo", vec!["LegacyEnum".to_owned()]),
+                ("../shared/models/enums", vec!["LegacyEnum".to_owned()]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,260 @@
+//! Dry-run analysis of porting a single legacy model.
+//!
+//! [`model_impact`] assumes a legacy model has been fully ported to
+//! `shared_2023/` and every import of it switched over, then works out which
+//! files would change status. A file whose only legacy imports are of that
+//! model becomes migrated; a legacy file importing other legacy models too
+//! becomes partial.
+//!
+//! A model's import names are its registered exports (e.g. `Contract` and
+//! `ContractCodeGen`) plus the model name itself, so models missing from the
+//! registry can still be analyzed by name.
+//!
+//! # Examples
+//!
+//! ```
+//! use camino::Utf8PathBuf;
+//! use ch_core::{
+//!     FileId, FileInfo, ImportInfo, ImportKind, MigrationStatus, ModelRegistry, ModelSource,
+//!     SourceLocation,
+//! };
+//! use ch_scanner::model_impact;
+//!
+//! let mut file = FileInfo::new(FileId::new(1), Utf8PathBuf::from("src/app/a.ts"));
+//! file.imports = vec![ImportInfo::new(
+//!     "'../shared/models/contract'",
+//!     ImportKind::Named,
+//!     ["Contract".to_owned()].into_iter().collect(),
+//!     Some(ModelSource::SharedLegacy),
+//!     SourceLocation::default(),
+//! )]
+//! .into();
+//! file.status = MigrationStatus::Legacy;
+//!
+//! let impact = model_impact(&[file], &ModelRegistry::new(), "Contract");
+//! assert_eq!(impact.changes[0].to, MigrationStatus::Migrated);
+//! ```
+
+use camino::Utf8PathBuf;
+use ch_core::{FileInfo, FxHashSet, MigrationStatus, ModelRegistry};
+use serde::{Deserialize, Serialize};
+
+/// A file whose status would change once the model is ported, or that
+/// changed between two scans (see [`diff_scans`](crate::diff_scans)).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusChange {
+    /// Path of the file.
+    pub path: Utf8PathBuf,
+
+    /// Current status, or the previous one in a scan diff.
+    pub from: MigrationStatus,
+
+    /// Status after the port, or the current one in a scan diff.
+    pub to: MigrationStatus,
+
+    /// Legacy import names the file would still use, sorted; for a scan diff,
+    /// the names it uses now.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub remaining: Vec<String>,
+}
+
+/// The impact of porting one legacy model.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelImpact {
+    /// Legacy model name.
+    pub model: String,
+
+    /// Modern model name, if the model is already ported.
+    pub modern_equivalent: Option<String>,
+
+    /// Import names counted as the model, sorted.
+    pub names: Vec<String>,
+
+    /// Number of files importing the model.
+    pub consumers: usize,
+
+    /// Files that would change status, sorted by path.
+    pub changes: Vec<StatusChange>,
+
+    /// Files that would no longer be blocked on unported models, sorted by path.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub unblocked: Vec<Utf8PathBuf>,
+}
+
+impl ModelImpact {
+    /// Returns the number of files that would become migrated.
+    #[must_use]
+    pub fn migrated_count(&self) -> usize {
+        self.changes
+            .iter()
+            .filter(|change| change.to == MigrationStatus::Migrated)
+            .count()
+    }
+}
+
+/// Works out which files would change status if `model` were ported.
+///
+/// See the [module documentation](self) for how the model's imports are found.
+///
+/// # Arguments
+///
+/// * `files` - The scanned files
+/// * `registry` - Model registry used to find the model's exports
+/// * `model` - Legacy model name
+#[must_use]
+pub fn model_impact(files: &[FileInfo], registry: &ModelRegistry, model: &str) -> ModelImpact {
+    let mut names: FxHashSet<&str> = registry
+        .get_legacy_model(model)
+        .map(|definition| definition.exports.iter().map(String::as_str).collect())
+        .unwrap_or_default();
+    names.insert(model);
+
+    let mut consumers = 0;
+    let mut changes = Vec::new();
+    let mut unblocked = Vec::new();
+
+    for file in files {
+        let legacy: Vec<&str> = file
+            .legacy_imports()
+            .flat_map(|import| import.names.iter())
+            .map(String::as_str)
+            .collect();
+        if !legacy.iter().any(|name| names.contains(name)) {
+            continue;
+        }
+        consumers += 1;
+
+        let mut remaining: Vec<&str> =
+            legacy.into_iter().filter(|name| !names.contains(name)).collect();
+        remaining.sort_unstable();
+        remaining.dedup();
+
+        let missing = registry.missing_equivalents(file);
+        if !missing.is_empty() && missing.iter().all(|name| names.contains(name)) {
+            unblocked.push(file.path.clone());
+        }
+
+        // Switched imports come from shared_2023/, so the file uses modern models
+        let to = if remaining.is_empty() {
+            MigrationStatus::Migrated
+        } else {
+            MigrationStatus::Partial
+        };
+        if to != file.status {
+            changes.push(StatusChange {
+                path: file.path.clone(),
+                from: file.status,
+                to,
+                remaining: remaining.into_iter().map(str::to_owned).collect(),
+            });
+        }
+    }
+
+    changes.sort_unstable_by(|a, b| a.path.cmp(&b.path));
+    unblocked.sort_unstable();
+
+    let mut names: Vec<String> = names.into_iter().map(str::to_owned).collect();
+    names.sort_unstable();
+
+    ModelImpact {
+        model: model.to_owned(),
+        modern_equivalent: registry
+            .modern_model_for(model)
+            .map(|definition| definition.name.clone()),
+        names,
+        consumers,
+        changes,
+        unblocked,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ch_core::{FileId, ImportInfo, ImportKind, ModelDefinition, ModelSource, SourceLocation};
+
+    fn make_file(path: &str, status: MigrationStatus, imports: &[(&str, ModelSource)]) -> FileInfo {
+        let mut file = FileInfo::new(FileId::new(0), Utf8PathBuf::from(path));
+        file.status = status;
+        file.imports = imports
+            .iter()
+            .map(|(name, source)| {
+                ImportInfo::new(
+                    "'../shared/models'",
+                    ImportKind::Named,
+                    [(*name).to_owned()].into_iter().collect(),
+                    Some(*source),
+                    SourceLocation::default(),
+                )
+            })
+            .collect();
+        file
+    }
+
+    fn registry() -> ModelRegistry {
+        let mut registry = ModelRegistry::new();
+        let mut contract =
+            ModelDefinition::new("Contract", ModelSource::SharedLegacy, "models/contract.ts");
+        contract.add_export("Contract");
+        contract.add_export("ContractCodeGen");
+        registry.register(contract);
+        let mut job = ModelDefinition::new("Job", ModelSource::SharedLegacy, "models/job.ts");
+        job.add_export("Job");
+        registry.register(job);
+        registry
+    }
+
+    #[test]
+    fn test_model_impact_status_changes() {
+        let legacy = ModelSource::SharedLegacy;
+        let modern = ModelSource::Shared2023;
+        let files = vec![
+            make_file("src/d.ts", MigrationStatus::Legacy, &[("Job", legacy)]),
+            make_file("src/c.ts", MigrationStatus::Partial, &[("Contract", legacy), ("Job", modern)]),
+            make_file(
+                "src/b.ts",
+                MigrationStatus::Legacy,
+                &[("ContractCodeGen", legacy), ("Job", legacy)],
+            ),
+            make_file("src/a.ts", MigrationStatus::Legacy, &[("Contract", legacy)]),
+        ];
+
+        let impact = model_impact(&files, &registry(), "Contract");
+
+        assert_eq!(impact.names, ["Contract", "ContractCodeGen"]);
+        assert_eq!(impact.consumers, 3);
+        assert_eq!(impact.modern_equivalent, None);
+        let changes: Vec<_> = impact
+            .changes
+            .iter()
+            .map(|change| (change.path.as_str(), change.from, change.to))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                ("src/a.ts", MigrationStatus::Legacy, MigrationStatus::Migrated),
+                ("src/b.ts", MigrationStatus::Legacy, MigrationStatus::Partial),
+                ("src/c.ts", MigrationStatus::Partial, MigrationStatus::Migrated),
+            ]
+        );
+        assert_eq!(impact.changes[1].remaining, ["Job"]);
+        assert_eq!(impact.migrated_count(), 2);
+        // Job has no port either, so only a.ts and c.ts are fully unblocked
+        assert_eq!(impact.unblocked, ["src/a.ts", "src/c.ts"]);
+    }
+
+    #[test]
+    fn test_model_impact_unknown_model() {
+        let files = vec![make_file(
+            "src/a.ts",
+            MigrationStatus::Legacy,
+            &[("Job", ModelSource::SharedLegacy)],
+        )];
+
+        let impact = model_impact(&files, &registry(), "Missing");
+
+        assert_eq!(impact.consumers, 0);
+        assert!(impact.changes.is_empty());
+        assert!(impact.unblocked.is_empty());
+    }
+}
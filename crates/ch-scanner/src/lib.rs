@@ -8,10 +8,26 @@
 //!
 //! The main entry point is [`Scanner`], which combines:
 //!
-//! - [`FileWalker`]: Directory traversal respecting `.gitignore` patterns
+//! - [`FileWalker`]: Directory traversal respecting `.gitignore` and
+//!   `.chmigrateignore` patterns
 //! - [`FileAnalyzer`]: Parallel file processing with rayon + bumpalo arenas
 //! - [`ScanCache`]: Concurrent caching with `FxHashMap` + `RwLock`
 //! - [`ScanStats`]: Atomic statistics for progress tracking
+//! - [`CodeOwners`]: `CODEOWNERS` parsing for per-team attribution
+//! - [`rollup_by_directory`] / [`rollup_by_owner`]: Per-directory and per-team aggregation
+//! - [`rollup_by_model`]: Files consuming each legacy model
+//! - [`check_parity`]: Export parity between legacy models and their ports
+//! - [`plan_migration`]: Migration order from file imports and unported models
+//! - [`model_impact`]: Files whose status would change once a model is ported
+//! - [`ScanProfile`]: Per-phase timing from [`Scanner::scan_profiled`]
+//! - [`write_status_file`]: Atomically updated statistics for dashboards
+//! - [`ResourceLimits`]: Memory limits that switch scans to a low-memory mode
+//! - [`RetryPolicy`]: Retries of files that failed to read transiently
+//! - [`ScanConfig::with_cache_dir`]: On-disk cache letting full scans skip unchanged files
+//! - [`Scanner::file_id`]: File ids kept across renames and, with a cache, across runs
+//! - [`Scanner::save_cache`] / [`Scanner::load_cache`]: Results shown at once in a later session
+//! - [`Scanner::duplicates`]: Copied files, sharing one analysis past the second copy
+//! - [`ScanObserver`]: Per-file hooks for pushing timings and counts into metrics systems
 //!
 //! # Example
 //!
@@ -60,11 +76,25 @@
 //!         ScanUpdate::PathsDiscovered(count) => println!("Found {} files", count),
 //!         ScanUpdate::FileScanned(info) => println!("Scanned: {}", info.path),
 //!         ScanUpdate::FileError { path, .. } => println!("Error: {}", path),
+//!         ScanUpdate::Progress(stats) => println!("{:.0}% done", stats.progress_percent()),
 //!         ScanUpdate::Complete(result) => println!("Done: {} total", result.stats.total),
 //!     }
 //! }
 //! ```
 //!
+//! Consumers only showing progress can skip the per-file updates and wait for
+//! [`ScanUpdate::Progress`], sent at most every [`ProgressInterval`].
+//!
+//! # Cancellation
+//!
+//! [`Scanner::scan_cancellable`] and [`Scanner::scan_streaming_cancellable`]
+//! take a [`CancellationToken`]. Cancelling it stops the scan before the next
+//! file and makes it return [`ScanError::Cancelled`]; files analyzed so far
+//! stay in the cache.
+//!
+//! [`Scanner::scan_streaming_controlled`] takes a [`ScanControl`] instead,
+//! which can also pause a streaming scan and resume it where it stopped.
+//!
 //! # Architecture
 //!
 //! ```text
@@ -95,26 +125,95 @@
 
 mod analyzer;
 mod cache;
+mod control;
+mod dedup;
+mod diff;
 mod error;
+mod history;
+mod ids;
+mod impact;
+mod ignores;
+mod limits;
+mod observer;
+mod ownership;
+mod parity;
+mod persist;
+mod plan;
+mod priority;
+mod profile;
+mod progress;
+mod recipe;
 mod registry;
+mod renames;
+mod retry;
+mod rollup;
 mod stats;
+mod status_file;
+mod tsconfig;
 mod walker;
 
 pub use analyzer::FileAnalyzer;
-pub use cache::ScanCache;
-pub use error::ScanError;
+pub use cache::{CacheView, ScanCache};
+pub use control::ScanControl;
+pub use diff::{diff_scans, ScanDiff};
+pub use error::{ErrorCategory, ScanError};
+pub use history::{
+    append_history, compact_history, load_history, HistoryCompaction, HistoryEntry,
+    HistoryRetention, Trend, TrendPoint,
+};
+pub use ignores::ImportIgnores;
+pub use impact::{model_impact, ModelImpact, StatusChange};
+pub use limits::{resident_bytes, ResourceLimits};
+pub use observer::ScanObserver;
+pub use recipe::{import_recipe, MigrationRecipe};
 pub use registry::{RegistryBuildResult, RegistryBuilder};
-pub use stats::{ScanStats, StatsSnapshot};
-pub use walker::FileWalker;
-
+pub use ownership::CodeOwners;
+pub use parity::{check_parity, ParityGap, ParityReport};
+pub use plan::{plan_migration, MigrationPlan, MigrationWave, ModelBlocker, PlannedFile};
+pub use profile::{FileTiming, ScanProfile};
+pub use progress::ProgressInterval;
+pub use renames::ModelRenames;
+pub use retry::RetryPolicy;
+pub use rollup::{
+    directory_group, rollup_by_directory, rollup_by_model, rollup_by_owner, DirectoryStats,
+    ModelConsumers, OwnerStats, UNOWNED,
+};
+pub use stats::{
+    FileKind, ParseCounts, ScanProgress, ScanStats, SizeBucket, SkipCounts, StatsDelta,
+    StatsSnapshot,
+};
+pub use status_file::{write_status_file, StatusFile};
+pub use walker::{FileWalker, SkipReason, SkippedPath, WalkOutput, IGNORE_FILE_NAME};
+
+/// Token for cancelling a scan, re-exported from `tokio-util`.
+pub use tokio_util::sync::CancellationToken;
+
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use camino::{Utf8Path, Utf8PathBuf};
-use ch_core::{FileInfo, MigrationStatus, ModelRegistry};
+use ch_core::{FileId, FileInfo, FxHashMap, FxHashSet, MigrationStatus, ModelRegistry};
+use parking_lot::RwLock;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use rustc_hash::FxHasher;
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
 use ch_ts_parser::ModelPathMatcher;
+use control::PAUSE_CHUNK;
+use dedup::Duplicates;
+use ids::FileIds;
+use limits::MemoryGuard;
+use observer::SharedObserver;
+use persist::{roots_file, CacheSplit, FileStamp, PersistentCache, Snapshot};
+use priority::ScanPriority;
+use profile::FileTimings;
+use progress::ProgressTicker;
+use tsconfig::TsProject;
+use walker::PathGlobs;
 
 /// Update sent during a streaming scan operation.
 ///
@@ -150,6 +249,12 @@ pub enum ScanUpdate {
         error: ScanError,
     },
 
+    /// Statistics so far, sent every few files or milliseconds.
+    ///
+    /// Throttled as set by [`ScanConfig::with_progress_interval`], so
+    /// progress bars can follow the scan without handling every file.
+    Progress(StatsSnapshot),
+
     /// Scan completed with final statistics.
     ///
     /// Sent after all files have been processed. The result contains
@@ -166,22 +271,68 @@ pub enum ScanUpdate {
 /// use camino::Utf8Path;
 ///
 /// let config = ScanConfig::new(Utf8Path::new("./src"))
-///     .with_skip_dirs(&["vendor", "third_party"]);
+///     .with_skip_dirs(&["vendor", "third_party"])
+///     .with_exclude_globs(&["**/*.spec.ts"]);
 /// ```
 #[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)] // Independent switches, not a state machine
 pub struct ScanConfig {
     /// Root directory to scan.
     pub root: Utf8PathBuf,
+    /// Further directories scanned alongside `root` into the same cache and
+    /// statistics.
+    pub additional_roots: Vec<Utf8PathBuf>,
     /// Additional directories to skip.
     pub skip_dirs: Vec<String>,
     /// Whether to follow symbolic links.
     pub follow_links: bool,
+    /// Glob patterns files must match to be scanned; empty means all files.
+    pub include_globs: Vec<String>,
+    /// Glob patterns for files to leave out of scans.
+    pub exclude_globs: Vec<String>,
+    /// Path to a `tsconfig.json` whose project restricts scans to the files
+    /// it builds.
+    pub tsconfig: Option<Utf8PathBuf>,
     /// Path to the legacy shared directory (for building model registry).
     pub shared_path: Option<Utf8PathBuf>,
     /// Path to the modern `shared_2023` directory (for building model registry).
     pub shared_2023_path: Option<Utf8PathBuf>,
     /// Whether to build the model registry for import filtering.
     pub use_registry: bool,
+    /// Path to a `CODEOWNERS` file for attributing files to teams.
+    pub codeowners_path: Option<Utf8PathBuf>,
+    /// Path to a model rename mapping applied to the registry.
+    pub renames_path: Option<Utf8PathBuf>,
+    /// Import paths or model names excluded from migration status.
+    pub ignore_imports: Vec<String>,
+    /// Memory limits that switch the scanner to low-memory mode.
+    pub limits: ResourceLimits,
+    /// Directory of the on-disk cache that lets full scans skip unchanged
+    /// files.
+    pub cache_dir: Option<Utf8PathBuf>,
+    /// Size in bytes above which files are skipped instead of parsed.
+    pub max_file_size: Option<u64>,
+    /// Whether to skip files whose contents look binary.
+    pub skip_binary: bool,
+    /// Size in bytes from which scans memory-map files instead of reading
+    /// them (see [`ScanConfig::with_mmap_threshold`]).
+    pub mmap_threshold: Option<u64>,
+    /// Directories whose files streaming scans process first; relative
+    /// paths are taken from `root`.
+    pub priority_dirs: Vec<Utf8PathBuf>,
+    /// Whether streaming scans first process directories where the previous
+    /// scan found files needing migration.
+    pub legacy_first: bool,
+    /// Most files the cache keeps with imports and model references; older
+    /// ones are compacted.
+    pub max_detailed_files: Option<usize>,
+    /// Number of threads in the scanner's own thread pool; `None` shares
+    /// rayon's global pool.
+    pub threads: Option<usize>,
+    /// How files that failed with a transient error are retried.
+    pub retry: RetryPolicy,
+    /// How often streaming scans send [`ScanUpdate::Progress`].
+    pub progress: ProgressInterval,
 }
 
 impl ScanConfig {
@@ -203,14 +354,53 @@ impl ScanConfig {
     pub fn new(root: &Utf8Path) -> Self {
         Self {
             root: root.to_owned(),
+            additional_roots: Vec::new(),
             skip_dirs: Vec::new(),
             follow_links: false,
+            include_globs: Vec::new(),
+            exclude_globs: Vec::new(),
+            tsconfig: None,
             shared_path: None,
             shared_2023_path: None,
             use_registry: false,
+            codeowners_path: None,
+            renames_path: None,
+            ignore_imports: Vec::new(),
+            limits: ResourceLimits::default(),
+            cache_dir: None,
+            max_file_size: None,
+            skip_binary: false,
+            mmap_threshold: None,
+            priority_dirs: Vec::new(),
+            legacy_first: false,
+            max_detailed_files: None,
+            threads: None,
+            retry: RetryPolicy::default(),
+            progress: ProgressInterval::default(),
         }
     }
 
+    /// Adds directories to scan alongside the root.
+    ///
+    /// Full scans walk every root into one cache and one set of statistics,
+    /// so an app split across `src/app` and `src/admin` needs only one
+    /// scanner. A file under several roots is counted once.
+    ///
+    /// # Arguments
+    ///
+    /// * `roots` - Further directories to scan
+    #[must_use]
+    pub fn with_additional_roots<P: AsRef<Utf8Path>>(mut self, roots: &[P]) -> Self {
+        self.additional_roots.extend(roots.iter().map(|root| root.as_ref().to_owned()));
+        self
+    }
+
+    /// Returns the root followed by the additional roots.
+    pub fn roots(&self) -> impl Iterator<Item = &Utf8Path> {
+        std::iter::once(self.root.as_path())
+            .chain(self.additional_roots.iter().map(Utf8PathBuf::as_path))
+    }
+
     /// Adds directories to skip during scanning.
     ///
     /// # Arguments
@@ -233,6 +423,53 @@ impl ScanConfig {
         self
     }
 
+    /// Adds glob patterns limiting scans to the files matching one of them.
+    ///
+    /// Patterns are matched against paths relative to the root, e.g.
+    /// `src/app/**`. Invalid patterns make [`Scanner::new`] fail.
+    ///
+    /// # Arguments
+    ///
+    /// * `patterns` - Glob patterns for files to scan
+    #[must_use]
+    pub fn with_include_globs<S: AsRef<str>>(mut self, patterns: &[S]) -> Self {
+        self.include_globs.extend(patterns.iter().map(|p| p.as_ref().to_owned()));
+        self
+    }
+
+    /// Adds glob patterns for files to leave out of scans.
+    ///
+    /// Patterns are matched against paths relative to the root, and `*`
+    /// matches across directories, so `*.spec.ts` and `**/*.spec.ts` both
+    /// exclude every spec file. Invalid patterns make [`Scanner::new`] fail.
+    ///
+    /// # Arguments
+    ///
+    /// * `patterns` - Glob patterns for files to skip
+    #[must_use]
+    pub fn with_exclude_globs<S: AsRef<str>>(mut self, patterns: &[S]) -> Self {
+        self.exclude_globs.extend(patterns.iter().map(|p| p.as_ref().to_owned()));
+        self
+    }
+
+    /// Restricts scans to the files the project of a `tsconfig.json` builds.
+    ///
+    /// The project's `files`, `include` and `exclude` settings apply on top
+    /// of the glob patterns, leaving out directories the compiler never
+    /// sees. Imports through its `paths` aliases, such as
+    /// `@shared/models/foo`, are matched against the shared directories the
+    /// aliases stand for. An unreadable or invalid config makes
+    /// [`Scanner::new`] fail.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the `tsconfig.json` file
+    #[must_use]
+    pub fn with_tsconfig(mut self, path: &Utf8Path) -> Self {
+        self.tsconfig = Some(path.to_owned());
+        self
+    }
+
     /// Configures the paths to the shared directories for building the model registry.
     ///
     /// When set, the scanner will build a model registry and use it to filter
@@ -259,6 +496,221 @@ impl ScanConfig {
         self.use_registry = use_registry;
         self
     }
+
+    /// Configures a `CODEOWNERS` file used to attribute files to teams.
+    ///
+    /// When set, each scanned [`FileInfo`] has its `owners` populated.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the `CODEOWNERS` file
+    #[must_use]
+    pub fn with_codeowners(mut self, path: &Utf8Path) -> Self {
+        self.codeowners_path = Some(path.to_owned());
+        self
+    }
+
+    /// Configures a model rename mapping file (see [`ModelRenames`]).
+    ///
+    /// The mapping is applied when the model registry is built, so it only
+    /// takes effect together with [`with_shared_paths`](Self::with_shared_paths).
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the rename mapping file
+    #[must_use]
+    pub fn with_renames(mut self, path: &Utf8Path) -> Self {
+        self.renames_path = Some(path.to_owned());
+        self
+    }
+
+    /// Adds rules for model imports to exclude from migration status.
+    ///
+    /// See [`ImportIgnores`] for how rules match.
+    ///
+    /// # Arguments
+    ///
+    /// * `rules` - Import paths or model names to ignore
+    #[must_use]
+    pub fn with_ignore_imports<S: AsRef<str>>(mut self, rules: &[S]) -> Self {
+        self.ignore_imports.extend(rules.iter().map(|rule| rule.as_ref().to_owned()));
+        self
+    }
+
+    /// Configures memory limits (see [`ResourceLimits`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `limits` - Limits that switch the scanner to low-memory mode
+    #[must_use]
+    pub const fn with_resource_limits(mut self, limits: ResourceLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Keeps analysis results in an on-disk cache under `dir`, e.g.
+    /// `.ch-migrate/cache`.
+    ///
+    /// Full scans ([`Scanner::scan`] and [`Scanner::scan_streaming`]) then
+    /// reuse the results of files unchanged since the previous full scan and
+    /// only parse the rest. A file is unchanged if its size and modification
+    /// time, or failing that its content hash, match. The cache is discarded
+    /// when the tool version or anything else affecting analysis changes.
+    ///
+    /// Without a cache directory, repeat scans by the same scanner still
+    /// reuse the files it holds in memory; the directory carries them over
+    /// to the next process.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - Directory holding the cache, created when first saved
+    #[must_use]
+    pub fn with_cache_dir(mut self, dir: &Utf8Path) -> Self {
+        self.cache_dir = Some(dir.to_owned());
+        self
+    }
+
+    /// Skips files larger than `bytes` instead of reading and parsing them.
+    ///
+    /// Skipped files are reported as [`ScanError::Skipped`] errors.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Largest file size to analyze
+    #[must_use]
+    pub const fn with_max_file_size(mut self, bytes: u64) -> Self {
+        self.max_file_size = Some(bytes);
+        self
+    }
+
+    /// Configures whether to skip files whose contents look binary.
+    ///
+    /// Skipped files are reported as [`ScanError::Skipped`] errors rather
+    /// than failing in the parser (see [`FileAnalyzer::with_skip_binary`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `skip` - Whether to sniff files for binary content
+    #[must_use]
+    pub const fn with_skip_binary(mut self, skip: bool) -> Self {
+        self.skip_binary = skip;
+        self
+    }
+
+    /// Memory-maps files of at least `bytes` instead of reading them.
+    ///
+    /// Cuts allocation and copying for very large generated files (see
+    /// [`FileAnalyzer::with_mmap_threshold`]). A mapped file must not be
+    /// modified while it's analyzed, so only scans and directory rescans
+    /// map files; [`Scanner::rescan_files`], which runs as editors save
+    /// files, always reads them. Leave this unset for scanners that run
+    /// while files are being edited, like watch sessions.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Smallest file size to map
+    #[must_use]
+    pub const fn with_mmap_threshold(mut self, bytes: u64) -> Self {
+        self.mmap_threshold = Some(bytes);
+        self
+    }
+
+    /// Adds directories whose files streaming scans process first.
+    ///
+    /// Files under the first directory come before those under the second,
+    /// and so on, so the TUI shows results for the code being migrated
+    /// early. Relative paths are taken from the root. Full scans are not
+    /// affected.
+    ///
+    /// # Arguments
+    ///
+    /// * `dirs` - Directories to scan first, most important first
+    #[must_use]
+    pub fn with_priority_dirs<P: AsRef<Utf8Path>>(mut self, dirs: &[P]) -> Self {
+        self.priority_dirs.extend(dirs.iter().map(|dir| dir.as_ref().to_owned()));
+        self
+    }
+
+    /// Configures whether streaming scans process likely legacy files first.
+    ///
+    /// Directories are ranked by the files needing migration the previous
+    /// scan found there, from the scan cache or the scanner's own last scan,
+    /// and are processed after any [priority
+    /// directories](Self::with_priority_dirs).
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to rank directories by previous legacy hits
+    #[must_use]
+    pub const fn with_legacy_first(mut self, enabled: bool) -> Self {
+        self.legacy_first = enabled;
+        self
+    }
+
+    /// Limits how many files the cache keeps in full detail.
+    ///
+    /// Beyond the limit, the least recently used files keep only their
+    /// status (see [`ScanCache::with_max_detailed_files`]), bounding memory
+    /// in long watch sessions on very large trees. The next full scan
+    /// analyzes compacted files again.
+    ///
+    /// Compaction already happens during a scan, so the cache of a limited
+    /// scanner lacks imports and model references for most files of a large
+    /// tree. Scanners whose results feed reports should not be limited.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - Most files to keep imports and model references for
+    #[must_use]
+    pub const fn with_max_detailed_files(mut self, limit: usize) -> Self {
+        self.max_detailed_files = Some(limit);
+        self
+    }
+
+    /// Runs the scanner's parallel work in its own pool of `threads`
+    /// threads.
+    ///
+    /// Without a thread count, the scanner shares rayon's global pool, with
+    /// a thread per CPU, with the rest of the process. A dedicated pool
+    /// bounds the CPU scans use without limiting other rayon users; clones
+    /// of the scanner share it. `0` starts a thread per CPU.
+    ///
+    /// # Arguments
+    ///
+    /// * `threads` - Number of threads analyzing files
+    #[must_use]
+    pub const fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+
+    /// Configures how files that failed with a transient error are retried
+    /// (see [`RetryPolicy`]).
+    ///
+    /// By default they are retried twice, once every other file is done.
+    ///
+    /// # Arguments
+    ///
+    /// * `retry` - Number of passes and the delay before each
+    #[must_use]
+    pub const fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Configures how often streaming scans send [`ScanUpdate::Progress`]
+    /// (see [`ProgressInterval`]).
+    ///
+    /// By default one is sent every 256 files or 100 milliseconds.
+    ///
+    /// # Arguments
+    ///
+    /// * `progress` - Files and time between updates
+    #[must_use]
+    pub const fn with_progress_interval(mut self, progress: ProgressInterval) -> Self {
+        self.progress = progress;
+        self
+    }
 }
 
 /// Result of a scan operation.
@@ -270,6 +722,17 @@ pub struct ScanResult {
     pub stats: StatsSnapshot,
     /// Non-fatal errors encountered during scanning.
     pub errors: Vec<(Utf8PathBuf, ScanError)>,
+    /// Paths the walker left out, counted in `stats.skipped`.
+    pub skipped: Vec<SkippedPath>,
+}
+
+/// Result of re-scanning specific files with [`Scanner::rescan_files`].
+#[derive(Debug)]
+pub struct RescanResult {
+    /// Outcome for each re-scanned file.
+    pub outcomes: Vec<(Utf8PathBuf, Result<(), ScanError>)>,
+    /// Change applied to the scanner's statistics.
+    pub delta: StatsDelta,
 }
 
 /// The main scanner for TypeScript files.
@@ -320,14 +783,91 @@ pub struct Scanner {
     config: ScanConfig,
     /// Model path matcher for import detection.
     model_path_matcher: ModelPathMatcher,
-    /// Model registry for filtering imports (shared via Arc for cloning).
-    registry: Arc<ModelRegistry>,
+    /// Model registry for filtering imports, replaced whole when updated
+    /// (shared via Arc for cloning).
+    registry: Arc<RwLock<Arc<ModelRegistry>>>,
+    /// Builder of the registry, if this scanner built it.
+    builder: Option<Arc<RegistryBuilder>>,
+    /// Ownership rules from `CODEOWNERS` (if configured).
+    owners: Option<Arc<CodeOwners>>,
+    /// Rules for model imports excluded from migration status (if configured).
+    ignores: Option<Arc<ImportIgnores>>,
+    /// Compiled include and exclude patterns for walked files.
+    globs: Arc<PathGlobs>,
+    /// Project restricting walked files to those it builds (if configured).
+    project: Option<Arc<TsProject>>,
     /// File analysis results cache (shared via Arc for cloning).
     cache: Arc<ScanCache>,
     /// Statistics counters (shared via Arc for cloning).
     stats: Arc<ScanStats>,
+    /// Time spent building the registry, if this scanner built it.
+    registry_build_time: Option<Duration>,
+    /// Memory limit state (shared via Arc for cloning).
+    memory: Arc<MemoryGuard>,
+    /// Number of analysis threads currently working on a file.
+    busy: Arc<AtomicUsize>,
+    /// Paths the last walk left out (shared via Arc for cloning).
+    skipped: Arc<RwLock<Vec<SkippedPath>>>,
+    /// Files counted as errors in the statistics (shared via Arc for cloning).
+    failed: Arc<RwLock<FxHashSet<Utf8PathBuf>>>,
+    /// Size and modification time of files when they were last read, so
+    /// unchanged cached files can be reused (shared via Arc for cloning).
+    stamps: Arc<RwLock<FxHashMap<Utf8PathBuf, FileStamp>>>,
+    /// Analysis time of each file analyzed since the last scan began
+    /// (shared via Arc for cloning).
+    timings: Arc<FileTimings>,
+    /// Ids handed out to files, kept across renames and, with a cache
+    /// directory, across runs (shared via Arc for cloning).
+    ids: Arc<FileIds>,
+    /// Files found to be copies of an earlier file with the same contents
+    /// (shared via Arc for cloning).
+    duplicates: Arc<Duplicates>,
+    /// Embedder's hook told about each file analyzed (if set).
+    observer: Option<SharedObserver>,
+    /// Thread pool for parallel work, if configured with a thread count
+    /// (shared via Arc for cloning).
+    pool: Option<Arc<ThreadPool>>,
+}
+
+/// Checks that a scan root exists and is a directory.
+fn validate_root(root: &Utf8Path) -> Result<(), ScanError> {
+    if !root.exists() {
+        return Err(ScanError::config(format!("root path does not exist: {root}")));
+    }
+    if !root.is_dir() {
+        return Err(ScanError::config(format!("root path is not a directory: {root}")));
+    }
+    Ok(())
+}
+
+/// Starts the thread pool for `config`, if it sets a thread count.
+fn build_pool(config: &ScanConfig) -> Result<Option<Arc<ThreadPool>>, ScanError> {
+    let Some(threads) = config.threads else {
+        return Ok(None);
+    };
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .thread_name(|index| format!("ch-scan-{index}"))
+        .build()
+        .map_err(|e| ScanError::config(format!("failed to start {threads} scan threads: {e}")))?;
+    Ok(Some(Arc::new(pool)))
+}
+
+/// Runs `op` in `pool`, or on the current thread (and rayon's global pool)
+/// without one.
+fn in_pool<R: Send>(pool: Option<&ThreadPool>, op: impl FnOnce() -> R + Send) -> R {
+    match pool {
+        Some(pool) => pool.install(op),
+        None => op(),
+    }
 }
 
+/// Buffer size for streaming scan channels.
+const STREAM_CHANNEL_CAPACITY: usize = 256;
+
+/// Buffer size for streaming scan channels in low-memory mode.
+const LOW_MEMORY_CHANNEL_CAPACITY: usize = 16;
+
 impl Scanner {
     /// Creates a new scanner with the given configuration.
     ///
@@ -341,7 +881,7 @@ impl Scanner {
     /// # Errors
     ///
     /// Returns [`ScanError::Config`] if the configuration is invalid
-    /// (e.g., root directory doesn't exist).
+    /// (e.g., root directory doesn't exist or a glob pattern is invalid).
     ///
     /// Returns [`ScanError::Registry`] if registry building fails (when enabled).
     ///
@@ -368,7 +908,7 @@ impl Scanner {
     /// # Errors
     ///
     /// Returns [`ScanError::Config`] if the configuration is invalid
-    /// (e.g., root directory doesn't exist).
+    /// (e.g., root directory doesn't exist or a glob pattern is invalid).
     ///
     /// Returns [`ScanError::Registry`] if registry building fails (when enabled).
     pub fn new_with_matcher(
@@ -376,21 +916,16 @@ impl Scanner {
         matcher: ModelPathMatcher,
     ) -> Result<Self, ScanError> {
         // Validate configuration
-        if !config.root.exists() {
-            return Err(ScanError::config(format!(
-                "root path does not exist: {}",
-                config.root
-            )));
-        }
-
-        if !config.root.is_dir() {
-            return Err(ScanError::config(format!(
-                "root path is not a directory: {}",
-                config.root
-            )));
+        for root in config.roots() {
+            validate_root(root)?;
         }
+        let pool = build_pool(&config)?;
+        let ids = Arc::new(Self::load_ids(&config));
 
         // Build model registry if configured
+        let started = Instant::now();
+        let mut registry_build_time = None;
+        let mut registry_builder = None;
         let registry = if config.use_registry {
             if let (Some(shared), Some(shared_2023)) =
                 (&config.shared_path, &config.shared_2023_path)
@@ -400,8 +935,14 @@ impl Scanner {
                     shared_2023 = %shared_2023,
                     "Building model registry"
                 );
-                let builder = RegistryBuilder::new(shared, shared_2023);
-                builder.build()?
+                let mut builder = RegistryBuilder::new(shared, shared_2023);
+                if let Some(path) = &config.renames_path {
+                    builder = builder.with_renames(ModelRenames::load(path)?);
+                }
+                let registry = in_pool(pool.as_deref(), || builder.build())?;
+                registry_build_time = Some(started.elapsed());
+                registry_builder = Some(Arc::new(builder));
+                registry
             } else {
                 warn!("Registry enabled but shared paths not configured, using empty registry");
                 ModelRegistry::new()
@@ -418,12 +959,40 @@ impl Scanner {
             "Creating scanner"
         );
 
+        let owners = Self::load_codeowners(&config)?;
+        let ignores = Self::import_ignores(&config);
+        let globs = Arc::new(PathGlobs::new(
+            config.roots(),
+            &config.include_globs,
+            &config.exclude_globs,
+        )?);
+        let project = Self::load_project(&config)?;
+        let matcher = Self::alias_matcher(matcher, project.as_deref());
+        let memory = Arc::new(MemoryGuard::new(config.limits));
+        let cache = Arc::new(Self::new_cache(&config));
+
         Ok(Self {
             config,
             model_path_matcher: matcher,
-            registry: Arc::new(registry),
-            cache: Arc::new(ScanCache::new()),
+            registry: Arc::new(RwLock::new(Arc::new(registry))),
+            builder: registry_builder,
+            owners,
+            ignores,
+            globs,
+            project,
+            cache,
             stats: Arc::new(ScanStats::new()),
+            registry_build_time,
+            memory,
+            busy: Arc::new(AtomicUsize::new(0)),
+            skipped: Arc::default(),
+            failed: Arc::default(),
+            stamps: Arc::default(),
+            timings: Arc::default(),
+            ids,
+            duplicates: Arc::default(),
+            observer: None,
+            pool,
         })
     }
 
@@ -447,19 +1016,11 @@ impl Scanner {
         registry: Arc<ModelRegistry>,
     ) -> Result<Self, ScanError> {
         // Validate configuration
-        if !config.root.exists() {
-            return Err(ScanError::config(format!(
-                "root path does not exist: {}",
-                config.root
-            )));
-        }
-
-        if !config.root.is_dir() {
-            return Err(ScanError::config(format!(
-                "root path is not a directory: {}",
-                config.root
-            )));
+        for root in config.roots() {
+            validate_root(root)?;
         }
+        let pool = build_pool(&config)?;
+        let ids = Arc::new(Self::load_ids(&config));
 
         info!(
             root = %config.root,
@@ -468,15 +1029,62 @@ impl Scanner {
             "Creating scanner with pre-built registry"
         );
 
+        let owners = Self::load_codeowners(&config)?;
+        let ignores = Self::import_ignores(&config);
+        let globs = Arc::new(PathGlobs::new(
+            config.roots(),
+            &config.include_globs,
+            &config.exclude_globs,
+        )?);
+        let project = Self::load_project(&config)?;
+        let matcher = Self::alias_matcher(matcher, project.as_deref());
+        let memory = Arc::new(MemoryGuard::new(config.limits));
+        let cache = Arc::new(Self::new_cache(&config));
+
         Ok(Self {
             config,
             model_path_matcher: matcher,
-            registry,
-            cache: Arc::new(ScanCache::new()),
+            registry: Arc::new(RwLock::new(registry)),
+            builder: None,
+            owners,
+            ignores,
+            globs,
+            project,
+            cache,
             stats: Arc::new(ScanStats::new()),
+            registry_build_time: None,
+            memory,
+            busy: Arc::new(AtomicUsize::new(0)),
+            skipped: Arc::default(),
+            failed: Arc::default(),
+            stamps: Arc::default(),
+            timings: Arc::default(),
+            ids,
+            duplicates: Arc::default(),
+            observer: None,
+            pool,
         })
     }
 
+    /// Reports each file analyzed from now on to `observer`.
+    ///
+    /// Clones made afterwards share the observer; earlier ones don't.
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - Hook pushing timings and counts into a metrics system
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let scanner = Scanner::new(config)?.with_observer(Arc::new(metrics));
+    /// ```
+    #[must_use]
+    pub fn with_observer(mut self, observer: Arc<dyn ScanObserver>) -> Self {
+        self.observer = Some(SharedObserver(observer));
+        self
+    }
+
     /// Performs a full scan of the configured directory.
     ///
     /// This method:
@@ -501,56 +1109,102 @@ impl Scanner {
     /// println!("Scanned {} files", result.stats.total);
     /// ```
     pub fn scan(&self) -> Result<ScanResult, ScanError> {
-        info!(root = %self.config.root, "Starting scan");
-
-        // Reset statistics for fresh scan
-        self.stats.reset();
-        self.cache.clear();
-
-        // Walk directory to collect paths
-        let walker = self.build_walker()?;
-        let paths = walker.collect_paths()?;
+        self.scan_cancellable(&CancellationToken::new())
+    }
 
-        info!(count = paths.len(), "Collected TypeScript files");
+    /// Performs a full scan that stops early once `cancel` is cancelled.
+    ///
+    /// Identical to [`scan()`](Self::scan) otherwise. Analysis threads check
+    /// the token before each file; once it is cancelled, files already being
+    /// analyzed finish and the rest are skipped. The cache and statistics
+    /// then hold only the files analyzed so far, and the scan cache
+    /// directory (if configured) is left as it was.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScanError::Walk`] if directory traversal fails, or
+    /// [`ScanError::Cancelled`] if the scan was cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let cancel = CancellationToken::new();
+    /// // Hand a clone to whatever decides to stop the scan, e.g. a UI
+    /// let stop = cancel.clone();
+    ///
+    /// match scanner.scan_cancellable(&cancel) {
+    ///     Err(ScanError::Cancelled) => println!("Scan stopped"),
+    ///     result => println!("Scanned {} files", result?.stats.total),
+    /// }
+    /// ```
+    pub fn scan_cancellable(&self, cancel: &CancellationToken) -> Result<ScanResult, ScanError> {
+        self.full_scan(cancel, None)
+    }
 
-        // Determine registry reference for filtering
-        let registry_ref = if self.config.use_registry {
-            Some(self.registry.as_ref())
-        } else {
-            None
+    /// Performs a full scan, recording how long each phase took.
+    ///
+    /// Identical to [`scan_cancellable()`](Self::scan_cancellable), but also
+    /// returns a [`ScanProfile`] with the time spent walking, parsing and
+    /// inserting into the cache, the registry build time from construction,
+    /// and the analysis time of every file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScanError::Walk`] if directory traversal fails, or
+    /// [`ScanError::Cancelled`] if the scan was cancelled.
+    pub fn scan_profiled(
+        &self,
+        cancel: &CancellationToken,
+    ) -> Result<(ScanResult, ScanProfile), ScanError> {
+        let mut profile = ScanProfile {
+            registry: self.registry_build_time,
+            ..ScanProfile::default()
         };
+        let result = self.full_scan(cancel, Some(&mut profile))?;
+        Ok((result, profile))
+    }
 
-        // Analyze files in parallel
-        let analyzer = FileAnalyzer::new();
-        let results = analyzer.analyze_files(&paths, &self.model_path_matcher, registry_ref);
+    /// Performs a full scan, filling in `profile` if one is given.
+    fn full_scan(
+        &self,
+        cancel: &CancellationToken,
+        mut profile: Option<&mut ScanProfile>,
+    ) -> Result<ScanResult, ScanError> {
+        info!(root = %self.config.root, roots = self.config.roots().count(), "Starting scan");
 
-        // Process results
-        let mut errors = Vec::new();
+        // Reset statistics for fresh scan, keeping what can be reused
+        let reusable = self.reusable_cache();
+        self.stats.reset();
+        self.cache.clear();
+        self.failed.write().clear();
+        self.timings.clear();
 
-        for (path, result) in results {
-            self.stats.increment_total();
+        // Walk directory to collect paths
+        let started = Instant::now();
+        let WalkOutput { paths, skipped } = self.walk_roots()?;
+        if let Some(profile) = profile.as_deref_mut() {
+            profile.walk = started.elapsed();
+        }
 
-            match result {
-                Ok(file_info) => {
-                    // Update statistics based on status
-                    match file_info.status {
-                        MigrationStatus::Legacy => self.stats.increment_legacy(),
-                        MigrationStatus::Migrated => self.stats.increment_migrated(),
-                        MigrationStatus::Partial => self.stats.increment_partial(),
-                        MigrationStatus::NoModels => self.stats.increment_no_models(),
-                        _ => {} // Handle any future status variants
-                    }
+        info!(count = paths.len(), skipped = skipped.len(), "Collected TypeScript files");
+        self.set_skipped(skipped.clone());
 
-                    debug!(path = %file_info.path, status = ?file_info.status, "Analyzed file");
-                    self.cache.insert(file_info);
-                }
-                Err(e) => {
-                    self.stats.increment_errors();
-                    warn!(path = %path, error = %e, "Failed to analyze file");
-                    errors.push((path, e));
-                }
-            }
+        if cancel.is_cancelled() {
+            return Err(ScanError::Cancelled);
+        }
+
+        self.stats.start(u64::try_from(paths.len()).unwrap_or(u64::MAX));
+        let split = self.split_reusable(&reusable, paths);
+        for file in split.reused {
+            self.record_file(file);
         }
+        let errors = self.analyze_into_cache(&split.stale, profile, cancel);
+        self.stats.finish();
+        if cancel.is_cancelled() {
+            info!(analyzed = self.stats.snapshot().total, "Scan cancelled");
+            return Err(ScanError::Cancelled);
+        }
+        self.save_persisted(&reusable);
 
         let stats = self.stats.snapshot();
         info!(
@@ -562,12 +1216,243 @@ impl Scanner {
             "Scan completed"
         );
 
-        Ok(ScanResult { stats, errors })
+        Ok(ScanResult {
+            stats,
+            errors,
+            skipped,
+        })
     }
 
-    /// Performs a streaming scan, sending results via channel.
+    /// Scans only the given files instead of walking the root.
     ///
-    /// Unlike [`scan()`](Self::scan), this method streams results as they become
+    /// Takes any list of paths, such as the lines of `git diff --name-only`,
+    /// and analyzes them in parallel as [`scan()`](Self::scan) would the
+    /// files it walked: the cache and statistics are replaced, the registry
+    /// still classifies imports, and files unchanged since the last scan, or
+    /// since one saved to the cache directory, are reused without parsing.
+    /// Paths without a TypeScript extension, or left out by the include and
+    /// exclude globs, a `.chmigrateignore` file or the `tsconfig.json`
+    /// project, are skipped, as are
+    /// repeated paths; files that can't be read are reported as errors.
+    /// Useful for checking the files of a commit quickly.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let result = scanner.scan_paths(&[Utf8PathBuf::from("src/app/foo.ts")]);
+    /// println!("Scanned {} files", result.stats.total);
+    ///
+    /// let changed = std::io::stdin().lines().map_while(Result::ok).map(Utf8PathBuf::from);
+    /// scanner.scan_paths(changed);
+    /// ```
+    pub fn scan_paths<P: AsRef<Utf8Path>>(&self, paths: impl IntoIterator<Item = P>) -> ScanResult {
+        let mut listed = FxHashSet::default();
+        let paths: Vec<Utf8PathBuf> = paths
+            .into_iter()
+            .filter_map(|path| {
+                let path = path.as_ref();
+                if !walker::is_typescript_path(path) {
+                    debug!(path = %path, "Skipping non-TypeScript file");
+                    return None;
+                }
+                if self.is_left_out(path) {
+                    debug!(path = %path, "Skipping file left out of scans");
+                    return None;
+                }
+                listed.insert(path.to_owned()).then(|| path.to_owned())
+            })
+            .collect();
+        info!(count = paths.len(), "Starting scan of listed files");
+
+        let reusable = self.reusable_cache();
+        self.stats.reset();
+        self.cache.clear();
+        self.failed.write().clear();
+        self.timings.clear();
+        self.set_skipped(Vec::new());
+
+        self.stats.start(u64::try_from(paths.len()).unwrap_or(u64::MAX));
+        let split = self.split_reusable(&reusable, paths);
+        for file in split.reused {
+            self.record_file(file);
+        }
+        let errors = self.analyze_into_cache(&split.stale, None, &CancellationToken::new());
+        self.stats.finish();
+        let stats = self.stats.snapshot();
+        info!(total = stats.total, errors = stats.errors, "Scan of listed files completed");
+
+        ScanResult {
+            stats,
+            errors,
+            skipped: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if walks leave out `path` for the include and exclude
+    /// globs, a [`.chmigrateignore`](IGNORE_FILE_NAME) file or the
+    /// `tsconfig.json` project.
+    fn is_left_out(&self, path: &Utf8Path) -> bool {
+        !self.globs.allows(path)
+            || self
+                .config
+                .roots()
+                .any(|root| walker::is_ignored_by_file(root, path))
+            || self.project.as_ref().is_some_and(|project| !project.includes(path))
+    }
+
+    /// Re-walks and re-analyzes only the files under a directory.
+    ///
+    /// Cached files under `dir` are dropped (so deleted files disappear) and
+    /// replaced with the files found there now; the rest of the cache is
+    /// kept. A relative `dir` that doesn't already start with a scan root
+    /// is taken relative to the first one, and `..` components are resolved
+    /// before checking that it is under a root. Far cheaper than
+    /// [`scan()`](Self::scan) after switching to a different area of the
+    /// tree.
+    ///
+    /// Statistics are recounted from the cache, and their error count keeps
+    /// the files that failed outside `dir`. The returned errors cover only
+    /// the files that failed in `dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScanError::Config`] if `dir` is not a directory under a
+    /// scan root.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let result = scanner.rescan_prefix(Utf8Path::new("contracts"))?;
+    /// println!("{} files after rescanning contracts", result.stats.total);
+    /// ```
+    pub fn rescan_prefix(&self, dir: &Utf8Path) -> Result<ScanResult, ScanError> {
+        let root = &self.config.root;
+        let dir = tsconfig::clean(dir);
+        let dir = if self.config.roots().any(|root| dir.starts_with(root)) || dir.is_absolute() {
+            dir
+        } else {
+            tsconfig::clean(&root.join(dir))
+        };
+        if !self.config.roots().any(|root| dir.starts_with(root)) {
+            return Err(ScanError::config(format!(
+                "{dir} is outside the scan root {root}"
+            )));
+        }
+        info!(dir = %dir, "Starting scan of directory");
+
+        let WalkOutput { paths, skipped } = self.build_walker_at(&dir)?.walk()?;
+        let found = paths.len();
+        let mut reusable = PersistentCache::in_memory();
+        reusable.remember(self.take_stamped(|path| path.starts_with(&dir)));
+        let removed = self.cache.remove_prefix(&dir);
+        self.failed.write().retain(|path| !path.starts_with(&dir));
+        self.timings.remove_prefix(&dir);
+        let split = self.split_reusable(&reusable, paths);
+        for file in split.reused {
+            self.cache.insert(file);
+        }
+        let errors = self.analyze_into_cache(&split.stale, None, &CancellationToken::new());
+
+        // Skips under the directory are replaced, the rest are kept
+        let mut all_skipped = self.skipped();
+        all_skipped.retain(|skip| !skip.path.starts_with(&dir));
+        all_skipped.extend(skipped.iter().cloned());
+
+        let mut stats = self.cache.stats();
+        stats.errors = u64::try_from(self.failed.read().len()).unwrap_or(u64::MAX);
+        let running = self.stats.snapshot();
+        stats.reused = running.reused;
+        stats.parsed = running.parsed;
+        stats.skipped = SkipCounts::from_skipped(&all_skipped);
+        self.stats.store(&stats);
+        *self.skipped.write() = all_skipped;
+        info!(
+            dir = %dir,
+            files = found,
+            removed,
+            errors = stats.errors,
+            "Scan of directory completed"
+        );
+
+        Ok(ScanResult {
+            stats,
+            errors,
+            skipped,
+        })
+    }
+
+    /// Analyzes `paths` in parallel, caching and counting each result.
+    ///
+    /// Parse, cache-insert and per-file times are added to `profile`, if
+    /// given; files not yet started when `cancel` is cancelled are skipped.
+    /// Returns the files that failed to analyze.
+    fn analyze_into_cache(
+        &self,
+        paths: &[Utf8PathBuf],
+        mut profile: Option<&mut ScanProfile>,
+        cancel: &CancellationToken,
+    ) -> Vec<(Utf8PathBuf, ScanError)> {
+        // Determine registry reference for filtering
+        let registry = self.registry();
+        let registry_ref = if self.config.use_registry {
+            Some(registry.as_ref())
+        } else {
+            None
+        };
+
+        // Analyze files in parallel
+        let started = Instant::now();
+        let analyzer = self.scan_analyzer().with_cancellation(cancel.clone());
+        let results = analyzer.analyze_files_timed(paths, &self.model_path_matcher, registry_ref);
+        let results = self.retry_transient(&analyzer, results, registry_ref);
+        let parse = started.elapsed();
+
+        // Process results
+        let mut errors = Vec::new();
+        let mut cache_insert = Duration::ZERO;
+
+        for (path, result, duration) in results {
+            self.stats.increment_total();
+            if let Some(profile) = profile.as_deref_mut() {
+                profile.record_file(path.clone(), duration);
+            }
+
+            match result {
+                Ok(file_info) => {
+                    // Update statistics based on status
+                    match file_info.status {
+                        MigrationStatus::Legacy => self.stats.increment_legacy(),
+                        MigrationStatus::Migrated => self.stats.increment_migrated(),
+                        MigrationStatus::Partial => self.stats.increment_partial(),
+                        MigrationStatus::NoModels => self.stats.increment_no_models(),
+                        _ => {} // Handle any future status variants
+                    }
+
+                    debug!(path = %file_info.path, status = ?file_info.status, "Analyzed file");
+                    let started = Instant::now();
+                    self.cache.insert(file_info);
+                    cache_insert += started.elapsed();
+                }
+                Err(e) => {
+                    self.stats.increment_errors();
+                    warn!(path = %path, error = %e, "Failed to analyze file");
+                    self.failed.write().insert(path.clone());
+                    errors.push((path, e));
+                }
+            }
+        }
+        if let Some(profile) = profile {
+            profile.parse = parse;
+            profile.cache_insert = cache_insert;
+            profile.finish();
+        }
+
+        errors
+    }
+
+    /// Performs a streaming scan, sending results via channel.
+    ///
+    /// Unlike [`scan()`](Self::scan), this method streams results as they become
     /// available, enabling live UI updates during the scan. Each file result is
     /// sent immediately after analysis completes.
     ///
@@ -592,6 +1477,8 @@ impl Scanner {
     ///
     /// If the receiver is dropped, `blocking_send` will fail and rayon threads
     /// will exit cleanly. The scan will stop early but the method still returns `Ok`.
+    /// To stop analysis itself, use
+    /// [`scan_streaming_cancellable()`](Self::scan_streaming_cancellable).
     ///
     /// # Examples
     ///
@@ -610,20 +1497,86 @@ impl Scanner {
     ///     // Process updates...
     /// }
     /// ```
-    #[allow(clippy::needless_pass_by_value)] // Sender is cloned internally for rayon threads
     pub fn scan_streaming(&self, tx: mpsc::Sender<ScanUpdate>) -> Result<(), ScanError> {
-        info!(root = %self.config.root, "Starting streaming scan");
+        self.scan_streaming_cancellable(tx, &CancellationToken::new())
+    }
+
+    /// Performs a streaming scan that stops early once `cancel` is
+    /// cancelled.
+    ///
+    /// Identical to [`scan_streaming()`](Self::scan_streaming) otherwise.
+    /// Once the token is cancelled, analysis threads skip the remaining
+    /// files, no [`ScanUpdate::Complete`] is sent and the method returns
+    /// [`ScanError::Cancelled`]. The cache and statistics hold the files
+    /// sent so far, and the scan cache directory (if configured) is left as
+    /// it was.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScanError::Walk`] if directory traversal fails, or
+    /// [`ScanError::Cancelled`] if the scan was cancelled.
+    pub fn scan_streaming_cancellable(
+        &self,
+        tx: mpsc::Sender<ScanUpdate>,
+        cancel: &CancellationToken,
+    ) -> Result<(), ScanError> {
+        self.scan_streaming_controlled(tx, &ScanControl::from(cancel.clone()))
+    }
 
-        // Reset statistics for fresh scan
+    /// Performs a streaming scan that can be paused, resumed and cancelled
+    /// through `control`.
+    ///
+    /// Identical to
+    /// [`scan_streaming_cancellable()`](Self::scan_streaming_cancellable)
+    /// otherwise. While `control` is paused, no more files are sent or
+    /// handed to analysis threads; those already being analyzed finish
+    /// first. Resuming continues with the next file, and cancelling stops a
+    /// paused scan as well.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScanError::Walk`] if directory traversal fails, or
+    /// [`ScanError::Cancelled`] if the scan was cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let control = ScanControl::new();
+    /// let handle = control.clone();
+    /// tokio::task::spawn_blocking(move || scanner.scan_streaming_controlled(tx, &handle));
+    ///
+    /// // Hold off parsing while the user types
+    /// control.pause();
+    /// control.resume();
+    /// ```
+    #[allow(clippy::needless_pass_by_value)] // Sender is cloned internally for rayon threads
+    pub fn scan_streaming_controlled(
+        &self,
+        tx: mpsc::Sender<ScanUpdate>,
+        control: &ScanControl,
+    ) -> Result<(), ScanError> {
+        info!(
+            root = %self.config.root,
+            roots = self.config.roots().count(),
+            "Starting streaming scan"
+        );
+
+        // Reset statistics for fresh scan, keeping what can be reused
+        let reusable = self.reusable_cache();
         self.stats.reset();
         self.cache.clear();
+        self.failed.write().clear();
+        self.timings.clear();
 
         // Walk directory to collect paths
-        let walker = self.build_walker()?;
-        let paths = walker.collect_paths()?;
+        let WalkOutput { paths, skipped } = self.walk_roots()?;
         let path_count = paths.len();
 
-        info!(count = path_count, "Collected TypeScript files");
+        info!(count = path_count, skipped = skipped.len(), "Collected TypeScript files");
+        self.set_skipped(skipped.clone());
+        if control.is_cancelled() {
+            return Err(ScanError::Cancelled);
+        }
 
         // Send paths discovered notification
         if tx.blocking_send(ScanUpdate::PathsDiscovered(path_count)).is_err() {
@@ -632,26 +1585,73 @@ impl Scanner {
         }
 
         // Determine registry reference for filtering
+        let registry = self.registry();
         let registry_ref = if self.config.use_registry {
-            Some(self.registry.as_ref())
+            Some(registry.as_ref())
         } else {
             None
         };
 
-        // Analyze files in parallel, streaming results
-        let analyzer = FileAnalyzer::new();
-        let errors = analyzer.analyze_files_streaming(
-            &paths,
-            &self.model_path_matcher,
-            registry_ref,
-            &tx,
-            &self.cache,
-            &self.stats,
-        );
+        // Send files unchanged since the last scan straight from the cache,
+        // likely legacy files first
+        self.stats.start(u64::try_from(path_count).unwrap_or(u64::MAX));
+        let priority = self.scan_priority(&reusable);
+        let split = self.split_reusable(&reusable, paths);
+        let progress = Arc::new(ProgressTicker::new(self.config.progress));
+        let (first, rest) = priority.partition(split.reused, |file| file.path.as_path());
+        for file in first.into_iter().chain(rest) {
+            if !control.wait_until_resumed() {
+                self.stats.finish();
+                return Err(ScanError::Cancelled);
+            }
+            self.record_file(file.clone());
+            let sent = tx.blocking_send(ScanUpdate::FileScanned(Box::new(file))).is_ok()
+                && (!progress.tick()
+                    || tx.blocking_send(ScanUpdate::Progress(self.stats.snapshot())).is_ok());
+            if !sent {
+                self.stats.finish();
+                return Ok(());
+            }
+        }
+
+        // Analyze files in parallel, streaming results; prioritized files
+        // are all analyzed before the rest start. Chunks are handed out one
+        // at a time so a pause holds the scan here, not the analysis threads
+        let analyzer = self
+            .scan_analyzer()
+            .with_cancellation(control.token().clone())
+            .with_progress(progress);
+        let (first, rest) = priority.partition(split.stale, Utf8PathBuf::as_path);
+        let mut errors = Vec::new();
+        for chunk in first.chunks(PAUSE_CHUNK).chain(rest.chunks(PAUSE_CHUNK)) {
+            if !control.wait_until_resumed() {
+                break;
+            }
+            errors.extend(analyzer.analyze_files_streaming(
+                chunk,
+                &self.model_path_matcher,
+                registry_ref,
+                &tx,
+                &self.cache,
+                &self.stats,
+            ));
+        }
+        let errors = self.retry_streamed(&analyzer, errors, registry_ref, &tx);
+        self.failed.write().extend(errors.iter().map(|(path, _)| path.clone()));
+        self.stats.finish();
+        if control.is_cancelled() {
+            info!(analyzed = self.stats.snapshot().total, "Streaming scan cancelled");
+            return Err(ScanError::Cancelled);
+        }
+        self.save_persisted(&reusable);
 
         // Build final result
         let stats = self.stats.snapshot();
-        let result = ScanResult { stats, errors };
+        let result = ScanResult {
+            stats,
+            errors,
+            skipped,
+        };
 
         info!(
             total = result.stats.total,
@@ -673,61 +1673,222 @@ impl Scanner {
     /// This is more efficient than a full scan when only a few files
     /// have changed (e.g., from file watching).
     ///
+    /// Each file's previous contribution to the statistics (its cached
+    /// status, or an error) is taken back before its new one is counted, so
+    /// statistics stay in step with the cache however often a file changes.
+    /// A file that no longer exists is dropped from the cache and counts
+    /// as a success. A cached file whose size and modification time, or
+    /// failing that whose content hash, is unchanged since it was last read
+    /// keeps its analysis without being parsed again.
+    ///
+    /// A file deleted and a new file with the same contents, in this call or
+    /// in calls shortly apart, are taken for a rename: the new file keeps
+    /// the deleted one's [`file_id`](Self::file_id).
+    ///
+    /// Files a walk would leave out for the include and exclude globs, a
+    /// [`.chmigrateignore`](IGNORE_FILE_NAME) file or the `tsconfig.json`
+    /// project aren't analyzed; if they were cached, they are dropped as if
+    /// deleted, and they have no outcome.
+    ///
     /// # Arguments
     ///
     /// * `paths` - The file paths to re-scan
     ///
     /// # Returns
     ///
-    /// A vector of `(path, Result<(), ScanError>)` indicating success/failure
-    /// for each file.
+    /// A [`RescanResult`] with the outcome for each file and the change
+    /// applied to the statistics.
     ///
     /// # Examples
     ///
     /// ```ignore
-    /// let results = scanner.rescan_files(&[
+    /// let result = scanner.rescan_files(&[
     ///     Utf8PathBuf::from("src/foo.ts"),
     ///     Utf8PathBuf::from("src/bar.ts"),
     /// ]);
+    /// println!("{} more legacy files", result.delta.legacy);
     /// ```
-    pub fn rescan_files(&self, paths: &[Utf8PathBuf]) -> Vec<(Utf8PathBuf, Result<(), ScanError>)> {
+    pub fn rescan_files(&self, paths: &[Utf8PathBuf]) -> RescanResult {
         debug!(count = paths.len(), "Re-scanning files");
 
         // Determine registry reference for filtering
+        let registry = self.registry();
         let registry_ref = if self.config.use_registry {
-            Some(self.registry.as_ref())
+            Some(registry.as_ref())
         } else {
             None
         };
 
-        let analyzer = FileAnalyzer::new();
-        let results = analyzer.analyze_files(paths, &self.model_path_matcher, registry_ref);
+        // Files walks leave out are dropped instead
+        let mut delta = StatsDelta::default();
+        let (paths, left_out): (Vec<_>, Vec<_>) =
+            paths.iter().cloned().partition(|path| !self.is_left_out(path));
+        for path in &left_out {
+            debug!(path = %path, "Dropping file left out of scans");
+            if self.failed.write().remove(path) {
+                delta.remove_error();
+            }
+            if let Some(previous) = self.cache.remove(path) {
+                delta.remove(previous.status);
+            }
+            self.timings.remove(path);
+        }
+
+        // Files only touched since they were read keep their analysis
+        let wanted: FxHashSet<&Utf8Path> = paths.iter().map(Utf8PathBuf::as_path).collect();
+        let mut reusable = PersistentCache::in_memory();
+        reusable.remember(self.take_stamped(|path| wanted.contains(path)));
+        let split = self.split_reusable(&reusable, paths);
+
+        let analyzer = self.analyzer();
+        let results = analyzer.analyze_files(&split.stale, &self.model_path_matcher, registry_ref);
 
-        results
+        let mut renamed = false;
+        let mut outcomes: Vec<_> = results
             .into_iter()
-            .map(|(path, result)| {
-                let outcome = match result {
+            .map(|(path, mut result)| {
+                // Take back what the file counted before
+                if self.failed.write().remove(&path) {
+                    delta.remove_error();
+                }
+                let previous = match &mut result {
                     Ok(file_info) => {
-                        // Update cache and statistics
-                        // Note: We don't decrement old status since we'd need to track it
-                        match file_info.status {
-                            MigrationStatus::Legacy => self.stats.increment_legacy(),
-                            MigrationStatus::Migrated => self.stats.increment_migrated(),
-                            MigrationStatus::Partial => self.stats.increment_partial(),
-                            MigrationStatus::NoModels => self.stats.increment_no_models(),
-                            _ => {} // Handle any future status variants
+                        if !self.cache.contains(&path) {
+                            renamed |= self.follow_created(file_info);
                         }
-                        self.cache.insert(file_info);
+                        self.cache.replace(file_info.clone())
+                    }
+                    Err(_) => self.cache.remove(&path),
+                };
+                if let Some(previous) = &previous {
+                    delta.remove(previous.status);
+                }
+
+                let outcome = match result {
+                    Ok(file_info) => {
+                        delta.add(file_info.status);
+                        Ok(())
+                    }
+                    Err(ScanError::Read { error, .. })
+                        if error.kind() == std::io::ErrorKind::NotFound =>
+                    {
+                        debug!(path = %path, "Dropping deleted file");
+                        self.timings.remove(&path);
+                        renamed |= self.follow_deleted(&path, previous.as_ref());
                         Ok(())
                     }
                     Err(e) => {
-                        self.stats.increment_errors();
+                        delta.add_error();
+                        self.failed.write().insert(path.clone());
                         Err(e)
                     }
                 };
                 (path, outcome)
             })
-            .collect()
+            .collect();
+        outcomes.extend(split.reused.into_iter().map(|file| (file.path, Ok(()))));
+        if renamed && self.ids.is_persistent() {
+            if let Err(e) = self.ids.save() {
+                warn!(error = %e, "Failed to save file ids");
+            }
+        }
+
+        self.stats.apply(&delta);
+        RescanResult { outcomes, delta }
+    }
+
+    /// Gives `file`, new to the cache, the id of a file with the same
+    /// contents deleted recently, taking it for a rename. Returns `true` if
+    /// so.
+    fn follow_created(&self, file: &mut FileInfo) -> bool {
+        let Some(id) = self.ids.created(&file.path, file.content_hash) else {
+            return false;
+        };
+        debug!(path = %file.path, "Following renamed file");
+        file.id = id;
+        true
+    }
+
+    /// Forgets the id of the deleted file at `path`, last cached as
+    /// `previous`, unless a file with the same contents was created
+    /// recently: the id moves to that file instead. Returns `true` if it
+    /// moved.
+    fn follow_deleted(&self, path: &Utf8Path, previous: Option<&FileInfo>) -> bool {
+        let Some(previous) = previous else {
+            self.ids.remove(path);
+            return false;
+        };
+        let Some((renamed, id)) = self.ids.deleted(path, previous.content_hash) else {
+            return false;
+        };
+        debug!(path = %renamed, "Following renamed file");
+        if let Some(mut file) = self.cache.get_by_path(&renamed) {
+            file.id = id;
+            self.cache.replace(file);
+        }
+        true
+    }
+
+    /// Analyzes again, per the retry policy, the files in `results` that
+    /// failed with a transient error, replacing their results with those of
+    /// the last attempt.
+    fn retry_transient(
+        &self,
+        analyzer: &FileAnalyzer,
+        mut results: Vec<(Utf8PathBuf, Result<FileInfo, ScanError>, Duration)>,
+        registry: Option<&ModelRegistry>,
+    ) -> Vec<(Utf8PathBuf, Result<FileInfo, ScanError>, Duration)> {
+        let policy = self.config.retry;
+        let mut settled = Vec::new();
+        for attempt in 1..=policy.attempts {
+            let (transient, done): (Vec<_>, Vec<_>) = results
+                .into_iter()
+                .partition(|(_, result, _)| result.as_ref().is_err_and(ScanError::is_transient));
+            settled.extend(done);
+            results = transient;
+            if results.is_empty() || analyzer.is_cancelled() {
+                break;
+            }
+
+            std::thread::sleep(policy.delay);
+            info!(files = results.len(), attempt, "Retrying files that failed transiently");
+            let paths: Vec<_> = results.into_iter().map(|(path, _, _)| path).collect();
+            results = analyzer.analyze_files_timed(&paths, &self.model_path_matcher, registry);
+        }
+        settled.extend(results);
+        settled
+    }
+
+    /// Retries the transient `errors` of a streaming scan, counting and
+    /// sending the files that now succeed, and returns the errors left.
+    fn retry_streamed(
+        &self,
+        analyzer: &FileAnalyzer,
+        errors: Vec<(Utf8PathBuf, ScanError)>,
+        registry: Option<&ModelRegistry>,
+        tx: &mpsc::Sender<ScanUpdate>,
+    ) -> Vec<(Utf8PathBuf, ScanError)> {
+        if !errors.iter().any(|(_, error)| error.is_transient()) {
+            return errors;
+        }
+
+        let failed = errors.into_iter().map(|(path, error)| (path, Err(error), Duration::ZERO));
+        let mut delta = StatsDelta::default();
+        let mut left = Vec::new();
+        for (path, result, _) in self.retry_transient(analyzer, failed.collect(), registry) {
+            match result {
+                Ok(file_info) => {
+                    debug!(path = %path, "Read file on retry");
+                    delta.remove_error();
+                    delta.add(file_info.status);
+                    self.cache.insert(file_info.clone());
+                    let _ = tx.blocking_send(ScanUpdate::FileScanned(Box::new(file_info)));
+                }
+                Err(error) => left.push((path, error)),
+            }
+        }
+        self.stats.apply(&delta);
+        left
     }
 
     /// Returns a snapshot of current statistics.
@@ -743,6 +1904,42 @@ impl Scanner {
         self.stats.snapshot()
     }
 
+    /// Returns up to `n` of the files that took longest to analyze, slowest
+    /// first.
+    ///
+    /// Covers the files analyzed by the last scan and the rescans
+    /// since, each with its latest time spent reading, parsing and
+    /// analyzing it. Files reused from a cache weren't analyzed, so they
+    /// aren't listed. Useful for finding inputs that stall scans without
+    /// running [`scan_profiled`](Self::scan_profiled).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// for file in scanner.slowest_files(5) {
+    ///     println!("{:>8.1?} {}", file.duration, file.path);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn slowest_files(&self, n: usize) -> Vec<FileTiming> {
+        self.timings.slowest(n)
+    }
+
+    /// Returns the paths the last walk left out, with the reason for each.
+    ///
+    /// Answers why a directory is missing from the results; their counts are
+    /// in [`StatsSnapshot::skipped`].
+    #[must_use]
+    pub fn skipped(&self) -> Vec<SkippedPath> {
+        self.skipped.read().clone()
+    }
+
+    /// Replaces the skipped paths and their counts in the statistics.
+    fn set_skipped(&self, skipped: Vec<SkippedPath>) {
+        self.stats.set_skipped(SkipCounts::from_skipped(&skipped));
+        *self.skipped.write() = skipped;
+    }
+
     /// Returns a clone of the file info for the given path, if cached.
     ///
     /// # Arguments
@@ -761,6 +1958,68 @@ impl Scanner {
         self.cache.get_by_path(path)
     }
 
+    /// Returns the id of the file at `path`, if it has been scanned.
+    ///
+    /// Ids stay the same when [`rescan_files`](Self::rescan_files) finds a
+    /// file was renamed and, with a cache directory, across runs, so they
+    /// can key annotations and history that outlive a scan.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let id = scanner.file_id(Utf8Path::new("src/foo.ts"));
+    /// ```
+    #[must_use]
+    pub fn file_id(&self, path: &Utf8Path) -> Option<FileId> {
+        self.ids.get(path)
+    }
+
+    /// Returns the current path of the file with `id`, if any.
+    #[must_use]
+    pub fn file_path(&self, id: FileId) -> Option<Utf8PathBuf> {
+        self.ids.path_of(id)
+    }
+
+    /// Returns the first file analyzed with the same contents as the file at
+    /// `path`, whose analysis it shares.
+    ///
+    /// Copies are only reported while both files are cached with the same
+    /// contents; a copy changed since, or whose original was, isn't one.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// if let Some(original) = scanner.duplicate_of(Utf8Path::new("src/gen/copy.ts")) {
+    ///     println!("Copy of {original}");
+    /// }
+    /// ```
+    #[must_use]
+    pub fn duplicate_of(&self, path: &Utf8Path) -> Option<Utf8PathBuf> {
+        let original = self.duplicates.original_of(path)?;
+        let view = self.cache.view();
+        let same = view.get(path)?.content_hash == view.get(&original)?.content_hash;
+        same.then_some(original)
+    }
+
+    /// Returns every file found to be a copy of another, with that file,
+    /// sorted by path.
+    ///
+    /// See [`duplicate_of`](Self::duplicate_of).
+    #[must_use]
+    pub fn duplicates(&self) -> Vec<(Utf8PathBuf, Utf8PathBuf)> {
+        let mut duplicates = self.duplicates.all();
+        {
+            let view = self.cache.view();
+            duplicates.retain(|(path, original)| {
+                view.get(path).zip(view.get(original)).is_some_and(|(file, original)| {
+                    file.content_hash == original.content_hash
+                })
+            });
+        }
+        duplicates.sort_unstable();
+        duplicates
+    }
+
     /// Returns all files with the specified migration status.
     ///
     /// # Arguments
@@ -810,16 +2069,101 @@ impl Scanner {
         &self.cache
     }
 
+    /// Saves the cached files to `path`, for [`load_cache`](Self::load_cache)
+    /// to restore in a later session.
+    ///
+    /// The file is written next to `path` and renamed into place, creating
+    /// its directory if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be encoded, written or renamed.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// scanner.scan()?;
+    /// scanner.save_cache(Utf8Path::new(".ch-migrate/session.msgpack"))?;
+    /// ```
+    pub fn save_cache(&self, path: &Utf8Path) -> io::Result<()> {
+        let files = self.cache.all_files();
+        let stamps = {
+            let stamps = self.stamps.read();
+            files
+                .iter()
+                .filter(|file| !self.cache.is_compacted(&file.path))
+                .filter_map(|file| Some((file.path.clone(), *stamps.get(&file.path)?)))
+                .collect()
+        };
+        let count = files.len();
+        Snapshot::new(self.cache_fingerprint(), files, stamps).write(path)?;
+        debug!(path = %path, files = count, "Saved scan cache");
+        Ok(())
+    }
+
+    /// Returns the file in the cache directory where a UI keeps its last
+    /// session's results for [`load_cache`](Self::load_cache), or `None`
+    /// without a cache directory.
+    ///
+    /// Each set of scan roots has its own file, as with the on-disk cache.
+    #[must_use]
+    pub fn saved_cache_path(&self) -> Option<Utf8PathBuf> {
+        let dir = self.config.cache_dir.as_ref()?;
+        Some(roots_file(dir, self.config.roots(), "session"))
+    }
+
+    /// Replaces the cached files with those saved to `path` by
+    /// [`save_cache`](Self::save_cache), returning how many were loaded.
+    ///
+    /// The files and their statistics are available at once, so a UI can
+    /// show the last session's results while a fresh scan runs. If they
+    /// were analyzed with the same settings, that scan reuses the files
+    /// unchanged since, as it would the results of a previous scan.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or decoded, or was saved
+    /// by an incompatible version.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let restored = scanner.load_cache(Utf8Path::new(".ch-migrate/session.msgpack"))?;
+    /// println!("Showing {restored} files from the last session");
+    /// ```
+    pub fn load_cache(&self, path: &Utf8Path) -> io::Result<usize> {
+        let snapshot = Snapshot::read(path)?;
+        let reusable = snapshot.fingerprint == self.cache_fingerprint();
+        let count = snapshot.files.len();
+
+        self.cache.clear();
+        self.failed.write().clear();
+        for mut file in snapshot.files {
+            file.id = self.ids.assign(&file.path);
+            self.cache.insert(file);
+        }
+        *self.stamps.write() = if reusable {
+            snapshot.stamps.into_iter().collect()
+        } else {
+            FxHashMap::default()
+        };
+        self.stats.store(&self.cache.stats());
+        debug!(path = %path, files = count, reusable, "Loaded saved scan cache");
+        Ok(count)
+    }
+
     /// Returns the scanner configuration.
     #[must_use]
     pub const fn config(&self) -> &ScanConfig {
         &self.config
     }
 
-    /// Returns a reference to the model registry.
+    /// Returns the model registry.
     ///
     /// The registry contains all known model exports from the shared directories.
     /// Use this for model lookup or to display registry statistics in the TUI.
+    /// It is a snapshot: a later [`update_registry`](Self::update_registry)
+    /// replaces the scanner's registry without changing this one.
     ///
     /// # Examples
     ///
@@ -834,26 +2178,403 @@ impl Scanner {
     /// }
     /// ```
     #[must_use]
-    pub fn registry(&self) -> &ModelRegistry {
-        &self.registry
+    pub fn registry(&self) -> Arc<ModelRegistry> {
+        Arc::clone(&self.registry.read())
     }
 
     /// Returns a clone of the Arc-wrapped registry for sharing across threads.
     #[must_use]
     pub fn registry_arc(&self) -> Arc<ModelRegistry> {
-        Arc::clone(&self.registry)
+        self.registry()
     }
 
-    /// Builds a file walker with the current configuration.
-    fn build_walker(&self) -> Result<FileWalker, ScanError> {
-        let mut walker = FileWalker::new(&self.config.root)?;
+    /// Re-parses the changed shared files among `paths` and swaps in the
+    /// patched registry, instead of building a new scanner.
+    ///
+    /// The update is applied to a copy of the registry (see
+    /// [`RegistryBuilder::update`]), so scans already running keep the one
+    /// they started with, and a failed update changes nothing. Files
+    /// analyzed so far stay cached, but the next full scan analyzes every
+    /// file again, as import classification may have changed.
+    ///
+    /// Returns the number of shared files among `paths`; `0` if the scanner
+    /// didn't build its registry itself.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScanError::Config`] if a configured rename now targets a
+    /// model that is not in `shared_2023/`.
+    pub fn update_registry(&self, paths: &[Utf8PathBuf]) -> Result<usize, ScanError> {
+        let Some(builder) = &self.builder else {
+            return Ok(0);
+        };
+        let mut registry = ModelRegistry::clone(&self.registry());
+        let updated = in_pool(self.pool.as_deref(), || builder.update(&mut registry, paths))?;
+        if updated > 0 {
+            *self.registry.write() = Arc::new(registry);
+            self.stamps.write().clear();
+        }
+        Ok(updated)
+    }
+
+    /// Updates the registry from the changed shared files among `paths`, as
+    /// [`update_registry`](Self::update_registry) does, then re-scans the
+    /// cached files importing a model those files define or defined before,
+    /// or a name a barrel file now re-exports from another model.
+    ///
+    /// Those files' statuses may change with the registry, e.g. once a model
+    /// they import shows up in `shared_2023/`, so watch mode can follow
+    /// edits to the shared directories without a full scan. Compacted files,
+    /// whose imports were dropped, are left for the next full scan.
+    ///
+    /// Returns `None` if no shared file was among `paths`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScanError::Config`] if a configured rename now targets a
+    /// model that is not in `shared_2023/`.
+    pub fn refresh_registry(
+        &self,
+        paths: &[Utf8PathBuf],
+    ) -> Result<Option<RescanResult>, ScanError> {
+        let before = self.registry();
+        if self.update_registry(paths)? == 0 {
+            return Ok(None);
+        }
+        let after = self.registry();
+
+        let changed: FxHashSet<&Utf8Path> = paths.iter().map(Utf8PathBuf::as_path).collect();
+        let mut names = FxHashSet::default();
+        for model in before.iter_all_models().chain(after.iter_all_models()) {
+            if changed.contains(model.definition_path.as_path()) {
+                names.insert(model.name.as_str());
+                names.extend(model.exports.iter().map(String::as_str));
+            }
+        }
+        // Names a barrel file now attributes to another model, or no longer re-exports
+        for (barrel, dir, name) in before.barrel_exports().chain(after.barrel_exports()) {
+            let was = before.barrel_model(barrel, dir, name).map(|m| (m.source, &m.name));
+            let is = after.barrel_model(barrel, dir, name).map(|m| (m.source, &m.name));
+            if was != is {
+                names.insert(name);
+            }
+        }
+        let mut consumers: Vec<Utf8PathBuf> = {
+            let view = self.cache.view();
+            let importers = names.iter().flat_map(|name| view.importing(name));
+            importers.map(|file| file.path.clone()).collect()
+        };
+        consumers.sort_unstable();
+        consumers.dedup();
+
+        info!(models = names.len(), files = consumers.len(), "Re-scanning model consumers");
+        Ok(Some(self.rescan_files(&consumers)))
+    }
+
+    /// Returns `true` once a [`ResourceLimits`] limit has been exceeded.
+    ///
+    /// The scanner then stays in low-memory mode for the rest of its
+    /// lifetime, including its clones.
+    #[must_use]
+    pub fn is_low_memory(&self) -> bool {
+        self.memory.is_low_memory()
+    }
+
+    /// Returns the channel buffer size to use with
+    /// [`scan_streaming`](Self::scan_streaming).
+    ///
+    /// Smaller in low-memory mode, so fewer scanned files wait in the
+    /// channel when the receiver falls behind.
+    #[must_use]
+    pub fn channel_capacity(&self) -> usize {
+        if self.is_low_memory() {
+            LOW_MEMORY_CHANNEL_CAPACITY
+        } else {
+            STREAM_CHANNEL_CAPACITY
+        }
+    }
+
+    /// Returns the number of threads available to analyze files in parallel.
+    #[must_use]
+    pub fn thread_pool_size(&self) -> usize {
+        self.pool
+            .as_ref()
+            .map_or_else(rayon::current_num_threads, |pool| pool.current_num_threads())
+    }
+
+    /// Returns the number of threads analyzing a file right now, across
+    /// this scanner and its clones.
+    #[must_use]
+    pub fn busy_workers(&self) -> usize {
+        self.busy.load(Ordering::Relaxed)
+    }
+
+    /// Returns the `CODEOWNERS` rules used for team attribution, if configured.
+    #[must_use]
+    pub fn codeowners(&self) -> Option<&CodeOwners> {
+        self.owners.as_deref()
+    }
+
+    /// Loads the configured `CODEOWNERS` file, if any.
+    fn load_codeowners(config: &ScanConfig) -> Result<Option<Arc<CodeOwners>>, ScanError> {
+        let Some(path) = &config.codeowners_path else {
+            return Ok(None);
+        };
+
+        let owners = CodeOwners::load(path)?;
+        info!(path = %path, rules = owners.rule_count(), "Loaded CODEOWNERS");
+        Ok(Some(Arc::new(owners)))
+    }
+
+    /// Loads the `tsconfig.json` project, if configured.
+    fn load_project(config: &ScanConfig) -> Result<Option<Arc<TsProject>>, ScanError> {
+        let Some(path) = &config.tsconfig else {
+            return Ok(None);
+        };
+
+        let project = TsProject::load(path)?;
+        info!(path = %path, "Loaded tsconfig project");
+        Ok(Some(Arc::new(project)))
+    }
+
+    /// Adds the `paths` aliases of the `tsconfig.json` project, if
+    /// configured, to `matcher`.
+    fn alias_matcher(matcher: ModelPathMatcher, project: Option<&TsProject>) -> ModelPathMatcher {
+        let aliases = project.map_or(&[][..], TsProject::path_aliases);
+        if !aliases.is_empty() {
+            info!(count = aliases.len(), "Resolving tsconfig path aliases");
+        }
+        aliases
+            .iter()
+            .fold(matcher, |matcher, (pattern, targets)| matcher.with_path_alias(pattern, targets))
+    }
+
+    /// Loads the file ids saved in the cache directory, if configured.
+    fn load_ids(config: &ScanConfig) -> FileIds {
+        config
+            .cache_dir
+            .as_ref()
+            .map_or_else(FileIds::default, |dir| FileIds::load(dir, config.roots()))
+    }
+
+    /// Builds the configured import ignore rules, if any.
+    fn import_ignores(config: &ScanConfig) -> Option<Arc<ImportIgnores>> {
+        let ignores = ImportIgnores::new(&config.ignore_imports);
+        if ignores.is_empty() {
+            return None;
+        }
+
+        info!(rules = ignores.rule_count(), "Ignoring configured imports");
+        Some(Arc::new(ignores))
+    }
+
+    /// Loads the on-disk cache, if a cache directory is configured.
+    fn persistent_cache(&self) -> Option<PersistentCache> {
+        let dir = self.config.cache_dir.as_ref()?;
+        let cache = PersistentCache::load(dir, self.config.roots(), self.cache_fingerprint());
+        debug!(dir = %dir, files = cache.len(), "Loaded scan cache");
+        Some(cache)
+    }
+
+    /// Creates the cache for `config`, with its detail limit if any.
+    fn new_cache(config: &ScanConfig) -> ScanCache {
+        config
+            .max_detailed_files
+            .map_or_else(ScanCache::new, ScanCache::with_max_detailed_files)
+    }
+
+    /// Collects the results a full scan may reuse: the on-disk cache, if
+    /// configured, updated with the files still cached from the last scan.
+    fn reusable_cache(&self) -> PersistentCache {
+        let mut reusable = self.persistent_cache().unwrap_or_else(PersistentCache::in_memory);
+        reusable.remember(self.take_stamped(|_| true));
+        reusable
+    }
+
+    /// Takes the stamps of the files matching `within`, returning the
+    /// cached files among them.
+    fn take_stamped(&self, within: impl Fn(&Utf8Path) -> bool) -> Vec<(FileStamp, FileInfo)> {
+        let mut taken = Vec::new();
+        self.stamps.write().retain(|path, stamp| {
+            let keep = !within(path);
+            if !keep {
+                taken.push((*stamp, path.clone()));
+            }
+            keep
+        });
+        // Compacted files lack their imports, so they are analyzed again
+        taken
+            .into_iter()
+            .filter(|(_, path)| !self.cache.is_compacted(path))
+            .filter_map(|(stamp, path)| Some((stamp, self.cache.get(&path)?)))
+            .collect()
+    }
+
+    /// Returns the order of a streaming scan: the priority directories, then
+    /// directories ranked by the files needing migration in `previous` if
+    /// configured.
+    fn scan_priority(&self, previous: &PersistentCache) -> ScanPriority {
+        let dirs = self.config.priority_dirs.iter().map(|dir| self.config.root.join(dir)).collect();
+        let legacy_first = self.config.legacy_first;
+        ScanPriority::new(dirs, previous.files().filter(|_| legacy_first))
+    }
+
+    /// Splits `paths` into files reused from `reusable` and files to analyze,
+    /// counting the reused ones and keeping the stamps of all of them.
+    fn split_reusable(&self, reusable: &PersistentCache, paths: Vec<Utf8PathBuf>) -> CacheSplit {
+        let mut split = in_pool(self.pool.as_deref(), || CacheSplit::new(reusable, paths));
+        for file in &mut split.reused {
+            file.id = self.ids.assign(&file.path);
+        }
+        self.stats.add_reused(u64::try_from(split.reused.len()).unwrap_or(u64::MAX));
+        self.stamps.write().extend(std::mem::take(&mut split.stamps));
+        split
+    }
+
+    /// Forgets the ids of files the scan didn't find, then replaces the
+    /// on-disk cache, if `persisted` has one, with the cached files that
+    /// have stamps, and saves the ids.
+    ///
+    /// Failing to save only costs the next scan time, so it's logged.
+    fn save_persisted(&self, persisted: &PersistentCache) {
+        {
+            let (view, failed) = (self.cache.view(), self.failed.read());
+            self.ids.retain(|path| view.get(path).is_some() || failed.contains(path));
+            self.duplicates.retain(|path| view.get(path).is_some());
+        }
+        if !persisted.is_persistent() {
+            return;
+        }
+        if let Err(e) = self.ids.save() {
+            warn!(error = %e, "Failed to save file ids");
+        }
+        let stamps = self.stamps.read();
+        let files = self
+            .cache
+            .all_files()
+            .into_iter()
+            .filter(|file| !self.cache.is_compacted(&file.path))
+            .filter_map(|file| Some((*stamps.get(&file.path)?, file)))
+            .collect();
+        if let Err(e) = persisted.save(files) {
+            warn!(error = %e, "Failed to save scan cache");
+        }
+    }
+
+    /// Fingerprints the settings that analysis results depend on besides
+    /// file contents, so an on-disk cache is only reused with the same ones.
+    fn cache_fingerprint(&self) -> u64 {
+        let mut hasher = FxHasher::default();
+        env!("CARGO_PKG_VERSION").hash(&mut hasher);
+        format!("{:?}", self.model_path_matcher).hash(&mut hasher);
+        format!("{:?}", self.owners).hash(&mut hasher);
+        format!("{:?}", self.ignores).hash(&mut hasher);
+        self.config.max_file_size.hash(&mut hasher);
+        self.config.skip_binary.hash(&mut hasher);
+        self.config.use_registry.hash(&mut hasher);
+        if self.config.use_registry {
+            let registry = self.registry();
+            for models in [
+                registry.iter_legacy_models().collect::<Vec<_>>(),
+                registry.iter_modern_models().collect(),
+            ] {
+                let mut exports: Vec<&str> = models
+                    .iter()
+                    .flat_map(|model| model.exports.iter().map(String::as_str))
+                    .collect();
+                exports.sort_unstable();
+                exports.hash(&mut hasher);
+            }
+        }
+        hasher.finish()
+    }
+
+    /// Caches an analyzed file and counts it in the statistics.
+    fn record_file(&self, file: FileInfo) {
+        self.stats.increment_total();
+        match file.status {
+            MigrationStatus::Legacy => self.stats.increment_legacy(),
+            MigrationStatus::Migrated => self.stats.increment_migrated(),
+            MigrationStatus::Partial => self.stats.increment_partial(),
+            MigrationStatus::NoModels => self.stats.increment_no_models(),
+            _ => {} // Handle any future status variants
+        }
+        self.cache.insert(file);
+    }
+
+    /// Creates a file analyzer for scans, which maps large files if
+    /// configured.
+    ///
+    /// Rescans of changed files use [`Scanner::analyzer`] instead: a file
+    /// that was just saved may be written again while it's mapped.
+    fn scan_analyzer(&self) -> FileAnalyzer {
+        let analyzer = self.analyzer();
+        match self.config.mmap_threshold {
+            Some(bytes) => analyzer.with_mmap_threshold(bytes),
+            None => analyzer,
+        }
+    }
+
+    /// Creates a file analyzer with the current configuration, reading
+    /// every file.
+    fn analyzer(&self) -> FileAnalyzer {
+        let mut analyzer = FileAnalyzer::new();
+        if let Some(owners) = &self.owners {
+            analyzer = analyzer.with_codeowners(Arc::clone(owners));
+        }
+        if let Some(ignores) = &self.ignores {
+            analyzer = analyzer.with_import_ignores(Arc::clone(ignores));
+        }
+        if !self.config.limits.is_empty() {
+            analyzer = analyzer.with_memory_guard(Arc::clone(&self.memory));
+        }
+        if let Some(bytes) = self.config.max_file_size {
+            analyzer = analyzer.with_max_file_size(bytes);
+        }
+        if let Some(pool) = &self.pool {
+            analyzer = analyzer.with_thread_pool(Arc::clone(pool));
+        }
+        if let Some(observer) = &self.observer {
+            analyzer = analyzer.with_observer(observer.clone());
+        }
+        analyzer
+            .with_file_ids(Arc::clone(&self.ids))
+            .with_duplicates(Arc::clone(&self.duplicates))
+            .with_skip_binary(self.config.skip_binary)
+            .with_busy_counter(Arc::clone(&self.busy))
+            .with_timings(Arc::clone(&self.timings))
+            .with_parse_stats(Arc::clone(&self.stats))
+    }
+
+    /// Walks every scan root, merging the files found and paths skipped.
+    ///
+    /// A path under several roots, such as nested ones, is listed once.
+    fn walk_roots(&self) -> Result<WalkOutput, ScanError> {
+        let mut output = WalkOutput::default();
+        let mut seen = FxHashSet::default();
+        for root in self.config.roots() {
+            let walked = self.build_walker_at(root)?.walk()?;
+            output.paths.extend(walked.paths.into_iter().filter(|path| seen.insert(path.clone())));
+            output
+                .skipped
+                .extend(walked.skipped.into_iter().filter(|skip| seen.insert(skip.path.clone())));
+        }
+        Ok(output)
+    }
+
+    /// Builds a walker for `dir` with the configured walk options.
+    fn build_walker_at(&self, dir: &Utf8Path) -> Result<FileWalker, ScanError> {
+        let mut walker = FileWalker::new(dir)?;
 
         if !self.config.skip_dirs.is_empty() {
             let skip_dirs: Vec<&str> = self.config.skip_dirs.iter().map(String::as_str).collect();
             walker = walker.with_skip_dirs(&skip_dirs);
         }
 
-        walker = walker.with_follow_links(self.config.follow_links);
+        walker = walker
+            .with_follow_links(self.config.follow_links)
+            .with_path_globs(Arc::clone(&self.globs))
+            .with_project(self.project.clone());
 
         Ok(walker)
     }
@@ -863,6 +2584,24 @@ impl Scanner {
 mod tests {
     use super::*;
 
+    /// Creates a temporary directory, returning it with its path.
+    ///
+    /// Fails the test if the directory can't be created or its path isn't
+    /// UTF-8, rather than letting it pass without running.
+    #[allow(clippy::expect_used)] // a test can't run without its fixture
+    pub(crate) fn temp_root() -> (tempfile::TempDir, Utf8PathBuf) {
+        let dir = tempfile::TempDir::new().expect("failed to create a temporary directory");
+        let root = Utf8PathBuf::from_path_buf(dir.path().to_path_buf())
+            .expect("temporary directory path is not UTF-8");
+        (dir, root)
+    }
+
+    /// Creates a scanner, failing the test if the configuration is rejected.
+    #[allow(clippy::expect_used)] // a test can't run without its fixture
+    fn new_scanner(config: ScanConfig) -> Scanner {
+        Scanner::new(config).expect("failed to create the scanner")
+    }
+
     #[test]
     fn test_scan_config_new() {
         let config = ScanConfig::new(Utf8Path::new("./src"));
@@ -916,6 +2655,890 @@ mod tests {
         assert!(!config.use_registry);
     }
 
+    #[test]
+    fn test_scan_paths_only_listed_files() {
+        let (_dir, root) = crate::tests::temp_root();
+        let legacy = "import { Foo } from '../shared/models/foo';\nconst f: Foo = {};\n";
+        assert!(std::fs::write(root.join("a.ts"), legacy).is_ok());
+        assert!(std::fs::write(root.join("b.ts"), legacy).is_ok());
+        assert!(std::fs::write(root.join("notes.md"), "# notes").is_ok());
+
+        let scanner = new_scanner(ScanConfig::new(&root));
+        let result = scanner.scan_paths(&[
+            root.join("a.ts"),
+            root.join("notes.md"),
+            root.join("missing.ts"),
+        ]);
+
+        assert_eq!(result.stats.total, 2);
+        assert_eq!(result.stats.legacy, 1);
+        assert_eq!(result.stats.errors, 1);
+        assert_eq!(result.errors.len(), 1);
+        assert!(scanner.get_file(&root.join("a.ts")).is_some());
+        assert!(scanner.get_file(&root.join("b.ts")).is_none());
+        assert_eq!(scanner.busy_workers(), 0, "workers are idle once the scan returns");
+        assert!(scanner.thread_pool_size() >= 1);
+
+        // Any iterator of paths; repeats are scanned once and unchanged files reused
+        let listed = ["a.ts", "b.ts", "a.ts"].into_iter().map(|file| root.join(file));
+        let result = scanner.scan_paths(listed);
+        assert_eq!((result.stats.total, result.stats.legacy), (2, 2));
+        assert_eq!(result.stats.reused, 1);
+
+        // Files the globs leave out are skipped
+        let config = ScanConfig::new(&root).with_exclude_globs(&["b.ts"]);
+        let scanner = new_scanner(config);
+        let result = scanner.scan_paths(vec![root.join("a.ts"), root.join("b.ts")]);
+        assert_eq!(result.stats.total, 1);
+    }
+
+    #[test]
+    fn test_rescan_files_drops_files_left_out() {
+        let (_dir, root) = crate::tests::temp_root();
+        let legacy = "import { Foo } from '../shared/models/foo';\nconst f: Foo = {};\n";
+        for file in ["a.ts", "gen.ts", "b.spec.ts"] {
+            assert!(std::fs::write(root.join(file), legacy).is_ok());
+        }
+
+        let config = ScanConfig::new(&root).with_exclude_globs(&["*.spec.ts"]);
+        let scanner = new_scanner(config);
+        assert!(scanner.scan().is_ok());
+        assert_eq!(scanner.stats().total, 2);
+
+        // An ignore file written after the scan drops the cached file
+        assert!(std::fs::write(root.join(IGNORE_FILE_NAME), "gen.ts\n").is_ok());
+        let paths = ["a.ts", "gen.ts", "b.spec.ts"].map(|file| root.join(file));
+        let result = scanner.rescan_files(&paths);
+        let rescanned: Vec<_> = result.outcomes.iter().map(|(path, _)| path.clone()).collect();
+        assert_eq!(rescanned, [root.join("a.ts")]);
+        assert_eq!(scanner.stats().total, 1);
+        assert!(scanner.get_file(&root.join("gen.ts")).is_none());
+        assert!(scanner.get_file(&root.join("b.spec.ts")).is_none());
+        assert!(scanner.get_file(&root.join("a.ts")).is_some());
+    }
+
+    #[test]
+    fn test_rescan_prefix_replaces_subtree() {
+        let (_dir, root) = crate::tests::temp_root();
+        let legacy = "import { Foo } from '../shared/models/foo';\nconst f: Foo = {};\n";
+        assert!(std::fs::create_dir_all(root.join("contracts")).is_ok());
+        assert!(std::fs::create_dir_all(root.join("jobs")).is_ok());
+        assert!(std::fs::write(root.join("contracts/a.ts"), legacy).is_ok());
+        assert!(std::fs::write(root.join("contracts/b.ts"), legacy).is_ok());
+        assert!(std::fs::write(root.join("jobs/c.ts"), legacy).is_ok());
+        assert!(std::fs::write(root.join("jobs/bad.ts"), [0xff, 0xfe]).is_ok());
+
+        let scanner = new_scanner(ScanConfig::new(&root));
+        assert!(scanner.scan().is_ok());
+        assert_eq!(scanner.stats().total, 4);
+        assert_eq!(scanner.stats().errors, 1);
+
+        assert!(std::fs::remove_file(root.join("contracts/b.ts")).is_ok());
+        assert!(std::fs::write(root.join("contracts/d.ts"), "export const x = 1;\n").is_ok());
+        assert!(std::fs::write(root.join("jobs/e.ts"), legacy).is_ok());
+
+        let result = scanner.rescan_prefix(Utf8Path::new("contracts"));
+        assert!(result.is_ok());
+        let Ok(result) = result else {
+            return;
+        };
+        assert_eq!(result.stats.total, 3, "a.ts, d.ts and the untouched jobs/c.ts");
+        assert_eq!(result.stats.legacy, 2);
+        assert_eq!(result.stats.errors, 1, "jobs/bad.ts still fails");
+        assert!(result.errors.is_empty());
+        assert_eq!(scanner.stats(), result.stats);
+        assert!(scanner.get_file(&root.join("contracts/b.ts")).is_none());
+        assert!(scanner.get_file(&root.join("contracts/d.ts")).is_some());
+        assert!(scanner.get_file(&root.join("jobs/e.ts")).is_none(), "outside the prefix");
+
+        assert!(scanner.rescan_prefix(Utf8Path::new("/elsewhere")).is_err());
+        assert!(scanner.rescan_prefix(Utf8Path::new("contracts/../../elsewhere")).is_err());
+        assert!(scanner.rescan_prefix(Utf8Path::new("contracts/../jobs")).is_ok());
+        assert!(scanner.get_file(&root.join("jobs/e.ts")).is_some());
+    }
+
+    #[test]
+    fn test_scan_multiple_roots() {
+        let (_dir, root) = crate::tests::temp_root();
+        let legacy = "import { Foo } from '../shared/models/foo';\nconst f: Foo = {};\n";
+        assert!(std::fs::create_dir_all(root.join("app/jobs")).is_ok());
+        assert!(std::fs::create_dir_all(root.join("admin")).is_ok());
+        assert!(std::fs::write(root.join("app/a.ts"), legacy).is_ok());
+        assert!(std::fs::write(root.join("app/jobs/b.ts"), legacy).is_ok());
+        assert!(std::fs::write(root.join("admin/c.ts"), "export const c = 1;\n").is_ok());
+
+        // The nested app/jobs root adds nothing new
+        let config = ScanConfig::new(&root.join("app"))
+            .with_additional_roots(&[root.join("admin"), root.join("app/jobs")]);
+        assert_eq!(config.roots().count(), 3);
+        let scanner = new_scanner(config);
+        let result = scanner.scan();
+        assert!(result.is_ok());
+        let Ok(result) = result else {
+            return;
+        };
+        assert_eq!(result.stats.total, 3);
+        assert_eq!(result.stats.legacy, 2);
+        assert!(scanner.get_file(&root.join("admin/c.ts")).is_some());
+
+        assert!(std::fs::write(root.join("admin/d.ts"), legacy).is_ok());
+        let result = scanner.rescan_prefix(&root.join("admin"));
+        assert!(result.is_ok_and(|result| result.stats.total == 4));
+
+        let missing = ScanConfig::new(&root).with_additional_roots(&[root.join("missing")]);
+        assert!(matches!(Scanner::new(missing), Err(ScanError::Config(_))));
+    }
+
+    #[test]
+    fn test_scan_with_globs() {
+        let (_dir, root) = crate::tests::temp_root();
+        assert!(std::fs::create_dir_all(root.join("contracts")).is_ok());
+        assert!(std::fs::create_dir_all(root.join("jobs")).is_ok());
+        for file in ["contracts/a.ts", "contracts/a.spec.ts", "jobs/c.ts", "d.ts"] {
+            assert!(std::fs::write(root.join(file), "export const x = 1;\n").is_ok());
+        }
+
+        let config = ScanConfig::new(&root)
+            .with_include_globs(&["contracts/**", "jobs/**"])
+            .with_exclude_globs(&["contracts/*.spec.ts"]);
+        let scanner = new_scanner(config);
+        assert!(scanner.scan().is_ok());
+        assert_eq!(scanner.stats().total, 2);
+        assert!(scanner.get_file(&root.join("contracts/a.spec.ts")).is_none());
+        assert!(scanner.get_file(&root.join("d.ts")).is_none());
+
+        // Patterns stay relative to the root when walking a subdirectory
+        let result = scanner.rescan_prefix(Utf8Path::new("contracts"));
+        assert!(result.is_ok_and(|result| result.stats.total == 2));
+
+        let invalid = ScanConfig::new(&root).with_exclude_globs(&["[a"]);
+        assert!(matches!(Scanner::new(invalid), Err(ScanError::Config(_))));
+
+        // A tsconfig project narrows the walk further
+        let tsconfig = root.join("tsconfig.json");
+        assert!(std::fs::write(&tsconfig, r#"{"include": ["jobs", "d.ts"]}"#).is_ok());
+        let config = ScanConfig::new(&root).with_tsconfig(&tsconfig);
+        let scanner = new_scanner(config);
+        assert!(scanner.scan().is_ok());
+        assert_eq!(scanner.stats().total, 2);
+        assert!(scanner.get_file(&root.join("contracts/a.ts")).is_none());
+
+        // Imports through its path aliases are model imports
+        let aliased = "import { Foo } from '@lib/models/foo';\nconst f: Foo = {};\n";
+        assert!(std::fs::write(root.join("jobs/aliased.ts"), aliased).is_ok());
+        let paths = r#""compilerOptions": {"paths": {"@lib/*": ["shared/*"]}}"#;
+        let contents = format!(r#"{{"include": ["jobs"], {paths}}}"#);
+        assert!(std::fs::write(&tsconfig, contents).is_ok());
+        let scanner = new_scanner(ScanConfig::new(&root).with_tsconfig(&tsconfig));
+        assert!(scanner.scan().is_ok());
+        let file = scanner.get_file(&root.join("jobs/aliased.ts"));
+        assert_eq!(file.map(|file| file.status), Some(MigrationStatus::Legacy));
+
+        let missing = ScanConfig::new(&root).with_tsconfig(&root.join("missing.json"));
+        assert!(matches!(Scanner::new(missing), Err(ScanError::Config(_))));
+    }
+
+    #[test]
+    fn test_scan_skips_large_and_binary_files() {
+        let (_dir, root) = crate::tests::temp_root();
+        assert!(std::fs::write(root.join("a.ts"), "export const a = 1;\n").is_ok());
+        assert!(std::fs::write(root.join("big.ts"), "// padding\n".repeat(100)).is_ok());
+        assert!(std::fs::write(root.join("blob.ts"), b"\x00\x01binary").is_ok());
+
+        let config = ScanConfig::new(&root).with_max_file_size(512).with_skip_binary(true);
+        let scanner = new_scanner(config);
+        let result = scanner.scan();
+        assert!(result.is_ok());
+        let Ok(result) = result else {
+            return;
+        };
+        assert_eq!(result.stats.no_models, 1);
+        assert_eq!(result.stats.errors, 2);
+        let mut skipped: Vec<_> = result
+            .errors
+            .iter()
+            .map(|(path, error)| (path.file_name(), error.category()))
+            .collect();
+        skipped.sort_unstable_by_key(|(name, _)| *name);
+        assert_eq!(
+            skipped,
+            [
+                (Some("big.ts"), ErrorCategory::Skipped),
+                (Some("blob.ts"), ErrorCategory::Skipped),
+            ]
+        );
+
+        // Only the start is sniffed, and the rest of the file is still read
+        let (_dir, root) = crate::tests::temp_root();
+        let padding = "// padding\n".repeat(1000);
+        let long = format!("{padding}import {{ Foo }} from '../shared/models/foo';\n");
+        assert!(std::fs::write(root.join("long.ts"), long).is_ok());
+        let scanner = new_scanner(ScanConfig::new(&root).with_skip_binary(true));
+        assert!(scanner.scan().is_ok());
+        let file = scanner.get_file(&root.join("long.ts"));
+        assert_eq!(file.map(|file| file.status), Some(MigrationStatus::Legacy));
+    }
+
+    #[test]
+    fn test_scan_maps_files_over_threshold() {
+        let (_dir, root) = crate::tests::temp_root();
+        let legacy = "import { Foo } from '../shared/models/foo';\nconst f: Foo = {};\n";
+        assert!(std::fs::write(root.join("small.ts"), "export const a = 1;\n").is_ok());
+        assert!(std::fs::write(root.join("large.ts"), legacy).is_ok());
+        assert!(std::fs::write(root.join("empty.ts"), "").is_ok());
+        assert!(std::fs::write(root.join("blob.ts"), b"\x00\x01binary padding").is_ok());
+        assert!(std::fs::write(root.join("latin1.ts"), b"// caf\xe9 padding padding\n").is_ok());
+
+        let config = ScanConfig::new(&root).with_mmap_threshold(24).with_skip_binary(true);
+        let scanner = new_scanner(config);
+        let result = scanner.scan();
+        assert!(result.is_ok());
+        let Ok(result) = result else {
+            return;
+        };
+        assert_eq!((result.stats.legacy, result.stats.no_models), (1, 2));
+        let mut errors: Vec<_> = result
+            .errors
+            .iter()
+            .map(|(path, error)| (path.file_name(), error.category()))
+            .collect();
+        errors.sort_unstable_by_key(|(name, _)| *name);
+        assert_eq!(
+            errors,
+            [
+                (Some("blob.ts"), ErrorCategory::Skipped),
+                (Some("latin1.ts"), ErrorCategory::Encoding),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rescan_files_keeps_stats_in_step_with_cache() {
+        let (_dir, root) = crate::tests::temp_root();
+        let legacy = "import { Foo } from '../shared/models/foo';\nconst f: Foo = {};\n";
+        let migrated = "import { Foo } from '../shared_2023/models/foo';\nconst f: Foo = {};\n";
+        let (a, b, c) = (root.join("a.ts"), root.join("b.ts"), root.join("c.ts"));
+        assert!(std::fs::write(&a, legacy).is_ok());
+        assert!(std::fs::write(&b, legacy).is_ok());
+
+        let scanner = new_scanner(ScanConfig::new(&root));
+        assert!(scanner.scan().is_ok());
+        let consistent = |scanner: &Scanner, errors| {
+            let stats = scanner.stats();
+            let mut expected = scanner.cache().stats();
+            expected.total += errors;
+            expected.errors = errors;
+            // Parse counts add up the work done, which the cache doesn't know
+            expected.parsed = stats.parsed;
+            assert_eq!(stats, expected);
+        };
+
+        // Migrating a file moves it between counters, however often it's saved
+        assert!(std::fs::write(&a, migrated).is_ok());
+        let result = scanner.rescan_files(&[a.clone(), a.clone()]);
+        assert!(result.outcomes.iter().all(|(_, outcome)| outcome.is_ok()));
+        assert_eq!((result.delta.total, result.delta.legacy, result.delta.migrated), (0, -1, 1));
+        consistent(&scanner, 0);
+        assert_eq!((scanner.stats().legacy, scanner.stats().migrated), (1, 1));
+
+        // A file that can't be read replaces its status with an error
+        assert!(std::fs::write(&b, [0xff, 0xfe]).is_ok());
+        let result = scanner.rescan_files(std::slice::from_ref(&b));
+        assert!(result.outcomes[0].1.is_err());
+        consistent(&scanner, 1);
+        assert!(scanner.rescan_files(std::slice::from_ref(&b)).delta.is_empty());
+
+        // Fixing it takes the error back; a deleted file leaves the counts
+        assert!(std::fs::write(&b, legacy).is_ok());
+        assert!(std::fs::write(&c, legacy).is_ok());
+        scanner.rescan_files(&[b.clone(), c.clone()]);
+        consistent(&scanner, 0);
+        assert_eq!(scanner.stats().total, 3);
+        assert!(std::fs::remove_file(&c).is_ok());
+        let result = scanner.rescan_files(std::slice::from_ref(&c));
+        assert!(result.outcomes[0].1.is_ok());
+        assert_eq!((result.delta.total, result.delta.legacy), (-1, -1));
+        consistent(&scanner, 0);
+        assert_eq!(scanner.stats().total, 2);
+    }
+
+    #[test]
+    fn test_transient_failures_are_retried() {
+        let (_dir, root) = crate::tests::temp_root();
+        assert!(std::fs::write(root.join("a.ts"), "export const a = 1;\n").is_ok());
+        let failed = |name: &str, kind: std::io::ErrorKind| {
+            let path = root.join(name);
+            let error = ScanError::read(&path, std::io::Error::from(kind));
+            (path, Err(error), Duration::ZERO)
+        };
+        let results = || {
+            vec![
+                failed("a.ts", std::io::ErrorKind::TimedOut),
+                failed("b.ts", std::io::ErrorKind::PermissionDenied),
+                failed("c.ts", std::io::ErrorKind::TimedOut),
+            ]
+        };
+        let categories = |scanner: &Scanner| {
+            let mut retried = scanner.retry_transient(&scanner.analyzer(), results(), None);
+            retried.sort_by(|a, b| a.0.cmp(&b.0));
+            retried
+                .into_iter()
+                .map(|(_, result, _)| result.err().map(|e| e.category()))
+                .collect::<Vec<_>>()
+        };
+
+        let retry = RetryPolicy::default().with_delay(Duration::ZERO);
+        let scanner = new_scanner(ScanConfig::new(&root).with_retry(retry));
+        // Permission errors aren't retried; c.ts is retried but now missing
+        assert_eq!(
+            categories(&scanner),
+            [None, Some(ErrorCategory::Permission), Some(ErrorCategory::Read)]
+        );
+
+        let scanner = new_scanner(ScanConfig::new(&root).with_retry(RetryPolicy::none()));
+        assert_eq!(
+            categories(&scanner),
+            [
+                Some(ErrorCategory::Transient),
+                Some(ErrorCategory::Permission),
+                Some(ErrorCategory::Transient)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cancelled_scan_stops_early() {
+        let (_dir, root) = crate::tests::temp_root();
+        assert!(std::fs::write(root.join("a.ts"), "export const a = 1;\n").is_ok());
+        let scanner = new_scanner(ScanConfig::new(&root));
+
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        assert!(matches!(scanner.scan_cancellable(&cancel), Err(ScanError::Cancelled)));
+        assert_eq!(scanner.stats().total, 0);
+
+        let (tx, mut rx) = mpsc::channel(16);
+        let result = scanner.scan_streaming_cancellable(tx, &cancel);
+        assert!(matches!(result, Err(ScanError::Cancelled)));
+        assert!(rx.try_recv().is_err(), "no updates after cancelling");
+
+        // Analysis skips every file once cancelled
+        let analyzer = FileAnalyzer::new().with_cancellation(cancel);
+        let matcher = ModelPathMatcher::default();
+        assert!(analyzer.analyze_files(&[root.join("a.ts")], &matcher, None).is_empty());
+
+        assert!(scanner.scan_cancellable(&CancellationToken::new()).is_ok());
+        assert_eq!(scanner.stats().total, 1);
+    }
+
+    #[test]
+    fn test_streaming_scan_prioritizes_files() {
+        let (_dir, root) = crate::tests::temp_root();
+        let legacy = "import { Foo } from '../shared/models/foo';\nconst f: Foo = {};\n";
+        for (sub, contents) in [("a", "export const a = 1;\n"), ("b", legacy), ("c", "")] {
+            assert!(std::fs::create_dir(root.join(sub)).is_ok());
+            for file in ["x.ts", "y.ts"] {
+                assert!(std::fs::write(root.join(sub).join(file), contents).is_ok());
+            }
+        }
+        let first_dirs = |scanner: &Scanner| {
+            let (tx, mut rx) = mpsc::channel(16);
+            assert!(scanner.scan_streaming(tx).is_ok());
+            std::iter::from_fn(|| rx.try_recv().ok())
+                .filter_map(|update| match update {
+                    ScanUpdate::FileScanned(file) => file.path.parent().map(Utf8Path::to_owned),
+                    _ => None,
+                })
+                .take(2)
+                .collect::<Vec<_>>()
+        };
+
+        let config = ScanConfig::new(&root).with_priority_dirs(&["c"]).with_legacy_first(true);
+        let scanner = new_scanner(config);
+        assert_eq!(first_dirs(&scanner), [root.join("c"), root.join("c")]);
+
+        // Without priority directories, the last scan's legacy files lead
+        let scanner = new_scanner(ScanConfig::new(&root).with_legacy_first(true));
+        assert!(scanner.scan().is_ok());
+        assert_eq!(first_dirs(&scanner), [root.join("b"), root.join("b")]);
+    }
+
+    #[test]
+    fn test_scan_counts_parsed_files_by_kind_and_size() {
+        let (_dir, root) = crate::tests::temp_root();
+        let large = format!("export const a = '{}';\n", "x".repeat(5_000));
+        assert!(std::fs::write(root.join("a.ts"), "export const a = 1;\n").is_ok());
+        assert!(std::fs::write(root.join("b.tsx"), &large).is_ok());
+        let scanner = new_scanner(ScanConfig::new(&root));
+
+        let result = scanner.scan();
+        assert!(result.is_ok());
+        let Ok(result) = result else {
+            return;
+        };
+        let parsed = result.stats.parsed;
+        assert_eq!(parsed.kind(FileKind::Ts), 1);
+        assert_eq!(parsed.kind(FileKind::Tsx), 1);
+        assert_eq!((parsed.size(SizeBucket::Small), parsed.size(SizeBucket::Medium)), (1, 1));
+        assert_eq!(parsed.bytes, 20 + large.len() as u64);
+        assert_eq!(scanner.stats().parsed, parsed);
+    }
+
+    #[test]
+    fn test_scanner_runs_in_own_thread_pool() {
+        let (_dir, root) = crate::tests::temp_root();
+        for name in ["a.ts", "b.ts", "c.ts"] {
+            assert!(std::fs::write(root.join(name), "export const a = 1;\n").is_ok());
+        }
+        let scanner = new_scanner(ScanConfig::new(&root).with_threads(2));
+        assert_eq!(scanner.thread_pool_size(), 2);
+        assert_eq!(scanner.clone().thread_pool_size(), 2);
+
+        let result = scanner.scan();
+        assert!(result.is_ok());
+        let Ok(result) = result else {
+            return;
+        };
+        assert_eq!(result.stats.total, 3);
+        assert_eq!(scanner.cache().len(), 3);
+    }
+
+    #[test]
+    fn test_streaming_scan_sends_throttled_progress() {
+        let (_dir, root) = crate::tests::temp_root();
+        for name in ["a.ts", "b.ts", "c.ts", "d.ts", "e.ts"] {
+            assert!(std::fs::write(root.join(name), "export const a = 1;\n").is_ok());
+        }
+        let interval = ProgressInterval::default()
+            .with_files(2)
+            .with_interval(Duration::from_secs(3600));
+        let config = ScanConfig::new(&root).with_progress_interval(interval);
+        let scanner = new_scanner(config);
+
+        // Analyzed files, then the same files reused, are both counted
+        for _ in 0..2 {
+            let (tx, mut rx) = mpsc::channel(32);
+            assert!(scanner.scan_streaming(tx).is_ok());
+            let progress: Vec<_> = std::iter::from_fn(|| rx.try_recv().ok())
+                .filter_map(|update| match update {
+                    ScanUpdate::Progress(stats) => Some(stats.total),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(progress.len(), 2);
+            assert!(progress.iter().all(|&total| (2..=5).contains(&total)));
+        }
+        assert_eq!(scanner.stats().reused, 5);
+    }
+
+    #[test]
+    fn test_paused_streaming_scan_resumes() {
+        let (_dir, root) = crate::tests::temp_root();
+        for name in ["a.ts", "b.ts", "c.ts"] {
+            assert!(std::fs::write(root.join(name), "export const a = 1;\n").is_ok());
+        }
+        let scanner = new_scanner(ScanConfig::new(&root));
+
+        let control = ScanControl::new();
+        control.pause();
+        let (tx, mut rx) = mpsc::channel(16);
+        std::thread::scope(|scope| {
+            let scan = scope.spawn(|| scanner.scan_streaming_controlled(tx, &control));
+            assert!(matches!(rx.blocking_recv(), Some(ScanUpdate::PathsDiscovered(3))));
+            std::thread::sleep(Duration::from_millis(100));
+            assert!(rx.is_empty(), "no file is scanned while paused");
+
+            control.resume();
+            let scanned = std::iter::from_fn(|| rx.blocking_recv())
+                .filter(|update| matches!(update, ScanUpdate::FileScanned(_)))
+                .count();
+            assert_eq!(scanned, 3);
+            assert!(scan.join().is_ok_and(|result| result.is_ok()));
+        });
+    }
+
+    #[test]
+    fn test_scan_reuses_unchanged_files_from_cache_dir() {
+        let (_dir, root) = crate::tests::temp_root();
+        let legacy = "import { Foo } from '../shared/models/foo-model';\nconst f: Foo = {};\n";
+        let migrated = "import { Foo } from '../shared_2023/models/foo1';\nconst f: Foo = {};\n";
+        assert_eq!(legacy.len(), migrated.len());
+        assert!(std::fs::create_dir_all(root.join("src")).is_ok());
+        assert!(std::fs::write(root.join("src/a.ts"), legacy).is_ok());
+        assert!(std::fs::write(root.join("src/b.ts"), legacy).is_ok());
+        // Stamps of files modified just before a scan aren't trusted
+        let a = std::fs::File::options().write(true).open(root.join("src/a.ts"));
+        assert!(a.is_ok());
+        let Ok(a) = a else {
+            return;
+        };
+        let modified = std::time::SystemTime::now() - Duration::from_secs(60);
+        assert!(a.set_modified(modified).is_ok());
+
+        let config = ScanConfig::new(&root.join("src")).with_cache_dir(&root.join("cache"));
+        let scanner = new_scanner(config);
+        assert!(scanner.scan().is_ok());
+        assert_eq!(scanner.stats().legacy, 2);
+
+        // Same size and modification time: reused without reading the file
+        assert!(std::fs::write(root.join("src/a.ts"), migrated).is_ok());
+        assert!(a.set_modified(modified).is_ok());
+        // Changed size: analyzed again
+        assert!(std::fs::write(root.join("src/b.ts"), "export const b = 1;\n").is_ok());
+
+        let result = scanner.scan();
+        assert!(result.is_ok());
+        let stats = scanner.stats();
+        assert_eq!(stats.total, 2);
+        assert_eq!(stats.legacy, 1, "a.ts comes from the cache");
+        assert_eq!(stats.no_models, 1);
+    }
+
+    #[test]
+    fn test_file_ids_follow_renames_across_runs() {
+        let (_dir, root) = crate::tests::temp_root();
+        let (old, new) = (root.join("src/old.ts"), root.join("src/new.ts"));
+        assert!(std::fs::create_dir_all(root.join("src")).is_ok());
+        assert!(std::fs::write(&old, "export const a = 1;\n").is_ok());
+        let config = ScanConfig::new(&root.join("src")).with_cache_dir(&root.join("cache"));
+        let scanner = new_scanner(config.clone());
+        assert!(scanner.scan().is_ok());
+        let id = scanner.file_id(&old);
+        assert!(id.is_some(), "scanned file has no id");
+
+        // Either half of the rename may be rescanned first, together or not
+        assert!(std::fs::rename(&old, &new).is_ok());
+        let result = scanner.rescan_files(&[new.clone(), old.clone()]);
+        assert!(result.outcomes.iter().all(|(_, outcome)| outcome.is_ok()));
+        assert_eq!(scanner.file_id(&new), id);
+        assert_eq!(scanner.file_id(&old), None);
+        assert!(std::fs::rename(&new, &old).is_ok());
+        scanner.rescan_files(std::slice::from_ref(&new));
+        scanner.rescan_files(std::slice::from_ref(&old));
+        assert!(std::fs::rename(&old, &new).is_ok());
+        scanner.rescan_files(std::slice::from_ref(&new));
+        scanner.rescan_files(std::slice::from_ref(&old));
+        assert_eq!(id.and_then(|id| scanner.file_path(id)), Some(new.clone()));
+        assert_eq!(scanner.get_file(&new).map(|file| file.id), id);
+        assert!(scanner.get_file(&old).is_none());
+
+        // A later run, and a full scan in it, keep the id
+        let reloaded = new_scanner(config);
+        assert_eq!(reloaded.file_id(&new), id);
+        assert!(reloaded.scan().is_ok());
+        assert_eq!(reloaded.get_file(&new).map(|file| file.id), id);
+    }
+
+    #[test]
+    fn test_repeat_scans_reuse_unchanged_files() {
+        let (_dir, root) = crate::tests::temp_root();
+        let legacy = "import { Foo } from '../shared/models/foo';\nconst f: Foo = {};\n";
+        for file in ["a.ts", "b.ts", "c.ts"] {
+            assert!(std::fs::write(root.join(file), legacy).is_ok());
+        }
+
+        let scanner = new_scanner(ScanConfig::new(&root));
+        assert!(scanner.scan().is_ok());
+        assert_eq!(scanner.stats().reused, 0);
+
+        // Rewritten with the same contents: reused after comparing hashes
+        assert!(std::fs::write(root.join("a.ts"), legacy).is_ok());
+        assert!(std::fs::write(root.join("b.ts"), "export const b = 1;\n").is_ok());
+        assert!(scanner.scan().is_ok());
+        let stats = scanner.stats();
+        assert_eq!((stats.total, stats.legacy, stats.reused), (3, 2, 2));
+
+        let result = scanner.rescan_files(&[root.join("a.ts"), root.join("c.ts")]);
+        assert_eq!(result.outcomes.len(), 2);
+        assert!(result.delta.is_empty());
+        assert_eq!(scanner.stats().reused, 4);
+    }
+
+    #[test]
+    fn test_saved_cache_restores_results_in_new_scanner() {
+        let (_dir, root) = crate::tests::temp_root();
+        let legacy = "import { Foo } from '../shared/models/foo';\nconst f: Foo = {};\n";
+        assert!(std::fs::create_dir_all(root.join("src")).is_ok());
+        assert!(std::fs::write(root.join("src/a.ts"), legacy).is_ok());
+        assert!(std::fs::write(root.join("src/b.ts"), "export const b = 1;\n").is_ok());
+        let config = ScanConfig::new(&root.join("src"));
+        let scanner = new_scanner(config.clone());
+        assert!(scanner.scan().is_ok());
+        let snapshot = root.join("session/cache.msgpack");
+        assert!(scanner.save_cache(&snapshot).is_ok());
+
+        // Results are there before any scan, and the next scan reuses them
+        let restored = new_scanner(config);
+        assert_eq!(restored.load_cache(&snapshot).ok(), Some(2));
+        let stats = restored.stats();
+        assert_eq!((stats.total, stats.legacy, stats.no_models), (2, 1, 1));
+        let a = restored.get_file(&root.join("src/a.ts"));
+        assert_eq!(a.map(|file| file.status), Some(MigrationStatus::Legacy));
+        assert!(restored.scan().is_ok());
+        assert_eq!(restored.stats().reused, 2);
+
+        assert!(restored.load_cache(&root.join("missing.msgpack")).is_err());
+        assert!(std::fs::write(root.join("bad.msgpack"), "not a cache").is_ok());
+        assert!(restored.load_cache(&root.join("bad.msgpack")).is_err());
+    }
+
+    #[test]
+    fn test_copied_files_are_parsed_at_most_twice() {
+        let (_dir, root) = crate::tests::temp_root();
+        let legacy = "import { Foo } from '../shared/models/foo';\nconst f: Foo = {};\n";
+        for file in ["a.ts", "b.ts", "d.ts"] {
+            assert!(std::fs::write(root.join(file), legacy).is_ok());
+        }
+        assert!(std::fs::write(root.join("c.ts"), "export const c = 1;\n").is_ok());
+        let scanner = new_scanner(ScanConfig::new(&root).with_threads(1));
+        let result = scanner.scan();
+        assert!(result.is_ok());
+        let Ok(result) = result else {
+            return;
+        };
+
+        // The third copy shares the analysis but keeps its own path and id
+        assert_eq!(result.stats.parsed.kind(FileKind::Ts), 3);
+        assert_eq!((result.stats.total, result.stats.legacy), (4, 3));
+        let duplicates = scanner.duplicates();
+        assert_eq!(duplicates.len(), 2);
+        let (copy, original) = &duplicates[0];
+        assert_eq!(duplicates[1].1, *original);
+        assert_eq!(scanner.duplicate_of(copy).as_ref(), Some(original));
+        assert_eq!(scanner.duplicate_of(original), None);
+        for (copy, _) in &duplicates {
+            let file = scanner.get_file(copy);
+            assert_eq!(file.as_ref().map(|file| &file.path), Some(copy));
+            assert_eq!(file.map(|file| file.id), scanner.file_id(copy));
+            assert_ne!(scanner.file_id(copy), scanner.file_id(original));
+        }
+
+        // A changed copy is no longer one
+        assert!(std::fs::write(copy, "export const b = 1;\n").is_ok());
+        scanner.rescan_files(std::slice::from_ref(copy));
+        assert_eq!(scanner.duplicates().len(), 1);
+    }
+
+    #[test]
+    fn test_observer_sees_each_analyzed_file() {
+        #[derive(Default)]
+        struct Recorder {
+            events: parking_lot::Mutex<Vec<(&'static str, Utf8PathBuf)>>,
+        }
+
+        impl ScanObserver for Recorder {
+            fn on_file_start(&self, path: &Utf8Path) {
+                self.events.lock().push(("start", path.to_owned()));
+            }
+
+            fn on_file_done(&self, path: &Utf8Path, file: &FileInfo, _elapsed: Duration) {
+                assert_eq!(path, file.path);
+                self.events.lock().push(("done", path.to_owned()));
+            }
+
+            fn on_error(&self, path: &Utf8Path, _error: &ScanError) {
+                self.events.lock().push(("error", path.to_owned()));
+            }
+        }
+
+        let (_dir, root) = crate::tests::temp_root();
+        assert!(std::fs::write(root.join("a.ts"), "export const a = 1;\n").is_ok());
+        assert!(std::fs::write(root.join("b.ts"), [0xff, 0xfe]).is_ok());
+        let recorder = Arc::new(Recorder::default());
+        let config = ScanConfig::new(&root).with_threads(1).with_retry(RetryPolicy::none());
+        let scanner = new_scanner(config);
+        let scanner = scanner.with_observer(Arc::clone(&recorder) as Arc<dyn ScanObserver>);
+        assert!(scanner.scan().is_ok());
+
+        let mut events = std::mem::take(&mut *recorder.events.lock());
+        events.sort();
+        let (a, b) = (root.join("a.ts"), root.join("b.ts"));
+        assert_eq!(
+            events,
+            [("done", a.clone()), ("error", b.clone()), ("start", a), ("start", b)]
+        );
+    }
+
+    #[test]
+    fn test_update_registry_swaps_in_patched_registry() {
+        let (_dir, root) = crate::tests::temp_root();
+        for sub in ["app", "shared/models", "shared_2023/models"] {
+            assert!(std::fs::create_dir_all(root.join(sub)).is_ok());
+        }
+        let model = root.join("shared/models/bar.ts");
+        assert!(std::fs::write(&model, "export class Baz {}\n").is_ok());
+        let consumer = "import { Bar } from '../shared/models/bar';\nconst b: Bar = {};\n";
+        assert!(std::fs::write(root.join("app/a.ts"), consumer).is_ok());
+
+        let config = ScanConfig::new(&root.join("app"))
+            .with_shared_paths(&root.join("shared"), &root.join("shared_2023"));
+        let scanner = new_scanner(config);
+        let before = scanner.registry();
+        assert!(scanner.scan().is_ok());
+        assert_eq!(scanner.stats().legacy, 0);
+
+        assert!(std::fs::write(&model, "export class Bar {}\n").is_ok());
+        assert_eq!(scanner.update_registry(&[model, root.join("app/a.ts")]).ok(), Some(1));
+        assert!(scanner.registry().is_legacy_export("Bar"));
+        assert!(!before.is_legacy_export("Bar"), "snapshots are not patched");
+        assert!(scanner.scan().is_ok());
+        assert_eq!((scanner.stats().legacy, scanner.stats().reused), (1, 0));
+    }
+
+    #[test]
+    fn test_refresh_registry_rescans_model_consumers() {
+        let (_dir, root) = crate::tests::temp_root();
+        for sub in ["app", "shared/models", "shared_2023/models"] {
+            assert!(std::fs::create_dir_all(root.join(sub)).is_ok());
+        }
+        let consumer = "import { Job } from '../shared_2023/models/job';\nconst j: Job = {};\n";
+        assert!(std::fs::write(root.join("app/a.ts"), consumer).is_ok());
+        assert!(std::fs::write(root.join("app/b.ts"), "export const x = 1;\n").is_ok());
+
+        let config = ScanConfig::new(&root.join("app"))
+            .with_shared_paths(&root.join("shared"), &root.join("shared_2023"));
+        let scanner = new_scanner(config);
+        assert!(scanner.scan().is_ok());
+        assert_eq!(scanner.stats().migrated, 0, "Job isn't a known model yet");
+
+        // A model added to shared_2023 is recognized in the files importing it
+        let model = root.join("shared_2023/models/job.ts");
+        assert!(std::fs::write(&model, "export class Job {}\n").is_ok());
+        let result = scanner.refresh_registry(std::slice::from_ref(&model));
+        assert!(matches!(result, Ok(Some(_))), "the registry should be updated");
+        let rescanned: Vec<_> = result
+            .iter()
+            .flatten()
+            .flat_map(|result| &result.outcomes)
+            .map(|(path, _)| path)
+            .collect();
+        assert_eq!(rescanned, [&root.join("app/a.ts")]);
+        assert_eq!(scanner.stats().migrated, 1);
+        assert!(matches!(scanner.refresh_registry(&[root.join("app/b.ts")]), Ok(None)));
+    }
+
+    #[test]
+    fn test_barrel_imports_take_the_source_of_reexported_models() {
+        let (_dir, root) = crate::tests::temp_root();
+        for sub in ["app", "shared/models", "shared_2023/models"] {
+            assert!(std::fs::create_dir_all(root.join(sub)).is_ok());
+        }
+        assert!(std::fs::write(root.join("shared/models/job.ts"), "export class Job {}\n").is_ok());
+        let modern = root.join("shared_2023/models/job.ts");
+        assert!(std::fs::write(&modern, "export class Job {}\n").is_ok());
+        let barrel = root.join("shared/index.ts");
+        assert!(std::fs::write(&barrel, "export { Job } from './models/job';\n").is_ok());
+        let consumer = "import { Job } from '../shared';\nconst j: Job = {};\n";
+        assert!(std::fs::write(root.join("app/a.ts"), consumer).is_ok());
+
+        let config = ScanConfig::new(&root.join("app"))
+            .with_shared_paths(&root.join("shared"), &root.join("shared_2023"));
+        let scanner = new_scanner(config);
+        assert!(scanner.scan().is_ok());
+        assert_eq!(scanner.stats().legacy, 1);
+
+        // Pointing the barrel at the port migrates its importers
+        let ported = "export { Job } from '../shared_2023/models/job';\n";
+        assert!(std::fs::write(&barrel, ported).is_ok());
+        let result = scanner.refresh_registry(std::slice::from_ref(&barrel));
+        assert!(result.is_ok_and(|result| result.is_some_and(|r| r.outcomes.len() == 1)));
+        assert_eq!((scanner.stats().legacy, scanner.stats().migrated), (0, 1));
+    }
+
+    #[test]
+    fn test_require_imports_count_as_model_usage() {
+        let (_dir, root) = crate::tests::temp_root();
+        for sub in ["app", "shared/models", "shared_2023/models"] {
+            assert!(std::fs::create_dir_all(root.join(sub)).is_ok());
+        }
+        for models in ["shared/models/job.ts", "shared_2023/models/job.ts"] {
+            assert!(std::fs::write(root.join(models), "export class Job {}\n").is_ok());
+        }
+        let files = [
+            ("app/a.ts", "const models = require('../shared/models/job');\n"),
+            ("app/b.ts", "const { Job: J } = require('../shared_2023/models/job');\n"),
+            ("app/c.ts", "const { helper } = require('../shared/models/job');\n"),
+        ];
+        for (file, contents) in files {
+            assert!(std::fs::write(root.join(file), contents).is_ok());
+        }
+
+        let config = ScanConfig::new(&root.join("app"))
+            .with_shared_paths(&root.join("shared"), &root.join("shared_2023"));
+        let scanner = new_scanner(config);
+        assert!(scanner.scan().is_ok());
+        let stats = scanner.stats();
+        assert_eq!((stats.legacy, stats.migrated, stats.no_models), (1, 1, 1));
+    }
+
+    #[test]
+    fn test_namespace_imports_count_by_members_used() {
+        let (_dir, root) = crate::tests::temp_root();
+        for sub in ["app", "shared/models", "shared_2023/models"] {
+            assert!(std::fs::create_dir_all(root.join(sub)).is_ok());
+        }
+        for models in ["shared/models/job.ts", "shared_2023/models/job.ts"] {
+            assert!(std::fs::write(root.join(models), "export class Job {}\n").is_ok());
+        }
+        let files = [
+            ("app/a.ts", "import * as M from '../shared/models/job';\nconst j = new M.Job();\n"),
+            ("app/b.ts", "const m = require('../shared_2023/models/job');\nlet j: m.Job;\n"),
+            ("app/c.ts", "import * as M from '../shared/models/job';\nM.helper();\n"),
+        ];
+        for (file, contents) in files {
+            assert!(std::fs::write(root.join(file), contents).is_ok());
+        }
+
+        let config = ScanConfig::new(&root.join("app"))
+            .with_shared_paths(&root.join("shared"), &root.join("shared_2023"));
+        let scanner = new_scanner(config);
+        assert!(scanner.scan().is_ok());
+        let stats = scanner.stats();
+        assert_eq!((stats.legacy, stats.migrated, stats.no_models), (1, 1, 1));
+    }
+
+    #[test]
+    fn test_slowest_files_track_analyzed_files() {
+        let (_dir, root) = crate::tests::temp_root();
+        for file in ["a.ts", "b.ts", "c.ts"] {
+            assert!(std::fs::write(root.join(file), "export const x = 1;\n").is_ok());
+        }
+        let scanner = new_scanner(ScanConfig::new(&root));
+
+        let (tx, _rx) = mpsc::channel(16);
+        assert!(scanner.scan_streaming(tx).is_ok());
+        let slowest = scanner.slowest_files(2);
+        assert_eq!(slowest.len(), 2);
+        assert!(slowest[0].duration >= slowest[1].duration);
+
+        assert!(std::fs::remove_file(root.join("a.ts")).is_ok());
+        let _ = scanner.rescan_files(&[root.join("a.ts")]);
+        let mut paths: Vec<_> = scanner.slowest_files(10).into_iter().map(|f| f.path).collect();
+        paths.sort();
+        assert_eq!(paths, [root.join("b.ts"), root.join("c.ts")]);
+    }
+
+    #[test]
+    fn test_detail_limit_compacts_and_reanalyzes() {
+        let (_dir, root) = crate::tests::temp_root();
+        let legacy = "import { Foo } from '../shared/models/foo';\nconst f: Foo = {};\n";
+        for file in ["a.ts", "b.ts", "c.ts"] {
+            assert!(std::fs::write(root.join(file), legacy).is_ok());
+        }
+        let scanner = new_scanner(ScanConfig::new(&root).with_max_detailed_files(1));
+
+        assert!(scanner.scan().is_ok());
+        let files = scanner.cache().all_files();
+        assert_eq!(files.len(), 3);
+        assert_eq!(files.iter().filter(|f| f.imports.is_empty()).count(), 2);
+        assert_eq!(scanner.cache().stats().legacy, 3);
+
+        // Only the detailed file is reused
+        assert!(scanner.scan().is_ok());
+        let stats = scanner.stats();
+        assert_eq!((stats.legacy, stats.reused), (3, 1));
+    }
+
     #[test]
     fn test_scanner_invalid_root() {
         let config = ScanConfig::new(Utf8Path::new("/nonexistent/path/that/does/not/exist"));
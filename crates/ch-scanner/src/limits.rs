@@ -0,0 +1,201 @@
+//! Memory limits for scans in resource-limited environments.
+//!
+//! By default each analysis thread keeps an arena from a `bumpalo_herd::Herd`
+//! for the whole scan, which is fast but holds on to every string parsed
+//! until the scan ends. On a large tree inside a container with a memory
+//! limit, that can get the process OOM-killed.
+//!
+//! [`ResourceLimits`] sets optional ceilings on resident memory and per-thread
+//! arena size. Once one is exceeded the scanner logs a warning and switches to
+//! low-memory mode for the rest of its lifetime:
+//!
+//! - each thread reuses one arena, reset after every file, instead of the herd
+//! - streaming scans use a smaller channel buffer (see
+//!   [`Scanner::channel_capacity`](crate::Scanner::channel_capacity))
+//!
+//! Arenas already handed out by the herd are freed when the current scan
+//! ends, so the switch lowers memory use from the next file on rather than
+//! shrinking it immediately.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tracing::warn;
+
+/// Number of files a thread analyzes between resident memory checks.
+///
+/// Reading the resident set size costs a small file read, so it is sampled
+/// rather than checked after every file.
+const RESIDENT_CHECK_INTERVAL: usize = 32;
+
+/// Optional memory limits for a scan.
+///
+/// # Examples
+///
+/// ```
+/// use ch_scanner::ResourceLimits;
+///
+/// let limits = ResourceLimits::default()
+///     .with_max_resident_bytes(512 * 1024 * 1024)
+///     .with_max_arena_bytes(64 * 1024 * 1024);
+/// assert!(!limits.is_empty());
+/// assert!(ResourceLimits::default().is_empty());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// Resident set size of the process above which to switch to low-memory
+    /// mode. Only checked on platforms that report it (Linux).
+    pub max_resident_bytes: Option<u64>,
+
+    /// Bytes allocated in one analysis thread's arena above which to switch
+    /// to low-memory mode.
+    pub max_arena_bytes: Option<usize>,
+}
+
+impl ResourceLimits {
+    /// Returns `true` if no limit is set.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.max_resident_bytes.is_none() && self.max_arena_bytes.is_none()
+    }
+
+    /// Sets the resident set size limit.
+    #[must_use]
+    pub const fn with_max_resident_bytes(mut self, bytes: u64) -> Self {
+        self.max_resident_bytes = Some(bytes);
+        self
+    }
+
+    /// Sets the per-thread arena size limit.
+    #[must_use]
+    pub const fn with_max_arena_bytes(mut self, bytes: usize) -> Self {
+        self.max_arena_bytes = Some(bytes);
+        self
+    }
+}
+
+impl From<&ch_core::ScanConfig> for ResourceLimits {
+    /// Converts the configured `max_memory_mb` and `max_arena_mb` from MiB.
+    fn from(scan: &ch_core::ScanConfig) -> Self {
+        const MIB: u64 = 1024 * 1024;
+
+        Self {
+            max_resident_bytes: scan.max_memory_mb.map(|mb| mb.saturating_mul(MIB)),
+            max_arena_bytes: scan
+                .max_arena_mb
+                .map(|mb| usize::try_from(mb.saturating_mul(MIB)).unwrap_or(usize::MAX)),
+        }
+    }
+}
+
+/// Tracks whether a scanner has exceeded its [`ResourceLimits`].
+///
+/// Shared between a scanner's clones and analyzers, so once any thread
+/// crosses a limit every later file is analyzed in low-memory mode.
+#[derive(Debug, Default)]
+pub(crate) struct MemoryGuard {
+    /// Limits that trigger low-memory mode.
+    limits: ResourceLimits,
+    /// Whether a limit has been exceeded.
+    low_memory: AtomicBool,
+}
+
+impl MemoryGuard {
+    /// Creates a guard for the given limits.
+    pub(crate) const fn new(limits: ResourceLimits) -> Self {
+        Self {
+            limits,
+            low_memory: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns `true` once a limit has been exceeded.
+    pub(crate) fn is_low_memory(&self) -> bool {
+        self.low_memory.load(Ordering::Relaxed)
+    }
+
+    /// Checks the limits after a thread analyzed a file into an arena now
+    /// holding `arena_bytes`.
+    ///
+    /// `analyzed` counts the thread's earlier files; resident memory is
+    /// sampled on the first file and every [`RESIDENT_CHECK_INTERVAL`] after.
+    pub(crate) fn check(&self, arena_bytes: usize, analyzed: usize) {
+        if self.limits.is_empty() || self.is_low_memory() {
+            return;
+        }
+
+        let to_u64 = |bytes: usize| u64::try_from(bytes).unwrap_or(u64::MAX);
+        if let Some(max) = self.limits.max_arena_bytes.filter(|&max| arena_bytes > max) {
+            self.enter_low_memory("arena", to_u64(arena_bytes), to_u64(max));
+            return;
+        }
+
+        if analyzed % RESIDENT_CHECK_INTERVAL != 0 {
+            return;
+        }
+        if let Some(max) = self.limits.max_resident_bytes {
+            if let Some(resident) = resident_bytes().filter(|&resident| resident > max) {
+                self.enter_low_memory("resident", resident, max);
+            }
+        }
+    }
+
+    /// Switches to low-memory mode, warning the first time.
+    fn enter_low_memory(&self, limit: &str, bytes: u64, max: u64) {
+        if !self.low_memory.swap(true, Ordering::Relaxed) {
+            warn!(limit, bytes, max, "Memory limit exceeded, switching to low-memory mode");
+        }
+    }
+}
+
+/// Returns the resident set size of this process, if the platform reports it.
+#[must_use]
+pub fn resident_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arena_limit_enters_low_memory() {
+        let guard = MemoryGuard::new(ResourceLimits::default().with_max_arena_bytes(1024));
+        guard.check(512, 1);
+        assert!(!guard.is_low_memory());
+
+        guard.check(2048, 2);
+        assert!(guard.is_low_memory());
+    }
+
+    #[test]
+    fn test_resident_limit_enters_low_memory() {
+        let guard = MemoryGuard::new(ResourceLimits::default().with_max_resident_bytes(1));
+        guard.check(0, 1);
+        assert!(!guard.is_low_memory(), "resident memory is only sampled periodically");
+
+        guard.check(0, RESIDENT_CHECK_INTERVAL);
+        assert_eq!(guard.is_low_memory(), resident_bytes().is_some());
+    }
+
+    #[test]
+    fn test_limits_from_config() {
+        let scan = ch_core::ScanConfig {
+            max_memory_mb: Some(512),
+            ..ch_core::ScanConfig::default()
+        };
+        let limits = ResourceLimits::from(&scan);
+        assert_eq!(limits.max_resident_bytes, Some(512 * 1024 * 1024));
+        assert_eq!(limits.max_arena_bytes, None);
+        assert!(ResourceLimits::from(&ch_core::ScanConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn test_no_limits() {
+        let guard = MemoryGuard::default();
+        guard.check(usize::MAX, 0);
+        assert!(!guard.is_low_memory());
+    }
+}
@@ -0,0 +1,74 @@
+//! Hooks for reporting scans to external metrics systems.
+//!
+//! An embedder implements [`ScanObserver`] and hands it to
+//! [`Scanner::with_observer`]; every scan, streaming scan and rescan then
+//! calls it as files are analyzed, on the analysis threads. Files reused
+//! from the cache aren't analyzed and aren't reported, and a file retried
+//! after a transient error is reported once per attempt.
+//!
+//! [`Scanner::with_observer`]: crate::Scanner::with_observer
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use camino::Utf8Path;
+use ch_core::FileInfo;
+
+use crate::error::ScanError;
+
+/// Receives a call as each file's analysis starts and ends.
+///
+/// Every method does nothing by default. Calls come from several threads
+/// at once and hold up the file being analyzed, so they should be quick:
+/// bump a counter or record a histogram sample, not write to a socket.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::atomic::{AtomicU64, Ordering};
+/// use std::time::Duration;
+///
+/// use camino::Utf8Path;
+/// use ch_core::FileInfo;
+/// use ch_scanner::ScanObserver;
+///
+/// #[derive(Default)]
+/// struct ParseTime {
+///     micros: AtomicU64,
+/// }
+///
+/// impl ScanObserver for ParseTime {
+///     fn on_file_done(&self, _path: &Utf8Path, _file: &FileInfo, elapsed: Duration) {
+///         let micros = u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX);
+///         self.micros.fetch_add(micros, Ordering::Relaxed);
+///     }
+/// }
+/// ```
+pub trait ScanObserver: Send + Sync {
+    /// Called before the file at `path` is read.
+    fn on_file_start(&self, path: &Utf8Path) {
+        let _ = path;
+    }
+
+    /// Called once the file at `path` has been analyzed into `file`, which
+    /// took `elapsed`.
+    fn on_file_done(&self, path: &Utf8Path, file: &FileInfo, elapsed: Duration) {
+        let _ = (path, file, elapsed);
+    }
+
+    /// Called when the file at `path` failed to analyze with `error`.
+    fn on_error(&self, path: &Utf8Path, error: &ScanError) {
+        let _ = (path, error);
+    }
+}
+
+/// An observer shared by a scanner's clones and its analyzers.
+#[derive(Clone)]
+pub(crate) struct SharedObserver(pub(crate) Arc<dyn ScanObserver>);
+
+impl fmt::Debug for SharedObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedObserver")
+    }
+}
@@ -0,0 +1,239 @@
+//! Code ownership resolution from `CODEOWNERS` files.
+//!
+//! This module provides [`CodeOwners`], which parses a GitHub-style
+//! `CODEOWNERS` file and resolves the owners of scanned files so migration
+//! progress can be reported per team.
+//!
+//! # Format
+//!
+//! Each non-comment line holds a gitignore-style pattern followed by one or
+//! more owners. As on GitHub, the **last** matching pattern wins, and a
+//! pattern without owners marks matching files as unowned:
+//!
+//! ```text
+//! # Default owners
+//! *                       @clickhome/platform
+//! /app/contracts/         @clickhome/contracts
+//! /app/jobs/**/*.ts       @clickhome/jobs @alice
+//! /app/generated/
+//! ```
+//!
+//! # Root Directory
+//!
+//! Patterns are relative to the repository root. When the file lives in a
+//! `.github/` or `docs/` directory, the root is that directory's parent;
+//! otherwise it is the directory containing the file.
+//!
+//! # Examples
+//!
+//! ```
+//! use camino::Utf8Path;
+//! use ch_scanner::CodeOwners;
+//!
+//! let owners = CodeOwners::parse(
+//!     "* @platform\n/app/contracts/ @contracts\n",
+//!     Utf8Path::new("/repo"),
+//! )?;
+//!
+//! assert_eq!(owners.owners_of(Utf8Path::new("/repo/app/contracts/list.ts")), ["@contracts"]);
+//! assert_eq!(owners.owners_of(Utf8Path::new("/repo/app/jobs/job.ts")), ["@platform"]);
+//! # Ok::<(), ch_scanner::ScanError>(())
+//! ```
+
+use std::fs;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+use crate::error::ScanError;
+
+/// A single `CODEOWNERS` rule: a pattern and the owners it assigns.
+#[derive(Debug)]
+struct OwnerRule {
+    /// Compiled matcher for the rule's pattern.
+    matcher: Gitignore,
+    /// Owners assigned by this rule (empty means explicitly unowned).
+    owners: Vec<String>,
+}
+
+/// Parsed `CODEOWNERS` rules for resolving file owners.
+///
+/// See the [module documentation](self) for the supported format.
+#[derive(Debug)]
+pub struct CodeOwners {
+    /// Repository root that patterns are relative to (absolute).
+    root: Utf8PathBuf,
+    /// Rules in file order (later rules take precedence).
+    rules: Vec<OwnerRule>,
+}
+
+impl CodeOwners {
+    /// Loads and parses a `CODEOWNERS` file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the `CODEOWNERS` file
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScanError::Read`] if the file cannot be read, or
+    /// [`ScanError::Config`] if a pattern is invalid.
+    pub fn load(path: &Utf8Path) -> Result<Self, ScanError> {
+        let contents =
+            fs::read_to_string(path.as_std_path()).map_err(|e| ScanError::read(path, e))?;
+        Self::parse(&contents, &Self::root_for(path))
+    }
+
+    /// Parses `CODEOWNERS` contents with patterns relative to `root`.
+    ///
+    /// # Arguments
+    ///
+    /// * `contents` - The `CODEOWNERS` file contents
+    /// * `root` - Repository root the patterns are relative to
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScanError::Config`] if a pattern is invalid.
+    pub fn parse(contents: &str, root: &Utf8Path) -> Result<Self, ScanError> {
+        let root = absolute(root);
+        let mut rules = Vec::new();
+
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.split_once('#').map_or(line, |(content, _)| content);
+            let mut tokens = line.split_whitespace();
+            let Some(pattern) = tokens.next() else {
+                continue;
+            };
+
+            let mut builder = GitignoreBuilder::new(&root);
+            builder.add_line(None, pattern).map_err(|e| {
+                ScanError::config(format!("CODEOWNERS line {}: {e}", index + 1))
+            })?;
+            let matcher = builder.build().map_err(|e| {
+                ScanError::config(format!("CODEOWNERS line {}: {e}", index + 1))
+            })?;
+
+            rules.push(OwnerRule {
+                matcher,
+                owners: tokens.map(ToOwned::to_owned).collect(),
+            });
+        }
+
+        Ok(Self { root, rules })
+    }
+
+    /// Returns the owners of the given file.
+    ///
+    /// Returns an empty slice if no rule matches, if the matching rule has no
+    /// owners, or if the file lies outside the repository root.
+    #[must_use]
+    pub fn owners_of(&self, path: &Utf8Path) -> &[String] {
+        let path = absolute(path);
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return &[];
+        };
+
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| {
+                rule.matcher
+                    .matched_path_or_any_parents(relative, false)
+                    .is_ignore()
+            })
+            .map_or(&[], |rule| rule.owners.as_slice())
+    }
+
+    /// Returns the number of rules.
+    #[must_use]
+    pub fn rule_count(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Returns the repository root for a `CODEOWNERS` file location.
+    fn root_for(path: &Utf8Path) -> Utf8PathBuf {
+        let dir = path.parent().unwrap_or_else(|| Utf8Path::new("."));
+        let in_config_dir = dir
+            .file_name()
+            .is_some_and(|name| name == ".github" || name == "docs");
+
+        match dir.parent() {
+            Some(parent) if in_config_dir => parent.to_owned(),
+            _ => dir.to_owned(),
+        }
+    }
+}
+
+/// Makes a path absolute without touching the filesystem.
+fn absolute(path: &Utf8Path) -> Utf8PathBuf {
+    let path = if path.as_str().is_empty() {
+        Utf8Path::new(".")
+    } else {
+        path
+    };
+
+    std::path::absolute(path.as_std_path())
+        .ok()
+        .and_then(|p| Utf8PathBuf::try_from(p).ok())
+        .unwrap_or_else(|| path.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODEOWNERS: &str = "\
+# Default owners
+*                   @platform
+/app/contracts/     @contracts      # contracts team
+/app/jobs/**/*.ts   @jobs @alice
+/app/generated/
+";
+
+    fn owners() -> Option<CodeOwners> {
+        CodeOwners::parse(CODEOWNERS, Utf8Path::new("/repo")).ok()
+    }
+
+    #[test]
+    fn test_codeowners_last_match_wins() {
+        let owners = owners();
+        let owners_of = |path: &str| {
+            owners
+                .as_ref()
+                .map(|o| o.owners_of(Utf8Path::new(path)).to_vec())
+                .unwrap_or_default()
+        };
+
+        assert_eq!(owners_of("/repo/main.ts"), ["@platform"]);
+        assert_eq!(owners_of("/repo/app/contracts/list/a.ts"), ["@contracts"]);
+        assert_eq!(owners_of("/repo/app/jobs/queue/b.ts"), ["@jobs", "@alice"]);
+        assert!(owners_of("/repo/app/generated/c.ts").is_empty());
+    }
+
+    #[test]
+    fn test_codeowners_outside_root() {
+        let owners = owners();
+        assert!(owners.is_some_and(|o| o.owners_of(Utf8Path::new("/other/a.ts")).is_empty()));
+    }
+
+    #[test]
+    fn test_codeowners_skips_comments_and_blank_lines() {
+        assert_eq!(owners().map(|o| o.rule_count()), Some(4));
+    }
+
+    #[test]
+    fn test_codeowners_root_for() {
+        assert_eq!(
+            CodeOwners::root_for(Utf8Path::new("/repo/.github/CODEOWNERS")),
+            Utf8PathBuf::from("/repo")
+        );
+        assert_eq!(
+            CodeOwners::root_for(Utf8Path::new("/repo/docs/CODEOWNERS")),
+            Utf8PathBuf::from("/repo")
+        );
+        assert_eq!(
+            CodeOwners::root_for(Utf8Path::new("/repo/CODEOWNERS")),
+            Utf8PathBuf::from("/repo")
+        );
+    }
+}
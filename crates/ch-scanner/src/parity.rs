@@ -0,0 +1,188 @@
+//! Export parity between legacy models and their `shared_2023/` ports.
+//!
+//! A legacy model can only be swapped for its modern counterpart if the port
+//! provides every export consumers may import. [`check_parity`] compares the
+//! export set of each legacy model with the modern model of the same base
+//! name (or its renamed target) and reports the exports the port is missing.
+//!
+//! Exports derived from the model name follow renames, so with
+//! `ActiveContract` renamed to `ContractActive`, the legacy
+//! `ActiveContractCodeGen` is expected as `ContractActiveCodeGen`.
+//!
+//! # Examples
+//!
+//! ```
+//! use ch_core::{ModelDefinition, ModelRegistry, ModelSource};
+//! use ch_scanner::check_parity;
+//!
+//! let mut registry = ModelRegistry::new();
+//! let mut legacy = ModelDefinition::new("Job", ModelSource::SharedLegacy, "models/job.ts");
+//! legacy.add_export("Job");
+//! legacy.add_export("JobCodeGenForm");
+//! registry.register(legacy);
+//! let mut modern = ModelDefinition::new("Job", ModelSource::Shared2023, "models/job.ts");
+//! modern.add_export("Job");
+//! registry.register(modern);
+//!
+//! let report = check_parity(&registry);
+//! assert_eq!(report.matched, 1);
+//! assert_eq!(report.gaps[0].missing_exports, ["JobCodeGenForm"]);
+//! ```
+
+use camino::Utf8PathBuf;
+use ch_core::{ModelDefinition, ModelRegistry};
+use serde::{Deserialize, Serialize};
+
+/// A ported model whose modern version lacks some legacy exports.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParityGap {
+    /// Legacy model name.
+    pub legacy: String,
+
+    /// Modern model name (differs from `legacy` for renamed models).
+    pub modern: String,
+
+    /// Definition path of the modern model.
+    pub modern_path: Utf8PathBuf,
+
+    /// Expected modern export names that `shared_2023/` does not provide.
+    pub missing_exports: Vec<String>,
+}
+
+/// Result of comparing legacy and modern export sets.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParityReport {
+    /// Number of legacy models with a modern counterpart.
+    pub matched: usize,
+
+    /// Number of legacy models without a modern counterpart.
+    pub unported: usize,
+
+    /// Matched models with missing exports, sorted by legacy name.
+    pub gaps: Vec<ParityGap>,
+}
+
+impl ParityReport {
+    /// Returns `true` if every ported model provides all legacy exports.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.gaps.is_empty()
+    }
+
+    /// Returns the total number of missing exports across all models.
+    #[must_use]
+    pub fn missing_export_count(&self) -> usize {
+        self.gaps.iter().map(|gap| gap.missing_exports.len()).sum()
+    }
+}
+
+/// Compares the exports of every legacy model with its modern counterpart.
+///
+/// Legacy models are matched to modern ones with
+/// [`ModelRegistry::modern_model_for`], so recorded renames are honoured.
+/// Legacy models without a counterpart are only counted; they are reported
+/// as blocked by other commands.
+#[must_use]
+pub fn check_parity(registry: &ModelRegistry) -> ParityReport {
+    let mut report = ParityReport::default();
+
+    for legacy in registry.iter_legacy_models() {
+        let Some(modern) = registry.modern_model_for(&legacy.name) else {
+            report.unported += 1;
+            continue;
+        };
+        report.matched += 1;
+
+        let missing_exports = missing_exports(legacy, modern);
+        if !missing_exports.is_empty() {
+            report.gaps.push(ParityGap {
+                legacy: legacy.name.clone(),
+                modern: modern.name.clone(),
+                modern_path: modern.definition_path.clone(),
+                missing_exports,
+            });
+        }
+    }
+
+    report.gaps.sort_by(|a, b| a.legacy.cmp(&b.legacy));
+    report
+}
+
+/// Returns the expected modern exports of `legacy` that `modern` lacks, sorted.
+fn missing_exports(legacy: &ModelDefinition, modern: &ModelDefinition) -> Vec<String> {
+    let mut missing: Vec<String> = legacy
+        .exports
+        .iter()
+        .map(|export| match export.strip_prefix(legacy.name.as_str()) {
+            Some(suffix) => format!("{}{suffix}", modern.name),
+            None => export.clone(),
+        })
+        .filter(|expected| !modern.exports.contains(expected))
+        .collect();
+    missing.sort_unstable();
+    missing.dedup();
+    missing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ch_core::ModelSource;
+
+    fn model(name: &str, source: ModelSource, exports: &[&str]) -> ModelDefinition {
+        let mut definition = ModelDefinition::new(name, source, format!("models/{name}.ts"));
+        for export in exports {
+            definition.add_export(*export);
+        }
+        definition
+    }
+
+    #[test]
+    fn test_check_parity_reports_missing_exports() {
+        let mut registry = ModelRegistry::new();
+        registry.register(model("Job", ModelSource::SharedLegacy, &["Job", "JobModel", "JobCodeGen"]));
+        registry.register(model("Job", ModelSource::Shared2023, &["Job", "JobModel"]));
+        registry.register(model("Lot", ModelSource::SharedLegacy, &["Lot"]));
+        registry.register(model("Lot", ModelSource::Shared2023, &["Lot", "LotCodeGen"]));
+        registry.register(model("Quote", ModelSource::SharedLegacy, &["Quote"]));
+
+        let report = check_parity(&registry);
+        assert_eq!(report.matched, 2);
+        assert_eq!(report.unported, 1);
+        assert_eq!(report.gaps.len(), 1);
+        assert_eq!(report.gaps[0].legacy, "Job");
+        assert_eq!(report.gaps[0].missing_exports, ["JobCodeGen"]);
+        assert_eq!(report.missing_export_count(), 1);
+        assert!(!report.is_complete());
+    }
+
+    #[test]
+    fn test_check_parity_follows_renames() {
+        let mut registry = ModelRegistry::new();
+        registry.register(model(
+            "ActiveContract",
+            ModelSource::SharedLegacy,
+            &["ActiveContract", "ActiveContractCodeGen", "ContractStatus"],
+        ));
+        registry.register(model(
+            "ContractActive",
+            ModelSource::Shared2023,
+            &["ContractActive", "ContractActiveCodeGen"],
+        ));
+        registry.add_rename("ActiveContract", "ContractActive");
+
+        let report = check_parity(&registry);
+        assert_eq!(report.matched, 1);
+        assert_eq!(report.gaps.len(), 1);
+        assert_eq!(report.gaps[0].modern, "ContractActive");
+        assert_eq!(report.gaps[0].missing_exports, ["ContractStatus"]);
+    }
+
+    #[test]
+    fn test_check_parity_complete() {
+        let mut registry = ModelRegistry::new();
+        registry.register(model("Job", ModelSource::SharedLegacy, &["Job"]));
+        registry.register(model("Job", ModelSource::Shared2023, &["Job", "JobModel"]));
+        assert!(check_parity(&registry).is_complete());
+    }
+}
@@ -0,0 +1,456 @@
+//! On-disk cache of analysis results, reused across scans.
+//!
+//! A scanner with a cache directory ([`ScanConfig::with_cache_dir`]) saves
+//! the [`FileInfo`] of every analyzed file in a `MessagePack` file there,
+//! along with the file's size and modification time when it was read. On the
+//! next scan, files whose size and modification time are unchanged are taken
+//! from the cache without being read. Touched files are read and hashed, and
+//! reused if their content hash still matches; only the rest are parsed.
+//!
+//! The cache also records a fingerprint of everything else analysis depends
+//! on: the tool version, import matching, ignore and ownership rules, and
+//! the model registry. If any of them changed, the whole cache is discarded.
+//!
+//! The same lookup reuses the files a scanner still holds in memory from its
+//! previous scan, with or without a cache directory.
+//!
+//! [`ScanConfig::with_cache_dir`]: crate::ScanConfig::with_cache_dir
+
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use ch_core::{FileInfo, FxHashMap};
+use rayon::prelude::*;
+use rustc_hash::FxHasher;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::analyzer::hash_content;
+
+/// Version of the cache file layout; older files are discarded.
+const FORMAT_VERSION: u32 = 4;
+
+/// Time after a file's modification during which its stamp isn't trusted.
+///
+/// File systems with coarse timestamps can give a file modified again
+/// within this window the same stamp, as in git's "racy clean" problem.
+const RACY_WINDOW: Duration = Duration::from_secs(2);
+
+/// Size and modification time of a file when it was read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct FileStamp {
+    /// Modification time in nanoseconds since the Unix epoch.
+    modified_ns: u64,
+    /// Size in bytes.
+    size: u64,
+}
+
+impl FileStamp {
+    /// Reads the stamp of the file at `path`, or `None` if it can't be read.
+    pub(crate) fn of(path: &Utf8Path) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+        let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        Some(Self {
+            modified_ns: u64::try_from(modified.as_nanos()).unwrap_or(u64::MAX),
+            size: metadata.len(),
+        })
+    }
+
+    /// Returns the stamp to keep for a file stamped at `now_ns`.
+    ///
+    /// A file modified within [`RACY_WINDOW`] of being stamped gets a stamp
+    /// that matches nothing, so the next lookup compares content hashes.
+    fn settled(self, now_ns: u64) -> Self {
+        let window = u64::try_from(RACY_WINDOW.as_nanos()).unwrap_or(u64::MAX);
+        if now_ns.saturating_sub(self.modified_ns) < window {
+            Self {
+                modified_ns: 0,
+                ..self
+            }
+        } else {
+            self
+        }
+    }
+}
+
+/// A cached analysis result with the stamp of the file it came from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedFile {
+    stamp: FileStamp,
+    file: FileInfo,
+}
+
+/// Contents of a cache file.
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheContents {
+    version: u32,
+    fingerprint: u64,
+    files: Vec<CachedFile>,
+}
+
+/// The files of a scan, split by whether the cache could supply them.
+#[derive(Debug, Default)]
+pub(crate) struct CacheSplit {
+    /// Cached analyses of unchanged files.
+    pub(crate) reused: Vec<FileInfo>,
+    /// Files to analyze.
+    pub(crate) stale: Vec<Utf8PathBuf>,
+    /// Stamps of all files, taken before any of them is read.
+    pub(crate) stamps: FxHashMap<Utf8PathBuf, FileStamp>,
+}
+
+impl CacheSplit {
+    /// Splits the walked `paths` into files reused from `cache` and files to
+    /// analyze.
+    pub(crate) fn new(cache: &PersistentCache, paths: Vec<Utf8PathBuf>) -> Self {
+        let split = cache.split(paths);
+        if !split.reused.is_empty() {
+            info!(
+                reused = split.reused.len(),
+                stale = split.stale.len(),
+                "Reusing unchanged files from the scan cache"
+            );
+        }
+        split
+    }
+}
+
+/// Analysis results of a previous scan, loaded from the cache directory or
+/// kept in memory.
+#[derive(Debug)]
+pub(crate) struct PersistentCache {
+    /// Cache file for the scan roots, or `None` for a cache only in memory.
+    path: Option<Utf8PathBuf>,
+    /// Fingerprint of the current analysis settings.
+    fingerprint: u64,
+    /// Cached results by file path.
+    files: FxHashMap<Utf8PathBuf, CachedFile>,
+}
+
+impl PersistentCache {
+    /// Loads the cache of scans of `roots` from `dir`.
+    ///
+    /// Each set of scan roots has its own file. A missing or unreadable
+    /// file, or one saved with a different `fingerprint`, yields an empty
+    /// cache.
+    pub(crate) fn load<'a>(
+        dir: &Utf8Path,
+        roots: impl IntoIterator<Item = &'a Utf8Path>,
+        fingerprint: u64,
+    ) -> Self {
+        let path = roots_file(dir, roots, "scan");
+
+        let files = match fs::read(&path) {
+            Ok(bytes) => match rmp_serde::from_slice::<CacheContents>(&bytes) {
+                Ok(contents)
+                    if contents.version == FORMAT_VERSION
+                        && contents.fingerprint == fingerprint =>
+                {
+                    contents
+                        .files
+                        .into_iter()
+                        .map(|cached| (cached.file.path.clone(), cached))
+                        .collect()
+                }
+                Ok(_) => {
+                    debug!(path = %path, "Scan cache is outdated; analyzing every file");
+                    FxHashMap::default()
+                }
+                Err(e) => {
+                    warn!(path = %path, error = %e, "Ignoring unreadable scan cache");
+                    FxHashMap::default()
+                }
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => FxHashMap::default(),
+            Err(e) => {
+                warn!(path = %path, error = %e, "Ignoring unreadable scan cache");
+                FxHashMap::default()
+            }
+        };
+
+        Self {
+            path: Some(path),
+            fingerprint,
+            files,
+        }
+    }
+
+    /// Creates an empty cache that is never saved.
+    pub(crate) fn in_memory() -> Self {
+        Self {
+            path: None,
+            fingerprint: 0,
+            files: FxHashMap::default(),
+        }
+    }
+
+    /// Returns `true` if the cache is saved to a file.
+    pub(crate) const fn is_persistent(&self) -> bool {
+        self.path.is_some()
+    }
+
+    /// Adds the stamped analyses of files still in memory, replacing any
+    /// loaded for the same paths.
+    pub(crate) fn remember(&mut self, files: Vec<(FileStamp, FileInfo)>) {
+        self.files.extend(
+            files
+                .into_iter()
+                .map(|(stamp, file)| (file.path.clone(), CachedFile { stamp, file })),
+        );
+    }
+
+    /// Returns the number of cached files.
+    pub(crate) fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Returns the cached analyses, changed files included.
+    pub(crate) fn files(&self) -> impl Iterator<Item = &FileInfo> {
+        self.files.values().map(|cached| &cached.file)
+    }
+
+    /// Returns the cached analysis of `path` if the file is unchanged.
+    ///
+    /// A file with the cached size and modification time is unchanged;
+    /// otherwise its contents are hashed and compared with the cached hash.
+    pub(crate) fn lookup(&self, path: &Utf8Path, stamp: FileStamp) -> Option<FileInfo> {
+        let cached = self.files.get(path)?;
+        if cached.stamp == stamp {
+            return Some(cached.file.clone());
+        }
+
+        let contents = fs::read_to_string(path).ok()?;
+        (hash_content(&contents) == cached.file.content_hash).then(|| cached.file.clone())
+    }
+
+    /// Looks up `paths` in parallel, splitting them into reused and stale
+    /// files.
+    ///
+    /// Stamps of files modified just before the lookup are kept
+    /// [settled](FileStamp::settled).
+    pub(crate) fn split(&self, paths: Vec<Utf8PathBuf>) -> CacheSplit {
+        let now_ns = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |now| u64::try_from(now.as_nanos()).unwrap_or(u64::MAX));
+        let looked_up: Vec<_> = paths
+            .into_par_iter()
+            .map(|path| {
+                let stamp = FileStamp::of(&path);
+                let cached = stamp.and_then(|stamp| self.lookup(&path, stamp));
+                (path, stamp, cached)
+            })
+            .collect();
+
+        let mut split = CacheSplit::default();
+        for (path, stamp, cached) in looked_up {
+            if let Some(stamp) = stamp {
+                split.stamps.insert(path.clone(), stamp.settled(now_ns));
+            }
+            match cached {
+                Some(file) => split.reused.push(file),
+                None => split.stale.push(path),
+            }
+        }
+        split
+    }
+
+    /// Replaces the cache file with `files`; a cache in memory isn't saved.
+    ///
+    /// The file is written next to its final path and renamed into place,
+    /// creating the cache directory if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be encoded, written or renamed.
+    pub(crate) fn save(&self, files: Vec<(FileStamp, FileInfo)>) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let contents = CacheContents {
+            version: FORMAT_VERSION,
+            fingerprint: self.fingerprint,
+            files: files
+                .into_iter()
+                .map(|(stamp, file)| CachedFile { stamp, file })
+                .collect(),
+        };
+        let bytes = rmp_serde::to_vec_named(&contents).map_err(io::Error::other)?;
+        write_atomically(path, &bytes)
+    }
+}
+
+/// Contents of a file written by [`Scanner::save_cache`], restored by
+/// [`Scanner::load_cache`].
+///
+/// Unlike the cache directory's files, a snapshot holds every cached file,
+/// so a later session can show all of the last results at once. Only the
+/// files with stamps may be reused by a scan, and only if the fingerprint
+/// still matches.
+///
+/// [`Scanner::save_cache`]: crate::Scanner::save_cache
+/// [`Scanner::load_cache`]: crate::Scanner::load_cache
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Snapshot {
+    version: u32,
+    /// Fingerprint of the analysis settings the files were analyzed with.
+    pub(crate) fingerprint: u64,
+    /// Every cached file.
+    pub(crate) files: Vec<FileInfo>,
+    /// Stamps of the files a later scan may reuse.
+    pub(crate) stamps: Vec<(Utf8PathBuf, FileStamp)>,
+}
+
+impl Snapshot {
+    /// Creates a snapshot of `files` analyzed with the settings of
+    /// `fingerprint`.
+    pub(crate) const fn new(
+        fingerprint: u64,
+        files: Vec<FileInfo>,
+        stamps: Vec<(Utf8PathBuf, FileStamp)>,
+    ) -> Self {
+        Self {
+            version: FORMAT_VERSION,
+            fingerprint,
+            files,
+            stamps,
+        }
+    }
+
+    /// Reads the snapshot at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or decoded, or was
+    /// written with another layout.
+    pub(crate) fn read(path: &Utf8Path) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        let snapshot: Self = rmp_serde::from_slice(&bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if snapshot.version != FORMAT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported scan cache version {}", snapshot.version),
+            ));
+        }
+        Ok(snapshot)
+    }
+
+    /// Replaces the file at `path` with the snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be encoded, written or renamed.
+    pub(crate) fn write(&self, path: &Utf8Path) -> io::Result<()> {
+        let bytes = rmp_serde::to_vec_named(self).map_err(io::Error::other)?;
+        write_atomically(path, &bytes)
+    }
+}
+
+/// Returns the file named `stem` in `dir` for scans of `roots`.
+///
+/// Each set of scan roots has its own files.
+pub(crate) fn roots_file<'a>(
+    dir: &Utf8Path,
+    roots: impl IntoIterator<Item = &'a Utf8Path>,
+    stem: &str,
+) -> Utf8PathBuf {
+    let mut hasher = FxHasher::default();
+    for root in roots {
+        root.hash(&mut hasher);
+    }
+    dir.join(format!("{stem}-{:016x}.msgpack", hasher.finish()))
+}
+
+/// Writes `bytes` next to `path` and renames the file into place, creating
+/// the parent directory if needed.
+pub(crate) fn write_atomically(path: &Utf8Path, bytes: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    let temp_path = Utf8PathBuf::from(format!("{path}.tmp"));
+    fs::write(&temp_path, bytes)?;
+    fs::rename(&temp_path, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp_path);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ch_core::{FileId, MigrationStatus};
+
+    #[test]
+    fn test_persistent_cache_round_trip() {
+        let (_temp, dir) = crate::tests::temp_root();
+        let path = dir.join("a.ts");
+        let contents = "export const a = 1;\n";
+        assert!(fs::write(&path, contents).is_ok());
+        let stamp = FileStamp::of(&path);
+        assert!(stamp.is_some());
+        let Some(stamp) = stamp else {
+            return;
+        };
+
+        let mut file = FileInfo::new(FileId::new(1), path.clone());
+        file.status = MigrationStatus::Legacy;
+        file.content_hash = hash_content(contents);
+        let cache_dir = dir.join("cache");
+        let empty = PersistentCache::load(&cache_dir, [dir.as_path()], 7);
+        assert_eq!(empty.len(), 0);
+        assert!(empty.save(vec![(stamp, file)]).is_ok());
+
+        let cache = PersistentCache::load(&cache_dir, [dir.as_path()], 7);
+        assert_eq!(cache.len(), 1);
+        let hit = cache.lookup(&path, stamp);
+        assert_eq!(hit.map(|file| file.status), Some(MigrationStatus::Legacy));
+
+        // A different stamp with the same contents is still a hit
+        let touched = FileStamp {
+            modified_ns: stamp.modified_ns + 1,
+            ..stamp
+        };
+        assert!(cache.lookup(&path, touched).is_some());
+        assert!(fs::write(&path, "export const a = 2;\n").is_ok());
+        assert!(cache.lookup(&path, touched).is_none());
+
+        let load = |roots: &[&Utf8Path], fingerprint| {
+            PersistentCache::load(&cache_dir, roots.iter().copied(), fingerprint).len()
+        };
+        assert_eq!(load(&[&dir], 8), 0, "other settings");
+        assert_eq!(load(&[&path], 7), 0, "other root");
+        assert_eq!(load(&[&dir, &path], 7), 0, "other roots");
+    }
+
+    #[test]
+    fn test_in_memory_cache_reuses_remembered_files() {
+        let (_temp, dir) = crate::tests::temp_root();
+        let paths = [dir.join("a.ts"), dir.join("b.ts"), dir.join("c.ts")];
+        for path in &paths {
+            assert!(fs::write(path, "export const a = 1;\n").is_ok());
+        }
+        let remembered = paths[..2]
+            .iter()
+            .filter_map(|path| {
+                let mut file = FileInfo::new(FileId::new(1), path.clone());
+                file.content_hash = hash_content("export const a = 1;\n");
+                Some((FileStamp::of(path)?, file))
+            })
+            .collect();
+        assert!(fs::write(&paths[1], "export const b = 2;\n").is_ok());
+
+        let mut cache = PersistentCache::in_memory();
+        assert!(!cache.is_persistent());
+        cache.remember(remembered);
+        let split = CacheSplit::new(&cache, paths.to_vec());
+        let reused: Vec<_> = split.reused.iter().map(|file| file.path.clone()).collect();
+        assert_eq!(reused, [paths[0].clone()]);
+        assert_eq!(split.stale, paths[1..]);
+        assert_eq!(split.stamps.len(), 3);
+        assert!(split.stamps.values().all(|stamp| stamp.modified_ns == 0), "just written");
+        assert!(cache.save(Vec::new()).is_ok());
+    }
+}
@@ -0,0 +1,389 @@
+//! Migration ordering based on file and model dependencies.
+//!
+//! [`plan_migration`] orders the files that still need migration into waves.
+//! A file depends on every other pending file it imports with a relative
+//! path: the imported file may expose legacy model types in its own exports,
+//! so it is migrated first. Files in the same wave don't depend on each
+//! other and can be migrated independently.
+//!
+//! Files importing a legacy model without a `shared_2023/` equivalent can't
+//! be migrated until that model is ported. They are left out of the waves,
+//! together with every file depending on them, and the missing models are
+//! listed as blockers, most shared first.
+//!
+//! # Examples
+//!
+//! ```
+//! use camino::Utf8PathBuf;
+//! use ch_core::{FileId, FileInfo, MigrationStatus, ModelRegistry};
+//! use ch_scanner::plan_migration;
+//!
+//! let mut file = FileInfo::new(FileId::new(1), Utf8PathBuf::from("src/app/a.ts"));
+//! file.status = MigrationStatus::Legacy;
+//!
+//! let plan = plan_migration(&[file], &ModelRegistry::new());
+//! assert_eq!(plan.waves.len(), 1);
+//! assert_eq!(plan.waves[0].files[0].path, "src/app/a.ts");
+//! ```
+
+use std::collections::BTreeMap;
+
+use camino::{Utf8Component, Utf8Path, Utf8PathBuf};
+use ch_core::{FileInfo, FxHashMap, ModelRegistry};
+use serde::{Deserialize, Serialize};
+
+/// A file in the migration plan.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedFile {
+    /// Path of the file.
+    pub path: Utf8PathBuf,
+
+    /// Legacy model names the file imports, sorted.
+    pub legacy_models: Vec<String>,
+
+    /// Pending files this file imports, which are migrated before it.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub depends_on: Vec<Utf8PathBuf>,
+
+    /// Legacy model names without a modern equivalent (blocked files only).
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub missing_models: Vec<String>,
+}
+
+/// A group of files that can be migrated independently of each other.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationWave {
+    /// Files in the wave, sorted by path.
+    pub files: Vec<PlannedFile>,
+
+    /// Whether this is the last wave, holding files in (or depending on) an
+    /// import cycle, which have to be migrated together.
+    pub cyclic: bool,
+}
+
+/// A legacy model that has to be ported before some files can be migrated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelBlocker {
+    /// Legacy model name.
+    pub name: String,
+
+    /// Number of files importing it.
+    pub files: usize,
+}
+
+/// An ordered migration plan.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationPlan {
+    /// Waves in migration order.
+    pub waves: Vec<MigrationWave>,
+
+    /// Files waiting for models to be ported, sorted by path.
+    pub blocked: Vec<PlannedFile>,
+
+    /// Models without a modern equivalent, sorted by the number of files
+    /// they block (most first), then by name.
+    pub blockers: Vec<ModelBlocker>,
+}
+
+impl MigrationPlan {
+    /// Returns the number of files that can be migrated now, in any wave.
+    #[must_use]
+    pub fn planned_count(&self) -> usize {
+        self.waves.iter().map(|wave| wave.files.len()).sum()
+    }
+}
+
+/// Orders the files needing migration into waves.
+///
+/// See the [module documentation](self) for how dependencies are found.
+///
+/// # Arguments
+///
+/// * `files` - The scanned files; files not needing migration are skipped
+/// * `registry` - Model registry used to find legacy models without a port
+#[must_use]
+pub fn plan_migration(files: &[FileInfo], registry: &ModelRegistry) -> MigrationPlan {
+    let mut pending: Vec<&FileInfo> = files.iter().filter(|file| file.needs_migration()).collect();
+    pending.sort_unstable_by(|a, b| a.path.cmp(&b.path));
+
+    let index: FxHashMap<&Utf8Path, usize> = pending
+        .iter()
+        .enumerate()
+        .map(|(i, file)| (file.path.as_path(), i))
+        .collect();
+
+    let deps: Vec<Vec<usize>> = pending
+        .iter()
+        .enumerate()
+        .map(|(i, file)| {
+            let mut deps: Vec<usize> = file
+                .imports
+                .iter()
+                .filter_map(|import| resolve_import(&file.path, &import.path, &index))
+                .filter(|&dep| dep != i)
+                .collect();
+            deps.sort_unstable();
+            deps.dedup();
+            deps
+        })
+        .collect();
+
+    let mut dependents = vec![Vec::new(); pending.len()];
+    for (i, file_deps) in deps.iter().enumerate() {
+        for &dep in file_deps {
+            dependents[dep].push(i);
+        }
+    }
+
+    let missing: Vec<Vec<String>> = pending
+        .iter()
+        .map(|file| sorted_names(registry.missing_equivalents(file)))
+        .collect();
+
+    // Files with missing models block everything that depends on them
+    let mut blocked: Vec<bool> = missing.iter().map(|names| !names.is_empty()).collect();
+    let mut stack: Vec<usize> = (0..pending.len()).filter(|&i| blocked[i]).collect();
+    while let Some(i) = stack.pop() {
+        for &dependent in &dependents[i] {
+            if !blocked[dependent] {
+                blocked[dependent] = true;
+                stack.push(dependent);
+            }
+        }
+    }
+
+    let planned_file = |i: usize| PlannedFile {
+        path: pending[i].path.clone(),
+        legacy_models: sorted_names(
+            pending[i]
+                .legacy_imports()
+                .flat_map(|import| import.used_names().iter().map(String::as_str))
+                .collect(),
+        ),
+        depends_on: deps[i].iter().map(|&dep| pending[dep].path.clone()).collect(),
+        missing_models: missing[i].clone(),
+    };
+
+    // Layer the unblocked files: each wave holds the files whose
+    // dependencies are all in earlier waves
+    let mut remaining: Vec<usize> = deps.iter().map(Vec::len).collect();
+    let mut current: Vec<usize> = (0..pending.len())
+        .filter(|&i| !blocked[i] && remaining[i] == 0)
+        .collect();
+    let mut placed = vec![false; pending.len()];
+    let mut waves = Vec::new();
+
+    while !current.is_empty() {
+        let mut next = Vec::new();
+        for &i in &current {
+            placed[i] = true;
+            for &dependent in &dependents[i] {
+                remaining[dependent] -= 1;
+                if remaining[dependent] == 0 {
+                    next.push(dependent);
+                }
+            }
+        }
+        current.sort_unstable();
+        waves.push(MigrationWave {
+            files: current.iter().map(|&i| planned_file(i)).collect(),
+            cyclic: false,
+        });
+        current = next;
+    }
+
+    // Whatever is left is part of, or depends on, an import cycle
+    let cyclic: Vec<PlannedFile> = (0..pending.len())
+        .filter(|&i| !blocked[i] && !placed[i])
+        .map(planned_file)
+        .collect();
+    if !cyclic.is_empty() {
+        waves.push(MigrationWave {
+            files: cyclic,
+            cyclic: true,
+        });
+    }
+
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for name in missing.iter().flatten() {
+        *counts.entry(name.as_str()).or_default() += 1;
+    }
+    let mut blockers: Vec<ModelBlocker> = counts
+        .into_iter()
+        .map(|(name, files)| ModelBlocker {
+            name: name.to_owned(),
+            files,
+        })
+        .collect();
+    blockers.sort_by_key(|blocker| std::cmp::Reverse(blocker.files));
+
+    MigrationPlan {
+        waves,
+        blocked: (0..pending.len())
+            .filter(|&i| blocked[i])
+            .map(planned_file)
+            .collect(),
+        blockers,
+    }
+}
+
+/// Returns `names` sorted and deduplicated.
+fn sorted_names(mut names: Vec<&str>) -> Vec<String> {
+    names.sort_unstable();
+    names.dedup();
+    names.into_iter().map(str::to_owned).collect()
+}
+
+/// Resolves a relative import of `from` to one of the indexed files.
+///
+/// Tries the module path with a `.ts` extension, as written, and as a
+/// directory with an `index.ts`. Package imports are not resolved.
+fn resolve_import(
+    from: &Utf8Path,
+    specifier: &str,
+    index: &FxHashMap<&Utf8Path, usize>,
+) -> Option<usize> {
+    let specifier = specifier.trim_matches(|c| matches!(c, '\'' | '"' | '`'));
+    if !specifier.starts_with('.') {
+        return None;
+    }
+
+    let mut base = Utf8PathBuf::new();
+    for component in from.parent()?.join(specifier).components() {
+        match component {
+            Utf8Component::CurDir => {}
+            Utf8Component::ParentDir => {
+                if !base.pop() {
+                    return None;
+                }
+            }
+            other => base.push(other),
+        }
+    }
+
+    [
+        Utf8PathBuf::from(format!("{base}.ts")),
+        base.join("index.ts"),
+        base,
+    ]
+    .iter()
+    .find_map(|candidate| index.get(candidate.as_path()).copied())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ch_core::{
+        FileId, ImportInfo, ImportKind, MigrationStatus, ModelDefinition, ModelSource,
+        SourceLocation,
+    };
+
+    fn make_file(path: &str, imports: &[(&str, &str, Option<ModelSource>)]) -> FileInfo {
+        let mut file = FileInfo::new(FileId::new(0), Utf8PathBuf::from(path));
+        file.status = MigrationStatus::Legacy;
+        file.imports = imports
+            .iter()
+            .map(|(path, name, source)| {
+                ImportInfo::new(
+                    *path,
+                    ImportKind::Named,
+                    [(*name).to_owned()].into_iter().collect(),
+                    *source,
+                    SourceLocation::default(),
+                )
+            })
+            .collect();
+        file
+    }
+
+    fn registry() -> ModelRegistry {
+        let mut registry = ModelRegistry::new();
+        for (name, source) in [
+            ("Job", ModelSource::SharedLegacy),
+            ("Job", ModelSource::Shared2023),
+            ("Contract", ModelSource::SharedLegacy),
+        ] {
+            let mut definition = ModelDefinition::new(name, source, "models/model.ts");
+            definition.add_export(name);
+            registry.register(definition);
+        }
+        registry
+    }
+
+    fn wave_paths(plan: &MigrationPlan) -> Vec<Vec<&str>> {
+        plan.waves
+            .iter()
+            .map(|wave| wave.files.iter().map(|file| file.path.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_plan_orders_imported_files_first() {
+        let legacy = Some(ModelSource::SharedLegacy);
+        let files = vec![
+            make_file("src/app/a.ts", &[("'./b'", "B", None), ("'../shared/job'", "Job", legacy)]),
+            make_file("src/app/b.ts", &[("'./lib/index'", "C", None)]),
+            make_file("src/app/lib/index.ts", &[("'../../shared/job'", "Job", legacy)]),
+            make_file("src/app/d.ts", &[("'@angular/core'", "Component", None)]),
+        ];
+
+        let plan = plan_migration(&files, &registry());
+
+        assert_eq!(
+            wave_paths(&plan),
+            [
+                vec!["src/app/d.ts", "src/app/lib/index.ts"],
+                vec!["src/app/b.ts"],
+                vec!["src/app/a.ts"],
+            ]
+        );
+        assert_eq!(plan.waves[2].files[0].depends_on, ["src/app/b.ts"]);
+        assert_eq!(plan.waves[2].files[0].legacy_models, ["Job"]);
+        assert!(plan.blocked.is_empty());
+    }
+
+    #[test]
+    fn test_plan_blocks_unported_models_and_dependents() {
+        let legacy = Some(ModelSource::SharedLegacy);
+        let files = vec![
+            make_file("src/a.ts", &[("'./b.ts'", "B", None)]),
+            make_file("src/b.ts", &[("'./shared/contract'", "Contract", legacy)]),
+            make_file("src/c.ts", &[("'./shared/contract'", "Contract", legacy)]),
+            make_file("src/d.ts", &[("'./shared/job'", "Job", legacy)]),
+        ];
+
+        let plan = plan_migration(&files, &registry());
+
+        assert_eq!(wave_paths(&plan), [vec!["src/d.ts"]]);
+        let blocked: Vec<_> = plan.blocked.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(blocked, ["src/a.ts", "src/b.ts", "src/c.ts"]);
+        assert!(plan.blocked[0].missing_models.is_empty());
+        assert_eq!(plan.blocked[1].missing_models, ["Contract"]);
+        assert_eq!(
+            plan.blockers,
+            [ModelBlocker {
+                name: "Contract".to_owned(),
+                files: 2,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_plan_groups_import_cycles() {
+        let files = vec![
+            make_file("src/a.ts", &[("'./b'", "B", None)]),
+            make_file("src/b.ts", &[("'./a'", "A", None)]),
+            make_file("src/c.ts", &[("'./a'", "A", None)]),
+            make_file("src/d.ts", &[]),
+        ];
+
+        let plan = plan_migration(&files, &registry());
+
+        assert_eq!(
+            wave_paths(&plan),
+            [vec!["src/d.ts"], vec!["src/a.ts", "src/b.ts", "src/c.ts"]]
+        );
+        assert!(plan.waves[1].cyclic);
+        assert_eq!(plan.planned_count(), 4);
+    }
+}
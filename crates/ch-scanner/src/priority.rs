@@ -0,0 +1,147 @@
+//! Ordering of streaming scans so likely legacy files come first.
+//!
+//! A streaming scan of a large tree takes a while to reach the files that
+//! still need migrating. [`ScanPriority`] moves files likely to need it to
+//! the front:
+//!
+//! 1. files under the configured priority directories, in the order the
+//!    directories were given
+//! 2. files in directories where the previous scan found files needing
+//!    migration, most such files first
+//!
+//! Other files keep their walk order behind them.
+
+use std::cmp::Reverse;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use ch_core::{FileInfo, FxHashMap};
+
+/// Rank of a prioritized file; lower ranks are scanned first.
+type Rank = (usize, Reverse<usize>);
+
+/// Decides which files a streaming scan processes first.
+#[derive(Debug, Default)]
+pub(crate) struct ScanPriority {
+    /// Directories whose files come first, most important first.
+    dirs: Vec<Utf8PathBuf>,
+    /// Number of files needing migration in the previous scan, by directory.
+    legacy_hits: FxHashMap<Utf8PathBuf, usize>,
+}
+
+impl ScanPriority {
+    /// Creates a priority for `dirs`, ranking other directories by the files
+    /// needing migration among `previous`.
+    pub(crate) fn new<'a>(
+        dirs: Vec<Utf8PathBuf>,
+        previous: impl IntoIterator<Item = &'a FileInfo>,
+    ) -> Self {
+        let mut legacy_hits = FxHashMap::default();
+        for file in previous {
+            if let Some(dir) = file.path.parent().filter(|_| file.needs_migration()) {
+                *legacy_hits.entry(dir.to_owned()).or_insert(0) += 1;
+            }
+        }
+        Self { dirs, legacy_hits }
+    }
+
+    /// Returns `true` if no file is prioritized.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.dirs.is_empty() && self.legacy_hits.is_empty()
+    }
+
+    /// Returns the rank of `path`, or `None` if it isn't prioritized.
+    fn rank(&self, path: &Utf8Path) -> Option<Rank> {
+        let dir = self.dirs.iter().position(|dir| path.starts_with(dir));
+        let hits = path.parent().and_then(|parent| self.legacy_hits.get(parent)).copied();
+        if dir.is_none() && hits.is_none() {
+            return None;
+        }
+        Some((dir.unwrap_or(self.dirs.len()), Reverse(hits.unwrap_or(0))))
+    }
+
+    /// Splits `items` into the prioritized ones, in scan order, and the
+    /// rest, in their original order.
+    pub(crate) fn partition<T>(
+        &self,
+        items: Vec<T>,
+        path: impl Fn(&T) -> &Utf8Path,
+    ) -> (Vec<T>, Vec<T>) {
+        if self.is_empty() {
+            return (Vec::new(), items);
+        }
+
+        let mut first = Vec::new();
+        let mut rest = Vec::new();
+        for item in items {
+            match self.rank(path(&item)) {
+                Some(rank) => first.push((rank, item)),
+                None => rest.push(item),
+            }
+        }
+        first.sort_by_key(|(rank, _)| *rank);
+        (first.into_iter().map(|(_, item)| item).collect(), rest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ch_core::{FileId, MigrationStatus};
+
+    fn file(path: &str, status: MigrationStatus) -> FileInfo {
+        let mut file = FileInfo::new(FileId::new(1), Utf8PathBuf::from(path));
+        file.status = status;
+        file
+    }
+
+    fn paths(paths: &[&str]) -> Vec<Utf8PathBuf> {
+        paths.iter().map(Utf8PathBuf::from).collect()
+    }
+
+    #[test]
+    fn test_priority_dirs_come_first_in_order() {
+        let priority =
+            ScanPriority::new(paths(&["app/billing", "app/jobs"]), std::iter::empty());
+        let (first, rest) = priority.partition(
+            paths(&["app/a.ts", "app/jobs/j.ts", "app/billing/b.ts", "app/z.ts"]),
+            Utf8PathBuf::as_path,
+        );
+        assert_eq!(first, paths(&["app/billing/b.ts", "app/jobs/j.ts"]));
+        assert_eq!(rest, paths(&["app/a.ts", "app/z.ts"]));
+    }
+
+    #[test]
+    fn test_previous_legacy_hits_rank_directories() {
+        let previous = [
+            file("app/few/a.ts", MigrationStatus::Legacy),
+            file("app/many/a.ts", MigrationStatus::Legacy),
+            file("app/many/b.ts", MigrationStatus::Partial),
+            file("app/done/a.ts", MigrationStatus::Migrated),
+        ];
+        let priority = ScanPriority::new(paths(&["app/pinned"]), &previous);
+        let (first, rest) = priority.partition(
+            paths(&[
+                "app/done/a.ts",
+                "app/few/a.ts",
+                "app/many/c.ts",
+                "app/pinned/p.ts",
+                "app/many/a.ts",
+            ]),
+            Utf8PathBuf::as_path,
+        );
+        assert_eq!(
+            first,
+            paths(&["app/pinned/p.ts", "app/many/c.ts", "app/many/a.ts", "app/few/a.ts"])
+        );
+        assert_eq!(rest, paths(&["app/done/a.ts"]));
+    }
+
+    #[test]
+    fn test_empty_priority_keeps_order() {
+        let priority = ScanPriority::default();
+        assert!(priority.is_empty());
+        let (first, rest) = priority.partition(paths(&["b.ts", "a.ts"]), Utf8PathBuf::as_path);
+        assert!(first.is_empty());
+        assert_eq!(rest, paths(&["b.ts", "a.ts"]));
+    }
+}
@@ -0,0 +1,190 @@
+//! Per-phase timing of a scan.
+//!
+//! [`Scanner::scan_profiled`](crate::Scanner::scan_profiled) records how long
+//! each phase of a scan took, along with the analysis time of every file, so
+//! slow scans can be attributed to the walk, the registry, parsing, or cache
+//! insertion, and to the individual files that dominate parsing.
+//!
+//! Parsing runs in parallel, so [`ScanProfile::parse`] is wall-clock time
+//! while the per-file durations add up to CPU time across all threads.
+//!
+//! Every scanner also keeps the analysis time of each file it analyzed,
+//! whichever way it scanned, so
+//! [`Scanner::slowest_files`](crate::Scanner::slowest_files) can point at
+//! pathological inputs without a profiled scan.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//! use ch_scanner::{FileTiming, ScanProfile};
+//!
+//! let mut profile = ScanProfile::default();
+//! profile.record_file("src/a.ts".into(), Duration::from_millis(3));
+//! profile.record_file("src/b.ts".into(), Duration::from_millis(9));
+//! profile.finish();
+//!
+//! assert_eq!(profile.slowest(1)[0].path, "src/b.ts");
+//! ```
+
+use std::time::Duration;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use ch_core::FxHashMap;
+use parking_lot::RwLock;
+
+/// Analysis time of a single file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileTiming {
+    /// Path of the analyzed file.
+    pub path: Utf8PathBuf,
+
+    /// Time spent reading, parsing and analyzing the file.
+    pub duration: Duration,
+}
+
+/// Time spent in each phase of a scan.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScanProfile {
+    /// Time spent building the model registry, if the scanner built one.
+    pub registry: Option<Duration>,
+
+    /// Time spent walking the directory tree.
+    pub walk: Duration,
+
+    /// Wall-clock time spent analyzing files in parallel.
+    pub parse: Duration,
+
+    /// Time spent inserting results into the cache.
+    pub cache_insert: Duration,
+
+    /// Per-file analysis times, slowest first once [`finish`](Self::finish)ed.
+    pub files: Vec<FileTiming>,
+}
+
+impl ScanProfile {
+    /// Records the analysis time of a file.
+    pub fn record_file(&mut self, path: Utf8PathBuf, duration: Duration) {
+        self.files.push(FileTiming { path, duration });
+    }
+
+    /// Sorts the per-file timings, slowest first.
+    pub fn finish(&mut self) {
+        sort_slowest_first(&mut self.files);
+    }
+
+    /// Returns up to `n` of the slowest files.
+    #[must_use]
+    pub fn slowest(&self, n: usize) -> &[FileTiming] {
+        &self.files[..n.min(self.files.len())]
+    }
+
+    /// Returns the total time across all phases.
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.registry.unwrap_or_default() + self.walk + self.parse + self.cache_insert
+    }
+}
+
+/// Sorts `files` slowest first, by path among equally slow ones.
+fn sort_slowest_first(files: &mut [FileTiming]) {
+    files.sort_by(|a, b| b.duration.cmp(&a.duration).then_with(|| a.path.cmp(&b.path)));
+}
+
+/// Latest analysis time of each file.
+///
+/// Analysis threads buffer their own timings and merge them in one batch
+/// when they finish, so recording doesn't contend on the lock per file.
+#[derive(Debug, Default)]
+pub(crate) struct FileTimings {
+    /// Analysis time by file path.
+    files: RwLock<FxHashMap<Utf8PathBuf, Duration>>,
+}
+
+impl FileTimings {
+    /// Records the analysis times of `files`, replacing any earlier ones.
+    pub(crate) fn record_all(&self, files: impl IntoIterator<Item = (Utf8PathBuf, Duration)>) {
+        self.files.write().extend(files);
+    }
+
+    /// Forgets the timing of `path`.
+    pub(crate) fn remove(&self, path: &Utf8Path) {
+        self.files.write().remove(path);
+    }
+
+    /// Forgets the timings of the files under `dir`.
+    pub(crate) fn remove_prefix(&self, dir: &Utf8Path) {
+        self.files.write().retain(|path, _| !path.starts_with(dir));
+    }
+
+    /// Forgets every timing.
+    pub(crate) fn clear(&self) {
+        self.files.write().clear();
+    }
+
+    /// Returns up to `n` of the slowest files, slowest first.
+    pub(crate) fn slowest(&self, n: usize) -> Vec<FileTiming> {
+        let mut files: Vec<_> = self
+            .files
+            .read()
+            .iter()
+            .map(|(path, duration)| FileTiming {
+                path: path.clone(),
+                duration: *duration,
+            })
+            .collect();
+        sort_slowest_first(&mut files);
+        files.truncate(n);
+        files
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_slowest_sorted() {
+        let mut profile = ScanProfile::default();
+        profile.record_file("a.ts".into(), Duration::from_millis(2));
+        profile.record_file("b.ts".into(), Duration::from_millis(7));
+        profile.record_file("c.ts".into(), Duration::from_millis(7));
+        profile.finish();
+
+        let slowest: Vec<_> = profile.slowest(2).iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(slowest, ["b.ts", "c.ts"]);
+        assert_eq!(profile.slowest(10).len(), 3);
+    }
+
+    #[test]
+    fn test_file_timings_keep_latest() {
+        let timings = FileTimings::default();
+        timings.record_all([
+            ("src/a.ts".into(), Duration::from_millis(9)),
+            ("src/b.ts".into(), Duration::from_millis(4)),
+        ]);
+        timings.record_all([
+            ("lib/c.ts".into(), Duration::from_millis(6)),
+            ("src/a.ts".into(), Duration::from_millis(1)),
+        ]);
+
+        let slowest: Vec<_> = timings.slowest(2).into_iter().map(|f| f.path).collect();
+        assert_eq!(slowest, ["lib/c.ts", "src/b.ts"]);
+
+        timings.remove_prefix("src".into());
+        timings.remove("lib/c.ts".into());
+        assert!(timings.slowest(10).is_empty());
+    }
+
+    #[test]
+    fn test_profile_total() {
+        let profile = ScanProfile {
+            registry: Some(Duration::from_millis(5)),
+            walk: Duration::from_millis(1),
+            parse: Duration::from_millis(10),
+            cache_insert: Duration::from_millis(2),
+            files: Vec::new(),
+        };
+        assert_eq!(profile.total(), Duration::from_millis(18));
+    }
+}
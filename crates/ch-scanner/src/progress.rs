@@ -0,0 +1,146 @@
+//! Throttled progress updates for streaming scans.
+//!
+//! A streaming scan sends a [`ScanUpdate::FileScanned`] for every file,
+//! which on trees of small files is thousands of messages a second. A
+//! consumer that only draws a progress bar can wait for
+//! [`ScanUpdate::Progress`] instead, which carries the statistics so far and
+//! is sent once [`ProgressInterval::files`] files have finished or
+//! [`ProgressInterval::interval`] has passed since the last one, whichever
+//! comes first. Either is checked as files finish, so no update is sent
+//! while a single slow file is being analyzed.
+//!
+//! [`ScanUpdate::FileScanned`]: crate::ScanUpdate::FileScanned
+//! [`ScanUpdate::Progress`]: crate::ScanUpdate::Progress
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// How often a streaming scan sends a progress update.
+///
+/// The default sends one every 256 files or every 100 milliseconds.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use ch_scanner::ProgressInterval;
+///
+/// let interval = ProgressInterval::default()
+///     .with_files(1000)
+///     .with_interval(Duration::from_millis(250));
+/// assert_eq!(interval.files, 1000);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProgressInterval {
+    /// Files finished between updates; `0` sends one after every file.
+    pub files: u64,
+
+    /// Time between updates while files keep finishing.
+    pub interval: Duration,
+}
+
+impl Default for ProgressInterval {
+    fn default() -> Self {
+        Self {
+            files: 256,
+            interval: Duration::from_millis(100),
+        }
+    }
+}
+
+impl ProgressInterval {
+    /// Sets the number of files finished between updates.
+    ///
+    /// # Arguments
+    ///
+    /// * `files` - Files per update; `0` sends one after every file
+    #[must_use]
+    pub const fn with_files(mut self, files: u64) -> Self {
+        self.files = files;
+        self
+    }
+
+    /// Sets the time between updates.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - Longest wait for an update while files keep finishing
+    #[must_use]
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+}
+
+/// Decides when the next progress update is due, shared by analysis threads.
+#[derive(Debug)]
+pub(crate) struct ProgressTicker {
+    /// Files per update.
+    files: u64,
+    /// Time between updates, in nanoseconds.
+    interval_ns: u64,
+    /// When the scan started.
+    started: Instant,
+    /// Nanoseconds after `started` of the last update.
+    last_ns: AtomicU64,
+    /// Files finished since the last update.
+    since: AtomicU64,
+}
+
+impl ProgressTicker {
+    /// Creates a ticker sending updates at `interval`.
+    pub(crate) fn new(interval: ProgressInterval) -> Self {
+        Self {
+            files: interval.files,
+            interval_ns: u64::try_from(interval.interval.as_nanos()).unwrap_or(u64::MAX),
+            started: Instant::now(),
+            last_ns: AtomicU64::new(0),
+            since: AtomicU64::new(0),
+        }
+    }
+
+    /// Counts a finished file, returning `true` if an update is due.
+    ///
+    /// Of threads finishing files at the same time, only one is told to
+    /// send the update.
+    pub(crate) fn tick(&self) -> bool {
+        let files = self.since.fetch_add(1, Ordering::Relaxed) + 1;
+        let now_ns = u64::try_from(self.started.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        let last_ns = self.last_ns.load(Ordering::Relaxed);
+        if files < self.files && now_ns.saturating_sub(last_ns) < self.interval_ns {
+            return false;
+        }
+        if self
+            .last_ns
+            .compare_exchange(last_ns, now_ns, Ordering::Relaxed, Ordering::Relaxed)
+            .is_err()
+        {
+            return false;
+        }
+        self.since.store(0, Ordering::Relaxed);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ticker_sends_every_n_files_or_after_interval() {
+        let hour = Duration::from_secs(3600);
+        let interval = ProgressInterval::default().with_files(3).with_interval(hour);
+        let ticker = ProgressTicker::new(interval);
+        let due: Vec<bool> = (0..7).map(|_| ticker.tick()).collect();
+        assert_eq!(due, [false, false, true, false, false, true, false]);
+
+        let every_file = ProgressTicker::new(ProgressInterval::default().with_files(0));
+        assert!(every_file.tick() && every_file.tick());
+
+        let timed = ProgressTicker::new(
+            ProgressInterval::default().with_files(u64::MAX).with_interval(Duration::ZERO),
+        );
+        assert!(timed.tick());
+    }
+}
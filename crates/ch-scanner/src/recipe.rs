@@ -0,0 +1,209 @@
+//! Per-import migration hints.
+//!
+//! [`import_recipe`] suggests the next step for migrating a single legacy
+//! import, based on the import kind and on the model registry. Imports of
+//! names without a `shared_2023/` equivalent are blocked until the model is
+//! ported; otherwise the hint depends on how the import is written.
+//!
+//! # Examples
+//!
+//! ```
+//! use ch_core::{ImportInfo, ImportKind, ModelDefinition, ModelRegistry, ModelSource};
+//! use ch_core::SourceLocation;
+//! use ch_scanner::import_recipe;
+//! use smallvec::smallvec;
+//!
+//! let mut modern = ModelDefinition::new(
+//!     "Contract",
+//!     ModelSource::Shared2023,
+//!     "shared_2023/models/contract.ts",
+//! );
+//! modern.add_export("Contract");
+//! let mut registry = ModelRegistry::new();
+//! registry.register(modern);
+//!
+//! let import = ImportInfo::new(
+//!     "../shared/models/contract",
+//!     ImportKind::TypeOnly,
+//!     smallvec!["Contract".to_string()],
+//!     Some(ModelSource::SharedLegacy),
+//!     SourceLocation::new(1, 0, 0),
+//! );
+//! let recipe = import_recipe(&import, &registry);
+//! assert_eq!(recipe.to_string(), "switch to shared_2023 interface Contract");
+//! ```
+
+use std::fmt;
+
+use ch_core::{ImportInfo, ImportKind, ModelRegistry};
+
+/// A suggested next step for migrating one legacy import.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationRecipe {
+    /// Some imported names have no `shared_2023/` equivalent yet.
+    Blocked {
+        /// The legacy names that need porting.
+        missing: Vec<String>,
+    },
+    /// A type-only import: switch to the modern interfaces.
+    SwitchInterface {
+        /// The modern names to import instead.
+        modern: Vec<String>,
+    },
+    /// A namespace import or a require of a whole module: list the members
+    /// actually used before replacing it.
+    EnumerateMembers,
+    /// A dynamic import: point the lazy import at `shared_2023/`.
+    UpdateLazyPath,
+    /// A named or default import: import the modern names instead.
+    SwitchImport {
+        /// The modern names to import instead.
+        modern: Vec<String>,
+    },
+    /// A side-effect import or bare require: import the modern module
+    /// instead.
+    SwitchSideEffect,
+}
+
+impl fmt::Display for MigrationRecipe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Blocked { missing } => {
+                write!(f, "blocked: request port of {}", missing.join(", "))
+            }
+            Self::SwitchInterface { modern } if modern.is_empty() => {
+                f.write_str("switch to shared_2023 interfaces")
+            }
+            Self::SwitchInterface { modern } => {
+                let noun = if modern.len() == 1 { "interface" } else { "interfaces" };
+                write!(f, "switch to shared_2023 {noun} {}", modern.join(", "))
+            }
+            Self::EnumerateMembers => f.write_str("enumerate used members first"),
+            Self::UpdateLazyPath => f.write_str("update lazy import path to shared_2023"),
+            Self::SwitchImport { modern } if modern.is_empty() => {
+                f.write_str("import from shared_2023")
+            }
+            Self::SwitchImport { modern } => {
+                write!(f, "import {} from shared_2023", modern.join(", "))
+            }
+            Self::SwitchSideEffect => f.write_str("import the shared_2023 module instead"),
+        }
+    }
+}
+
+/// Suggests how to migrate a legacy import.
+///
+/// Namespace and require imports are judged by the members used through
+/// them. Those used through no members, and dynamic imports, don't name the
+/// models they use, so their hints depend only on the kind. Otherwise, names
+/// without a modern equivalent make the import [`MigrationRecipe::Blocked`];
+/// with an empty registry nothing is known about equivalents and no import
+/// is blocked.
+#[must_use]
+pub fn import_recipe(import: &ImportInfo, registry: &ModelRegistry) -> MigrationRecipe {
+    match import.kind {
+        ImportKind::Namespace | ImportKind::Require if !import.members.is_empty() => {}
+        ImportKind::Require if import.names.is_empty() => {
+            return MigrationRecipe::SwitchSideEffect;
+        }
+        ImportKind::Namespace | ImportKind::Require => return MigrationRecipe::EnumerateMembers,
+        ImportKind::Dynamic => return MigrationRecipe::UpdateLazyPath,
+        ImportKind::SideEffect => return MigrationRecipe::SwitchSideEffect,
+        _ => {}
+    }
+
+    let known = !registry.is_empty();
+    let mut missing = Vec::new();
+    let mut modern = Vec::new();
+    for name in import.used_names() {
+        match registry.modern_name_for(name) {
+            Some(modern_name) => modern.push(modern_name),
+            None if known => missing.push(name.clone()),
+            None => {}
+        }
+    }
+
+    if !missing.is_empty() {
+        MigrationRecipe::Blocked { missing }
+    } else if import.kind == ImportKind::TypeOnly {
+        MigrationRecipe::SwitchInterface { modern }
+    } else {
+        MigrationRecipe::SwitchImport { modern }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ch_core::{ModelDefinition, ModelSource, SourceLocation};
+    use smallvec::SmallVec;
+
+    fn registry() -> ModelRegistry {
+        let mut legacy = ModelDefinition::new(
+            "ActiveContract",
+            ModelSource::SharedLegacy,
+            "shared/models/active-contract.ts",
+        );
+        legacy.add_export("ActiveContract");
+        let mut modern = ModelDefinition::new(
+            "ContractActive",
+            ModelSource::Shared2023,
+            "shared_2023/models/contract-active.ts",
+        );
+        modern.add_export("ContractActive");
+        let mut registry = ModelRegistry::new();
+        registry.register(legacy);
+        registry.register(modern);
+        registry.add_rename("ActiveContract", "ContractActive");
+        registry
+    }
+
+    fn import(kind: ImportKind, names: &[&str]) -> ImportInfo {
+        ImportInfo::new(
+            "../shared/models/active-contract",
+            kind,
+            names.iter().map(ToString::to_string).collect::<SmallVec<_>>(),
+            Some(ModelSource::SharedLegacy),
+            SourceLocation::new(1, 0, 0),
+        )
+    }
+
+    #[test]
+    fn test_import_recipe_by_kind() {
+        let registry = registry();
+
+        let recipe = import_recipe(&import(ImportKind::TypeOnly, &["ActiveContract"]), &registry);
+        assert_eq!(recipe.to_string(), "switch to shared_2023 interface ContractActive");
+
+        let recipe = import_recipe(&import(ImportKind::Named, &["ActiveContract"]), &registry);
+        assert_eq!(recipe.to_string(), "import ContractActive from shared_2023");
+
+        let recipe = import_recipe(&import(ImportKind::Namespace, &["Models"]), &registry);
+        assert_eq!(recipe, MigrationRecipe::EnumerateMembers);
+
+        let mut namespace = import(ImportKind::Namespace, &["Models"]);
+        namespace.members.push("ActiveContract".to_string());
+        let recipe = import_recipe(&namespace, &registry);
+        assert_eq!(recipe.to_string(), "import ContractActive from shared_2023");
+
+        let recipe = import_recipe(&import(ImportKind::Dynamic, &[]), &registry);
+        assert_eq!(recipe, MigrationRecipe::UpdateLazyPath);
+
+        let recipe = import_recipe(&import(ImportKind::Require, &["models"]), &registry);
+        assert_eq!(recipe, MigrationRecipe::EnumerateMembers);
+        let recipe = import_recipe(&import(ImportKind::Require, &[]), &registry);
+        assert_eq!(recipe, MigrationRecipe::SwitchSideEffect);
+    }
+
+    #[test]
+    fn test_import_recipe_blocked() {
+        let registry = registry();
+        let names = ["ActiveContract", "Unported"];
+
+        let recipe = import_recipe(&import(ImportKind::TypeOnly, &names), &registry);
+        assert_eq!(recipe.to_string(), "blocked: request port of Unported");
+
+        let recipe = import_recipe(&import(ImportKind::Named, &names), &ModelRegistry::new());
+        assert_eq!(recipe.to_string(), "import from shared_2023");
+    }
+}
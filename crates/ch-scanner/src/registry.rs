@@ -32,17 +32,43 @@
 //! - `shared_2023/interfaces.ts` - Modern interface definitions
 //! - `shared_2023/interfaces.codegen.ts` - Modern codegen interfaces
 //! - `shared_2023/models/*.ts` - Modern model files
+//!
+//! A file's `export * from './foo'` adds every name `foo` exports to the
+//! file's own exports. Wildcard chains are followed at most
+//! [`MAX_REEXPORT_DEPTH`] files deep.
+//!
+//! After some of these files change, [`RegistryBuilder::update`] re-parses
+//! just those files and patches an existing registry.
+//!
+//! # Barrel Files
+//!
+//! Components often import models from an `index.ts` barrel, such as
+//! `shared/index.ts`, rather than from the model file. The builder reads
+//! every `index.ts` in the shared directories and follows each
+//! `export { X } from './foo'` or `export * from './foo'` through further
+//! barrels to the model file or interfaces file defining `X`, recording it
+//! with [`ModelRegistry::add_barrel_export`]. Only relative re-exports are
+//! followed, at most [`MAX_REEXPORT_DEPTH`] files deep.
 
 use std::fs;
 
 use camino::{Utf8Path, Utf8PathBuf};
-use ch_core::{ModelDefinition, ModelRegistry, ModelSource};
+use ch_core::{FxHashMap, FxHashSet, ModelDefinition, ModelRegistry, ModelSource};
 use ch_ts_parser::{extract_exports, get_typescript_export_query, kebab_to_pascal, ExportInfo};
 use rayon::prelude::*;
 use smallvec::SmallVec;
 use tracing::{debug, info, warn};
 
 use crate::error::ScanError;
+use crate::renames::ModelRenames;
+use crate::tsconfig::normalize;
+
+/// Most files followed from a barrel file to the model defining an export,
+/// guarding against re-export cycles.
+const MAX_REEXPORT_DEPTH: usize = 16;
+
+/// Names of barrel files.
+const BARREL_NAMES: &[&str] = &["index.ts", "index.tsx"];
 
 /// Builder for constructing a [`ModelRegistry`] from the shared directories.
 ///
@@ -71,6 +97,9 @@ pub struct RegistryBuilder {
 
     /// Path to the modern `shared_2023` directory.
     shared_2023_path: Utf8PathBuf,
+
+    /// Models renamed during porting, applied after scanning.
+    renames: ModelRenames,
 }
 
 impl RegistryBuilder {
@@ -85,6 +114,7 @@ impl RegistryBuilder {
         Self {
             shared_path: shared_path.to_owned(),
             shared_2023_path: shared_2023_path.to_owned(),
+            renames: ModelRenames::default(),
         }
     }
 
@@ -101,9 +131,20 @@ impl RegistryBuilder {
         Self {
             shared_path: root.join("shared"),
             shared_2023_path: root.join("shared_2023"),
+            renames: ModelRenames::default(),
         }
     }
 
+    /// Sets the models renamed during porting.
+    ///
+    /// The renames are validated against the built registry and recorded in
+    /// it for cross-referencing (see [`ModelRenames::apply`]).
+    #[must_use]
+    pub fn with_renames(mut self, renames: ModelRenames) -> Self {
+        self.renames = renames;
+        self
+    }
+
     /// Builds the model registry by scanning all model definition files.
     ///
     /// This method:
@@ -121,6 +162,7 @@ impl RegistryBuilder {
     /// Returns [`ScanError`] if:
     /// - The export query fails to compile
     /// - Critical directories cannot be read (non-critical failures are logged but ignored)
+    /// - A configured rename targets a model that is not in `shared_2023/`
     pub fn build(&self) -> Result<ModelRegistry, ScanError> {
         info!(
             shared = %self.shared_path,
@@ -164,17 +206,143 @@ impl RegistryBuilder {
             &mut registry,
         );
 
+        self.index_barrels(&mut registry);
+        self.renames.apply(&mut registry)?;
+
         info!(
             legacy_models = registry.legacy_model_count(),
             modern_models = registry.modern_model_count(),
             legacy_exports = registry.legacy_export_count(),
             modern_exports = registry.modern_export_count(),
+            renames = registry.rename_count(),
+            barrel_exports = registry.barrel_exports().count(),
             "Model registry built"
         );
 
         Ok(registry)
     }
 
+    /// Re-parses the shared files among `paths` and patches `registry`, built
+    /// by this builder, in place instead of rebuilding it.
+    ///
+    /// Models defined in a changed file are replaced with what the file
+    /// exports now, or removed if the file was deleted. Paths other than
+    /// the interfaces, model and barrel files of the shared directories,
+    /// spelled as the builder's paths are, are ignored, including files a
+    /// model only reaches through `export *`; such a model picks up their
+    /// changes the next time it is re-parsed. Barrel files are read again
+    /// and renames validated again afterwards.
+    ///
+    /// Returns the number of shared files among `paths`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScanError::Config`] if a rename now targets a model that is
+    /// not in `shared_2023/`. `registry` is patched regardless, so update a
+    /// clone to keep the old registry on failure.
+    pub fn update(
+        &self,
+        registry: &mut ModelRegistry,
+        paths: &[Utf8PathBuf],
+    ) -> Result<usize, ScanError> {
+        let mut updated = 0;
+        for path in paths {
+            let Some((source, is_interfaces)) = self.shared_file(path) else {
+                if self.is_barrel(path) {
+                    updated += 1;
+                }
+                continue;
+            };
+            updated += 1;
+            let removed = registry.remove_defined_in(path);
+            if is_interfaces {
+                Self::parse_interfaces_file(path, source, registry);
+            } else if let Some(definition) = Self::parse_model_file(path, source) {
+                registry.register(definition);
+            }
+            debug!(path = %path, removed, "Re-parsed shared file");
+        }
+
+        if updated > 0 {
+            self.index_barrels(registry);
+            self.renames.apply(registry)?;
+            info!(
+                files = updated,
+                legacy_models = registry.legacy_model_count(),
+                modern_models = registry.modern_model_count(),
+                "Model registry updated"
+            );
+        }
+        Ok(updated)
+    }
+
+    /// Returns the source of `path` and whether it is an interfaces file, if
+    /// it is one of the files [`build`](Self::build) parses.
+    fn shared_file(&self, path: &Utf8Path) -> Option<(ModelSource, bool)> {
+        [
+            (&self.shared_path, ModelSource::SharedLegacy),
+            (&self.shared_2023_path, ModelSource::Shared2023),
+        ]
+        .into_iter()
+        .find_map(|(dir, source)| {
+            if path.parent() == Some(dir.as_path()) {
+                let name = path.file_name()?;
+                return (name == "interfaces.ts" || name == "interfaces.codegen.ts")
+                    .then_some((source, true));
+            }
+            let is_model = path.parent() == Some(dir.join("models").as_path())
+                && path.extension().is_some_and(|ext| ext == "ts" || ext == "tsx")
+                && !path.file_name().is_some_and(|name| BARREL_NAMES.contains(&name));
+            is_model.then_some((source, false))
+        })
+    }
+
+    /// Returns `true` if `path` is a barrel file in a shared directory.
+    fn is_barrel(&self, path: &Utf8Path) -> bool {
+        path.file_name().is_some_and(|name| BARREL_NAMES.contains(&name))
+            && (path.starts_with(&self.shared_path) || path.starts_with(&self.shared_2023_path))
+    }
+
+    /// Replaces the registry's barrel files with those in the shared
+    /// directories now, resolving each re-exported name to its model.
+    fn index_barrels(&self, registry: &mut ModelRegistry) {
+        let mut found = Vec::new();
+        {
+            let mut resolver = ReexportResolver::new(registry);
+            for (dir, barrel_source) in [
+                (&self.shared_path, ModelSource::SharedLegacy),
+                (&self.shared_2023_path, ModelSource::Shared2023),
+            ] {
+                for barrel in find_barrels(dir) {
+                    let Some(relative) = barrel.parent().and_then(|p| p.strip_prefix(dir).ok())
+                    else {
+                        continue;
+                    };
+                    let barrel_dir: Vec<&str> =
+                        relative.components().map(|component| component.as_str()).collect();
+                    let barrel_dir = barrel_dir.join("/");
+                    let exports = resolver.exports(&barrel).to_vec();
+                    let mut names: Vec<String> = exports
+                        .iter()
+                        .filter(|export| export.is_reexport())
+                        .map(|export| export.name.clone())
+                        .collect();
+                    names.extend(resolver.wildcard_names(&barrel, &exports));
+                    for name in names {
+                        if let Some((source, model)) = resolver.resolve(&barrel, &name, 0) {
+                            found.push((barrel_source, barrel_dir.clone(), name, source, model));
+                        }
+                    }
+                }
+            }
+        }
+
+        registry.clear_barrels();
+        for (barrel_source, barrel_dir, name, source, model) in found {
+            registry.add_barrel_export(barrel_source, &barrel_dir, name, source, model);
+        }
+    }
+
     /// Parses an interfaces file and registers all exports.
     ///
     /// Interface files typically contain many interface declarations and
@@ -217,9 +385,12 @@ impl RegistryBuilder {
             .to_owned();
 
         let mut definition = ModelDefinition::new(model_name, source, path);
-        for export in &exports {
+        for export in exports.iter().filter(|export| !export.is_wildcard()) {
             definition.add_export(&export.name);
         }
+        for name in ReexportResolver::default().wildcard_names(path, &exports) {
+            definition.add_export(name);
+        }
 
         debug!(
             path = %path,
@@ -232,8 +403,9 @@ impl RegistryBuilder {
 
     /// Scans a model directory and registers all model files.
     ///
-    /// Each `.ts` file in the models directory is treated as a separate model.
-    /// The model name is derived from the filename using kebab-to-pascal conversion.
+    /// Each `.ts` file in the models directory is treated as a separate model,
+    /// except barrel files. The model name is derived from the filename using
+    /// kebab-to-pascal conversion.
     fn scan_model_directory(dir: &Utf8Path, source: ModelSource, registry: &mut ModelRegistry) {
         if !dir.exists() {
             debug!(dir = %dir, "Models directory not found, skipping");
@@ -248,6 +420,7 @@ impl RegistryBuilder {
                     e.path()
                         .extension()
                         .is_some_and(|ext| ext == "ts" || ext == "tsx")
+                        && !e.file_name().to_str().is_some_and(|name| BARREL_NAMES.contains(&name))
                 })
                 .collect(),
             Err(e) => {
@@ -265,31 +438,8 @@ impl RegistryBuilder {
         let results: Vec<_> = entries
             .par_iter()
             .filter_map(|entry| {
-                let path = entry.path();
-                let utf8_path = Utf8PathBuf::try_from(path.clone()).ok()?;
-                let contents = fs::read_to_string(&path).ok()?;
-                let exports = Self::extract_exports_from_source(&contents).ok()?;
-
-                if exports.is_empty() {
-                    return None;
-                }
-
-                // Derive model name from filename
-                let model_name = utf8_path
-                    .file_stem()
-                    .map(kebab_to_pascal)
-                    .unwrap_or_default();
-
-                if model_name.is_empty() {
-                    return None;
-                }
-
-                let mut definition = ModelDefinition::new(&model_name, source, &utf8_path);
-                for export in &exports {
-                    definition.add_export(&export.name);
-                }
-
-                Some(definition)
+                let path = Utf8PathBuf::try_from(entry.path()).ok()?;
+                Self::parse_model_file(&path, source)
             })
             .collect();
 
@@ -304,6 +454,39 @@ impl RegistryBuilder {
         }
     }
 
+    /// Parses a model file into a definition named after the file.
+    ///
+    /// Returns `None` if the file can't be read or parsed, or exports
+    /// nothing.
+    fn parse_model_file(path: &Utf8Path, source: ModelSource) -> Option<ModelDefinition> {
+        let contents = fs::read_to_string(path).ok()?;
+        let exports = Self::extract_exports_from_source(&contents).ok()?;
+
+        if exports.is_empty() {
+            return None;
+        }
+
+        // Derive model name from filename
+        let model_name = path
+            .file_stem()
+            .map(kebab_to_pascal)
+            .unwrap_or_default();
+
+        if model_name.is_empty() {
+            return None;
+        }
+
+        let mut definition = ModelDefinition::new(&model_name, source, path);
+        for export in exports.iter().filter(|export| !export.is_wildcard()) {
+            definition.add_export(&export.name);
+        }
+        for name in ReexportResolver::default().wildcard_names(path, &exports) {
+            definition.add_export(name);
+        }
+
+        Some(definition)
+    }
+
     /// Extracts exports from TypeScript source code.
     fn extract_exports_from_source(source: &str) -> Result<SmallVec<[ExportInfo; 16]>, ScanError> {
         let query = get_typescript_export_query().map_err(|e| ScanError::config(e.to_string()))?;
@@ -321,6 +504,182 @@ impl RegistryBuilder {
     }
 }
 
+/// Follows re-exports from barrel files to the models defining them.
+#[derive(Default)]
+struct ReexportResolver<'r> {
+    /// Registered models by normalized definition path.
+    models: FxHashMap<Utf8PathBuf, &'r ModelDefinition>,
+    /// Exports of the files parsed so far, by normalized path.
+    parsed: FxHashMap<Utf8PathBuf, SmallVec<[ExportInfo; 16]>>,
+}
+
+impl<'r> ReexportResolver<'r> {
+    /// Creates a resolver for the models in `registry`.
+    fn new(registry: &'r ModelRegistry) -> Self {
+        let models = registry
+            .iter_all_models()
+            .map(|model| (normalize(&model.definition_path), model))
+            .collect();
+        Self {
+            models,
+            parsed: FxHashMap::default(),
+        }
+    }
+
+    /// Returns the exports of the file at `path`, parsing it the first time;
+    /// none if it can't be read or parsed.
+    fn exports(&mut self, path: &Utf8Path) -> &[ExportInfo] {
+        self.parsed.entry(normalize(path)).or_insert_with(|| {
+            let exports = fs::read_to_string(path)
+                .ok()
+                .and_then(|contents| RegistryBuilder::extract_exports_from_source(&contents).ok());
+            if exports.is_none() {
+                debug!(path = %path, "Failed to read re-exporting file");
+            }
+            exports.unwrap_or_default()
+        })
+    }
+
+    /// Returns the source and name of the model defining `name`, as
+    /// exported by the file at `path` after `depth` re-exports.
+    fn resolve(
+        &mut self,
+        path: &Utf8Path,
+        name: &str,
+        depth: usize,
+    ) -> Option<(ModelSource, String)> {
+        if let Some(model) = self.models.get(&normalize(path)) {
+            return model
+                .exports
+                .iter()
+                .any(|export| export == name)
+                .then(|| (model.source, model.name.clone()));
+        }
+        if depth >= MAX_REEXPORT_DEPTH {
+            debug!(path = %path, name, "Re-exports nested too deeply");
+            return None;
+        }
+
+        // A name re-exported by name shadows the same name from a wildcard
+        let dir = path.parent()?.to_owned();
+        let exports = self.exports(path);
+        let named = exports.iter().filter(|export| export.is_reexport() && export.name == name);
+        let wildcards = exports.iter().filter(|export| export.is_wildcard());
+        let modules: Vec<String> =
+            named.chain(wildcards).filter_map(|export| export.reexport_source.clone()).collect();
+        modules.iter().find_map(|module| {
+            let target = resolve_module(&dir, module)?;
+            self.resolve(&target, name, depth + 1)
+        })
+    }
+
+    /// Returns the names brought in by the `export *` re-exports among
+    /// `exports`, the exports of the file at `path`, following wildcard
+    /// chains at most [`MAX_REEXPORT_DEPTH`] files deep.
+    fn wildcard_names(&mut self, path: &Utf8Path, exports: &[ExportInfo]) -> Vec<String> {
+        let mut names = Vec::new();
+        let mut visited = FxHashSet::default();
+        visited.insert(normalize(path));
+        let mut pending = wildcard_targets(path, exports, 1);
+        while let Some((target, depth)) = pending.pop() {
+            if !visited.insert(normalize(&target)) {
+                continue;
+            }
+            if let Some(model) = self.models.get(&normalize(&target)) {
+                names.extend(model.exports.iter().cloned());
+                continue;
+            }
+            let exports = self.exports(&target).to_vec();
+            names.extend(
+                exports
+                    .iter()
+                    .filter(|export| !export.is_wildcard())
+                    .map(|export| export.name.clone()),
+            );
+            if depth >= MAX_REEXPORT_DEPTH {
+                debug!(path = %target, "Wildcard re-exports nested too deeply");
+                continue;
+            }
+            pending.extend(wildcard_targets(&target, &exports, depth + 1));
+        }
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+}
+
+/// Returns the files the `export *` re-exports among `exports`, the exports
+/// of the file at `path`, refer to, each with `depth`.
+fn wildcard_targets(
+    path: &Utf8Path,
+    exports: &[ExportInfo],
+    depth: usize,
+) -> Vec<(Utf8PathBuf, usize)> {
+    let Some(dir) = path.parent() else {
+        return Vec::new();
+    };
+    exports
+        .iter()
+        .filter(|export| export.is_wildcard())
+        .filter_map(|export| {
+            let module = export.reexport_source.as_deref()?;
+            let target = resolve_module(dir, module);
+            if target.is_none() {
+                debug!(path = %path, module, "Wildcard re-export target not found");
+            }
+            target.map(|target| (target, depth))
+        })
+        .collect()
+}
+
+/// Returns the file a relative module specifier in `dir` refers to, trying
+/// the extensions and `index` files `tsc` does.
+fn resolve_module(dir: &Utf8Path, module: &str) -> Option<Utf8PathBuf> {
+    let module = module.trim_matches(|c| c == '"' || c == '\'');
+    if !module.starts_with('.') {
+        return None;
+    }
+    let base = dir.join(module);
+    let has_extension = base.extension().is_some_and(|ext| ext == "ts" || ext == "tsx");
+    let candidates = [
+        has_extension.then(|| base.clone()),
+        Some(base.with_extension("ts")),
+        Some(base.with_extension("tsx")),
+        Some(base.join("index.ts")),
+        Some(base.join("index.tsx")),
+    ];
+    candidates.into_iter().flatten().find(|candidate| candidate.is_file())
+}
+
+/// Returns the barrel files under `dir`, leaving out `node_modules`, hidden
+/// directories and directories reached through symbolic links.
+fn find_barrels(dir: &Utf8Path) -> Vec<Utf8PathBuf> {
+    let mut barrels = Vec::new();
+    let mut pending = vec![dir.to_owned()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(Result::ok) {
+            let Ok(path) = Utf8PathBuf::try_from(entry.path()) else {
+                continue;
+            };
+            let Some(name) = path.file_name() else {
+                continue;
+            };
+            if entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+                if name != "node_modules" && !name.starts_with('.') {
+                    pending.push(path);
+                }
+            } else if BARREL_NAMES.contains(&name) {
+                barrels.push(path);
+            }
+        }
+    }
+    barrels.sort_unstable();
+    barrels
+}
+
 /// Result of building a model registry.
 #[derive(Debug)]
 pub struct RegistryBuildResult {
@@ -392,6 +751,151 @@ export { Bar };
         assert!(names.contains(&"Bar"));
     }
 
+    #[test]
+    fn test_registry_update_patches_changed_files() {
+        let (_dir, root) = crate::tests::temp_root();
+        let models = root.join("shared/models");
+        let models_2023 = root.join("shared_2023/models");
+        assert!(fs::create_dir_all(&models).is_ok());
+        assert!(fs::create_dir_all(&models_2023).is_ok());
+        assert!(fs::write(models.join("foo.ts"), "export class Foo {}\n").is_ok());
+        assert!(fs::write(models.join("bar.ts"), "export class Bar {}\n").is_ok());
+
+        let builder = RegistryBuilder::from_root(&root);
+        let registry = builder.build();
+        assert!(registry.is_ok());
+        let Ok(mut registry) = registry else {
+            return;
+        };
+        assert_eq!(registry.legacy_model_count(), 2);
+
+        // Changed, deleted, added and unrelated files
+        assert!(fs::write(models.join("foo.ts"), "export class FooModel {}\n").is_ok());
+        assert!(fs::remove_file(models.join("bar.ts")).is_ok());
+        assert!(fs::write(models_2023.join("foo.ts"), "export class Foo {}\n").is_ok());
+        let changed = [
+            models.join("foo.ts"),
+            models.join("bar.ts"),
+            models_2023.join("foo.ts"),
+            root.join("app/main.ts"),
+        ];
+        assert_eq!(builder.update(&mut registry, &changed).ok(), Some(3));
+
+        assert!(registry.is_legacy_export("FooModel"));
+        assert!(!registry.is_legacy_export("Foo"));
+        assert!(registry.get_legacy_model("Bar").is_none());
+        assert!(registry.is_modern_export("Foo"));
+        assert_eq!(registry.stats(), builder.build().map(|r| r.stats()).unwrap_or_default());
+    }
+
+    #[test]
+    fn test_wildcard_reexports_add_transitive_names() {
+        let (_dir, root) = crate::tests::temp_root();
+        let files = [
+            (
+                "shared/interfaces.ts",
+                "export * from './active-contract';\nexport interface IJob {}\n",
+            ),
+            (
+                "shared/active-contract.ts",
+                concat!(
+                    "export interface ActiveContractModel {}\n",
+                    "export * from './contract/status';\n",
+                    "export * from './missing';\n",
+                ),
+            ),
+            ("shared/contract/status.ts", "export interface ContractStatus {}\n"),
+            ("shared/models/foo.ts", "export * from '../gen/foo';\nexport class Foo {}\n"),
+            ("shared/gen/foo.ts", "export class FooCodeGen {}\nexport * from './loop';\n"),
+            ("shared/gen/loop.ts", "export * from './foo';\nexport interface Loop {}\n"),
+            ("shared/models/index.ts", "export * from './foo';\n"),
+            ("shared/index.ts", "export * from './models';\nexport * from './interfaces';\n"),
+        ];
+        for (file, contents) in files {
+            let path = root.join(file);
+            assert!(path.parent().is_some_and(|parent| fs::create_dir_all(parent).is_ok()));
+            assert!(fs::write(path, contents).is_ok());
+        }
+
+        let registry = RegistryBuilder::from_root(&root).build();
+        assert!(registry.is_ok());
+        let Ok(registry) = registry else {
+            return;
+        };
+        let exports = |model: &str| {
+            let mut exports = registry.get_legacy_model(model).map(|m| m.exports.to_vec());
+            if let Some(exports) = &mut exports {
+                exports.sort_unstable();
+            }
+            exports
+        };
+        let interfaces = ["ActiveContractModel", "ContractStatus", "IJob"];
+        assert_eq!(exports("interfaces"), Some(interfaces.map(str::to_owned).to_vec()));
+        let foo = ["Foo", "FooCodeGen", "Loop"];
+        assert_eq!(exports("Foo"), Some(foo.map(str::to_owned).to_vec()));
+        assert!(!registry.is_legacy_export("*"));
+        assert!(registry.get_legacy_model("Index").is_none());
+
+        let legacy = ModelSource::SharedLegacy;
+        let model = |name: &str| registry.barrel_model(legacy, "", name).map(|m| m.name.as_str());
+        assert_eq!(model("Loop"), Some("Foo"));
+        assert_eq!(model("ContractStatus"), Some("interfaces"));
+        assert_eq!(model("Missing"), None);
+    }
+
+    #[test]
+    fn test_barrel_reexports_resolve_to_defining_models() {
+        let (_dir, root) = crate::tests::temp_root();
+        let files = [
+            ("shared/models/foo.ts", "export class Foo {}\nexport interface FooModel {}\n"),
+            ("shared/interfaces.ts", "export interface IThing {}\n"),
+            ("shared_2023/models/bar.ts", "export class Bar {}\n"),
+            ("shared/models/index.ts", "export { Foo } from './foo';\n"),
+            (
+                "shared/index.ts",
+                concat!(
+                    "export { Foo } from './models';\n",
+                    "export { IThing } from './interfaces';\n",
+                    "export { Bar } from '../shared_2023/models/bar';\n",
+                    "export { Missing } from './nowhere';\n",
+                ),
+            ),
+            ("shared/domain/index.ts", "export { FooModel } from '../models/foo.ts';\n"),
+            ("shared/loop/index.ts", "export { Loop } from './index';\n"),
+        ];
+        for (file, contents) in files {
+            let path = root.join(file);
+            assert!(path.parent().is_some_and(|parent| fs::create_dir_all(parent).is_ok()));
+            assert!(fs::write(path, contents).is_ok());
+        }
+
+        let builder = RegistryBuilder::from_root(&root);
+        let registry = builder.build();
+        assert!(registry.is_ok());
+        let Ok(mut registry) = registry else {
+            return;
+        };
+        let legacy = ModelSource::SharedLegacy;
+        let model = |registry: &ModelRegistry, dir: &str, name: &str| {
+            registry.barrel_model(legacy, dir, name).map(|model| (model.source, model.name.clone()))
+        };
+        assert_eq!(model(&registry, "", "Foo"), Some((legacy, "Foo".to_owned())));
+        assert_eq!(model(&registry, "", "IThing"), Some((legacy, "interfaces".to_owned())));
+        let bar = Some((ModelSource::Shared2023, "Bar".to_owned()));
+        assert_eq!(model(&registry, "", "Bar"), bar);
+        assert_eq!(model(&registry, "domain", "FooModel"), Some((legacy, "Foo".to_owned())));
+        assert_eq!(model(&registry, "", "Missing"), None);
+        assert_eq!(model(&registry, "loop", "Loop"), None);
+        assert_eq!(model(&registry, "", "FooModel"), None);
+
+        // A changed barrel is read again
+        let models_barrel = root.join("shared/models/index.ts");
+        assert!(fs::write(&models_barrel, "export const unrelated = 1;\n").is_ok());
+        assert_eq!(builder.update(&mut registry, &[models_barrel]).ok(), Some(1));
+        assert_eq!(model(&registry, "", "Foo"), None);
+        assert_eq!(model(&registry, "", "Bar"), bar);
+    }
+
     #[test]
     fn test_registry_build_result() {
         let result = RegistryBuildResult {
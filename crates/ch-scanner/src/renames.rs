@@ -0,0 +1,252 @@
+//! User-maintained mapping of models renamed during porting.
+//!
+//! Some models were given a new name when they were ported from `shared/` to
+//! `shared_2023/` (e.g. `ActiveContract` became `ContractActive`). Name-based
+//! cross-referencing can't find these, so [`ModelRenames`] reads a mapping
+//! file and records each rename in the [`ModelRegistry`].
+//!
+//! # Format
+//!
+//! One `LegacyName -> ModernName` pair per line. Blank lines and `#` comments
+//! are ignored:
+//!
+//! ```text
+//! # Models renamed while porting to shared_2023
+//! ActiveContract  -> ContractActive
+//! JobSummary      -> JobOverview
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use ch_core::{ModelDefinition, ModelRegistry, ModelSource};
+//! use ch_scanner::ModelRenames;
+//!
+//! let mut registry = ModelRegistry::new();
+//! let mut modern = ModelDefinition::new(
+//!     "ContractActive",
+//!     ModelSource::Shared2023,
+//!     "shared_2023/models/contract-active.ts",
+//! );
+//! modern.add_export("ContractActive");
+//! registry.register(modern);
+//!
+//! let renames = ModelRenames::parse("ActiveContract -> ContractActive\n")?;
+//! renames.apply(&mut registry)?;
+//! assert_eq!(registry.modern_name_for("ActiveContract").as_deref(), Some("ContractActive"));
+//! # Ok::<(), ch_scanner::ScanError>(())
+//! ```
+
+use std::fs;
+
+use camino::Utf8Path;
+use ch_core::ModelRegistry;
+use tracing::warn;
+
+use crate::error::ScanError;
+
+/// A single rename from the mapping file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ModelRename {
+    /// Model name in `shared/`.
+    legacy: String,
+    /// Model name in `shared_2023/`.
+    modern: String,
+    /// Line number in the mapping file (1-based).
+    line: usize,
+}
+
+/// Parsed model rename mapping.
+///
+/// See the [module documentation](self) for the file format.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelRenames {
+    /// Renames in file order.
+    renames: Vec<ModelRename>,
+}
+
+impl ModelRenames {
+    /// Loads and parses a rename mapping file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScanError::Read`] if the file cannot be read, or
+    /// [`ScanError::Config`] if a line is malformed.
+    pub fn load(path: &Utf8Path) -> Result<Self, ScanError> {
+        let contents =
+            fs::read_to_string(path.as_std_path()).map_err(|e| ScanError::read(path, e))?;
+        Self::parse(&contents)
+    }
+
+    /// Parses rename mapping contents.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScanError::Config`] if a line is not a `Legacy -> Modern`
+    /// pair, or if a legacy model is mapped to two different names.
+    pub fn parse(contents: &str) -> Result<Self, ScanError> {
+        let mut renames: Vec<ModelRename> = Vec::new();
+
+        for (index, line) in contents.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.split_once('#').map_or(line, |(content, _)| content).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (legacy, modern) = line
+                .split_once("->")
+                .map(|(legacy, modern)| (legacy.trim(), modern.trim()))
+                .filter(|(legacy, modern)| is_model_name(legacy) && is_model_name(modern))
+                .ok_or_else(|| {
+                    ScanError::config(format!(
+                        "model renames line {line_number}: expected `LegacyName -> ModernName`"
+                    ))
+                })?;
+
+            if let Some(existing) = renames.iter().find(|r| r.legacy == legacy) {
+                if existing.modern != modern {
+                    return Err(ScanError::config(format!(
+                        "model renames line {line_number}: {legacy} is already mapped to {} on line {}",
+                        existing.modern, existing.line
+                    )));
+                }
+                continue;
+            }
+
+            renames.push(ModelRename {
+                legacy: legacy.to_owned(),
+                modern: modern.to_owned(),
+                line: line_number,
+            });
+        }
+
+        Ok(Self { renames })
+    }
+
+    /// Returns the number of renames.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.renames.len()
+    }
+
+    /// Returns `true` if there are no renames.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.renames.is_empty()
+    }
+
+    /// Validates the renames against the registry and records them in it.
+    ///
+    /// Every target must be a model or export in `shared_2023/`. Renames
+    /// whose legacy name isn't in `shared/` are recorded anyway (the legacy
+    /// model may already have been deleted) but logged as stale.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScanError::Config`] listing every rename whose target does
+    /// not exist in the modern registry. No renames are recorded in that case.
+    pub fn apply(&self, registry: &mut ModelRegistry) -> Result<(), ScanError> {
+        let missing: Vec<_> = self
+            .renames
+            .iter()
+            .filter(|r| {
+                registry.get_modern_model(&r.modern).is_none() && !registry.is_modern_export(&r.modern)
+            })
+            .map(|r| format!("{} (line {})", r.modern, r.line))
+            .collect();
+        if !missing.is_empty() {
+            return Err(ScanError::config(format!(
+                "model renames: targets not found in shared_2023: {}",
+                missing.join(", ")
+            )));
+        }
+
+        for rename in &self.renames {
+            if registry.get_legacy_model(&rename.legacy).is_none()
+                && !registry.is_legacy_export(&rename.legacy)
+            {
+                warn!(
+                    legacy = %rename.legacy,
+                    line = rename.line,
+                    "Renamed model not found in shared, mapping may be stale"
+                );
+            }
+            registry.add_rename(rename.legacy.clone(), rename.modern.clone());
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns `true` if `name` looks like a TypeScript identifier.
+fn is_model_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ch_core::{ModelDefinition, ModelSource};
+
+    fn registry() -> ModelRegistry {
+        let mut registry = ModelRegistry::new();
+        let mut legacy =
+            ModelDefinition::new("ActiveContract", ModelSource::SharedLegacy, "shared/models/a.ts");
+        legacy.add_export("ActiveContract");
+        registry.register(legacy);
+        let mut modern =
+            ModelDefinition::new("ContractActive", ModelSource::Shared2023, "shared_2023/models/c.ts");
+        modern.add_export("ContractActive");
+        registry.register(modern);
+        registry
+    }
+
+    #[test]
+    fn test_renames_parse() {
+        let renames = ModelRenames::parse(
+            "# header\n\nActiveContract -> ContractActive  # ported in 2023\nJob->JobV2\nJob -> JobV2\n",
+        );
+        assert_eq!(renames.as_ref().map(ModelRenames::len).ok(), Some(2));
+    }
+
+    #[test]
+    fn test_renames_parse_errors() {
+        let err = ModelRenames::parse("A -> B\nC = D\n").err();
+        assert!(err.is_some_and(|e| e.to_string().contains("line 2")));
+
+        let err = ModelRenames::parse("A -> B\nA -> C\n").err();
+        assert!(err.is_some_and(|e| e.to_string().contains("already mapped to B on line 1")));
+
+        assert!(ModelRenames::parse("A ->\n").is_err());
+        assert!(ModelRenames::parse("A B -> C\n").is_err());
+    }
+
+    #[test]
+    fn test_renames_apply() {
+        let mut registry = registry();
+        let renames = ModelRenames::parse("ActiveContract -> ContractActive\n");
+        assert!(renames.is_ok_and(|r| r.apply(&mut registry).is_ok()));
+        assert_eq!(registry.rename_count(), 1);
+        assert_eq!(
+            registry.modern_name_for("ActiveContract").as_deref(),
+            Some("ContractActive")
+        );
+    }
+
+    #[test]
+    fn test_renames_apply_rejects_unknown_targets() {
+        let mut registry = registry();
+        let renames = ModelRenames::parse("ActiveContract -> ContractActiv\nJob -> JobV2\n");
+        let err = renames.ok().and_then(|r| r.apply(&mut registry).err());
+        assert!(err.is_some_and(|e| {
+            let msg = e.to_string();
+            msg.contains("ContractActiv (line 1)") && msg.contains("JobV2 (line 2)")
+        }));
+        assert_eq!(registry.rename_count(), 0);
+    }
+}
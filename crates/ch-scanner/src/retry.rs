@@ -0,0 +1,87 @@
+//! Retrying files that failed for reasons likely to pass.
+//!
+//! Network file systems fail reads now and then with time-outs, stale
+//! handles or busy resources that a moment later are gone. Rather than mark
+//! such files as errors until the next scan, a scan retries them once every
+//! other file is done: [`RetryPolicy`] sets how many more passes it makes
+//! over the files still failing transiently and how long it waits before
+//! each. Files that keep failing are reported with their last error.
+//!
+//! Which errors are transient is decided by
+//! [`ScanError::is_transient`](crate::ScanError::is_transient).
+
+use std::time::Duration;
+
+/// How a scan retries files that failed with a transient error.
+///
+/// The default makes two more passes, a quarter of a second apart.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use ch_scanner::RetryPolicy;
+///
+/// let policy = RetryPolicy::default()
+///     .with_attempts(5)
+///     .with_delay(Duration::from_secs(1));
+/// assert!(policy.is_enabled());
+/// assert!(!RetryPolicy::none().is_enabled());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Passes made over the files still failing transiently.
+    pub attempts: u32,
+
+    /// Time waited before each pass.
+    pub delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 2,
+            delay: Duration::from_millis(250),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a policy that never retries.
+    #[must_use]
+    pub const fn none() -> Self {
+        Self {
+            attempts: 0,
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// Returns `true` if failed files are retried at all.
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.attempts > 0
+    }
+
+    /// Sets the number of passes over files failing transiently.
+    ///
+    /// # Arguments
+    ///
+    /// * `attempts` - Passes after the first read; `0` disables retrying
+    #[must_use]
+    pub const fn with_attempts(mut self, attempts: u32) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    /// Sets the time waited before each pass.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - Pause giving the file system time to recover
+    #[must_use]
+    pub const fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
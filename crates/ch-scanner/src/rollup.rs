@@ -0,0 +1,309 @@
+//! Per-directory, per-team and per-model aggregation of scan results.
+//!
+//! This module provides [`rollup_by_directory`], which groups scanned files
+//! by their directory subtree (truncated to a configurable depth), and
+//! [`rollup_by_owner`], which groups them by owning team. Both aggregate a
+//! [`StatsSnapshot`] for each group; [`directory_group`] gives the directory
+//! group of a single file.
+//!
+//! [`rollup_by_model`] pivots the other way: it inverts the files' legacy
+//! imports into an index from each legacy model to the files consuming it.
+//!
+//! # Examples
+//!
+//! ```
+//! use camino::{Utf8Path, Utf8PathBuf};
+//! use ch_core::{FileId, FileInfo, MigrationStatus};
+//! use ch_scanner::rollup_by_directory;
+//!
+//! let mut legacy = FileInfo::new(FileId::new(1), Utf8PathBuf::from("/src/app/contracts/a.ts"));
+//! legacy.status = MigrationStatus::Legacy;
+//! let mut migrated = FileInfo::new(FileId::new(2), Utf8PathBuf::from("/src/app/jobs/b.ts"));
+//! migrated.status = MigrationStatus::Migrated;
+//!
+//! let rollup = rollup_by_directory(&[legacy, migrated], Utf8Path::new("/src"), 2);
+//! assert_eq!(rollup.len(), 2);
+//! assert_eq!(rollup[0].path, "app/contracts");
+//! assert_eq!(rollup[0].stats.legacy, 1);
+//! ```
+
+use std::collections::BTreeMap;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use ch_core::{FileInfo, ImportInfo, ModelRegistry};
+use serde::{Deserialize, Serialize};
+
+use crate::stats::StatsSnapshot;
+
+/// Aggregated statistics for a directory subtree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirectoryStats {
+    /// Directory path relative to the scan root (`.` for the root itself).
+    pub path: Utf8PathBuf,
+
+    /// Statistics for all files in the subtree.
+    pub stats: StatsSnapshot,
+}
+
+/// Aggregated statistics for an owning team.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OwnerStats {
+    /// The owning team (or [`UNOWNED`] for files without an owner).
+    pub owner: String,
+
+    /// Statistics for all files owned by the team.
+    pub stats: StatsSnapshot,
+}
+
+/// The files consuming a legacy model.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelConsumers {
+    /// Legacy model name, as imported.
+    pub name: String,
+
+    /// Name of the `shared_2023/` equivalent (after renames), if one exists.
+    pub modern_equivalent: Option<String>,
+
+    /// Files importing the model, sorted by path.
+    pub files: Vec<Utf8PathBuf>,
+}
+
+impl ModelConsumers {
+    /// Returns the number of files importing the model.
+    #[must_use]
+    pub fn consumer_count(&self) -> usize {
+        self.files.len()
+    }
+}
+
+/// Group label used by [`rollup_by_owner`] for files without an owner.
+pub const UNOWNED: &str = "(unowned)";
+
+/// Aggregates file statistics per directory subtree.
+///
+/// Each file is attributed to the first `depth` components of its parent
+/// directory, relative to `root`. Files directly in `root` are grouped
+/// under `.`. Files outside `root` are grouped by their absolute parent.
+///
+/// # Arguments
+///
+/// * `files` - The scanned files to aggregate
+/// * `root` - The scan root that paths are made relative to
+/// * `depth` - Maximum number of directory components per group (minimum 1)
+///
+/// # Returns
+///
+/// One [`DirectoryStats`] per directory group, sorted by path.
+#[must_use]
+pub fn rollup_by_directory(files: &[FileInfo], root: &Utf8Path, depth: usize) -> Vec<DirectoryStats> {
+    let depth = depth.max(1);
+    let mut groups: BTreeMap<Utf8PathBuf, StatsSnapshot> = BTreeMap::new();
+
+    for file in files {
+        groups
+            .entry(directory_group(&file.path, root, depth))
+            .or_default()
+            .record(file.status);
+    }
+
+    groups
+        .into_iter()
+        .map(|(path, stats)| DirectoryStats { path, stats })
+        .collect()
+}
+
+/// Returns the directory group [`rollup_by_directory`] attributes a file to.
+///
+/// This is the first `depth` (minimum 1) components of the file's parent
+/// directory relative to `root`, or `.` for files directly in `root`.
+#[must_use]
+pub fn directory_group(path: &Utf8Path, root: &Utf8Path, depth: usize) -> Utf8PathBuf {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    let dir: Utf8PathBuf = relative
+        .parent()
+        .map(|parent| parent.components().take(depth.max(1)).collect())
+        .unwrap_or_default();
+    if dir.as_str().is_empty() {
+        Utf8PathBuf::from(".")
+    } else {
+        dir
+    }
+}
+
+/// Aggregates file statistics per owning team.
+///
+/// Each file is attributed to its [primary owner](FileInfo::primary_owner);
+/// files without an owner are grouped under [`UNOWNED`].
+///
+/// # Returns
+///
+/// One [`OwnerStats`] per team, sorted by owner name, with the unowned
+/// group (if any) last.
+#[must_use]
+pub fn rollup_by_owner(files: &[FileInfo]) -> Vec<OwnerStats> {
+    let mut groups: BTreeMap<&str, StatsSnapshot> = BTreeMap::new();
+    let mut unowned: Option<StatsSnapshot> = None;
+
+    for file in files {
+        match file.primary_owner() {
+            Some(owner) => groups.entry(owner).or_default().record(file.status),
+            None => unowned.get_or_insert_default().record(file.status),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(owner, stats)| OwnerStats {
+            owner: owner.to_owned(),
+            stats,
+        })
+        .chain(unowned.map(|stats| OwnerStats {
+            owner: UNOWNED.to_owned(),
+            stats,
+        }))
+        .collect()
+}
+
+/// Groups files by the legacy models they import.
+///
+/// Every name imported from the legacy `shared/` directory becomes one
+/// entry listing the files importing it. The registry supplies each model's
+/// modern equivalent, applying any renames.
+///
+/// # Returns
+///
+/// One [`ModelConsumers`] per legacy model, sorted by the number of
+/// consumers (most first), then by name.
+#[must_use]
+pub fn rollup_by_model(files: &[FileInfo], registry: &ModelRegistry) -> Vec<ModelConsumers> {
+    let mut index: BTreeMap<&str, Vec<&Utf8Path>> = BTreeMap::new();
+    for file in files {
+        for name in file.legacy_imports().flat_map(ImportInfo::used_names) {
+            index.entry(name).or_default().push(&file.path);
+        }
+    }
+
+    let mut models: Vec<ModelConsumers> = index
+        .into_iter()
+        .map(|(name, mut paths)| {
+            paths.sort_unstable();
+            paths.dedup();
+            ModelConsumers {
+                name: name.to_owned(),
+                modern_equivalent: registry.modern_name_for(name),
+                files: paths.into_iter().map(Utf8Path::to_path_buf).collect(),
+            }
+        })
+        .collect();
+    models.sort_by(|a, b| b.files.len().cmp(&a.files.len()).then_with(|| a.name.cmp(&b.name)));
+    models
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ch_core::{FileId, MigrationStatus};
+
+    fn make_file(path: &str, status: MigrationStatus) -> FileInfo {
+        let mut file = FileInfo::new(FileId::new(0), Utf8PathBuf::from(path));
+        file.status = status;
+        file
+    }
+
+    #[test]
+    fn test_rollup_by_directory_depth() {
+        let files = vec![
+            make_file("/src/app/contracts/a.ts", MigrationStatus::Legacy),
+            make_file("/src/app/contracts/deep/b.ts", MigrationStatus::Partial),
+            make_file("/src/app/jobs/c.ts", MigrationStatus::Migrated),
+            make_file("/src/main.ts", MigrationStatus::NoModels),
+        ];
+
+        let depth_one = rollup_by_directory(&files, Utf8Path::new("/src"), 1);
+        let paths: Vec<_> = depth_one.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(paths, vec![".", "app"]);
+        assert_eq!(depth_one[1].stats.total, 3);
+
+        let depth_two = rollup_by_directory(&files, Utf8Path::new("/src"), 2);
+        let paths: Vec<_> = depth_two.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(paths, vec![".", "app/contracts", "app/jobs"]);
+        assert_eq!(depth_two[1].stats.legacy, 1);
+        assert_eq!(depth_two[1].stats.partial, 1);
+    }
+
+    #[test]
+    fn test_rollup_by_directory_zero_depth_is_one() {
+        let files = vec![make_file("/src/app/a.ts", MigrationStatus::Legacy)];
+        let rollup = rollup_by_directory(&files, Utf8Path::new("/src"), 0);
+        assert_eq!(rollup.len(), 1);
+        assert_eq!(rollup[0].path, "app");
+    }
+
+    #[test]
+    fn test_rollup_by_owner() {
+        let mut contracts = make_file("/src/app/contracts/a.ts", MigrationStatus::Legacy);
+        contracts.owners.push("@contracts".to_owned());
+        let mut jobs = make_file("/src/app/jobs/b.ts", MigrationStatus::Migrated);
+        jobs.owners.push("@jobs".to_owned());
+        jobs.owners.push("@alice".to_owned());
+        let unowned = make_file("/src/main.ts", MigrationStatus::Partial);
+
+        let rollup = rollup_by_owner(&[unowned, jobs, contracts]);
+        let owners: Vec<_> = rollup.iter().map(|o| o.owner.as_str()).collect();
+        assert_eq!(owners, vec!["@contracts", "@jobs", UNOWNED]);
+        assert_eq!(rollup[0].stats.legacy, 1);
+        assert_eq!(rollup[1].stats.migrated, 1);
+        assert_eq!(rollup[2].stats.partial, 1);
+    }
+
+    #[test]
+    fn test_directory_group() {
+        let root = Utf8Path::new("/src");
+        assert_eq!(directory_group(Utf8Path::new("/src/app/jobs/a.ts"), root, 1), "app");
+        assert_eq!(directory_group(Utf8Path::new("/src/app/jobs/a.ts"), root, 2), "app/jobs");
+        assert_eq!(directory_group(Utf8Path::new("/src/main.ts"), root, 1), ".");
+    }
+
+    #[test]
+    fn test_rollup_by_model() {
+        use ch_core::{ImportInfo, ImportKind, ModelDefinition, ModelSource, SourceLocation};
+        use smallvec::smallvec;
+
+        let import = |names: &[&str], source| {
+            ImportInfo::new(
+                "../shared/models",
+                ImportKind::Named,
+                names.iter().map(|&name| name.to_owned()).collect(),
+                Some(source),
+                SourceLocation::default(),
+            )
+        };
+        let mut a = make_file("/src/app/a.ts", MigrationStatus::Legacy);
+        a.imports.push(import(&["Foo", "Bar"], ModelSource::SharedLegacy));
+        a.imports.push(import(&["Foo"], ModelSource::SharedLegacy));
+        let mut b = make_file("/src/app/b.ts", MigrationStatus::Partial);
+        b.imports.push(import(&["Foo"], ModelSource::SharedLegacy));
+        b.imports.push(import(&["Baz"], ModelSource::Shared2023));
+
+        let mut registry = ModelRegistry::new();
+        registry.register(ModelDefinition {
+            name: "Foo".to_owned(),
+            source: ModelSource::Shared2023,
+            definition_path: Utf8PathBuf::from("shared_2023/foo.ts"),
+            exports: smallvec!["Foo".to_owned()],
+        });
+
+        let models = rollup_by_model(&[b, a], &registry);
+        let names: Vec<_> = models.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["Foo", "Bar"]);
+        assert_eq!(models[0].files, vec!["/src/app/a.ts", "/src/app/b.ts"]);
+        assert_eq!(models[0].modern_equivalent.as_deref(), Some("Foo"));
+        assert_eq!(models[1].consumer_count(), 1);
+        assert_eq!(models[1].modern_equivalent, None);
+    }
+
+    #[test]
+    fn test_rollup_by_directory_empty() {
+        assert!(rollup_by_directory(&[], Utf8Path::new("/src"), 1).is_empty());
+    }
+}
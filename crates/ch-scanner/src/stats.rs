@@ -1,13 +1,19 @@
 //! Scan statistics with atomic counters.
 //!
-//! This module provides [`ScanStats`] for tracking scan progress and
-//! [`StatsSnapshot`] for point-in-time statistics views.
+//! This module provides [`ScanStats`] for tracking scan progress,
+//! [`StatsSnapshot`] for point-in-time statistics views and [`StatsDelta`]
+//! for the change caused by rescanning files. Snapshots taken while a scan
+//! runs carry a [`ScanProgress`], from which they estimate the scan's
+//! throughput and remaining time. Files parsed (rather than reused) are
+//! also counted by [`FileKind`] and [`SizeBucket`], with the bytes parsed,
+//! in [`ParseCounts`].
 //!
 //! # Thread Safety
 //!
 //! All counters use [`AtomicU64`] with [`Relaxed`](std::sync::atomic::Ordering::Relaxed)
 //! ordering for maximum performance. Statistics are for informational purposes
-//! and don't require strict ordering guarantees.
+//! and don't require strict ordering guarantees. Walker skip counts are set
+//! once per walk, so they sit behind a lock instead.
 //!
 //! # Examples
 //!
@@ -26,9 +32,15 @@
 //! ```
 
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 
+use camino::Utf8Path;
+use ch_core::MigrationStatus;
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 
+use crate::walker::{SkipReason, SkippedPath};
+
 /// Atomic counters for scan statistics.
 ///
 /// Uses relaxed atomic ordering for maximum performance. These statistics
@@ -68,6 +80,20 @@ pub struct ScanStats {
     no_models: AtomicU64,
     /// Number of files that failed to scan (read or parse errors).
     errors: AtomicU64,
+    /// Number of files taken unchanged from a previous scan.
+    reused: AtomicU64,
+    /// Number of `.ts` files parsed.
+    parsed_ts: AtomicU64,
+    /// Number of `.tsx` files parsed.
+    parsed_tsx: AtomicU64,
+    /// Number of files parsed, by [`SizeBucket`].
+    parsed_sizes: [AtomicU64; SizeBucket::ALL.len()],
+    /// Total size in bytes of the files parsed.
+    parsed_bytes: AtomicU64,
+    /// Paths the walker left out, by reason.
+    skipped: Mutex<SkipCounts>,
+    /// Start time and size of the scan in progress, if any.
+    running: Mutex<Option<(Instant, u64)>>,
 }
 
 impl ScanStats {
@@ -123,6 +149,42 @@ impl ScanStats {
         self.errors.fetch_add(1, Ordering::Relaxed);
     }
 
+    /// Counts `count` files taken unchanged from a previous scan instead of
+    /// being parsed.
+    ///
+    /// Reused files are still counted in `total` and by status.
+    #[inline]
+    pub fn add_reused(&self, count: u64) {
+        self.reused.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Counts a file of `bytes` bytes at `path` handed to the parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use camino::Utf8Path;
+    /// use ch_scanner::{FileKind, ScanStats, SizeBucket};
+    ///
+    /// let stats = ScanStats::new();
+    /// stats.record_parsed(Utf8Path::new("src/app.tsx"), 6000);
+    ///
+    /// let parsed = stats.snapshot().parsed;
+    /// assert_eq!(parsed.kind(FileKind::Tsx), 1);
+    /// assert_eq!(parsed.size(SizeBucket::Medium), 1);
+    /// assert_eq!(parsed.bytes, 6000);
+    /// ```
+    #[inline]
+    pub fn record_parsed(&self, path: &Utf8Path, bytes: u64) {
+        let kind = match FileKind::of(path) {
+            FileKind::Ts => &self.parsed_ts,
+            FileKind::Tsx => &self.parsed_tsx,
+        };
+        kind.fetch_add(1, Ordering::Relaxed);
+        self.parsed_sizes[SizeBucket::of(bytes) as usize].fetch_add(1, Ordering::Relaxed);
+        self.parsed_bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
     /// Returns a point-in-time snapshot of all statistics.
     ///
     /// The snapshot is consistent in that all values are read at
@@ -151,6 +213,106 @@ impl ScanStats {
             partial: self.partial.load(Ordering::Relaxed),
             no_models: self.no_models.load(Ordering::Relaxed),
             errors: self.errors.load(Ordering::Relaxed),
+            reused: self.reused.load(Ordering::Relaxed),
+            parsed: ParseCounts {
+                ts: self.parsed_ts.load(Ordering::Relaxed),
+                tsx: self.parsed_tsx.load(Ordering::Relaxed),
+                sizes: self.parsed_sizes.each_ref().map(|count| count.load(Ordering::Relaxed)),
+                bytes: self.parsed_bytes.load(Ordering::Relaxed),
+            },
+            skipped: *self.skipped.lock(),
+            progress: self.running.lock().map(|(started, discovered)| ScanProgress {
+                discovered,
+                elapsed: started.elapsed(),
+            }),
+        }
+    }
+
+    /// Records the start of analyzing `discovered` files.
+    ///
+    /// Until [`finish()`](Self::finish), snapshots carry a [`ScanProgress`]
+    /// with the time since this call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ch_scanner::ScanStats;
+    ///
+    /// let stats = ScanStats::new();
+    /// stats.start(100);
+    /// assert_eq!(stats.snapshot().progress.map(|p| p.discovered), Some(100));
+    ///
+    /// stats.finish();
+    /// assert!(stats.snapshot().progress.is_none());
+    /// ```
+    pub fn start(&self, discovered: u64) {
+        *self.running.lock() = Some((Instant::now(), discovered));
+    }
+
+    /// Records the end of the scan started with [`start()`](Self::start).
+    pub fn finish(&self) {
+        *self.running.lock() = None;
+    }
+
+    /// Replaces the walker skip counts.
+    pub fn set_skipped(&self, skipped: SkipCounts) {
+        *self.skipped.lock() = skipped;
+    }
+
+    /// Replaces all counters with the values of a snapshot.
+    ///
+    /// Useful after rescanning part of the tree, when the counts are rebuilt
+    /// from the cache rather than accumulated.
+    pub fn store(&self, snapshot: &StatsSnapshot) {
+        self.total.store(snapshot.total, Ordering::Relaxed);
+        self.legacy.store(snapshot.legacy, Ordering::Relaxed);
+        self.migrated.store(snapshot.migrated, Ordering::Relaxed);
+        self.partial.store(snapshot.partial, Ordering::Relaxed);
+        self.no_models.store(snapshot.no_models, Ordering::Relaxed);
+        self.errors.store(snapshot.errors, Ordering::Relaxed);
+        self.reused.store(snapshot.reused, Ordering::Relaxed);
+        self.store_parsed(&snapshot.parsed);
+        self.set_skipped(snapshot.skipped);
+    }
+
+    /// Adds a signed change to the counters.
+    ///
+    /// Counters never go below zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ch_core::MigrationStatus;
+    /// use ch_scanner::{ScanStats, StatsDelta};
+    ///
+    /// let stats = ScanStats::new();
+    /// stats.increment_total();
+    /// stats.increment_legacy();
+    ///
+    /// let mut delta = StatsDelta::default();
+    /// delta.remove(MigrationStatus::Legacy);
+    /// delta.add(MigrationStatus::Migrated);
+    /// stats.apply(&delta);
+    ///
+    /// let snap = stats.snapshot();
+    /// assert_eq!((snap.total, snap.legacy, snap.migrated), (1, 0, 1));
+    /// ```
+    pub fn apply(&self, delta: &StatsDelta) {
+        for (counter, change) in [
+            (&self.total, delta.total),
+            (&self.legacy, delta.legacy),
+            (&self.migrated, delta.migrated),
+            (&self.partial, delta.partial),
+            (&self.no_models, delta.no_models),
+            (&self.errors, delta.errors),
+        ] {
+            if change >= 0 {
+                counter.fetch_add(change.unsigned_abs(), Ordering::Relaxed);
+            } else {
+                let _ = counter.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| {
+                    Some(count.saturating_sub(change.unsigned_abs()))
+                });
+            }
         }
     }
 
@@ -164,6 +326,272 @@ impl ScanStats {
         self.partial.store(0, Ordering::Relaxed);
         self.no_models.store(0, Ordering::Relaxed);
         self.errors.store(0, Ordering::Relaxed);
+        self.reused.store(0, Ordering::Relaxed);
+        self.store_parsed(&ParseCounts::default());
+        self.set_skipped(SkipCounts::default());
+        self.finish();
+    }
+
+    /// Replaces the parse counters.
+    fn store_parsed(&self, parsed: &ParseCounts) {
+        self.parsed_ts.store(parsed.ts, Ordering::Relaxed);
+        self.parsed_tsx.store(parsed.tsx, Ordering::Relaxed);
+        for (counter, count) in self.parsed_sizes.iter().zip(parsed.sizes) {
+            counter.store(count, Ordering::Relaxed);
+        }
+        self.parsed_bytes.store(parsed.bytes, Ordering::Relaxed);
+    }
+}
+
+/// Progress of a scan that is still running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScanProgress {
+    /// Number of files the scan will analyze.
+    pub discovered: u64,
+    /// Time since analysis started.
+    pub elapsed: Duration,
+}
+
+/// Number of paths the walker left out of a scan, by [`SkipReason`].
+///
+/// # Examples
+///
+/// ```
+/// use ch_scanner::{SkipCounts, SkipReason};
+///
+/// let mut counts = SkipCounts::default();
+/// counts.record(SkipReason::PermissionDenied);
+/// counts.record(SkipReason::Excluded);
+///
+/// assert_eq!(counts.permission_denied, 1);
+/// assert_eq!(counts.total(), 2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SkipCounts {
+    /// Directories that could not be read.
+    #[serde(default)]
+    pub permission_denied: u64,
+    /// Symbolic links with a missing target, or that loop.
+    #[serde(default)]
+    pub broken_symlink: u64,
+    /// Symbolic links not followed.
+    #[serde(default)]
+    pub symlink: u64,
+    /// TypeScript files with non-UTF-8 paths.
+    #[serde(default)]
+    pub non_utf8: u64,
+    /// Skip-listed directories holding TypeScript files.
+    #[serde(default)]
+    pub excluded: u64,
+    /// Directories and files already reached through another path.
+    #[serde(default)]
+    pub duplicate: u64,
+}
+
+impl SkipCounts {
+    /// Counts the given skipped paths.
+    #[must_use]
+    pub fn from_skipped(skipped: &[SkippedPath]) -> Self {
+        let mut counts = Self::default();
+        for path in skipped {
+            counts.record(path.reason);
+        }
+        counts
+    }
+
+    /// Counts one skipped path.
+    pub fn record(&mut self, reason: SkipReason) {
+        match reason {
+            SkipReason::PermissionDenied => self.permission_denied += 1,
+            SkipReason::BrokenSymlink => self.broken_symlink += 1,
+            SkipReason::Symlink => self.symlink += 1,
+            SkipReason::NonUtf8 => self.non_utf8 += 1,
+            SkipReason::Excluded => self.excluded += 1,
+            SkipReason::Duplicate => self.duplicate += 1,
+        }
+    }
+
+    /// Returns the count for one reason.
+    #[must_use]
+    pub const fn get(&self, reason: SkipReason) -> u64 {
+        match reason {
+            SkipReason::PermissionDenied => self.permission_denied,
+            SkipReason::BrokenSymlink => self.broken_symlink,
+            SkipReason::Symlink => self.symlink,
+            SkipReason::NonUtf8 => self.non_utf8,
+            SkipReason::Excluded => self.excluded,
+            SkipReason::Duplicate => self.duplicate,
+        }
+    }
+
+    /// Returns the number of skipped paths.
+    #[must_use]
+    pub const fn total(&self) -> u64 {
+        self.permission_denied
+            + self.broken_symlink
+            + self.symlink
+            + self.non_utf8
+            + self.excluded
+            + self.duplicate
+    }
+
+    /// Returns `true` if nothing was skipped.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+}
+
+/// Kind of TypeScript file, by extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileKind {
+    /// A `.ts` file, declaration files included.
+    Ts,
+    /// A `.tsx` file.
+    Tsx,
+}
+
+impl FileKind {
+    /// Every kind, in display order.
+    pub const ALL: [Self; 2] = [Self::Ts, Self::Tsx];
+
+    /// Returns the kind of the file at `path`.
+    #[must_use]
+    pub fn of(path: &Utf8Path) -> Self {
+        if path.extension() == Some("tsx") {
+            Self::Tsx
+        } else {
+            Self::Ts
+        }
+    }
+
+    /// Returns the file extension, e.g. `.tsx`.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Ts => ".ts",
+            Self::Tsx => ".tsx",
+        }
+    }
+}
+
+/// Size range of a parsed file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SizeBucket {
+    /// Under 4 KiB.
+    Small,
+    /// From 4 KiB up to 32 KiB.
+    Medium,
+    /// From 32 KiB up to 256 KiB.
+    Large,
+    /// 256 KiB and over, e.g. generated files.
+    Huge,
+}
+
+impl SizeBucket {
+    /// Every bucket, smallest first.
+    pub const ALL: [Self; 4] = [Self::Small, Self::Medium, Self::Large, Self::Huge];
+
+    /// Returns the bucket of a file of `bytes` bytes.
+    #[must_use]
+    pub const fn of(bytes: u64) -> Self {
+        match bytes {
+            0..4_096 => Self::Small,
+            4_096..32_768 => Self::Medium,
+            32_768..262_144 => Self::Large,
+            _ => Self::Huge,
+        }
+    }
+
+    /// Returns the size range for display, e.g. `4-32 KiB`.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Small => "under 4 KiB",
+            Self::Medium => "4-32 KiB",
+            Self::Large => "32-256 KiB",
+            Self::Huge => "256 KiB and over",
+        }
+    }
+}
+
+/// Files handed to the parser, by [`FileKind`] and [`SizeBucket`], with
+/// their total size.
+///
+/// Files reused from a previous scan aren't counted, so these show the work
+/// a scan did rather than the makeup of the tree.
+///
+/// # Examples
+///
+/// ```
+/// use ch_scanner::{FileKind, ParseCounts, SizeBucket};
+///
+/// let mut parsed = ParseCounts::default();
+/// parsed.record(FileKind::Ts, 1200);
+/// parsed.record(FileKind::Tsx, 40_000);
+///
+/// assert_eq!(parsed.files(), 2);
+/// assert_eq!(parsed.size(SizeBucket::Large), 1);
+/// assert_eq!(parsed.average_bytes(), Some(20_600));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ParseCounts {
+    /// Number of `.ts` files parsed.
+    #[serde(default)]
+    pub ts: u64,
+    /// Number of `.tsx` files parsed.
+    #[serde(default)]
+    pub tsx: u64,
+    /// Number of files parsed, by [`SizeBucket`], smallest first.
+    #[serde(default)]
+    pub sizes: [u64; SizeBucket::ALL.len()],
+    /// Total size in bytes of the files parsed.
+    #[serde(default)]
+    pub bytes: u64,
+}
+
+impl ParseCounts {
+    /// Counts one parsed file.
+    pub fn record(&mut self, kind: FileKind, bytes: u64) {
+        match kind {
+            FileKind::Ts => self.ts += 1,
+            FileKind::Tsx => self.tsx += 1,
+        }
+        self.sizes[SizeBucket::of(bytes) as usize] += 1;
+        self.bytes += bytes;
+    }
+
+    /// Returns the number of files parsed of one kind.
+    #[must_use]
+    pub const fn kind(&self, kind: FileKind) -> u64 {
+        match kind {
+            FileKind::Ts => self.ts,
+            FileKind::Tsx => self.tsx,
+        }
+    }
+
+    /// Returns the number of files parsed in one size range.
+    #[must_use]
+    pub const fn size(&self, bucket: SizeBucket) -> u64 {
+        self.sizes[bucket as usize]
+    }
+
+    /// Returns the number of files parsed.
+    #[must_use]
+    pub const fn files(&self) -> u64 {
+        self.ts + self.tsx
+    }
+
+    /// Returns the average size in bytes of the files parsed, if any.
+    #[must_use]
+    pub fn average_bytes(&self) -> Option<u64> {
+        self.bytes.checked_div(self.files())
+    }
+
+    /// Returns `true` if no file was parsed.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.files() == 0
     }
 }
 
@@ -198,9 +626,58 @@ pub struct StatsSnapshot {
     pub no_models: u64,
     /// Number of files that failed to scan.
     pub errors: u64,
+    /// Number of files taken unchanged from a previous scan instead of being
+    /// parsed (also counted in `total`).
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub reused: u64,
+    /// Files parsed by kind and size, with the bytes parsed (not counting
+    /// reused files).
+    #[serde(default, skip_serializing_if = "ParseCounts::is_empty")]
+    pub parsed: ParseCounts,
+    /// Paths the walker left out, by reason (not counted in `total`).
+    #[serde(default, skip_serializing_if = "SkipCounts::is_empty")]
+    pub skipped: SkipCounts,
+    /// Progress of the scan, while it is running (not serialized).
+    #[serde(skip)]
+    pub progress: Option<ScanProgress>,
+}
+
+/// Returns `true` for a zero counter, which snapshots don't serialize.
+#[allow(clippy::trivially_copy_pass_by_ref)] // serde passes fields by reference
+const fn is_zero(count: &u64) -> bool {
+    *count == 0
 }
 
 impl StatsSnapshot {
+    /// Counts one scanned file with the given status.
+    ///
+    /// Useful for aggregating snapshots over a subset of files, such as a
+    /// directory subtree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ch_core::MigrationStatus;
+    /// use ch_scanner::StatsSnapshot;
+    ///
+    /// let mut snap = StatsSnapshot::default();
+    /// snap.record(MigrationStatus::Legacy);
+    /// snap.record(MigrationStatus::Migrated);
+    ///
+    /// assert_eq!(snap.total, 2);
+    /// assert_eq!(snap.legacy, 1);
+    /// assert_eq!(snap.migrated, 1);
+    /// ```
+    pub fn record(&mut self, status: MigrationStatus) {
+        self.total += 1;
+        match status {
+            MigrationStatus::Legacy => self.legacy += 1,
+            MigrationStatus::Migrated => self.migrated += 1,
+            MigrationStatus::Partial => self.partial += 1,
+            MigrationStatus::NoModels | _ => self.no_models += 1,
+        }
+    }
+
     /// Returns the migration progress as a percentage.
     ///
     /// Calculated as: `migrated / (legacy + migrated + partial) * 100`
@@ -220,6 +697,7 @@ impl StatsSnapshot {
     ///     partial: 10,
     ///     no_models: 0,
     ///     errors: 0,
+    ///     ..StatsSnapshot::default()
     /// };
     ///
     /// assert!((snap.progress_percent() - 60.0).abs() < 0.1);
@@ -251,6 +729,7 @@ impl StatsSnapshot {
     ///     partial: 10,
     ///     no_models: 0,
     ///     errors: 0,
+    ///     ..StatsSnapshot::default()
     /// };
     ///
     /// assert_eq!(snap.needs_migration(), 40);
@@ -275,6 +754,7 @@ impl StatsSnapshot {
     ///     partial: 10,
     ///     no_models: 20,
     ///     errors: 0,
+    ///     ..StatsSnapshot::default()
     /// };
     ///
     /// assert_eq!(snap.with_models(), 100);
@@ -302,6 +782,7 @@ impl StatsSnapshot {
     ///     partial: 5,
     ///     no_models: 0,
     ///     errors: 5,
+    ///     ..StatsSnapshot::default()
     /// };
     ///
     /// assert!((snap.success_rate() - 95.0).abs() < 0.1);
@@ -315,6 +796,164 @@ impl StatsSnapshot {
 
         ((self.total - self.errors) as f64 / self.total as f64) * 100.0
     }
+
+    /// Returns the running scan's throughput in files per second.
+    ///
+    /// Returns `None` if no scan is running, or if it only just started.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Acceptable for statistics display
+    pub fn files_per_sec(&self) -> Option<f64> {
+        let elapsed = self.progress?.elapsed.as_secs_f64();
+        (elapsed > 0.0).then(|| self.total as f64 / elapsed)
+    }
+
+    /// Returns the estimated time until the running scan finishes, at its
+    /// throughput so far.
+    ///
+    /// Returns `None` if no scan is running or no file has been scanned yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ch_scanner::{ScanProgress, StatsSnapshot};
+    ///
+    /// let snap = StatsSnapshot {
+    ///     total: 250,
+    ///     progress: Some(ScanProgress {
+    ///         discovered: 1000,
+    ///         elapsed: Duration::from_secs(5),
+    ///     }),
+    ///     ..StatsSnapshot::default()
+    /// };
+    ///
+    /// assert_eq!(snap.files_per_sec(), Some(50.0));
+    /// assert_eq!(snap.eta(), Some(Duration::from_secs(15)));
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Acceptable for statistics display
+    pub fn eta(&self) -> Option<Duration> {
+        let remaining = self.progress?.discovered.saturating_sub(self.total);
+        let rate = self.files_per_sec().filter(|rate| *rate > 0.0)?;
+        Some(Duration::from_secs_f64(remaining as f64 / rate))
+    }
+}
+
+/// Signed change to the statistics caused by rescanning files.
+///
+/// Each rescanned file takes back what it counted before (its previous
+/// status, or an error) and adds what it counts now, so counters follow the
+/// current state of the tree instead of drifting upwards.
+///
+/// # Examples
+///
+/// ```
+/// use ch_core::MigrationStatus;
+/// use ch_scanner::{StatsDelta, StatsSnapshot};
+///
+/// // A legacy file was migrated, and a file that failed to parse now parses
+/// let mut delta = StatsDelta::default();
+/// delta.remove(MigrationStatus::Legacy);
+/// delta.add(MigrationStatus::Migrated);
+/// delta.remove_error();
+/// delta.add(MigrationStatus::NoModels);
+///
+/// let before = StatsSnapshot { total: 2, legacy: 1, errors: 1, ..StatsSnapshot::default() };
+/// let after = delta.applied_to(&before);
+/// assert_eq!((after.total, after.legacy, after.migrated), (2, 0, 1));
+/// assert_eq!((after.no_models, after.errors), (1, 0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct StatsDelta {
+    /// Change in the number of files scanned.
+    pub total: i64,
+    /// Change in the number of files with only legacy imports.
+    pub legacy: i64,
+    /// Change in the number of files with only migrated imports.
+    pub migrated: i64,
+    /// Change in the number of files with both legacy and migrated imports.
+    pub partial: i64,
+    /// Change in the number of files with no model imports.
+    pub no_models: i64,
+    /// Change in the number of files that failed to scan.
+    pub errors: i64,
+}
+
+impl StatsDelta {
+    /// Counts a file that now has the given status.
+    pub fn add(&mut self, status: MigrationStatus) {
+        self.total += 1;
+        if let Some(count) = self.status_mut(status) {
+            *count += 1;
+        }
+    }
+
+    /// Takes back a file that had the given status.
+    pub fn remove(&mut self, status: MigrationStatus) {
+        self.total -= 1;
+        if let Some(count) = self.status_mut(status) {
+            *count -= 1;
+        }
+    }
+
+    /// Counts a file that now fails to scan.
+    pub fn add_error(&mut self) {
+        self.total += 1;
+        self.errors += 1;
+    }
+
+    /// Takes back a file that failed to scan.
+    pub fn remove_error(&mut self) {
+        self.total -= 1;
+        self.errors -= 1;
+    }
+
+    /// Returns `true` if no counter changes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns `snapshot` with this change applied, clamping counters at
+    /// zero.
+    #[must_use]
+    pub fn applied_to(&self, snapshot: &StatsSnapshot) -> StatsSnapshot {
+        let apply = |count: u64, change: i64| count.saturating_add_signed(change);
+        StatsSnapshot {
+            total: apply(snapshot.total, self.total),
+            legacy: apply(snapshot.legacy, self.legacy),
+            migrated: apply(snapshot.migrated, self.migrated),
+            partial: apply(snapshot.partial, self.partial),
+            no_models: apply(snapshot.no_models, self.no_models),
+            errors: apply(snapshot.errors, self.errors),
+            reused: snapshot.reused,
+            parsed: snapshot.parsed,
+            skipped: snapshot.skipped,
+            progress: snapshot.progress,
+        }
+    }
+
+    /// Returns the counter for files with `status`.
+    fn status_mut(&mut self, status: MigrationStatus) -> Option<&mut i64> {
+        match status {
+            MigrationStatus::Legacy => Some(&mut self.legacy),
+            MigrationStatus::Migrated => Some(&mut self.migrated),
+            MigrationStatus::Partial => Some(&mut self.partial),
+            MigrationStatus::NoModels => Some(&mut self.no_models),
+            _ => None, // Handle any future status variants
+        }
+    }
+}
+
+impl std::ops::AddAssign for StatsDelta {
+    fn add_assign(&mut self, other: Self) {
+        self.total += other.total;
+        self.legacy += other.legacy;
+        self.migrated += other.migrated;
+        self.partial += other.partial;
+        self.no_models += other.no_models;
+        self.errors += other.errors;
+    }
 }
 
 #[cfg(test)]
@@ -367,6 +1006,79 @@ mod tests {
         assert_eq!(snap.legacy, 0);
     }
 
+    #[test]
+    fn test_scan_stats_apply_delta() {
+        let stats = ScanStats::new();
+        stats.increment_total();
+        stats.increment_legacy();
+
+        let mut delta = StatsDelta::default();
+        delta.remove(MigrationStatus::Legacy);
+        delta.add(MigrationStatus::Partial);
+        delta.add_error();
+        stats.apply(&delta);
+
+        let snap = stats.snapshot();
+        let before = StatsSnapshot { total: 1, legacy: 1, ..StatsSnapshot::default() };
+        assert_eq!(snap, delta.applied_to(&before));
+        assert_eq!((snap.total, snap.legacy, snap.partial, snap.errors), (2, 0, 1, 1));
+
+        // Counters stop at zero
+        let mut delta = StatsDelta::default();
+        delta.remove(MigrationStatus::Migrated);
+        delta.remove(MigrationStatus::Migrated);
+        stats.apply(&delta);
+        let snap = stats.snapshot();
+        assert_eq!((snap.total, snap.migrated), (0, 0));
+    }
+
+    #[test]
+    fn test_scan_stats_progress_while_running() {
+        let stats = ScanStats::new();
+        assert!(stats.snapshot().progress.is_none());
+        assert!(stats.snapshot().eta().is_none());
+
+        stats.start(4);
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(stats.snapshot().eta().is_none(), "no file scanned yet");
+        stats.increment_total();
+        let snap = stats.snapshot();
+        assert!(snap.files_per_sec().is_some_and(|rate| rate > 0.0));
+        assert!(snap.eta().is_some());
+
+        // A finished scan compares equal to the same counts without progress
+        stats.finish();
+        assert_eq!(stats.snapshot(), StatsSnapshot { total: 1, ..StatsSnapshot::default() });
+        stats.start(4);
+        stats.reset();
+        assert!(stats.snapshot().progress.is_none());
+    }
+
+    #[test]
+    fn test_scan_stats_parse_counts() {
+        assert_eq!(SizeBucket::of(0), SizeBucket::Small);
+        assert_eq!(SizeBucket::of(4_095), SizeBucket::Small);
+        assert_eq!(SizeBucket::of(4_096), SizeBucket::Medium);
+        assert_eq!(SizeBucket::of(262_144), SizeBucket::Huge);
+        assert_eq!(FileKind::of(Utf8Path::new("a.d.ts")), FileKind::Ts);
+
+        let stats = ScanStats::new();
+        stats.record_parsed(Utf8Path::new("a.ts"), 100);
+        stats.record_parsed(Utf8Path::new("b.tsx"), 50_000);
+        stats.record_parsed(Utf8Path::new("c.ts"), 300_000);
+        let parsed = stats.snapshot().parsed;
+        assert_eq!((parsed.ts, parsed.tsx, parsed.bytes), (2, 1, 350_100));
+        assert_eq!(parsed.sizes, [1, 0, 1, 1]);
+        assert_eq!(parsed.average_bytes(), Some(116_700));
+
+        // Counts survive a store and go with a reset
+        stats.store(&StatsSnapshot { parsed, ..StatsSnapshot::default() });
+        assert_eq!(stats.snapshot().parsed, parsed);
+        stats.reset();
+        assert!(stats.snapshot().parsed.is_empty());
+        assert_eq!(ParseCounts::default().average_bytes(), None);
+    }
+
     #[test]
     fn test_stats_snapshot_progress_percent() {
         // No files with models -> 100%
@@ -398,6 +1110,7 @@ mod tests {
             partial: 10,
             no_models: 0,
             errors: 0,
+            ..StatsSnapshot::default()
         };
         assert!((snap.progress_percent() - 60.0).abs() < f64::EPSILON);
     }
@@ -411,6 +1124,7 @@ mod tests {
             partial: 20,
             no_models: 0,
             errors: 0,
+            ..StatsSnapshot::default()
         };
         assert_eq!(snap.needs_migration(), 50);
     }
@@ -424,6 +1138,7 @@ mod tests {
             partial: 20,
             no_models: 20,
             errors: 0,
+            ..StatsSnapshot::default()
         };
         assert_eq!(snap.with_models(), 100);
     }
@@ -452,6 +1167,22 @@ mod tests {
         assert!((snap.success_rate() - 95.0).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn test_stats_snapshot_record() {
+        let mut snap = StatsSnapshot::default();
+        snap.record(MigrationStatus::Legacy);
+        snap.record(MigrationStatus::Partial);
+        snap.record(MigrationStatus::Migrated);
+        snap.record(MigrationStatus::NoModels);
+
+        assert_eq!(snap.total, 4);
+        assert_eq!(snap.legacy, 1);
+        assert_eq!(snap.partial, 1);
+        assert_eq!(snap.migrated, 1);
+        assert_eq!(snap.no_models, 1);
+        assert_eq!(snap.errors, 0);
+    }
+
     #[test]
     fn test_stats_snapshot_serialization() {
         let snap = StatsSnapshot {
@@ -461,6 +1192,7 @@ mod tests {
             partial: 10,
             no_models: 0,
             errors: 0,
+            ..StatsSnapshot::default()
         };
 
         let json = serde_json::to_string(&snap).expect("Serialization failed");
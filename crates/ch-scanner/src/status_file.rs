@@ -0,0 +1,105 @@
+//! Current statistics written to a JSON file for polling.
+//!
+//! Watch mode can keep a small status file up to date so dashboards and
+//! editor integrations can poll it instead of talking to a server. The file
+//! holds one [`StatusFile`] object and is replaced atomically (written to a
+//! temporary file next to it, then renamed), so readers never see a partial
+//! write.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use camino::Utf8Path;
+//! use ch_scanner::{write_status_file, StatsSnapshot, StatusFile};
+//!
+//! let status = StatusFile::new(StatsSnapshot::default());
+//! write_status_file(Utf8Path::new(".ch-migrate/status.json"), &status)?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::fs;
+use std::io;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::stats::StatsSnapshot;
+
+/// Contents of the status file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusFile {
+    /// When the statistics last changed (Unix timestamp in seconds).
+    pub updated_at: u64,
+
+    /// Current statistics.
+    pub stats: StatsSnapshot,
+}
+
+impl StatusFile {
+    /// Creates a status for the given statistics, updated now.
+    #[must_use]
+    pub fn new(stats: StatsSnapshot) -> Self {
+        let updated_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        Self { updated_at, stats }
+    }
+}
+
+/// Atomically replaces the status file at `path`.
+///
+/// Parent directories are created if they don't exist.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written or renamed into place.
+pub fn write_status_file(path: &Utf8Path, status: &StatusFile) -> io::Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut contents = serde_json::to_string_pretty(status)?;
+    contents.push('\n');
+
+    let temp_path = Utf8PathBuf::from(format!("{path}.tmp"));
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp_path);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_status_file_replaces_contents() {
+        let (_dir, root) = crate::tests::temp_root();
+        let path = root.join("nested/status.json");
+
+        let first = StatusFile::new(StatsSnapshot {
+            total: 10,
+            legacy: 4,
+            ..StatsSnapshot::default()
+        });
+        let second = StatusFile {
+            stats: StatsSnapshot {
+                total: 10,
+                migrated: 10,
+                ..StatsSnapshot::default()
+            },
+            ..first.clone()
+        };
+
+        assert!(write_status_file(&path, &first).is_ok());
+        assert!(write_status_file(&path, &second).is_ok());
+
+        let written = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<StatusFile>(&contents).ok());
+        assert_eq!(written, Some(second));
+        assert!(!Utf8PathBuf::from(format!("{path}.tmp")).exists());
+    }
+}
@@ -0,0 +1,494 @@
+//! Restricting scans to the files a `tsconfig.json` project builds.
+//!
+//! Trees often hold directories the TypeScript compiler never sees: old
+//! prototypes, scripts, copies kept for reference. [`TsProject`] reads a
+//! project's `files`, `include` and `exclude` settings so a
+//! [`FileWalker`](crate::FileWalker) can leave everything else out, the way
+//! `tsc` does:
+//!
+//! - files listed in `files` are always built
+//! - files matching an `include` pattern are built unless they match an
+//!   `exclude` pattern
+//! - without `files` or `include`, everything under the project directory
+//!   is included
+//! - without `exclude`, `node_modules`, `bower_components` and
+//!   `jspm_packages` directories and the `outDir` are excluded
+//!
+//! Patterns are relative to the directory of the config file that sets
+//! them. `*` and `?` don't match `/`, `**/` matches any number of
+//! directories, and a pattern whose last part has no `.` or wildcard names
+//! a directory and covers everything below it. Settings missing from a
+//! config are taken from the configs it `extends`, if given by a relative
+//! path; configs extended by package name are not followed.
+//!
+//! The project's `paths` aliases are read too, with their targets made
+//! absolute from `baseUrl`, or from the directory of the config setting
+//! `paths` without one, so imports through them can be matched against the
+//! shared directories.
+//!
+//! Config files may contain comments and trailing commas, as `tsc` allows.
+
+use std::collections::BTreeMap;
+use std::fs;
+
+use camino::{Utf8Component, Utf8Path, Utf8PathBuf};
+use ch_core::FxHashSet;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::error::ScanError;
+
+/// Directories `tsc` excludes when a project sets no `exclude`.
+const DEFAULT_EXCLUDE: &[&str] = &["**/node_modules", "**/bower_components", "**/jspm_packages"];
+
+/// Most configs followed through `extends`, guarding against cycles.
+const MAX_EXTENDS_DEPTH: usize = 16;
+
+/// The settings of a config file this module uses.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawConfig {
+    extends: Option<Extends>,
+    files: Option<Vec<String>>,
+    include: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+    #[serde(default)]
+    compiler_options: RawCompilerOptions,
+}
+
+/// The compiler options this module uses.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawCompilerOptions {
+    out_dir: Option<String>,
+    base_url: Option<String>,
+    paths: Option<BTreeMap<String, Vec<String>>>,
+}
+
+/// One config extended, or several (TypeScript 5.0 and later).
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Extends {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// A setting with the directory of the config file that set it.
+#[derive(Debug)]
+struct Setting<T> {
+    dir: Utf8PathBuf,
+    value: T,
+}
+
+/// Settings gathered from a config and the configs it extends.
+#[derive(Debug, Default)]
+struct Settings {
+    files: Option<Setting<Vec<String>>>,
+    include: Option<Setting<Vec<String>>>,
+    exclude: Option<Setting<Vec<String>>>,
+    out_dir: Option<Setting<String>>,
+    base_url: Option<Setting<String>>,
+    paths: Option<Setting<BTreeMap<String, Vec<String>>>>,
+}
+
+impl Settings {
+    /// Reads the config at `path`, filling in the settings not set yet by
+    /// a config extending it.
+    fn read(&mut self, path: &Utf8Path, depth: usize) -> Result<(), ScanError> {
+        if depth > MAX_EXTENDS_DEPTH {
+            return Err(ScanError::config(format!("tsconfig extends too deeply: {path}")));
+        }
+        let text = fs::read_to_string(path)
+            .map_err(|e| ScanError::config(format!("failed to read tsconfig {path}: {e}")))?;
+        let raw: RawConfig = serde_json::from_str(&strip_jsonc(&text))
+            .map_err(|e| ScanError::config(format!("invalid tsconfig {path}: {e}")))?;
+        let dir = path.parent().map_or_else(Utf8PathBuf::new, Utf8Path::to_owned);
+
+        fill(&mut self.files, raw.files, &dir);
+        fill(&mut self.include, raw.include, &dir);
+        fill(&mut self.exclude, raw.exclude, &dir);
+        fill(&mut self.out_dir, raw.compiler_options.out_dir, &dir);
+        fill(&mut self.base_url, raw.compiler_options.base_url, &dir);
+        fill(&mut self.paths, raw.compiler_options.paths, &dir);
+
+        let bases = match raw.extends {
+            None => Vec::new(),
+            Some(Extends::One(base)) => vec![base],
+            Some(Extends::Many(bases)) => bases,
+        };
+        // Later bases take precedence over earlier ones
+        for base in bases.iter().rev() {
+            if !base.starts_with('.') && !Utf8Path::new(base).is_absolute() {
+                debug!(config = %path, extends = %base, "Not following tsconfig package");
+                continue;
+            }
+            let mut base_path = dir.join(base);
+            if base_path.extension() != Some("json") {
+                base_path.set_extension("json");
+            }
+            self.read(&base_path, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// Sets `setting` to `value`, set in a config in `dir`, unless already set.
+fn fill<T>(setting: &mut Option<Setting<T>>, value: Option<T>, dir: &Utf8Path) {
+    if setting.is_none() {
+        *setting = value.map(|value| Setting {
+            dir: dir.to_owned(),
+            value,
+        });
+    }
+}
+
+/// Patterns compiled relative to the directories of the configs setting them.
+#[derive(Debug)]
+struct Patterns {
+    set: GlobSet,
+}
+
+impl Patterns {
+    /// Compiles `patterns` relative to `dir`; `directories` patterns also
+    /// cover everything below what they match.
+    fn new<S: AsRef<str>>(
+        dir: &Utf8Path,
+        patterns: &[S],
+        directories: bool,
+    ) -> Result<Self, ScanError> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let pattern = pattern.as_ref();
+            let mut base = normalize(dir);
+            let mut relative = pattern.trim_end_matches('/');
+            loop {
+                if let Some(rest) = relative.strip_prefix("./") {
+                    relative = rest;
+                } else if let Some(rest) = relative.strip_prefix("../") {
+                    base.pop();
+                    relative = rest;
+                } else {
+                    break;
+                }
+            }
+            let base = globset::escape(base.as_str());
+            let last = relative.rsplit('/').next().unwrap_or(relative);
+            let globs = if directories {
+                vec![format!("{base}/{relative}"), format!("{base}/{relative}/**")]
+            } else if last.contains(['.', '*', '?']) {
+                vec![format!("{base}/{relative}")]
+            } else {
+                vec![format!("{base}/{relative}/**")]
+            };
+            for glob in globs {
+                let glob = GlobBuilder::new(&glob).literal_separator(true).build().map_err(|e| {
+                    ScanError::config(format!("invalid tsconfig pattern {pattern}: {e}"))
+                })?;
+                builder.add(glob);
+            }
+        }
+        let set = builder
+            .build()
+            .map_err(|e| ScanError::config(format!("invalid tsconfig patterns: {e}")))?;
+        Ok(Self { set })
+    }
+
+    /// Returns `true` if the normalized `path` matches a pattern.
+    fn is_match(&self, path: &Utf8Path) -> bool {
+        self.set.is_match(path)
+    }
+}
+
+/// The files a `tsconfig.json` project builds.
+#[derive(Debug)]
+pub(crate) struct TsProject {
+    /// Files listed in `files`, normalized.
+    files: FxHashSet<Utf8PathBuf>,
+    /// `include` patterns, or `None` if only `files` are built.
+    include: Option<Patterns>,
+    /// `exclude` patterns.
+    exclude: Patterns,
+    /// `paths` aliases with their absolute targets.
+    aliases: Vec<(String, Vec<String>)>,
+}
+
+impl TsProject {
+    /// Reads the project of the config file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScanError::Config`] if the config or a config it extends
+    /// can't be read or parsed, or holds an invalid pattern.
+    pub(crate) fn load(path: &Utf8Path) -> Result<Self, ScanError> {
+        let mut settings = Settings::default();
+        settings.read(path, 0)?;
+        let dir = path.parent().map_or_else(Utf8PathBuf::new, Utf8Path::to_owned);
+
+        let files = settings.files.as_ref().map_or_else(FxHashSet::default, |files| {
+            files.value.iter().map(|file| normalize(&files.dir.join(file))).collect()
+        });
+        let include = match (&settings.include, &settings.files) {
+            (Some(include), _) => Some(Patterns::new(&include.dir, &include.value, false)?),
+            (None, Some(_)) => None,
+            (None, None) => Some(Patterns::new(&dir, &["**/*"], false)?),
+        };
+        let exclude = if let Some(exclude) = &settings.exclude {
+            Patterns::new(&exclude.dir, &exclude.value, true)?
+        } else {
+            let mut patterns: Vec<String> =
+                DEFAULT_EXCLUDE.iter().map(|&pattern| pattern.to_owned()).collect();
+            if let Some(out_dir) = &settings.out_dir {
+                let out_dir = normalize(&out_dir.dir.join(&out_dir.value));
+                if let Ok(relative) = out_dir.strip_prefix(normalize(&dir)) {
+                    patterns.push(relative.to_string());
+                }
+            }
+            Patterns::new(&dir, &patterns, true)?
+        };
+
+        let aliases = settings.paths.map_or_else(Vec::new, |paths| {
+            let base = settings
+                .base_url
+                .map_or(paths.dir, |base_url| base_url.dir.join(base_url.value));
+            paths
+                .value
+                .into_iter()
+                .map(|(pattern, targets)| {
+                    let targets =
+                        targets.iter().map(|target| normalize(&base.join(target)).into()).collect();
+                    (pattern, targets)
+                })
+                .collect()
+        });
+
+        Ok(Self {
+            files,
+            include,
+            exclude,
+            aliases,
+        })
+    }
+
+    /// Returns the `paths` aliases, each with its targets made absolute.
+    pub(crate) fn path_aliases(&self) -> &[(String, Vec<String>)] {
+        &self.aliases
+    }
+
+    /// Returns `true` if the project builds the file at `path`.
+    pub(crate) fn includes(&self, path: &Utf8Path) -> bool {
+        let path = normalize(path);
+        self.files.contains(&path)
+            || (self.include.as_ref().is_some_and(|include| include.is_match(&path))
+                && !self.exclude.is_match(&path))
+    }
+}
+
+/// Makes `path` absolute, removing `.` components and resolving `..` ones
+/// lexically.
+pub(crate) fn normalize(path: &Utf8Path) -> Utf8PathBuf {
+    let absolute = std::path::absolute(path)
+        .ok()
+        .and_then(|absolute| Utf8PathBuf::from_path_buf(absolute).ok())
+        .unwrap_or_else(|| path.to_owned());
+    clean(&absolute)
+}
+
+/// Removes `.` components from `path` and resolves `..` ones lexically,
+/// keeping leading `..` components of a relative path.
+pub(crate) fn clean(path: &Utf8Path) -> Utf8PathBuf {
+    let mut normalized = Utf8PathBuf::new();
+    for component in path.components() {
+        match component {
+            Utf8Component::CurDir => {}
+            Utf8Component::ParentDir => match normalized.components().next_back() {
+                Some(Utf8Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                Some(Utf8Component::RootDir | Utf8Component::Prefix(_)) => {}
+                _ => normalized.push(".."),
+            },
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Removes comments and trailing commas from JSON, as `tsc` accepts them in
+/// config files.
+fn strip_jsonc(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            if c == '\\' {
+                out.extend(chars.next());
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => {
+                while chars.next_if(|&next| next != '\n').is_some() {}
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = None;
+                for next in chars.by_ref() {
+                    if previous == Some('*') && next == '/' {
+                        break;
+                    }
+                    previous = Some(next);
+                }
+            }
+            (']' | '}', _) => {
+                let kept = out.trim_end().len();
+                if out[..kept].ends_with(',') {
+                    out.truncate(kept - 1);
+                }
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Utf8Path, file: &str, contents: &str) {
+        let path = dir.join(file);
+        assert!(path.parent().is_some_and(|parent| fs::create_dir_all(parent).is_ok()));
+        assert!(fs::write(path, contents).is_ok());
+    }
+
+    #[test]
+    fn test_strip_jsonc() {
+        let text = concat!(
+            "{\n  // comment\n",
+            "  \"a\": \"// not /* a comment\", /* x */\n",
+            "  \"b\": [1, 2,],\n}",
+        );
+        let value: Result<serde_json::Value, _> = serde_json::from_str(&strip_jsonc(text));
+        assert_eq!(
+            value.ok(),
+            Some(serde_json::json!({"a": "// not /* a comment", "b": [1, 2]}))
+        );
+        assert_eq!(normalize(Utf8Path::new("/a/../b/./c.ts")), "/b/c.ts");
+        assert!(normalize(Utf8Path::new("./b/c.ts")).ends_with("b/c.ts"));
+    }
+
+    #[test]
+    fn test_clean() {
+        assert_eq!(clean(Utf8Path::new("app/./x/../y")), "app/y");
+        assert_eq!(clean(Utf8Path::new("app/../../y")), "../y");
+        assert_eq!(clean(Utf8Path::new("../../y")), "../../y");
+        assert_eq!(clean(Utf8Path::new("/../y")), "/y");
+    }
+
+    #[test]
+    fn test_project_include_exclude_and_files() {
+        let (_dir, root) = crate::tests::temp_root();
+        write(
+            &root,
+            "tsconfig.json",
+            r#"{
+                // Only the app is built
+                "include": ["src", "types/*.d.ts"],
+                "exclude": ["src/legacy", "**/*.spec.ts",],
+                "files": ["tools/build.ts"],
+            }"#,
+        );
+        let project = TsProject::load(&root.join("tsconfig.json"));
+        assert!(project.is_ok());
+        let Ok(project) = project else {
+            return;
+        };
+
+        assert!(project.includes(&root.join("src/app/a.ts")));
+        assert!(project.includes(&root.join("./types/env.d.ts")));
+        assert!(project.includes(&root.join("tools/build.ts")));
+        assert!(!project.includes(&root.join("src/legacy/b.ts")));
+        assert!(!project.includes(&root.join("src/app/a.spec.ts")));
+        assert!(!project.includes(&root.join("types/nested/x.d.ts")), "* stops at /");
+        assert!(!project.includes(&root.join("tools/other.ts")));
+        assert!(!project.includes(&root.join("prototypes/c.ts")));
+    }
+
+    #[test]
+    fn test_project_defaults_and_extends() {
+        let (_dir, root) = crate::tests::temp_root();
+        write(
+            &root,
+            "config/base.json",
+            r#"{"include": ["../src"], "compilerOptions": {"outDir": "../out"}}"#,
+        );
+        write(&root, "tsconfig.json", r#"{"extends": ["@scope/preset", "./config/base"]}"#);
+        write(&root, "plain/tsconfig.json", r#"{"compilerOptions": {"outDir": "build"}}"#);
+
+        let project = TsProject::load(&root.join("tsconfig.json"));
+        assert!(project.is_ok());
+        let Ok(project) = project else {
+            return;
+        };
+        assert!(project.includes(&root.join("src/a.ts")));
+        assert!(!project.includes(&root.join("scripts/b.ts")));
+        assert!(!project.includes(&root.join("src/node_modules/pkg/c.ts")));
+
+        // Without include or files everything but the default excludes is built
+        let plain = TsProject::load(&root.join("plain/tsconfig.json"));
+        assert!(plain.is_ok());
+        let Ok(plain) = plain else {
+            return;
+        };
+        assert!(plain.includes(&root.join("plain/deep/a.ts")));
+        assert!(!plain.includes(&root.join("plain/build/a.ts")));
+        assert!(!plain.includes(&root.join("src/a.ts")));
+
+        write(&root, "bad.json", "{\"include\": [}");
+        assert!(matches!(TsProject::load(&root.join("bad.json")), Err(ScanError::Config(_))));
+        assert!(TsProject::load(&root.join("missing.json")).is_err());
+    }
+
+    #[test]
+    fn test_project_path_aliases() {
+        let (_dir, root) = crate::tests::temp_root();
+        let root = normalize(&root);
+        write(
+            &root,
+            "config/base.json",
+            r#"{"compilerOptions": {"paths": {"@shared/*": ["shared/*", "../vendor/*"]}}}"#,
+        );
+        write(
+            &root,
+            "tsconfig.json",
+            r#"{"extends": "./config/base", "compilerOptions": {"baseUrl": "src"}}"#,
+        );
+        write(
+            &root,
+            "plain/tsconfig.json",
+            r#"{"compilerOptions": {"paths": {"@models": ["../shared_2023/models"]}}}"#,
+        );
+
+        // Targets are taken from baseUrl, even one set by an extending config
+        let project = TsProject::load(&root.join("tsconfig.json"));
+        let aliases = project.as_ref().map(TsProject::path_aliases).ok();
+        let targets = vec![root.join("src/shared/*").into(), root.join("vendor/*").into()];
+        assert_eq!(aliases, Some(&[("@shared/*".to_owned(), targets)][..]));
+
+        // Without baseUrl they're taken from the config setting paths
+        let plain = TsProject::load(&root.join("plain/tsconfig.json"));
+        let aliases = plain.as_ref().map(TsProject::path_aliases).ok();
+        let targets = vec![root.join("shared_2023/models").into()];
+        assert_eq!(aliases, Some(&[("@models".to_owned(), targets)][..]));
+    }
+}
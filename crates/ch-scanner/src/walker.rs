@@ -6,9 +6,16 @@
 //! # Features
 //!
 //! - Respects `.gitignore` and `.ignore` patterns
+//! - Respects [`.chmigrateignore`](IGNORE_FILE_NAME) patterns, for files to
+//!   leave out of migration tracking only
 //! - Filters for TypeScript files (`.ts`, `.tsx`)
 //! - Skips hidden directories and files
+//! - Filters files by include and exclude glob patterns
+//! - Optionally restricts files to those a `tsconfig.json` project builds
+//! - When following symbolic links, walks each directory and lists each file
+//!   once, however many links reach it
 //! - Converts paths to UTF-8 [`Utf8PathBuf`](camino::Utf8PathBuf)
+//! - Records paths it leaves out, and why, as [`SkippedPath`]s
 //!
 //! # Examples
 //!
@@ -24,10 +31,22 @@
 //! }
 //! ```
 
+use std::collections::BTreeSet;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
 use camino::{Utf8Path, Utf8PathBuf};
-use ignore::WalkBuilder;
+use ch_core::FxHashSet;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::gitignore::GitignoreBuilder;
+use ignore::{Match, WalkBuilder};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
 
 use crate::error::ScanError;
+use crate::tsconfig::TsProject;
 
 /// Default directories to skip during scanning.
 ///
@@ -46,9 +65,184 @@ const SKIP_DIRECTORIES: &[&str] = &[
     ".nuxt",
 ];
 
+/// Name of the ignore file for paths to leave out of migration tracking.
+///
+/// It uses `.gitignore` syntax and, like `.gitignore`, applies to the
+/// directory holding it and everything below, so a `.chmigrateignore` at the
+/// scan root can exclude generated code without touching the real
+/// `.gitignore`.
+pub const IGNORE_FILE_NAME: &str = ".chmigrateignore";
+
 /// TypeScript file extensions to include in the scan.
 const TYPESCRIPT_EXTENSIONS: &[&str] = &["ts", "tsx"];
 
+/// Returns `true` if `path` has a TypeScript extension.
+pub(crate) fn is_typescript_path(path: &Utf8Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| TYPESCRIPT_EXTENSIONS.contains(&ext))
+}
+
+/// Returns `true` if a [`.chmigrateignore`](IGNORE_FILE_NAME) in the
+/// directories from `root` down to `path` ignores `path`.
+///
+/// As in a walk, a deeper file overrides the ones above it, and the files
+/// are read on every call so edits to them apply at once. An ignore file
+/// that can't be parsed is skipped.
+pub(crate) fn is_ignored_by_file(root: &Utf8Path, path: &Utf8Path) -> bool {
+    if !path.starts_with(root) {
+        return false;
+    }
+    for dir in path.ancestors().skip(1) {
+        let file = dir.join(IGNORE_FILE_NAME);
+        if file.is_file() {
+            let mut builder = GitignoreBuilder::new(dir);
+            if let Some(e) = builder.add(&file) {
+                debug!(path = %file, error = %e, "Skipping invalid ignore file");
+            } else if let Ok(matcher) = builder.build() {
+                match matcher.matched_path_or_any_parents(path, false) {
+                    Match::Ignore(_) => return true,
+                    Match::Whitelist(_) => return false,
+                    Match::None => {}
+                }
+            }
+        }
+        if dir == root {
+            break;
+        }
+    }
+    false
+}
+
+/// Why the walker left a path out of a scan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// A directory that could not be read.
+    PermissionDenied,
+    /// A symbolic link whose target doesn't exist, or that loops back to one
+    /// of its ancestors.
+    BrokenSymlink,
+    /// A symbolic link to a directory or TypeScript file, not followed
+    /// because following links is disabled.
+    Symlink,
+    /// A TypeScript file whose path is not valid UTF-8.
+    NonUtf8,
+    /// A directory on the skip list (e.g. `node_modules`) holding TypeScript
+    /// files.
+    Excluded,
+    /// A directory or TypeScript file already reached through another path,
+    /// by following a symbolic link.
+    Duplicate,
+}
+
+impl SkipReason {
+    /// All reasons, in display order.
+    pub const ALL: [Self; 6] = [
+        Self::PermissionDenied,
+        Self::BrokenSymlink,
+        Self::Symlink,
+        Self::NonUtf8,
+        Self::Excluded,
+        Self::Duplicate,
+    ];
+
+    /// Returns a short human-readable description.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::PermissionDenied => "permission denied",
+            Self::BrokenSymlink => "broken symlink",
+            Self::Symlink => "symlink not followed",
+            Self::NonUtf8 => "non-UTF-8 path",
+            Self::Excluded => "excluded",
+            Self::Duplicate => "duplicate path",
+        }
+    }
+}
+
+/// A path the walker left out of a scan.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkippedPath {
+    /// The skipped path (lossily converted if it isn't UTF-8).
+    pub path: Utf8PathBuf,
+    /// Why it was skipped.
+    pub reason: SkipReason,
+}
+
+/// Compiled include and exclude glob patterns.
+///
+/// Patterns are matched against a file's path relative to the innermost of
+/// `bases` holding it, so `src/**` and `**/*.spec.ts` mean the same thing
+/// for every directory a scanner walks under its roots. `*` also matches `/`, so `*.spec.ts`
+/// excludes spec files at any depth.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PathGlobs {
+    /// Directories the patterns are relative to.
+    bases: Vec<Utf8PathBuf>,
+    /// Files must match one of these, if set.
+    include: Option<GlobSet>,
+    /// Files matching one of these are left out, if set.
+    exclude: Option<GlobSet>,
+}
+
+impl PathGlobs {
+    /// Compiles `include` and `exclude` patterns relative to `bases`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScanError::Config`] if a pattern is invalid.
+    pub(crate) fn new<'a, S: AsRef<str>>(
+        bases: impl IntoIterator<Item = &'a Utf8Path>,
+        include: &[S],
+        exclude: &[S],
+    ) -> Result<Self, ScanError> {
+        Ok(Self {
+            bases: bases.into_iter().map(Utf8Path::to_owned).collect(),
+            include: compile_globs(include)?,
+            exclude: compile_globs(exclude)?,
+        })
+    }
+
+    /// Returns `true` if the patterns let `path` into a scan.
+    pub(crate) fn allows(&self, path: &Utf8Path) -> bool {
+        let relative = self
+            .bases
+            .iter()
+            .filter_map(|base| path.strip_prefix(base).ok())
+            .min_by_key(|relative| relative.as_str().len())
+            .unwrap_or(path);
+        self.include.as_ref().is_none_or(|include| include.is_match(relative))
+            && !self.exclude.as_ref().is_some_and(|exclude| exclude.is_match(relative))
+    }
+}
+
+/// Compiles glob patterns into a set, or `None` if there are none.
+fn compile_globs<S: AsRef<str>>(patterns: &[S]) -> Result<Option<GlobSet>, ScanError> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern.as_ref())
+            .map_err(|e| ScanError::config(format!("invalid glob pattern: {e}")))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map(Some)
+        .map_err(|e| ScanError::config(format!("invalid glob patterns: {e}")))
+}
+
+/// The result of walking a directory tree.
+#[derive(Debug, Default)]
+pub struct WalkOutput {
+    /// TypeScript files found.
+    pub paths: Vec<Utf8PathBuf>,
+    /// Paths left out, in the order they were found.
+    pub skipped: Vec<SkippedPath>,
+}
+
 /// A file walker that discovers TypeScript files in a directory tree.
 ///
 /// Uses the `ignore` crate for efficient traversal with gitignore support.
@@ -80,6 +274,10 @@ pub struct FileWalker {
     skip_dirs: Vec<String>,
     /// Whether to follow symbolic links.
     follow_links: bool,
+    /// Include and exclude patterns for files.
+    globs: Arc<PathGlobs>,
+    /// Project restricting files to those it builds, if any.
+    project: Option<Arc<TsProject>>,
 }
 
 impl FileWalker {
@@ -118,6 +316,11 @@ impl FileWalker {
             root: root.to_owned(),
             skip_dirs: Vec::new(),
             follow_links: false,
+            globs: Arc::new(PathGlobs {
+                bases: vec![root.to_owned()],
+                ..PathGlobs::default()
+            }),
+            project: None,
         })
     }
 
@@ -154,6 +357,86 @@ impl FileWalker {
         self
     }
 
+    /// Only walks files matching at least one of `patterns`.
+    ///
+    /// Patterns are matched against paths relative to the root, e.g.
+    /// `src/app/**`, and replace any include patterns set before. By default
+    /// every TypeScript file is included.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScanError::Config`] if a pattern is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let walker = FileWalker::new(root)?
+    ///     .with_include_globs(&["src/app/**"])?;
+    /// ```
+    pub fn with_include_globs<S: AsRef<str>>(mut self, patterns: &[S]) -> Result<Self, ScanError> {
+        Arc::make_mut(&mut self.globs).include = compile_globs(patterns)?;
+        Ok(self)
+    }
+
+    /// Leaves out files matching any of `patterns`.
+    ///
+    /// Patterns are matched against paths relative to the root, e.g.
+    /// `**/*.spec.ts`, and replace any exclude patterns set before. Unlike
+    /// skip-listed directories, excluded files are not recorded as
+    /// [`SkippedPath`]s.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScanError::Config`] if a pattern is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let walker = FileWalker::new(root)?
+    ///     .with_exclude_globs(&["**/*.spec.ts", "src/legacy/**"])?;
+    /// ```
+    pub fn with_exclude_globs<S: AsRef<str>>(mut self, patterns: &[S]) -> Result<Self, ScanError> {
+        Arc::make_mut(&mut self.globs).exclude = compile_globs(patterns)?;
+        Ok(self)
+    }
+
+    /// Uses compiled patterns, which may be relative to a directory above
+    /// the root.
+    #[must_use]
+    pub(crate) fn with_path_globs(mut self, globs: Arc<PathGlobs>) -> Self {
+        self.globs = globs;
+        self
+    }
+
+    /// Only walks files the TypeScript project configured by the
+    /// `tsconfig.json` at `path` builds.
+    ///
+    /// The project's `files`, `include` and `exclude` settings are applied
+    /// as `tsc` applies them, relative to the config file, following
+    /// relative `extends`. Files the project doesn't build are not recorded
+    /// as [`SkippedPath`]s.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScanError::Config`] if the config can't be read or parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let walker = FileWalker::new(root)?
+    ///     .with_tsconfig(&root.join("tsconfig.json"))?;
+    /// ```
+    pub fn with_tsconfig(self, path: &Utf8Path) -> Result<Self, ScanError> {
+        Ok(self.with_project(Some(Arc::new(TsProject::load(path)?))))
+    }
+
+    /// Uses an already loaded project, or none.
+    #[must_use]
+    pub(crate) fn with_project(mut self, project: Option<Arc<TsProject>>) -> Self {
+        self.project = project;
+        self
+    }
+
     /// Collects all TypeScript file paths in the directory tree.
     ///
     /// Walks the directory tree starting from the root, filtering for
@@ -179,78 +462,178 @@ impl FileWalker {
     /// }
     /// ```
     pub fn collect_paths(&self) -> Result<Vec<Utf8PathBuf>, ScanError> {
-        let mut paths = Vec::new();
-        let walker = self.build_walker();
+        self.walk().map(|output| output.paths)
+    }
 
-        for result in walker {
-            let entry = result?;
+    /// Walks the directory tree, collecting TypeScript files and the paths
+    /// left out.
+    ///
+    /// Unreadable directories, broken or unfollowed symbolic links, non-UTF-8
+    /// paths and skip-listed directories are recorded as [`SkippedPath`]s
+    /// instead of failing the walk. When following links, so are
+    /// directories and files reached before through another path: entries
+    /// are visited in name order, and the first path reaching each is kept.
+    /// Files hidden by `.gitignore` or [`.chmigrateignore`](IGNORE_FILE_NAME),
+    /// by being hidden, by glob patterns or by the `tsconfig.json` project
+    /// are not recorded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScanError::Walk`] if traversal fails for another reason,
+    /// such as an invalid ignore file.
+    pub fn walk(&self) -> Result<WalkOutput, ScanError> {
+        let mut output = WalkOutput::default();
+        let mut excluded = BTreeSet::new();
+        let visited = self.follow_links.then(Arc::<Visited>::default);
+
+        for result in self.build_walker(visited.as_ref()) {
+            let entry = match result {
+                Ok(entry) => entry,
+                Err(e) => {
+                    let Some(reason) = error_skip_reason(&e) else {
+                        return Err(e.into());
+                    };
+                    let path = error_path(&e).unwrap_or_else(|| self.root.as_std_path());
+                    debug!(path = %path.display(), reason = reason.label(), "Skipping path");
+                    output.skipped.push(SkippedPath {
+                        path: lossy_path(path),
+                        reason,
+                    });
+                    continue;
+                }
+            };
+            let path = entry.path();
+
+            if entry.path_is_symlink() && !self.follow_links {
+                if let Some(reason) = symlink_skip_reason(path) {
+                    output.skipped.push(SkippedPath {
+                        path: lossy_path(path),
+                        reason,
+                    });
+                }
+                continue;
+            }
 
             // Skip directories and non-files
             if !entry.file_type().is_some_and(|ft| ft.is_file()) {
                 continue;
             }
 
-            let path = entry.path();
-
             // Convert to UTF-8 path
-            let utf8_path = Utf8Path::from_path(path)
-                .ok_or_else(|| ScanError::NonUtf8Path(path.to_owned()))?;
+            let Some(utf8_path) = Utf8Path::from_path(path) else {
+                if path.extension().is_some_and(|ext| {
+                    TYPESCRIPT_EXTENSIONS.iter().any(|ts| ext.as_encoded_bytes() == ts.as_bytes())
+                }) {
+                    output.skipped.push(SkippedPath {
+                        path: lossy_path(path),
+                        reason: SkipReason::NonUtf8,
+                    });
+                }
+                continue;
+            };
 
-            // Check if it's a TypeScript file
-            if !self.is_typescript_file(utf8_path) {
+            // Check if it's a TypeScript file the patterns let in
+            if !self.is_typescript_file(utf8_path) || !self.globs.allows(utf8_path) {
+                continue;
+            }
+            if self.project.as_ref().is_some_and(|project| !project.includes(utf8_path)) {
+                continue;
+            }
+            if visited.as_ref().is_some_and(|visited| !visited.first_visit(path)) {
+                debug!(path = %utf8_path, "Skipping file reached through another path");
+                output.skipped.push(SkippedPath {
+                    path: utf8_path.to_owned(),
+                    reason: SkipReason::Duplicate,
+                });
                 continue;
             }
 
-            // Skip files in excluded directories
-            if self.should_skip_path(utf8_path) {
+            // Skip files in excluded directories, recording each directory once
+            if let Some(dir) = self.skipped_ancestor(utf8_path) {
+                if excluded.insert(dir.clone()) {
+                    output.skipped.push(SkippedPath {
+                        path: dir,
+                        reason: SkipReason::Excluded,
+                    });
+                }
                 continue;
             }
 
-            paths.push(utf8_path.to_owned());
+            output.paths.push(utf8_path.to_owned());
         }
 
-        Ok(paths)
+        if let Some(visited) = visited {
+            output.skipped.extend(visited.duplicate_dirs.lock().drain(..).map(|path| {
+                SkippedPath {
+                    path,
+                    reason: SkipReason::Duplicate,
+                }
+            }));
+        }
+        Ok(output)
     }
 
     /// Builds the ignore walker with configured settings.
-    fn build_walker(&self) -> ignore::Walk {
-        WalkBuilder::new(&self.root)
+    ///
+    /// With `visited`, directories reached before are not entered again.
+    fn build_walker(&self, visited: Option<&Arc<Visited>>) -> ignore::Walk {
+        let mut builder = WalkBuilder::new(&self.root);
+        builder
             // Enable standard filters (.gitignore, .ignore, hidden files)
             .standard_filters(true)
+            .add_custom_ignore_filename(IGNORE_FILE_NAME)
             // Don't follow links by default
             .follow_links(self.follow_links)
             // Use a single thread for walking (we parallelize later)
             .threads(1)
             // Don't require the root to be a git repo
-            .require_git(false)
-            .build()
+            .require_git(false);
+        if let Some(visited) = visited {
+            let visited = Arc::clone(visited);
+            // Name order decides which of the paths to a directory is kept
+            builder.sort_by_file_name(Ord::cmp).filter_entry(move |entry| {
+                if !entry.file_type().is_some_and(|ft| ft.is_dir())
+                    || visited.first_visit(entry.path())
+                {
+                    return true;
+                }
+                debug!(path = %entry.path().display(), "Skipping directory reached before");
+                visited.duplicate_dirs.lock().push(lossy_path(entry.path()));
+                false
+            });
+        }
+        builder.build()
     }
 
     /// Checks if a path is a TypeScript file based on extension.
     #[allow(clippy::unused_self)] // Method signature kept for consistency
     fn is_typescript_file(&self, path: &Utf8Path) -> bool {
-        path.extension()
-            .is_some_and(|ext| TYPESCRIPT_EXTENSIONS.contains(&ext))
+        is_typescript_path(path)
     }
 
     /// Checks if a path should be skipped based on directory name.
+    #[cfg(test)]
     fn should_skip_path(&self, path: &Utf8Path) -> bool {
+        self.skipped_ancestor(path).is_some()
+    }
+
+    /// Returns the outermost skip-listed directory containing `path`, if any.
+    fn skipped_ancestor(&self, path: &Utf8Path) -> Option<Utf8PathBuf> {
+        let mut dir = Utf8PathBuf::new();
         // Check each component of the path
         for component in path.components() {
             let component_str = component.as_str();
+            dir.push(component_str);
 
-            // Skip standard directories
-            if SKIP_DIRECTORIES.contains(&component_str) {
-                return true;
-            }
-
-            // Skip user-specified directories
-            if self.skip_dirs.iter().any(|d| d == component_str) {
-                return true;
+            // Skip standard and user-specified directories
+            if SKIP_DIRECTORIES.contains(&component_str)
+                || self.skip_dirs.iter().any(|d| d == component_str)
+            {
+                return Some(dir);
             }
         }
 
-        false
+        None
     }
 
     /// Returns the root directory being walked.
@@ -261,6 +644,101 @@ impl FileWalker {
     }
 }
 
+/// Identity of a file or directory, the same through every link to it.
+#[cfg(unix)]
+type FileKey = (u64, u64);
+
+/// Identity of a file or directory, the same through every link to it.
+#[cfg(not(unix))]
+type FileKey = std::path::PathBuf;
+
+/// Returns the device and inode of the file or directory at `path`.
+#[cfg(unix)]
+fn file_key(path: &Path) -> Option<FileKey> {
+    use std::os::unix::fs::MetadataExt;
+
+    std::fs::metadata(path).ok().map(|metadata| (metadata.dev(), metadata.ino()))
+}
+
+/// Returns the canonical path of the file or directory at `path`.
+#[cfg(not(unix))]
+fn file_key(path: &Path) -> Option<FileKey> {
+    std::fs::canonicalize(path).ok()
+}
+
+/// Directories and files reached so far by a walk following links.
+#[derive(Debug, Default)]
+struct Visited {
+    /// Identities of the directories and files reached.
+    seen: Mutex<FxHashSet<FileKey>>,
+    /// Directories not entered for having been reached before.
+    duplicate_dirs: Mutex<Vec<Utf8PathBuf>>,
+}
+
+impl Visited {
+    /// Returns `true` unless the directory or file at `path` was reached
+    /// before through another path.
+    ///
+    /// A path whose identity can't be read counts as a first visit.
+    fn first_visit(&self, path: &Path) -> bool {
+        file_key(path).is_none_or(|key| self.seen.lock().insert(key))
+    }
+}
+
+/// Returns why a walk error means skipping a path, or `None` if the walk
+/// should fail.
+fn error_skip_reason(error: &ignore::Error) -> Option<SkipReason> {
+    if innermost(error).is_some_and(|e| matches!(e, ignore::Error::Loop { .. })) {
+        return Some(SkipReason::BrokenSymlink);
+    }
+    match error.io_error()?.kind() {
+        io::ErrorKind::PermissionDenied => Some(SkipReason::PermissionDenied),
+        // Following a link whose target is gone
+        io::ErrorKind::NotFound => Some(SkipReason::BrokenSymlink),
+        _ => None,
+    }
+}
+
+/// Returns the error wrapped in path, depth and line number context.
+fn innermost(error: &ignore::Error) -> Option<&ignore::Error> {
+    match error {
+        ignore::Error::WithPath { err, .. }
+        | ignore::Error::WithDepth { err, .. }
+        | ignore::Error::WithLineNumber { err, .. } => innermost(err),
+        ignore::Error::Partial(_) => None,
+        other => Some(other),
+    }
+}
+
+/// Returns the path a walk error is about, if it names one.
+fn error_path(error: &ignore::Error) -> Option<&Path> {
+    match error {
+        ignore::Error::WithPath { path, .. } => Some(path),
+        ignore::Error::WithDepth { err, .. } | ignore::Error::WithLineNumber { err, .. } => {
+            error_path(err)
+        }
+        ignore::Error::Loop { child, .. } => Some(child),
+        _ => None,
+    }
+}
+
+/// Returns why an unfollowed symbolic link is skipped, or `None` if its
+/// target couldn't hold TypeScript files anyway.
+fn symlink_skip_reason(path: &Path) -> Option<SkipReason> {
+    match std::fs::metadata(path) {
+        Err(_) => Some(SkipReason::BrokenSymlink),
+        Ok(metadata) if metadata.is_dir() => Some(SkipReason::Symlink),
+        Ok(_) => Utf8Path::from_path(path)
+            .is_some_and(is_typescript_path)
+            .then_some(SkipReason::Symlink),
+    }
+}
+
+/// Converts a path to UTF-8, replacing invalid sequences.
+fn lossy_path(path: &Path) -> Utf8PathBuf {
+    Utf8PathBuf::from(path.to_string_lossy().into_owned())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -271,6 +749,8 @@ mod tests {
             root: Utf8PathBuf::from("."),
             skip_dirs: Vec::new(),
             follow_links: false,
+            globs: Arc::default(),
+            project: None,
         };
 
         assert!(walker.is_typescript_file(Utf8Path::new("foo.ts")));
@@ -287,6 +767,8 @@ mod tests {
             root: Utf8PathBuf::from("."),
             skip_dirs: vec!["custom_skip".to_owned()],
             follow_links: false,
+            globs: Arc::default(),
+            project: None,
         };
 
         // Standard skip directories
@@ -310,6 +792,8 @@ mod tests {
             root: Utf8PathBuf::from("."),
             skip_dirs: Vec::new(),
             follow_links: false,
+            globs: Arc::default(),
+            project: None,
         }
         .with_skip_dirs(&["vendor", "third_party"]);
 
@@ -323,9 +807,195 @@ mod tests {
             root: Utf8PathBuf::from("."),
             skip_dirs: Vec::new(),
             follow_links: false,
+            globs: Arc::default(),
+            project: None,
         }
         .with_follow_links(true);
 
         assert!(walker.follow_links);
     }
+
+    #[test]
+    fn test_path_globs() {
+        let globs = PathGlobs::new(
+            [Utf8Path::new("/repo"), Utf8Path::new("/repo/admin")],
+            &["src/**"],
+            &["*.spec.ts", "src/legacy/**"],
+        );
+        assert!(globs.is_ok());
+        let globs = globs.unwrap_or_default();
+
+        assert!(globs.allows(Utf8Path::new("/repo/src/app/a.ts")));
+        assert!(!globs.allows(Utf8Path::new("/repo/src/app/a.spec.ts")));
+        assert!(!globs.allows(Utf8Path::new("/repo/src/legacy/b.ts")));
+        assert!(!globs.allows(Utf8Path::new("/repo/tools/c.ts")));
+        assert!(globs.allows(Utf8Path::new("/repo/admin/src/d.ts")), "relative to admin");
+        assert!(PathGlobs::default().allows(Utf8Path::new("/repo/tools/c.ts")));
+
+        let invalid = PathGlobs::new([Utf8Path::new("/repo")], &[], &["src/[a"]);
+        assert!(matches!(invalid, Err(ScanError::Config(_))));
+    }
+
+    #[test]
+    fn test_walk_with_globs() {
+        use std::fs;
+
+        let (_dir, root) = crate::tests::temp_root();
+        for file in ["src/a.ts", "src/a.spec.ts", "src/gen/b.ts", "tools/c.ts"] {
+            let path = root.join(file);
+            assert!(path.parent().is_some_and(|parent| fs::create_dir_all(parent).is_ok()));
+            assert!(fs::write(&path, "export {};").is_ok());
+        }
+
+        let output = FileWalker::new(&root)
+            .and_then(|walker| walker.with_include_globs(&["src/**"]))
+            .and_then(|walker| walker.with_exclude_globs(&["**/*.spec.ts", "src/gen/**"]))
+            .and_then(|walker| walker.walk());
+        assert!(output.is_ok());
+        let Ok(output) = output else {
+            return;
+        };
+        assert_eq!(output.paths, [root.join("src/a.ts")]);
+        assert!(output.skipped.is_empty());
+    }
+
+    #[test]
+    fn test_walk_with_tsconfig() {
+        use std::fs;
+
+        let (_dir, root) = crate::tests::temp_root();
+        for file in ["src/a.ts", "src/old/b.ts", "prototypes/c.ts", "scripts/d.ts"] {
+            let path = root.join(file);
+            assert!(path.parent().is_some_and(|parent| fs::create_dir_all(parent).is_ok()));
+            assert!(fs::write(&path, "export {};").is_ok());
+        }
+        let config = root.join("tsconfig.json");
+        let contents = r#"{"include": ["src"], "exclude": ["src/old"], "files": ["scripts/d.ts"]}"#;
+        assert!(fs::write(&config, contents).is_ok());
+
+        let output = FileWalker::new(&root)
+            .and_then(|walker| walker.with_tsconfig(&config))
+            .and_then(|walker| walker.walk());
+        assert!(output.is_ok());
+        let Ok(output) = output else {
+            return;
+        };
+        let mut paths = output.paths;
+        paths.sort();
+        assert_eq!(paths, [root.join("scripts/d.ts"), root.join("src/a.ts")]);
+        assert!(output.skipped.is_empty());
+
+        let missing = FileWalker::new(&root).and_then(|w| w.with_tsconfig(&root.join("none.json")));
+        assert!(matches!(missing, Err(ScanError::Config(_))));
+    }
+
+    #[test]
+    fn test_walk_respects_chmigrateignore() {
+        use std::fs;
+
+        let (_dir, root) = crate::tests::temp_root();
+        for file in ["src/a.ts", "src/a.gen.ts", "src/generated/b.ts", "src/api/c.ts"] {
+            let path = root.join(file);
+            assert!(path.parent().is_some_and(|parent| fs::create_dir_all(parent).is_ok()));
+            assert!(fs::write(&path, "export {};").is_ok());
+        }
+        assert!(fs::write(root.join(IGNORE_FILE_NAME), "*.gen.ts\ngenerated/\n").is_ok());
+
+        let output = FileWalker::new(&root).and_then(|walker| walker.walk());
+        assert!(output.is_ok());
+        let Ok(output) = output else {
+            return;
+        };
+        let mut paths = output.paths;
+        paths.sort();
+        assert_eq!(paths, [root.join("src/a.ts"), root.join("src/api/c.ts")]);
+
+        // Walking a subdirectory still honours the file at the scan root
+        let output = FileWalker::new(&root.join("src")).and_then(|walker| walker.walk());
+        assert!(output.is_ok_and(|output| output.paths.len() == 2));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_walk_records_skipped_paths() {
+        use std::fs;
+        use std::os::unix::fs::symlink;
+
+        let (_dir, root) = crate::tests::temp_root();
+        for file in ["app/a.ts", "node_modules/pkg/index.d.ts", "node_modules/pkg/b.ts"] {
+            let path = root.join(file);
+            assert!(path.parent().is_some_and(|parent| fs::create_dir_all(parent).is_ok()));
+            assert!(fs::write(&path, "export {};").is_ok());
+        }
+        assert!(symlink(root.join("app"), root.join("linked")).is_ok());
+        assert!(symlink(root.join("missing.ts"), root.join("app/broken.ts")).is_ok());
+
+        let walker = FileWalker::new(&root).map(|walker| walker.walk());
+        assert!(matches!(walker, Ok(Ok(_))));
+        let Ok(Ok(output)) = walker else {
+            return;
+        };
+
+        assert_eq!(output.paths, [root.join("app/a.ts")]);
+        let mut skipped: Vec<_> = output
+            .skipped
+            .iter()
+            .map(|skip| (skip.path.strip_prefix(&root).unwrap_or(&skip.path).as_str(), skip.reason))
+            .collect();
+        skipped.sort_unstable();
+        assert_eq!(
+            skipped,
+            [
+                ("app/broken.ts", SkipReason::BrokenSymlink),
+                ("linked", SkipReason::Symlink),
+                ("node_modules", SkipReason::Excluded),
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_walk_following_links_visits_each_file_once() {
+        use std::fs;
+        use std::os::unix::fs::symlink;
+
+        let (_dir, root) = crate::tests::temp_root();
+        for file in ["app/a.ts", "app/sub/b.ts", "lib/c.ts"] {
+            let path = root.join(file);
+            assert!(path.parent().is_some_and(|parent| fs::create_dir_all(parent).is_ok()));
+            assert!(fs::write(&path, "export {};").is_ok());
+        }
+        // A second way into a directory, links looping back up (left out by
+        // the walk itself), and a second way to a file
+        assert!(symlink(root.join("app"), root.join("linked")).is_ok());
+        assert!(symlink(root.join("app"), root.join("app/sub/loop")).is_ok());
+        assert!(symlink(&root, root.join("lib/up")).is_ok());
+        assert!(symlink(root.join("lib/c.ts"), root.join("z-alias.ts")).is_ok());
+
+        let walker = FileWalker::new(&root).map(|walker| walker.with_follow_links(true).walk());
+        assert!(matches!(walker, Ok(Ok(_))));
+        let Ok(Ok(output)) = walker else {
+            return;
+        };
+
+        let mut paths = output.paths.clone();
+        paths.sort_unstable();
+        let expected = ["app/a.ts", "app/sub/b.ts", "lib/c.ts"].map(|file| root.join(file));
+        assert_eq!(paths, expected);
+        let mut skipped: Vec<_> = output
+            .skipped
+            .iter()
+            .map(|skip| (skip.path.strip_prefix(&root).unwrap_or(&skip.path).as_str(), skip.reason))
+            .collect();
+        skipped.sort_unstable();
+        assert_eq!(
+            skipped,
+            [
+                ("app/sub/loop", SkipReason::BrokenSymlink),
+                ("lib/up", SkipReason::BrokenSymlink),
+                ("linked", SkipReason::Duplicate),
+                ("z-alias.ts", SkipReason::Duplicate),
+            ]
+        );
+    }
 }
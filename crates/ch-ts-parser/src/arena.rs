@@ -164,6 +164,7 @@ impl std::fmt::Display for ArenaStr<'_> {
 ///     names: smallvec![ArenaStr::new(arena.alloc_str("Foo"))],
 ///     source: None,
 ///     location: SourceLocation::default(),
+///     aliases: smallvec![],
 /// };
 ///
 /// // Convert to owned when needed
@@ -187,6 +188,9 @@ pub struct BumpImportInfo<'bump> {
 
     /// The location of the import statement in the source file.
     pub location: SourceLocation,
+
+    /// Names imported under another local name, as `(name, alias)` pairs.
+    pub aliases: SmallVec<[(ArenaStr<'bump>, ArenaStr<'bump>); 1]>,
 }
 
 impl BumpImportInfo<'_> {
@@ -206,16 +210,19 @@ impl BumpImportInfo<'_> {
 
     /// Converts this arena-backed import info into an owned [`ImportInfo`].
     ///
-    /// This allocates new strings for the path and names.
+    /// This allocates new strings for the path, names and aliases.
     #[must_use]
     pub fn into_owned(self) -> ImportInfo {
-        ImportInfo::new(
+        let import = ImportInfo::new(
             self.path.as_str().to_owned(),
             self.kind,
             self.names.iter().map(|s| s.as_str().to_owned()).collect(),
             self.source,
             self.location,
-        )
+        );
+        self.aliases
+            .iter()
+            .fold(import, |import, (name, alias)| import.with_alias(name.as_str(), alias.as_str()))
     }
 }
 
@@ -243,6 +250,9 @@ pub struct BumpImportBuilder<'bump> {
     /// Imported names.
     names: SmallVec<[ArenaStr<'bump>; 4]>,
 
+    /// Imported names with their local aliases.
+    aliases: SmallVec<[(ArenaStr<'bump>, ArenaStr<'bump>); 1]>,
+
     /// The kind of import detected.
     kind: Option<ImportKind>,
 
@@ -261,6 +271,7 @@ impl<'bump> BumpImportBuilder<'bump> {
         Self {
             source_path: None,
             names: SmallVec::new(),
+            aliases: SmallVec::new(),
             kind: None,
             location,
             is_type_only,
@@ -282,6 +293,20 @@ impl<'bump> BumpImportBuilder<'bump> {
         }
     }
 
+    /// Records that the named import `name` is bound locally as `alias`.
+    #[inline]
+    pub fn add_alias(&mut self, name: ArenaStr<'bump>, alias: ArenaStr<'bump>) {
+        self.aliases.push((name, alias));
+    }
+
+    /// Sets this as a require of the whole module, bound to `name` if the
+    /// result is assigned.
+    #[inline]
+    pub fn set_require_import(&mut self, name: Option<ArenaStr<'bump>>) {
+        self.names.extend(name);
+        self.kind = Some(ImportKind::Require);
+    }
+
     /// Sets this as a default import.
     #[inline]
     pub fn set_default_import(&mut self, name: ArenaStr<'bump>) {
@@ -325,6 +350,7 @@ impl<'bump> BumpImportBuilder<'bump> {
             names: self.names,
             source,
             location: self.location,
+            aliases: self.aliases,
         })
     }
 }
@@ -442,6 +468,7 @@ pub fn create_dynamic_bump_import(
         names: SmallVec::new(),
         source,
         location,
+        aliases: SmallVec::new(),
     }
 }
 
@@ -504,6 +531,7 @@ mod tests {
             ],
             source: Some(ModelSource::SharedLegacy),
             location: SourceLocation::new(10, 5, 245),
+            aliases: smallvec![],
         };
 
         let owned: ImportInfo = bump_info.into();
@@ -527,6 +555,7 @@ mod tests {
             names: smallvec![],
             source: Some(ModelSource::SharedLegacy),
             location: SourceLocation::default(),
+            aliases: smallvec![],
         };
         assert!(legacy.is_legacy_import());
         assert!(legacy.is_model_import());
@@ -537,6 +566,7 @@ mod tests {
             names: smallvec![],
             source: Some(ModelSource::Shared2023),
             location: SourceLocation::default(),
+            aliases: smallvec![],
         };
         assert!(!new.is_legacy_import());
         assert!(new.is_model_import());
@@ -547,6 +577,7 @@ mod tests {
             names: smallvec![],
             source: None,
             location: SourceLocation::default(),
+            aliases: smallvec![],
         };
         assert!(!other.is_legacy_import());
         assert!(!other.is_model_import());
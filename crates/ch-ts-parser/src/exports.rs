@@ -9,6 +9,7 @@
 //! - `export interface Foo { }` - Interface exports
 //! - `export { Foo, Bar }` - Named exports
 //! - `export { Foo } from './foo'` - Re-exports
+//! - `export * from './foo'` - Wildcard re-exports, named `*`
 //!
 //! # Examples
 //!
@@ -40,6 +41,7 @@ use crate::arena::{ArenaStr, StringInterner};
 /// - Export interface declarations: `export interface FooModel { }`
 /// - Named export clauses: `export { Foo, Bar }`
 /// - Re-exports: `export { Foo } from './foo'`
+/// - Wildcard re-exports: `export * from './foo'`
 ///
 /// # Capture Names
 ///
@@ -48,7 +50,8 @@ use crate::arena::{ArenaStr, StringInterner};
 /// - `export.named.name` - Named export identifier
 /// - `export.reexport.name` - Re-export identifier
 /// - `export.reexport.source` - Re-export source path
-pub const EXPORT_QUERY: &str = r"
+/// - `export.wildcard.source` - Wildcard re-export source path
+pub const EXPORT_QUERY: &str = r#"
 ; Export class declaration: export class FooCodeGen extends Bar { }
 (export_statement
   declaration: (class_declaration
@@ -63,7 +66,8 @@ pub const EXPORT_QUERY: &str = r"
 (export_statement
   (export_clause
     (export_specifier
-      name: (identifier) @export.named.name)))
+      name: (identifier) @export.named.name))
+  !source)
 
 ; Re-export: export { Foo } from './foo'
 (export_statement
@@ -71,7 +75,12 @@ pub const EXPORT_QUERY: &str = r"
     (export_specifier
       name: (identifier) @export.reexport.name))
   source: (string) @export.reexport.source)
-";
+
+; Wildcard re-export: export * from './foo' (not export * as foo from './foo')
+(export_statement
+  "*"
+  source: (string) @export.wildcard.source)
+"#;
 
 /// Capture index for `export.class.name`.
 pub const CAPTURE_EXPORT_CLASS_NAME: u32 = 0;
@@ -88,6 +97,12 @@ pub const CAPTURE_EXPORT_REEXPORT_NAME: u32 = 3;
 /// Capture index for `export.reexport.source`.
 pub const CAPTURE_EXPORT_REEXPORT_SOURCE: u32 = 4;
 
+/// Capture index for `export.wildcard.source`.
+pub const CAPTURE_EXPORT_WILDCARD_SOURCE: u32 = 5;
+
+/// Name given to wildcard re-exports, which export no single name.
+pub const WILDCARD_EXPORT_NAME: &str = "*";
+
 /// Global cache for the compiled export query (TypeScript).
 static COMPILED_EXPORT_QUERY_TS: OnceLock<Query> = OnceLock::new();
 
@@ -120,7 +135,8 @@ pub struct ExportInfo {
     /// The location of the export in the source file.
     pub location: SourceLocation,
 
-    /// For re-exports, the source path being re-exported from.
+    /// For re-exports and wildcard re-exports, the source path being
+    /// re-exported from.
     pub reexport_source: Option<String>,
 }
 
@@ -171,6 +187,13 @@ impl ExportInfo {
     pub const fn is_reexport(&self) -> bool {
         matches!(self.kind, ExportKind::ReExport)
     }
+
+    /// Returns `true` if this is a wildcard re-export.
+    #[inline]
+    #[must_use]
+    pub const fn is_wildcard(&self) -> bool {
+        matches!(self.kind, ExportKind::Wildcard)
+    }
 }
 
 /// Arena-backed export information for efficient parsing.
@@ -304,13 +327,18 @@ pub fn extract_exports_arena<'bump>(
     let mut cursor = QueryCursor::new();
     let mut exports: SmallVec<[BumpExportInfo<'bump>; 16]> = SmallVec::new();
 
-    // Track re-export sources - set when we encounter the source pattern in a re-export match
-    let mut pending_reexport_source: Option<ArenaStr<'bump>> = None;
-
     cursor.set_max_start_depth(None);
     let mut matches = cursor.matches(query, root, source_bytes);
 
     while let Some(match_) = matches.next() {
+        // The source follows the names in a re-export, so find it first
+        let reexport_source = match_
+            .captures
+            .iter()
+            .find(|capture| capture.index == CAPTURE_EXPORT_REEXPORT_SOURCE)
+            .and_then(|capture| node_text(capture.node, source_bytes))
+            .map(|source_path| interner.intern(source_path));
+
         for capture in match_.captures {
             let node = capture.node;
             let capture_index = capture.index;
@@ -352,9 +380,14 @@ pub fn extract_exports_arena<'bump>(
                         });
                     }
                 }
-                idx if idx == CAPTURE_EXPORT_REEXPORT_SOURCE => {
+                idx if idx == CAPTURE_EXPORT_WILDCARD_SOURCE => {
                     if let Some(source_path) = node_text(node, source_bytes) {
-                        pending_reexport_source = Some(interner.intern(source_path));
+                        exports.push(BumpExportInfo {
+                            name: interner.intern(WILDCARD_EXPORT_NAME),
+                            kind: ExportKind::Wildcard,
+                            location: node_to_location(node),
+                            reexport_source: Some(interner.intern(source_path)),
+                        });
                     }
                 }
                 idx if idx == CAPTURE_EXPORT_REEXPORT_NAME => {
@@ -365,7 +398,7 @@ pub fn extract_exports_arena<'bump>(
                             name: interned,
                             kind: ExportKind::ReExport,
                             location,
-                            reexport_source: pending_reexport_source,
+                            reexport_source,
                         });
                     }
                 }
@@ -545,6 +578,26 @@ mod tests {
         assert!(exports.iter().all(|e| e.reexport_source.as_deref() == Some("'./foo'")));
     }
 
+    #[test]
+    fn test_extract_wildcard_reexport() {
+        let source = r#"
+export * from './active-contract';
+export * as contracts from './contracts';
+export { Foo } from './foo';
+"#;
+        let mut parser = create_parser();
+        let tree = parser.parse(source, None).expect("Parse failed");
+        let query = create_query();
+
+        let exports = extract_exports(&tree, source, &query);
+        assert_eq!(exports.len(), 2);
+        assert!(exports[0].is_wildcard());
+        assert_eq!(exports[0].name, WILDCARD_EXPORT_NAME);
+        assert_eq!(exports[0].reexport_source.as_deref(), Some("'./active-contract'"));
+        assert!(exports[1].is_reexport());
+        assert_eq!(exports[1].reexport_source.as_deref(), Some("'./foo'"));
+    }
+
     #[test]
     fn test_extract_multiple_export_types() {
         let source = r#"
@@ -33,8 +33,9 @@ use crate::arena::{
     create_dynamic_bump_import, BumpImportBuilder, BumpImportInfo, StringInterner,
 };
 use crate::queries::{
-    CAPTURE_IMPORT_DEFAULT_NAME, CAPTURE_IMPORT_DYNAMIC_SOURCE, CAPTURE_IMPORT_NAMED_NAME,
-    CAPTURE_IMPORT_NAMESPACE_NAME, CAPTURE_IMPORT_SOURCE, CAPTURE_IMPORT_STATEMENT,
+    CAPTURE_IMPORT_DEFAULT_NAME, CAPTURE_IMPORT_DYNAMIC_SOURCE, CAPTURE_IMPORT_NAMED_ALIAS,
+    CAPTURE_IMPORT_NAMED_NAME, CAPTURE_IMPORT_NAMESPACE_NAME, CAPTURE_IMPORT_REQUIRE_SOURCE,
+    CAPTURE_IMPORT_SOURCE, CAPTURE_IMPORT_STATEMENT,
 };
 use crate::source::detect_model_source;
 
@@ -145,6 +146,7 @@ pub fn extract_imports_arena<'bump>(
     let mut static_imports: FxHashMap<(usize, usize), BumpImportBuilder<'bump>> =
         FxHashMap::default();
     let mut dynamic_imports: SmallVec<[BumpImportInfo<'bump>; 8]> = smallvec![];
+    let mut require_imports: SmallVec<[BumpImportInfo<'bump>; 2]> = smallvec![];
 
     // Execute the query and iterate over matches using StreamingIterator
     cursor.set_max_start_depth(None);
@@ -195,6 +197,24 @@ pub fn extract_imports_arena<'bump>(
                         }
                     }
                 }
+                idx if idx == CAPTURE_IMPORT_NAMED_ALIAS => {
+                    // Pair the alias with the name it renames
+                    let name = node.parent().and_then(|spec| spec.child_by_field_name("name"));
+                    if let (Some(parent), Some(name)) = (find_import_statement_parent(node), name)
+                    {
+                        let key = (parent.start_byte(), parent.end_byte());
+                        let builder = static_imports.entry(key).or_insert_with(|| {
+                            let location = node_to_location(parent);
+                            let is_type_only = check_type_only(parent, source_bytes);
+                            BumpImportBuilder::new(location, is_type_only)
+                        });
+                        if let (Some(name), Some(alias)) =
+                            (node_text(name, source_bytes), node_text(node, source_bytes))
+                        {
+                            builder.add_alias(interner.intern(name), interner.intern(alias));
+                        }
+                    }
+                }
                 idx if idx == CAPTURE_IMPORT_DEFAULT_NAME => {
                     // Set as default import
                     if let Some(parent) = find_import_statement_parent(node) {
@@ -234,6 +254,10 @@ pub fn extract_imports_arena<'bump>(
                         dynamic_imports.push(create_dynamic_bump_import(path, model_source, location));
                     }
                 }
+                idx if idx == CAPTURE_IMPORT_REQUIRE_SOURCE => {
+                    // CommonJS require - create directly with the names it binds
+                    require_imports.extend(require_import(node, source_bytes, &mut interner));
+                }
                 _ => {}
             }
         }
@@ -246,6 +270,7 @@ pub fn extract_imports_arena<'bump>(
         .collect();
 
     imports.extend(dynamic_imports);
+    imports.extend(require_imports);
 
     // Sort by source location for consistent ordering
     imports.sort_by_key(|i| (i.location.line, i.location.column));
@@ -253,6 +278,79 @@ pub fn extract_imports_arena<'bump>(
     imports
 }
 
+/// Builds the import for the module path `source_node` of a `require()`
+/// call or an `import foo = require()` clause.
+///
+/// A require destructured into names is a named import of those names;
+/// otherwise it requires the whole module, bound to a name if assigned.
+fn require_import<'bump>(
+    source_node: Node<'_>,
+    source: &[u8],
+    interner: &mut StringInterner<'bump>,
+) -> Option<BumpImportInfo<'bump>> {
+    let parent = source_node.parent()?;
+    let (site, binding) = if parent.kind() == "import_require_clause" {
+        let name = parent.named_child(0).filter(|name| name.kind() == "identifier");
+        (parent.parent().unwrap_or(parent), name)
+    } else {
+        // The string is an argument of the call, which may initialize a variable
+        let call = parent.parent()?;
+        let declarator = call.parent().filter(|declarator| {
+            declarator.kind() == "variable_declarator"
+                && declarator.child_by_field_name("value") == Some(call)
+        });
+        (call, declarator.and_then(|declarator| declarator.child_by_field_name("name")))
+    };
+
+    let mut builder = BumpImportBuilder::new(node_to_location(site), false);
+    builder.set_source(interner.intern(node_text(source_node, source)?));
+    match binding {
+        Some(pattern) if pattern.kind() == "object_pattern" => {
+            add_destructured_names(&mut builder, pattern, source, interner);
+        }
+        Some(name) if name.kind() == "identifier" => {
+            let name = node_text(name, source).map(|name| interner.intern(name));
+            builder.set_require_import(name);
+        }
+        _ => builder.set_require_import(None),
+    }
+    builder.build(detect_model_source)
+}
+
+/// Adds the properties an object pattern such as `{ Foo, Bar: B }`
+/// destructures as named imports, with the names they're bound to as
+/// aliases.
+fn add_destructured_names<'bump>(
+    builder: &mut BumpImportBuilder<'bump>,
+    pattern: Node<'_>,
+    source: &[u8],
+    interner: &mut StringInterner<'bump>,
+) {
+    let mut cursor = pattern.walk();
+    for property in pattern.named_children(&mut cursor) {
+        let (key, value) = match property.kind() {
+            "shorthand_property_identifier_pattern" => (Some(property), None),
+            "object_assignment_pattern" => (property.child_by_field_name("left"), None),
+            "pair_pattern" => {
+                (property.child_by_field_name("key"), property.child_by_field_name("value"))
+            }
+            _ => continue,
+        };
+        let Some(name) = key.and_then(|key| node_text(key, source)) else {
+            continue;
+        };
+        let name = interner.intern(name);
+        builder.add_named_import(name);
+        let alias = value
+            .filter(|value| value.kind() == "identifier")
+            .and_then(|value| node_text(value, source))
+            .filter(|alias| *alias != name.as_str());
+        if let Some(alias) = alias {
+            builder.add_alias(name, interner.intern(alias));
+        }
+    }
+}
+
 /// Finds the parent `import_statement` node for a given node.
 fn find_import_statement_parent(node: Node<'_>) -> Option<Node<'_>> {
     let mut current = Some(node);
@@ -351,6 +449,55 @@ mod tests {
         assert!(import.is_legacy_import());
     }
 
+    #[test]
+    fn test_extract_aliased_named_imports() {
+        let source = r#"import { ActiveContract as AC, Job } from '../shared/models/active-contract';"#;
+        let mut parser = create_parser();
+        let tree = parser.parse(source, None).expect("Parse failed");
+        let query = create_query();
+
+        let imports = extract_imports(&tree, source, &query);
+        assert_eq!(imports.len(), 1);
+
+        let import = &imports[0];
+        assert_eq!(import.names.as_slice(), ["ActiveContract", "Job"]);
+        assert_eq!(import.aliases.as_slice(), [("ActiveContract".to_owned(), "AC".to_owned())]);
+        assert_eq!(import.local_name("ActiveContract"), "AC");
+        assert_eq!(import.local_name("Job"), "Job");
+    }
+
+    #[test]
+    fn test_extract_require_imports() {
+        let source = r#"
+import legacy = require('../shared/models/legacy');
+const models = require('../shared/models/index');
+const { Foo, Bar: B, Baz = null } = require('../shared/models/foo');
+require('../shared/models/polyfills');
+const notRequire = load('../shared/models/other');
+"#;
+        let mut parser = create_parser();
+        let tree = parser.parse(source, None).expect("Parse failed");
+        let query = create_query();
+
+        let imports = extract_imports(&tree, source, &query);
+        let summary: Vec<_> = imports
+            .iter()
+            .map(|i| (i.location.line, i.kind, i.names.iter().map(String::as_str).collect()))
+            .collect::<Vec<(u32, ImportKind, Vec<&str>)>>();
+        assert_eq!(
+            summary,
+            [
+                (2, ImportKind::Require, vec!["legacy"]),
+                (3, ImportKind::Require, vec!["models"]),
+                (4, ImportKind::Named, vec!["Foo", "Bar", "Baz"]),
+                (5, ImportKind::Require, vec![]),
+            ]
+        );
+        assert!(imports.iter().all(ImportInfo::is_legacy_import));
+        assert_eq!(imports[2].local_name("Bar"), "B");
+        assert_eq!(imports[2].aliases.len(), 1);
+    }
+
     #[test]
     fn test_extract_default_import() {
         let source = r#"import Foo from '../shared_2023/models/foo';"#;
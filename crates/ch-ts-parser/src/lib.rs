@@ -4,6 +4,7 @@
 //!
 //! - Extract import statements (static and dynamic)
 //! - Detect model/interface references from shared directories
+//! - Count references to imported names outside import statements
 //! - Support incremental re-parsing on file changes
 //! - Efficiently categorize imports as legacy (`shared/`) or new (`shared_2023/`)
 //!
@@ -168,6 +169,7 @@ pub mod exports;
 mod import;
 mod parser;
 pub mod queries;
+mod references;
 pub mod source;
 
 // Re-export main types for convenient access
@@ -181,6 +183,9 @@ pub use arena::{ArenaStr, BumpImportBuilder, BumpImportInfo, StringInterner};
 // Re-export import extraction functions
 pub use import::{extract_imports, extract_imports_arena};
 
+// Re-export body-reference counting
+pub use references::{count_references, namespace_members};
+
 // Re-export export extraction functions and types
 pub use exports::{
     extract_exports, extract_exports_arena, get_tsx_export_query, get_typescript_export_query,
@@ -188,7 +193,7 @@ pub use exports::{
 };
 
 // Re-export tree-sitter types that appear in our public API
-pub use tree_sitter::InputEdit;
+pub use tree_sitter::{InputEdit, Tree};
 
 // Re-export bumpalo for convenience (consumers need it for ArenaParser)
 pub use bumpalo::Bump;
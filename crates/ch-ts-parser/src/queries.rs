@@ -14,10 +14,12 @@ use crate::error::ParseError;
 ///
 /// This query captures:
 /// - Static import statements with their source paths
-/// - Named imports (individual identifiers)
+/// - Named imports (individual identifiers), and the aliases of those
+///   renamed with `as`
 /// - Default imports
 /// - Namespace imports (`import * as`)
 /// - Dynamic imports (`import()` expressions)
+/// - Requires (`require()` calls and `import foo = require()`)
 ///
 /// # Capture Names
 ///
@@ -27,7 +29,10 @@ use crate::error::ParseError;
 /// - `import.default.name` - Default import identifier
 /// - `import.namespace.name` - Namespace import identifier
 /// - `import.dynamic.source` - Dynamic import path string
-pub const IMPORT_QUERY: &str = r"
+/// - `import.named.alias` - Local name of a named import renamed with `as`
+/// - `import.require.function` - The `require` identifier of a require call
+/// - `import.require.source` - Required module path string
+pub const IMPORT_QUERY: &str = r#"
 ; Static imports with source path
 (import_statement
   source: (string) @import.source) @import.statement
@@ -55,7 +60,26 @@ pub const IMPORT_QUERY: &str = r"
   function: (import)
   arguments: (arguments
     (string) @import.dynamic.source))
-";
+
+; Aliased named imports: import { Foo as F } from '...'
+(import_statement
+  (import_clause
+    (named_imports
+      (import_specifier
+        alias: (identifier) @import.named.alias))))
+
+; CommonJS requires: const foo = require('./path'), or a bare require('./path')
+(call_expression
+  function: (identifier) @import.require.function
+  arguments: (arguments
+    .
+    (string) @import.require.source)
+  (#eq? @import.require.function "require"))
+
+; TypeScript import-require: import foo = require('./path')
+(import_require_clause
+  source: (string) @import.require.source)
+"#;
 
 /// Capture index for `import.source`.
 pub const CAPTURE_IMPORT_SOURCE: u32 = 0;
@@ -75,6 +99,15 @@ pub const CAPTURE_IMPORT_NAMESPACE_NAME: u32 = 4;
 /// Capture index for `import.dynamic.source`.
 pub const CAPTURE_IMPORT_DYNAMIC_SOURCE: u32 = 5;
 
+/// Capture index for `import.named.alias`.
+pub const CAPTURE_IMPORT_NAMED_ALIAS: u32 = 6;
+
+/// Capture index for `import.require.function`.
+pub const CAPTURE_IMPORT_REQUIRE_FUNCTION: u32 = 7;
+
+/// Capture index for `import.require.source`.
+pub const CAPTURE_IMPORT_REQUIRE_SOURCE: u32 = 8;
+
 /// Global cache for the compiled import query (TypeScript).
 static COMPILED_QUERY_TS: OnceLock<Query> = OnceLock::new();
 
@@ -181,6 +214,11 @@ mod tests {
         assert!(names.contains(&"import.default.name"));
         assert!(names.contains(&"import.namespace.name"));
         assert!(names.contains(&"import.dynamic.source"));
+        assert!(names.contains(&"import.named.alias"));
+        assert_eq!(
+            capture_name(&query, CAPTURE_IMPORT_REQUIRE_SOURCE),
+            Some("import.require.source")
+        );
     }
 
     #[test]
@@ -188,7 +226,7 @@ mod tests {
         let language: Language = tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into();
         let query = compile_query(&language).expect("Query should compile");
 
-        // We have 5 patterns in our query
-        assert_eq!(query.pattern_count(), 5);
+        // We have 8 patterns in our query
+        assert_eq!(query.pattern_count(), 8);
     }
 }
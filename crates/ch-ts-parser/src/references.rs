@@ -0,0 +1,304 @@
+//! Body-reference counting for imported names.
+//!
+//! Import extraction only sees import statements. This module walks the rest
+//! of the syntax tree and counts how often each imported name is referenced,
+//! so imports that are never used (dead imports) can be told apart from
+//! imports the file actually depends on.
+//!
+//! Value identifiers, type identifiers and shorthand properties (`{ Foo }`)
+//! count as references. Property names (`obj.Foo`) and anything inside an
+//! import statement do not.
+//!
+//! A namespace import (`import * as Models`) references the whole module, so
+//! [`namespace_members`] finds the members used through it instead:
+//! `Models.Foo` in values and types, and `const { Foo } = Models`.
+//!
+//! # Examples
+//!
+//! ```
+//! use ch_ts_parser::{count_references, TsParser};
+//!
+//! let source = r#"
+//!     import { Foo, Bar } from '../shared/models';
+//!     const foo: Foo = new Foo();
+//! "#;
+//! let mut parser = TsParser::new()?;
+//! let result = parser.parse(source)?;
+//!
+//! let counts = count_references(&result.tree, source, &["Foo", "Bar"]);
+//! assert_eq!(counts.as_slice(), &[2, 0]);
+//! # Ok::<(), ch_ts_parser::ParseError>(())
+//! ```
+//!
+//! ```
+//! use ch_ts_parser::{namespace_members, TsParser};
+//!
+//! let source = r#"
+//!     import * as Models from '../shared/models';
+//!     const job: Models.Job = new Models.Job(Models.JobStatus.Open);
+//! "#;
+//! let mut parser = TsParser::new()?;
+//! let result = parser.parse(source)?;
+//!
+//! let members = namespace_members(&result.tree, source, &["Models"]);
+//! assert_eq!(members[0].as_slice(), ["Job", "JobStatus"]);
+//! # Ok::<(), ch_ts_parser::ParseError>(())
+//! ```
+
+use smallvec::SmallVec;
+use tree_sitter::{Node, Tree};
+
+/// Node kinds that reference a name in scope.
+const REFERENCE_KINDS: &[&str] = &[
+    "identifier",
+    "type_identifier",
+    "shorthand_property_identifier",
+    "shorthand_property_identifier_pattern",
+];
+
+/// Counts references to each of `names` outside import statements.
+///
+/// # Arguments
+///
+/// * `tree` - Syntax tree of `source`
+/// * `source` - The source code the tree was parsed from
+/// * `names` - Names to count (typically the names a file imports)
+///
+/// # Returns
+///
+/// One count per entry in `names`, in the same order.
+#[must_use]
+pub fn count_references(tree: &Tree, source: &str, names: &[&str]) -> SmallVec<[usize; 8]> {
+    let mut counts: SmallVec<[usize; 8]> = SmallVec::from_elem(0, names.len());
+    if names.is_empty() {
+        return counts;
+    }
+
+    let mut cursor = tree.walk();
+    let mut visit_children = true;
+    loop {
+        let node = cursor.node();
+        if visit_children {
+            if node.kind() == "import_statement" {
+                visit_children = false;
+                continue;
+            }
+            if REFERENCE_KINDS.contains(&node.kind()) {
+                record(node, source, names, &mut counts);
+            }
+            if cursor.goto_first_child() {
+                continue;
+            }
+        }
+
+        if cursor.goto_next_sibling() {
+            visit_children = true;
+        } else if cursor.goto_parent() {
+            visit_children = false;
+        } else {
+            break;
+        }
+    }
+
+    counts
+}
+
+/// Finds the members used through each of `namespaces` outside import
+/// statements.
+///
+/// # Arguments
+///
+/// * `tree` - Syntax tree of `source`
+/// * `source` - The source code the tree was parsed from
+/// * `namespaces` - Local names bound to whole modules (typically by
+///   `import * as`)
+///
+/// # Returns
+///
+/// The members of each entry in `namespaces`, in the same order, each sorted
+/// and without duplicates.
+#[must_use]
+pub fn namespace_members(
+    tree: &Tree,
+    source: &str,
+    namespaces: &[&str],
+) -> Vec<SmallVec<[String; 4]>> {
+    let mut members: Vec<SmallVec<[String; 4]>> = vec![SmallVec::new(); namespaces.len()];
+    if namespaces.is_empty() {
+        return members;
+    }
+
+    let mut cursor = tree.walk();
+    let mut visit_children = true;
+    loop {
+        let node = cursor.node();
+        if visit_children {
+            if node.kind() == "import_statement" {
+                visit_children = false;
+                continue;
+            }
+            record_members(node, source, namespaces, &mut members);
+            if cursor.goto_first_child() {
+                continue;
+            }
+        }
+
+        if cursor.goto_next_sibling() {
+            visit_children = true;
+        } else if cursor.goto_parent() {
+            visit_children = false;
+        } else {
+            break;
+        }
+    }
+
+    for members in &mut members {
+        members.sort_unstable();
+        members.dedup();
+    }
+    members
+}
+
+/// Records the members `node` uses, if it accesses one of `namespaces`.
+fn record_members(
+    node: Node<'_>,
+    source: &str,
+    namespaces: &[&str],
+    members: &mut [SmallVec<[String; 4]>],
+) {
+    let (object, properties) = match node.kind() {
+        // Models.Foo and the Models.Foo in Models.Foo.Bar, as values and types
+        "member_expression" | "nested_identifier" => {
+            (node.child_by_field_name("object"), node.child_by_field_name("property"))
+        }
+        "nested_type_identifier" => {
+            (node.child_by_field_name("module"), node.child_by_field_name("name"))
+        }
+        // const { Foo, Bar: B } = Models
+        "variable_declarator" => (
+            node.child_by_field_name("value"),
+            node.child_by_field_name("name").filter(|name| name.kind() == "object_pattern"),
+        ),
+        _ => return,
+    };
+    let (Some(object), Some(properties)) = (object, properties) else {
+        return;
+    };
+    if object.kind() != "identifier" {
+        return;
+    }
+    let Some(index) = source
+        .get(object.byte_range())
+        .and_then(|text| namespaces.iter().position(|namespace| *namespace == text))
+    else {
+        return;
+    };
+
+    let mut add = |property: Node<'_>| {
+        if let Some(text) = source.get(property.byte_range()) {
+            members[index].push(text.to_owned());
+        }
+    };
+    if properties.kind() != "object_pattern" {
+        add(properties);
+        return;
+    }
+    let mut cursor = properties.walk();
+    for property in properties.named_children(&mut cursor) {
+        let key = match property.kind() {
+            "shorthand_property_identifier_pattern" => Some(property),
+            "object_assignment_pattern" => property.child_by_field_name("left"),
+            "pair_pattern" => property.child_by_field_name("key"),
+            _ => None,
+        };
+        if let Some(key) = key {
+            add(key);
+        }
+    }
+}
+
+/// Increments the count of the name `node` spells, if it is one of `names`.
+fn record(node: Node<'_>, source: &str, names: &[&str], counts: &mut [usize]) {
+    let Some(text) = source.get(node.byte_range()) else {
+        return;
+    };
+    if let Some(index) = names.iter().position(|name| *name == text) {
+        counts[index] += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TsParser;
+
+    fn counts(source: &str, names: &[&str]) -> Vec<usize> {
+        TsParser::new()
+            .and_then(|mut parser| parser.parse(source))
+            .map(|result| count_references(&result.tree, source, names).to_vec())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_count_references_skips_imports() {
+        let source = "import { Foo, Bar } from '../shared/models/foo';\nexport const x = 1;\n";
+        assert_eq!(counts(source, &["Foo", "Bar"]), [0, 0]);
+    }
+
+    #[test]
+    fn test_count_references_values_and_types() {
+        let source = r"
+            import { Foo, FooModel } from '../shared/models/foo';
+            function load(model: FooModel): Foo {
+                return new Foo(model);
+            }
+        ";
+        assert_eq!(counts(source, &["Foo", "FooModel"]), [2, 1]);
+    }
+
+    #[test]
+    fn test_count_references_ignores_property_names() {
+        let source = r"
+            import { Foo } from '../shared/models/foo';
+            const value = config.Foo;
+            const wrapped = { Foo };
+        ";
+        assert_eq!(counts(source, &["Foo"]), [1]);
+    }
+
+    #[test]
+    fn test_count_references_namespace_import() {
+        let source = r"
+            import * as Models from '../shared/models';
+            const job = new Models.Job();
+        ";
+        assert_eq!(counts(source, &["Models"]), [1]);
+    }
+
+    #[test]
+    fn test_namespace_members_values_types_and_destructuring() {
+        let source = r"
+            import * as Models from '../shared/models';
+            import * as Unused from '../shared/models/unused';
+            const contract: Models.ActiveContract<string> = new Models.ActiveContract();
+            const status: Models.Status.Kind = Models.Status.Kind.Open;
+            type Form = typeof Models.ContractForm;
+            const { Job, Task: T, Note = null } = Models;
+            const other = config.Foo;
+        ";
+        let members = TsParser::new()
+            .and_then(|mut parser| parser.parse(source))
+            .map(|result| namespace_members(&result.tree, source, &["Models", "Unused", "config"]))
+            .unwrap_or_default();
+        let members: Vec<Vec<&str>> =
+            members.iter().map(|m| m.iter().map(String::as_str).collect()).collect();
+        assert_eq!(
+            members,
+            [
+                vec!["ActiveContract", "ContractForm", "Job", "Note", "Status", "Task"],
+                vec![],
+                vec!["Foo"],
+            ]
+        );
+    }
+}
@@ -17,12 +17,22 @@
 //!
 //! Other imports from shared directories (e.g., `shared/utils/`, `shared/services/`)
 //! are **not** considered model imports and will return `None`.
+//!
+//! # Path Aliases
+//!
+//! Imports such as `@shared/models/foo` go through `tsconfig.json` `paths`
+//! aliases rather than naming the directory. A [`ModelPathMatcher`] given
+//! those aliases with [`ModelPathMatcher::with_path_alias`] resolves such an
+//! import the way `tsc` does before matching it: the alias with the longest
+//! prefix before its `*` wins, and the import is a model import if one of
+//! its targets is. Relative imports are never aliased.
 
 use ch_core::{ModelSource, ScanConfig};
 
 /// Precomputed path matcher for shared model imports.
 #[derive(Debug, Clone)]
 pub struct ModelPathMatcher {
+    legacy_dir: String,
     modern_dir: String,
     legacy_models: String,
     legacy_models_slash: String,
@@ -32,6 +42,31 @@ pub struct ModelPathMatcher {
     modern_models_slash: String,
     modern_interfaces: String,
     modern_interfaces_slash: String,
+    /// `paths` aliases, most specific first.
+    aliases: Vec<PathAlias>,
+}
+
+/// A `tsconfig.json` `paths` entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PathAlias {
+    /// Part of the pattern before its `*`, or the whole pattern.
+    prefix: String,
+    /// Part of the pattern after its `*`, or `None` for a pattern without.
+    suffix: Option<String>,
+    /// Paths the alias stands for, with `*` standing for what it matched.
+    targets: Vec<String>,
+}
+
+impl PathAlias {
+    /// Returns what the pattern's `*` matched in `path`, or `""` for a
+    /// pattern without one, if `path` matches.
+    fn capture<'p>(&self, path: &'p str) -> Option<&'p str> {
+        let rest = path.strip_prefix(&self.prefix)?;
+        match &self.suffix {
+            None => rest.is_empty().then_some(""),
+            Some(suffix) => rest.strip_suffix(suffix.as_str()),
+        }
+    }
 }
 
 impl ModelPathMatcher {
@@ -49,6 +84,7 @@ impl ModelPathMatcher {
         let modern_interfaces_slash = format!("/{modern_interfaces}");
 
         Self {
+            legacy_dir: shared_dir.to_owned(),
             modern_dir: shared_2023_dir.to_owned(),
             legacy_models,
             legacy_models_slash,
@@ -58,9 +94,113 @@ impl ModelPathMatcher {
             modern_models_slash,
             modern_interfaces,
             modern_interfaces_slash,
+            aliases: Vec::new(),
         }
     }
 
+    /// Resolves imports matching a `tsconfig.json` `paths` alias before
+    /// matching them.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The alias, such as `@shared/*`; at most one `*`
+    /// * `targets` - Paths it stands for, such as `src/shared/*`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ch_core::ModelSource;
+    /// use ch_ts_parser::{detect_model_source_with, ModelPathMatcher};
+    ///
+    /// let matcher = ModelPathMatcher::default().with_path_alias("@shared/*", &["src/shared/*"]);
+    /// assert_eq!(
+    ///     detect_model_source_with("'@shared/models/foo'", &matcher),
+    ///     Some(ModelSource::SharedLegacy)
+    /// );
+    /// ```
+    #[must_use]
+    pub fn with_path_alias<S: AsRef<str>>(mut self, pattern: &str, targets: &[S]) -> Self {
+        let (prefix, suffix) = match pattern.split_once('*') {
+            Some((prefix, suffix)) => (prefix, Some(suffix.to_owned())),
+            None => (pattern, None),
+        };
+        self.aliases.push(PathAlias {
+            prefix: prefix.to_owned(),
+            suffix,
+            targets: targets.iter().map(|target| target.as_ref().to_owned()).collect(),
+        });
+        // Exact patterns first, then longest prefix, as `tsc` picks them
+        self.aliases.sort_by(|a, b| {
+            a.suffix
+                .is_some()
+                .cmp(&b.suffix.is_some())
+                .then_with(|| b.prefix.len().cmp(&a.prefix.len()))
+                .then_with(|| a.prefix.cmp(&b.prefix))
+        });
+        self
+    }
+
+    /// Returns the shared directory and the directory under it whose barrel
+    /// file an import names, such as `SharedLegacy` and `""` for
+    /// `'../shared'` or `'../shared/index'`, and `"domain"` for
+    /// `'../shared/domain'`.
+    ///
+    /// Aliases are resolved first. Whether the directory has a barrel file,
+    /// and what it re-exports, is up to the
+    /// [`ModelRegistry`](ch_core::ModelRegistry).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ch_core::ModelSource;
+    /// use ch_ts_parser::ModelPathMatcher;
+    ///
+    /// let matcher = ModelPathMatcher::default();
+    /// let barrel = matcher.barrel_of("'../../shared_2023/index.ts'");
+    /// assert_eq!(barrel, Some((ModelSource::Shared2023, String::new())));
+    /// assert_eq!(matcher.barrel_of("'./local'"), None);
+    /// ```
+    #[must_use]
+    pub fn barrel_of(&self, import_path: &str) -> Option<(ModelSource, String)> {
+        let path = strip_quotes(import_path);
+        match self.resolve_alias(path) {
+            Some(targets) => targets.iter().find_map(|target| self.barrel_dir(target)),
+            None => self.barrel_dir(path),
+        }
+    }
+
+    /// Splits an unaliased path at its last shared directory, as for
+    /// [`barrel_of`](Self::barrel_of).
+    fn barrel_dir(&self, path: &str) -> Option<(ModelSource, String)> {
+        let path = path.trim_end_matches('/');
+        let path = [".tsx", ".ts"].iter().find_map(|ext| path.strip_suffix(ext)).unwrap_or(path);
+        let path = path.strip_suffix("/index").unwrap_or(path);
+        let segments: Vec<&str> = path.split('/').collect();
+        segments.iter().enumerate().rev().find_map(|(at, segment)| {
+            let source = if *segment == self.modern_dir {
+                ModelSource::Shared2023
+            } else if *segment == self.legacy_dir {
+                ModelSource::SharedLegacy
+            } else {
+                return None;
+            };
+            Some((source, segments[at + 1..].join("/")))
+        })
+    }
+
+    /// Returns the targets of the alias `path` matches, with `*` filled in.
+    ///
+    /// Relative paths and paths matching no alias return `None`.
+    fn resolve_alias(&self, path: &str) -> Option<Vec<String>> {
+        if path.starts_with('.') {
+            return None;
+        }
+        self.aliases.iter().find_map(|alias| {
+            let captured = alias.capture(path)?;
+            Some(alias.targets.iter().map(|target| target.replacen('*', captured, 1)).collect())
+        })
+    }
+
     /// Creates a matcher from scan configuration.
     #[must_use]
     pub fn from_scan_config(config: &ScanConfig) -> Self {
@@ -153,7 +293,15 @@ pub fn detect_model_source_with(
     matcher: &ModelPathMatcher,
 ) -> Option<ModelSource> {
     let path = strip_quotes(import_path);
+    if let Some(targets) = matcher.resolve_alias(path) {
+        return targets.iter().find_map(|target| match_model_path(target, matcher));
+    }
+    match_model_path(path, matcher)
+}
 
+/// Matches an unquoted, unaliased path against the matcher's directories.
+#[inline]
+fn match_model_path(path: &str, matcher: &ModelPathMatcher) -> Option<ModelSource> {
     if is_shared_2023_model_import_with(path, matcher) {
         return Some(ModelSource::Shared2023);
     }
@@ -460,6 +608,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_path_aliases_resolve_before_matching() {
+        let matcher = ModelPathMatcher::default()
+            .with_path_alias("@app/*", &["src/app/*"])
+            .with_path_alias("@app/shared/*", &["src/app/shared/*"])
+            .with_path_alias("@models", &["/repo/shared_2023/models/index.ts"])
+            .with_path_alias("@lib/*", &["vendor/*", "src/shared/*"]);
+        let detect = |path| detect_model_source_with(path, &matcher);
+
+        assert_eq!(detect("'@app/shared/models/foo'"), Some(ModelSource::SharedLegacy));
+        assert_eq!(detect("'@app/shared/utils/foo'"), None);
+        assert_eq!(detect("'@models'"), Some(ModelSource::Shared2023));
+        assert_eq!(detect("'@models/foo'"), None);
+        assert_eq!(detect("'@lib/interfaces'"), Some(ModelSource::SharedLegacy));
+        // Relative paths match as before, and unknown aliases don't match
+        assert_eq!(detect("'../shared/models/foo'"), Some(ModelSource::SharedLegacy));
+        assert_eq!(detect("'@shared/models/foo'"), None);
+
+        // The longest prefix wins, even when a shorter one would match
+        let aliased = ModelPathMatcher::default()
+            .with_path_alias("shared/*", &["shared/*"])
+            .with_path_alias("shared/models/*", &["elsewhere/*"]);
+        assert_eq!(detect_model_source_with("'shared/models/foo'", &aliased), None);
+    }
+
+    #[test]
+    fn test_barrel_of_splits_at_the_last_shared_directory() {
+        let matcher = ModelPathMatcher::default().with_path_alias("@app/*", &["/repo/src/*"]);
+        let legacy = |dir: &str| Some((ModelSource::SharedLegacy, dir.to_owned()));
+
+        assert_eq!(matcher.barrel_of("'../shared'"), legacy(""));
+        assert_eq!(matcher.barrel_of("'shared/index'"), legacy(""));
+        assert_eq!(matcher.barrel_of("'../shared/domain/'"), legacy("domain"));
+        assert_eq!(matcher.barrel_of("'@app/shared/domain/index.ts'"), legacy("domain"));
+        assert_eq!(
+            matcher.barrel_of("'../shared/vendor/shared_2023'"),
+            Some((ModelSource::Shared2023, String::new()))
+        );
+        assert_eq!(matcher.barrel_of("'../shared-ui'"), None);
+        assert_eq!(matcher.barrel_of("'@angular/core'"), None);
+    }
+
     #[test]
     fn test_strip_quotes() {
         assert_eq!(strip_quotes("'foo'"), "foo");
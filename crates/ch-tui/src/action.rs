@@ -10,8 +10,16 @@
 //! ```text
 //! Key/Mouse Event → Component → Action → App State Update
 //! ```
+//!
+//! In normal mode, keys are mapped to actions through the
+//! [command registry](crate::commands), which users can rebind.
+
+use ch_core::{FxHashMap, MigrationStatus};
+use ch_scanner::StatsSnapshot;
 
-use ch_core::MigrationStatus;
+use crate::app::StatusMessage;
+use crate::buffers::BufferState;
+use crate::view::ViewSpec;
 
 /// User-initiated actions in the TUI.
 ///
@@ -78,6 +86,33 @@ pub enum Action {
     /// Set a specific status filter.
     SetStatusFilter(Option<MigrationStatus>),
 
+    /// Toggle showing only files with dead (unused) legacy imports.
+    ToggleDeadImportsFilter,
+
+    /// Cycle through sort orders (Path → Priority → Status → Imports → Path).
+    CycleSort,
+
+    // =========================================================================
+    // Views
+    // =========================================================================
+    /// Apply a view (filter + sort) loaded from a view string.
+    LoadView(ViewSpec),
+
+    /// Copy the current view string to the clipboard.
+    CopyView,
+
+    // =========================================================================
+    // Command Line
+    // =========================================================================
+    /// Enter command mode (`:` prompt).
+    EnterCommandMode,
+
+    /// Exit command mode without executing.
+    ExitCommandMode,
+
+    /// Execute a command entered at the `:` prompt.
+    ExecuteCommand(String),
+
     // =========================================================================
     // File Operations
     // =========================================================================
@@ -87,6 +122,42 @@ pub enum Action {
     /// Rescan a specific file.
     RescanFile(camino::Utf8PathBuf),
 
+    /// Update the model registry from a changed shared file and rescan the
+    /// files importing its models.
+    UpdateRegistry(camino::Utf8PathBuf),
+
+    /// Apply the outcome of a model registry update run in the background:
+    /// the number of importing files rechecked, `None` if `path` defines no
+    /// models, or the message describing why the update failed.
+    RegistryUpdated {
+        /// The changed shared file.
+        path: camino::Utf8PathBuf,
+        /// What the update did.
+        outcome: Result<Option<usize>, StatusMessage>,
+    },
+
+    /// Apply a background check of which files on screen are open in an
+    /// editor.
+    EditorBuffersChecked(FxHashMap<camino::Utf8PathBuf, BufferState>),
+
+    /// Rescan only the directory of the selected file, dropping deleted files.
+    RescanDirectory,
+
+    /// Apply the outcome of a directory rescan run in the background: the
+    /// statistics after it, or the message describing why it failed.
+    DirectoryRescanned {
+        /// The rescanned directory.
+        dir: camino::Utf8PathBuf,
+        /// What the rescan did.
+        outcome: Result<StatsSnapshot, StatusMessage>,
+    },
+
+    /// Cancel the running background scan, keeping the files scanned so far.
+    CancelScan,
+
+    /// Pause the running background scan, or resume it if paused.
+    TogglePauseScan,
+
     /// Start a background streaming scan.
     ///
     /// This initiates a new scan that streams results incrementally.
@@ -110,12 +181,27 @@ pub enum Action {
     /// Hide the help panel.
     HideHelp,
 
+    /// Toggle the debug overlay.
+    ToggleDebug,
+
+    /// Close the topmost overlay.
+    CloseOverlay,
+
     /// Show a status message.
     ShowStatus(String),
 
     /// Clear the status message.
     ClearStatus,
 
+    /// Expand the status message into a scrollable popup.
+    ShowMessage,
+
+    /// Close the expanded status message popup.
+    HideMessage,
+
+    /// Scroll the expanded status message by a number of lines.
+    ScrollMessage(isize),
+
     // =========================================================================
     // Directory Setup
     // =========================================================================
@@ -137,6 +223,9 @@ pub enum Action {
     /// Render the UI.
     Render,
 
+    /// Clear the screen and redraw everything, e.g. after external output.
+    Refresh,
+
     /// Tick (periodic update).
     Tick,
 
@@ -178,6 +267,8 @@ impl Action {
                 | Self::ClearFilter
                 | Self::CycleStatusFilter
                 | Self::SetStatusFilter(_)
+                | Self::ToggleDeadImportsFilter
+                | Self::LoadView(_)
         )
     }
 
@@ -186,7 +277,7 @@ impl Action {
     pub const fn modifies_filter(&self) -> bool {
         matches!(
             self,
-            Self::SetFilter(_) | Self::ClearFilter | Self::SetStatusFilter(_)
+            Self::SetFilter(_) | Self::ClearFilter | Self::SetStatusFilter(_) | Self::LoadView(_)
         )
     }
 }
@@ -11,29 +11,42 @@
 //!  ├── scanner: Scanner          # File analysis results
 //!  ├── watcher: FileWatcher      # Live file change detection
 //!  ├── scan_state: ScanState     # Background scan progress
-//!  ├── mode: AppMode             # Current UI mode
+//!  ├── overlays: OverlayStack    # Open modal overlays
 //!  ├── focus: Focus              # Active panel
 //!  ├── file_list_state: FileListState
 //!  ├── detail_state: DetailPaneState
 //!  ├── filter: FilterState       # Current filter configuration
+//!  ├── sort: SortOrder           # Current file list ordering
 //!  └── status: Option<StatusMessage>
 //! ```
 
 use std::time::Instant;
 
-use camino::Utf8PathBuf;
-use ch_core::{Config, FileInfo, MigrationStatus};
-use ch_scanner::{ScanConfig as ScannerConfig, ScanResult, ScanUpdate, Scanner, StatsSnapshot};
+use camino::{Utf8Path, Utf8PathBuf};
+use ch_core::{Config, FileInfo, FxHashMap, MigrationStatus};
+use ch_scanner::{
+    rollup_by_owner, OwnerStats, ResourceLimits, RetryPolicy, ScanConfig as ScannerConfig,
+    ScanControl, ScanResult, ScanUpdate, Scanner, StatsSnapshot,
+};
 use ch_ts_parser::ModelPathMatcher;
 use ch_watcher::FileEvent;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent};
 use ratatui::layout::Rect;
+use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
 use crate::action::Action;
+use crate::buffers::{self, BufferState, EditorBuffers};
+use crate::commands::{self, KeyBindings};
+use crate::debug::DebugStats;
 use crate::error::TuiError;
+use crate::overlay::{Overlay, OverlayStack};
+use crate::session::{self, Session};
+use crate::view::{SortOrder, ViewSpec};
 
 /// The current mode of the application UI.
+///
+/// Derived from the topmost open [`Overlay`]; see [`App::mode`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum AppMode {
     /// Normal browsing mode.
@@ -48,6 +61,15 @@ pub enum AppMode {
 
     /// Directory setup overlay is displayed.
     DirectorySetup,
+
+    /// Command input mode (typing a `:` command).
+    Command,
+
+    /// Expanded status message popup is displayed.
+    Message,
+
+    /// Debug overlay is displayed.
+    Debug,
 }
 
 /// Current state of the background scan.
@@ -94,6 +116,21 @@ impl ScanState {
     }
 }
 
+/// A rescan requested via [`Action::Rescan`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RescanRequest {
+    /// No rescan requested.
+    None,
+
+    /// The event loop should start a background scan.
+    Start,
+
+    /// A scan is running; start one more when it completes.
+    ///
+    /// At most one rescan is queued; further requests are ignored.
+    Queued,
+}
+
 /// Which panel has focus.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum Focus {
@@ -284,10 +321,12 @@ impl FileListState {
     /// Ensures the selected item is visible.
     fn ensure_visible(&mut self) {
         if let Some(selected) = self.selected {
+            // Before the first render the height is unknown (zero)
+            let visible_height = self.visible_height.max(1);
             if selected < self.scroll_offset {
                 self.scroll_offset = selected;
-            } else if selected >= self.scroll_offset + self.visible_height {
-                self.scroll_offset = selected.saturating_sub(self.visible_height - 1);
+            } else if selected >= self.scroll_offset + visible_height {
+                self.scroll_offset = selected.saturating_sub(visible_height - 1);
             }
         }
     }
@@ -300,6 +339,40 @@ pub struct DetailPaneState {
     pub scroll_offset: usize,
 }
 
+/// State for the expanded status message popup.
+///
+/// Holds a copy of the message, so it stays readable after the status bar
+/// message is hidden or replaced.
+#[derive(Debug, Clone)]
+pub struct MessagePopupState {
+    /// The message being shown.
+    pub message: StatusMessage,
+
+    /// Scroll offset within the message, clamped when rendered.
+    pub scroll_offset: usize,
+}
+
+impl MessagePopupState {
+    /// Creates popup state showing `message` from the top.
+    #[must_use]
+    pub const fn new(message: StatusMessage) -> Self {
+        Self {
+            message,
+            scroll_offset: 0,
+        }
+    }
+
+    /// Scrolls by `delta` lines, stopping at the top.
+    ///
+    /// The offset is bounded by the message length; the exact limit depends
+    /// on how the text wraps and is applied when rendering.
+    pub fn scroll_by(&mut self, delta: isize) {
+        let details: usize = self.message.details.iter().map(String::len).sum();
+        let limit = self.message.text.len() + details;
+        self.scroll_offset = self.scroll_offset.saturating_add_signed(delta).min(limit);
+    }
+}
+
 /// Filter configuration state.
 #[derive(Debug, Clone, Default)]
 pub struct FilterState {
@@ -308,6 +381,15 @@ pub struct FilterState {
 
     /// Status filter (show only files with this status).
     pub status: Option<MigrationStatus>,
+
+    /// Multi-status filter loaded from a view.
+    ///
+    /// When non-empty, files matching any of these statuses are shown
+    /// and `status` is ignored.
+    pub statuses: Vec<MigrationStatus>,
+
+    /// Show only files with legacy imports that are never used.
+    pub dead_imports: bool,
 }
 
 /// Field focus for directory setup input.
@@ -400,17 +482,45 @@ impl FilterState {
     /// Returns `true` if any filter is active.
     #[must_use]
     pub fn is_active(&self) -> bool {
-        !self.text.is_empty() || self.status.is_some()
+        !self.text.is_empty()
+            || self.status.is_some()
+            || !self.statuses.is_empty()
+            || self.dead_imports
     }
 
     /// Clears all filters.
     pub fn clear(&mut self) {
         self.text.clear();
         self.status = None;
+        self.statuses.clear();
+        self.dead_imports = false;
+    }
+
+    /// Returns `true` if a file with the given status passes the status filter.
+    #[must_use]
+    pub fn matches_status(&self, status: MigrationStatus) -> bool {
+        if self.statuses.is_empty() {
+            self.status.is_none_or(|s| s == status)
+        } else {
+            self.statuses.contains(&status)
+        }
+    }
+
+    /// Returns the statuses currently filtered on (empty shows all).
+    #[must_use]
+    pub fn active_statuses(&self) -> Vec<MigrationStatus> {
+        if self.statuses.is_empty() {
+            self.status.into_iter().collect()
+        } else {
+            self.statuses.clone()
+        }
     }
 
     /// Cycles through status filters.
+    ///
+    /// Any multi-status filter loaded from a view is discarded.
     pub fn cycle_status(&mut self) {
+        self.statuses.clear();
         self.status = match self.status {
             None => Some(MigrationStatus::Legacy),
             Some(MigrationStatus::Legacy) => Some(MigrationStatus::Partial),
@@ -422,7 +532,7 @@ impl FilterState {
 }
 
 /// Status message to display in the status bar.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StatusMessage {
     /// The message text.
     pub text: String,
@@ -432,6 +542,11 @@ pub struct StatusMessage {
 
     /// Whether this is an error message.
     pub is_error: bool,
+
+    /// Underlying causes of an error, outermost first.
+    ///
+    /// Only shown in the expanded message popup.
+    pub details: Vec<String>,
 }
 
 impl StatusMessage {
@@ -442,6 +557,7 @@ impl StatusMessage {
             text: text.into(),
             timestamp: Instant::now(),
             is_error: false,
+            details: Vec::new(),
         }
     }
 
@@ -452,9 +568,34 @@ impl StatusMessage {
             text: text.into(),
             timestamp: Instant::now(),
             is_error: true,
+            details: Vec::new(),
         }
     }
 
+    /// Creates an error message from `error`, prefixed with `context`.
+    ///
+    /// The error's sources are kept as [`details`](Self::details), skipping
+    /// any whose text is already part of the message.
+    #[must_use]
+    pub fn from_error(context: &str, error: &(dyn std::error::Error + 'static)) -> Self {
+        let mut message = Self::error(format!("{context}: {error}"));
+        let mut source = error.source();
+        while let Some(cause) = source {
+            let text = cause.to_string();
+            if !message.text.contains(&text) && !message.details.contains(&text) {
+                message.details.push(text);
+            }
+            source = cause.source();
+        }
+        message
+    }
+
+    /// Returns `true` if the message has more to show than fits on one line.
+    #[must_use]
+    pub fn has_more(&self, width: usize) -> bool {
+        !self.details.is_empty() || self.text.chars().count() > width
+    }
+
     /// Returns `true` if the message should be auto-hidden.
     ///
     /// Messages are hidden after 5 seconds.
@@ -464,6 +605,23 @@ impl StatusMessage {
     }
 }
 
+/// Whether the file watcher picks up changes to a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchCoverage<'a> {
+    /// The file is inside the watched tree.
+    Watched {
+        /// Directory the watcher covers.
+        root: &'a Utf8Path,
+    },
+    /// The file is outside the watched tree, so edits don't refresh it.
+    Unwatched {
+        /// Directory the watcher covers.
+        root: &'a Utf8Path,
+    },
+    /// The file watcher is disabled or not running.
+    Off,
+}
+
 /// The main application state.
 pub struct App {
     /// The configuration.
@@ -475,8 +633,8 @@ pub struct App {
     /// Cached list of all files (sorted by path).
     files: Vec<FileInfo>,
 
-    /// Current UI mode.
-    pub mode: AppMode,
+    /// Open modal overlays; the topmost one receives key events.
+    pub overlays: OverlayStack,
 
     /// Which panel has focus.
     pub focus: Focus,
@@ -487,35 +645,82 @@ pub struct App {
     /// Detail pane widget state.
     pub detail_state: DetailPaneState,
 
+    /// Files on screen that are open in an editor.
+    pub editor_buffers: EditorBuffers,
+
     /// Current filter configuration.
     pub filter: FilterState,
 
+    /// Current sort order of the file list.
+    pub sort: SortOrder,
+
+    /// Keys bound to each command in normal mode.
+    pub key_bindings: KeyBindings,
+
+    /// Text typed at the `:` command prompt.
+    pub command_input: String,
+
     /// Status message to display.
     pub status: Option<StatusMessage>,
 
+    /// Expanded status message, while its popup is open.
+    pub message_popup: Option<MessagePopupState>,
+
     /// Directory setup input state.
     pub directory_setup: DirectorySetup,
 
+    /// Runtime health figures for the debug overlay.
+    pub debug: DebugStats,
+
     /// Pending watcher restart path (if needed).
     pending_watcher_restart: Option<Utf8PathBuf>,
 
+    /// Directory the running file watcher covers, if any.
+    watch_root: Option<Utf8PathBuf>,
+
     /// Whether the application should quit.
     pub should_quit: bool,
 
+    /// Whether the application should restart itself after quitting.
+    upgrade_requested: bool,
+
+    /// Session being resumed, finished once the first scan completes.
+    resumed_session: Option<Session>,
+
     /// Last scan statistics.
     pub stats: StatsSnapshot,
 
+    /// Per-team statistics (empty unless a CODEOWNERS file is configured).
+    pub team_stats: Vec<OwnerStats>,
+
     /// Terminal size (updated on resize).
     pub terminal_size: Rect,
 
     /// Current state of the background scan.
     pub scan_state: ScanState,
 
+    /// Outstanding rescan request, if any.
+    rescan: RescanRequest,
+
+    /// Handle that pauses or cancels the current background scan.
+    scan_control: ScanControl,
+
     /// Flag indicating files vec needs re-sorting.
     ///
     /// Set when files are added during streaming scan.
     /// Cleared after sorting on render.
     files_dirty: bool,
+
+    /// Positions in `files` of the results restored from the last session,
+    /// while the first scan replaces them; `None` otherwise.
+    saved_positions: Option<FxHashMap<Utf8PathBuf, usize>>,
+
+    /// Sender for the actions of background tasks, applied once they finish.
+    task_tx: mpsc::UnboundedSender<Action>,
+
+    /// Receiver for the actions of background tasks, until the event loop
+    /// takes it.
+    task_rx: Option<mpsc::UnboundedReceiver<Action>>,
 }
 
 impl App {
@@ -524,11 +729,10 @@ impl App {
     pub fn new(config: Config, scanner: Scanner) -> Self {
         let needs_setup = Self::requires_directory_setup(&config);
         let directory_setup = DirectorySetup::from_config(&config);
-        let mode = if needs_setup {
-            AppMode::DirectorySetup
-        } else {
-            AppMode::Normal
-        };
+        let mut overlays = OverlayStack::new();
+        if needs_setup {
+            overlays.push(Overlay::DirectorySetup);
+        }
         let status = if needs_setup {
             Some(StatusMessage::info(
                 "Select directories and press Enter to apply",
@@ -536,26 +740,49 @@ impl App {
         } else {
             None
         };
+        let (task_tx, task_rx) = mpsc::unbounded_channel();
         Self {
             config,
             scanner,
             files: Vec::new(),
-            mode,
+            overlays,
             focus: Focus::FileList,
             file_list_state: FileListState::new(),
             detail_state: DetailPaneState::default(),
+            editor_buffers: EditorBuffers::default(),
             filter: FilterState::default(),
+            sort: SortOrder::default(),
+            key_bindings: KeyBindings::default(),
+            command_input: String::new(),
             status,
+            message_popup: None,
+            debug: DebugStats::default(),
             directory_setup,
             pending_watcher_restart: None,
+            watch_root: None,
             should_quit: false,
+            upgrade_requested: false,
+            resumed_session: None,
             stats: StatsSnapshot::default(),
+            team_stats: Vec::new(),
             terminal_size: Rect::default(),
             scan_state: ScanState::Idle,
+            rescan: RescanRequest::None,
+            scan_control: ScanControl::new(),
             files_dirty: false,
+            saved_positions: None,
+            task_tx,
+            task_rx: Some(task_rx),
         }
     }
 
+    /// Sets the keys bound to each command.
+    #[must_use]
+    pub fn with_key_bindings(mut self, key_bindings: KeyBindings) -> Self {
+        self.key_bindings = key_bindings;
+        self
+    }
+
     /// Performs the initial scan.
     ///
     /// # Errors
@@ -587,48 +814,63 @@ impl App {
             return Action::Quit;
         }
 
-        match self.mode {
-            AppMode::Normal => self.handle_normal_key(key),
-            AppMode::Filtering => self.handle_filter_key(key),
-            AppMode::Help => self.handle_help_key(key),
-            AppMode::DirectorySetup => self.handle_directory_setup_key(key),
+        // Help is reachable from anywhere, on top of whatever is open
+        if key.code == KeyCode::F(1) {
+            return Action::ShowHelp;
         }
-    }
 
-    /// Handles a key event in normal mode.
-    fn handle_normal_key(&mut self, key: KeyEvent) -> Action {
-        match key.code {
-            KeyCode::Char('q') => Action::Quit,
-            KeyCode::Char('?') => Action::ToggleHelp,
-            KeyCode::Char('j') | KeyCode::Down => Action::NextItem,
-            KeyCode::Char('k') | KeyCode::Up => Action::PreviousItem,
-            KeyCode::Char('g') | KeyCode::Home => Action::FirstItem,
-            KeyCode::Char('G') | KeyCode::End => Action::LastItem,
-            KeyCode::PageDown => Action::PageDown,
-            KeyCode::PageUp => Action::PageUp,
-            KeyCode::Tab => Action::ToggleFocus,
-            KeyCode::Char('/') => Action::EnterFilterMode,
-            KeyCode::Char('f') => Action::CycleStatusFilter,
-            KeyCode::Char('o') => Action::OpenInEditor,
-            KeyCode::Char('r') => Action::Rescan,
-            KeyCode::Char('d') => Action::EnterDirectorySetup,
-            KeyCode::Esc => {
-                if self.filter.is_active() {
-                    Action::ClearFilter
-                } else {
-                    Action::None
-                }
-            }
-            _ => Action::None,
+        // So is the debug overlay, to inspect whatever is slow
+        if key.code == KeyCode::F(12) {
+            return Action::ToggleDebug;
         }
+
+        // Esc always closes the topmost overlay
+        let Some(overlay) = self.overlays.top() else {
+            return self.handle_normal_key(key);
+        };
+        if key.code == KeyCode::Esc {
+            return Action::CloseOverlay;
+        }
+
+        match overlay {
+            Overlay::Filter => self.handle_filter_key(key),
+            Overlay::Help => self.handle_help_key(key),
+            Overlay::DirectorySetup => self.handle_directory_setup_key(key),
+            Overlay::Command => self.handle_command_key(key),
+            Overlay::Message => self.handle_message_key(key),
+            Overlay::Debug => Action::None,
+        }
+    }
+
+    /// Returns the current UI mode, set by the topmost overlay.
+    #[must_use]
+    pub fn mode(&self) -> AppMode {
+        self.overlays.top().map_or(AppMode::Normal, Overlay::mode)
+    }
+
+    /// Returns the mode the help panel describes: the one keys go to once
+    /// help is closed.
+    #[must_use]
+    pub fn help_mode(&self) -> AppMode {
+        self.overlays
+            .iter()
+            .filter(|&overlay| overlay != Overlay::Help)
+            .last()
+            .map_or(AppMode::Normal, Overlay::mode)
+    }
+
+    /// Handles a key event in normal mode, using the command bound to it.
+    fn handle_normal_key(&self, key: KeyEvent) -> Action {
+        self.key_bindings
+            .command_for(key)
+            .map_or(Action::None, |command| command.action(self))
     }
 
     /// Handles a key event in filter mode.
     fn handle_filter_key(&mut self, key: KeyEvent) -> Action {
         match key.code {
-            KeyCode::Esc => Action::ExitFilterMode,
             KeyCode::Enter => {
-                self.mode = AppMode::Normal;
+                self.overlays.remove(Overlay::Filter);
                 Action::None
             }
             KeyCode::Backspace => {
@@ -643,11 +885,51 @@ impl App {
         }
     }
 
+    /// Handles a key event in command mode.
+    fn handle_command_key(&mut self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Enter => {
+                let input = std::mem::take(&mut self.command_input);
+                // Registered commands may need the event loop (e.g. opening
+                // the editor), so they are returned rather than executed
+                match commands::find(input.trim()) {
+                    Some(command) => {
+                        self.overlays.remove(Overlay::Command);
+                        command.action(self)
+                    }
+                    None => Action::ExecuteCommand(input),
+                }
+            }
+            KeyCode::Backspace => {
+                self.command_input.pop();
+                Action::None
+            }
+            KeyCode::Char(c) => {
+                self.command_input.push(c);
+                Action::None
+            }
+            _ => Action::None,
+        }
+    }
+
     /// Handles a key event in help mode.
     #[allow(clippy::unused_self)] // Keep &mut self for consistency
     fn handle_help_key(&mut self, key: KeyEvent) -> Action {
         match key.code {
-            KeyCode::Esc | KeyCode::Char('q' | '?') => Action::HideHelp,
+            KeyCode::Char('q' | '?') => Action::HideHelp,
+            _ => Action::None,
+        }
+    }
+
+    /// Handles a key event in the expanded message popup.
+    fn handle_message_key(&mut self, key: KeyEvent) -> Action {
+        let page = isize::try_from(self.terminal_size.height / 2).unwrap_or(1).max(1);
+        match key.code {
+            KeyCode::Char('q' | 'm') | KeyCode::Enter => Action::HideMessage,
+            KeyCode::Char('j') | KeyCode::Down => Action::ScrollMessage(1),
+            KeyCode::Char('k') | KeyCode::Up => Action::ScrollMessage(-1),
+            KeyCode::PageDown => Action::ScrollMessage(page),
+            KeyCode::PageUp => Action::ScrollMessage(-page),
             _ => Action::None,
         }
     }
@@ -655,7 +937,6 @@ impl App {
     /// Handles a key event in directory setup mode.
     fn handle_directory_setup_key(&mut self, key: KeyEvent) -> Action {
         match key.code {
-            KeyCode::Esc => Action::ExitDirectorySetup,
             KeyCode::Enter => Action::ApplyDirectorySetup,
             KeyCode::Tab => {
                 self.directory_setup.focus_next();
@@ -724,11 +1005,9 @@ impl App {
             }
 
             Action::EnterFilterMode => {
-                self.mode = AppMode::Filtering;
-            }
-            Action::ExitFilterMode => {
-                self.mode = AppMode::Normal;
+                self.overlays.push(Overlay::Filter);
             }
+            Action::ExitFilterMode => self.close_overlay(Overlay::Filter),
             Action::SetFilter(text) => {
                 self.filter.text = text;
                 self.apply_filter();
@@ -736,58 +1015,99 @@ impl App {
             Action::ClearFilter => {
                 self.filter.clear();
                 self.file_list_state.clear_filter();
-                self.mode = AppMode::Normal;
+                self.overlays.remove(Overlay::Filter);
             }
             Action::CycleStatusFilter => {
                 self.filter.cycle_status();
                 self.apply_filter();
             }
+            Action::ToggleDeadImportsFilter => {
+                self.filter.dead_imports = !self.filter.dead_imports;
+                self.apply_filter();
+                self.status = Some(StatusMessage::info(if self.filter.dead_imports {
+                    "Showing files with dead legacy imports"
+                } else {
+                    "Showing all files"
+                }));
+            }
             Action::SetStatusFilter(status) => {
+                self.filter.statuses.clear();
                 self.filter.status = status;
                 self.apply_filter();
             }
+            Action::CycleSort => {
+                self.sort = self.sort.next();
+                self.sort_and_refresh_files();
+                self.status = Some(StatusMessage::info(format!("Sort: {}", self.sort)));
+            }
 
-            Action::Rescan => {
-                if let Err(e) = self.rescan() {
-                    warn!(error = %e, "Rescan failed");
-                    self.status = Some(StatusMessage::error(format!("Rescan failed: {e}")));
-                }
+            Action::LoadView(view) => {
+                self.apply_view(view);
+                self.status = Some(StatusMessage::info(format!(
+                    "View loaded: {}",
+                    display_view(&self.current_view())
+                )));
+            }
+
+            Action::EnterCommandMode => {
+                self.command_input.clear();
+                self.overlays.push(Overlay::Command);
+            }
+            Action::ExitCommandMode => self.close_overlay(Overlay::Command),
+            Action::ExecuteCommand(command) => {
+                self.overlays.remove(Overlay::Command);
+                self.execute_command(&command);
             }
+
+            Action::Rescan => self.request_rescan(),
             Action::RescanFile(path) => {
                 self.rescan_file(&path);
             }
+            Action::UpdateRegistry(path) => self.update_registry(path),
+            Action::RegistryUpdated { path, outcome } => self.registry_updated(&path, outcome),
+            Action::EditorBuffersChecked(states) => {
+                if self.editor_buffers.finish_check(states) {
+                    debug!("Editor buffers changed");
+                }
+            }
+            Action::RescanDirectory => self.rescan_directory(),
+            Action::DirectoryRescanned { dir, outcome } => self.directory_rescanned(&dir, outcome),
+            Action::CancelScan => self.cancel_scan(),
+            Action::TogglePauseScan => self.toggle_pause_scan(),
 
             Action::ToggleHelp => {
-                self.mode = if self.mode == AppMode::Help {
-                    AppMode::Normal
+                if self.overlays.top() == Some(Overlay::Help) {
+                    self.overlays.pop();
                 } else {
-                    AppMode::Help
-                };
+                    self.overlays.push(Overlay::Help);
+                }
             }
             Action::ShowHelp => {
-                self.mode = AppMode::Help;
+                self.overlays.push(Overlay::Help);
             }
-            Action::HideHelp => {
-                self.mode = AppMode::Normal;
+            Action::HideHelp => self.close_overlay(Overlay::Help),
+            Action::ToggleDebug => {
+                if self.overlays.top() == Some(Overlay::Debug) {
+                    self.overlays.pop();
+                } else {
+                    self.overlays.push(Overlay::Debug);
+                }
+            }
+            Action::CloseOverlay => {
+                if let Some(overlay) = self.overlays.top() {
+                    self.close_overlay(overlay);
+                }
             }
 
             Action::EnterDirectorySetup => {
                 self.directory_setup.refresh_from_config(&self.config);
-                self.mode = AppMode::DirectorySetup;
-            }
-            Action::ExitDirectorySetup => {
-                if Self::requires_directory_setup(&self.config) {
-                    self.status = Some(StatusMessage::error(
-                        "Directory setup required to continue",
-                    ));
-                } else {
-                    self.mode = AppMode::Normal;
-                }
+                self.overlays.push(Overlay::DirectorySetup);
             }
+            Action::ExitDirectorySetup => self.close_overlay(Overlay::DirectorySetup),
             Action::ApplyDirectorySetup => {
                 match self.apply_directory_setup() {
                     Ok(()) => {
-                        self.mode = AppMode::Normal;
+                        self.overlays.remove(Overlay::DirectorySetup);
                     }
                     Err(e) => {
                         self.status = Some(StatusMessage::error(format!("{e}")));
@@ -801,8 +1121,21 @@ impl App {
             Action::ClearStatus => {
                 self.status = None;
             }
+            Action::ShowMessage => match self.status.clone() {
+                Some(message) => {
+                    self.message_popup = Some(MessagePopupState::new(message));
+                    self.overlays.push(Overlay::Message);
+                }
+                None => self.status = Some(StatusMessage::info("No status message")),
+            },
+            Action::HideMessage => self.close_overlay(Overlay::Message),
+            Action::ScrollMessage(delta) => {
+                if let Some(popup) = self.message_popup.as_mut() {
+                    popup.scroll_by(delta);
+                }
+            }
 
-            Action::OpenInEditor => {}
+            Action::OpenInEditor | Action::CopyView | Action::Refresh => {}
             Action::CopyPath => {
                 // Not implemented yet
             }
@@ -811,6 +1144,25 @@ impl App {
         }
     }
 
+    /// Closes `overlay`, returning to whatever is beneath it.
+    ///
+    /// Directory setup stays open while the configured directories are
+    /// unusable, since there is nothing to return to.
+    fn close_overlay(&mut self, overlay: Overlay) {
+        match overlay {
+            Overlay::DirectorySetup if Self::requires_directory_setup(&self.config) => {
+                self.status = Some(StatusMessage::error(
+                    "Directory setup required to continue",
+                ));
+                return;
+            }
+            Overlay::Command => self.command_input.clear(),
+            Overlay::Message => self.message_popup = None,
+            Overlay::Filter | Overlay::Help | Overlay::DirectorySetup | Overlay::Debug => {}
+        }
+        self.overlays.remove(overlay);
+    }
+
     /// Handles a tick event (periodic update).
     pub fn tick(&mut self) {
         // Clear stale status messages
@@ -819,6 +1171,34 @@ impl App {
                 self.status = None;
             }
         }
+
+        self.refresh_editor_buffers();
+    }
+
+    /// Re-checks which of the files on screen are open in an editor, in the
+    /// background.
+    ///
+    /// Detection reads swap files and editor backups, so it runs on the
+    /// blocking pool and reports back with [`Action::EditorBuffersChecked`].
+    fn refresh_editor_buffers(&mut self) {
+        if !self.editor_buffers.start_check() {
+            return;
+        }
+
+        let state = &self.file_list_state;
+        let len = state.len(self.files.len());
+        let start = state.scroll_offset.min(len);
+        let end = (start + state.visible_height.max(1)).min(len);
+
+        let files = &self.files;
+        let on_screen: Vec<Utf8PathBuf> = (start..end)
+            .chain(state.selected)
+            .filter_map(|idx| files.get(state.actual_index(idx)))
+            .map(|file| file.path.clone())
+            .collect();
+        self.run_in_background(move |_| {
+            Action::EditorBuffersChecked(buffers::detect_all(&on_screen))
+        });
     }
 
     /// Handles a scan update from the background streaming scan.
@@ -837,6 +1217,16 @@ impl App {
                     discovered: count,
                     scanned: 0,
                 };
+                if self.saved_positions.is_some() {
+                    // Saved results stay listed until the scan replaces them
+                    self.status = Some(StatusMessage::info(format!(
+                        "Scanning {count} files; showing the last session's results"
+                    )));
+                    return;
+                }
+                // Drop results of any previous scan; the scanner cache was cleared too
+                self.files.clear();
+                self.stats = StatsSnapshot::default();
                 // Pre-allocate storage for efficiency
                 self.files.reserve(count);
                 self.status = Some(StatusMessage::info(format!("Scanning {count} files...")));
@@ -848,23 +1238,38 @@ impl App {
                 // Update stats incrementally
                 self.update_stats_for_file(&file_info);
 
-                // Add to local files vec
-                self.files.push(file_info);
+                // Replace the saved result for the file, or add to local files vec
+                if let Some(saved) = self.replace_saved_file(file_info) {
+                    self.uncount_file(saved.status);
+                }
                 self.files_dirty = true;
 
                 // Update progress counter
                 if let ScanState::Scanning {
-                    discovered,
-                    ref mut scanned,
+                    ref mut scanned, ..
                 } = self.scan_state
                 {
                     *scanned += 1;
-                    // Update status message periodically (every 100 files)
-                    if *scanned % 100 == 0 {
-                        self.status = Some(StatusMessage::info(format!(
-                            "Scanning... {scanned}/{discovered} files"
-                        )));
-                    }
+                }
+            }
+            ScanUpdate::Progress(progress) => {
+                if let ScanState::Scanning {
+                    discovered,
+                    scanned,
+                } = self.scan_state
+                {
+                    let eta = progress
+                        .eta()
+                        .map(|eta| format!(", about {}s left", eta.as_secs()))
+                        .unwrap_or_default();
+                    let saved = if self.saved_positions.is_some() {
+                        "; showing the last session's results"
+                    } else {
+                        ""
+                    };
+                    self.status = Some(StatusMessage::info(format!(
+                        "Scanning... {scanned}/{discovered} files{eta}{saved}"
+                    )));
                 }
             }
             ScanUpdate::FileError { path, error } => {
@@ -880,16 +1285,128 @@ impl App {
                 );
                 self.scan_state = ScanState::Complete;
                 self.stats = result.stats;
-                // Force sort and apply filters
-                self.sort_and_refresh_files();
+                self.debug.cache_rebuilt_at = Some(Instant::now());
+                if self.saved_positions.take().is_some() {
+                    // Drop saved results of files the scan no longer found
+                    self.refresh_file_list();
+                } else {
+                    // Force sort and apply filters
+                    self.sort_and_refresh_files();
+                }
                 self.status = Some(StatusMessage::info(format!(
                     "Scanned {} files",
                     self.stats.total
                 )));
+                self.finish_resume();
+
+                if self.rescan == RescanRequest::Queued {
+                    debug!("Starting queued rescan");
+                    self.rescan = RescanRequest::None;
+                    self.request_rescan();
+                }
             }
         }
     }
 
+    /// Handles a background scan that ended without completing.
+    ///
+    /// Resets the scan state so that later rescan requests are not ignored.
+    pub fn handle_scan_aborted(&mut self) {
+        if self.scan_state.is_scanning() {
+            warn!("Background scan ended before completing");
+            self.scan_state = ScanState::Idle;
+            self.rescan = RescanRequest::None;
+            self.status = Some(StatusMessage::error("Scan failed; press r to retry"));
+        }
+    }
+
+    /// Requests a full background rescan.
+    ///
+    /// If a scan is already running, one additional rescan is queued to start
+    /// when it completes; any further requests are ignored.
+    fn request_rescan(&mut self) {
+        if self.scan_state.is_scanning() {
+            debug!(rescan = ?self.rescan, "Rescan requested while scan running");
+            self.rescan = RescanRequest::Queued;
+            self.status = Some(StatusMessage::info("scan already running"));
+            return;
+        }
+
+        info!("Rescanning files");
+        self.rescan = RescanRequest::Start;
+        self.scan_state = ScanState::Scanning {
+            discovered: 0,
+            scanned: 0,
+        };
+        self.status = Some(StatusMessage::info("Rescanning..."));
+    }
+
+    /// Cancels the running background scan.
+    ///
+    /// The files scanned so far stay in the list, and a queued rescan is
+    /// dropped.
+    pub fn cancel_scan(&mut self) {
+        if !self.scan_state.is_scanning() {
+            self.status = Some(StatusMessage::info("no scan running"));
+            return;
+        }
+
+        info!("Cancelling background scan");
+        self.scan_control.cancel();
+        self.scan_state = ScanState::Idle;
+        self.rescan = RescanRequest::None;
+        self.sort_and_refresh_files();
+        self.status = Some(StatusMessage::info(format!(
+            "Scan cancelled after {} files; press r to rescan",
+            self.stats.total
+        )));
+    }
+
+    /// Pauses the running background scan, or resumes it if paused.
+    ///
+    /// Files being analyzed when pausing still arrive; no others are
+    /// parsed until the scan is resumed.
+    pub fn toggle_pause_scan(&mut self) {
+        if !self.scan_state.is_scanning() {
+            self.status = Some(StatusMessage::info("no scan running"));
+            return;
+        }
+
+        if self.scan_control.is_paused() {
+            info!("Resuming background scan");
+            self.scan_control.resume();
+            self.status = Some(StatusMessage::info("Scan resumed"));
+        } else {
+            info!("Pausing background scan");
+            self.scan_control.pause();
+            self.status = Some(StatusMessage::info("Scan paused; press p to resume"));
+        }
+    }
+
+    /// Returns `true` if the running background scan is paused.
+    #[must_use]
+    pub fn is_scan_paused(&self) -> bool {
+        self.scan_state.is_scanning() && self.scan_control.is_paused()
+    }
+
+    /// Returns a handle for a new background scan, replacing the one that
+    /// [`cancel_scan`](Self::cancel_scan) and
+    /// [`toggle_pause_scan`](Self::toggle_pause_scan) act on.
+    pub fn new_scan_control(&mut self) -> ScanControl {
+        self.scan_control = ScanControl::new();
+        self.scan_control.clone()
+    }
+
+    /// Returns `true` (once) if the event loop should start a background scan.
+    pub fn take_scan_request(&mut self) -> bool {
+        if self.rescan == RescanRequest::Start {
+            self.rescan = RescanRequest::None;
+            true
+        } else {
+            false
+        }
+    }
+
     /// Updates internal stats based on a newly scanned file.
     fn update_stats_for_file(&mut self, file_info: &FileInfo) {
         self.stats.total += 1;
@@ -902,6 +1419,40 @@ impl App {
         }
     }
 
+    /// Takes `file_info` into the list in place of its saved result, which
+    /// is returned, or adds it if it has none.
+    fn replace_saved_file(&mut self, file_info: FileInfo) -> Option<FileInfo> {
+        let Some(positions) = &mut self.saved_positions else {
+            self.files.push(file_info);
+            return None;
+        };
+        if let Some(saved) = positions
+            .get(&file_info.path)
+            .and_then(|&index| self.files.get_mut(index))
+        {
+            return Some(std::mem::replace(saved, file_info));
+        }
+        positions.insert(file_info.path.clone(), self.files.len());
+        self.files.push(file_info);
+        None
+    }
+
+    /// Removes a file with `status` from the stats.
+    const fn uncount_file(&mut self, status: MigrationStatus) {
+        self.stats.total = self.stats.total.saturating_sub(1);
+        match status {
+            MigrationStatus::Legacy => self.stats.legacy = self.stats.legacy.saturating_sub(1),
+            MigrationStatus::Migrated => {
+                self.stats.migrated = self.stats.migrated.saturating_sub(1);
+            }
+            MigrationStatus::Partial => self.stats.partial = self.stats.partial.saturating_sub(1),
+            MigrationStatus::NoModels => {
+                self.stats.no_models = self.stats.no_models.saturating_sub(1);
+            }
+            _ => {}
+        }
+    }
+
     /// Sorts files if dirty (called before render).
     ///
     /// This deferred sorting approach avoids O(n log n) sort per file
@@ -909,8 +1460,12 @@ impl App {
     /// sorted once before each render.
     pub fn sort_files_if_needed(&mut self) {
         if self.files_dirty {
-            self.files.sort_by(|a, b| a.path.cmp(&b.path));
+            let sort = self.sort;
+            self.files.sort_by(|a, b| sort.compare(a, b));
             self.files_dirty = false;
+            if let Some(positions) = &mut self.saved_positions {
+                *positions = Self::positions(&self.files);
+            }
 
             // Re-apply filter if active
             if self.filter.is_active() {
@@ -926,8 +1481,10 @@ impl App {
 
     /// Sorts files and refreshes the display after a scan completes.
     fn sort_and_refresh_files(&mut self) {
-        self.files.sort_by(|a, b| a.path.cmp(&b.path));
+        let sort = self.sort;
+        self.files.sort_by(|a, b| sort.compare(a, b));
         self.files_dirty = false;
+        self.refresh_team_stats();
 
         // Re-apply filter if active
         if self.filter.is_active() {
@@ -937,6 +1494,47 @@ impl App {
         }
     }
 
+    /// Lists the results saved by the last session, if the scanner has a
+    /// cache directory, until the first scan replaces them.
+    pub fn load_saved_results(&mut self) {
+        let Some(path) = self.scanner.saved_cache_path() else {
+            return;
+        };
+        match self.scanner.load_cache(&path) {
+            Ok(0) => {}
+            Ok(count) => {
+                info!(path = %path, count, "Showing the last session's results");
+                self.stats = self.scanner.stats();
+                self.refresh_file_list();
+                self.saved_positions = Some(Self::positions(&self.files));
+                self.status = Some(StatusMessage::info(format!(
+                    "Showing {count} files from the last session"
+                )));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => warn!(path = %path, error = %e, "Ignoring saved scan results"),
+        }
+    }
+
+    /// Saves the scan results for the next session, if the scanner has a
+    /// cache directory and its last scan completed.
+    pub fn save_results(&self) {
+        if !matches!(self.scan_state, ScanState::Complete) {
+            return;
+        }
+        let Some(path) = self.scanner.saved_cache_path() else {
+            return;
+        };
+        if let Err(e) = self.scanner.save_cache(&path) {
+            warn!(path = %path, error = %e, "Failed to save scan results");
+        }
+    }
+
+    /// Returns the position of each file in `files` by path.
+    fn positions(files: &[FileInfo]) -> FxHashMap<Utf8PathBuf, usize> {
+        files.iter().enumerate().map(|(index, file)| (file.path.clone(), index)).collect()
+    }
+
     /// Returns true if the directory setup should be shown.
     #[must_use]
     pub fn needs_directory_setup(&self) -> bool {
@@ -948,6 +1546,52 @@ impl App {
         self.pending_watcher_restart.take()
     }
 
+    /// Records the directory the file watcher covers, or `None` once it
+    /// has stopped or failed to start.
+    pub fn set_watch_root(&mut self, root: Option<Utf8PathBuf>) {
+        self.watch_root = root;
+    }
+
+    /// Returns whether the file watcher picks up changes to `path`.
+    ///
+    /// Without recursive watching, only files directly in the watched
+    /// directory are covered.
+    #[must_use]
+    pub fn watch_coverage(&self, path: &Utf8Path) -> WatchCoverage<'_> {
+        let Some(root) = self.watch_root.as_deref() else {
+            return WatchCoverage::Off;
+        };
+        let watched = if self.config.watch.recursive {
+            path.starts_with(root)
+        } else {
+            path.parent() == Some(root)
+        };
+        if watched {
+            WatchCoverage::Watched { root }
+        } else {
+            WatchCoverage::Unwatched { root }
+        }
+    }
+
+    /// Returns a warning when the file watcher covers less than the scanned
+    /// tree, so some edits won't refresh the file list.
+    #[must_use]
+    pub fn watch_notice(&self) -> Option<String> {
+        let root = self.watch_root.as_deref()?;
+        let scan_root = &self.scanner.config().root;
+        if !scan_root.starts_with(root) {
+            Some(format!(
+                "Watching {root} but scanning {scan_root}: edits outside {root} need a rescan"
+            ))
+        } else if !self.config.watch.recursive {
+            Some(format!(
+                "Watching only the top level of {root}: edits in subdirectories need a rescan"
+            ))
+        } else {
+            None
+        }
+    }
+
     /// Performs a full rescan.
     fn rescan(&mut self) -> Result<ScanResult, TuiError> {
         info!("Rescanning files");
@@ -983,7 +1627,7 @@ impl App {
         };
 
         if let Err(e) = self.rescan() {
-            self.status = Some(StatusMessage::error(format!("Rescan failed: {e}")));
+            self.status = Some(StatusMessage::from_error("Rescan failed", &e));
         } else {
             self.status = Some(StatusMessage::info("Directories updated"));
         }
@@ -1004,19 +1648,47 @@ impl App {
 
     fn rebuild_scanner(&mut self) -> Result<(), TuiError> {
         // Use app_path for scanning to restrict to application code only
-        let scanner_config = ScannerConfig::new(&self.config.scan.app_path)
-            .with_skip_dirs(&["node_modules", "dist", ".git"]);
+        let mut scanner_config = ScannerConfig::new(&self.config.scan.app_path)
+            .with_skip_dirs(&["node_modules", "dist", ".git"])
+            .with_resource_limits(ResourceLimits::from(&self.config.scan));
+        if let Some(codeowners) = &self.config.scan.codeowners_path {
+            scanner_config = scanner_config.with_codeowners(codeowners);
+        }
+        if let Some(tsconfig) = &self.config.scan.tsconfig_path {
+            scanner_config = scanner_config.with_tsconfig(tsconfig);
+        }
+        if self.config.watch.enabled && self.config.watch.shared {
+            scanner_config = scanner_config.with_shared_paths(
+                &self.config.scan.shared_path,
+                &self.config.scan.shared_2023_path,
+            );
+        }
+        if let Some(threads) = self.config.scan.max_parallel_jobs {
+            scanner_config = scanner_config.with_threads(threads);
+        }
+        if let Some(attempts) = self.config.scan.retry_attempts {
+            scanner_config =
+                scanner_config.with_retry(RetryPolicy::default().with_attempts(attempts));
+        }
         let matcher = ModelPathMatcher::from_scan_config(&self.config.scan);
         self.scanner = Scanner::new_with_matcher(scanner_config, matcher)?;
+        // Results saved for the old directories don't stand in for the new ones
+        self.saved_positions = None;
         Ok(())
     }
 
     /// Rescans a specific file.
     fn rescan_file(&mut self, path: &Utf8PathBuf) {
         debug!(path = %path, "Rescanning file");
-        let results = self.scanner.rescan_files(std::slice::from_ref(path));
+        if self.editor_buffers.get(path) == Some(BufferState::Modified) {
+            self.status = Some(StatusMessage::info(format!(
+                "{} has unsaved changes in an editor; showing the saved version",
+                path.file_name().unwrap_or(path.as_str())
+            )));
+        }
+        let result = self.scanner.rescan_files(std::slice::from_ref(path));
 
-        for (p, result) in results {
+        for (p, result) in result.outcomes {
             if let Err(e) = result {
                 warn!(path = %p, error = %e, "Failed to rescan file");
             }
@@ -1026,12 +1698,110 @@ impl App {
         self.refresh_file_list();
     }
 
+    /// Takes the receiver for the actions of background tasks.
+    ///
+    /// The event loop applies each action it receives with
+    /// [`update`](Self::update). Returns `None` once taken.
+    pub fn take_task_receiver(&mut self) -> Option<mpsc::UnboundedReceiver<Action>> {
+        self.task_rx.take()
+    }
+
+    /// Runs `task` with the scanner on the blocking pool, sending the action
+    /// it returns to the event loop.
+    fn run_in_background(&self, task: impl FnOnce(&Scanner) -> Action + Send + 'static) {
+        let scanner = self.scanner.clone();
+        let tx = self.task_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            // The event loop has ended if nothing receives the action
+            let _ = tx.send(task(&scanner));
+        });
+    }
+
+    /// Updates the model registry from the changed shared file at `path`
+    /// and rescans the files importing its models, in the background.
+    fn update_registry(&self, path: Utf8PathBuf) {
+        debug!(path = %path, "Updating model registry");
+        self.run_in_background(move |scanner| {
+            let outcome = match scanner.refresh_registry(std::slice::from_ref(&path)) {
+                Ok(result) => Ok(result.map(|result| {
+                    for (p, result) in &result.outcomes {
+                        if let Err(e) = result {
+                            warn!(path = %p, error = %e, "Failed to rescan file");
+                        }
+                    }
+                    result.outcomes.len()
+                })),
+                Err(e) => Err(StatusMessage::from_error("Model update failed", &e)),
+            };
+            Action::RegistryUpdated { path, outcome }
+        });
+    }
+
+    /// Shows the outcome of a model registry update.
+    fn registry_updated(&mut self, path: &Utf8Path, outcome: Result<Option<usize>, StatusMessage>) {
+        match outcome {
+            Ok(Some(rechecked)) => {
+                self.stats = self.scanner.stats();
+                self.refresh_file_list();
+                let name = path.file_name().unwrap_or(path.as_str());
+                self.status = Some(StatusMessage::info(format!(
+                    "Models updated from {name}; {rechecked} importing files rechecked"
+                )));
+            }
+            Ok(None) => debug!(path = %path, "Not a model file"),
+            Err(message) => self.status = Some(message),
+        }
+    }
+
+    /// Rescans the directory of the selected file in the background.
+    ///
+    /// Only that subtree is walked again; files deleted from it leave the
+    /// list and the rest of the cache is kept.
+    fn rescan_directory(&mut self) {
+        if self.scan_state.is_scanning() {
+            self.status = Some(StatusMessage::info("scan already running"));
+            return;
+        }
+        let Some(dir) = self
+            .selected_file()
+            .and_then(|file| file.path.parent())
+            .map(Utf8Path::to_path_buf)
+        else {
+            self.status = Some(StatusMessage::info("No file selected"));
+            return;
+        };
+
+        debug!(dir = %dir, "Rescanning directory");
+        self.status = Some(StatusMessage::info(format!("Rescanning {dir}")));
+        self.run_in_background(move |scanner| {
+            let outcome = scanner
+                .rescan_prefix(&dir)
+                .map(|result| result.stats)
+                .map_err(|e| StatusMessage::from_error("Rescan failed", &e));
+            Action::DirectoryRescanned { dir, outcome }
+        });
+    }
+
+    /// Shows the outcome of a directory rescan.
+    fn directory_rescanned(&mut self, dir: &Utf8Path, outcome: Result<StatsSnapshot, StatusMessage>) {
+        match outcome {
+            Ok(stats) => {
+                self.stats = stats;
+                self.refresh_file_list();
+                self.status = Some(StatusMessage::info(format!("Rescanned {dir}")));
+            }
+            Err(message) => self.status = Some(message),
+        }
+    }
+
     /// Refreshes the file list from the scanner cache.
     fn refresh_file_list(&mut self) {
         self.files = self.scanner.cache().all_files();
 
-        // Sort by path for consistent ordering
-        self.files.sort_by(|a, b| a.path.cmp(&b.path));
+        // Sort by the active sort order (ties broken by path)
+        let sort = self.sort;
+        self.files.sort_by(|a, b| sort.compare(a, b));
+        self.refresh_team_stats();
 
         // Re-apply filter if active
         if self.filter.is_active() {
@@ -1041,6 +1811,15 @@ impl App {
         }
     }
 
+    /// Recomputes per-team statistics when code ownership is configured.
+    fn refresh_team_stats(&mut self) {
+        self.team_stats = if self.scanner.codeowners().is_some() {
+            rollup_by_owner(&self.files)
+        } else {
+            Vec::new()
+        };
+    }
+
     /// Applies the current filter to the file list.
     fn apply_filter(&mut self) {
         if !self.filter.is_active() {
@@ -1049,7 +1828,7 @@ impl App {
         }
 
         let text_lower = self.filter.text.to_lowercase();
-        let status_filter = self.filter.status;
+        let filter = &self.filter;
 
         let indices: Vec<usize> = self
             .files
@@ -1061,9 +1840,12 @@ impl App {
                     text_lower.is_empty() || file.path.as_str().to_lowercase().contains(&text_lower);
 
                 // Status filter
-                let status_match = status_filter.is_none_or(|s| file.status == s);
+                let status_match = filter.matches_status(file.status);
 
-                text_match && status_match
+                // Dead import filter
+                let dead_match = !filter.dead_imports || file.has_dead_legacy_imports();
+
+                text_match && status_match && dead_match
             })
             .map(|(i, _)| i)
             .collect();
@@ -1071,6 +1853,117 @@ impl App {
         self.file_list_state.set_filter(Some(indices));
     }
 
+    /// Returns the current filter and sort state as a shareable view.
+    #[must_use]
+    pub fn current_view(&self) -> ViewSpec {
+        ViewSpec {
+            statuses: self.filter.active_statuses(),
+            sort: self.sort,
+            path: self.filter.text.clone(),
+            dead_imports: self.filter.dead_imports,
+        }
+    }
+
+    /// Replaces the current filter and sort state with the given view.
+    pub fn apply_view(&mut self, view: ViewSpec) {
+        self.filter.text = view.path;
+        self.filter.status = None;
+        self.filter.statuses = view.statuses;
+        self.filter.dead_imports = view.dead_imports;
+        if let [status] = self.filter.statuses[..] {
+            self.filter.status = Some(status);
+            self.filter.statuses.clear();
+        }
+        self.sort = view.sort;
+        self.sort_and_refresh_files();
+        if !self.filter.is_active() {
+            self.file_list_state.clear_filter();
+        }
+    }
+
+    /// Returns the state to carry across an in-place restart.
+    #[must_use]
+    pub fn session(&self) -> Session {
+        Session {
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            view: self.current_view(),
+            selected: self.selected_file().map(|file| file.path.clone()),
+        }
+    }
+
+    /// Restores state saved by [`session`](Self::session).
+    ///
+    /// The view is applied immediately; the selection is restored once the
+    /// scan completes and the file is back in the list.
+    pub fn restore_session(&mut self, session: Session) {
+        self.apply_view(session.view.clone());
+        self.resumed_session = Some(session);
+    }
+
+    /// Returns `true` if the user asked to restart into a new binary.
+    #[must_use]
+    pub const fn upgrade_requested(&self) -> bool {
+        self.upgrade_requested
+    }
+
+    /// Finishes resuming a session once the file list is populated.
+    ///
+    /// Reselects the saved file (if it is still listed) and reports the
+    /// resume in the status bar.
+    fn finish_resume(&mut self) {
+        let Some(session) = self.resumed_session.take() else {
+            return;
+        };
+
+        let file_index = session
+            .selected
+            .as_ref()
+            .and_then(|path| self.files.iter().position(|file| &file.path == path));
+        let display_index = file_index.and_then(|file_index| match self.file_list_state.filtered_indices() {
+            Some(indices) => indices.iter().position(|&index| index == file_index),
+            None => Some(file_index),
+        });
+        if let Some(display_index) = display_index {
+            self.file_list_state.select(display_index, self.files.len());
+        }
+
+        self.status = Some(StatusMessage::info(session::resumed_message(&session)));
+    }
+
+    /// Executes a command entered at the `:` prompt.
+    ///
+    /// Supported commands:
+    ///
+    /// - `load-view <spec>` - apply a view string
+    /// - `view` - show the current view string
+    /// - `upgrade` - restart into the installed binary, keeping the session
+    fn execute_command(&mut self, command: &str) {
+        let command = command.trim();
+        let (name, args) = command.split_once(' ').unwrap_or((command, ""));
+
+        match name {
+            "" => {}
+            "load-view" => match args.parse::<ViewSpec>() {
+                Ok(view) => self.update(Action::LoadView(view)),
+                Err(e) => self.status = Some(StatusMessage::error(format!("{e}"))),
+            },
+            "view" => {
+                self.status = Some(StatusMessage::info(format!(
+                    "View: {}",
+                    display_view(&self.current_view())
+                )));
+            }
+            "upgrade" => {
+                info!("Upgrade requested");
+                self.upgrade_requested = true;
+                self.should_quit = true;
+            }
+            other => {
+                self.status = Some(StatusMessage::error(format!("Unknown command: {other}")));
+            }
+        }
+    }
+
     /// Returns the currently selected file, if any.
     #[must_use]
     pub fn selected_file(&self) -> Option<&FileInfo> {
@@ -1132,6 +2025,21 @@ impl App {
         // Return action to rescan the file
         Action::RescanFile(event.path)
     }
+
+    /// Handles a change in a watched shared model directory.
+    ///
+    /// Returns an `Action` to update the model registry, or `None`.
+    #[must_use]
+    pub fn handle_shared_change(&mut self, event: FileEvent) -> Action {
+        if !event.is_typescript() {
+            return Action::None;
+        }
+
+        info!(path = %event.path, "Shared file changed, updating models");
+        let file_name = event.file_name().unwrap_or(event.path.as_str());
+        self.status = Some(StatusMessage::info(format!("Shared file changed: {file_name}")));
+        Action::UpdateRegistry(event.path)
+    }
 }
 
 #[derive(Debug)]
@@ -1160,6 +2068,15 @@ fn parse_dir_input(label: &str, input: &str) -> Result<Utf8PathBuf, TuiError> {
     Ok(path)
 }
 
+/// Formats a view for status messages, naming the default view explicitly.
+fn display_view(view: &ViewSpec) -> String {
+    if view.is_default() {
+        "(default)".to_owned()
+    } else {
+        view.to_string()
+    }
+}
+
 fn is_valid_dir(path: &Utf8PathBuf) -> bool {
     !path.as_str().is_empty() && path.exists() && path.is_dir()
 }
@@ -1181,6 +2098,69 @@ mod tests {
         assert_eq!(AppMode::default(), AppMode::Normal);
     }
 
+    /// Creates an app whose directories need setting up.
+    ///
+    /// Fails the test if the temporary directory can't be scanned, rather
+    /// than letting it pass without running.
+    #[allow(clippy::expect_used)] // a test can't run without its fixture
+    fn app_needing_setup() -> App {
+        let root = Utf8PathBuf::from_path_buf(std::env::temp_dir())
+            .expect("temporary directory path is not UTF-8");
+        let scanner = Scanner::new(ScannerConfig::new(&root)).expect("failed to create the scanner");
+        let mut config = Config::default();
+        config.scan.root_path = root.join("ch-tui-missing-root");
+        App::new(config, scanner)
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        let action = app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+        app.update(action);
+    }
+
+    #[test]
+    fn test_overlay_esc_pops_topmost() {
+        let mut app = app_needing_setup();
+        assert_eq!(app.mode(), AppMode::DirectorySetup);
+        press(&mut app, KeyCode::Char('x'));
+
+        // Help opens over the setup dialog and Esc returns to it intact
+        press(&mut app, KeyCode::F(1));
+        assert_eq!(app.mode(), AppMode::Help);
+        assert_eq!(app.help_mode(), AppMode::DirectorySetup);
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.mode(), AppMode::DirectorySetup);
+        assert!(app.directory_setup.active_input_mut().ends_with('x'));
+
+        // Setup can't be dismissed while the directories are missing
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.mode(), AppMode::DirectorySetup);
+        assert!(app.status.as_ref().is_some_and(|status| status.is_error));
+    }
+
+    #[test]
+    fn test_watch_coverage_and_notice() {
+        let mut app = app_needing_setup();
+        let scan_root = app.scanner.config().root.clone();
+        let file = scan_root.join("jobs/list.ts");
+        assert_eq!(app.watch_coverage(&file), WatchCoverage::Off);
+        assert_eq!(app.watch_notice(), None);
+
+        app.set_watch_root(Some(scan_root.clone()));
+        assert_eq!(app.watch_coverage(&file), WatchCoverage::Watched { root: &scan_root });
+        assert_eq!(app.watch_notice(), None);
+
+        app.config.watch.recursive = false;
+        assert_eq!(app.watch_coverage(&file), WatchCoverage::Unwatched { root: &scan_root });
+        assert!(app.watch_notice().is_some_and(|notice| notice.contains("top level")));
+
+        app.config.watch.recursive = true;
+        let jobs = scan_root.join("jobs");
+        app.set_watch_root(Some(jobs.clone()));
+        let other = scan_root.join("contracts/a.ts");
+        assert_eq!(app.watch_coverage(&other), WatchCoverage::Unwatched { root: &jobs });
+        assert!(app.watch_notice().is_some_and(|notice| notice.contains("need a rescan")));
+    }
+
     #[test]
     fn test_focus_toggle() {
         assert_eq!(Focus::FileList.toggle(), Focus::DetailPane);
@@ -1223,6 +2203,21 @@ mod tests {
         assert!(filter.is_active());
     }
 
+    #[test]
+    fn test_filter_state_matches_statuses() {
+        let mut filter = FilterState::default();
+        assert!(filter.matches_status(MigrationStatus::Migrated));
+
+        filter.statuses = vec![MigrationStatus::Legacy, MigrationStatus::Partial];
+        assert!(filter.is_active());
+        assert!(filter.matches_status(MigrationStatus::Partial));
+        assert!(!filter.matches_status(MigrationStatus::Migrated));
+
+        filter.cycle_status();
+        assert!(filter.statuses.is_empty());
+        assert_eq!(filter.status, Some(MigrationStatus::Legacy));
+    }
+
     #[test]
     fn test_file_list_state_navigation() {
         let mut state = FileListState::new();
@@ -1261,4 +2256,174 @@ mod tests {
         let err = StatusMessage::error("Error!");
         assert!(err.is_error);
     }
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("failed to read config")]
+    struct ReadError(#[source] std::io::Error);
+
+    #[test]
+    fn test_status_message_from_error() {
+        let cause = std::io::Error::new(std::io::ErrorKind::NotFound, "no such file");
+        let msg = StatusMessage::from_error("Rescan failed", &ReadError(cause));
+        assert!(msg.is_error);
+        assert_eq!(msg.text, "Rescan failed: failed to read config");
+        assert_eq!(msg.details, ["no such file"]);
+        assert!(msg.has_more(80));
+
+        // Sources already included in the message are not repeated
+        let msg = StatusMessage::from_error("Scan", &TuiError::from(std::io::Error::other("gone")));
+        assert!(msg.details.is_empty());
+        assert!(!msg.has_more(80));
+    }
+
+    #[test]
+    fn test_message_popup_scroll_and_close() {
+        let mut app = app_needing_setup();
+        app.overlays.remove(Overlay::DirectorySetup);
+        app.status = None;
+
+        press(&mut app, KeyCode::Char('m'));
+        assert_eq!(app.mode(), AppMode::Normal);
+        assert!(app.status.as_ref().is_some_and(|status| status.text == "No status message"));
+
+        app.status = Some(StatusMessage::error("x".repeat(500)));
+        press(&mut app, KeyCode::Char('m'));
+        assert_eq!(app.mode(), AppMode::Message);
+
+        // The popup keeps its copy when the status bar message goes away
+        app.status = None;
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Char('k'));
+        let popup = app.message_popup.as_ref();
+        assert_eq!(popup.map(|popup| popup.scroll_offset), Some(1));
+        assert!(popup.is_some_and(|popup| popup.message.is_error));
+
+        press(&mut app, KeyCode::Char('k'));
+        press(&mut app, KeyCode::Char('k'));
+        assert_eq!(app.message_popup.as_ref().map(|popup| popup.scroll_offset), Some(0));
+
+        press(&mut app, KeyCode::Char('q'));
+        assert_eq!(app.mode(), AppMode::Normal);
+        assert!(app.message_popup.is_none());
+    }
+    #[test]
+    fn test_f12_toggles_debug_over_any_overlay() {
+        let mut app = app_needing_setup();
+        assert_eq!(app.mode(), AppMode::DirectorySetup);
+
+        press(&mut app, KeyCode::F(12));
+        assert_eq!(app.mode(), AppMode::Debug);
+        press(&mut app, KeyCode::F(12));
+        assert_eq!(app.mode(), AppMode::DirectorySetup);
+
+        press(&mut app, KeyCode::F(12));
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.mode(), AppMode::DirectorySetup);
+    }
+
+    #[test]
+    fn test_rescan_directory_needs_selection() {
+        let mut app = app_needing_setup();
+        app.overlays.remove(Overlay::DirectorySetup);
+
+        press(&mut app, KeyCode::Char('R'));
+        assert!(app
+            .status
+            .as_ref()
+            .is_some_and(|status| status.text == "No file selected"));
+    }
+
+    #[test]
+    fn test_cancel_scan_stops_background_scan() {
+        let mut app = app_needing_setup();
+        app.overlays.remove(Overlay::DirectorySetup);
+
+        press(&mut app, KeyCode::Char('x'));
+        assert!(app
+            .status
+            .as_ref()
+            .is_some_and(|status| status.text == "no scan running"));
+
+        let cancel = app.new_scan_control();
+        app.scan_state = ScanState::Scanning {
+            discovered: 10,
+            scanned: 3,
+        };
+        app.rescan = RescanRequest::Queued;
+        press(&mut app, KeyCode::Char('x'));
+        assert!(cancel.is_cancelled());
+        assert!(!app.scan_state.is_scanning());
+        assert_eq!(app.rescan, RescanRequest::None);
+        assert!(!app.new_scan_control().is_cancelled(), "next scan gets a fresh token");
+    }
+
+    #[test]
+    fn test_pause_scan_toggles_background_scan() {
+        let mut app = app_needing_setup();
+        app.overlays.remove(Overlay::DirectorySetup);
+
+        press(&mut app, KeyCode::Char('p'));
+        assert!(app
+            .status
+            .as_ref()
+            .is_some_and(|status| status.text == "no scan running"));
+
+        let control = app.new_scan_control();
+        app.scan_state = ScanState::Scanning {
+            discovered: 10,
+            scanned: 3,
+        };
+        press(&mut app, KeyCode::Char('p'));
+        assert!(control.is_paused());
+        assert!(app.is_scan_paused());
+        press(&mut app, KeyCode::Char('p'));
+        assert!(!control.is_paused());
+
+        // Cancelling a paused scan stops it rather than leaving it waiting
+        press(&mut app, KeyCode::Char('p'));
+        press(&mut app, KeyCode::Char('x'));
+        assert!(control.is_cancelled());
+        assert!(!app.is_scan_paused());
+        assert!(!app.new_scan_control().is_paused(), "next scan starts running");
+    }
+
+    #[test]
+    fn test_ctrl_l_refreshes() {
+        let mut app = app_needing_setup();
+        app.overlays.remove(Overlay::DirectorySetup);
+
+        let action = app.handle_key(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::CONTROL));
+        assert_eq!(action, Action::Refresh);
+        let action = app.handle_key(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::NONE));
+        assert_ne!(action, Action::Refresh);
+    }
+
+    #[test]
+    fn test_rebound_keys_and_command_prompt() {
+        let app = app_needing_setup();
+        let overrides = "toggle-help=h,quit=x".parse().unwrap_or_default();
+        let key_bindings = KeyBindings::resolve(&overrides);
+        assert!(key_bindings.is_ok());
+        let Ok(key_bindings) = key_bindings else {
+            return;
+        };
+        let mut app = app.with_key_bindings(key_bindings);
+        app.overlays.remove(Overlay::DirectorySetup);
+
+        press(&mut app, KeyCode::Char('?'));
+        assert_eq!(app.mode(), AppMode::Normal);
+        press(&mut app, KeyCode::Char('h'));
+        assert_eq!(app.mode(), AppMode::Help);
+        press(&mut app, KeyCode::Esc);
+
+        // Registered commands run from the prompt by ID
+        press(&mut app, KeyCode::Char(':'));
+        for c in "quit".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.mode(), AppMode::Normal);
+        assert!(app.should_quit);
+    }
 }
@@ -0,0 +1,401 @@
+//! Detection of files open in a text editor.
+//!
+//! Editors don't offer a common way to list their open buffers, but several
+//! leave files on disk while they are editing:
+//!
+//! - Vim and Neovim keep a swap file while a buffer is open: `.name.ts.swp`
+//!   beside the file, or for Neovim a `%`-encoded path under
+//!   `$XDG_STATE_HOME/nvim/swap`. Its header records whether the buffer has
+//!   unsaved changes.
+//! - Emacs creates a `.#name.ts` lock once a buffer is modified.
+//! - VS Code, including Insiders and `VSCodium`, backs up every editor with
+//!   unsaved changes for hot exit, under `Backups/<workspace>/file/` in its
+//!   user data directory, shortly after each edit. A backup starts with the
+//!   file's URI. Its command line (`code --status`) lists windows but not
+//!   their files, and files open without changes leave no trace, so VS Code
+//!   files are only detected once modified.
+//!
+//! Detection reads the filesystem, so the TUI runs [`detect_all`] on the
+//! blocking pool and keeps the results in [`EditorBuffers`].
+//!
+//! The scanner only sees what is saved on disk, so a file with unsaved
+//! changes may be about to change status. Operations that rewrite source
+//! files should check [`rewrite_conflicts`] first, so they don't overwrite
+//! or get overwritten by an editor's unsaved edits.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::time::{Duration, Instant};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use ch_core::{FxHashMap, FxHashSet};
+
+/// Offset of the "modified" flag in a Vim swap file header.
+///
+/// The last byte of the file name field in block 0 is `U` while the buffer
+/// has unsaved changes.
+const VIM_DIRTY_OFFSET: usize = 1007;
+
+/// Swap file extensions Vim uses when earlier ones are taken.
+const VIM_SWAP_EXTENSIONS: &[&str] = &["swp", "swo", "swn"];
+
+/// How often [`EditorBuffers::start_check`] lets the files be checked again.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// User data directories of VS Code builds, under the platform's config
+/// directory.
+const VSCODE_PRODUCTS: &[&str] = &["Code", "Code - Insiders", "VSCodium", "Code - OSS"];
+
+/// Most bytes read looking for the end of a VS Code backup's first line.
+const VSCODE_PREAMBLE_LIMIT: u64 = 8 * 1024;
+
+/// Whether a file is open in an editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufferState {
+    /// Open in an editor, with no unsaved changes known.
+    Open,
+
+    /// Open with unsaved changes.
+    Modified,
+}
+
+impl BufferState {
+    /// Returns a short description for display.
+    #[must_use]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Open => "open in editor",
+            Self::Modified => "unsaved changes in editor",
+        }
+    }
+}
+
+/// Returns the editor state of each of `paths` that is open in an editor.
+///
+/// Checks swap and lock files beside each file and the backups of VS Code
+/// once for all of them.
+#[must_use]
+pub fn detect_all(paths: &[Utf8PathBuf]) -> FxHashMap<Utf8PathBuf, BufferState> {
+    detect_with(paths, &vscode_modified_files(&vscode_user_dirs()))
+}
+
+/// Returns the files in `paths` with unsaved changes in an editor, in order.
+///
+/// This is the conflict guard for operations that rewrite source files:
+/// rewriting such a file either loses the unsaved edits or is undone by
+/// the editor's next save, so callers should leave these files out or stop.
+/// It checks the filesystem afresh rather than trusting [`EditorBuffers`].
+#[must_use]
+pub fn rewrite_conflicts(paths: &[Utf8PathBuf]) -> Vec<Utf8PathBuf> {
+    let states = detect_all(paths);
+    paths
+        .iter()
+        .filter(|path| states.get(*path) == Some(&BufferState::Modified))
+        .cloned()
+        .collect()
+}
+
+/// Returns the editor state of `paths`, given the files VS Code has unsaved
+/// changes to.
+fn detect_with(
+    paths: &[Utf8PathBuf],
+    vscode: &FxHashSet<Utf8PathBuf>,
+) -> FxHashMap<Utf8PathBuf, BufferState> {
+    paths
+        .iter()
+        .filter_map(|path| {
+            let in_vscode = !vscode.is_empty()
+                && path.canonicalize_utf8().is_ok_and(|absolute| vscode.contains(&absolute));
+            let state = if in_vscode { Some(BufferState::Modified) } else { detect(path) };
+            state.map(|state| (path.clone(), state))
+        })
+        .collect()
+}
+
+/// Returns whether `path` is open in an editor, based on swap and lock files.
+#[must_use]
+pub fn detect(path: &Utf8Path) -> Option<BufferState> {
+    let (dir, name) = (path.parent()?, path.file_name()?);
+
+    if dir.join(format!(".#{name}")).symlink_metadata().is_ok() {
+        return Some(BufferState::Modified);
+    }
+
+    let swap_files = VIM_SWAP_EXTENSIONS
+        .iter()
+        .map(|ext| dir.join(format!(".{name}.{ext}")))
+        .chain(nvim_swap_file(path));
+
+    let mut state = None;
+    for swap in swap_files.filter(|swap| swap.is_file()) {
+        if swap_is_modified(&swap) {
+            return Some(BufferState::Modified);
+        }
+        state = Some(BufferState::Open);
+    }
+    state
+}
+
+/// Returns the Neovim swap file `path` would have in the default directory.
+fn nvim_swap_file(path: &Utf8Path) -> Option<Utf8PathBuf> {
+    let state_home = std::env::var("XDG_STATE_HOME")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+        .map(Utf8PathBuf::from)
+        .or_else(|| {
+            std::env::var("HOME")
+                .ok()
+                .map(|home| Utf8PathBuf::from(home).join(".local/state"))
+        })?;
+    let absolute = path.canonicalize_utf8().ok()?;
+
+    Some(
+        state_home
+            .join("nvim/swap")
+            .join(format!("{}.swp", absolute.as_str().replace('/', "%"))),
+    )
+}
+
+/// Returns `true` if a Vim swap file records unsaved changes.
+fn swap_is_modified(swap: &Utf8Path) -> bool {
+    let mut header = [0_u8; VIM_DIRTY_OFFSET + 1];
+    File::open(swap)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .is_ok_and(|()| header[VIM_DIRTY_OFFSET] == b'U')
+}
+
+/// Returns the user data directories VS Code builds may use.
+fn vscode_user_dirs() -> Vec<Utf8PathBuf> {
+    let env_dir = |name| {
+        std::env::var(name)
+            .ok()
+            .filter(|dir: &String| !dir.is_empty())
+            .map(Utf8PathBuf::from)
+    };
+    let config = if cfg!(windows) {
+        env_dir("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        env_dir("HOME").map(|home| home.join("Library/Application Support"))
+    } else {
+        env_dir("XDG_CONFIG_HOME").or_else(|| env_dir("HOME").map(|home| home.join(".config")))
+    };
+
+    let portable = env_dir("VSCODE_PORTABLE").map(|dir| dir.join("user-data"));
+    portable
+        .into_iter()
+        .chain(config.into_iter().flat_map(|config| {
+            VSCODE_PRODUCTS.iter().map(move |product| config.join(product))
+        }))
+        .collect()
+}
+
+/// Returns the absolute paths of the files VS Code holds unsaved changes
+/// to, from the backups in `user_dirs`.
+fn vscode_modified_files(user_dirs: &[Utf8PathBuf]) -> FxHashSet<Utf8PathBuf> {
+    user_dirs
+        .iter()
+        .filter_map(|dir| dir.join("Backups").read_dir_utf8().ok())
+        .flatten()
+        .flatten()
+        .filter_map(|workspace| workspace.path().join("file").read_dir_utf8().ok())
+        .flatten()
+        .flatten()
+        .filter_map(|backup| vscode_backup_source(backup.path()))
+        .map(|path| path.canonicalize_utf8().unwrap_or(path))
+        .collect()
+}
+
+/// Returns the file a VS Code backup holds unsaved changes to.
+///
+/// A backup starts with the file's URI, optionally followed by a space and
+/// JSON metadata, on its own line.
+fn vscode_backup_source(backup: &Utf8Path) -> Option<Utf8PathBuf> {
+    let file = File::open(backup).ok()?;
+    let mut preamble = Vec::new();
+    BufReader::new(file.take(VSCODE_PREAMBLE_LIMIT))
+        .read_until(b'\n', &mut preamble)
+        .ok()?;
+    let line = std::str::from_utf8(&preamble).ok()?;
+    file_uri_path(line.split([' ', '\n']).next()?)
+}
+
+/// Returns the local path a `file:` URI names.
+///
+/// URIs naming another host, or with another scheme, have no local path.
+fn file_uri_path(uri: &str) -> Option<Utf8PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+    if !path.starts_with('/') {
+        return None;
+    }
+    let path = percent_encoding::percent_decode_str(path).decode_utf8().ok()?;
+
+    // Windows paths come as `/c:/...`
+    let bytes = path.as_bytes();
+    let drive = bytes.len() > 2 && bytes[1].is_ascii_alphabetic() && bytes[2] == b':';
+    Some(Utf8PathBuf::from(if drive { &path[1..] } else { &path[..] }))
+}
+
+/// Editor state of recently checked files.
+///
+/// The TUI checks the rows on screen in the background: it asks
+/// [`start_check`](Self::start_check) whether a check is due, runs
+/// [`detect_all`] off the event loop and hands the result to
+/// [`finish_check`](Self::finish_check).
+#[derive(Debug, Default)]
+pub struct EditorBuffers {
+    /// Files found open, with their state.
+    states: FxHashMap<Utf8PathBuf, BufferState>,
+
+    /// When the last check started.
+    checked_at: Option<Instant>,
+
+    /// Whether a check is running.
+    checking: bool,
+}
+
+impl EditorBuffers {
+    /// Returns the state of `path`, if it was open when last checked.
+    #[must_use]
+    pub fn get(&self, path: &Utf8Path) -> Option<BufferState> {
+        self.states.get(path).copied()
+    }
+
+    /// Starts a check if one is due: at most once per second, and never
+    /// while the last one is still running.
+    ///
+    /// Returns `true` if the caller should check the files now.
+    pub fn start_check(&mut self) -> bool {
+        let recent = self
+            .checked_at
+            .is_some_and(|checked| checked.elapsed() < REFRESH_INTERVAL);
+        if self.checking || recent {
+            return false;
+        }
+        self.checked_at = Some(Instant::now());
+        self.checking = true;
+        true
+    }
+
+    /// Stores the result of a check begun with
+    /// [`start_check`](Self::start_check).
+    ///
+    /// Returns `true` if the set of open files changed.
+    pub fn finish_check(&mut self, states: FxHashMap<Utf8PathBuf, BufferState>) -> bool {
+        self.checking = false;
+        let changed = states != self.states;
+        self.states = states;
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a file named `name` in a temporary directory, returning both.
+    ///
+    /// Fails the test if the file can't be created, rather than letting it
+    /// pass without running.
+    #[allow(clippy::expect_used)] // a test can't run without its fixture
+    fn temp_file(name: &str) -> (tempfile::TempDir, Utf8PathBuf) {
+        let dir = tempfile::TempDir::new().expect("failed to create a temporary directory");
+        let path = Utf8PathBuf::from_path_buf(dir.path().join(name))
+            .expect("temporary directory path is not UTF-8");
+        assert!(std::fs::write(&path, "export class Foo {}\n").is_ok());
+        (dir, path)
+    }
+
+    #[test]
+    fn test_detect_vim_swap_file() {
+        let (_dir, path) = temp_file("vim.ts");
+        let swap = path.with_file_name(".vim.ts.swp");
+        assert_eq!(detect(&path), None);
+
+        let mut header = vec![0_u8; 4096];
+        assert!(std::fs::write(&swap, &header).is_ok());
+        assert_eq!(detect(&path), Some(BufferState::Open));
+
+        header[VIM_DIRTY_OFFSET] = b'U';
+        assert!(std::fs::write(&swap, &header).is_ok());
+        assert_eq!(detect(&path), Some(BufferState::Modified));
+    }
+
+    #[test]
+    fn test_file_uri_path() {
+        let path = |uri| file_uri_path(uri).map(Utf8PathBuf::into_string);
+        assert_eq!(path("file:///home/dev/app/job.ts"), Some("/home/dev/app/job.ts".into()));
+        assert_eq!(path("file:///home/dev/my%20app/a.ts"), Some("/home/dev/my app/a.ts".into()));
+        assert_eq!(path("file:///c%3A/src/job.ts"), Some("c:/src/job.ts".into()));
+        assert_eq!(path("file://server/share/job.ts"), None);
+        assert_eq!(path("untitled:Untitled-1"), None);
+        assert_eq!(path("vscode-remote://ssh-remote%2Bbox/home/dev/job.ts"), None);
+    }
+
+    #[test]
+    fn test_detect_vscode_backup() {
+        let (dir, path) = temp_file("vscode.ts");
+        let (_other_dir, other) = temp_file("other.ts");
+        let user_dir = Utf8PathBuf::from_path_buf(dir.path().join("Code")).unwrap_or_default();
+        let backups = user_dir.join("Backups/1712345678901/file");
+        assert!(std::fs::create_dir_all(&backups).is_ok());
+        let paths = [path.clone(), other.clone()];
+        assert!(vscode_modified_files(std::slice::from_ref(&user_dir)).is_empty());
+
+        // A backup with metadata and one without, and an untitled editor
+        let uri = |path: &Utf8Path| format!("file://{path}");
+        let with_meta = format!(
+            "{} {{\"mtime\":1,\"size\":21}}\nexport class Foo {{ x }}\n",
+            uri(&path)
+        );
+        assert!(std::fs::write(backups.join("1a2b3c"), with_meta).is_ok());
+        let untitled = user_dir.join("Backups/1712345678901/untitled");
+        assert!(std::fs::create_dir_all(&untitled).is_ok());
+        assert!(std::fs::write(untitled.join("4d5e6f"), "untitled:Untitled-1\nfoo").is_ok());
+
+        let vscode = vscode_modified_files(std::slice::from_ref(&user_dir));
+        assert_eq!(vscode.len(), 1);
+        let states = detect_with(&paths, &vscode);
+        assert_eq!(states.get(&path), Some(&BufferState::Modified));
+        assert_eq!(states.get(&other), None);
+
+        assert!(std::fs::write(backups.join("7a8b9c"), format!("{}\n", uri(&other))).is_ok());
+        let vscode = vscode_modified_files(std::slice::from_ref(&user_dir));
+        assert_eq!(detect_with(&paths, &vscode).len(), 2);
+    }
+
+    #[test]
+    fn test_rewrite_conflicts() {
+        let (_dir, path) = temp_file("guarded.ts");
+        let (_other_dir, other) = temp_file("free.ts");
+        assert!(std::fs::write(path.with_file_name(".#guarded.ts"), "user@host.1234").is_ok());
+        assert!(std::fs::write(other.with_file_name(".free.ts.swp"), [0_u8; 4096]).is_ok());
+
+        // Files only open in an editor can still be rewritten
+        assert_eq!(rewrite_conflicts(&[other.clone(), path.clone()]), [path]);
+        assert!(rewrite_conflicts(&[other]).is_empty());
+    }
+
+    #[test]
+    fn test_editor_buffers_checks() {
+        let mut buffers = EditorBuffers::default();
+        let path = Utf8PathBuf::from("src/app/job.ts");
+        assert!(buffers.start_check());
+        // Not again while the check runs, or within the interval
+        assert!(!buffers.start_check());
+
+        let mut states = FxHashMap::default();
+        states.insert(path.clone(), BufferState::Open);
+        assert!(buffers.finish_check(states.clone()));
+        assert_eq!(buffers.get(&path), Some(BufferState::Open));
+        assert!(!buffers.start_check());
+        assert!(!buffers.finish_check(states));
+    }
+
+    #[test]
+    fn test_detect_emacs_lock() {
+        let (_dir, path) = temp_file("emacs.ts");
+        let lock = path.with_file_name(".#emacs.ts");
+        assert!(std::fs::write(&lock, "user@host.1234").is_ok());
+        assert_eq!(detect(&path), Some(BufferState::Modified));
+    }
+}
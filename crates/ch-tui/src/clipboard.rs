@@ -0,0 +1,60 @@
+//! Clipboard access via the OSC 52 terminal escape sequence.
+//!
+//! OSC 52 lets the terminal emulator set the system clipboard, which also
+//! works over SSH and inside tmux without a platform clipboard library.
+
+use std::io::{self, Write};
+
+/// Base64 alphabet used to encode the OSC 52 payload.
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Copies text to the system clipboard.
+///
+/// # Errors
+///
+/// Returns an error if writing to the terminal fails.
+pub fn copy(text: &str) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    write!(stdout, "\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))?;
+    stdout.flush()
+}
+
+/// Encodes bytes as padded standard base64.
+fn base64_encode(input: &[u8]) -> String {
+    let mut out = String::with_capacity(input.len().div_ceil(3) * 4);
+    for chunk in input.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = chunk.get(1).copied().unwrap_or(0);
+        let b2 = chunk.get(2).copied().unwrap_or(0);
+        let n = (u32::from(b0) << 16) | (u32::from(b1) << 8) | u32::from(b2);
+
+        out.push(char::from(BASE64_ALPHABET[(n >> 18) as usize & 0x3f]));
+        out.push(char::from(BASE64_ALPHABET[(n >> 12) as usize & 0x3f]));
+        out.push(if chunk.len() > 1 {
+            char::from(BASE64_ALPHABET[(n >> 6) as usize & 0x3f])
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            char::from(BASE64_ALPHABET[n as usize & 0x3f])
+        } else {
+            '='
+        });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_encode() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"sort=priority"), "c29ydD1wcmlvcml0eQ==");
+    }
+}
@@ -0,0 +1,540 @@
+//! Registry of commands that can be bound to keys.
+//!
+//! Every normal-mode key press goes through the [`COMMANDS`] registry: each
+//! [`Command`] has a stable ID, a description for the help panel, default
+//! keys, a check for when it can't do anything (shown as unavailable in
+//! help), and a handler producing the [`Action`] to perform. Adding a
+//! command here makes it bindable, listed in help, and runnable from the
+//! `:` prompt by its ID.
+//!
+//! [`KeyBindings`] resolves the keys for each command from the defaults and
+//! the user's [`KeyOverrides`] (`--keys "rescan=R F5,quit=ctrl+q"`).
+//!
+//! Keys handled inside overlays (typing a filter, scrolling a message) are
+//! not part of the registry, nor are the keys that work over any overlay:
+//! Ctrl+C, F1, F12 for the debug overlay, and Esc to close the overlay.
+
+use std::fmt;
+use std::str::FromStr;
+
+use ch_core::KeyOverrides;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::action::Action;
+use crate::app::App;
+use crate::editor;
+use crate::error::TuiError;
+
+/// A command that can be bound to keys and run from the `:` prompt.
+#[derive(Debug)]
+pub struct Command {
+    /// Stable identifier used in key bindings and at the `:` prompt.
+    pub id: &'static str,
+    /// Description shown in the help panel.
+    pub description: &'static str,
+    /// Keys bound to the command unless overridden.
+    pub default_keys: &'static [&'static str],
+    /// Returns why the command can't do anything in the current state.
+    unavailable: fn(&App) -> Option<&'static str>,
+    /// Produces the action to perform.
+    handler: fn(&App) -> Action,
+}
+
+impl Command {
+    /// Returns the action to perform for this command in the current state.
+    #[must_use]
+    pub fn action(&self, app: &App) -> Action {
+        (self.handler)(app)
+    }
+
+    /// Returns why the command can't do anything in the current state, e.g.
+    /// `no file selected`, or `None` if it can.
+    #[must_use]
+    pub fn unavailable_reason(&self, app: &App) -> Option<&'static str> {
+        (self.unavailable)(app)
+    }
+}
+
+/// Availability of commands that work in any state.
+const fn available(_: &App) -> Option<&'static str> {
+    None
+}
+
+/// All commands, in the order they are listed in help.
+pub static COMMANDS: &[Command] = &[
+    // Navigation
+    Command {
+        id: "next-item",
+        description: "Next file",
+        default_keys: &["j", "down"],
+        unavailable: available,
+        handler: |_| Action::NextItem,
+    },
+    Command {
+        id: "previous-item",
+        description: "Previous file",
+        default_keys: &["k", "up"],
+        unavailable: available,
+        handler: |_| Action::PreviousItem,
+    },
+    Command {
+        id: "first-item",
+        description: "Go to first file",
+        default_keys: &["g", "home"],
+        unavailable: available,
+        handler: |_| Action::FirstItem,
+    },
+    Command {
+        id: "last-item",
+        description: "Go to last file",
+        default_keys: &["G", "end"],
+        unavailable: available,
+        handler: |_| Action::LastItem,
+    },
+    Command {
+        id: "page-down",
+        description: "Page down",
+        default_keys: &["pgdn"],
+        unavailable: available,
+        handler: |_| Action::PageDown,
+    },
+    Command {
+        id: "page-up",
+        description: "Page up",
+        default_keys: &["pgup"],
+        unavailable: available,
+        handler: |_| Action::PageUp,
+    },
+    Command {
+        id: "toggle-focus",
+        description: "Toggle list/details focus",
+        default_keys: &["tab"],
+        unavailable: available,
+        handler: |_| Action::ToggleFocus,
+    },
+    // Filtering
+    Command {
+        id: "filter",
+        description: "Start filter mode",
+        default_keys: &["/"],
+        unavailable: available,
+        handler: |_| Action::EnterFilterMode,
+    },
+    Command {
+        id: "clear-filter",
+        description: "Clear filter",
+        default_keys: &["esc"],
+        unavailable: |app| (!app.filter.is_active()).then_some("no filter active"),
+        handler: |app| {
+            if app.filter.is_active() {
+                Action::ClearFilter
+            } else {
+                Action::None
+            }
+        },
+    },
+    Command {
+        id: "cycle-status-filter",
+        description: "Cycle status filter",
+        default_keys: &["f"],
+        unavailable: available,
+        handler: |_| Action::CycleStatusFilter,
+    },
+    Command {
+        id: "toggle-dead-imports",
+        description: "Toggle dead-import filter",
+        default_keys: &["u"],
+        unavailable: available,
+        handler: |_| Action::ToggleDeadImportsFilter,
+    },
+    Command {
+        id: "cycle-sort",
+        description: "Cycle sort order",
+        default_keys: &["s"],
+        unavailable: available,
+        handler: |_| Action::CycleSort,
+    },
+    Command {
+        id: "copy-view",
+        description: "Copy view string",
+        default_keys: &["y"],
+        unavailable: available,
+        handler: |_| Action::CopyView,
+    },
+    Command {
+        id: "command",
+        description: "Run a command by name",
+        default_keys: &[":"],
+        unavailable: available,
+        handler: |_| Action::EnterCommandMode,
+    },
+    // Actions
+    Command {
+        id: "rescan",
+        description: "Rescan all files",
+        default_keys: &["r"],
+        unavailable: available,
+        handler: |_| Action::Rescan,
+    },
+    Command {
+        id: "rescan-directory",
+        description: "Rescan selected file's directory",
+        default_keys: &["R"],
+        unavailable: |app| {
+            if app.scan_state.is_scanning() {
+                Some("scan running")
+            } else {
+                app.selected_file().is_none().then_some("no file selected")
+            }
+        },
+        handler: |_| Action::RescanDirectory,
+    },
+    Command {
+        id: "cancel-scan",
+        description: "Cancel running scan",
+        default_keys: &["x"],
+        unavailable: |app| (!app.scan_state.is_scanning()).then_some("no scan running"),
+        handler: |_| Action::CancelScan,
+    },
+    Command {
+        id: "pause-scan",
+        description: "Pause or resume running scan",
+        default_keys: &["p"],
+        unavailable: |app| (!app.scan_state.is_scanning()).then_some("no scan running"),
+        handler: |_| Action::TogglePauseScan,
+    },
+    Command {
+        id: "open-in-editor",
+        description: "Open file in editor",
+        default_keys: &["o"],
+        unavailable: |app| {
+            if app.selected_file().is_none() {
+                Some("no file selected")
+            } else {
+                (!editor::is_available(&app.config)).then_some("no editor configured")
+            }
+        },
+        handler: |_| Action::OpenInEditor,
+    },
+    Command {
+        id: "directory-setup",
+        description: "Configure directories",
+        default_keys: &["d"],
+        unavailable: available,
+        handler: |_| Action::EnterDirectorySetup,
+    },
+    Command {
+        id: "show-message",
+        description: "Expand status message",
+        default_keys: &["m"],
+        unavailable: available,
+        handler: |_| Action::ShowMessage,
+    },
+    Command {
+        id: "refresh",
+        description: "Redraw the screen",
+        default_keys: &["ctrl+l"],
+        unavailable: available,
+        handler: |_| Action::Refresh,
+    },
+    Command {
+        id: "toggle-help",
+        description: "Toggle help panel",
+        default_keys: &["?"],
+        unavailable: available,
+        handler: |_| Action::ToggleHelp,
+    },
+    Command {
+        id: "quit",
+        description: "Quit",
+        default_keys: &["q"],
+        unavailable: available,
+        handler: |_| Action::Quit,
+    },
+];
+
+/// Returns the command with the given ID.
+#[must_use]
+pub fn find(id: &str) -> Option<&'static Command> {
+    COMMANDS.iter().find(|command| command.id == id)
+}
+
+/// A key with its modifiers, as written in key bindings.
+///
+/// Parses names like `j`, `G`, `ctrl+r`, `pgdn` or `f5`; see
+/// [`KeyChord::from_str`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    /// The key.
+    pub code: KeyCode,
+    /// Control and Alt modifiers (Shift is part of the character).
+    pub modifiers: KeyModifiers,
+}
+
+impl From<KeyEvent> for KeyChord {
+    fn from(key: KeyEvent) -> Self {
+        let modifiers = match key.code {
+            KeyCode::Char(_) => key.modifiers - KeyModifiers::SHIFT,
+            _ => key.modifiers,
+        };
+        Self {
+            code: key.code,
+            modifiers,
+        }
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = TuiError;
+
+    /// Parses a key such as `j`, `G`, `?`, `ctrl+r`, `alt+down`, `pgdn`,
+    /// `f5`, `space` or `esc`. Names and modifiers are case-insensitive;
+    /// single characters are not.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || TuiError::config(format!("invalid key: {s}"));
+
+        let mut modifiers = KeyModifiers::NONE;
+        let mut key = s.trim();
+        // A trailing `+` is the key itself, as in `ctrl++`
+        while let Some((modifier, rest)) =
+            key.split_once('+').filter(|(_, rest)| !rest.is_empty())
+        {
+            modifiers |= match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                _ => return Err(invalid()),
+            };
+            key = rest;
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_ascii_lowercase().as_str() {
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pgup" | "pageup" => KeyCode::PageUp,
+                "pgdn" | "pagedown" => KeyCode::PageDown,
+                "tab" => KeyCode::Tab,
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                name => name
+                    .strip_prefix('f')
+                    .and_then(|n| n.parse().ok())
+                    .filter(|n| (1..=12).contains(n))
+                    .map(KeyCode::F)
+                    .ok_or_else(invalid)?,
+            },
+        };
+
+        Ok(Self { code, modifiers })
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            f.write_str("Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            f.write_str("Alt+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::Up => f.write_str("↑"),
+            KeyCode::Down => f.write_str("↓"),
+            KeyCode::Left => f.write_str("←"),
+            KeyCode::Right => f.write_str("→"),
+            KeyCode::PageUp => f.write_str("PgUp"),
+            KeyCode::PageDown => f.write_str("PgDn"),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+/// The keys bound to each command.
+#[derive(Debug)]
+pub struct KeyBindings {
+    /// Every command with its keys, in [`COMMANDS`] order.
+    bindings: Vec<(&'static Command, Vec<KeyChord>)>,
+}
+
+impl KeyBindings {
+    /// Resolves key bindings from the defaults and user overrides.
+    ///
+    /// An overridden command gets exactly the given keys, and those keys are
+    /// removed from any command that had them by default.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TuiError::Config`] if an override names an unknown command
+    /// or key, or binds the same key to two commands.
+    pub fn resolve(overrides: &KeyOverrides) -> Result<Self, TuiError> {
+        let mut claimed: Vec<(KeyChord, &str)> = Vec::new();
+        for (id, keys) in overrides.iter() {
+            if find(id).is_none() {
+                return Err(TuiError::config(format!("unknown command in key bindings: {id}")));
+            }
+            for key in keys {
+                let chord: KeyChord = key.parse()?;
+                if let Some((_, other)) = claimed.iter().find(|(claimed, _)| *claimed == chord) {
+                    return Err(TuiError::config(format!(
+                        "key {key} is bound to both {other} and {id}"
+                    )));
+                }
+                claimed.push((chord, id));
+            }
+        }
+
+        let mut bindings = Self::default();
+        for (command, keys) in &mut bindings.bindings {
+            match overrides.get(command.id) {
+                Some(overridden) => {
+                    *keys = overridden.iter().map(|key| key.parse()).collect::<Result<_, _>>()?;
+                }
+                None => keys.retain(|key| !claimed.iter().any(|(chord, _)| chord == key)),
+            }
+        }
+
+        Ok(bindings)
+    }
+
+    /// Returns the command bound to a key press.
+    #[must_use]
+    pub fn command_for(&self, key: KeyEvent) -> Option<&'static Command> {
+        let chord = KeyChord::from(key);
+        self.bindings
+            .iter()
+            .find(|(_, keys)| keys.contains(&chord))
+            .map(|(command, _)| *command)
+    }
+
+    /// Returns an iterator over every command with its keys, in help order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static Command, &[KeyChord])> {
+        self.bindings.iter().map(|(command, keys)| (*command, keys.as_slice()))
+    }
+
+    /// Returns `true` if a command's keys differ from its defaults, because
+    /// it was overridden or another command took one of its keys.
+    #[must_use]
+    pub fn is_customized(&self, command: &Command) -> bool {
+        self.bindings
+            .iter()
+            .find(|(bound, _)| bound.id == command.id)
+            .is_some_and(|(_, keys)| {
+                let defaults: Vec<KeyChord> =
+                    command.default_keys.iter().filter_map(|key| key.parse().ok()).collect();
+                *keys != defaults
+            })
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            bindings: COMMANDS
+                .iter()
+                .map(|command| {
+                    let keys = command
+                        .default_keys
+                        .iter()
+                        .filter_map(|key| key.parse().ok())
+                        .collect();
+                    (command, keys)
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    fn bound_id(bindings: &KeyBindings, code: KeyCode, modifiers: KeyModifiers) -> Option<&str> {
+        bindings.command_for(key(code, modifiers)).map(|command| command.id)
+    }
+
+    #[test]
+    fn test_default_keys_parse_and_are_unique() {
+        let mut seen = Vec::new();
+        for command in COMMANDS {
+            assert!(find(command.id).is_some_and(|found| std::ptr::eq(found, command)));
+            for key in command.default_keys {
+                let chord = key.parse::<KeyChord>();
+                assert!(chord.as_ref().is_ok_and(|chord| !seen.contains(chord)), "{key}");
+                seen.extend(chord);
+            }
+        }
+    }
+
+    #[test]
+    fn test_key_chord_parse_and_display() {
+        let parse = |s: &str| s.parse::<KeyChord>().map(|chord| chord.to_string()).ok();
+        assert_eq!(parse("G").as_deref(), Some("G"));
+        assert_eq!(parse("ctrl+r").as_deref(), Some("Ctrl+r"));
+        assert_eq!(parse("Alt+Down").as_deref(), Some("Alt+↓"));
+        assert_eq!(parse("pgdn").as_deref(), Some("PgDn"));
+        assert_eq!(parse("F5").as_deref(), Some("F5"));
+        assert_eq!(parse("+").as_deref(), Some("+"));
+        assert_eq!(parse("ctrl++").as_deref(), Some("Ctrl++"));
+        assert!(parse("f13").is_none());
+        assert!(parse("hyper+x").is_none());
+        assert!(parse("enterr").is_none());
+    }
+
+    #[test]
+    fn test_key_bindings_defaults() {
+        let bindings = KeyBindings::default();
+        assert_eq!(bound_id(&bindings, KeyCode::Char('j'), KeyModifiers::NONE), Some("next-item"));
+        assert_eq!(bound_id(&bindings, KeyCode::Down, KeyModifiers::NONE), Some("next-item"));
+        assert_eq!(bound_id(&bindings, KeyCode::Char('G'), KeyModifiers::SHIFT), Some("last-item"));
+        assert_eq!(bound_id(&bindings, KeyCode::Char('j'), KeyModifiers::CONTROL), None);
+    }
+
+    #[test]
+    fn test_key_bindings_overrides() {
+        let overrides: KeyOverrides = "rescan=R ctrl+r,quit=r,copy-view=none"
+            .parse()
+            .unwrap_or_default();
+        let bindings = KeyBindings::resolve(&overrides);
+        assert!(bindings.is_ok());
+        let bindings = bindings.unwrap_or_default();
+
+        assert_eq!(bound_id(&bindings, KeyCode::Char('R'), KeyModifiers::SHIFT), Some("rescan"));
+        assert_eq!(bound_id(&bindings, KeyCode::Char('r'), KeyModifiers::CONTROL), Some("rescan"));
+        assert_eq!(bound_id(&bindings, KeyCode::Char('r'), KeyModifiers::NONE), Some("quit"));
+        assert_eq!(bound_id(&bindings, KeyCode::Char('q'), KeyModifiers::NONE), None);
+        assert_eq!(bound_id(&bindings, KeyCode::Char('y'), KeyModifiers::NONE), None);
+
+        let customized = |id| find(id).is_some_and(|command| bindings.is_customized(command));
+        assert!(customized("rescan"));
+        assert!(customized("copy-view"));
+        // Lost R to rescan without being overridden itself
+        assert!(customized("rescan-directory"));
+        assert!(!customized("next-item"));
+    }
+
+    #[test]
+    fn test_key_bindings_invalid_overrides() {
+        let resolve = |s: &str| KeyBindings::resolve(&s.parse().unwrap_or_default());
+        assert!(resolve("rescan=R").is_ok());
+        assert!(resolve("launch-rockets=x").is_err());
+        assert!(resolve("rescan=hyper+r").is_err());
+        assert!(resolve("rescan=x,quit=x").is_err());
+    }
+}
@@ -0,0 +1,72 @@
+//! Command input component.
+//!
+//! Displays a `:` prompt overlay for entering commands such as `load-view`.
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Widget};
+
+use crate::theme::Theme;
+
+/// A command prompt overlay widget.
+///
+/// Displays a centered text input prefixed with `:`.
+/// This is shown as a modal overlay when command mode is active.
+pub struct CommandInput<'a> {
+    /// The current command text.
+    text: &'a str,
+    /// Theme for styling.
+    theme: &'a Theme,
+}
+
+impl<'a> CommandInput<'a> {
+    /// Creates a new command input widget.
+    #[must_use]
+    pub const fn new(text: &'a str, theme: &'a Theme) -> Self {
+        Self { text, theme }
+    }
+}
+
+impl Widget for &CommandInput<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // Clear the area first for overlay effect
+        Clear.render(area, buf);
+
+        let input_content = Line::from(vec![
+            Span::styled(":", Style::default().fg(self.theme.accent)),
+            Span::styled(self.text, self.theme.base_style()),
+            Span::styled("▌", Style::default().fg(self.theme.accent)),
+        ]);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(self.theme.focused_border_style)
+            .title(Span::styled(
+                " Command (Esc to cancel, Enter to run) ",
+                Style::default()
+                    .fg(self.theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .style(Style::default().bg(Color::Rgb(30, 30, 40)));
+
+        let paragraph = Paragraph::new(input_content)
+            .block(block)
+            .alignment(ratatui::layout::Alignment::Left);
+
+        paragraph.render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_input_new() {
+        let theme = Theme::dark();
+        let input = CommandInput::new("load-view sort=priority", &theme);
+        assert_eq!(input.text, "load-view sort=priority");
+    }
+}
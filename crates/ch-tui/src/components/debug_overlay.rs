@@ -0,0 +1,148 @@
+//! Debug overlay component.
+//!
+//! Shows runtime health figures (thread pool activity, channel depths,
+//! tokio runtime tasks, frame render times and scan cache age) for
+//! diagnosing a sluggish UI.
+
+use std::time::Duration;
+
+use ch_scanner::Scanner;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Widget};
+
+use crate::debug::{DebugStats, RuntimeHealth};
+use crate::theme::Theme;
+
+/// The debug overlay, toggled with F12.
+pub struct DebugOverlay<'a> {
+    /// Figures collected by the event loop.
+    stats: &'a DebugStats,
+    /// The scanner, for thread pool and cache figures.
+    scanner: &'a Scanner,
+    /// Theme for styling.
+    theme: &'a Theme,
+}
+
+impl<'a> DebugOverlay<'a> {
+    /// Creates a new debug overlay.
+    #[must_use]
+    pub const fn new(stats: &'a DebugStats, scanner: &'a Scanner, theme: &'a Theme) -> Self {
+        Self {
+            stats,
+            scanner,
+            theme,
+        }
+    }
+
+    /// Builds the overlay lines, grouped under section headings.
+    fn build_lines(&self) -> Vec<Line<'static>> {
+        let stats = self.stats;
+        let runtime = |value: fn(&RuntimeHealth) -> usize| {
+            stats.runtime.as_ref().map_or_else(|| "n/a".to_owned(), |r| value(r).to_string())
+        };
+        let cache_age = stats.cache_rebuilt_at.map_or_else(
+            || "not yet".to_owned(),
+            |at| format!("{} ago", format_duration(at.elapsed())),
+        );
+        let memory_mode = if self.scanner.is_low_memory() {
+            "low-memory"
+        } else {
+            "normal"
+        };
+
+        let mut lines = Vec::new();
+        self.section(&mut lines, "Scanner thread pool");
+        self.row(&mut lines, "Threads", self.scanner.thread_pool_size().to_string());
+        self.row(&mut lines, "Busy", self.scanner.busy_workers().to_string());
+        self.row(&mut lines, "Memory mode", memory_mode.to_owned());
+        self.section(&mut lines, "Channels");
+        self.row(&mut lines, "Scan updates", stats.scan_queue.to_string());
+        self.row(&mut lines, "Watcher events", stats.watcher_queue.to_string());
+        self.section(&mut lines, "Tokio runtime");
+        self.row(&mut lines, "Workers", runtime(|r| r.workers));
+        self.row(&mut lines, "Alive tasks", runtime(|r| r.alive_tasks));
+        self.row(&mut lines, "Global queue", runtime(|r| r.global_queue_depth));
+        self.section(&mut lines, "Rendering");
+        self.row(&mut lines, "Last frame", format_frame(stats.last_frame()));
+        self.row(&mut lines, "Average frame", format_frame(stats.average_frame()));
+        self.row(&mut lines, "Slowest frame", format_frame(stats.slowest_frame()));
+        self.section(&mut lines, "Scan cache");
+        self.row(&mut lines, "Files", self.scanner.cache().len().to_string());
+        self.row(&mut lines, "Rebuilt", cache_age);
+        lines
+    }
+
+    /// Appends a section heading, after a blank line unless it is the first.
+    fn section(&self, lines: &mut Vec<Line<'static>>, title: &'static str) {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            title,
+            Style::default()
+                .fg(self.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+
+    /// Appends a label and its value.
+    fn row(&self, lines: &mut Vec<Line<'static>>, label: &'static str, value: String) {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {label:<16}"), Style::default().fg(Color::DarkGray)),
+            Span::styled(value, self.theme.base_style()),
+        ]));
+    }
+}
+
+/// Formats a frame render time in milliseconds, or `n/a` before the first.
+fn format_frame(duration: Option<Duration>) -> String {
+    duration.map_or_else(
+        || "n/a".to_owned(),
+        |duration| format!("{:.1} ms", duration.as_secs_f64() * 1000.0),
+    )
+}
+
+/// Formats an elapsed time in whole seconds, minutes or hours.
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m", secs / 60),
+        _ => format!("{}h", secs / 3600),
+    }
+}
+
+impl Widget for &DebugOverlay<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // Clear the background
+        Clear.render(area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(self.theme.focused_border_style)
+            .title(Span::styled(" Debug (F12 to close) ", self.theme.header_style));
+
+        Paragraph::new(self.build_lines()).block(block).render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(5)), "5s");
+        assert_eq!(format_duration(Duration::from_secs(125)), "2m");
+        assert_eq!(format_duration(Duration::from_secs(7200)), "2h");
+    }
+
+    #[test]
+    fn test_format_frame() {
+        assert_eq!(format_frame(None), "n/a");
+        assert_eq!(format_frame(Some(Duration::from_micros(2500))), "2.5 ms");
+    }
+}
@@ -3,7 +3,8 @@
 //! Displays detailed information about the selected file, including
 //! its imports and model references.
 
-use ch_core::FileInfo;
+use ch_core::{FileInfo, ModelRegistry, StatusLabels};
+use ch_scanner::import_recipe;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
@@ -13,7 +14,8 @@ use ratatui::widgets::{
     Widget, Wrap,
 };
 
-use crate::app::DetailPaneState;
+use crate::app::{DetailPaneState, WatchCoverage};
+use crate::buffers::BufferState;
 use crate::theme::Theme;
 
 /// A stateful detail pane widget.
@@ -21,8 +23,12 @@ use crate::theme::Theme;
 /// Displays detailed information about the selected file:
 /// - File path and name
 /// - Migration status
-/// - Legacy imports list
+/// - Editor state, if the file is open in an editor
+/// - Whether the file watcher covers the file
+/// - Whether the file's details were compacted to save memory
+/// - Legacy imports list, each with a migration recipe
 /// - Migrated imports list
+/// - Ignored imports list (dimmed)
 /// - Model references
 ///
 /// Uses [`StatefulWidget`] to maintain scroll state.
@@ -31,6 +37,16 @@ pub struct DetailPane<'a> {
     file: Option<&'a FileInfo>,
     /// Whether this widget has focus.
     focused: bool,
+    /// Display labels for statuses.
+    labels: &'a StatusLabels,
+    /// Whether the file is open in an editor.
+    buffer_state: Option<BufferState>,
+    /// Whether the file watcher covers the file.
+    watch_coverage: Option<WatchCoverage<'a>>,
+    /// Whether the cache dropped the file's imports to save memory.
+    compacted: bool,
+    /// Model registry used for migration recipes.
+    registry: Option<&'a ModelRegistry>,
     /// Theme for styling.
     theme: &'a Theme,
 }
@@ -42,10 +58,52 @@ impl<'a> DetailPane<'a> {
         Self {
             file,
             focused,
+            labels: StatusLabels::standard(),
+            buffer_state: None,
+            watch_coverage: None,
+            compacted: false,
+            registry: None,
             theme,
         }
     }
 
+    /// Sets whether the file is open in an editor.
+    #[must_use]
+    pub const fn with_buffer_state(mut self, state: Option<BufferState>) -> Self {
+        self.buffer_state = state;
+        self
+    }
+
+    /// Sets whether the file watcher covers the file.
+    #[must_use]
+    pub const fn with_watch_coverage(mut self, coverage: Option<WatchCoverage<'a>>) -> Self {
+        self.watch_coverage = coverage;
+        self
+    }
+
+    /// Sets whether the cache dropped the file's imports and model
+    /// references to save memory.
+    #[must_use]
+    pub const fn with_compacted(mut self, compacted: bool) -> Self {
+        self.compacted = compacted;
+        self
+    }
+
+    /// Sets the display labels used for the file status.
+    #[must_use]
+    pub const fn with_labels(mut self, labels: &'a StatusLabels) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Sets the model registry used to suggest a migration recipe for each
+    /// legacy import.
+    #[must_use]
+    pub const fn with_registry(mut self, registry: &'a ModelRegistry) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
     /// Renders the "no selection" placeholder.
     fn render_placeholder(&self, area: Rect, buf: &mut Buffer) {
         let block = Block::default()
@@ -118,11 +176,50 @@ impl<'a> DetailPane<'a> {
         lines.push(Line::from(vec![
             Span::styled("Status: ", Style::default().fg(Color::DarkGray)),
             Span::styled(
-                file.status.label(),
+                self.labels.get(file.status),
                 self.theme.status_style(file.status),
             ),
         ]));
 
+        // Editor state
+        if let Some(state) = self.buffer_state {
+            let style = match state {
+                BufferState::Open => Style::default().fg(self.theme.accent),
+                BufferState::Modified => Style::default().fg(Color::Yellow),
+            };
+            lines.push(Line::from(vec![
+                Span::styled("Editor: ", Style::default().fg(Color::DarkGray)),
+                Span::styled(state.description(), style),
+            ]));
+        }
+
+        // Watcher coverage
+        if let Some(coverage) = self.watch_coverage {
+            let (text, style) = match coverage {
+                WatchCoverage::Watched { root } => {
+                    (format!("yes (root: {root})"), Style::default().fg(Color::Green))
+                }
+                WatchCoverage::Unwatched { root } => {
+                    (format!("no (root: {root})"), Style::default().fg(Color::Yellow))
+                }
+                WatchCoverage::Off => {
+                    ("no (file watching off)".to_owned(), self.theme.dimmed_style())
+                }
+            };
+            lines.push(Line::from(vec![
+                Span::styled("Watched: ", Style::default().fg(Color::DarkGray)),
+                Span::styled(text, style),
+            ]));
+        }
+
+        // Compacted details
+        if self.compacted {
+            lines.push(Line::from(vec![
+                Span::styled("Details: ", Style::default().fg(Color::DarkGray)),
+                Span::styled("compacted to save memory", Style::default().fg(Color::Yellow)),
+            ]));
+        }
+
         // Separator
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
@@ -130,9 +227,19 @@ impl<'a> DetailPane<'a> {
             Style::default().fg(Color::DarkGray),
         )));
 
-        // Legacy imports
+        // Legacy imports, unless a compacted file only kept its status
+        let dropped = self.compacted && file.imports.is_empty();
         let legacy_imports: Vec<_> = file.legacy_imports().collect();
-        if legacy_imports.is_empty() {
+        if dropped {
+            lines.push(Line::from(Span::styled(
+                "Imports not kept in memory;",
+                self.theme.dimmed_style(),
+            )));
+            lines.push(Line::from(Span::styled(
+                "rescan the directory to show them",
+                self.theme.dimmed_style(),
+            )));
+        } else if legacy_imports.is_empty() {
             lines.push(Line::from(Span::styled(
                 "No legacy imports",
                 self.theme.dimmed_style(),
@@ -145,13 +252,27 @@ impl<'a> DetailPane<'a> {
                     Style::default().fg(self.theme.legacy_fg),
                 ),
             ]));
+            let dead = file.dead_legacy_imports();
             for import in &legacy_imports {
                 for name in &import.names {
-                    lines.push(Line::from(vec![
+                    let mut spans = vec![
                         Span::raw("  "),
                         Span::styled("•", Style::default().fg(self.theme.legacy_fg)),
                         Span::raw(" "),
                         Span::styled(name.clone(), self.theme.base_style()),
+                    ];
+                    if dead.contains(&name.as_str()) {
+                        spans.push(Span::styled(" (unused)", self.theme.dimmed_style()));
+                    }
+                    lines.push(Line::from(spans));
+                }
+                if let Some(registry) = self.registry {
+                    lines.push(Line::from(vec![
+                        Span::raw("    "),
+                        Span::styled(
+                            format!("→ {}", import_recipe(import, registry)),
+                            self.theme.dimmed_style().add_modifier(Modifier::ITALIC),
+                        ),
                     ]));
                 }
             }
@@ -160,10 +281,12 @@ impl<'a> DetailPane<'a> {
         // Migrated imports
         let migrated_imports: Vec<_> = file.migrated_imports().collect();
         if migrated_imports.is_empty() {
-            lines.push(Line::from(Span::styled(
-                "No migrated imports",
-                self.theme.dimmed_style(),
-            )));
+            if !dropped {
+                lines.push(Line::from(Span::styled(
+                    "No migrated imports",
+                    self.theme.dimmed_style(),
+                )));
+            }
         } else {
             lines.push(Line::from(vec![
                 Span::styled("Migrated: ", Style::default().fg(Color::DarkGray)),
@@ -184,6 +307,29 @@ impl<'a> DetailPane<'a> {
             }
         }
 
+        // Ignored imports (excluded from status by configuration)
+        if !file.ignored_imports.is_empty() {
+            lines.push(Line::from(vec![
+                Span::styled("Ignored: ", Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    format!("{} imports", file.ignored_imports.len()),
+                    self.theme.dimmed_style(),
+                ),
+            ]));
+            for import in &file.ignored_imports {
+                let path = import.path.trim_matches(|c| matches!(c, '\'' | '"' | '`'));
+                for name in &import.names {
+                    lines.push(Line::from(vec![
+                        Span::raw("  "),
+                        Span::styled("•", self.theme.dimmed_style()),
+                        Span::raw(" "),
+                        Span::styled(name.clone(), self.theme.dimmed_style()),
+                        Span::styled(format!(" ({path})"), self.theme.dimmed_style()),
+                    ]));
+                }
+            }
+        }
+
         // Model references section
         if !file.model_refs.is_empty() {
             lines.push(Line::from(""));
@@ -267,3 +413,36 @@ impl StatefulWidget for &DetailPane<'_> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ch_core::{FileId, MigrationStatus};
+
+    /// Renders the pane and returns its text, one string per line.
+    fn render_lines(pane: &DetailPane<'_>) -> Vec<String> {
+        let area = Rect::new(0, 0, 60, 20);
+        let mut buf = Buffer::empty(area);
+        pane.render(area, &mut buf, &mut DetailPaneState::default());
+        (0..area.height)
+            .map(|y| (0..area.width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_detail_pane_marks_compacted_file() {
+        let theme = Theme::dark();
+        let mut file = FileInfo::new(FileId::new(1), "src/a.ts".into());
+        file.status = MigrationStatus::Legacy;
+        let contains = |lines: &[String], text: &str| lines.iter().any(|line| line.contains(text));
+
+        let lines = render_lines(&DetailPane::new(Some(&file), false, &theme).with_compacted(true));
+        assert!(contains(&lines, "compacted to save memory"));
+        assert!(contains(&lines, "Imports not kept in memory"));
+        assert!(!contains(&lines, "No legacy imports"));
+
+        let lines = render_lines(&DetailPane::new(Some(&file), false, &theme));
+        assert!(!contains(&lines, "compacted"));
+        assert!(contains(&lines, "No legacy imports"));
+    }
+}
@@ -2,22 +2,24 @@
 //!
 //! Displays a scrollable, selectable list of files with their migration status.
 
-use ch_core::FileInfo;
+use ch_core::{FileInfo, StatusLabels};
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Rect};
-use ratatui::text::Span;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{
     Block, Borders, Cell, HighlightSpacing, Row, StatefulWidget, Table, TableState,
 };
 
 use crate::app::{FileListState, FilterState};
+use crate::buffers::{BufferState, EditorBuffers};
 use crate::theme::Theme;
 
 /// A stateful file list widget.
 ///
 /// Displays files in a table with:
 /// - Selection indicator
-/// - File path (truncated if needed)
+/// - File path (truncated if needed), marked if open in an editor
 /// - Migration status badge
 ///
 /// Uses [`StatefulWidget`] to maintain scroll and selection state.
@@ -28,6 +30,10 @@ pub struct FileListView<'a> {
     filter: &'a FilterState,
     /// Whether this widget has focus.
     focused: bool,
+    /// Display labels for statuses.
+    labels: &'a StatusLabels,
+    /// Files open in an editor, if tracked.
+    editor_buffers: Option<&'a EditorBuffers>,
     /// Theme for styling.
     theme: &'a Theme,
 }
@@ -45,10 +51,26 @@ impl<'a> FileListView<'a> {
             files,
             filter,
             focused,
+            labels: StatusLabels::standard(),
+            editor_buffers: None,
             theme,
         }
     }
 
+    /// Marks files that are open in an editor.
+    #[must_use]
+    pub const fn with_editor_buffers(mut self, buffers: &'a EditorBuffers) -> Self {
+        self.editor_buffers = Some(buffers);
+        self
+    }
+
+    /// Sets the display labels used for the status column.
+    #[must_use]
+    pub const fn with_labels(mut self, labels: &'a StatusLabels) -> Self {
+        self.labels = labels;
+        self
+    }
+
     /// Builds rows for the table from the file list.
     fn build_rows(&self, state: &FileListState) -> Vec<Row<'a>> {
         let indices = state.filtered_indices();
@@ -74,16 +96,24 @@ impl<'a> FileListView<'a> {
 
         // Truncate long paths
         let path_display = truncate_path(file.path.as_str(), 60);
+        let mut path_spans = vec![Span::styled(path_display, self.theme.base_style())];
+
+        // Editor marker: ● open, ✎ unsaved changes
+        let buffer_state = self.editor_buffers.and_then(|buffers| buffers.get(&file.path));
+        if let Some(state) = buffer_state {
+            let marker = match state {
+                BufferState::Open => " ●",
+                BufferState::Modified => " ✎",
+            };
+            path_spans.push(Span::styled(marker, Style::default().fg(self.theme.accent)));
+        }
 
         // Build cells
         let cells = vec![
             Cell::from(Span::styled(status_indicator, status_style)),
+            Cell::from(Line::from(path_spans)),
             Cell::from(Span::styled(
-                path_display,
-                self.theme.base_style(),
-            )),
-            Cell::from(Span::styled(
-                file.status.label(),
+                self.labels.get(file.status),
                 status_style,
             )),
         ];
@@ -1,7 +1,8 @@
 //! Header bar component.
 //!
 //! Displays the application title, project path, and file count.
-//! During active scans, shows a scanning indicator.
+//! During active scans, shows a scanning (or paused) indicator, and below
+//! it a warning when the file watcher doesn't cover the whole scanned tree.
 
 use ch_core::Config;
 use ratatui::buffer::Buffer;
@@ -19,6 +20,7 @@ use crate::app::ScanState;
 /// - Project path
 /// - Total file count (or scanning indicator)
 /// - Help indicator
+/// - Watcher warning, if any
 pub struct HeaderBar<'a> {
     /// The configuration (for project path).
     config: &'a Config,
@@ -26,6 +28,10 @@ pub struct HeaderBar<'a> {
     file_count: usize,
     /// Current scan state for scanning indicator.
     scan_state: &'a ScanState,
+    /// Whether the running scan is paused.
+    paused: bool,
+    /// Warning shown when the watcher misses part of the scanned tree.
+    watch_notice: Option<&'a str>,
 }
 
 impl<'a> HeaderBar<'a> {
@@ -36,8 +42,24 @@ impl<'a> HeaderBar<'a> {
             config,
             file_count,
             scan_state,
+            paused: false,
+            watch_notice: None,
         }
     }
+
+    /// Marks the running scan as paused.
+    #[must_use]
+    pub const fn with_scan_paused(mut self, paused: bool) -> Self {
+        self.paused = paused;
+        self
+    }
+
+    /// Sets the warning shown when the watcher misses part of the scanned tree.
+    #[must_use]
+    pub const fn with_watch_notice(mut self, notice: Option<&'a str>) -> Self {
+        self.watch_notice = notice;
+        self
+    }
 }
 
 impl Widget for &HeaderBar<'_> {
@@ -63,6 +85,9 @@ impl Widget for &HeaderBar<'_> {
 
         // Build status indicator based on scan state
         let status_span = match self.scan_state {
+            ScanState::Scanning { scanned, .. } if self.paused => {
+                Span::styled(format!("Paused ({scanned} files)"), scanning_style)
+            }
             ScanState::Scanning { scanned, .. } => {
                 Span::styled(format!("Scanning... ({scanned} files)"), scanning_style)
             }
@@ -71,7 +96,7 @@ impl Widget for &HeaderBar<'_> {
             }
         };
 
-        let line = Line::from(vec![
+        let mut lines = vec![Line::from(vec![
             Span::styled("ch-migrate", title_style),
             Span::raw(" │ "),
             Span::styled(path_display, path_style),
@@ -79,13 +104,16 @@ impl Widget for &HeaderBar<'_> {
             status_span,
             Span::raw(" │ "),
             Span::styled("? for help", help_style),
-        ]);
+        ])];
+        if let Some(notice) = self.watch_notice {
+            lines.push(Line::from(Span::styled(format!("⚠ {notice}"), help_style)));
+        }
 
         let block = Block::default()
             .borders(Borders::BOTTOM)
             .border_style(Style::default().fg(Color::DarkGray));
 
-        let paragraph = Paragraph::new(line).block(block);
+        let paragraph = Paragraph::new(lines).block(block);
         paragraph.render(area, buf);
     }
 }
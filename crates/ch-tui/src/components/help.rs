@@ -1,6 +1,7 @@
 //! Help panel component.
 //!
-//! Displays a modal overlay with key bindings and help information.
+//! Displays a modal overlay with key bindings and help information,
+//! generated from the [command registry](crate::commands).
 
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Rect};
@@ -8,104 +9,132 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::Span;
 use ratatui::widgets::{Block, Borders, Cell, Clear, Row, Table, Widget};
 
+use crate::app::AppMode;
+use crate::commands::KeyBindings;
 use crate::theme::Theme;
 
-/// Key binding definition for the help panel.
+/// Key binding outside the command registry, for the help panel.
 struct KeyBinding {
     /// The key(s) to press.
     key: &'static str,
     /// Description of what the key does.
     description: &'static str,
-    /// The mode(s) where this binding applies.
-    mode: &'static str,
+    /// The mode where this binding applies, or `None` for every mode.
+    mode: Option<AppMode>,
 }
 
-/// Static list of key bindings to display.
-const KEY_BINDINGS: &[KeyBinding] = &[
-    // Navigation
+/// Keys handled by overlays or in every mode, listed after the commands.
+const OVERLAY_KEY_BINDINGS: &[KeyBinding] = &[
     KeyBinding {
-        key: "j / ↓",
-        description: "Next file",
-        mode: "Normal",
-    },
-    KeyBinding {
-        key: "k / ↑",
-        description: "Previous file",
-        mode: "Normal",
+        key: "Enter",
+        description: "Confirm filter",
+        mode: Some(AppMode::Filtering),
     },
     KeyBinding {
-        key: "g / Home",
-        description: "Go to first file",
-        mode: "Normal",
+        key: "Backspace",
+        description: "Delete character",
+        mode: Some(AppMode::Filtering),
     },
     KeyBinding {
-        key: "G / End",
-        description: "Go to last file",
-        mode: "Normal",
+        key: "Enter",
+        description: "Run command",
+        mode: Some(AppMode::Command),
     },
     KeyBinding {
-        key: "PgDn / PgUp",
-        description: "Page down / up",
-        mode: "Normal",
+        key: "Backspace",
+        description: "Delete character",
+        mode: Some(AppMode::Command),
     },
     KeyBinding {
-        key: "Tab",
-        description: "Toggle focus (List/Details)",
-        mode: "Normal",
+        key: "j / k / ↓ / ↑",
+        description: "Scroll message",
+        mode: Some(AppMode::Message),
     },
-    // Filtering
     KeyBinding {
-        key: "/",
-        description: "Start filter mode",
-        mode: "Normal",
+        key: "PgDn / PgUp",
+        description: "Scroll message by half a screen",
+        mode: Some(AppMode::Message),
     },
     KeyBinding {
-        key: "f",
-        description: "Cycle status filter",
-        mode: "Normal",
+        key: "q / m / Enter",
+        description: "Close message",
+        mode: Some(AppMode::Message),
     },
     KeyBinding {
-        key: "Esc",
-        description: "Clear filter / Exit mode",
-        mode: "Filter/Help",
+        key: "Enter",
+        description: "Apply directories",
+        mode: Some(AppMode::DirectorySetup),
     },
     KeyBinding {
-        key: "Enter",
-        description: "Confirm filter",
-        mode: "Filter",
+        key: "Tab / Shift+Tab",
+        description: "Next / previous field",
+        mode: Some(AppMode::DirectorySetup),
     },
-    // Actions
     KeyBinding {
-        key: "r",
-        description: "Rescan all files",
-        mode: "Normal",
+        key: "q / ?",
+        description: "Close help",
+        mode: Some(AppMode::Help),
     },
     KeyBinding {
-        key: "o",
-        description: "Open file in editor",
-        mode: "Normal",
+        key: "Esc",
+        description: "Close topmost overlay",
+        mode: None,
     },
     KeyBinding {
-        key: "d",
-        description: "Configure directories",
-        mode: "Normal",
+        key: "F1",
+        description: "Help over any overlay",
+        mode: None,
     },
     KeyBinding {
-        key: "?",
-        description: "Toggle help panel",
-        mode: "Normal",
+        key: "F12",
+        description: "Toggle debug overlay",
+        mode: None,
     },
     KeyBinding {
-        key: "q / Ctrl+c",
+        key: "Ctrl+c",
         description: "Quit",
-        mode: "Any",
+        mode: None,
     },
 ];
 
+/// Modes with their own keys, in help order.
+const MODES: [AppMode; 6] = [
+    AppMode::Normal,
+    AppMode::Filtering,
+    AppMode::Command,
+    AppMode::Message,
+    AppMode::DirectorySetup,
+    AppMode::Help,
+];
+
+/// Returns the name of a mode's section in the help panel.
+const fn mode_title(mode: AppMode) -> &'static str {
+    match mode {
+        AppMode::Normal => "Normal",
+        AppMode::Filtering => "Filter",
+        AppMode::Help => "Help",
+        AppMode::DirectorySetup => "Directory setup",
+        AppMode::Command => "Command prompt",
+        AppMode::Message => "Message",
+        AppMode::Debug => "Debug overlay",
+    }
+}
+
 /// A help panel overlay widget.
 ///
-/// Displays key bindings in a table format as a modal overlay.
+/// Lists the keys of each mode in its own section, starting with the mode
+/// help was opened from: the keys bound to each command (with the command
+/// IDs used for rebinding and at the `:` prompt) in normal mode, then the
+/// overlay keys, then keys that work in every mode. Keys changed from the
+/// defaults are marked with `*`, and commands that can't do anything right
+/// now are dimmed with the reason.
 pub struct HelpPanel<'a> {
+    /// Keys bound to each command.
+    key_bindings: &'a KeyBindings,
+    /// Mode whose keys are listed first.
+    mode: AppMode,
+    /// Commands that can't do anything right now, by ID, with the reason.
+    unavailable: &'a [(&'static str, &'static str)],
     /// Theme for styling.
     theme: &'a Theme,
 }
@@ -113,33 +142,139 @@ pub struct HelpPanel<'a> {
 impl<'a> HelpPanel<'a> {
     /// Creates a new help panel.
     #[must_use]
-    pub const fn new(theme: &'a Theme) -> Self {
-        Self { theme }
+    pub const fn new(key_bindings: &'a KeyBindings, theme: &'a Theme) -> Self {
+        Self {
+            key_bindings,
+            mode: AppMode::Normal,
+            unavailable: &[],
+            theme,
+        }
+    }
+
+    /// Sets the mode whose keys are listed first (see [`App::help_mode`]).
+    ///
+    /// [`App::help_mode`]: crate::app::App::help_mode
+    #[must_use]
+    pub const fn with_mode(mut self, mode: AppMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the commands that can't do anything right now, as command IDs
+    /// with the reason.
+    #[must_use]
+    pub const fn with_unavailable(
+        mut self,
+        unavailable: &'a [(&'static str, &'static str)],
+    ) -> Self {
+        self.unavailable = unavailable;
+        self
     }
 
-    /// Builds the table rows from key bindings.
+    /// Builds the table rows: one section per mode, the current mode first,
+    /// then the keys that work in every mode.
     fn build_rows(&self) -> Vec<Row<'static>> {
-        KEY_BINDINGS
-            .iter()
-            .map(|binding| {
-                Row::new(vec![
-                    Cell::from(Span::styled(
-                        binding.key,
-                        Style::default()
-                            .fg(Color::Yellow)
-                            .add_modifier(Modifier::BOLD),
-                    )),
-                    Cell::from(Span::styled(
-                        binding.description,
-                        self.theme.base_style(),
-                    )),
-                    Cell::from(Span::styled(
-                        binding.mode,
-                        Style::default().fg(Color::DarkGray),
-                    )),
-                ])
-            })
-            .collect()
+        let mut modes = vec![self.mode];
+        modes.extend(MODES.into_iter().filter(|&mode| mode != self.mode));
+
+        let mut rows = Vec::new();
+        for mode in modes {
+            let current = mode == self.mode;
+            if mode == AppMode::Normal {
+                rows.push(self.section(mode_title(mode), current));
+                rows.extend(self.command_rows());
+                continue;
+            }
+            let bindings: Vec<&KeyBinding> = OVERLAY_KEY_BINDINGS
+                .iter()
+                .filter(|binding| binding.mode == Some(mode))
+                .collect();
+            if bindings.is_empty() && !current {
+                continue;
+            }
+            rows.push(self.section(mode_title(mode), current));
+            rows.extend(bindings.into_iter().map(|binding| self.overlay_row(binding)));
+        }
+
+        rows.push(self.section("Any mode", false));
+        rows.extend(
+            OVERLAY_KEY_BINDINGS
+                .iter()
+                .filter(|binding| binding.mode.is_none())
+                .map(|binding| self.overlay_row(binding)),
+        );
+        if self.key_bindings.iter().any(|(command, _)| self.key_bindings.is_customized(command)) {
+            let note =
+                Span::styled("* key binding customized", Style::default().fg(Color::DarkGray));
+            rows.push(Row::new(vec![Cell::from(""), Cell::from(note)]));
+        }
+        rows
+    }
+
+    /// Builds the rows for the commands bound in normal mode.
+    fn command_rows(&self) -> impl Iterator<Item = Row<'static>> + '_ {
+        self.key_bindings.iter().map(|(command, keys)| {
+            let mut key = if keys.is_empty() {
+                "unbound".to_owned()
+            } else {
+                keys.iter().map(ToString::to_string).collect::<Vec<_>>().join(" / ")
+            };
+            if self.key_bindings.is_customized(command) {
+                key.push_str(" *");
+            }
+            let reason = self
+                .unavailable
+                .iter()
+                .find(|(id, _)| *id == command.id)
+                .map(|(_, reason)| *reason);
+
+            let Some(reason) = reason else {
+                return self.row(key, command.description.to_owned(), command.id);
+            };
+            let dim = Style::default().fg(Color::DarkGray);
+            Row::new(vec![
+                Cell::from(Span::styled(key, dim)),
+                Cell::from(Span::styled(format!("{} ({reason})", command.description), dim)),
+                Cell::from(Span::styled(command.id, dim)),
+            ])
+        })
+    }
+
+    /// Builds the row for a key outside the command registry.
+    fn overlay_row(&self, binding: &KeyBinding) -> Row<'static> {
+        self.row(binding.key.to_owned(), binding.description.to_owned(), "")
+    }
+
+    /// Builds a section heading row, marking the current mode.
+    fn section(&self, title: &'static str, current: bool) -> Row<'static> {
+        let title = if current {
+            format!("{title} (current)")
+        } else {
+            title.to_owned()
+        };
+        Row::new(vec![
+            Cell::from(""),
+            Cell::from(Span::styled(
+                title,
+                Style::default()
+                    .fg(self.theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            )),
+        ])
+    }
+
+    /// Builds one table row.
+    fn row(&self, key: String, description: String, command: &'static str) -> Row<'static> {
+        Row::new(vec![
+            Cell::from(Span::styled(
+                key,
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Cell::from(Span::styled(description, self.theme.base_style())),
+            Cell::from(Span::styled(command, Style::default().fg(Color::DarkGray))),
+        ])
     }
 }
 
@@ -175,7 +310,7 @@ impl Widget for &HelpPanel<'_> {
                     .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
             )),
             Cell::from(Span::styled(
-                "Mode",
+                "Command",
                 Style::default()
                     .fg(self.theme.accent)
                     .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
@@ -191,7 +326,7 @@ impl Widget for &HelpPanel<'_> {
         let widths = [
             Constraint::Length(15),
             Constraint::Min(25),
-            Constraint::Length(12),
+            Constraint::Length(20),
         ];
 
         // Build table
@@ -211,11 +346,59 @@ mod tests {
     #[test]
     fn test_help_panel_new() {
         let theme = Theme::dark();
-        let _panel = HelpPanel::new(&theme);
+        let key_bindings = KeyBindings::default();
+        let _panel = HelpPanel::new(&key_bindings, &theme);
+    }
+
+    /// Renders the panel and returns its text, one string per line.
+    fn render_lines(panel: &HelpPanel<'_>) -> Vec<String> {
+        let area = Rect::new(0, 0, 100, 80);
+        let mut buf = Buffer::empty(area);
+        panel.render(area, &mut buf);
+        (0..area.height)
+            .map(|y| (0..area.width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_help_rows_include_commands_and_overlay_keys() {
+        let theme = Theme::dark();
+        let key_bindings = KeyBindings::default();
+        let panel = HelpPanel::new(&key_bindings, &theme);
+        // One heading per mode with keys, plus "Any mode"
+        assert_eq!(
+            panel.build_rows().len(),
+            crate::commands::COMMANDS.len() + OVERLAY_KEY_BINDINGS.len() + MODES.len() + 1
+        );
     }
 
     #[test]
-    fn test_key_bindings_not_empty() {
-        assert!(!KEY_BINDINGS.is_empty());
+    fn test_help_starts_with_current_mode() {
+        let theme = Theme::dark();
+        let key_bindings = KeyBindings::default();
+        let panel = HelpPanel::new(&key_bindings, &theme).with_mode(AppMode::Message);
+        let lines = render_lines(&panel);
+
+        let position = |text: &str| lines.iter().position(|line| line.contains(text));
+        assert!(position("Message (current)") < position("Normal"));
+        assert!(position("Scroll message") < position("Next file"));
+        assert!(position("Normal (current)").is_none());
+    }
+
+    #[test]
+    fn test_help_shows_overrides_and_unavailable_commands() {
+        let theme = Theme::dark();
+        let overrides = "quit=ctrl+q,copy-view=none".parse().unwrap_or_default();
+        let key_bindings = KeyBindings::resolve(&overrides).unwrap_or_default();
+        let unavailable = [("open-in-editor", "no editor configured")];
+        let panel = HelpPanel::new(&key_bindings, &theme).with_unavailable(&unavailable);
+        let lines = render_lines(&panel);
+
+        let line = |text: &str| lines.iter().find(|line| line.contains(text)).cloned();
+        assert!(line("quit").is_some_and(|line| line.contains("Ctrl+q *")));
+        assert!(line("copy-view").is_some_and(|line| line.contains("unbound *")));
+        assert!(line("open-in-editor").is_some_and(|line| line.contains("(no editor configured)")));
+        assert!(line("next-item").is_some_and(|line| !line.contains('*')));
+        assert!(line("key binding customized").is_some());
     }
 }
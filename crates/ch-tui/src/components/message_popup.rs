@@ -0,0 +1,114 @@
+//! Expanded status message popup.
+//!
+//! Shows the full text of a status message, wrapped to the popup width,
+//! followed by the chain of underlying errors.
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap};
+
+use crate::app::MessagePopupState;
+use crate::theme::Theme;
+
+/// The expanded status message popup.
+///
+/// Scrolls with j/k and PgUp/PgDn; the scroll offset is clamped to the
+/// wrapped text when rendered.
+pub struct MessagePopup<'a> {
+    /// The popup state.
+    state: &'a MessagePopupState,
+    /// Theme for styling.
+    theme: &'a Theme,
+}
+
+impl<'a> MessagePopup<'a> {
+    /// Creates a new message popup.
+    #[must_use]
+    pub const fn new(state: &'a MessagePopupState, theme: &'a Theme) -> Self {
+        Self { state, theme }
+    }
+
+    /// Builds the message lines.
+    fn build_lines(&self) -> Vec<Line<'a>> {
+        let message = &self.state.message;
+        let text_style = if message.is_error {
+            Style::default().fg(self.theme.error_fg)
+        } else {
+            self.theme.base_style()
+        };
+
+        let mut lines: Vec<Line<'a>> = message
+            .text
+            .lines()
+            .map(|line| Line::from(Span::styled(line, text_style)))
+            .collect();
+
+        if !message.details.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "Caused by:",
+                Style::default().fg(Color::DarkGray),
+            )));
+            for detail in &message.details {
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled("•", Style::default().fg(self.theme.accent)),
+                    Span::raw(" "),
+                    Span::styled(detail.as_str(), self.theme.base_style()),
+                ]));
+            }
+        }
+
+        lines
+    }
+}
+
+/// Returns the number of rows `lines` take when wrapped to `width` columns.
+fn wrapped_height(lines: &[Line<'_>], width: u16) -> usize {
+    let width = usize::from(width.max(1));
+    lines
+        .iter()
+        .map(|line| line.width().div_ceil(width).max(1))
+        .sum()
+}
+
+impl Widget for &MessagePopup<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // Clear the background
+        Clear.render(area, buf);
+
+        let title = if self.state.message.is_error {
+            " Error "
+        } else {
+            " Message "
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(self.theme.focused_border_style)
+            .title(Span::styled(
+                title,
+                Style::default()
+                    .fg(self.theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .title_bottom(Line::from(Span::styled(
+                " j/k: scroll │ q/Esc: close ",
+                Style::default().fg(Color::DarkGray),
+            )));
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let lines = self.build_lines();
+        let max_scroll =
+            wrapped_height(&lines, inner.width).saturating_sub(usize::from(inner.height));
+        let scroll = u16::try_from(self.state.scroll_offset.min(max_scroll)).unwrap_or(u16::MAX);
+
+        Paragraph::new(Text::from(lines))
+            .wrap(Wrap { trim: false })
+            .scroll((scroll, 0))
+            .render(inner, buf);
+    }
+}
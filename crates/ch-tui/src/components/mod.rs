@@ -7,7 +7,8 @@
 //!
 //! - **Widgets** (`Widget` trait): Stateless rendering - `HeaderBar`, `StatsPanel`, `StatusBar`
 //! - **Stateful Widgets** (`StatefulWidget` trait): Selection/scroll state - `FileListView`, `DetailPane`
-//! - **Overlays**: Modal overlays - `HelpPanel`, `FilterInput`, `DirectoryInput`
+//! - **Overlays**: Modal overlays - `HelpPanel`, `FilterInput`, `CommandInput`, `DirectoryInput`,
+//!   `MessagePopup`, `DebugOverlay`
 //!
 //! # Usage
 //!
@@ -15,20 +16,26 @@
 //! use ch_tui::components::{FileListView, HeaderBar};
 //! ```
 
+mod command_input;
+mod debug_overlay;
 mod detail_pane;
 mod directory_input;
 mod file_list;
 mod filter_input;
 mod header;
 mod help;
+mod message_popup;
 mod stats_panel;
 mod status_bar;
 
+pub use command_input::CommandInput;
+pub use debug_overlay::DebugOverlay;
 pub use detail_pane::DetailPane;
 pub use directory_input::DirectoryInput;
 pub use file_list::FileListView;
 pub use filter_input::FilterInput;
 pub use header::HeaderBar;
 pub use help::HelpPanel;
+pub use message_popup::MessagePopup;
 pub use stats_panel::StatsPanel;
 pub use status_bar::StatusBar;
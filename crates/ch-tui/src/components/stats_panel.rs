@@ -3,7 +3,10 @@
 //! Displays migration statistics and progress gauge.
 //! During active scans, shows a scanning progress indicator.
 
-use ch_scanner::StatsSnapshot;
+use std::time::Duration;
+
+use ch_core::{MigrationStatus, StatusLabels};
+use ch_scanner::{OwnerStats, StatsSnapshot};
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
@@ -16,13 +19,24 @@ use crate::theme::Theme;
 /// The statistics panel component.
 ///
 /// Displays:
-/// - During scanning: Progress bar with "Scanning X/Y files"
-/// - After scan: Legacy, Partial, Migrated, No Models counts with migration gauge
+/// - During scanning: Progress bar with "Scanning X/Y files", followed by the
+///   throughput, estimated time remaining and bytes parsed once the scanner
+///   reports them
+/// - After scan: Legacy, Partial, Migrated, No Models counts with migration gauge,
+///   followed by per-team progress when code ownership is configured
 pub struct StatsPanel<'a> {
     /// Statistics snapshot.
     stats: &'a StatsSnapshot,
+    /// Per-team statistics (may be empty).
+    teams: &'a [OwnerStats],
     /// Current scan state for progress display.
     scan_state: &'a ScanState,
+    /// Live scanner statistics, for throughput and ETA while scanning.
+    live: Option<StatsSnapshot>,
+    /// Whether the running scan is paused.
+    paused: bool,
+    /// Display labels for statuses.
+    labels: &'a StatusLabels,
     /// Theme for styling.
     theme: &'a Theme,
 }
@@ -33,10 +47,43 @@ impl<'a> StatsPanel<'a> {
     pub const fn new(stats: &'a StatsSnapshot, scan_state: &'a ScanState, theme: &'a Theme) -> Self {
         Self {
             stats,
+            teams: &[],
             scan_state,
+            live: None,
+            paused: false,
+            labels: StatusLabels::standard(),
             theme,
         }
     }
+
+    /// Sets the per-team statistics shown below the totals.
+    #[must_use]
+    pub const fn with_teams(mut self, teams: &'a [OwnerStats]) -> Self {
+        self.teams = teams;
+        self
+    }
+
+    /// Sets the scanner's live statistics, whose throughput and ETA are
+    /// shown while scanning.
+    #[must_use]
+    pub const fn with_live_stats(mut self, live: StatsSnapshot) -> Self {
+        self.live = Some(live);
+        self
+    }
+
+    /// Marks the running scan as paused.
+    #[must_use]
+    pub const fn with_scan_paused(mut self, paused: bool) -> Self {
+        self.paused = paused;
+        self
+    }
+
+    /// Sets the display labels used for status counts.
+    #[must_use]
+    pub const fn with_labels(mut self, labels: &'a StatusLabels) -> Self {
+        self.labels = labels;
+        self
+    }
 }
 
 impl Widget for &StatsPanel<'_> {
@@ -49,33 +96,43 @@ impl Widget for &StatsPanel<'_> {
         let inner = block.inner(area);
         block.render(area, buf);
 
-        let chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Min(40), Constraint::Length(30)])
-            .split(inner);
+        let chunks = split_panel(inner);
 
         // Show scanning progress OR migration stats based on scan state
         if let ScanState::Scanning { discovered, scanned } = self.scan_state {
             // Render scanning progress
-            render_scanning_progress(*discovered, *scanned, &chunks, buf);
+            let live = self.live.as_ref().filter(|_| !self.paused);
+            render_scanning_progress(*discovered, *scanned, self.paused, live, &chunks, buf);
         } else {
             // Render normal migration stats
-            render_migration_stats(self.stats, &chunks, buf, self.theme);
+            render_migration_stats(self.stats, self.labels, &chunks, buf, self.theme);
+            render_team_stats(self.teams, chunks[0], buf);
         }
     }
 }
 
-/// Renders the scanning progress view.
+/// Splits the panel into the stats text and the 30-column progress gauge.
+fn split_panel(inner: Rect) -> [Rect; 2] {
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(40), Constraint::Length(30)])
+        .areas(inner)
+}
+
+/// Renders the scanning progress view; throughput and ETA are left out
+/// while paused.
 fn render_scanning_progress(
     discovered: usize,
     scanned: usize,
+    paused: bool,
+    live: Option<&StatsSnapshot>,
     chunks: &[Rect],
     buf: &mut Buffer,
 ) {
     // Scanning status text
-    let scanning_line = Line::from(vec![
+    let mut spans = vec![
         Span::styled(
-            "Scanning... ",
+            if paused { "Paused " } else { "Scanning... " },
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
@@ -84,7 +141,26 @@ fn render_scanning_progress(
             format!("{scanned}/{discovered} files"),
             Style::default().fg(Color::White),
         ),
-    ]);
+    ];
+    if let Some(rate) = live.and_then(StatsSnapshot::files_per_sec) {
+        spans.push(Span::styled(
+            format!("  {rate:.0} files/s"),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    if let Some(eta) = live.and_then(StatsSnapshot::eta) {
+        spans.push(Span::styled(
+            format!("  ETA {}", format_eta(eta)),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    if let Some(parsed) = live.map(|live| live.parsed).filter(|parsed| !parsed.is_empty()) {
+        spans.push(Span::styled(
+            format!("  {} parsed", format_bytes(parsed.bytes)),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    let scanning_line = Line::from(spans);
 
     let status_paragraph = Paragraph::new(scanning_line);
     status_paragraph.render(chunks[0], buf);
@@ -105,34 +181,63 @@ fn render_scanning_progress(
     gauge.render(chunks[1], buf);
 }
 
+/// Formats a size in bytes as KiB, or MiB from a mebibyte up.
+#[allow(clippy::cast_precision_loss)] // Acceptable for display
+fn format_bytes(bytes: u64) -> String {
+    let kib = bytes as f64 / 1024.0;
+    if kib < 1024.0 {
+        format!("{kib:.0} KiB")
+    } else {
+        format!("{:.1} MiB", kib / 1024.0)
+    }
+}
+
+/// Formats a time remaining as `m:ss`, or `h:mm:ss` from an hour up.
+fn format_eta(eta: Duration) -> String {
+    let secs = eta.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
 /// Renders the normal migration statistics view.
 fn render_migration_stats(
     stats: &StatsSnapshot,
+    labels: &StatusLabels,
     chunks: &[Rect],
     buf: &mut Buffer,
     theme: &Theme,
 ) {
     // Render stats counts
+    let label = |status| {
+        Span::styled(
+            format!("{}: ", labels.get(status)),
+            Style::default().fg(Color::DarkGray),
+        )
+    };
     let stats_line = Line::from(vec![
-        Span::styled("Legacy: ", Style::default().fg(Color::DarkGray)),
+        label(MigrationStatus::Legacy),
         Span::styled(
             format!("{}", stats.legacy),
             Style::default().fg(theme.legacy_fg),
         ),
         Span::raw(" │ "),
-        Span::styled("Partial: ", Style::default().fg(Color::DarkGray)),
+        label(MigrationStatus::Partial),
         Span::styled(
             format!("{}", stats.partial),
             Style::default().fg(theme.partial_fg),
         ),
         Span::raw(" │ "),
-        Span::styled("Migrated: ", Style::default().fg(Color::DarkGray)),
+        label(MigrationStatus::Migrated),
         Span::styled(
             format!("{}", stats.migrated),
             Style::default().fg(theme.migrated_fg),
         ),
         Span::raw(" │ "),
-        Span::styled("No Models: ", Style::default().fg(Color::DarkGray)),
+        label(MigrationStatus::NoModels),
         Span::styled(
             format!("{}", stats.no_models),
             Style::default().fg(theme.no_models_fg),
@@ -158,3 +263,61 @@ fn render_migration_stats(
 
     gauge.render(chunks[1], buf);
 }
+
+/// Renders per-team completion on the second line of the stats area.
+fn render_team_stats(teams: &[OwnerStats], area: Rect, buf: &mut Buffer) {
+    if teams.is_empty() || area.height < 2 {
+        return;
+    }
+
+    let mut spans = vec![Span::styled("Teams: ", Style::default().fg(Color::DarkGray))];
+    for (i, team) in teams.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::raw(" │ "));
+        }
+        spans.push(Span::styled(
+            format!("{} ", team.owner),
+            Style::default().fg(Color::White),
+        ));
+        spans.push(Span::styled(
+            format!("{:.1}%", team.stats.progress_percent()),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    let row = Rect {
+        y: area.y + 1,
+        height: 1,
+        ..area
+    };
+    Paragraph::new(Line::from(spans)).render(row, buf);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_panel_golden_sizes() {
+        // The panel is 3 lines tall; its bottom border leaves 2 for content
+        for (width, text_width) in [(80, 50), (120, 90), (200, 170)] {
+            let [text, gauge] = split_panel(Rect::new(0, 3, width, 2));
+            assert_eq!(text, Rect::new(0, 3, text_width, 2));
+            assert_eq!(gauge, Rect::new(text_width, 3, 30, 2));
+        }
+    }
+
+    #[test]
+    fn test_format_eta() {
+        assert_eq!(format_eta(Duration::from_secs(7)), "0:07");
+        assert_eq!(format_eta(Duration::from_millis(125_900)), "2:05");
+        assert_eq!(format_eta(Duration::from_secs(3723)), "1:02:03");
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(300), "0 KiB");
+        assert_eq!(format_bytes(512 * 1024), "512 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 + 512 * 1024), "3.5 MiB");
+    }
+}
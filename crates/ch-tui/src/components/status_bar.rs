@@ -1,22 +1,29 @@
 //! Status bar component.
 //!
-//! Displays status messages, mode indicators, and help hints.
+//! Displays status messages, mode indicators, and help hints. Long
+//! messages wrap onto up to [`MAX_HEIGHT`] lines; anything beyond that is
+//! readable in the expanded message popup.
 
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Paragraph, Widget};
+use ratatui::widgets::{Paragraph, Widget, Wrap};
 
 use crate::app::{App, AppMode};
 use crate::theme::Theme;
+use crate::view::SortOrder;
+
+/// Maximum number of lines the status bar grows to.
+pub const MAX_HEIGHT: u16 = 3;
 
 /// The status bar component.
 ///
 /// Displays:
 /// - Current mode indicator
-/// - Status message (if any)
+/// - Status message (if any), with a hint when it can be expanded
 /// - Filter indicator (if active)
+/// - Sort indicator (if not sorted by path)
 /// - Help hint
 pub struct StatusBar<'a> {
     /// The application state.
@@ -32,16 +39,29 @@ impl<'a> StatusBar<'a> {
         Self { app, theme }
     }
 
-    /// Builds the status line spans.
-    fn build_line(&self) -> Line<'a> {
+    /// Returns the number of lines needed to show the status at `width`.
+    ///
+    /// Between 1 and [`MAX_HEIGHT`].
+    #[must_use]
+    pub fn height(&self, width: u16) -> u16 {
+        let width = usize::from(width.max(1));
+        let lines = self.build_line(width).width().div_ceil(width);
+        u16::try_from(lines).unwrap_or(MAX_HEIGHT).clamp(1, MAX_HEIGHT)
+    }
+
+    /// Builds the status line spans for a bar `width` columns wide.
+    fn build_line(&self, width: usize) -> Line<'a> {
         let mut spans = Vec::new();
 
         // Mode indicator
-        let mode_text = match self.app.mode {
+        let mode_text = match self.app.mode() {
             AppMode::Normal => "NORMAL",
             AppMode::Filtering => "FILTER",
             AppMode::Help => "HELP",
             AppMode::DirectorySetup => "SETUP",
+            AppMode::Command => "COMMAND",
+            AppMode::Message => "MESSAGE",
+            AppMode::Debug => "DEBUG",
         };
         spans.push(Span::styled(
             format!(" {mode_text} "),
@@ -52,13 +72,17 @@ impl<'a> StatusBar<'a> {
         ));
         spans.push(Span::raw(" "));
 
-        // Status message
+        // Status message, with an expand hint if it won't fit
         if let Some(ref status) = self.app.status {
             let style = if status.is_error {
                 Style::default().fg(self.theme.error_fg)
             } else {
                 Style::default().fg(self.theme.fg)
             };
+            let room = width * usize::from(MAX_HEIGHT) / 2;
+            if status.has_more(room) {
+                spans.push(Span::styled("[m: more] ", Style::default().fg(Color::DarkGray)));
+            }
             spans.push(Span::styled(status.text.clone(), style));
             spans.push(Span::raw(" │ "));
         }
@@ -73,15 +97,33 @@ impl<'a> StatusBar<'a> {
                 ));
                 spans.push(Span::raw(" "));
             }
-            if let Some(status) = self.app.filter.status {
+            for status in self.app.filter.active_statuses() {
                 spans.push(Span::styled(
-                    status.label(),
+                    self.app.config.labels.get(status),
                     self.theme.status_style(status),
                 ));
+                spans.push(Span::raw(" "));
+            }
+            if self.app.filter.dead_imports {
+                spans.push(Span::styled(
+                    "Dead imports",
+                    Style::default().fg(Color::Yellow),
+                ));
+                spans.push(Span::raw(" "));
             }
             spans.push(Span::raw(" │ "));
         }
 
+        // Sort indicator (only when not the default path order)
+        if self.app.sort != SortOrder::default() {
+            spans.push(Span::styled("Sort: ", Style::default().fg(Color::DarkGray)));
+            spans.push(Span::styled(
+                self.app.sort.as_str(),
+                Style::default().fg(Color::Yellow),
+            ));
+            spans.push(Span::raw(" │ "));
+        }
+
         // File count
         spans.push(Span::styled(
             format!("{}/{}", self.app.filtered_count(), self.app.file_count()),
@@ -94,8 +136,10 @@ impl<'a> StatusBar<'a> {
 
 impl Widget for &StatusBar<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let line = self.build_line();
-        let paragraph = Paragraph::new(line).style(self.theme.status_bar_style);
+        let line = self.build_line(usize::from(area.width));
+        let paragraph = Paragraph::new(line)
+            .style(self.theme.status_bar_style)
+            .wrap(Wrap { trim: false });
         paragraph.render(area, buf);
     }
 }
@@ -0,0 +1,130 @@
+//! Runtime health figures for the debug overlay.
+//!
+//! [`DebugStats`] collects what the event loop observes: how long frames
+//! take to render, how many scan updates and watcher events are waiting in
+//! their channels, the tokio runtime's task counts, and when the scan cache
+//! was last rebuilt. The debug overlay (F12) shows them next to the
+//! scanner's thread pool activity, so "the TUI feels sluggish" reports come
+//! with actual numbers.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//! use ch_tui::debug::DebugStats;
+//!
+//! let mut stats = DebugStats::default();
+//! stats.record_frame(Duration::from_millis(4));
+//! stats.record_frame(Duration::from_millis(8));
+//! assert_eq!(stats.last_frame(), Some(Duration::from_millis(8)));
+//! assert_eq!(stats.average_frame(), Some(Duration::from_millis(6)));
+//! assert_eq!(stats.slowest_frame(), Some(Duration::from_millis(8)));
+//! ```
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Number of recent frames kept for render time figures.
+const FRAME_SAMPLES: usize = 120;
+
+/// Health of the tokio runtime driving the event loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeHealth {
+    /// Number of runtime worker threads.
+    pub workers: usize,
+    /// Number of tasks alive on the runtime.
+    pub alive_tasks: usize,
+    /// Number of tasks waiting in the runtime's global queue.
+    pub global_queue_depth: usize,
+}
+
+impl RuntimeHealth {
+    /// Samples the runtime the caller is running on, if any.
+    #[must_use]
+    pub fn current() -> Option<Self> {
+        let handle = tokio::runtime::Handle::try_current().ok()?;
+        let metrics = handle.metrics();
+        Some(Self {
+            workers: metrics.num_workers(),
+            alive_tasks: metrics.num_alive_tasks(),
+            global_queue_depth: metrics.global_queue_depth(),
+        })
+    }
+}
+
+/// Figures shown in the debug overlay, updated by the event loop.
+#[derive(Debug, Clone, Default)]
+pub struct DebugStats {
+    /// Render times of the most recent frames, oldest first.
+    frame_times: VecDeque<Duration>,
+    /// Scan updates waiting to be handled.
+    pub scan_queue: usize,
+    /// File watcher events waiting to be handled.
+    pub watcher_queue: usize,
+    /// Last runtime sample, if running on a tokio runtime.
+    pub runtime: Option<RuntimeHealth>,
+    /// When the scan cache was last rebuilt by a full scan.
+    pub cache_rebuilt_at: Option<Instant>,
+}
+
+impl DebugStats {
+    /// Records how long a frame took to render.
+    pub fn record_frame(&mut self, elapsed: Duration) {
+        if self.frame_times.len() == FRAME_SAMPLES {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(elapsed);
+    }
+
+    /// Records the channel depths and samples the runtime.
+    pub fn sample(&mut self, scan_queue: usize, watcher_queue: usize) {
+        self.scan_queue = scan_queue;
+        self.watcher_queue = watcher_queue;
+        self.runtime = RuntimeHealth::current();
+    }
+
+    /// Returns the render time of the last frame.
+    #[must_use]
+    pub fn last_frame(&self) -> Option<Duration> {
+        self.frame_times.back().copied()
+    }
+
+    /// Returns the average render time over the recent frames.
+    #[must_use]
+    pub fn average_frame(&self) -> Option<Duration> {
+        let count = u32::try_from(self.frame_times.len()).ok().filter(|&count| count > 0)?;
+        Some(self.frame_times.iter().sum::<Duration>() / count)
+    }
+
+    /// Returns the slowest render time over the recent frames.
+    #[must_use]
+    pub fn slowest_frame(&self) -> Option<Duration> {
+        self.frame_times.iter().max().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_times_keep_recent_samples() {
+        let mut stats = DebugStats::default();
+        assert_eq!(stats.average_frame(), None);
+
+        stats.record_frame(Duration::from_secs(1));
+        for _ in 0..FRAME_SAMPLES {
+            stats.record_frame(Duration::from_millis(2));
+        }
+        assert_eq!(stats.slowest_frame(), Some(Duration::from_millis(2)));
+        assert_eq!(stats.average_frame(), Some(Duration::from_millis(2)));
+    }
+
+    #[tokio::test]
+    async fn test_sample_reads_runtime() {
+        let mut stats = DebugStats::default();
+        stats.sample(3, 1);
+        assert_eq!((stats.scan_queue, stats.watcher_queue), (3, 1));
+        assert!(stats.runtime.is_some_and(|runtime| runtime.workers >= 1));
+    }
+}
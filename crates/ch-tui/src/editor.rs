@@ -90,6 +90,28 @@ fn editor_kind_from_program(program: &str) -> EditorKind {
     }
 }
 
+/// Editors tried, in order, when none is configured.
+const FALLBACK_EDITORS: [&str; 5] = ["cursor", "code", "nvim", "vim", "nano"];
+
+/// Returns `true` if an editor is configured with `--editor`, `$VISUAL` or
+/// `$EDITOR`, or one of the fallback editors is on `PATH`.
+pub fn is_available(config: &Config) -> bool {
+    let configured = config.editor.editor.is_some()
+        || env::var_os("VISUAL").is_some()
+        || env::var_os("EDITOR").is_some();
+    if configured {
+        return true;
+    }
+    let Some(path) = env::var_os("PATH") else {
+        return false;
+    };
+    env::split_paths(&path).any(|dir| {
+        FALLBACK_EDITORS.iter().any(|editor| {
+            dir.join(editor).is_file() || dir.join(format!("{editor}.exe")).is_file()
+        })
+    })
+}
+
 fn resolve_editor(config: &Config) -> Result<EditorCommand, TuiError> {
     let mut candidates = Vec::new();
 
@@ -100,15 +122,7 @@ fn resolve_editor(config: &Config) -> Result<EditorCommand, TuiError> {
     } else if let Ok(editor) = env::var("EDITOR") {
         candidates.push(editor);
     } else {
-        candidates.extend([
-            "cursor",
-            "code",
-            "nvim",
-            "vim",
-            "nano",
-        ]
-        .into_iter()
-        .map(str::to_owned));
+        candidates.extend(FALLBACK_EDITORS.into_iter().map(str::to_owned));
     }
 
     for candidate in candidates {
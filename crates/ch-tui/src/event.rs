@@ -10,6 +10,7 @@
 //! - **Terminal**: Key presses, mouse events, window resizing
 //! - **File Watcher**: File change notifications from `ch-watcher`
 //! - **Scanner**: Streaming scan results from `ch-scanner`
+//! - **Background tasks**: Outcomes of work run off the event loop
 //! - **Timer**: Periodic tick events for animations and updates
 //!
 //! # Example
@@ -32,6 +33,8 @@ use ch_scanner::ScanUpdate;
 use ch_watcher::FileEvent;
 use crossterm::event::{KeyEvent, MouseEvent};
 
+use crate::action::Action;
+
 /// Events that can be processed by the TUI.
 ///
 /// This enum unifies all event sources into a single type that can be
@@ -56,12 +59,18 @@ pub enum Event {
     /// A file changed in the watched directory.
     FileChanged(FileEvent),
 
+    /// A file changed in a watched shared model directory.
+    SharedChanged(FileEvent),
+
     /// Scan progress update from background task.
     ///
     /// These events are streamed from the background scanner and include
     /// file discovery counts, individual file results, and completion status.
     ScanUpdate(ScanUpdate),
 
+    /// A background task finished, with the action applying its outcome.
+    TaskFinished(Action),
+
     /// Periodic tick for animations and updates.
     ///
     /// The tick rate is configured via [`TuiConfig::tick_rate_ms`].
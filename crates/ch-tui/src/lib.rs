@@ -13,9 +13,15 @@
 //!   event.rs         # Event types (Key, Mouse, File, Tick, Render)
 //!   tui.rs           # Terminal wrapper with async event streaming
 //!   action.rs        # User actions (commands from key bindings)
+//!   commands.rs      # Command registry and key binding resolution
 //!   ui.rs            # Main layout rendering orchestration
 //!   theme.rs         # Color scheme and styling constants
 //!   error.rs         # TUI-specific error types
+//!   view.rs          # Shareable view strings (filter + sort)
+//!   session.rs       # Session handover for in-place upgrades
+//!   overlay.rs       # Stack of modal overlays
+//!   buffers.rs       # Detection of files open in an editor
+//!   debug.rs         # Runtime health figures for the debug overlay
 //!   components/
 //!     mod.rs         # Component trait definition
 //!     file_list.rs   # FileListView + FileListState
@@ -25,6 +31,9 @@
 //!     status_bar.rs  # StatusBar component
 //!     help.rs        # HelpPanel modal overlay
 //!     filter_input.rs # Filter/search input component
+//!     command_input.rs # `:` command prompt component
+//!     message_popup.rs # Expanded status message overlay
+//!     debug_overlay.rs # Runtime health (F12) overlay
 //! ```
 //!
 //! # Usage
@@ -48,30 +57,49 @@
 
 pub mod action;
 pub mod app;
+pub mod buffers;
+mod clipboard;
+pub mod commands;
 pub mod components;
+pub mod debug;
 mod editor;
 pub mod error;
 pub mod event;
+pub mod overlay;
+pub mod session;
+mod shared_watch;
 pub mod theme;
-mod toolchain;
+pub mod toolchain;
 pub mod tui;
 pub mod ui;
+pub mod view;
+
+use std::time::Instant;
 
 use ch_core::Config;
-use ch_scanner::{ScanUpdate, Scanner};
+use ch_scanner::{
+    write_status_file, ScanError, ScanUpdate, Scanner, StatsSnapshot, StatusFile,
+};
 use ch_watcher::{FileWatcher, TypeScriptFilter};
 use tokio::sync::mpsc;
 use tracing::{debug, error, info};
 
+use shared_watch::SharedWatch;
+
 // Public re-exports
 pub use action::Action;
+pub use commands::{Command, KeyBindings, KeyChord};
 pub use app::{
     App, AppMode, DetailPaneState, FileListState, FilterState, Focus, ScanState, StatusMessage,
+    WatchCoverage,
 };
 pub use error::TuiError;
 pub use event::Event;
+pub use overlay::{Overlay, OverlayStack};
+pub use session::Session;
 pub use theme::Theme;
 pub use tui::Tui;
+pub use view::{SortOrder, ViewSpec};
 
 /// Runs the TUI application with the given configuration and scanner.
 ///
@@ -110,6 +138,55 @@ pub use tui::Tui;
 /// }
 /// ```
 pub async fn run(config: Config, scanner: Scanner) -> Result<(), TuiError> {
+    run_with_view(config, scanner, None).await
+}
+
+/// Runs the TUI application starting from the given view.
+///
+/// Behaves like [`run`], but applies `view` (filter + sort) before the
+/// first frame so the file list opens exactly as it was shared.
+///
+/// # Arguments
+///
+/// * `config` - The application configuration
+/// * `scanner` - The file scanner (pre-configured)
+/// * `view` - Initial view to apply, if any
+///
+/// # Errors
+///
+/// Returns an error if terminal initialization, scanning, or the file
+/// watcher fails.
+pub async fn run_with_view(
+    config: Config,
+    scanner: Scanner,
+    view: Option<ViewSpec>,
+) -> Result<(), TuiError> {
+    run_with_session(config, scanner, view, None).await
+}
+
+/// Runs the TUI application, resuming a session handed over by `:upgrade`.
+///
+/// Behaves like [`run_with_view`], but also restores `session` (view and
+/// selected file). A `session` takes precedence over `view`.
+///
+/// When the user runs `:upgrade`, the terminal is restored, the current
+/// session is written to a temporary file, and the process is replaced by
+/// a fresh copy of the binary (see [`session::exec_upgrade`]). This
+/// function only returns in that case if the restart failed.
+///
+/// # Errors
+///
+/// Returns an error if terminal initialization, scanning, or the file
+/// watcher fails, or if an upgrade could not be started.
+pub async fn run_with_session(
+    config: Config,
+    scanner: Scanner,
+    view: Option<ViewSpec>,
+    session: Option<Session>,
+) -> Result<(), TuiError> {
+    // Reject bad key bindings before taking over the terminal
+    let key_bindings = KeyBindings::resolve(&config.tui.keys)?;
+
     // Initialize TUI
     // tick_rate_ms and frame_rate are small UI timing values, precision loss is acceptable
     #[allow(clippy::cast_precision_loss)]
@@ -120,7 +197,12 @@ pub async fn run(config: Config, scanner: Scanner) -> Result<(), TuiError> {
     let mut tui = Tui::new(tick_rate)?.with_frame_rate(frame_rate);
 
     // Initialize app
-    let mut app = App::new(config.clone(), scanner);
+    let mut app = App::new(config.clone(), scanner).with_key_bindings(key_bindings);
+    if let Some(session) = session {
+        app.restore_session(session);
+    } else if let Some(view) = view {
+        app.apply_view(view);
+    }
 
     // Get theme from config
     let theme = Theme::from_scheme(config.tui.color_scheme);
@@ -133,28 +215,54 @@ pub async fn run(config: Config, scanner: Scanner) -> Result<(), TuiError> {
         debug!("Directory setup required; delaying initial scan and watcher");
         None
     } else {
+        // List the last session's results while the scan runs
+        app.load_saved_results();
+
         // Spawn streaming scan in background for instant UI
         info!("Starting background streaming scan");
-        Some(spawn_background_scan(&app.scanner))
+        app.scan_state = ScanState::Scanning {
+            discovered: 0,
+            scanned: 0,
+        };
+        Some(spawn_background_scan(&mut app))
     };
 
-    // Start watcher AFTER scan complete (handled in event loop)
-    let mut watcher: Option<FileWatcher> = None;
+    // Start watchers AFTER scan complete (handled in event loop)
+    let mut watchers = Watchers::default();
 
     // Main event loop
     info!("Entering main event loop");
-    let result = run_event_loop(&mut tui, &mut app, &mut watcher, scan_rx, &config, &theme).await;
+    let result = run_event_loop(&mut tui, &mut app, &mut watchers, scan_rx, &config, &theme).await;
+
+    // Stop a scan still running, so its thread doesn't hold up shutdown
+    if app.scan_state.is_scanning() {
+        app.cancel_scan();
+    }
 
     // Exit terminal (restore state)
     tui.exit()?;
 
-    // Shutdown watcher gracefully
-    if let Some(w) = watcher {
+    // Keep the results for the next session to show at once
+    app.save_results();
+
+    // Shutdown watchers gracefully
+    if let Some(w) = watchers.app {
         info!("Shutting down file watcher");
         if let Err(e) = w.shutdown().await {
             error!(error = %e, "Error shutting down watcher");
         }
     }
+    if let Some(shared) = watchers.shared {
+        shared.shutdown().await;
+    }
+
+    if result.is_ok() && app.upgrade_requested() {
+        let path = app.session().save_temp()?;
+        info!(session = %path, "Restarting into upgraded binary");
+        let err = session::exec_upgrade(&path);
+        let _ = std::fs::remove_file(&path);
+        return Err(TuiError::config(format!("upgrade failed: {err}")));
+    }
 
     result
 }
@@ -162,34 +270,58 @@ pub async fn run(config: Config, scanner: Scanner) -> Result<(), TuiError> {
 /// Spawns a background streaming scan task.
 ///
 /// Returns a receiver for scan updates that can be polled in the event loop.
-fn spawn_background_scan(scanner: &Scanner) -> mpsc::Receiver<ScanUpdate> {
-    let (tx, rx) = mpsc::channel(256); // Buffer for smooth streaming
-    let scanner_clone = scanner.clone();
+/// The scan stops before its next file once [`App::cancel_scan`] is called,
+/// and holds while paused with [`App::toggle_pause_scan`].
+fn spawn_background_scan(app: &mut App) -> mpsc::Receiver<ScanUpdate> {
+    let control = app.new_scan_control();
+    let (tx, rx) = mpsc::channel(app.scanner.channel_capacity());
+    let scanner_clone = app.scanner.clone();
 
     tokio::task::spawn_blocking(move || {
-        if let Err(e) = scanner_clone.scan_streaming(tx) {
-            error!(error = %e, "Background scan failed");
+        match scanner_clone.scan_streaming_controlled(tx, &control) {
+            Ok(()) => {}
+            Err(ScanError::Cancelled) => debug!("Background scan cancelled"),
+            Err(e) => error!(error = %e, "Background scan failed"),
         }
     });
 
     rx
 }
 
+/// File watchers of the event loop, started once the first scan completes.
+#[derive(Debug, Default)]
+struct Watchers {
+    /// Watcher on the app directory.
+    app: Option<FileWatcher>,
+    /// Watchers on the shared model directories, if `watch.shared` is set.
+    shared: Option<SharedWatch>,
+}
+
 /// Runs the main event loop.
 async fn run_event_loop(
     tui: &mut Tui,
     app: &mut App,
-    watcher: &mut Option<FileWatcher>,
+    watchers: &mut Watchers,
     mut scan_rx: Option<mpsc::Receiver<ScanUpdate>>,
     config: &Config,
     theme: &Theme,
 ) -> Result<(), TuiError> {
+    let mut status_written: Option<StatsSnapshot> = None;
+    let mut task_rx = app.take_task_receiver();
+
     loop {
         // Sort files if dirty before rendering (deferred sorting)
         app.sort_files_if_needed();
 
-        // Draw the UI
+        // Draw the UI, timing it for the debug overlay
+        app.debug.sample(
+            scan_rx.as_ref().map_or(0, mpsc::Receiver::len),
+            watchers.app.as_ref().map_or(0, FileWatcher::pending_events)
+                + watchers.shared.as_ref().map_or(0, SharedWatch::pending_events),
+        );
+        let frame_started = Instant::now();
         tui.draw(|frame| ui::render(app, frame, theme))?;
+        app.debug.record_frame(frame_started.elapsed());
 
         // Wait for next event
         let event = tokio::select! {
@@ -198,7 +330,7 @@ async fn run_event_loop(
 
             // File watcher events
             file_event = async {
-                match watcher {
+                match &mut watchers.app {
                     Some(w) => w.recv().await,
                     None => std::future::pending().await,
                 }
@@ -206,6 +338,22 @@ async fn run_event_loop(
                 file_event.map(Event::FileChanged)
             },
 
+            // Shared model directory events
+            shared_event = async {
+                match &mut watchers.shared {
+                    Some(shared) => shared.recv().await,
+                    None => std::future::pending().await,
+                }
+            } => {
+                // A stopped watcher would end every wait at once
+                if shared_event.is_none() {
+                    if let Some(shared) = watchers.shared.take() {
+                        shared.shutdown().await;
+                    }
+                }
+                shared_event.map(Event::SharedChanged)
+            },
+
             // Scan update events
             scan_update = async {
                 match &mut scan_rx {
@@ -213,8 +361,23 @@ async fn run_event_loop(
                     None => std::future::pending().await,
                 }
             } => {
+                // Channel closed without a completion update: the scan failed
+                if scan_update.is_none() {
+                    scan_rx = None;
+                    app.handle_scan_aborted();
+                }
                 scan_update.map(Event::ScanUpdate)
             }
+
+            // Background task outcomes
+            task_action = async {
+                match &mut task_rx {
+                    Some(rx) => rx.recv().await,
+                    None => std::future::pending().await,
+                }
+            } => {
+                task_action.map(Event::TaskFinished)
+            }
         };
 
         // Process event
@@ -227,12 +390,18 @@ async fn run_event_loop(
                     Action::Render
                 }
                 Event::FileChanged(file_event) => app.handle_file_change(file_event),
+                Event::SharedChanged(file_event) => app.handle_shared_change(file_event),
                 Event::ScanUpdate(update) => {
                     let is_complete = matches!(update, ScanUpdate::Complete(_));
                     app.handle_scan_update(update);
 
+                    // Clear the scan receiver since scan is done
+                    if is_complete {
+                        scan_rx = None;
+                    }
+
                     // Start watcher after scan completes
-                    if is_complete && config.watch.enabled && watcher.is_none() {
+                    if is_complete && config.watch.enabled && watchers.app.is_none() {
                         // Watch app_path only (not root_path) to match scan scope
                         info!(app_path = %config.scan.app_path, "Starting file watcher after scan");
                         match FileWatcher::new(
@@ -242,17 +411,26 @@ async fn run_event_loop(
                         )
                         .await
                         {
-                            Ok(w) => *watcher = Some(w),
+                            Ok(w) => {
+                                app.set_watch_root(Some(config.scan.app_path.clone()));
+                                watchers.app = Some(w);
+                            }
                             Err(e) => {
                                 error!(error = %e, "Failed to start file watcher");
-                                app.status = Some(StatusMessage::error(format!("Watcher failed: {e}")));
+                                app.status = Some(StatusMessage::from_error("Watcher failed", &e));
                             }
                         }
-                        // Clear the scan receiver since scan is done
-                        scan_rx = None;
+                    }
+                    if is_complete
+                        && app.config.watch.enabled
+                        && app.config.watch.shared
+                        && watchers.shared.is_none()
+                    {
+                        watchers.shared = Some(SharedWatch::start(&app.config).await);
                     }
                     Action::Render
                 }
+                Event::TaskFinished(action) => action,
                 Event::Tick => {
                     app.tick();
                     Action::None
@@ -273,32 +451,64 @@ async fn run_event_loop(
                         if let Err(e) =
                             editor::run_editor(&path, &app.config.scan.root_path, &app.config, tui, location)
                         {
-                            app.status = Some(StatusMessage::error(format!("Editor failed: {e}")));
+                            app.status = Some(StatusMessage::from_error("Editor failed", &e));
                         }
                     } else {
                         app.status = Some(StatusMessage::info("No file selected"));
                     }
                 }
+                Action::CopyView => {
+                    let view = app.current_view().to_string();
+                    match clipboard::copy(&view) {
+                        Ok(()) => {
+                            app.status = Some(StatusMessage::info(format!("Copied view: {view}")));
+                        }
+                        Err(e) => {
+                            app.status = Some(StatusMessage::from_error("Copy failed", &e));
+                        }
+                    }
+                }
+                Action::Refresh => {
+                    // The next loop iteration repaints the whole screen
+                    if let Err(e) = tui.clear() {
+                        app.status = Some(StatusMessage::from_error("Redraw failed", &e));
+                    }
+                }
                 _ => app.update(action),
             }
 
+            if app.take_scan_request() {
+                info!("Starting background rescan");
+                scan_rx = Some(spawn_background_scan(app));
+            }
+
             if let Some(root) = app.take_watcher_restart() {
-                if let Some(existing) = watcher.take() {
+                if let Some(existing) = watchers.app.take() {
                     if let Err(e) = existing.shutdown().await {
                         error!(error = %e, "Error shutting down watcher");
                     }
                 }
+                // The shared directories may have moved too; restarted after the next scan
+                if let Some(shared) = watchers.shared.take() {
+                    shared.shutdown().await;
+                }
 
                 info!(path = %root, "Restarting file watcher");
                 match FileWatcher::new(&root, &app.config.watch, TypeScriptFilter::default()).await {
-                    Ok(w) => *watcher = Some(w),
+                    Ok(w) => {
+                        app.set_watch_root(Some(root));
+                        watchers.app = Some(w);
+                    }
                     Err(e) => {
                         error!(error = %e, "Failed to restart file watcher");
-                        app.status = Some(StatusMessage::error(format!("Watcher failed: {e}")));
-                        *watcher = None;
+                        app.status = Some(StatusMessage::from_error("Watcher failed", &e));
+                        app.set_watch_root(None);
+                        watchers.app = None;
                     }
                 }
             }
+
+            update_status_file(app, &mut status_written);
         }
 
         // Check for quit
@@ -311,6 +521,27 @@ async fn run_event_loop(
     Ok(())
 }
 
+/// Rewrites the configured status file if the statistics changed.
+///
+/// Only complete figures are written: nothing is written until the first
+/// scan finishes, or while a rescan is running. `written` holds the
+/// statistics last written (or attempted, so a failing path is reported
+/// once per change rather than on every event).
+fn update_status_file(app: &mut App, written: &mut Option<StatsSnapshot>) {
+    let Some(path) = app.config.watch.status_file.clone() else {
+        return;
+    };
+    if !matches!(app.scan_state, ScanState::Complete) || *written == Some(app.stats) {
+        return;
+    }
+
+    *written = Some(app.stats);
+    if let Err(e) = write_status_file(&path, &StatusFile::new(app.stats)) {
+        error!(path = %path, error = %e, "Failed to write status file");
+        app.status = Some(StatusMessage::from_error("Status file failed", &e));
+    }
+}
+
 /// Runs the TUI application without file watching.
 ///
 /// This is a simplified version of [`run`] that doesn't start the file watcher,
@@ -0,0 +1,164 @@
+//! Stack of modal overlays drawn above the main layout.
+//!
+//! Overlays (help, directory setup, the expanded status message, the debug
+//! overlay, the filter and command prompts) are kept on an [`OverlayStack`]. Only the topmost
+//! overlay receives key events, Esc always closes it, and overlays are
+//! rendered bottom to top so the one with focus is drawn last. Closing an
+//! overlay returns to whatever was beneath it with that state untouched.
+//!
+//! # Examples
+//!
+//! ```
+//! use ch_tui::overlay::{Overlay, OverlayStack};
+//!
+//! let mut overlays = OverlayStack::new();
+//! overlays.push(Overlay::DirectorySetup);
+//! overlays.push(Overlay::Help);
+//! assert_eq!(overlays.top(), Some(Overlay::Help));
+//!
+//! overlays.pop();
+//! assert_eq!(overlays.top(), Some(Overlay::DirectorySetup));
+//! ```
+
+use crate::app::AppMode;
+
+/// A modal overlay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overlay {
+    /// Filter input prompt.
+    Filter,
+
+    /// `:` command prompt.
+    Command,
+
+    /// Help panel.
+    Help,
+
+    /// Directory setup dialog.
+    DirectorySetup,
+
+    /// Expanded status message.
+    Message,
+
+    /// Runtime health figures.
+    Debug,
+}
+
+impl Overlay {
+    /// Returns the UI mode this overlay puts the application in.
+    #[must_use]
+    pub const fn mode(self) -> AppMode {
+        match self {
+            Self::Filter => AppMode::Filtering,
+            Self::Command => AppMode::Command,
+            Self::Help => AppMode::Help,
+            Self::DirectorySetup => AppMode::DirectorySetup,
+            Self::Message => AppMode::Message,
+            Self::Debug => AppMode::Debug,
+        }
+    }
+}
+
+/// Open overlays, in render order (topmost last).
+///
+/// Each overlay appears at most once; pushing one that is already open
+/// brings it to the top.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OverlayStack {
+    /// Open overlays, bottom first.
+    overlays: Vec<Overlay>,
+}
+
+impl OverlayStack {
+    /// Creates an empty stack.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            overlays: Vec::new(),
+        }
+    }
+
+    /// Opens `overlay` on top of the stack.
+    pub fn push(&mut self, overlay: Overlay) {
+        self.remove(overlay);
+        self.overlays.push(overlay);
+    }
+
+    /// Closes the topmost overlay and returns it.
+    pub fn pop(&mut self) -> Option<Overlay> {
+        self.overlays.pop()
+    }
+
+    /// Closes `overlay` wherever it is in the stack.
+    ///
+    /// Returns `true` if it was open.
+    pub fn remove(&mut self, overlay: Overlay) -> bool {
+        let len = self.overlays.len();
+        self.overlays.retain(|open| *open != overlay);
+        self.overlays.len() != len
+    }
+
+    /// Returns the topmost overlay, which receives key events.
+    #[must_use]
+    pub fn top(&self) -> Option<Overlay> {
+        self.overlays.last().copied()
+    }
+
+    /// Returns `true` if `overlay` is open.
+    #[must_use]
+    pub fn contains(&self, overlay: Overlay) -> bool {
+        self.overlays.contains(&overlay)
+    }
+
+    /// Returns `true` if no overlay is open.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.overlays.is_empty()
+    }
+
+    /// Iterates over the open overlays in render order (bottom first).
+    pub fn iter(&self) -> impl Iterator<Item = Overlay> + '_ {
+        self.overlays.iter().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlay_stack_push_pop() {
+        let mut overlays = OverlayStack::new();
+        assert!(overlays.is_empty());
+        assert_eq!(overlays.pop(), None);
+
+        overlays.push(Overlay::Filter);
+        overlays.push(Overlay::Help);
+        assert_eq!(overlays.top(), Some(Overlay::Help));
+        assert_eq!(overlays.pop(), Some(Overlay::Help));
+        assert_eq!(overlays.top(), Some(Overlay::Filter));
+    }
+
+    #[test]
+    fn test_overlay_stack_push_existing_moves_to_top() {
+        let mut overlays = OverlayStack::new();
+        overlays.push(Overlay::Help);
+        overlays.push(Overlay::DirectorySetup);
+        overlays.push(Overlay::Help);
+
+        let order: Vec<_> = overlays.iter().collect();
+        assert_eq!(order, [Overlay::DirectorySetup, Overlay::Help]);
+    }
+
+    #[test]
+    fn test_overlay_stack_remove() {
+        let mut overlays = OverlayStack::new();
+        overlays.push(Overlay::DirectorySetup);
+        overlays.push(Overlay::Help);
+
+        assert!(overlays.remove(Overlay::DirectorySetup));
+        assert!(!overlays.remove(Overlay::Command));
+        assert!(!overlays.contains(Overlay::DirectorySetup));
+        assert_eq!(overlays.top(), Some(Overlay::Help));
+    }
+}
@@ -0,0 +1,244 @@
+//! Session state handed over when the TUI restarts itself.
+//!
+//! The `:upgrade` command lets a long-running `watch` session pick up a newly
+//! installed binary without losing its place. On upgrade the TUI restores the
+//! terminal, writes a [`Session`] to a private temporary file, and replaces
+//! the process with a fresh copy of itself (same arguments, same working
+//! directory). The new process finds the file through [`RESUME_ENV`],
+//! restores the view and selection, and deletes it.
+//!
+//! # Format
+//!
+//! The session file is line-oriented text, so it stays readable across
+//! versions:
+//!
+//! ```text
+//! version 0.1.0
+//! view status=legacy,partial sort=priority
+//! selected /src/app/contracts/list.ts
+//! ```
+//!
+//! Unknown keys are ignored, so older binaries can resume sessions written by
+//! newer ones.
+
+use std::fmt::{self, Write as _};
+use std::fs;
+use std::io::{self, Write as _};
+
+use camino::{Utf8Path, Utf8PathBuf};
+
+use crate::error::TuiError;
+use crate::view::ViewSpec;
+
+/// Environment variable naming the session file to resume from.
+pub const RESUME_ENV: &str = "CH_MIGRATE_RESUME_SESSION";
+
+/// State carried across an in-place restart.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Session {
+    /// Version of the binary that wrote the session.
+    pub version: String,
+
+    /// Filter and sort state of the file list.
+    pub view: ViewSpec,
+
+    /// Path of the selected file, if any.
+    pub selected: Option<Utf8PathBuf>,
+}
+
+impl Session {
+    /// Writes the session to a new file in the temporary directory and
+    /// returns its path.
+    ///
+    /// The file gets an unpredictable name and is created exclusively and
+    /// readable only by the current user, so other users can neither read it
+    /// nor plant a file or symlink in its place.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created or written.
+    pub fn save_temp(&self) -> io::Result<Utf8PathBuf> {
+        let mut file = tempfile::Builder::new().prefix("ch-migrate-session-").tempfile()?;
+        file.write_all(self.to_string().as_bytes())?;
+        let (_, path) = file.keep().map_err(|e| e.error)?;
+        Utf8PathBuf::from_path_buf(path).map_err(|path| {
+            let _ = fs::remove_file(&path);
+            io::Error::new(io::ErrorKind::InvalidData, "temporary directory path is not UTF-8")
+        })
+    }
+
+    /// Writes the session to a new file at `path`.
+    ///
+    /// Like [`save_temp`](Self::save_temp), the file is only readable by the
+    /// current user, and an existing file or symlink is never overwritten.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` exists or the file cannot be written.
+    pub fn save(&self, path: &Utf8Path) -> io::Result<()> {
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        options.open(path)?.write_all(self.to_string().as_bytes())
+    }
+
+    /// Loads a session written by [`save`](Self::save).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TuiError::Terminal`] if the file cannot be read, or
+    /// [`TuiError::Config`] if it is malformed.
+    pub fn load(path: &Utf8Path) -> Result<Self, TuiError> {
+        fs::read_to_string(path)?.parse()
+    }
+}
+
+impl fmt::Display for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "version {}", self.version)?;
+        writeln!(f, "view {}", self.view)?;
+        if let Some(selected) = &self.selected {
+            writeln!(f, "selected {selected}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Session {
+    type Err = TuiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut session = Self::default();
+
+        for line in s.lines() {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            match key {
+                "version" => value.clone_into(&mut session.version),
+                "view" => session.view = value.parse()?,
+                "selected" if !value.is_empty() => session.selected = Some(Utf8PathBuf::from(value)),
+                _ => {}
+            }
+        }
+
+        Ok(session)
+    }
+}
+
+/// Replaces the current process with a fresh copy of itself.
+///
+/// The program is re-run from its original `argv[0]` (so an upgraded binary
+/// on `PATH` is picked up) with the same arguments and working directory,
+/// and with [`RESUME_ENV`] pointing at `session_path`.
+///
+/// Only returns if the new process could not be started.
+#[cfg(unix)]
+#[must_use]
+pub fn exec_upgrade(session_path: &Utf8Path) -> io::Error {
+    use std::os::unix::process::CommandExt;
+
+    let mut args = std::env::args_os();
+    let Some(program) = args.next() else {
+        return io::Error::new(io::ErrorKind::NotFound, "cannot determine program path");
+    };
+    let cwd = std::env::current_dir()
+        .ok()
+        .and_then(|dir| Utf8PathBuf::from_path_buf(dir).ok())
+        .unwrap_or_else(|| Utf8PathBuf::from("."));
+
+    crate::toolchain::command(&program.to_string_lossy(), &cwd)
+        .args(args)
+        .env(RESUME_ENV, session_path.as_str())
+        .exec()
+}
+
+/// Replaces the current process with a fresh copy of itself.
+///
+/// In-place upgrades are only supported on Unix.
+#[cfg(not(unix))]
+#[must_use]
+pub fn exec_upgrade(_session_path: &Utf8Path) -> io::Error {
+    io::Error::new(io::ErrorKind::Unsupported, "in-place upgrade requires a Unix platform")
+}
+
+/// Formats a one-line description of a resumed session.
+pub(crate) fn resumed_message(session: &Session) -> String {
+    let mut message = String::from("Resumed session");
+    if !session.version.is_empty() {
+        let _ = write!(message, " from v{}", session.version);
+    }
+    let _ = write!(message, " (now v{})", env!("CARGO_PKG_VERSION"));
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ch_core::MigrationStatus;
+
+    use crate::view::SortOrder;
+
+    fn session() -> Session {
+        Session {
+            version: "0.1.0".to_owned(),
+            view: ViewSpec {
+                statuses: vec![MigrationStatus::Legacy],
+                sort: SortOrder::Priority,
+                path: "app/contracts".to_owned(),
+                dead_imports: true,
+            },
+            selected: Some(Utf8PathBuf::from("/src/app/contracts/list view.ts")),
+        }
+    }
+
+    #[test]
+    fn test_session_round_trip() {
+        let session = session();
+        assert_eq!(session.to_string().parse::<Session>().ok(), Some(session));
+    }
+
+    #[test]
+    fn test_session_ignores_unknown_keys() {
+        let parsed: Result<Session, _> = "version 9.9.9\npins a.ts,b.ts\nview sort=status\n".parse();
+        assert!(parsed.is_ok_and(|s| s.view.sort == SortOrder::Status && s.selected.is_none()));
+    }
+
+    #[test]
+    fn test_session_rejects_bad_view() {
+        assert!("view sort=sideways\n".parse::<Session>().is_err());
+    }
+
+    #[test]
+    fn test_session_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("ch-tui-session-test-{}", std::process::id()));
+        let _ = fs::create_dir_all(&dir);
+        let path = Utf8PathBuf::from_path_buf(dir.join("session")).unwrap_or_default();
+
+        let session = session();
+        assert!(session.save(&path).is_ok());
+        assert_eq!(Session::load(&path).ok(), Some(session.clone()));
+        assert!(session.save(&path).is_err(), "an existing file must not be overwritten");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_session_save_temp_is_private() {
+        let session = session();
+        let first = session.save_temp();
+        let second = session.save_temp();
+        assert!(matches!((&first, &second), (Ok(a), Ok(b)) if a != b));
+
+        if let Ok(path) = &first {
+            assert_eq!(Session::load(path).ok(), Some(session));
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let mode = fs::metadata(path).map(|m| m.permissions().mode() & 0o777);
+                assert_eq!(mode.ok(), Some(0o600));
+            }
+        }
+        for path in [first, second].into_iter().flatten() {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
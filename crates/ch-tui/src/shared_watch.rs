@@ -0,0 +1,74 @@
+//! Watching the shared model directories in watch mode.
+//!
+//! With `watch.shared` set, [`SharedWatch`] watches `shared/` and
+//! `shared_2023/` alongside the app directory. Each changed file reaches the
+//! app as an [`Event::SharedChanged`](crate::Event::SharedChanged), which
+//! updates the scanner's model registry and re-checks the files importing
+//! the models it defines.
+
+use camino::Utf8PathBuf;
+use ch_core::Config;
+use ch_watcher::{FileEvent, FileWatcher, TypeScriptFilter};
+use futures_util::future::select_all;
+use tracing::{error, info};
+
+/// Watchers on the shared model directories.
+#[derive(Debug)]
+pub(crate) struct SharedWatch {
+    /// Each watcher with its directory as configured.
+    watchers: Vec<(FileWatcher, Utf8PathBuf)>,
+}
+
+impl SharedWatch {
+    /// Starts watching the shared directories of `config`.
+    ///
+    /// A directory that can't be watched is logged and left out.
+    pub(crate) async fn start(config: &Config) -> Self {
+        let mut watchers = Vec::new();
+        for dir in [&config.scan.shared_path, &config.scan.shared_2023_path] {
+            match FileWatcher::new(dir, &config.watch, TypeScriptFilter::default()).await {
+                Ok(watcher) => {
+                    info!(path = %dir, "Watching shared directory");
+                    watchers.push((watcher, dir.clone()));
+                }
+                Err(e) => error!(path = %dir, error = %e, "Failed to watch shared directory"),
+            }
+        }
+        Self { watchers }
+    }
+
+    /// Returns the number of debounced events waiting to be received.
+    pub(crate) fn pending_events(&self) -> usize {
+        self.watchers.iter().map(|(watcher, _)| watcher.pending_events()).sum()
+    }
+
+    /// Receives the next changed file, its path spelled under the configured
+    /// directory as the registry's are.
+    ///
+    /// Waits forever if no directory is watched, and returns `None` once a
+    /// watcher has stopped.
+    pub(crate) async fn recv(&mut self) -> Option<FileEvent> {
+        if self.watchers.is_empty() {
+            return std::future::pending().await;
+        }
+        let receives = self.watchers.iter_mut().map(|(watcher, dir)| {
+            Box::pin(async move {
+                let mut event = watcher.recv().await?;
+                if let Ok(relative) = event.path.strip_prefix(watcher.watch_path()) {
+                    event.path = dir.join(relative);
+                }
+                Some(event)
+            })
+        });
+        select_all(receives).await.0
+    }
+
+    /// Stops every watcher.
+    pub(crate) async fn shutdown(self) {
+        for (watcher, dir) in self.watchers {
+            if let Err(e) = watcher.shutdown().await {
+                error!(path = %dir, error = %e, "Error shutting down shared directory watcher");
+            }
+        }
+    }
+}
@@ -170,7 +170,7 @@ impl Tui {
         io::stdout().execute(EnableBracketedPaste)?;
 
         self.terminal.hide_cursor()?;
-        self.terminal.clear()?;
+        self.clear()?;
 
         self.start_event_loop();
 
@@ -219,6 +219,21 @@ impl Tui {
         Ok(())
     }
 
+    /// Clears the screen and forgets the previous frame.
+    ///
+    /// Ratatui only repaints cells that changed since the last frame, so
+    /// output written behind its back (an editor, a shell command) would stay
+    /// on screen. After this, the next [`draw()`](Self::draw) repaints every
+    /// cell.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the terminal cannot be cleared.
+    pub fn clear(&mut self) -> Result<(), TuiError> {
+        self.terminal.clear()?;
+        Ok(())
+    }
+
     /// Returns the next event from the event loop.
     ///
     /// This is an async method that waits for the next event.
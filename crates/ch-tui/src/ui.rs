@@ -21,106 +21,202 @@
 //! +------------------------------------------------------------------+
 //! ```
 
-use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::layout::{Constraint, Direction, Flex, Layout, Rect};
 use ratatui::Frame;
 
-use crate::app::{App, AppMode, Focus};
+use crate::app::{App, Focus};
 use crate::components::{
-    DetailPane, DirectoryInput, FileListView, FilterInput, HeaderBar, HelpPanel, StatsPanel,
-    StatusBar,
+    CommandInput, DebugOverlay, DetailPane, DirectoryInput, FileListView, FilterInput, HeaderBar,
+    HelpPanel, MessagePopup, StatsPanel, StatusBar,
 };
+use crate::overlay::Overlay;
 use crate::theme::Theme;
 
+/// Areas of the main layout, computed from the terminal size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct MainLayout {
+    /// Header bar (3 lines).
+    header: Rect,
+    /// Stats panel (3 lines).
+    stats: Rect,
+    /// File list (left 60% of the main content).
+    file_list: Rect,
+    /// Detail pane (right 40% of the main content).
+    details: Rect,
+    /// Status bar (1 line, more while a long message wraps).
+    status_bar: Rect,
+}
+
+impl MainLayout {
+    /// Splits `area`, giving the status bar `status_height` lines.
+    fn new(area: Rect, status_height: u16) -> Self {
+        let main_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),  // Header
+                Constraint::Length(3),  // Stats
+                Constraint::Min(10),    // Main content
+                Constraint::Length(status_height), // Status bar
+            ])
+            .split(area);
+
+        // Split horizontally: file list (60%) | details (40%)
+        let content_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(main_chunks[2]);
+
+        Self {
+            header: main_chunks[0],
+            stats: main_chunks[1],
+            file_list: content_chunks[0],
+            details: content_chunks[1],
+            status_bar: main_chunks[3],
+        }
+    }
+}
+
 /// Renders the entire UI based on the current application state.
 pub fn render(app: &App, frame: &mut Frame, theme: &Theme) {
     let area = frame.area();
-
-    // Main vertical layout:
-    // - Header (3 lines)
-    // - Stats Panel (3 lines)
-    // - Main Content (flexible)
-    // - Status Bar (1 line)
-    let main_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),  // Header
-            Constraint::Length(3),  // Stats
-            Constraint::Min(10),    // Main content
-            Constraint::Length(1),  // Status bar
-        ])
-        .split(area);
+    let status_bar = StatusBar::new(app, theme);
+    let layout = MainLayout::new(area, status_bar.height(area.width));
 
     // Render header
-    let header = HeaderBar::new(&app.config, app.file_count(), &app.scan_state);
-    frame.render_widget(&header, main_chunks[0]);
+    let watch_notice = app.watch_notice();
+    let header = HeaderBar::new(&app.config, app.file_count(), &app.scan_state)
+        .with_scan_paused(app.is_scan_paused())
+        .with_watch_notice(watch_notice.as_deref());
+    frame.render_widget(&header, layout.header);
 
-    // Render stats panel
-    let stats_panel = StatsPanel::new(&app.stats, &app.scan_state, theme);
-    frame.render_widget(&stats_panel, main_chunks[1]);
+    // Render stats panel, with the scanner's throughput while scanning
+    let mut stats_panel = StatsPanel::new(&app.stats, &app.scan_state, theme)
+        .with_teams(&app.team_stats)
+        .with_labels(&app.config.labels)
+        .with_scan_paused(app.is_scan_paused());
+    if app.scan_state.is_scanning() {
+        stats_panel = stats_panel.with_live_stats(app.scanner.stats());
+    }
+    frame.render_widget(&stats_panel, layout.stats);
 
     // Render main content (file list + details)
-    render_main_content(app, frame, main_chunks[2], theme);
+    render_main_content(app, frame, &layout, theme);
 
     // Render status bar
-    let status_bar = StatusBar::new(app, theme);
-    frame.render_widget(&status_bar, main_chunks[3]);
+    frame.render_widget(&status_bar, layout.status_bar);
 
-    // Render filter input overlay if in filter mode
-    if app.mode == AppMode::Filtering {
-        let filter_input = FilterInput::new(&app.filter.text, theme);
-        let filter_area = centered_rect(50, 3, area);
-        frame.render_widget(&filter_input, filter_area);
+    // Render overlays bottom to top, so the one receiving keys is on top
+    for overlay in app.overlays.iter() {
+        render_overlay(app, overlay, frame, theme);
     }
+}
 
-    // Render help panel overlay if in help mode
-    if app.mode == AppMode::Help {
-        let help_panel = HelpPanel::new(theme);
-        let help_area = centered_rect(60, 70, area);
-        frame.render_widget(&help_panel, help_area);
+/// Renders a single modal overlay centered in the frame.
+fn render_overlay(app: &App, overlay: Overlay, frame: &mut Frame, theme: &Theme) {
+    let area = overlay_area(overlay, frame.area());
+    match overlay {
+        Overlay::Filter => {
+            let filter_input = FilterInput::new(&app.filter.text, theme);
+            frame.render_widget(&filter_input, area);
+        }
+        Overlay::Command => {
+            let command_input = CommandInput::new(&app.command_input, theme);
+            frame.render_widget(&command_input, area);
+        }
+        Overlay::Help => {
+            let unavailable: Vec<_> = app
+                .key_bindings
+                .iter()
+                .filter_map(|(command, _)| {
+                    command.unavailable_reason(app).map(|reason| (command.id, reason))
+                })
+                .collect();
+            let help_panel = HelpPanel::new(&app.key_bindings, theme)
+                .with_mode(app.help_mode())
+                .with_unavailable(&unavailable);
+            frame.render_widget(&help_panel, area);
+        }
+        Overlay::DirectorySetup => {
+            let dir_input = DirectoryInput::new(&app.directory_setup, theme);
+            frame.render_widget(&dir_input, area);
+        }
+        Overlay::Message => {
+            if let Some(state) = &app.message_popup {
+                let popup = MessagePopup::new(state, theme);
+                frame.render_widget(&popup, area);
+            }
+        }
+        Overlay::Debug => {
+            let debug_overlay = DebugOverlay::new(&app.debug, &app.scanner, theme);
+            frame.render_widget(&debug_overlay, area);
+        }
     }
+}
 
-    // Render directory setup overlay if active
-    if app.mode == AppMode::DirectorySetup {
-        let dir_input = DirectoryInput::new(&app.directory_setup, theme);
-        let dir_area = centered_rect(80, 30, area);
-        frame.render_widget(&dir_input, dir_area);
+/// Returns where `overlay` is drawn in a frame of size `area`.
+fn overlay_area(overlay: Overlay, area: Rect) -> Rect {
+    match overlay {
+        Overlay::Filter => centered_prompt_rect(50, area),
+        Overlay::Command => centered_prompt_rect(60, area),
+        Overlay::Help => centered_rect(60, 70, area),
+        Overlay::DirectorySetup => centered_rect(80, 30, area),
+        Overlay::Message => centered_rect(70, 50, area),
+        Overlay::Debug => centered_rect(50, 90, area),
     }
 }
 
 /// Renders the main content area (file list and detail pane).
-fn render_main_content(app: &App, frame: &mut Frame, area: Rect, theme: &Theme) {
-    // Split horizontally: file list (60%) | details (40%)
-    let content_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
-        .split(area);
-
+fn render_main_content(app: &App, frame: &mut Frame, layout: &MainLayout, theme: &Theme) {
     // Render file list
     let file_list = FileListView::new(
         app.files(),
         &app.filter,
         app.focus == Focus::FileList,
         theme,
-    );
+    )
+    .with_labels(&app.config.labels)
+    .with_editor_buffers(&app.editor_buffers);
     frame.render_stateful_widget(
         &file_list,
-        content_chunks[0],
+        layout.file_list,
         &mut app.file_list_state.clone(),
     );
 
     // Render detail pane
-    let detail_pane = DetailPane::new(
-        app.selected_file(),
-        app.focus == Focus::DetailPane,
-        theme,
-    );
+    let selected = app.selected_file();
+    let registry = app.scanner.registry();
+    let detail_pane = DetailPane::new(selected, app.focus == Focus::DetailPane, theme)
+        .with_labels(&app.config.labels)
+        .with_registry(&registry)
+        .with_buffer_state(selected.and_then(|file| app.editor_buffers.get(&file.path)))
+        .with_watch_coverage(selected.map(|file| app.watch_coverage(&file.path)))
+        .with_compacted(selected.is_some_and(|file| app.scanner.cache().is_compacted(&file.path)));
     frame.render_stateful_widget(
         &detail_pane,
-        content_chunks[1],
+        layout.details,
         &mut app.detail_state.clone(),
     );
 }
 
+/// Creates a centered, three-line rectangle for a single-line prompt.
+///
+/// The height is fixed, so the bordered input stays visible at any terminal
+/// height.
+fn centered_prompt_rect(percent_x: u16, area: Rect) -> Rect {
+    let [row] = Layout::vertical([Constraint::Length(3)])
+        .flex(Flex::Center)
+        .areas(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(row)[1]
+}
+
 /// Creates a centered rectangle with the given percentage width and height.
 fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
     let popup_layout = Layout::default()
@@ -157,4 +253,94 @@ mod tests {
         assert!(centered.width < area.width);
         assert!(centered.height < area.height);
     }
+
+    /// Representative terminal sizes, as (width, height).
+    const SIZES: [(u16, u16); 3] = [(80, 24), (120, 40), (200, 60)];
+
+    #[test]
+    fn test_main_layout_golden_sizes() {
+        // (width, height, status lines, content height, file list width, details width)
+        let golden = [
+            (80, 24, 1, 17, 48, 32),
+            (80, 24, 3, 15, 48, 32),
+            (120, 40, 1, 33, 72, 48),
+            (120, 40, 3, 31, 72, 48),
+            (200, 60, 1, 53, 120, 80),
+            (200, 60, 3, 51, 120, 80),
+        ];
+
+        for (width, height, status, content, list_width, details_width) in golden {
+            let layout = MainLayout::new(Rect::new(0, 0, width, height), status);
+            assert_eq!(layout.header, Rect::new(0, 0, width, 3));
+            assert_eq!(layout.stats, Rect::new(0, 3, width, 3));
+            assert_eq!(layout.file_list, Rect::new(0, 6, list_width, content));
+            assert_eq!(layout.details, Rect::new(list_width, 6, details_width, content));
+            assert_eq!(layout.status_bar, Rect::new(0, height - status, width, status));
+        }
+    }
+
+    #[test]
+    fn test_main_layout_wrapped_status_only_shrinks_content() {
+        for (width, height) in SIZES {
+            let area = Rect::new(0, 0, width, height);
+            let single = MainLayout::new(area, 1);
+            let wrapped = MainLayout::new(area, 3);
+
+            assert_eq!(wrapped.header, single.header);
+            assert_eq!(wrapped.stats, single.stats);
+            assert_eq!(wrapped.file_list.height, single.file_list.height - 2);
+            assert_eq!(wrapped.details.height, single.details.height - 2);
+        }
+    }
+
+    #[test]
+    fn test_overlay_areas_golden_sizes() {
+        let golden = [
+            (80, 24, Overlay::Filter, Rect::new(20, 11, 40, 3)),
+            (80, 24, Overlay::Command, Rect::new(16, 11, 48, 3)),
+            (80, 24, Overlay::Help, Rect::new(16, 4, 48, 16)),
+            (80, 24, Overlay::DirectorySetup, Rect::new(8, 8, 64, 8)),
+            (80, 24, Overlay::Message, Rect::new(12, 6, 56, 12)),
+            (80, 24, Overlay::Debug, Rect::new(20, 1, 40, 22)),
+            (120, 40, Overlay::Filter, Rect::new(30, 19, 60, 3)),
+            (120, 40, Overlay::Command, Rect::new(24, 19, 72, 3)),
+            (120, 40, Overlay::Help, Rect::new(24, 6, 72, 28)),
+            (120, 40, Overlay::DirectorySetup, Rect::new(12, 14, 96, 12)),
+            (120, 40, Overlay::Message, Rect::new(18, 10, 84, 20)),
+            (120, 40, Overlay::Debug, Rect::new(30, 2, 60, 36)),
+            (200, 60, Overlay::Filter, Rect::new(50, 29, 100, 3)),
+            (200, 60, Overlay::Command, Rect::new(40, 29, 120, 3)),
+            (200, 60, Overlay::Help, Rect::new(40, 9, 120, 42)),
+            (200, 60, Overlay::DirectorySetup, Rect::new(20, 21, 160, 18)),
+            (200, 60, Overlay::Message, Rect::new(30, 15, 140, 30)),
+            (200, 60, Overlay::Debug, Rect::new(50, 3, 100, 54)),
+        ];
+
+        for (width, height, overlay, expected) in golden {
+            let area = Rect::new(0, 0, width, height);
+            assert_eq!(overlay_area(overlay, area), expected, "{overlay:?} at {width}x{height}");
+        }
+    }
+
+    #[test]
+    fn test_overlay_areas_fit_their_content() {
+        // Borders plus the lines each overlay needs at minimum
+        let min_heights = [
+            (Overlay::Filter, 3),
+            (Overlay::Command, 3),
+            (Overlay::Help, 10),
+            (Overlay::DirectorySetup, 5),
+            (Overlay::Message, 5),
+            (Overlay::Debug, 10),
+        ];
+
+        for (width, height) in SIZES {
+            let area = Rect::new(0, 0, width, height);
+            for (overlay, min_height) in min_heights {
+                let rect = overlay_area(overlay, area);
+                assert!(rect.height >= min_height, "{overlay:?} at {width}x{height}");
+                assert_eq!(rect.intersection(area), rect);
+            }
+        }
+    }
 }
@@ -0,0 +1,324 @@
+//! Shareable view specifications.
+//!
+//! A [`ViewSpec`] captures the filter, sort, and path state of the file list
+//! in a compact textual form so that teammates can reproduce an exact view:
+//!
+//! ```text
+//! status=legacy,partial sort=priority path=app/contracts
+//! ```
+//!
+//! Views can be copied from the TUI with `y`, applied with `:load-view <spec>`,
+//! or passed on startup with `ch-migrate watch --view <spec>`.
+//!
+//! # Format
+//!
+//! A view is a whitespace-separated list of `key=value` pairs:
+//!
+//! - `status` - comma-separated statuses (`legacy`, `partial`, `migrated`, `no_models`)
+//! - `sort` - one of `path`, `priority`, `status`, `imports`
+//! - `path` - case-insensitive path substring filter, with whitespace and `%`
+//!   percent-encoded (`path=my%20app`)
+//! - `only` - `dead-imports` to show only files with unused legacy imports
+//!
+//! Keys may appear in any order; omitted keys use their defaults.
+//!
+//! # Examples
+//!
+//! ```
+//! use ch_core::MigrationStatus;
+//! use ch_tui::view::{SortOrder, ViewSpec};
+//!
+//! let view: ViewSpec = "status=legacy,partial sort=priority path=app/contracts"
+//!     .parse()
+//!     .unwrap_or_default();
+//!
+//! assert_eq!(view.statuses, vec![MigrationStatus::Legacy, MigrationStatus::Partial]);
+//! assert_eq!(view.sort, SortOrder::Priority);
+//! assert_eq!(view.path, "app/contracts");
+//! assert_eq!(view.to_string(), "status=legacy,partial sort=priority path=app/contracts");
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt::{self, Write as _};
+use std::str::FromStr;
+
+use ch_core::{FileInfo, MigrationStatus};
+
+use crate::error::TuiError;
+
+/// Sort order for the file list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SortOrder {
+    /// Alphabetical by path.
+    #[default]
+    Path,
+
+    /// Files with the most legacy imports first.
+    Priority,
+
+    /// Grouped by status (Legacy, Partial, Migrated, No Models).
+    Status,
+
+    /// Files with the most imports first.
+    Imports,
+}
+
+impl SortOrder {
+    /// Returns the identifier used in view strings.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Path => "path",
+            Self::Priority => "priority",
+            Self::Status => "status",
+            Self::Imports => "imports",
+        }
+    }
+
+    /// Returns the next sort order in cycle order.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Path => Self::Priority,
+            Self::Priority => Self::Status,
+            Self::Status => Self::Imports,
+            Self::Imports => Self::Path,
+        }
+    }
+
+    /// Compares two files according to this sort order.
+    ///
+    /// Ties are always broken by path so the ordering is stable and
+    /// reproducible across machines.
+    #[must_use]
+    pub fn compare(self, a: &FileInfo, b: &FileInfo) -> Ordering {
+        let primary = match self {
+            Self::Path => Ordering::Equal,
+            Self::Priority => b
+                .legacy_imports()
+                .count()
+                .cmp(&a.legacy_imports().count()),
+            Self::Status => status_rank(a.status).cmp(&status_rank(b.status)),
+            Self::Imports => b.import_count().cmp(&a.import_count()),
+        };
+        primary.then_with(|| a.path.cmp(&b.path))
+    }
+}
+
+impl fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SortOrder {
+    type Err = TuiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "path" => Ok(Self::Path),
+            "priority" => Ok(Self::Priority),
+            "status" => Ok(Self::Status),
+            "imports" => Ok(Self::Imports),
+            other => Err(TuiError::config(format!("unknown sort order: {other}"))),
+        }
+    }
+}
+
+/// A compact, shareable description of the file list view.
+///
+/// See the [module documentation](self) for the textual format.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ViewSpec {
+    /// Statuses to show (empty shows all).
+    pub statuses: Vec<MigrationStatus>,
+
+    /// Sort order for the file list.
+    pub sort: SortOrder,
+
+    /// Path substring filter (empty matches all).
+    pub path: String,
+
+    /// Show only files with legacy imports that are never used.
+    pub dead_imports: bool,
+}
+
+impl ViewSpec {
+    /// Returns `true` if this view matches the default (unfiltered, path-sorted) view.
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+impl fmt::Display for ViewSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::with_capacity(4);
+        if !self.statuses.is_empty() {
+            let statuses: Vec<_> = self.statuses.iter().map(|s| s.key()).collect();
+            parts.push(format!("status={}", statuses.join(",")));
+        }
+        if self.sort != SortOrder::default() {
+            parts.push(format!("sort={}", self.sort));
+        }
+        if !self.path.is_empty() {
+            parts.push(format!("path={}", encode_value(&self.path)));
+        }
+        if self.dead_imports {
+            parts.push("only=dead-imports".to_owned());
+        }
+        f.write_str(&parts.join(" "))
+    }
+}
+
+impl FromStr for ViewSpec {
+    type Err = TuiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut view = Self::default();
+
+        for token in s.split_whitespace() {
+            let (key, value) = token
+                .split_once('=')
+                .ok_or_else(|| TuiError::config(format!("expected key=value, got: {token}")))?;
+
+            match key {
+                "status" => {
+                    view.statuses.clear();
+                    for name in value.split(',').filter(|name| !name.is_empty()) {
+                        let status: MigrationStatus =
+                            name.parse().map_err(|e: ch_core::ConfigError| TuiError::config(e.to_string()))?;
+                        if !view.statuses.contains(&status) {
+                            view.statuses.push(status);
+                        }
+                    }
+                }
+                "sort" => view.sort = value.parse()?,
+                "path" => view.path = decode_value(value)?,
+                "only" if value == "dead-imports" => view.dead_imports = true,
+                "only" => return Err(TuiError::config(format!("unknown view filter: {value}"))),
+                other => return Err(TuiError::config(format!("unknown view key: {other}"))),
+            }
+        }
+
+        Ok(view)
+    }
+}
+
+/// Percent-encodes the characters that would split or corrupt a view token.
+fn encode_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == '%' || c.is_whitespace() {
+            let mut bytes = [0; 4];
+            for byte in c.encode_utf8(&mut bytes).bytes() {
+                let _ = write!(encoded, "%{byte:02X}");
+            }
+        } else {
+            encoded.push(c);
+        }
+    }
+    encoded
+}
+
+/// Decodes a value written by [`encode_value`].
+fn decode_value(value: &str) -> Result<String, TuiError> {
+    let invalid = || TuiError::config(format!("invalid percent-encoding in view value: {value}"));
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut rest = value.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex =
+                tail.get(..2).and_then(|hex| std::str::from_utf8(hex).ok()).ok_or_else(invalid)?;
+            bytes.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| invalid())
+}
+
+/// Returns the rank of a status for [`SortOrder::Status`] (most urgent first).
+const fn status_rank(status: MigrationStatus) -> u8 {
+    match status {
+        MigrationStatus::Legacy => 0,
+        MigrationStatus::Partial => 1,
+        MigrationStatus::Migrated => 2,
+        MigrationStatus::NoModels | _ => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view_round_trip() {
+        let text = "status=legacy,partial sort=priority path=app/contracts";
+        let view: Result<ViewSpec, _> = text.parse();
+        assert_eq!(view.map(|v| v.to_string()).ok().as_deref(), Some(text));
+    }
+
+    #[test]
+    fn test_view_path_with_spaces_round_trips() {
+        let view =
+            ViewSpec { path: "My Documents/100% done\tapp".to_owned(), ..ViewSpec::default() };
+        let text = view.to_string();
+        assert_eq!(text, "path=My%20Documents/100%25%20done%09app");
+        assert_eq!(text.parse::<ViewSpec>().ok(), Some(view));
+        assert!("path=bad%2".parse::<ViewSpec>().is_err());
+        assert!("path=bad%zz".parse::<ViewSpec>().is_err());
+    }
+
+    #[test]
+    fn test_view_default_is_empty() {
+        let view = ViewSpec::default();
+        assert!(view.is_default());
+        assert_eq!(view.to_string(), "");
+        assert_eq!("".parse::<ViewSpec>().ok(), Some(view));
+    }
+
+    #[test]
+    fn test_view_any_key_order() {
+        let view = "path=foo sort=imports status=migrated".parse::<ViewSpec>().ok();
+        assert_eq!(
+            view,
+            Some(ViewSpec {
+                statuses: vec![MigrationStatus::Migrated],
+                sort: SortOrder::Imports,
+                path: "foo".to_owned(),
+                dead_imports: false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_view_dead_imports() {
+        let view = "only=dead-imports sort=status".parse::<ViewSpec>().ok();
+        assert!(view.as_ref().is_some_and(|v| v.dead_imports && !v.is_default()));
+        assert_eq!(
+            view.map(|v| v.to_string()).as_deref(),
+            Some("sort=status only=dead-imports")
+        );
+        assert!("only=blocked".parse::<ViewSpec>().is_err());
+    }
+
+    #[test]
+    fn test_view_rejects_unknown_input() {
+        assert!("status=unknown".parse::<ViewSpec>().is_err());
+        assert!("sort=size".parse::<ViewSpec>().is_err());
+        assert!("color=red".parse::<ViewSpec>().is_err());
+        assert!("legacy".parse::<ViewSpec>().is_err());
+    }
+
+    #[test]
+    fn test_sort_order_cycle() {
+        let mut sort = SortOrder::default();
+        for _ in 0..4 {
+            sort = sort.next();
+        }
+        assert_eq!(sort, SortOrder::Path);
+    }
+}
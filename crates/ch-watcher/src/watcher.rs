@@ -290,6 +290,12 @@ impl FileWatcher {
         self.event_rx.try_recv()
     }
 
+    /// Returns the number of debounced events waiting to be received.
+    #[must_use]
+    pub fn pending_events(&self) -> usize {
+        self.event_rx.len()
+    }
+
     /// Returns a mutable reference to the event receiver.
     ///
     /// This is useful when you need to use the receiver directly with
@@ -516,6 +522,8 @@ mod tests {
             enabled: true,
             debounce_ms: 50, // Shorter debounce for faster tests
             recursive: true,
+            status_file: None,
+            shared: false,
         };
 
         let mut watcher = FileWatcher::new(path, &config, AcceptAllFilter)